@@ -0,0 +1,70 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use chrono::NaiveDateTime;
+use rateslib::calendars::{
+    get_calendar_by_name, ndt, Bus252Accrual, Cal, CalType, Convention, DateRoll, Modifier,
+    UnionCal,
+};
+
+/// The business days of a union of calendars, tested against each calendar in turn.
+fn is_bus_day_iterated(cals: &[Cal], date: &NaiveDateTime) -> bool {
+    cals.iter().all(|c| c.is_weekday(date)) && !cals.iter().any(|c| c.is_holiday(date))
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let cals: Vec<Cal> = ["tgt", "ldn", "nyc", "tyo"]
+        .iter()
+        .map(|n| get_calendar_by_name(n).unwrap())
+        .collect();
+    let ucal = UnionCal::new(cals.clone(), None);
+    let dates = ucal
+        .cal_date_range(&ndt(2000, 1, 1), &ndt(2049, 12, 31))
+        .unwrap();
+
+    c.bench_function("4-calendar union is_bus_day 50Y iterated", |z| {
+        z.iter(|| {
+            for date in dates.iter() {
+                black_box(is_bus_day_iterated(&cals, date));
+            }
+        })
+    });
+    c.bench_function("4-calendar union is_bus_day 50Y flattened", |z| {
+        z.iter(|| {
+            for date in dates.iter() {
+                black_box(ucal.is_bus_day(date));
+            }
+        })
+    });
+
+    // the adjusted dates of a 50Y monthly schedule
+    let schedule: Vec<NaiveDateTime> = (0..600)
+        .map(|i| ndt(2000 + i / 12, (i % 12) as u32 + 1, 15))
+        .collect();
+    c.bench_function("4-calendar union 50Y monthly schedule adjustment", |z| {
+        z.iter(|| {
+            for date in schedule.iter() {
+                black_box(ucal.roll(date, &Modifier::ModF, false));
+            }
+        })
+    });
+
+    // the Bus252 day count fraction of a 10Y period, computed and cached
+    let bus = CalType::Cal(get_calendar_by_name("bus").unwrap());
+    let accrual = Bus252Accrual::new(bus.clone());
+    let (s, e) = (ndt(2024, 1, 1), ndt(2034, 1, 1));
+    c.bench_function("Bus252 dcf 10Y period", |z| {
+        z.iter(|| {
+            black_box(
+                Convention::Bus252
+                    .dcf(&s, &e, None, None, None, None, Some(&bus))
+                    .unwrap(),
+            )
+        })
+    });
+    c.bench_function("Bus252 dcf 10Y period cached", |z| {
+        z.iter(|| black_box(accrual.dcf(&s, &e).unwrap()))
+    });
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,65 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use chrono::{Days, NaiveDateTime};
+use indexmap::IndexMap;
+use rateslib::calendars::{ndt, Convention, Modifier, NamedCal};
+use rateslib::curves::{CurveDF, CurveInterpolation, LogLinearInterpolator, Nodes, NodesTimestamp};
+use rateslib::dual::{Dual, Number};
+
+/// A 50 node curve with semi-annual nodes.
+fn nodes_fixture(ad: bool) -> Nodes {
+    let nodes = (0..50).map(|i| {
+        (
+            ndt(2000, 1, 1) + Days::new(i * 180),
+            (-0.03 * i as f64 / 2.0).exp(),
+        )
+    });
+    if ad {
+        Nodes::Dual(IndexMap::from_iter(
+            nodes
+                .enumerate()
+                .map(|(i, (k, v))| (k, Dual::new(v, vec![format!("crv{}", i)]))),
+        ))
+    } else {
+        Nodes::F64(IndexMap::from_iter(nodes))
+    }
+}
+
+fn evaluate(dates: &[NaiveDateTime], f: impl Fn(&NaiveDateTime) -> Number) {
+    for date in dates.iter() {
+        black_box(f(date));
+    }
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let dates: Vec<NaiveDateTime> = (0..100_000)
+        .map(|i| ndt(2000, 1, 2) + Days::new(i % 8800))
+        .collect();
+
+    for (name, ad) in [("f64", false), ("dual", true)] {
+        // evaluating the interpolator directly on nodes does not use the cache of a curve state
+        let nodes = NodesTimestamp::from(nodes_fixture(ad));
+        let interpolator = LogLinearInterpolator::new();
+        c.bench_function(
+            &format!("log-linear {name} 100k evaluations uncached"),
+            |z| z.iter(|| evaluate(&dates, |d| interpolator.interpolated_value(&nodes, d))),
+        );
+
+        let curve = CurveDF::try_new(
+            nodes_fixture(ad),
+            LogLinearInterpolator::new(),
+            "crv",
+            Convention::Act365F,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap();
+        c.bench_function(&format!("log-linear {name} 100k evaluations cached"), |z| {
+            z.iter(|| evaluate(&dates, |d| curve.interpolated_value(d)))
+        });
+    }
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,26 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use rateslib::dual::Dual;
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let vars: Vec<String> = (0..1000).map(|x| format!("v{}", x)).collect();
+    let a = Dual::try_new(2.0, vars, vec![1.0; 1000]).unwrap();
+    let b = Dual::new(3.0, vec![]);
+
+    c.bench_function("dual add empty vars", |z| {
+        z.iter(|| black_box(&a) + black_box(&b))
+    });
+    c.bench_function("dual sub empty vars", |z| {
+        z.iter(|| black_box(&a) - black_box(&b))
+    });
+    c.bench_function("dual mul empty vars", |z| {
+        z.iter(|| black_box(&b) * black_box(&a))
+    });
+    c.bench_function("dual div empty vars", |z| {
+        z.iter(|| black_box(&a) / black_box(&b))
+    });
+    c.bench_function("dual mul f64", |z| z.iter(|| black_box(&a) * 3.0_f64));
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
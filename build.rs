@@ -0,0 +1,41 @@
+//! Generates the `build_info` constants describing the version, features and toolchain of the
+//! compiled library.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(str::to_string))
+        .filter(|name| name != "DEFAULT")
+        .map(|name| name.to_lowercase().replace('_', "-"))
+        .collect();
+    features.sort();
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|v| v.trim().to_string())
+        .unwrap_or_default();
+
+    let source = format!(
+        "pub const VERSION: &str = {:?};\n\
+         pub const FEATURES: &[&str] = &{:?};\n\
+         pub const PROFILE: &str = {:?};\n\
+         pub const TARGET: &str = {:?};\n\
+         pub const RUSTC_VERSION: &str = {:?};\n",
+        env::var("CARGO_PKG_VERSION").unwrap(),
+        features,
+        env::var("PROFILE").unwrap(),
+        env::var("TARGET").unwrap(),
+        rustc_version,
+    );
+    let path = Path::new(&env::var("OUT_DIR").unwrap()).join("build_info.rs");
+    fs::write(path, source).unwrap();
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -1244,7 +1244,10 @@ class Curve(_Serialize):
         else:
             raise ValueError("`right` must be supplied as datetime or tenor string.")
 
-        dates = self.calendar.cal_date_range(start=left_, end=right_)
+        dates = [
+            datetime.combine(_, datetime.min.time())
+            for _ in self.calendar.cal_date_range(start=left_, end=right_).tolist()
+        ]
         rates = [self._plot_rate(_, upper_tenor, self._plot_modifier(upper_tenor)) for _ in dates]
         return dates, rates
 
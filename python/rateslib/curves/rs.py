@@ -10,14 +10,17 @@ from rateslib.default import NoInput, _drb
 from rateslib.dual import ADOrder, _get_adorder
 from rateslib.rs import Curve as CurveObj  # noqa: F401
 from rateslib.rs import (
+    CubicZeroRateInterpolator,
     FlatBackwardInterpolator,
     FlatForwardInterpolator,
     LinearInterpolator,
     LinearZeroRateInterpolator,
+    LogCubicInterpolator,
     LogLinearInterpolator,
     NullInterpolator,
     _get_convention_str,
     _get_modifier_str,
+    interpolate,  # noqa: F401
 )
 
 
@@ -125,5 +128,9 @@ def _get_interpolator(name: str):
         return FlatForwardInterpolator()
     elif name_ == "flat_backward":
         return FlatBackwardInterpolator()
+    elif name_ == "log_cubic":
+        return LogCubicInterpolator()
+    elif name_ == "cubic_zero_rate":
+        return CubicZeroRateInterpolator()
     else:
         raise ValueError("Interpolator `name` is invalid.")
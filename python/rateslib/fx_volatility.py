@@ -1,5 +1,6 @@
 from __future__ import annotations  # type hinting
 
+import json
 from datetime import datetime, timedelta
 from datetime import datetime as dt
 from typing import Union
@@ -28,6 +29,12 @@ from rateslib.splines import PPSplineDual, PPSplineDual2, PPSplineF64, evaluate
 
 TERMINAL_DATE = dt(2100, 1, 1)
 
+FX_VOL_SCHEMA_VERSION = 1
+"""
+The schema version of *Smiles* and *Surfaces* serialized to JSON. Data of an earlier version is
+migrated on loading, see :func:`_migrate_fx_vol_json`.
+"""
+
 
 class FXDeltaVolSmile:
     r"""
@@ -632,6 +639,45 @@ class FXDeltaVolSmile:
         """Get the variable names of elements updated by a Solver"""
         return tuple(f"{self.id}{i}" for i in range(self.n))
 
+    def to_json(self):
+        """
+        Convert the parameters of the *Smile* to JSON format, with a schema version.
+
+        Returns
+        -------
+        str
+        """
+        container = {
+            "schema_version": FX_VOL_SCHEMA_VERSION,
+            "nodes": {k: v.real for k, v in self.nodes.items()},
+            "eval_date": self.eval_date.strftime("%Y-%m-%d"),
+            "expiry": self.expiry.strftime("%Y-%m-%d"),
+            "delta_type": self.delta_type,
+            "id": self.id,
+            "ad": self.ad,
+        }
+        return json.dumps(container, default=str)
+
+    @classmethod
+    def from_json(cls, smile, **kwargs):
+        """
+        Reconstitute a *Smile* from JSON, migrating data of an earlier schema version.
+
+        Parameters
+        ----------
+        smile : str
+            The JSON string representation of the *Smile*.
+
+        Returns
+        -------
+        FXDeltaVolSmile
+        """
+        serial = _migrate_fx_vol_json(json.loads(smile))
+        serial["nodes"] = {float(k): v for k, v in serial["nodes"].items()}
+        serial["eval_date"] = datetime.strptime(serial["eval_date"], "%Y-%m-%d")
+        serial["expiry"] = datetime.strptime(serial["expiry"], "%Y-%m-%d")
+        return cls(**{**serial, **kwargs})
+
 
 class FXDeltaVolSurface:
     r"""
@@ -948,6 +994,57 @@ class FXDeltaVolSurface:
         """
         return self.get_smile(expiry)[delta_index]
 
+    def to_json(self):
+        """
+        Convert the parameters of the *Surface* to JSON format, with a schema version.
+
+        Returns
+        -------
+        str
+        """
+        if self.weights is NoInput.blank:
+            weights = None
+        else:
+            weights = {d.strftime("%Y-%m-%d"): w for d, w in self.weights.items()}
+
+        container = {
+            "schema_version": FX_VOL_SCHEMA_VERSION,
+            "delta_indexes": [float(d) for d in self.delta_indexes],
+            "expiries": [e.strftime("%Y-%m-%d") for e in self.expiries],
+            "node_values": [[v.real for v in smile.nodes.values()] for smile in self.smiles],
+            "eval_date": self.eval_date.strftime("%Y-%m-%d"),
+            "delta_type": self.delta_type,
+            "weights": weights,
+            "id": self.id,
+            "ad": self.ad,
+        }
+        return json.dumps(container, default=str)
+
+    @classmethod
+    def from_json(cls, surface, **kwargs):
+        """
+        Reconstitute a *Surface* from JSON, migrating data of an earlier schema version.
+
+        Parameters
+        ----------
+        surface : str
+            The JSON string representation of the *Surface*.
+
+        Returns
+        -------
+        FXDeltaVolSurface
+        """
+        serial = _migrate_fx_vol_json(json.loads(surface))
+        serial["expiries"] = [datetime.strptime(e, "%Y-%m-%d") for e in serial["expiries"]]
+        serial["eval_date"] = datetime.strptime(serial["eval_date"], "%Y-%m-%d")
+        if serial.get("weights", None) is not None:
+            serial["weights"] = Series(
+                {datetime.strptime(d, "%Y-%m-%d"): w for d, w in serial["weights"].items()},
+            )
+
+        serial = {k: v for k, v in serial.items() if v is not None}
+        return cls(**{**serial, **kwargs})
+
     def plot(self):
         plot_upper_bound = max([_.plot_upper_bound for _ in self.smiles])
         deltas = np.linspace(0.0, plot_upper_bound, 20)
@@ -975,6 +1072,33 @@ class FXDeltaVolSurface:
         return w
 
 
+def _migrate_fx_vol_json(serial: dict) -> dict:
+    """
+    Upgrade the deserialized data of a *Smile* or *Surface* to the current schema version.
+
+    Data without a ``schema_version`` is version 0. Each function in ``_FX_VOL_MIGRATIONS``
+    upgrades data of its version by one version, so data of any earlier version is migrated by
+    applying them in turn. The returned data excludes the ``schema_version``.
+    """
+    version = serial.pop("schema_version", 0)
+    if version > FX_VOL_SCHEMA_VERSION:
+        raise ValueError(
+            f"FX volatility schema version {version} is newer than the supported version "
+            f"{FX_VOL_SCHEMA_VERSION}.",
+        )
+    for v in range(version, FX_VOL_SCHEMA_VERSION):
+        serial = _FX_VOL_MIGRATIONS[v](serial)
+    return serial
+
+
+def _migrate_fx_vol_v0(serial: dict) -> dict:
+    # version 0 is the unversioned data whose fields are unchanged in version 1
+    return serial
+
+
+_FX_VOL_MIGRATIONS = {0: _migrate_fx_vol_v0}
+
+
 def _validate_delta_type(delta_type: str):
     if delta_type.lower() not in ["spot", "spot_pa", "forward", "forward_pa"]:
         raise ValueError("`delta_type` must be in {'spot', 'spot_pa', 'forward', 'forward_pa'}.")
@@ -20,6 +20,7 @@ from rateslib.instruments.core import (
     _update_with_defaults,
 )
 from rateslib.periods import Cashflow, FXCallPeriod, FXPutPeriod
+from rateslib.rs import PremiumCurrency
 from rateslib.solver import Solver
 from rateslib.splines import evaluate
 
@@ -62,7 +63,8 @@ class FXOption(Sensitivities, metaclass=ABCMeta):
         mid-market premium during pricing.
     premium_ccy: str, optional (RHS)
         The currency in which the premium is paid. Can *only* be one of the two currencies
-        in `pair`.
+        in `pair`. A mid-market premium in ccy1 (left side of `pair`) is converted at the FX rate
+        of `pair` for settlement on the premium payment date.
     option_fixing: float
         The value determined at expiry to set the moneyness of the option.
     delta_type: str in {"spot", "forward"}, optional (defaults.fx_delta_type)
@@ -187,20 +189,16 @@ class FXOption(Sensitivities, metaclass=ABCMeta):
                 True,
             )
 
-        if self.kwargs["premium_ccy"] not in [
-            self.kwargs["pair"][:3],
-            self.kwargs["pair"][3:],
-        ]:
-            raise ValueError(
-                f"`premium_ccy`: '{self.kwargs['premium_ccy']}' must be one of option "
-                f"currency pair: '{self.kwargs['pair']}'.",
-            )
-        elif self.kwargs["premium_ccy"] == self.kwargs["pair"][3:]:
-            self.kwargs["metric_period"] = "pips"
-            self.kwargs["delta_adjustment"] = ""
-        else:
+        self._premium_ccy = PremiumCurrency.from_pair(
+            self.kwargs["pair"], self.kwargs["premium_ccy"]
+        )
+        self.kwargs["premium_ccy"] = self._premium_ccy.ccy(self.kwargs["pair"]).name
+        if self._premium_ccy.premium_adjusted:
             self.kwargs["metric_period"] = "percent"
             self.kwargs["delta_adjustment"] = "_pa"
+        else:
+            self.kwargs["metric_period"] = "pips"
+            self.kwargs["delta_adjustment"] = ""
 
         # nothing to inherit or negate.
         # self.kwargs = _inherit_or_negate(self.kwargs)  # inherit or negate the complete arg list
@@ -326,11 +324,12 @@ class FXOption(Sensitivities, metaclass=ABCMeta):
                     "If not required, initialise the "
                     "FXOption with a `premium` of 0.0, and this will be avoided.",
                 )
+            # the price is converted to the premium currency at the FX rate for settlement on
+            # the premium date, not at spot.
             m_p = self.kwargs["payment"]
-            if self.kwargs["premium_ccy"] == self.kwargs["pair"][:3]:
-                premium = npv / (curves[3][m_p] * fx.rate("eurusd", m_p))
-            else:
-                premium = npv / curves[3][m_p]
+            premium = self._premium_ccy.premium(
+                npv / curves[3][m_p], fx.rate(self.kwargs["pair"], m_p)
+            )
 
             self.periods[1].notional = float(premium)
 
@@ -476,7 +475,7 @@ class FXOption(Sensitivities, metaclass=ABCMeta):
         self._set_premium(curves, fx)
 
         opt_npv = self.periods[0].npv(curves[1], curves[3], fx, base, local, vol)
-        if self.kwargs["premium_ccy"] == self.kwargs["pair"][:3]:
+        if self._premium_ccy == PremiumCurrency.Foreign:
             disc_curve = curves[1]
         else:
             disc_curve = curves[3]
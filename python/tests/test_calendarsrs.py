@@ -1,5 +1,6 @@
 from datetime import datetime as dt
 
+import numpy as np
 import pytest
 from rateslib.calendars import _get_modifier, get_calendar
 from rateslib.json import from_json
@@ -125,6 +126,18 @@ class TestCal:
     #     rules = get_calendar("tyo").rules
     #     assert rules[:10] == "Jan 1 (New"
 
+    @pytest.mark.parametrize("cal", ["simple_cal", "simple_union"])
+    def test_date_ranges_return_datetime64(self, cal, request) -> None:
+        cal = request.getfixturevalue(cal)
+        result = cal.bus_date_range(dt(2015, 9, 4), dt(2015, 9, 9))
+        expected = np.array(["2015-09-04", "2015-09-08", "2015-09-09"], dtype="datetime64[D]")
+        assert result.dtype == np.dtype("datetime64[D]")
+        np.testing.assert_array_equal(result, expected)
+
+        result = cal.cal_date_range(dt(2015, 9, 9), dt(2015, 9, 5), -2)
+        expected = np.array(["2015-09-09", "2015-09-07", "2015-09-05"], dtype="datetime64[D]")
+        np.testing.assert_array_equal(result, expected)
+
     def test_tyo_cal(self) -> None:
         tokyo = get_calendar("tyo")
         assert tokyo.holidays[0] == dt(1970, 1, 1)
@@ -6,18 +6,21 @@ from rateslib.calendars import _get_modifier, get_calendar
 from rateslib.curves.rs import (
     CurveObj,
     CurveRs,
+    CubicZeroRateInterpolator,
     FlatBackwardInterpolator,
     FlatForwardInterpolator,
     LinearInterpolator,
     LinearZeroRateInterpolator,
+    LogCubicInterpolator,
     LogLinearInterpolator,
     _get_convention,
     _get_convention_str,
     _get_interpolator,
+    interpolate,
 )
-from rateslib.dual import ADOrder, Dual2, _get_adorder
+from rateslib.dual import ADOrder, Dual, Dual2, _get_adorder
 from rateslib.json import from_json
-from rateslib.rs import Convention
+from rateslib.rs import Convention, SpliceAdjustment
 
 
 @pytest.mark.parametrize(
@@ -96,6 +99,8 @@ def indexcurvers():
         ("linear_zero_rate", LinearZeroRateInterpolator),
         ("flat_forward", FlatForwardInterpolator),
         ("flat_backward", FlatBackwardInterpolator),
+        ("log_cubic", LogCubicInterpolator),
+        ("cubic_zero_rate", CubicZeroRateInterpolator),
     ],
 )
 def test_get_interpolator(name, expected) -> None:
@@ -111,6 +116,8 @@ def test_get_interpolator(name, expected) -> None:
         "linear_zero_rate",
         "flat_forward",
         "flat_backward",
+        "log_cubic",
+        "cubic_zero_rate",
     ],
 )
 def test_pickle_interpolator(name) -> None:
@@ -170,6 +177,8 @@ def test_json_round_trip(curvers) -> None:
         "linear_zero_rate",
         "flat_forward",
         "flat_backward",
+        "log_cubic",
+        "cubic_zero_rate",
     ],
 )
 def test_interp_constructs(kind) -> None:
@@ -203,3 +212,125 @@ def test_pickle(curvers) -> None:
 
     obj = pickle.dumps(curvers)
     pickle.loads(obj)
+
+
+@pytest.mark.parametrize(
+    ("kind", "expected"),
+    [
+        ("linear", 0.375),
+        ("log_linear", 0.125**0.5),
+    ],
+)
+def test_interpolate(kind, expected) -> None:
+    result = interpolate(1.5, [0.0, 1.0, 2.0], [1.0, 0.5, 0.25], kind)
+    assert abs(result - expected) < 1e-15
+
+
+@pytest.mark.parametrize("kind", ["cubic", "log_cubic"])
+def test_interpolate_cubic_at_nodes(kind) -> None:
+    result = interpolate(1.0, [0.0, 1.0, 2.0], [1.0, 0.5, 0.25], kind)
+    assert abs(result - 0.5) < 1e-12
+
+
+def test_interpolate_dual() -> None:
+    result = interpolate(0.25, [0.0, 1.0], [Dual(1.0, ["a"], []), 3.0])
+    assert result == Dual(1.5, ["a"], [0.75])
+
+
+def test_interpolate_raises() -> None:
+    with pytest.raises(ValueError, match="not a valid interpolation"):
+        interpolate(0.5, [0.0, 1.0], [1.0, 2.0], "quadratic")
+    with pytest.raises(TypeError, match="Cannot mix"):
+        interpolate(0.5, [0.0, 1.0], [Dual(1.0, ["a"], []), Dual2(2.0, ["b"], [], [])])
+
+
+def test_sample(curvers) -> None:
+    dates, dfs = curvers.obj.sample(dt(2022, 3, 1), dt(2022, 3, 31))
+    assert dates[0] == dt(2022, 3, 1) and dates[-1] == dt(2022, 3, 31)
+    assert dfs == [float(curvers.obj[_]) for _ in dates]
+
+
+def test_curve_registry_round_trip(curve) -> None:
+    from rateslib.rs import CurveHandle, CurveRegistry, FlatCurve, SplicedCurve
+
+    flat = FlatCurve(3.0, _get_convention("Act365F"), dt(2022, 3, 1), "flat")
+    spliced = SplicedCurve(curve, curve, dt(2022, 3, 15), SpliceAdjustment.Scaled)
+    registry = CurveRegistry()
+    registry.insert(curve)
+    registry.insert(flat)
+    registry.insert(spliced, id="spliced")
+    assert registry.ids == ["flat", "spliced", "v"]
+
+    assert isinstance(registry.get("v"), CurveHandle)
+    assert isinstance(registry.get("v").to_curve(), CurveObj)
+    result = registry.get("flat")
+    assert isinstance(result.to_curve(), FlatCurve)
+    assert result.state_id == flat.state_id
+    result = registry.get("spliced")
+    assert isinstance(result.to_curve(), SplicedCurve)
+    assert result.id is None
+    assert result[dt(2022, 3, 20)] == spliced[dt(2022, 3, 20)]
+
+    # handles are shared, so a mutation via the registry is visible to a handle taken before it
+    handle = registry.get("flat")
+    registry.set_ad_order("flat", ADOrder.One)
+    assert isinstance(handle[dt(2022, 6, 1)], Dual)
+    assert handle.state_id != flat.state_id
+    handle.set_ad_order(ADOrder.Zero)
+    assert isinstance(registry.get("flat")[dt(2022, 6, 1)], float)
+
+
+def test_curve_registry_prices_by_id_at_price_time() -> None:
+    from rateslib.rs import IRS, CurveRegistry
+
+    def curve(df):
+        return CurveObj(
+            nodes={dt(2022, 1, 1): 1.0, dt(2032, 1, 1): df},
+            interpolator=_get_interpolator("log_linear"),
+            id="sofr",
+            ad=_get_adorder(0),
+            convention=_get_convention("Act360"),
+            modifier=_get_modifier("MF", True),
+            calendar=get_calendar("all"),
+        )
+
+    irs = IRS(
+        dt(2022, 1, 1),
+        dt(2027, 1, 1),
+        12,
+        1e6,
+        2.5,
+        _get_convention("Act360"),
+        get_calendar("all"),
+    )
+    registry = CurveRegistry()
+    registry.insert(curve(0.75))
+    assert registry.rate(irs, "sofr") == irs.rate(curve(0.75))
+    registry.insert(curve(0.70))
+    assert registry.rate(irs, "sofr") == irs.rate(curve(0.70))
+    assert registry.npv(irs, "sofr") == irs.npv(curve(0.70))
+    with pytest.raises(ValueError, match="is not found in the registry"):
+        registry.rate(irs, "estr")
+
+
+def test_curve_registry_insert_raises(curve) -> None:
+    from rateslib.rs import CurveRegistry, SplicedCurve
+
+    spliced = SplicedCurve(curve, curve, dt(2022, 3, 15), SpliceAdjustment.Scaled)
+    with pytest.raises(ValueError, match="An `id` is required"):
+        CurveRegistry().insert(spliced)
+    with pytest.raises(TypeError, match="is not a Curve"):
+        CurveRegistry().insert(1.0)
+
+
+def test_step_function_mixed_dual_types_raises() -> None:
+    from rateslib.rs import StepFunction
+
+    dual = StepFunction([dt(2022, 1, 1)], [Dual(1.0, ["x"], [])])
+    dual2 = StepFunction([dt(2022, 1, 1)], [Dual2(1.0, ["y"], [], [])])
+    with pytest.raises(TypeError, match="Cannot mix dual types"):
+        dual + dual2
+    with pytest.raises(TypeError, match="Cannot mix dual types"):
+        dual * Dual2(2.0, ["z"], [], [])
+    with pytest.raises(TypeError, match="Cannot mix dual types"):
+        dual == dual2
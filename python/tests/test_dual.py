@@ -1,4 +1,5 @@
 import math
+from decimal import Decimal
 from statistics import NormalDist
 
 import numpy as np
@@ -894,6 +895,31 @@ def test_numpy_dtypes(z, dtype) -> None:
     dtype(2) + z
 
 
+@pytest.mark.parametrize(
+    "z",
+    [
+        Dual(2.0, ["y"], []),
+        Dual2(3.0, ["x"], [1], [2]),
+    ],
+)
+@pytest.mark.parametrize(
+    "scalar",
+    [2, True, np.int64(2), np.float32(2.0), np.float64(2.0), Decimal("2.0"), np.array(2.0)],
+)
+def test_real_scalar_operands(z, scalar) -> None:
+    for result in [z + scalar, z - scalar, z * scalar, z / scalar, z**scalar]:
+        assert type(result) is type(z)
+    assert z * scalar == z * float(scalar)
+    assert z**scalar == z ** float(scalar)
+
+
+def test_one_element_array_operand_broadcasts() -> None:
+    z = Dual(2.0, ["y"], [])
+    result = z + np.array([1.0])
+    assert isinstance(result, np.ndarray)
+    assert result[0] == Dual(3.0, ["y"], [])
+
+
 class TestVariable:
     @pytest.mark.parametrize(
         ("op", "exp"),
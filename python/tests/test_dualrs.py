@@ -4,7 +4,20 @@ import numpy as np
 import pytest
 from packaging import version
 from rateslib.dual import Dual, Dual2, dual_solve, gradient
-from rateslib.rs import ADOrder, from_json
+from rateslib.rs import (
+    ADOrder,
+    FXRate,
+    FXRates,
+    Tape,
+    ddot,
+    dfmul,
+    fdmul,
+    from_json,
+    order_of,
+    set_order,
+    to_dual2,
+    to_f64,
+)
 
 DUAL_CORE_PY = False
 
@@ -33,6 +46,59 @@ def test_adorder_pickle(ad) -> None:
     assert ad == pickle.loads(pickle.dumps(ad))
 
 
+def test_adorder_int() -> None:
+    assert ADOrder(2) == ADOrder.Two
+    assert int(ADOrder.One) == 1
+    with pytest.raises(ValueError, match="Order for AD can only be in"):
+        ADOrder(3)
+
+
+@pytest.mark.parametrize(
+    ("obj", "expected"),
+    [
+        (2.5, ADOrder.Zero),
+        (Dual(1.0, ["x"], []), ADOrder.One),
+        (Dual2(1.0, ["x"], [], []), ADOrder.Two),
+    ],
+)
+def test_order_of(obj, expected) -> None:
+    assert order_of(obj) == expected
+
+
+def test_set_order_values(x_1) -> None:
+    assert set_order(2.5, 1) == 2.5
+    assert set_order(x_1, ADOrder.Zero) == 1.0
+    result = set_order(x_1, 2)
+    assert result == Dual2(1, ["v0", "v1"], [1, 2], [])
+    assert order_of(result) == ADOrder.Two
+
+
+def test_set_order_objects() -> None:
+    fxr = FXRates([FXRate("eur", "usd", 1.1)])
+    assert set_order(fxr, 2) is fxr
+    assert order_of(fxr) == ADOrder.Two
+
+    class Smile:
+        ad = 0
+
+        def _set_ad_order(self, order):
+            self.ad = order
+
+    smile = set_order(Smile(), ADOrder.One)
+    assert order_of(smile) == ADOrder.One
+
+    with pytest.raises(TypeError, match="Cannot set the AD order"):
+        set_order("x", 1)
+    with pytest.raises(TypeError, match="Cannot determine the AD order"):
+        order_of("x")
+
+
+def test_to_dual2_and_to_f64(x_1) -> None:
+    assert to_dual2(x_1) == Dual2(1, ["v0", "v1"], [1, 2], [])
+    assert to_dual2(2.0) == Dual2(2.0, [], [], [])
+    assert to_f64(x_1) == 1.0
+
+
 def test_json_round_trip(x_2) -> None:
     x2j = x_2.to_json()
     x_22 = from_json(x2j)
@@ -191,6 +257,71 @@ def test_dual_power_1d(power, expected) -> None:
     assert f.dual[0] == expected[1]
 
 
+def test_dual_exponent_power() -> None:
+    x = Dual(2.0, ["x"], [])
+    y = Dual(3.0, ["y"], [])
+    result = x**y
+    assert abs(result.real - 8.0) < 1e-12
+    assert abs(gradient(result, ["x"])[0] - 12.0) < 1e-12
+    assert abs(gradient(result, ["y"])[0] - 8.0 * math.log(2.0)) < 1e-12
+    with pytest.raises(TypeError, match="Cannot mix"):
+        x ** Dual2(3.0, ["y"], [], [])
+
+
+def test_dual2_exponent_power() -> None:
+    x = Dual2(2.0, ["x"], [], [])
+    result = x**x
+    expected = 4.0 * (math.log(2.0) + 1.0) ** 2 + 4.0 / 2.0
+    assert abs(gradient(result, ["x"], order=2)[0, 0] - expected) < 1e-12
+
+
+def test_dual_pow_modulo_raises(x_1) -> None:
+    with pytest.raises(TypeError, match="Power function with mod"):
+        pow(x_1, 2, 3)
+    with pytest.raises(TypeError, match="Power function with mod"):
+        pow(Dual2(2.0, ["x"], [], []), 2, 3)
+
+
+@pytest.mark.parametrize(
+    ("dual", "dual2", "match"),
+    [([1.0], [], "`vars` and `dual` must have the same length"),
+     ([1.0, 2.0], [1.0, 2.0, 3.0], "`vars` and `dual2` must have compatible lengths")],
+)
+def test_dual2_length_mismatch_raises(dual, dual2, match) -> None:
+    with pytest.raises(ValueError, match=match):
+        Dual2(2.0, ["x", "y"], dual, dual2)
+    with pytest.raises(ValueError, match=match):
+        Dual2.vars_from(Dual2(1.0, ["x", "y"], [], []), 2.0, ["x", "y"], dual, dual2)
+
+
+def test_tape_var_pow_modulo_raises() -> None:
+    x = Tape().variable(2.0)
+    assert float(x**3.0) == 8.0
+    with pytest.raises(TypeError, match="Power function with mod"):
+        pow(x, 2.0, 3)
+
+
+@pytest.mark.parametrize("cls", [Dual, Dual2])
+def test_float_base_dual_exponent_rpow(cls) -> None:
+    y = cls(3.0, ["y"], [], []) if cls is Dual2 else cls(3.0, ["y"], [])
+    result = 2.0**y
+    assert isinstance(result, cls)
+    assert abs(result.real - 8.0) < 1e-12
+    assert abs(gradient(result, ["y"])[0] - 8.0 * math.log(2.0)) < 1e-12
+    assert abs((2**y).real - 8.0) < 1e-12
+
+
+def test_tolerances_are_read_only_and_validated() -> None:
+    from rateslib.rs import Tolerances, set_thread_tolerances
+
+    t = Tolerances()
+    with pytest.raises(AttributeError):
+        t.solver = -1.0
+    with pytest.raises(ValueError, match="Tolerances must be finite"):
+        Tolerances(solver=-1.0)
+    set_thread_tolerances(None)
+
+
 def test_dual_truediv(x_1) -> None:
     expected = Dual(1, [], [])
     result = x_1 / x_1
@@ -368,3 +499,14 @@ def test_pickle(obj) -> None:
     pickled_obj = pickle.dumps(obj)
     reloaded = pickle.loads(pickled_obj)
     assert obj == reloaded
+
+
+def test_linalg_products_mixed_dual_types_raise() -> None:
+    d1, d2 = Dual(1.0, ["x"], []), Dual2(2.0, ["y"], [], [])
+    with pytest.raises(TypeError, match="Cannot mix"):
+        fdmul(np.array([[1.0, 2.0]]), [d1, d2])
+    with pytest.raises(TypeError, match="Cannot mix"):
+        dfmul([[d1, d2]], np.array([1.0, 2.0]))
+    with pytest.raises(TypeError, match="Cannot mix"):
+        ddot([d1, 1.0], [2.0, d2])
+    assert ddot([d1, 1.0], [2.0, d1]) == Dual(3.0, ["x"], [3.0])
@@ -3,7 +3,7 @@ from datetime import datetime as dt
 import numpy as np
 import pytest
 from matplotlib import pyplot as plt
-from pandas import DataFrame, Series
+from pandas import DataFrame, Series, Timestamp
 from pandas.testing import assert_frame_equal, assert_series_equal
 from rateslib.calendars import get_calendar
 from rateslib.curves import CompositeCurve, Curve, LineCurve
@@ -14,7 +14,12 @@ from rateslib.fx import (
     FXRates,
     forward_fx,
 )
-from rateslib.fx_volatility import FXDeltaVolSmile, FXDeltaVolSurface, _validate_delta_type
+from rateslib.fx_volatility import (
+    FX_VOL_SCHEMA_VERSION,
+    FXDeltaVolSmile,
+    FXDeltaVolSurface,
+    _validate_delta_type,
+)
 from rateslib.periods import FXPutPeriod
 
 
@@ -340,6 +345,48 @@ class TestFXDeltaVolSmile:
         with pytest.raises(TypeError, match="`FXDeltaVolSmile` is not iterable."):
             fxvs.__iter__()
 
+    def test_json_round_trip(self) -> None:
+        fxvs = FXDeltaVolSmile(
+            nodes={0.25: 10.0, 0.5: 9.5, 0.75: 11.0},
+            eval_date=dt(2023, 3, 16),
+            expiry=dt(2023, 6, 16),
+            delta_type="spot_pa",
+            id="vol",
+            ad=1,
+        )
+        serial = fxvs.to_json()
+        assert f'"schema_version": {FX_VOL_SCHEMA_VERSION}' in serial
+        result = FXDeltaVolSmile.from_json(serial)
+        assert result.nodes == fxvs.nodes
+        assert result.eval_date == fxvs.eval_date
+        assert result.expiry == fxvs.expiry
+        assert result.delta_type == fxvs.delta_type
+        assert result.id == fxvs.id
+        assert result.ad == fxvs.ad
+
+    def test_json_migrates_unversioned(self) -> None:
+        serial = (
+            '{"nodes": {"0.25": 10.0, "0.75": 11.0}, "eval_date": "2023-03-16", '
+            '"expiry": "2023-06-16", "delta_type": "forward", "id": "vol", "ad": 0}'
+        )
+        result = FXDeltaVolSmile.from_json(serial)
+        assert result.nodes == {0.25: 10.0, 0.75: 11.0}
+        assert result.delta_type == "forward"
+
+    def test_json_newer_version_raises(self) -> None:
+        fxvs = FXDeltaVolSmile(
+            nodes={0.5: 10.0},
+            eval_date=dt(2023, 3, 16),
+            expiry=dt(2023, 6, 16),
+            delta_type="forward",
+        )
+        serial = fxvs.to_json().replace(
+            f'"schema_version": {FX_VOL_SCHEMA_VERSION}',
+            f'"schema_version": {FX_VOL_SCHEMA_VERSION + 1}',
+        )
+        with pytest.raises(ValueError, match="is newer than the supported version"):
+            FXDeltaVolSmile.from_json(serial)
+
 
 class TestFXDeltaVolSurface:
     def test_expiry_before_eval(self) -> None:
@@ -589,9 +636,39 @@ class TestFXDeltaVolSurface:
         ]
 
         for i, date in enumerate(cal.cal_date_range(dt(2024, 2, 10), dt(2024, 3, 9))):
-            smile = fxvs_weights.get_smile(date)
+            smile = fxvs_weights.get_smile(Timestamp(date).to_pydatetime())
             assert abs(smile.nodes[0.5] - expected[i]) < 5e-3
 
+    @pytest.mark.parametrize(
+        "weights",
+        [NoInput(0), Series(2.0, index=[dt(2024, 1, 5), dt(2024, 1, 12), dt(2024, 2, 5)])],
+    )
+    def test_json_round_trip(self, weights) -> None:
+        fxvs = FXDeltaVolSurface(
+            delta_indexes=[0.25, 0.5, 0.75],
+            expiries=[dt(2024, 1, 1), dt(2025, 1, 1)],
+            node_values=[[11, 10, 12], [8, 7, 9]],
+            eval_date=dt(2023, 1, 1),
+            delta_type="forward",
+            weights=weights,
+            id="surf",
+        )
+        serial = fxvs.to_json()
+        assert f'"schema_version": {FX_VOL_SCHEMA_VERSION}' in serial
+        result = FXDeltaVolSurface.from_json(serial)
+        assert result.expiries == fxvs.expiries
+        assert result.delta_indexes == fxvs.delta_indexes
+        assert result.id == fxvs.id
+        for smile, expected in zip(result.smiles, fxvs.smiles):
+            assert smile.nodes == expected.nodes
+        if weights is NoInput.blank:
+            assert result.weights is NoInput.blank
+        else:
+            assert_series_equal(result.weights, fxvs.weights, check_freq=False)
+        result_vols = list(result.get_smile(dt(2024, 7, 1)).nodes.values())
+        expected_vols = list(fxvs.get_smile(dt(2024, 7, 1)).nodes.values())
+        assert result_vols == pytest.approx(expected_vols, abs=1e-12)
+
 
 def test_validate_delta_type() -> None:
     with pytest.raises(ValueError, match="`delta_type` must be in"):
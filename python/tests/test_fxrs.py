@@ -2,8 +2,18 @@ from datetime import datetime as dt
 
 import pytest
 from rateslib.fx import FXRates
+from rateslib.fx_volatility import FXDeltaVolSmile
 from rateslib.json import from_json
-from rateslib.rs import Ccy, Dual, Dual2, FXRate
+from rateslib.rs import (
+    BarrierKind,
+    Ccy,
+    Dual,
+    Dual2,
+    FXRate,
+    GarmanKohlhagen,
+    PremiumCurrency,
+    premium_fx_rate,
+)
 
 
 def test_ccy_creation() -> None:
@@ -12,6 +22,26 @@ def test_ccy_creation() -> None:
     assert c1 == c2
 
 
+def test_ccy_str_interop() -> None:
+    usd = Ccy("usd")
+    assert usd == "USD"
+    assert str(usd) == "usd"
+    assert {usd: 1}["usd"] == 1
+    assert usd.is_iso()
+    assert Ccy("jpy").minor_units() == 0
+    assert Ccy("abc").minor_units() is None
+
+
+@pytest.mark.parametrize(
+    ("lhs", "rhs", "expected"),
+    [("usd", "eur", "eurusd"), ("jpy", "usd", "usdjpy"), ("sek", "nok", "noksek")],
+)
+def test_ccy_pair_with(lhs, rhs, expected) -> None:
+    assert Ccy(lhs).pair_with(rhs) == expected
+    with pytest.raises(ValueError):
+        Ccy(lhs).pair_with(lhs)
+
+
 @pytest.mark.parametrize("val", [0.99, Dual(0.99, ["x"], []), Dual2(0.99, ["x"], [], [])])
 def test_fx_rate_creation(val) -> None:
     fxr = FXRate("usd", "eur", val, dt(2001, 1, 1))
@@ -31,3 +61,84 @@ def test_equality() -> None:
     fxr = FXRates({"eurusd": 1.08, "usdjpy": 110.0}, dt(2004, 1, 1))
     fxr2 = FXRates({"eurusd": 1.08, "usdjpy": 110.0}, dt(2004, 1, 1))
     assert fxr == fxr2
+
+
+def test_premium_currency() -> None:
+    domestic = PremiumCurrency.from_pair("eurusd", "usd")
+    foreign = PremiumCurrency.from_pair("eurusd", Ccy("eur"))
+    assert domestic == PremiumCurrency.Domestic
+    assert foreign == PremiumCurrency.Foreign
+    assert foreign.ccy("eurusd") == Ccy("eur")
+    assert foreign.premium_adjusted and not domestic.premium_adjusted
+    assert abs(foreign.premium(0.0125, 1.25) - 0.01) < 1e-15
+    assert abs(foreign.price(0.01, 1.25) - 0.0125) < 1e-15
+    assert abs(domestic.quote(0.0125, 1.25) - 125.0) < 1e-12
+    assert abs(foreign.quote(0.0125, 1.25) - 1.0) < 1e-12
+    with pytest.raises(ValueError, match="must be one of option currency pair"):
+        PremiumCurrency.from_pair("eurusd", "chf")
+    with pytest.raises(TypeError, match="Cannot mix"):
+        foreign.premium(Dual(0.0125, ["a"], []), Dual2(1.25, ["b"], [], []))
+
+
+def test_premium_currency_pickle() -> None:
+    import pickle
+
+    assert PremiumCurrency.Foreign == pickle.loads(pickle.dumps(PremiumCurrency.Foreign))
+
+
+def test_premium_fx_rate() -> None:
+    fxr = FXRates({"eurusd": 1.1}, settlement=dt(2023, 6, 20))
+    assert abs(premium_fx_rate(fxr.obj, "eurusd", dt(2023, 6, 20)) - 1.1) < 1e-15
+    with pytest.raises(ValueError, match="must be for settlement on the premium date"):
+        premium_fx_rate(fxr.obj, "eurusd", dt(2023, 3, 20))
+
+
+def _smile(nodes):
+    return FXDeltaVolSmile(
+        nodes=nodes,
+        delta_type="forward",
+        eval_date=dt(2023, 3, 16),
+        expiry=dt(2023, 6, 16),
+    )
+
+
+@pytest.mark.parametrize(
+    ("price", "level"),
+    [
+        (lambda gk, smile: gk.vanilla(1.12, smile, call=False), 1.12),
+        (lambda gk, smile: gk.digital(1.12, smile), 1.12),
+        (lambda gk, smile: gk.one_touch(1.2, smile), 1.2),
+        (lambda gk, smile: gk.no_touch(1.0, smile), 1.0),
+        (lambda gk, smile: gk.barrier(1.05, 1.2, BarrierKind.UpAndOut, smile), 1.05),
+    ],
+)
+def test_garman_kohlhagen_vol_from_smile(price, level) -> None:
+    gk = GarmanKohlhagen(1.10, 0.99, 0.995, 0.25)
+    smile = _smile({0.25: 10.15, 0.5: 7.8, 0.75: 8.9})
+    vol = smile.get_from_strike(level, gk.forward())[1]
+    flat = _smile({0.25: vol, 0.5: vol, 0.75: vol})
+    assert abs(price(gk, smile) - price(gk, flat)) < 1e-14
+
+
+def test_garman_kohlhagen_surface_requires_expiry() -> None:
+    from rateslib.fx_volatility import FXDeltaVolSurface
+
+    surface = FXDeltaVolSurface(
+        delta_indexes=[0.25, 0.5, 0.75],
+        expiries=[dt(2023, 6, 16), dt(2023, 9, 16)],
+        node_values=[[10.15, 7.8, 8.9], [10.0, 8.0, 9.0]],
+        eval_date=dt(2023, 3, 16),
+        delta_type="forward",
+    )
+    gk = GarmanKohlhagen(1.10, 0.99, 0.995, 0.25)
+    smile = surface.get_smile(dt(2023, 6, 16))
+    expected = gk.vanilla(1.12, smile)
+    assert abs(gk.vanilla(1.12, surface, expiry=dt(2023, 6, 16)) - expected) < 1e-14
+    with pytest.raises(ValueError, match="`expiry` required"):
+        gk.vanilla(1.12, surface)
+
+
+def test_barrier_kind_pickle() -> None:
+    import pickle
+
+    assert BarrierKind.DownAndIn == pickle.loads(pickle.dumps(BarrierKind.DownAndIn))
@@ -4017,6 +4017,31 @@ class TestFXOptions:
         expected = 0.0
         assert abs(result - expected) < 1e-6
 
+    @pytest.mark.parametrize("premium_ccy", ["gbp", "usd"])
+    def test_fx_call_npv_unpriced_pair_premium_ccy(self, premium_ccy) -> None:
+        # the premium is converted at the rate of the option pair on the premium date
+        gbpgbp = Curve({dt(2023, 3, 16): 1.0, dt(2023, 9, 16): 0.98}, calendar="ldn", id="gbpgbp")
+        usdusd = Curve({dt(2023, 3, 16): 1.0, dt(2023, 9, 16): 0.976}, calendar="nyc", id="usdusd")
+        gbpusd = Curve({dt(2023, 3, 16): 1.0, dt(2023, 9, 16): 0.985}, id="gbpusd")
+        fxf = FXForwards(
+            fx_curves={"gbpgbp": gbpgbp, "gbpusd": gbpusd, "usdusd": usdusd},
+            fx_rates=FXRates({"gbpusd": 1.24}, settlement=dt(2023, 3, 20)),
+        )
+        fxo = FXCall(
+            pair="gbpusd",
+            expiry=dt(2023, 6, 16),
+            notional=20e6,
+            delivery_lag=2,
+            payment_lag=dt(2023, 6, 20),
+            calendar="ldn",
+            strike=1.26,
+            premium_ccy=premium_ccy.upper(),
+        )
+        assert fxo.kwargs["premium_ccy"] == premium_ccy
+        curves = [None, fxf.curve("gbp", "usd"), None, fxf.curve("usd", "usd")]
+        result = fxo.npv(curves, fx=fxf, vol=8.9)
+        assert abs(result) < 1e-6
+
     def test_fx_call_cashflows(self, fxfo) -> None:
         fxo = FXCall(
             pair="eurusd",
@@ -0,0 +1,81 @@
+from datetime import datetime as dt
+
+import pytest
+from rateslib.calendars import _get_modifier, get_calendar
+from rateslib.curves.rs import CurveObj, _get_convention, _get_interpolator
+from rateslib.dual import _get_adorder
+from rateslib.rs import IRS, SBS, Convention, FixedRateBond, Modifier
+
+
+@pytest.fixture
+def curve():
+    return CurveObj(
+        nodes={dt(2024, 1, 1): 1.0, dt(2028, 1, 1): 0.85},
+        interpolator=_get_interpolator("log_linear"),
+        id="sofr",
+        ad=_get_adorder(0),
+        convention=_get_convention("Act360"),
+        modifier=_get_modifier("MF", True),
+        calendar=get_calendar("all"),
+    )
+
+
+def test_irs_conventions_from_spec(curve) -> None:
+    irs = IRS(dt(2024, 1, 10), dt(2027, 1, 10), None, 1e6, 3.0, spec="usd_irs")
+    expected = IRS(
+        dt(2024, 1, 10),
+        dt(2027, 1, 10),
+        12,
+        1e6,
+        3.0,
+        Convention.Act360,
+        get_calendar("nyc"),
+        Modifier.ModF,
+        2,
+    )
+    assert irs.cashflow_table("usd", curve) == expected.cashflow_table("usd", curve)
+
+
+def test_irs_given_conventions_take_precedence_over_spec(curve) -> None:
+    irs = IRS(dt(2024, 1, 10), dt(2027, 1, 10), 3, 1e6, 3.0, payment_lag=0, spec="usd_irs")
+    expected = IRS(
+        dt(2024, 1, 10),
+        dt(2027, 1, 10),
+        3,
+        1e6,
+        3.0,
+        Convention.Act360,
+        get_calendar("nyc"),
+        "MF",
+        0,
+    )
+    assert irs.cashflow_table("usd", curve) == expected.cashflow_table("usd", curve)
+
+
+def test_conventions_without_spec_raise() -> None:
+    with pytest.raises(ValueError, match="must be given, or be specified"):
+        IRS(dt(2024, 1, 10), dt(2027, 1, 10), None, 1e6, 3.0, Convention.Act360)
+    with pytest.raises(ValueError, match="not found"):
+        IRS(dt(2024, 1, 10), dt(2027, 1, 10), None, 1e6, 3.0, spec="bad_spec")
+
+
+def test_sbs_and_bond_conventions_from_spec() -> None:
+    sbs = SBS(dt(2022, 1, 1), dt(2025, 1, 1), None, 1e6, 5.0, spec="eur_sbs36")
+    assert sbs.float_spread == 5.0
+    bond = FixedRateBond(dt(2020, 2, 15), dt(2030, 2, 15), 1.5, spec="us_gb")
+    expected = FixedRateBond(
+        dt(2020, 2, 15), dt(2030, 2, 15), 1.5, 6, get_calendar("nyc"), ex_div=1
+    )
+    assert bond.uschedule == expected.uschedule
+
+
+def test_conventions_default_without_spec(curve) -> None:
+    nyc = get_calendar("nyc")
+    irs = IRS(dt(2024, 1, 10), dt(2027, 1, 10), 12, 1e6, 3.0, Convention.Act360, nyc)
+    expected = IRS(
+        dt(2024, 1, 10), dt(2027, 1, 10), 12, 1e6, 3.0, Convention.Act360, nyc, Modifier.ModF, 0
+    )
+    assert irs.cashflow_table("usd", curve) == expected.cashflow_table("usd", curve)
+    bond = FixedRateBond(dt(2020, 2, 15), dt(2030, 2, 15), 1.5, 6, nyc)
+    expected = FixedRateBond(dt(2020, 2, 15), dt(2030, 2, 15), 1.5, 6, nyc, ex_div=0)
+    assert bond.uschedule == expected.uschedule
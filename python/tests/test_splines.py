@@ -119,6 +119,15 @@ def test_ppev(t) -> None:
     assert (result == np.array([r1, r2, r3])).all()
 
 
+def test_sample(t) -> None:
+    bs = PPSplineF64(k=4, t=t, c=[1, 2, -1, 2, 1, 1, 2, 2.0])
+    x, y = bs.sample(1.0, 4.0, 1e-4)
+    assert x[0] == 1.0 and x[-1] == 4.0
+    assert y == [bs.ppev_single(_) for _ in x]
+    with pytest.raises(ValueError, match="`end` must be after `start`"):
+        bs.sample(4.0, 1.0)
+
+
 def test_csolve() -> None:
     t = [0, 0, 0, 0, 4, 4, 4, 4]
     tau = np.array([0, 1, 3, 4])
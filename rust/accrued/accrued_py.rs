@@ -0,0 +1,189 @@
+//! Wrapper module to export Rust accrued interest conventions to Python using pyo3 bindings.
+
+use crate::accrued::{is_ex_div, simple_yield, AccrualConvention, AccrualPeriod};
+use crate::calendars::calendar_py::extract_optional_roll_day;
+use crate::calendars::{CalType, RollDay};
+use crate::dates::dates_py::extract_date;
+use bincode::{deserialize, serialize};
+use chrono::NaiveDateTime;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+#[pymethods]
+impl AccrualConvention {
+    // Pickling
+    #[new]
+    fn new_py(item: u8) -> PyResult<AccrualConvention> {
+        match item {
+            0_u8 => Ok(AccrualConvention::LinearDays),
+            1_u8 => Ok(AccrualConvention::LinearDaysLongFrontSplit),
+            2_u8 => Ok(AccrualConvention::ThirtyE360),
+            3_u8 => Ok(AccrualConvention::Act365F1Y),
+            4_u8 => Ok(AccrualConvention::Act365NL),
+            _ => Err(PyValueError::new_err(
+                "unreachable code on AccrualConvention pickle.",
+            )),
+        }
+    }
+    pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
+        *self = deserialize(state.as_bytes()).unwrap();
+        Ok(())
+    }
+    pub fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new_bound(py, &serialize(&self).unwrap()))
+    }
+    pub fn __getnewargs__(&self) -> PyResult<(u8,)> {
+        match self {
+            AccrualConvention::LinearDays => Ok((0_u8,)),
+            AccrualConvention::LinearDaysLongFrontSplit => Ok((1_u8,)),
+            AccrualConvention::ThirtyE360 => Ok((2_u8,)),
+            AccrualConvention::Act365F1Y => Ok((3_u8,)),
+            AccrualConvention::Act365NL => Ok((4_u8,)),
+        }
+    }
+
+    /// Return the fraction of a coupon period's cashflow accrued at settlement.
+    ///
+    /// Parameters
+    /// ----------
+    /// settlement: datetime
+    ///     The settlement date, within the coupon period.
+    /// start: datetime
+    ///     The unadjusted start date of the coupon period.
+    /// end: datetime
+    ///     The unadjusted end date of the coupon period.
+    /// frequency: int
+    ///     The number of months in a regular coupon period.
+    /// stub: bool, optional
+    ///     Whether the coupon period is a stub period.
+    /// roll: RollDay, optional
+    ///     Used to project quasi-coupon dates for long stub periods.
+    ///
+    /// Returns
+    /// -------
+    /// float
+    #[pyo3(name = "fraction", signature = (settlement, start, end, frequency, stub=false, roll=None))]
+    fn fraction_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] settlement: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] start: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] end: NaiveDateTime,
+        frequency: i32,
+        stub: bool,
+        #[pyo3(from_py_with = "extract_optional_roll_day")] roll: Option<RollDay>,
+    ) -> PyResult<f64> {
+        let period = AccrualPeriod::new(
+            start,
+            end,
+            frequency,
+            stub,
+            roll.unwrap_or(RollDay::Unspecified {}),
+        );
+        self.fraction(&settlement, &period)
+    }
+
+    /// Return the accrued interest of a coupon period at settlement.
+    ///
+    /// Parameters
+    /// ----------
+    /// settlement: datetime
+    ///     The settlement date, within the coupon period.
+    /// start: datetime
+    ///     The unadjusted start date of the coupon period.
+    /// end: datetime
+    ///     The unadjusted end date of the coupon period.
+    /// frequency: int
+    ///     The number of months in a regular coupon period.
+    /// cashflow: float
+    ///     The cashflow of the coupon period, e.g. per 100 nominal.
+    /// stub: bool, optional
+    ///     Whether the coupon period is a stub period.
+    /// roll: RollDay, optional
+    ///     Used to project quasi-coupon dates for long stub periods.
+    /// ex_div: bool, optional
+    ///     Whether settlement is in the ex-dividend period, in which case accrued is negative.
+    ///
+    /// Returns
+    /// -------
+    /// float
+    #[pyo3(name = "accrued", signature = (settlement, start, end, frequency, cashflow, stub=false, roll=None, ex_div=false))]
+    #[allow(clippy::too_many_arguments)]
+    fn accrued_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] settlement: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] start: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] end: NaiveDateTime,
+        frequency: i32,
+        cashflow: f64,
+        stub: bool,
+        #[pyo3(from_py_with = "extract_optional_roll_day")] roll: Option<RollDay>,
+        ex_div: bool,
+    ) -> PyResult<f64> {
+        let period = AccrualPeriod::new(
+            start,
+            end,
+            frequency,
+            stub,
+            roll.unwrap_or(RollDay::Unspecified {}),
+        );
+        self.accrued(&settlement, &period, cashflow, ex_div)
+    }
+}
+
+/// Return whether a settlement is in the ex-dividend period of a coupon.
+///
+/// Parameters
+/// ----------
+/// settlement: datetime
+///     The settlement date to test.
+/// coupon: datetime
+///     The unadjusted coupon date.
+/// ex_div: int
+///     The number of business days prior to the coupon date of the ex-dividend date.
+/// calendar: Cal, UnionCal or NamedCal
+///     The business day calendar.
+/// inclusive: bool, optional
+///     Whether settlement on the ex-dividend date is ex-dividend.
+///
+/// Returns
+/// -------
+/// bool
+#[pyfunction]
+#[pyo3(name = "is_ex_div", signature = (settlement, coupon, ex_div, calendar, inclusive=false))]
+pub(crate) fn is_ex_div_py(
+    #[pyo3(from_py_with = "extract_date")] settlement: NaiveDateTime,
+    #[pyo3(from_py_with = "extract_date")] coupon: NaiveDateTime,
+    ex_div: i32,
+    calendar: CalType,
+    inclusive: bool,
+) -> bool {
+    is_ex_div(&settlement, &coupon, ex_div, &calendar, inclusive)
+}
+
+/// Return the simple yield of a bond under the Japanese convention.
+///
+/// Parameters
+/// ----------
+/// clean_price: float
+///     The clean price per 100 nominal.
+/// coupon_rate: float
+///     The annual coupon rate, in percent.
+/// settlement: datetime
+///     The settlement date.
+/// maturity: datetime
+///     The maturity date.
+///
+/// Returns
+/// -------
+/// float
+#[pyfunction]
+#[pyo3(name = "simple_yield")]
+pub(crate) fn simple_yield_py(
+    clean_price: f64,
+    coupon_rate: f64,
+    #[pyo3(from_py_with = "extract_date")] settlement: NaiveDateTime,
+    #[pyo3(from_py_with = "extract_date")] maturity: NaiveDateTime,
+) -> f64 {
+    simple_yield(clean_price, coupon_rate, &settlement, &maturity)
+}
@@ -0,0 +1,359 @@
+//! Accrued interest conventions for the settlement of bonds.
+//!
+//! An accrual convention determines the **fraction** of a coupon period's cashflow that is
+//! attributed to a settlement date. The accrued interest is that fraction of the cashflow, less
+//! one whole cashflow if the settlement is in the ex-dividend period of the coupon.
+
+use crate::calendars::{split_dcf, Convention, DateRoll, PeriodSplit, RollDay};
+use chrono::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, PyErr};
+use serde::{Deserialize, Serialize};
+
+pub(crate) mod accrued_py;
+
+/// The calculation method for the fraction of a coupon period accrued at settlement.
+#[pyclass(module = "rateslib.rs", eq, eq_int)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AccrualConvention {
+    /// Linear proportion of calendar days between the unadjusted coupon dates, e.g. UK Gilts
+    /// and German Bunds.
+    LinearDays,
+    /// As `LinearDays` except long stubs are split into a quasi-coupon period and a regular
+    /// period, e.g. US Treasuries (street convention).
+    LinearDaysLongFrontSplit,
+    /// One less the 30E360 fraction of a regular period remaining until the next coupon, with
+    /// stubs reverting to `LinearDays`, e.g. Swedish and German 30E bonds.
+    ThirtyE360,
+    /// Act365F fraction of a regular period, adjusted for periods longer than half a year, with
+    /// stubs reverting to `LinearDays`, e.g. Canadian GBs.
+    Act365F1Y,
+    /// Act365F fraction of a regular period where 29th February is not counted, e.g. Japanese
+    /// GBs.
+    Act365NL,
+}
+
+/// The unadjusted dates and characteristics of the coupon period in which settlement falls.
+#[derive(Clone)]
+pub struct AccrualPeriod {
+    /// The unadjusted start date of the coupon period.
+    pub start: NaiveDateTime,
+    /// The unadjusted end date of the coupon period.
+    pub end: NaiveDateTime,
+    /// The number of months in a regular coupon period.
+    pub frequency_months: i32,
+    /// Whether the coupon period is a stub period.
+    pub stub: bool,
+    /// The roll day used to project quasi-coupon dates for long stubs.
+    pub roll: RollDay,
+}
+
+impl AccrualPeriod {
+    pub fn new(
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+        frequency_months: i32,
+        stub: bool,
+        roll: RollDay,
+    ) -> Self {
+        AccrualPeriod {
+            start,
+            end,
+            frequency_months,
+            stub,
+            roll,
+        }
+    }
+
+    /// The number of regular coupon periods per year.
+    fn frequency(&self) -> f64 {
+        12.0 / self.frequency_months as f64
+    }
+}
+
+fn days_between(start: &NaiveDateTime, end: &NaiveDateTime) -> f64 {
+    (*end - *start).num_days() as f64
+}
+
+/// Return the number of days between two dates excluding any 29th February in `(start, end]`.
+pub fn days_no_leap(start: &NaiveDateTime, end: &NaiveDateTime) -> i64 {
+    let leap_days = (start.year()..=end.year())
+        .filter_map(|y| NaiveDate::from_ymd_opt(y, 2, 29))
+        .map(|d| d.and_hms_opt(0, 0, 0).unwrap())
+        .filter(|d| d > start && d <= end)
+        .count() as i64;
+    (*end - *start).num_days() - leap_days
+}
+
+impl AccrualConvention {
+    /// Return the fraction of the coupon period's cashflow accrued at `settlement`.
+    pub fn fraction(
+        &self,
+        settlement: &NaiveDateTime,
+        period: &AccrualPeriod,
+    ) -> Result<f64, PyErr> {
+        if *settlement < period.start || *settlement > period.end {
+            return Err(PyValueError::new_err(
+                "`settlement` must fall within the coupon period.",
+            ));
+        }
+        match self {
+            AccrualConvention::LinearDays => Ok(linear_days(settlement, period)),
+            AccrualConvention::LinearDaysLongFrontSplit => {
+                Ok(linear_days_long_front_split(settlement, period))
+            }
+            AccrualConvention::ThirtyE360 => thirty_e360(settlement, period),
+            AccrualConvention::Act365F1Y => Ok(act365f_1y(settlement, period)),
+            AccrualConvention::Act365NL => {
+                Ok(days_no_leap(&period.start, settlement) as f64 * period.frequency() / 365.0)
+            }
+        }
+    }
+
+    /// Return the accrued interest at `settlement` of a coupon period with the given `cashflow`.
+    ///
+    /// If `ex_div` the accrued is negative, being the portion of the cashflow not received.
+    pub fn accrued(
+        &self,
+        settlement: &NaiveDateTime,
+        period: &AccrualPeriod,
+        cashflow: f64,
+        ex_div: bool,
+    ) -> Result<f64, PyErr> {
+        let fraction = self.fraction(settlement, period)?;
+        Ok(if ex_div { fraction - 1.0 } else { fraction } * cashflow)
+    }
+}
+
+fn linear_days(settlement: &NaiveDateTime, period: &AccrualPeriod) -> f64 {
+    days_between(&period.start, settlement) / days_between(&period.start, &period.end)
+}
+
+fn linear_days_long_front_split(settlement: &NaiveDateTime, period: &AccrualPeriod) -> f64 {
+    if !period.stub {
+        return linear_days(settlement, period);
+    }
+    // Quasi-coupon periods are projected from the end of the period, so the fraction remaining
+    // at settlement is measured on the same sub-periods as the whole period.
+    let split = PeriodSplit::RegularPeriod {
+        frequency_months: period.frequency_months,
+        roll: &period.roll,
+        front: true,
+    };
+    let quasi_periods = |start: &NaiveDateTime| -> f64 {
+        split_dcf(start, &period.end, &split)
+            .iter()
+            .map(|p| p.fraction)
+            .sum()
+    };
+    1.0 - quasi_periods(settlement) / quasi_periods(&period.start)
+}
+
+fn thirty_e360(settlement: &NaiveDateTime, period: &AccrualPeriod) -> Result<f64, PyErr> {
+    if period.stub {
+        return Ok(linear_days(settlement, period));
+    }
+    let remaining =
+        Convention::ThirtyE360.dcf(settlement, &period.end, None, None, None, None, None)?;
+    Ok(1.0 - remaining * period.frequency())
+}
+
+fn act365f_1y(settlement: &NaiveDateTime, period: &AccrualPeriod) -> f64 {
+    if period.stub {
+        return linear_days(settlement, period);
+    }
+    let f = period.frequency();
+    let r = days_between(&period.start, settlement);
+    let s = days_between(&period.start, &period.end);
+    if r == s {
+        1.0
+    } else if r > 365.0 / f {
+        1.0 - (s - r) * f / 365.0
+    } else {
+        f * r / 365.0
+    }
+}
+
+/// Return whether a `settlement` is in the ex-dividend period of the coupon paid on the
+/// unadjusted `coupon` date.
+///
+/// The ex-dividend date is measured as `ex_div_days` business days prior to the coupon date,
+/// under lag rules. Settlement **after** the ex-dividend date is ex-dividend. If `inclusive`,
+/// as in some markets, settlement **on** the ex-dividend date is also ex-dividend.
+pub fn is_ex_div(
+    settlement: &NaiveDateTime,
+    coupon: &NaiveDateTime,
+    ex_div_days: i32,
+    calendar: &impl DateRoll,
+    inclusive: bool,
+) -> bool {
+    let ex_div_date = calendar.lag(coupon, -ex_div_days, true);
+    if inclusive {
+        *settlement >= ex_div_date
+    } else {
+        *settlement > ex_div_date
+    }
+}
+
+/// Return the simple yield, in percent, of a bond under the Japanese convention.
+///
+/// The redemption gain or loss is amortised linearly over the years to maturity, measured as
+/// days excluding 29th February divided by 365.
+pub fn simple_yield(
+    clean_price: f64,
+    coupon_rate: f64,
+    settlement: &NaiveDateTime,
+    maturity: &NaiveDateTime,
+) -> f64 {
+    let years = days_no_leap(settlement, maturity) as f64 / 365.0;
+    (coupon_rate + (100.0 - clean_price) / years) / clean_price * 100.0
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{get_calendar_by_name, ndt};
+
+    fn regular(start: NaiveDateTime, end: NaiveDateTime, frequency_months: i32) -> AccrualPeriod {
+        AccrualPeriod::new(start, end, frequency_months, false, RollDay::Unspecified {})
+    }
+
+    #[test]
+    fn test_gilt_linear_days() {
+        // 5% semi-annual: 104 of 181 days
+        let period = regular(ndt(2022, 1, 1), ndt(2022, 7, 1), 6);
+        let result = AccrualConvention::LinearDays
+            .accrued(&ndt(2022, 4, 15), &period, 2.5, false)
+            .unwrap();
+        assert!((result - 1.43646409).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_cadgb_act365f_1y() {
+        let period = regular(ndt(2022, 1, 1), ndt(2022, 7, 1), 6);
+        let result = AccrualConvention::Act365F1Y
+            .accrued(&ndt(2022, 4, 15), &period, 2.5, false)
+            .unwrap();
+        assert!((result - 1.42465753).abs() < 1e-8);
+
+        // IIAC Canadian conventions examples
+        let period = regular(ndt(2005, 8, 1), ndt(2006, 2, 1), 6);
+        for (settlement, expected) in [(ndt(2005, 12, 1), 1.671232), (ndt(2006, 1, 31), 2.486301)] {
+            let result = AccrualConvention::Act365F1Y
+                .accrued(&settlement, &period, 2.5, false)
+                .unwrap();
+            assert!((result - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_ust_street() {
+        let cf = 3.875 / 2.0;
+        let period = regular(ndt(2024, 8, 15), ndt(2025, 2, 15), 6);
+        let result = AccrualConvention::LinearDaysLongFrontSplit
+            .accrued(&ndt(2025, 2, 14), &period, cf, false)
+            .unwrap();
+        assert!((result - 1.926970).abs() < 1e-6);
+        let period = regular(ndt(2025, 2, 15), ndt(2025, 8, 15), 6);
+        let result = AccrualConvention::LinearDaysLongFrontSplit
+            .accrued(&ndt(2025, 2, 18), &period, cf, false)
+            .unwrap();
+        assert!((result - 0.032113).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_long_front_stub_split() {
+        // long stub 15th Mar to 15th Nov with a quasi-coupon on 15th May
+        let period = AccrualPeriod::new(
+            ndt(2024, 3, 15),
+            ndt(2024, 11, 15),
+            6,
+            true,
+            RollDay::Int { day: 15 },
+        );
+        let first = 61.0 / 182.0; // 15th Mar to 15th May in the 15th Nov to 15th May period
+        let convention = AccrualConvention::LinearDaysLongFrontSplit;
+        let result = convention.fraction(&ndt(2024, 5, 15), &period).unwrap();
+        assert!((result - first / (first + 1.0)).abs() < 1e-12);
+        let result = convention.fraction(&ndt(2024, 11, 15), &period).unwrap();
+        assert!((result - 1.0).abs() < 1e-12);
+        let result = convention.fraction(&ndt(2024, 8, 15), &period).unwrap();
+        let expected = (first + 92.0 / 184.0) / (first + 1.0);
+        assert!((result - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_sgb_30e360_with_ex_div() {
+        // SGB 1060: 0.75% annual, 5 business days ex-dividend
+        let stk = get_calendar_by_name("stk").unwrap();
+        let period = regular(ndt(2023, 5, 12), ndt(2024, 5, 12), 12);
+        for (settlement, expected) in [
+            (ndt(2024, 5, 3), 0.73125),
+            (ndt(2024, 5, 6), -0.0125),
+            (ndt(2024, 5, 7), -0.0104),
+            (ndt(2024, 5, 8), -0.008333),
+            (ndt(2024, 5, 12), 0.0),
+        ] {
+            let ex_div = is_ex_div(&settlement, &period.end, 5, &stk, false);
+            let result = AccrualConvention::ThirtyE360
+                .accrued(&settlement, &period, 0.75, ex_div)
+                .unwrap();
+            assert!((result - expected).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_gilt_ex_div() {
+        let ldn = get_calendar_by_name("ldn").unwrap();
+        // 7 business days before 7th Dec 1999 is 26th Nov 1999
+        assert!(!is_ex_div(
+            &ndt(1999, 11, 26),
+            &ndt(1999, 12, 7),
+            7,
+            &ldn,
+            false
+        ));
+        assert!(is_ex_div(
+            &ndt(1999, 11, 27),
+            &ndt(1999, 12, 7),
+            7,
+            &ldn,
+            false
+        ));
+        assert!(is_ex_div(
+            &ndt(1999, 11, 26),
+            &ndt(1999, 12, 7),
+            7,
+            &ldn,
+            true
+        ));
+    }
+
+    #[test]
+    fn test_jgb_act365nl() {
+        // 29th Feb 2024 is not counted: 60 - 1 days
+        assert_eq!(days_no_leap(&ndt(2023, 12, 20), &ndt(2024, 3, 20)), 90);
+        assert_eq!(days_no_leap(&ndt(2024, 2, 29), &ndt(2024, 3, 1)), 1);
+        let period = regular(ndt(2023, 12, 20), ndt(2024, 6, 20), 6);
+        let result = AccrualConvention::Act365NL
+            .accrued(&ndt(2024, 3, 20), &period, 0.05, false)
+            .unwrap();
+        assert!((result - 0.1 * 90.0 / 365.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_jgb_simple_yield() {
+        let result = simple_yield(99.0, 0.5, &ndt(2023, 3, 20), &ndt(2028, 3, 20));
+        let years = 1825.0 / 365.0;
+        assert!((result - (0.5 + 1.0 / years) / 99.0 * 100.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_settlement_outside_period_raises() {
+        let period = regular(ndt(2022, 1, 1), ndt(2022, 7, 1), 6);
+        assert!(AccrualConvention::LinearDays
+            .fraction(&ndt(2022, 8, 1), &period)
+            .is_err());
+    }
+}
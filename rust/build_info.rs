@@ -0,0 +1,49 @@
+//! Introspection of the build of the library, for diagnostics and bug reports.
+//!
+//! The constants are generated by the build script from the environment of the compilation.
+
+use crate::models::implied_vol;
+use crate::solver::{DEFAULT_MAX_ITER, DEFAULT_TOLERANCE};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+include!(concat!(env!("OUT_DIR"), "/build_info.rs"));
+
+/// Return a description of the build of the library.
+///
+/// Returns
+/// -------
+/// dict
+///     The crate ``version``, the enabled cargo ``features``, the build ``profile``, the
+///     ``target`` triple, the ``rustc`` version and the compiled default ``tolerances``.
+#[pyfunction]
+#[pyo3(name = "build_info")]
+pub fn build_info_py(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+    let tolerances = PyDict::new_bound(py);
+    tolerances.set_item("solver_tol", DEFAULT_TOLERANCE)?;
+    tolerances.set_item("solver_max_iter", DEFAULT_MAX_ITER)?;
+    tolerances.set_item("implied_vol_tol", implied_vol::TOLERANCE)?;
+
+    let info = PyDict::new_bound(py);
+    info.set_item("version", VERSION)?;
+    info.set_item("features", FEATURES.to_vec())?;
+    info.set_item("profile", PROFILE)?;
+    info.set_item("target", TARGET)?;
+    info.set_item("rustc", RUSTC_VERSION)?;
+    info.set_item("tolerances", tolerances)?;
+    Ok(info)
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_constants() {
+        assert_eq!(VERSION, env!("CARGO_PKG_VERSION"));
+        assert!(FEATURES.contains(&"pyo3-chrono"));
+        assert!(!FEATURES.contains(&"default"));
+        assert!(RUSTC_VERSION.starts_with("rustc"));
+    }
+}
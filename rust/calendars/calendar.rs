@@ -1,13 +1,19 @@
 use chrono::prelude::*;
-use chrono::Weekday;
+use chrono::{Days, TimeDelta, Weekday};
 use indexmap::set::IndexSet;
+use indexmap::IndexMap;
 use pyo3::exceptions::PyValueError;
 use pyo3::{pyclass, FromPyObject, PyErr};
-use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::OnceLock;
 
-use crate::calendars::dateroll::DateRoll;
+use crate::calendars::dateroll::{
+    lag_bus_days, step_bus_day_count, step_bus_days, DateRoll, Modifier,
+};
 use crate::calendars::named::get_calendar_by_name;
+use crate::logging::event;
 
 /// Container for calendar types.
 #[derive(Debug, Clone, PartialEq, FromPyObject, Serialize, Deserialize)]
@@ -26,11 +32,18 @@ pub enum CalType {
 /// - `holidays`: which defines specific dates that may be exceptions to the general working week, and cannot be
 ///   business days.
 ///
+/// Optionally, `early_closes` defines partial business days, e.g. Christmas Eve, with the time at
+/// which business closes. These remain business days for all date rolling.
+///
 #[pyclass(module = "rateslib.rs")]
-#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Default, Debug, Serialize, Deserialize)]
 pub struct Cal {
+    #[serde(serialize_with = "serialize_sorted_holidays")]
     pub(crate) holidays: IndexSet<NaiveDateTime>,
+    #[serde(serialize_with = "serialize_sorted_week_mask")]
     pub(crate) week_mask: HashSet<Weekday>,
+    #[serde(default, serialize_with = "serialize_sorted_early_closes")]
+    pub(crate) early_closes: IndexMap<NaiveDateTime, NaiveTime>,
     // pub(crate) meta: Vec<String>,
 }
 
@@ -49,9 +62,64 @@ impl Cal {
             week_mask: HashSet::from_iter(
                 week_mask.into_iter().map(|v| Weekday::try_from(v).unwrap()),
             ),
+            early_closes: IndexMap::new(),
             // meta: rules.into_iter().map(|x| x.to_string()).collect(),
         }
     }
+
+    /// Return the calendar with partial business days closing at the given times.
+    ///
+    /// An early close on a date which is not a business day has no effect.
+    pub fn with_early_closes(mut self, early_closes: Vec<(NaiveDateTime, NaiveTime)>) -> Self {
+        self.early_closes.extend(early_closes);
+        self
+    }
+
+    /// Return the calendar with additional holidays.
+    pub fn with_holidays(mut self, holidays: Vec<NaiveDateTime>) -> Self {
+        self.add_holidays(&holidays);
+        self
+    }
+
+    /// Add holidays to the calendar, e.g. for an ad-hoc market closure.
+    pub fn add_holidays(&mut self, holidays: &[NaiveDateTime]) {
+        self.holidays.extend(holidays.iter().cloned());
+    }
+
+    /// Remove holidays from the calendar, ignoring any date which is not a holiday.
+    pub fn remove_holidays(&mut self, holidays: &[NaiveDateTime]) {
+        for date in holidays {
+            self.holidays.shift_remove(date);
+        }
+    }
+}
+
+// Cal equality does not depend on the order of its collections, so they serialize sorted.
+
+fn serialize_sorted_holidays<S: Serializer>(
+    holidays: &IndexSet<NaiveDateTime>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut holidays: Vec<&NaiveDateTime> = holidays.iter().collect();
+    holidays.sort();
+    holidays.serialize(serializer)
+}
+
+fn serialize_sorted_week_mask<S: Serializer>(
+    week_mask: &HashSet<Weekday>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut week_mask: Vec<&Weekday> = week_mask.iter().collect();
+    week_mask.sort_by_key(|d| d.num_days_from_monday());
+    week_mask.serialize(serializer)
+}
+
+fn serialize_sorted_early_closes<S: Serializer>(
+    early_closes: &IndexMap<NaiveDateTime, NaiveTime>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let early_closes: BTreeMap<&NaiveDateTime, &NaiveTime> = early_closes.iter().collect();
+    early_closes.serialize(serializer)
 }
 
 /// A business day calendar which is the potential union of multiple calendars,
@@ -67,11 +135,23 @@ impl Cal {
 /// A business day is defined as allowing settlement relative to an associated calendar if:
 ///
 /// - the date in question is also a business day in the associated settlement calendar.
+///
+/// Calendars may have mixed weekends, e.g. a Friday and Saturday weekend combined with a Saturday
+/// and Sunday weekend. Business days are then added on the week of each calendar separately,
+/// observing the holidays of the union, and the latest date (or earliest when subtracting) is
+/// rolled to a business day of the union, as for the spot date of an FX pair such as *USDILS*.
+/// Adding business days may start from a date which is a business day of the week of any
+/// calendar. When the weekend of every calendar contains, or is contained by, that of every other
+/// this is the same as adding business days of the union.
 #[pyclass(module = "rateslib.rs")]
 #[derive(Clone, Default, Debug, Serialize, Deserialize)]
 pub struct UnionCal {
     pub(crate) calendars: Vec<Cal>,
     pub(crate) settlement_calendars: Option<Vec<Cal>>,
+    #[serde(skip)]
+    flattened: OnceLock<(Cal, Option<Cal>)>,
+    #[serde(skip)]
+    weeks: OnceLock<Vec<Cal>>,
 }
 
 impl UnionCal {
@@ -79,7 +159,147 @@ impl UnionCal {
         UnionCal {
             calendars,
             settlement_calendars,
+            flattened: OnceLock::new(),
+            weeks: OnceLock::new(),
+        }
+    }
+
+    /// Return the business day and settlement calendars each flattened into a single `Cal`.
+    ///
+    /// These are lazily constructed on first query and cached thereafter.
+    fn flattened(&self) -> &(Cal, Option<Cal>) {
+        self.flattened.get_or_init(|| {
+            (
+                flatten_cals(&self.calendars),
+                self.settlement_calendars.as_ref().map(|v| flatten_cals(v)),
+            )
+        })
+    }
+
+    /// Return a calendar for the week of each business day calendar with the holidays of the
+    /// union, if the calendars have mixed weekends, or else an empty vector.
+    ///
+    /// These are lazily constructed on first query and cached thereafter.
+    fn weeks(&self) -> &Vec<Cal> {
+        self.weeks.get_or_init(|| {
+            let mut masks: Vec<&HashSet<Weekday>> = Vec::new();
+            for cal in self.calendars.iter() {
+                if !masks.contains(&&cal.week_mask) {
+                    masks.push(&cal.week_mask);
+                }
+            }
+            let nested = masks
+                .iter()
+                .all(|a| masks.iter().all(|b| a.is_subset(b) || b.is_subset(a)));
+            if nested {
+                return vec![];
+            }
+            let holidays = &self.flattened().0.holidays;
+            masks
+                .into_iter()
+                .map(|mask| Cal {
+                    holidays: holidays.clone(),
+                    week_mask: mask.clone(),
+                    early_closes: IndexMap::new(),
+                })
+                .collect()
+        })
+    }
+
+    /// Whether the business day calendars have mixed weekends, where neither of two weekends
+    /// contains the other.
+    pub fn has_mixed_weekends(&self) -> bool {
+        !self.weeks().is_empty()
+    }
+
+    /// Discard the cached flattened calendars.
+    ///
+    /// Must be called after any mutation of `calendars` or `settlement_calendars`.
+    pub fn invalidate_cache(&mut self) {
+        event!(debug, "union calendar cache invalidated");
+        self.flattened = OnceLock::new();
+        self.weeks = OnceLock::new();
+    }
+
+    /// Return the business day calendars, or the settlement calendars if `settlement`.
+    fn members_mut(&mut self, settlement: bool) -> Result<&mut Vec<Cal>, PyErr> {
+        let members = match settlement {
+            false => Some(&mut self.calendars),
+            true => self.settlement_calendars.as_mut(),
+        };
+        match members {
+            Some(cals) if !cals.is_empty() => Ok(cals),
+            _ => Err(PyValueError::new_err(
+                "The UnionCal has no calendars of the requested type to modify.",
+            )),
+        }
+    }
+
+    /// Return the calendar with additional holidays, see [UnionCal::add_holidays].
+    pub fn with_holidays(
+        mut self,
+        holidays: Vec<NaiveDateTime>,
+        settlement: bool,
+    ) -> Result<Self, PyErr> {
+        self.add_holidays(&holidays, settlement)?;
+        Ok(self)
+    }
+
+    /// Add holidays to the first business day calendar, or settlement calendar if `settlement`,
+    /// which makes them holidays of the union.
+    ///
+    /// # Errors
+    ///
+    /// If there are no calendars of the requested type.
+    pub fn add_holidays(
+        &mut self,
+        holidays: &[NaiveDateTime],
+        settlement: bool,
+    ) -> Result<(), PyErr> {
+        self.members_mut(settlement)?[0].add_holidays(holidays);
+        self.invalidate_cache();
+        Ok(())
+    }
+
+    /// Remove holidays from every business day calendar, or settlement calendar if
+    /// `settlement`, so that they are not holidays of the union.
+    ///
+    /// # Errors
+    ///
+    /// If there are no calendars of the requested type.
+    pub fn remove_holidays(
+        &mut self,
+        holidays: &[NaiveDateTime],
+        settlement: bool,
+    ) -> Result<(), PyErr> {
+        for cal in self.members_mut(settlement)?.iter_mut() {
+            cal.remove_holidays(holidays);
         }
+        self.invalidate_cache();
+        Ok(())
+    }
+}
+
+/// Combine multiple calendars into one whose holidays and week mask are the union of all.
+fn flatten_cals(cals: &[Cal]) -> Cal {
+    Cal {
+        holidays: cals
+            .iter()
+            .flat_map(|c| c.holidays.iter().cloned())
+            .collect(),
+        week_mask: cals
+            .iter()
+            .flat_map(|c| c.week_mask.iter().cloned())
+            .collect(),
+        early_closes: cals.iter().flat_map(|c| c.early_closes.iter()).fold(
+            IndexMap::new(),
+            |mut acc, (date, time)| {
+                acc.entry(*date)
+                    .and_modify(|t: &mut NaiveTime| *t = (*t).min(*time))
+                    .or_insert(*time);
+                acc
+            },
+        ),
     }
 }
 
@@ -124,20 +344,14 @@ impl NamedCal {
             let cals: Vec<Cal> = parse_cals(parts[0])?;
             Ok(Self {
                 name: name_,
-                union_cal: UnionCal {
-                    calendars: cals,
-                    settlement_calendars: None,
-                },
+                union_cal: UnionCal::new(cals, None),
             })
         } else {
             let cals: Vec<Cal> = parse_cals(parts[0])?;
             let settle_cals: Vec<Cal> = parse_cals(parts[1])?;
             Ok(Self {
                 name: name_,
-                union_cal: UnionCal {
-                    calendars: cals,
-                    settlement_calendars: Some(settle_cals),
-                },
+                union_cal: UnionCal::new(cals, Some(settle_cals)),
             })
         }
     }
@@ -151,6 +365,23 @@ fn parse_cals(name: &str) -> Result<Vec<Cal>, PyErr> {
     Ok(cals)
 }
 
+/// Adjust a batch of dates to business dates of a named calendar under a modification rule.
+///
+/// The calendar is parsed from `calendar_name`, as [NamedCal::try_new], once for the whole
+/// batch. Dates which are already business dates, and settleable if `settlement`, are unchanged.
+pub fn adjust_dates(
+    dates: &[NaiveDateTime],
+    modifier: &Modifier,
+    calendar_name: &str,
+    settlement: bool,
+) -> Result<Vec<NaiveDateTime>, PyErr> {
+    let cal = NamedCal::try_new(calendar_name)?;
+    Ok(dates
+        .iter()
+        .map(|d| cal.roll(d, modifier, settlement))
+        .collect())
+}
+
 impl DateRoll for Cal {
     fn is_weekday(&self, date: &NaiveDateTime) -> bool {
         !self.week_mask.contains(&date.weekday())
@@ -163,21 +394,128 @@ impl DateRoll for Cal {
     fn is_settlement(&self, _date: &NaiveDateTime) -> bool {
         true
     }
+
+    fn early_close(&self, date: &NaiveDateTime) -> Option<NaiveTime> {
+        self.early_closes.get(date).copied()
+    }
+
+    /// A `Cal` has no settlement calendar so `settlement` has no effect.
+    fn add_bus_days(
+        &self,
+        date: &NaiveDateTime,
+        days: i32,
+        _settlement: bool,
+    ) -> Result<NaiveDateTime, PyErr> {
+        if self.holidays.is_empty() && self.is_weekday(date) {
+            return Ok(self.add_weekdays(date, days));
+        }
+        step_bus_days(self, date, days)
+    }
+
+    fn bus_day_count(&self, start: &NaiveDateTime, end: &NaiveDateTime) -> i64 {
+        if self.holidays.is_empty() {
+            return self.count_weekdays(start, end);
+        }
+        step_bus_day_count(self, start, end)
+    }
+}
+
+impl Cal {
+    /// Add business days to a weekday of a calendar without holidays, stepping whole weeks at
+    /// once.
+    fn add_weekdays(&self, date: &NaiveDateTime, days: i32) -> NaiveDateTime {
+        let per_week = 7 - self.week_mask.len() as i32;
+        let mut new_date = *date + TimeDelta::days(7 * (days / per_week) as i64);
+        for _ in 0..(days % per_week).abs() {
+            new_date = match days > 0 {
+                true => self.roll_forward_bus_day(&(new_date + Days::new(1))),
+                false => self.roll_backward_bus_day(&(new_date - Days::new(1))),
+            };
+        }
+        new_date
+    }
+
+    /// Count the weekdays from `start`, inclusive, to `end`, exclusive, counting whole weeks at
+    /// once.
+    fn count_weekdays(&self, start: &NaiveDateTime, end: &NaiveDateTime) -> i64 {
+        let mut days = (*end - *start).num_days();
+        if *start + TimeDelta::days(days) < *end {
+            days += 1; // a part day
+        }
+        if days <= 0 {
+            return 0;
+        }
+        let per_week = 7 - self.week_mask.len() as i64;
+        let rest = *start + TimeDelta::days(7 * (days / 7));
+        let partial = (0..days % 7)
+            .filter(|i| self.is_weekday(&(rest + TimeDelta::days(*i))))
+            .count();
+        per_week * (days / 7) + partial as i64
+    }
 }
 
 impl DateRoll for UnionCal {
     fn is_weekday(&self, date: &NaiveDateTime) -> bool {
-        self.calendars.iter().all(|cal| cal.is_weekday(date))
+        self.flattened().0.is_weekday(date)
     }
 
     fn is_holiday(&self, date: &NaiveDateTime) -> bool {
-        self.calendars.iter().any(|cal| cal.is_holiday(date))
+        self.flattened().0.is_holiday(date)
     }
 
     fn is_settlement(&self, date: &NaiveDateTime) -> bool {
-        self.settlement_calendars
+        self.flattened()
+            .1
             .as_ref()
-            .map_or(true, |v| !v.iter().any(|cal| cal.is_non_bus_day(date)))
+            .map_or(true, |cal| cal.is_bus_day(date))
+    }
+
+    fn early_close(&self, date: &NaiveDateTime) -> Option<NaiveTime> {
+        self.flattened().0.early_close(date)
+    }
+
+    fn add_bus_days(
+        &self,
+        date: &NaiveDateTime,
+        days: i32,
+        settlement: bool,
+    ) -> Result<NaiveDateTime, PyErr> {
+        let weeks = self.weeks();
+        let new_date = if weeks.is_empty() {
+            // the flattened calendar has the fast path of a calendar without holidays
+            self.flattened().0.add_bus_days(date, days, false)?
+        } else {
+            if weeks.iter().all(|c| c.is_non_bus_day(date)) {
+                return Err(PyValueError::new_err(
+                    "Cannot add business days to an input `date` that is not a business day.",
+                ));
+            }
+            let dates = weeks.iter().map(|c| c.lag(date, days, false));
+            match days < 0 {
+                true => self.roll_backward_bus_day(&dates.min().unwrap()),
+                false => self.roll_forward_bus_day(&dates.max().unwrap()),
+            }
+        };
+        if !settlement {
+            Ok(new_date)
+        } else if days < 0 {
+            Ok(self.roll_backward_settled_bus_day(&new_date))
+        } else {
+            Ok(self.roll_forward_settled_bus_day(&new_date))
+        }
+    }
+
+    fn bus_day_count(&self, start: &NaiveDateTime, end: &NaiveDateTime) -> i64 {
+        self.flattened().0.bus_day_count(start, end)
+    }
+
+    /// With mixed weekends a date which is a business day of the week of any calendar is lagged
+    /// from itself, see [UnionCal].
+    fn lag(&self, date: &NaiveDateTime, days: i32, settlement: bool) -> NaiveDateTime {
+        if !self.is_bus_day(date) && self.weeks().iter().any(|c| c.is_bus_day(date)) {
+            return self.add_bus_days(date, days, settlement).unwrap();
+        }
+        lag_bus_days(self, date, days, settlement)
     }
 }
 
@@ -193,6 +531,27 @@ impl DateRoll for NamedCal {
     fn is_settlement(&self, date: &NaiveDateTime) -> bool {
         self.union_cal.is_settlement(date)
     }
+
+    fn early_close(&self, date: &NaiveDateTime) -> Option<NaiveTime> {
+        self.union_cal.early_close(date)
+    }
+
+    fn add_bus_days(
+        &self,
+        date: &NaiveDateTime,
+        days: i32,
+        settlement: bool,
+    ) -> Result<NaiveDateTime, PyErr> {
+        self.union_cal.add_bus_days(date, days, settlement)
+    }
+
+    fn bus_day_count(&self, start: &NaiveDateTime, end: &NaiveDateTime) -> i64 {
+        self.union_cal.bus_day_count(start, end)
+    }
+
+    fn lag(&self, date: &NaiveDateTime, days: i32, settlement: bool) -> NaiveDateTime {
+        self.union_cal.lag(date, days, settlement)
+    }
 }
 
 impl DateRoll for CalType {
@@ -219,56 +578,168 @@ impl DateRoll for CalType {
             CalType::NamedCal(c) => c.is_settlement(date),
         }
     }
+
+    fn early_close(&self, date: &NaiveDateTime) -> Option<NaiveTime> {
+        match self {
+            CalType::Cal(c) => c.early_close(date),
+            CalType::UnionCal(c) => c.early_close(date),
+            CalType::NamedCal(c) => c.early_close(date),
+        }
+    }
+
+    fn add_bus_days(
+        &self,
+        date: &NaiveDateTime,
+        days: i32,
+        settlement: bool,
+    ) -> Result<NaiveDateTime, PyErr> {
+        match self {
+            CalType::Cal(c) => c.add_bus_days(date, days, settlement),
+            CalType::UnionCal(c) => c.add_bus_days(date, days, settlement),
+            CalType::NamedCal(c) => c.add_bus_days(date, days, settlement),
+        }
+    }
+
+    fn bus_day_count(&self, start: &NaiveDateTime, end: &NaiveDateTime) -> i64 {
+        match self {
+            CalType::Cal(c) => c.bus_day_count(start, end),
+            CalType::UnionCal(c) => c.bus_day_count(start, end),
+            CalType::NamedCal(c) => c.bus_day_count(start, end),
+        }
+    }
+
+    fn lag(&self, date: &NaiveDateTime, days: i32, settlement: bool) -> NaiveDateTime {
+        match self {
+            CalType::Cal(c) => c.lag(date, days, settlement),
+            CalType::UnionCal(c) => c.lag(date, days, settlement),
+            CalType::NamedCal(c) => c.lag(date, days, settlement),
+        }
+    }
 }
 
-impl<T> PartialEq<T> for UnionCal
-where
-    T: DateRoll,
-{
-    fn eq(&self, other: &T) -> bool {
-        let cd1 = self
-            .cal_date_range(&ndt(1970, 1, 1), &ndt(2200, 12, 31))
-            .unwrap();
-        let cd2 = other
-            .cal_date_range(&ndt(1970, 1, 1), &ndt(2200, 12, 31))
-            .unwrap();
-        cd1.iter().zip(cd2.iter()).all(|(x, y)| {
-            self.is_bus_day(x) == other.is_bus_day(x)
-                && self.is_settlement(x) == other.is_settlement(y)
-        })
+/// The effective business day structure of a calendar, used for equality comparisons.
+///
+/// Holidays are intersected with the weekdays of the week mask, since holidays falling on
+/// non-working days have no behavioural effect. A settlement calendar without any non-business
+/// days is equivalent to having no settlement calendar. Likewise only early closes on business
+/// days are compared.
+#[derive(PartialEq)]
+struct CalStructure {
+    week_mask: HashSet<Weekday>,
+    holidays: HashSet<NaiveDateTime>,
+    early_closes: HashMap<NaiveDateTime, NaiveTime>,
+    settlement: Option<(HashSet<Weekday>, HashSet<NaiveDateTime>)>,
+}
+
+/// Hash the structure consistently with its equality, by hashing each set in sorted order.
+impl Hash for CalStructure {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        fn sorted_mask(mask: &HashSet<Weekday>) -> Vec<u32> {
+            let mut days: Vec<u32> = mask.iter().map(|d| d.num_days_from_monday()).collect();
+            days.sort();
+            days
+        }
+        fn sorted<T: Ord + Clone>(items: impl Iterator<Item = T>) -> Vec<T> {
+            let mut items: Vec<T> = items.collect();
+            items.sort();
+            items
+        }
+        sorted_mask(&self.week_mask).hash(state);
+        sorted(self.holidays.iter().cloned()).hash(state);
+        sorted(self.early_closes.iter().map(|(d, t)| (*d, *t))).hash(state);
+        self.settlement
+            .as_ref()
+            .map(|(w, h)| (sorted_mask(w), sorted(h.iter().cloned())))
+            .hash(state);
     }
 }
 
-impl<T> PartialEq<T> for NamedCal
-where
-    T: DateRoll,
-{
-    fn eq(&self, other: &T) -> bool {
-        self.union_cal.eq(other)
+fn effective_holidays(cal: &Cal) -> HashSet<NaiveDateTime> {
+    cal.holidays
+        .iter()
+        .filter(|d| cal.is_weekday(d))
+        .cloned()
+        .collect()
+}
+
+fn effective_early_closes(cal: &Cal) -> HashMap<NaiveDateTime, NaiveTime> {
+    cal.early_closes
+        .iter()
+        .filter(|(d, _)| cal.is_bus_day(d))
+        .map(|(d, t)| (*d, *t))
+        .collect()
+}
+
+impl Cal {
+    fn structure(&self) -> CalStructure {
+        CalStructure {
+            week_mask: self.week_mask.clone(),
+            holidays: effective_holidays(self),
+            early_closes: effective_early_closes(self),
+            settlement: None,
+        }
     }
 }
 
-impl PartialEq<UnionCal> for Cal {
-    fn eq(&self, other: &UnionCal) -> bool {
-        let cd1 = self
-            .cal_date_range(&ndt(1970, 1, 1), &ndt(2200, 12, 31))
-            .unwrap();
-        let cd2 = other
-            .cal_date_range(&ndt(1970, 1, 1), &ndt(2200, 12, 31))
-            .unwrap();
-        cd1.iter().zip(cd2.iter()).all(|(x, y)| {
-            self.is_bus_day(x) == other.is_bus_day(x)
-                && self.is_settlement(x) == other.is_settlement(y)
-        })
+impl UnionCal {
+    fn structure(&self) -> CalStructure {
+        let (cal, settle) = self.flattened();
+        CalStructure {
+            week_mask: cal.week_mask.clone(),
+            holidays: effective_holidays(cal),
+            early_closes: effective_early_closes(cal),
+            settlement: settle
+                .as_ref()
+                .map(|c| (c.week_mask.clone(), effective_holidays(c)))
+                .filter(|(w, h)| !(w.is_empty() && h.is_empty())),
+        }
     }
 }
 
-impl PartialEq<NamedCal> for Cal {
-    fn eq(&self, other: &NamedCal) -> bool {
-        other.union_cal.eq(self)
+impl NamedCal {
+    fn structure(&self) -> CalStructure {
+        self.union_cal.structure()
     }
 }
 
+macro_rules! impl_structural_eq {
+    ($lhs: ident, $rhs: ident) => {
+        impl PartialEq<$rhs> for $lhs {
+            fn eq(&self, other: &$rhs) -> bool {
+                self.structure() == other.structure()
+            }
+        }
+    };
+}
+
+impl_structural_eq!(Cal, Cal);
+impl_structural_eq!(Cal, UnionCal);
+impl_structural_eq!(Cal, NamedCal);
+impl_structural_eq!(UnionCal, Cal);
+impl_structural_eq!(UnionCal, UnionCal);
+impl_structural_eq!(UnionCal, NamedCal);
+impl_structural_eq!(NamedCal, Cal);
+impl_structural_eq!(NamedCal, UnionCal);
+impl_structural_eq!(NamedCal, NamedCal);
+
+macro_rules! impl_structural_hash {
+    ($cal: ident) => {
+        impl Eq for $cal {}
+
+        /// Hash the calendar consistently with its equality, so that calendars with the same
+        /// effective business days hash equally regardless of their type.
+        impl Hash for $cal {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.structure().hash(state)
+            }
+        }
+    };
+}
+
+impl_structural_hash!(Cal);
+impl_structural_hash!(UnionCal);
+impl_structural_hash!(NamedCal);
+
 /// Create a `NaiveDateTime` with default null time.
 ///
 /// Panics if date values are invalid.
@@ -343,6 +814,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_union_cal_mixed_weekends() {
+        // a Friday and Saturday weekend with a Saturday and Sunday weekend, from Wednesday 5th
+        // June 2024
+        let usd = Cal::new(vec![], vec![5, 6]);
+        let ils = Cal::new(vec![], vec![4, 5]);
+        let ucal = UnionCal::new(vec![usd.clone(), ils.clone()], None);
+        assert!(ucal.has_mixed_weekends());
+        // each currency counts its own week and spot is a business day of both
+        assert_eq!(
+            ucal.add_bus_days(&ndt(2024, 6, 5), 2, false).unwrap(),
+            ndt(2024, 6, 10)
+        );
+        assert_eq!(
+            ucal.add_bus_days(&ndt(2024, 6, 6), 2, false).unwrap(),
+            ndt(2024, 6, 10)
+        );
+        assert_eq!(ucal.lag(&ndt(2024, 6, 7), 2, false), ndt(2024, 6, 11));
+        assert_eq!(ucal.lag(&ndt(2024, 6, 8), 2, false), ndt(2024, 6, 11));
+        assert!(ucal.add_bus_days(&ndt(2024, 6, 8), 2, false).is_err());
+        assert_eq!(
+            ucal.add_bus_days(&ndt(2024, 6, 10), -1, false).unwrap(),
+            ndt(2024, 6, 6)
+        );
+
+        // a holiday of either currency is observed by both
+        let ucal = UnionCal::new(vec![usd.clone(), ils], None)
+            .with_holidays(vec![ndt(2024, 6, 10)], false)
+            .unwrap();
+        assert_eq!(
+            ucal.add_bus_days(&ndt(2024, 6, 6), 2, false).unwrap(),
+            ndt(2024, 6, 11)
+        );
+
+        // nested weekends are the union of business days
+        let ucal = UnionCal::new(vec![usd, Cal::new(vec![], vec![])], None);
+        assert!(!ucal.has_mixed_weekends());
+        assert_eq!(
+            ucal.add_bus_days(&ndt(2024, 6, 6), 2, false).unwrap(),
+            ndt(2024, 6, 10)
+        );
+    }
+
     #[test]
     fn test_union_cal_with_settle() {
         let hols = vec![
@@ -392,6 +906,30 @@ mod tests {
         assert!(ncal.is_err());
     }
 
+    #[test]
+    fn test_adjust_dates() {
+        let dates = vec![
+            ndt(1970, 5, 1),
+            ndt(1970, 5, 2),
+            ndt(1970, 5, 5),
+            ndt(1970, 5, 30),
+        ];
+        let result = adjust_dates(&dates, &Modifier::ModF, "tgt,nyc|ldn", false).unwrap();
+        assert_eq!(
+            result,
+            vec![
+                ndt(1970, 5, 4),
+                ndt(1970, 5, 4),
+                ndt(1970, 5, 5),
+                ndt(1970, 5, 29)
+            ]
+        );
+        // Monday 4th May 1970 is a London holiday
+        let result = adjust_dates(&dates[..1], &Modifier::F, "tgt,nyc|ldn", true).unwrap();
+        assert_eq!(result, vec![ndt(1970, 5, 5)]);
+        assert!(adjust_dates(&dates, &Modifier::F, "bad_cal", false).is_err());
+    }
+
     #[test]
     fn test_docstring() {
         let ldn = Cal::new(vec![ndt(2017, 5, 1)], vec![5, 6]); // UK Monday 1st May Bank Holiday
@@ -439,4 +977,181 @@ mod tests {
         assert_ne!(cal2, ucal);
         assert_ne!(ucal, cal2);
     }
+
+    #[test]
+    fn test_union_cal_cache_invalidation() {
+        let mut ucal = UnionCal::new(vec![fixture_hol_cal()], None);
+        assert!(ucal.is_holiday(&ndt(2015, 9, 7)));
+        ucal.calendars = vec![Cal::new(vec![], vec![5, 6])];
+        ucal.invalidate_cache();
+        assert!(!ucal.is_holiday(&ndt(2015, 9, 7)));
+    }
+
+    #[test]
+    fn test_holiday_mutation() {
+        let closure = ndt(2015, 9, 10);
+        let mut cal = fixture_hol_cal().with_holidays(vec![closure]);
+        assert!(cal.is_holiday(&closure));
+        cal.remove_holidays(&[closure, ndt(2015, 9, 7)]);
+        assert!(!cal.is_holiday(&closure) && !cal.is_holiday(&ndt(2015, 9, 7)));
+        cal.add_holidays(&[closure]);
+        assert!(cal.is_holiday(&closure));
+
+        let mut ucal = UnionCal::new(vec![fixture_hol_cal(), fixture_hol_cal2()], None);
+        assert!(ucal.is_bus_day(&closure));
+        ucal.add_holidays(&[closure], false).unwrap();
+        assert!(ucal.is_non_bus_day(&closure));
+        // a holiday of several calendars is removed from each
+        let mut ucal = ucal.with_holidays(vec![closure], false).unwrap();
+        ucal.calendars[1].add_holidays(&[closure]);
+        ucal.invalidate_cache();
+        ucal.remove_holidays(&[closure], false).unwrap();
+        assert!(ucal.is_bus_day(&closure));
+        assert!(ucal.add_holidays(&[closure], true).is_err());
+    }
+
+    #[test]
+    fn test_weekend_only_fast_path() {
+        let start = ndt(2023, 12, 1);
+        for mask in [vec![5, 6], vec![4, 5], vec![6], vec![]] {
+            let cal = Cal::new(vec![], mask);
+            for offset in 0..14_u64 {
+                let date = start + Days::new(offset);
+                for end in [date, date + Days::new(1), date + Days::new(33)] {
+                    assert_eq!(
+                        cal.bus_day_count(&date, &end),
+                        step_bus_day_count(&cal, &date, &end)
+                    );
+                }
+                assert_eq!(cal.bus_day_count(&date, &start), 0);
+                if cal.is_non_bus_day(&date) {
+                    assert!(cal.add_bus_days(&date, 1, false).is_err());
+                    continue;
+                }
+                for days in [-23, -7, -5, -1, 0, 1, 4, 5, 6, 12, 40] {
+                    assert_eq!(
+                        cal.add_bus_days(&date, days, false).unwrap(),
+                        step_bus_days(&cal, &date, days).unwrap()
+                    );
+                }
+            }
+        }
+        let ucal = UnionCal::new(vec![Cal::new(vec![], vec![5, 6])], None);
+        assert_eq!(
+            ucal.add_bus_days(&ndt(2024, 1, 5), 1, false).unwrap(),
+            ndt(2024, 1, 8)
+        );
+        assert_eq!(ucal.bus_day_count(&ndt(2024, 1, 1), &ndt(2024, 2, 1)), 23);
+    }
+
+    #[test]
+    fn test_union_cal_flattened_matches_iterated() {
+        let names = ["tgt", "ldn", "nyc", "tyo"];
+        let cals: Vec<Cal> = names
+            .iter()
+            .map(|n| get_calendar_by_name(n).unwrap())
+            .collect();
+        let ucal = UnionCal::new(cals.clone(), None);
+        let dates = ucal
+            .cal_date_range(&ndt(2000, 1, 1), &ndt(2049, 12, 31))
+            .unwrap();
+        for d in dates.iter() {
+            let iterated =
+                cals.iter().all(|c| c.is_weekday(d)) && !cals.iter().any(|c| c.is_holiday(d));
+            assert_eq!(iterated, ucal.is_bus_day(d));
+        }
+    }
+
+    #[test]
+    fn test_structural_equality_ignores_weekend_holidays() {
+        let cal = fixture_hol_cal(); // contains a Saturday holiday
+        let cal2 = Cal::new(vec![ndt(2015, 9, 7)], vec![5, 6]);
+        assert_eq!(cal, cal2);
+        let ucal = UnionCal::new(vec![cal2.clone()], vec![Cal::new(vec![], vec![])].into());
+        assert_eq!(cal, ucal);
+        assert_eq!(ucal, UnionCal::new(vec![cal2], None));
+    }
+
+    #[test]
+    fn test_named_cal_equality() {
+        let named = NamedCal::try_new("tgt,ldn").unwrap();
+        let ucal = UnionCal::new(
+            vec![
+                get_calendar_by_name("ldn").unwrap(),
+                get_calendar_by_name("tgt").unwrap(),
+            ],
+            None,
+        );
+        assert_eq!(named, ucal);
+        assert_ne!(named, NamedCal::try_new("tgt,ldn|fed").unwrap());
+    }
+
+    #[test]
+    fn test_structural_hash() {
+        fn hash<T: Hash>(cal: &T) -> u64 {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            cal.hash(&mut hasher);
+            hasher.finish()
+        }
+        let named = NamedCal::try_new("tgt,ldn").unwrap();
+        let ucal = UnionCal::new(
+            vec![
+                get_calendar_by_name("ldn").unwrap(),
+                get_calendar_by_name("tgt").unwrap(),
+            ],
+            None,
+        );
+        assert_eq!(hash(&named), hash(&ucal));
+        assert_ne!(
+            hash(&named),
+            hash(&NamedCal::try_new("tgt,ldn|fed").unwrap())
+        );
+        // equal calendars hash equally despite a weekend holiday
+        assert_eq!(
+            hash(&fixture_hol_cal()),
+            hash(&Cal::new(vec![ndt(2015, 9, 7)], vec![6, 5]))
+        );
+
+        let keys: HashSet<Cal> = HashSet::from_iter(vec![
+            fixture_hol_cal(),
+            Cal::new(vec![ndt(2015, 9, 7)], vec![5, 6]),
+            fixture_hol_cal2(),
+        ]);
+        assert_eq!(keys.len(), 2);
+    }
+
+    #[test]
+    fn test_equivalent_between() {
+        let cal = fixture_hol_cal();
+        let cal2 = fixture_hol_cal2();
+        assert!(!cal.equivalent_between(&cal2, &ndt(2015, 1, 1), &ndt(2016, 12, 31)));
+        assert!(cal.equivalent_between(&cal2, &ndt(2017, 1, 1), &ndt(2017, 12, 31)));
+    }
+
+    #[test]
+    fn test_early_closes() {
+        let two_pm = NaiveTime::from_hms_opt(14, 0, 0).unwrap();
+        let noon = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+        let cal1 = Cal::new(vec![ndt(2024, 12, 25)], vec![5, 6]).with_early_closes(vec![
+            (ndt(2024, 12, 24), two_pm),
+            (ndt(2024, 12, 28), two_pm), // Saturday
+        ]);
+        assert!(!cal1.is_full_bus_day(&ndt(2024, 12, 24)));
+        assert!(cal1.is_bus_day(&ndt(2024, 12, 24)));
+        assert_eq!(cal1.close_time(&ndt(2024, 12, 24)), Some(two_pm));
+        assert_eq!(cal1.close_time(&ndt(2024, 12, 28)), None);
+        assert_eq!(cal1.close_time(&ndt(2024, 12, 27)), None);
+        assert!(cal1.is_full_bus_day(&ndt(2024, 12, 27)));
+
+        // the union observes the earliest close
+        let cal2 = Cal::new(vec![], vec![5, 6]).with_early_closes(vec![(ndt(2024, 12, 24), noon)]);
+        let ucal = UnionCal::new(vec![cal1.clone(), cal2], None);
+        assert_eq!(ucal.close_time(&ndt(2024, 12, 24)), Some(noon));
+
+        // early closes on non-business days have no effect on equality
+        let cal3 = Cal::new(vec![ndt(2024, 12, 25)], vec![5, 6])
+            .with_early_closes(vec![(ndt(2024, 12, 24), two_pm)]);
+        assert_eq!(cal1, cal3);
+        assert_ne!(cal3, Cal::new(vec![ndt(2024, 12, 25)], vec![5, 6]));
+    }
 }
@@ -1,16 +1,28 @@
 //! Wrapper module to export to Python using pyo3 bindings.
 
 use crate::calendars::named::get_calendar_by_name;
-use crate::calendars::{Cal, CalType, Convention, DateRoll, Modifier, NamedCal, RollDay, UnionCal};
+use crate::calendars::{
+    adjust_dates, easter, fixing_dates, good_friday, lockout, next_imm, parse_contract_code,
+    previous_imm, Bus252Accrual, Cal, CalType, Convention, DateRoll, FuturesExpiry, Modifier,
+    NamedCal, RollDay, UnionCal,
+};
+use crate::dates::dates_py::{
+    datetime64_array, extract_date, extract_dates, extract_optional_date,
+};
 use crate::json::json_py::DeserializedObj;
 use crate::json::JSON;
 use bincode::{deserialize, serialize};
-use chrono::NaiveDateTime;
+use chrono::{NaiveDateTime, NaiveTime};
 use indexmap::set::IndexSet;
+use numpy::datetime::{units::Days, Datetime};
+use numpy::PyArray1;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::types::PyBytes;
+use pyo3::types::{PyBytes, PyString};
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 
 impl IntoPy<PyObject> for CalType {
     fn into_py(self, py: Python<'_>) -> PyObject {
@@ -97,6 +109,22 @@ impl Modifier {
     pub fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
         Ok(PyBytes::new_bound(py, &serialize(&self).unwrap()))
     }
+    /// Parse a modifier from a string.
+    ///
+    /// Parameters
+    /// ----------
+    /// value: str
+    ///     One of *"F"*, *"MF"*, *"P"*, *"MP"* or *"NONE"*, case insensitive.
+    ///
+    /// Returns
+    /// -------
+    /// Modifier
+    #[staticmethod]
+    #[pyo3(name = "from_str")]
+    fn from_str_py(value: &str) -> PyResult<Modifier> {
+        Modifier::from_str(value)
+    }
+
     pub fn __getnewargs__<'py>(&self) -> PyResult<(u8,)> {
         match self {
             Modifier::Act => Ok((0_u8,)),
@@ -110,12 +138,196 @@ impl Modifier {
 
 #[pyfunction]
 pub(crate) fn _get_modifier_str(modifier: Modifier) -> String {
-    match modifier {
-        Modifier::F => "F".to_string(),
-        Modifier::ModF => "MF".to_string(),
-        Modifier::P => "P".to_string(),
-        Modifier::ModP => "MP".to_string(),
-        Modifier::Act => "NONE".to_string(),
+    modifier.to_string()
+}
+
+#[pymethods]
+impl RollDay {
+    /// Parse a roll day from a string.
+    ///
+    /// Parameters
+    /// ----------
+    /// value: str
+    ///     One of *"eom"*, *"som"*, *"imm"*, case insensitive, or a day of the month.
+    ///
+    /// Returns
+    /// -------
+    /// RollDay
+    #[staticmethod]
+    #[pyo3(name = "from_str")]
+    fn from_str_py(value: &str) -> PyResult<RollDay> {
+        RollDay::from_str(value)
+    }
+}
+
+/// Extract a modifier given as a `Modifier` or as a string, see [Modifier::from_str].
+pub(crate) fn extract_modifier(obj: &Bound<'_, PyAny>) -> PyResult<Modifier> {
+    match obj.downcast::<PyString>() {
+        Ok(s) => Modifier::from_str(&s.to_cow()?),
+        Err(_) => obj.extract::<Modifier>(),
+    }
+}
+
+/// Extract an optional modifier, see [extract_modifier], where `None` is no modifier.
+pub(crate) fn extract_optional_modifier(obj: &Bound<'_, PyAny>) -> PyResult<Option<Modifier>> {
+    if obj.is_none() {
+        Ok(None)
+    } else {
+        extract_modifier(obj).map(Some)
+    }
+}
+
+/// Extract a roll day given as a `RollDay` or as a string, see [RollDay::from_str].
+pub(crate) fn extract_roll_day(obj: &Bound<'_, PyAny>) -> PyResult<RollDay> {
+    match obj.downcast::<PyString>() {
+        Ok(s) => RollDay::from_str(&s.to_cow()?),
+        Err(_) => obj.extract::<RollDay>(),
+    }
+}
+
+/// Extract an optional roll day, see [extract_roll_day], where `None` is no roll day.
+pub(crate) fn extract_optional_roll_day(obj: &Bound<'_, PyAny>) -> PyResult<Option<RollDay>> {
+    if obj.is_none() {
+        Ok(None)
+    } else {
+        extract_roll_day(obj).map(Some)
+    }
+}
+
+#[pymethods]
+impl FuturesExpiry {
+    // Pickling
+    #[new]
+    fn new_py(item: u8) -> PyResult<FuturesExpiry> {
+        match item {
+            0_u8 => Ok(FuturesExpiry::Sofr3M),
+            1_u8 => Ok(FuturesExpiry::Sofr1M),
+            2_u8 => Ok(FuturesExpiry::Sonia3M),
+            3_u8 => Ok(FuturesExpiry::Sonia1M),
+            4_u8 => Ok(FuturesExpiry::Euribor3M),
+            _ => Err(PyValueError::new_err(
+                "unreachable code on FuturesExpiry pickle.",
+            )),
+        }
+    }
+    pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
+        *self = deserialize(state.as_bytes()).unwrap();
+        Ok(())
+    }
+    pub fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new_bound(py, &serialize(&self).unwrap()))
+    }
+    pub fn __getnewargs__(&self) -> PyResult<(u8,)> {
+        match self {
+            FuturesExpiry::Sofr3M => Ok((0_u8,)),
+            FuturesExpiry::Sofr1M => Ok((1_u8,)),
+            FuturesExpiry::Sonia3M => Ok((2_u8,)),
+            FuturesExpiry::Sonia1M => Ok((3_u8,)),
+            FuturesExpiry::Euribor3M => Ok((4_u8,)),
+        }
+    }
+
+    /// Return the reference period of a futures contract.
+    ///
+    /// Parameters
+    /// ----------
+    /// code: str
+    ///     The contract code, e.g. *"H25"*, as month code letter followed by a 2 or 4 digit year.
+    ///
+    /// Returns
+    /// -------
+    /// tuple of datetime
+    #[pyo3(name = "reference_period")]
+    fn reference_period_py(&self, code: &str) -> PyResult<(NaiveDateTime, NaiveDateTime)> {
+        let (year, month) = parse_contract_code(code)?;
+        Ok(self.reference_period(year, month))
+    }
+
+    /// Return the last trading date of a futures contract.
+    ///
+    /// Parameters
+    /// ----------
+    /// code: str
+    ///     The contract code, e.g. *"H25"*, as month code letter followed by a 2 or 4 digit year.
+    /// calendar: Cal, UnionCal or NamedCal
+    ///     The business day calendar of the exchange.
+    ///
+    /// Returns
+    /// -------
+    /// datetime
+    #[pyo3(name = "expiry")]
+    fn expiry_py(&self, code: &str, calendar: CalType) -> PyResult<NaiveDateTime> {
+        let (year, month) = parse_contract_code(code)?;
+        Ok(self.expiry(year, month, &calendar))
+    }
+}
+
+#[pymethods]
+impl Bus252Accrual {
+    #[new]
+    fn new_py(calendar: CalType) -> Self {
+        Bus252Accrual::new(calendar)
+    }
+
+    /// The business day calendar used to count days.
+    #[getter]
+    fn calendar(&self) -> CalType {
+        self.calendar.clone()
+    }
+
+    /// The number of accrual periods stored in the cache.
+    #[getter]
+    #[pyo3(name = "cache_len")]
+    fn cache_len_py(&self) -> usize {
+        self.cache_len()
+    }
+
+    /// Return the number of business days accrued in a period.
+    ///
+    /// Parameters
+    /// ----------
+    /// start: datetime
+    ///     The start date of the period, included if a business day.
+    /// end: datetime
+    ///     The end date of the period, excluded if a business day.
+    ///
+    /// Returns
+    /// -------
+    /// int
+    #[pyo3(name = "bus_days")]
+    fn bus_days_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] start: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] end: NaiveDateTime,
+    ) -> PyResult<i64> {
+        self.bus_days(&start, &end)
+    }
+
+    /// Return the *Bus252* day count fraction of a period.
+    ///
+    /// Parameters
+    /// ----------
+    /// start: datetime
+    ///     The start date of the period, included if a business day.
+    /// end: datetime
+    ///     The end date of the period, excluded if a business day.
+    ///
+    /// Returns
+    /// -------
+    /// float
+    #[pyo3(name = "dcf")]
+    fn dcf_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] start: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] end: NaiveDateTime,
+    ) -> PyResult<f64> {
+        self.dcf(&start, &end)
+    }
+
+    /// Remove all accrual periods stored in the cache.
+    #[pyo3(name = "clear_cache")]
+    fn clear_cache_py(&self) {
+        self.clear_cache()
     }
 }
 
@@ -129,9 +341,138 @@ impl Cal {
     ///     List of datetimes as the specific holiday days.
     /// week_mask: list[int],
     ///     List of integers defining the weekends, [5, 6] for Saturday and Sunday.
+    /// early_closes: list[tuple[datetime, time]], optional
+    ///     List of partial business days and the time at which business closes on each.
     #[new]
-    fn new_py(holidays: Vec<NaiveDateTime>, week_mask: Vec<u8>) -> PyResult<Self> {
-        Ok(Cal::new(holidays, week_mask))
+    #[pyo3(signature = (holidays, week_mask, early_closes=None))]
+    fn new_py(
+        #[pyo3(from_py_with = "extract_dates")] holidays: Vec<NaiveDateTime>,
+        week_mask: Vec<u8>,
+        early_closes: Option<Vec<(NaiveDateTime, NaiveTime)>>,
+    ) -> PyResult<Self> {
+        Ok(Cal::new(holidays, week_mask).with_early_closes(early_closes.unwrap_or_default()))
+    }
+
+    /// Create a new *Cal* object from holidays listed one date per line.
+    ///
+    /// Parameters
+    /// ----------
+    /// source: str
+    ///     The path to a file, or its content. Each line is an ISO 8601 date, e.g. "2024-12-25"
+    ///     or "20241225", optionally followed by a comma, semicolon, tab or space separated label.
+    /// week_mask: list[int],
+    ///     List of integers defining the weekends, [5, 6] for Saturday and Sunday.
+    ///
+    /// Returns
+    /// -------
+    /// Cal
+    #[staticmethod]
+    #[pyo3(name = "from_csv")]
+    fn from_csv_py(source: &str, week_mask: Vec<u8>) -> PyResult<Self> {
+        Cal::from_csv(source, week_mask)
+    }
+
+    /// Create a new *Cal* object from the events of an iCalendar file.
+    ///
+    /// Parameters
+    /// ----------
+    /// source: str
+    ///     The path to a file, or its content. Every date spanned by a VEVENT is a holiday.
+    /// week_mask: list[int],
+    ///     List of integers defining the weekends, [5, 6] for Saturday and Sunday.
+    ///
+    /// Returns
+    /// -------
+    /// Cal
+    #[staticmethod]
+    #[pyo3(name = "from_ics")]
+    fn from_ics_py(source: &str, week_mask: Vec<u8>) -> PyResult<Self> {
+        Cal::from_ics(source, week_mask)
+    }
+
+    /// Return the holidays of the calendar, one ISO 8601 date per line.
+    ///
+    /// Returns
+    /// -------
+    /// str
+    ///
+    /// Notes
+    /// -----
+    /// The output can be loaded with :meth:`Cal.from_csv`. The ``week_mask`` is not included.
+    #[pyo3(name = "to_csv")]
+    fn to_csv_py(&self) -> String {
+        self.to_csv()
+    }
+
+    /// Return a listing of the week mask and the holidays, with weekday names, in a range of years.
+    ///
+    /// Parameters
+    /// ----------
+    /// start_year: int
+    ///     The first year to list.
+    /// end_year: int, optional
+    ///     The last year to list, inclusive. If not given only the ``start_year`` is listed.
+    ///
+    /// Returns
+    /// -------
+    /// str
+    #[pyo3(name = "describe", signature = (start_year, end_year=None))]
+    fn describe_py(&self, start_year: i32, end_year: Option<i32>) -> String {
+        self.describe(start_year, end_year.unwrap_or(start_year))
+    }
+
+    /// Return a new *Cal* with additional holidays.
+    ///
+    /// Parameters
+    /// ----------
+    /// holidays: list[datetime]
+    ///     The dates to add as holidays.
+    ///
+    /// Returns
+    /// -------
+    /// Cal
+    #[pyo3(name = "with_holidays")]
+    fn with_holidays_py(
+        &self,
+        #[pyo3(from_py_with = "extract_dates")] holidays: Vec<NaiveDateTime>,
+    ) -> Self {
+        self.clone().with_holidays(holidays)
+    }
+
+    /// Add holidays to the calendar, e.g. for an ad-hoc market closure.
+    ///
+    /// Parameters
+    /// ----------
+    /// holidays: list[datetime]
+    ///     The dates to add as holidays.
+    ///
+    /// Returns
+    /// -------
+    /// None
+    #[pyo3(name = "add_holidays")]
+    fn add_holidays_py(
+        &mut self,
+        #[pyo3(from_py_with = "extract_dates")] holidays: Vec<NaiveDateTime>,
+    ) {
+        self.add_holidays(&holidays)
+    }
+
+    /// Remove holidays from the calendar.
+    ///
+    /// Parameters
+    /// ----------
+    /// holidays: list[datetime]
+    ///     The dates to remove. Any date which is not a holiday is ignored.
+    ///
+    /// Returns
+    /// -------
+    /// None
+    #[pyo3(name = "remove_holidays")]
+    fn remove_holidays_py(
+        &mut self,
+        #[pyo3(from_py_with = "extract_dates")] holidays: Vec<NaiveDateTime>,
+    ) {
+        self.remove_holidays(&holidays)
     }
 
     #[getter]
@@ -149,6 +490,14 @@ impl Cal {
         ))
     }
 
+    #[getter]
+    fn early_closes(&self) -> Vec<(NaiveDateTime, NaiveTime)> {
+        let mut early_closes: Vec<(NaiveDateTime, NaiveTime)> =
+            self.early_closes.iter().map(|(d, t)| (*d, *t)).collect();
+        early_closes.sort();
+        early_closes
+    }
+
     // #[getter]
     // fn rules(&self) -> PyResult<String> {
     //     Ok(self.meta.join(",\n"))
@@ -165,7 +514,7 @@ impl Cal {
     /// -------
     /// bool
     #[pyo3(name = "is_bus_day")]
-    fn is_bus_day_py(&self, date: NaiveDateTime) -> bool {
+    fn is_bus_day_py(&self, #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime) -> bool {
         self.is_bus_day(&date)
     }
 
@@ -180,10 +529,53 @@ impl Cal {
     /// -------
     /// bool
     #[pyo3(name = "is_non_bus_day")]
-    fn is_non_bus_day_py(&self, date: NaiveDateTime) -> bool {
+    fn is_non_bus_day_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+    ) -> bool {
         self.is_non_bus_day(&date)
     }
 
+    /// Return whether the `date` is a business day without an early close.
+    ///
+    /// Parameters
+    /// ----------
+    /// date: datetime
+    ///     Date to test
+    ///
+    /// Returns
+    /// -------
+    /// bool
+    #[pyo3(name = "is_full_bus_day")]
+    fn is_full_bus_day_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+    ) -> bool {
+        self.is_full_bus_day(&date)
+    }
+
+    /// Return the time at which business closes on a partial business day.
+    ///
+    /// Parameters
+    /// ----------
+    /// date: datetime
+    ///     Date to test
+    ///
+    /// Returns
+    /// -------
+    /// time or None
+    ///
+    /// Notes
+    /// -----
+    /// Returns *None* if the ``date`` is either a full business day or not a business day.
+    #[pyo3(name = "close_time")]
+    fn close_time_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+    ) -> Option<NaiveTime> {
+        self.close_time(&date)
+    }
+
     /// Return whether the `date` is a business day of an associated settlement calendar.
     ///
     /// .. note::
@@ -200,7 +592,7 @@ impl Cal {
     /// -------
     /// bool
     #[pyo3(name = "is_settlement")]
-    fn is_settlement_py(&self, date: NaiveDateTime) -> bool {
+    fn is_settlement_py(&self, #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime) -> bool {
         self.is_settlement(&date)
     }
 
@@ -223,9 +615,9 @@ impl Cal {
     #[pyo3(name = "add_days")]
     fn add_days_py(
         &self,
-        date: NaiveDateTime,
-        days: i8,
-        modifier: Modifier,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+        days: i32,
+        #[pyo3(from_py_with = "extract_modifier")] modifier: Modifier,
         settlement: bool,
     ) -> PyResult<NaiveDateTime> {
         Ok(self.add_days(&date, days, &modifier, settlement))
@@ -260,8 +652,8 @@ impl Cal {
     #[pyo3(name = "add_bus_days")]
     fn add_bus_days_py(
         &self,
-        date: NaiveDateTime,
-        days: i8,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+        days: i32,
         settlement: bool,
     ) -> PyResult<NaiveDateTime> {
         self.add_bus_days(&date, days, settlement)
@@ -284,15 +676,41 @@ impl Cal {
     #[pyo3(name = "add_months")]
     fn add_months_py(
         &self,
-        date: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
         months: i32,
-        modifier: Modifier,
-        roll: RollDay,
+        #[pyo3(from_py_with = "extract_modifier")] modifier: Modifier,
+        #[pyo3(from_py_with = "extract_roll_day")] roll: RollDay,
         settlement: bool,
     ) -> PyResult<NaiveDateTime> {
         Ok(self.add_months(&date, months, &modifier, &roll, settlement))
     }
 
+    /// Return a date separated by years from an input date, and rolled with a modifier.
+    ///
+    /// Parameters
+    /// ----------
+    /// date: datetime
+    ///     The original date to adjust.
+    /// years: int
+    ///     The number of years to add.
+    /// modifier: Modifier
+    ///     The rule to use to roll a resultant non-business day.
+    /// roll: RollDay
+    ///     The day of the month to adjust to.
+    /// settlement: bool
+    ///     Enforce an associated settlement calendar, if *True* and if one exists.
+    #[pyo3(name = "add_years")]
+    fn add_years_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+        years: i32,
+        #[pyo3(from_py_with = "extract_modifier")] modifier: Modifier,
+        #[pyo3(from_py_with = "extract_roll_day")] roll: RollDay,
+        settlement: bool,
+    ) -> PyResult<NaiveDateTime> {
+        Ok(self.add_years(&date, years, &modifier, &roll, settlement))
+    }
+
     /// Adjust a non-business date to a business date under a specific modification rule.
     ///
     /// Parameters
@@ -314,8 +732,8 @@ impl Cal {
     #[pyo3(name = "roll")]
     fn roll_py(
         &self,
-        date: NaiveDateTime,
-        modifier: Modifier,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_modifier")] modifier: Modifier,
         settlement: bool,
     ) -> PyResult<NaiveDateTime> {
         Ok(self.roll(&date, &modifier, settlement))
@@ -352,11 +770,16 @@ impl Cal {
     /// Adding (or subtracting) further business days adopts the
     /// :meth:`~rateslib.calendars.Cal.add_bus_days` approach with a valid result.
     #[pyo3(name = "lag")]
-    fn lag_py(&self, date: NaiveDateTime, days: i8, settlement: bool) -> NaiveDateTime {
+    fn lag_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+        days: i32,
+        settlement: bool,
+    ) -> NaiveDateTime {
         self.lag(&date, days, settlement)
     }
 
-    /// Return a list of business dates in a range.
+    /// Return the business dates in a range.
     ///
     /// Parameters
     /// ----------
@@ -364,20 +787,28 @@ impl Cal {
     ///     The start date of the range, inclusive.
     /// end: datetime
     ///     The end date of the range, inclusive.
+    /// step: int, optional
+    ///     The number of business days between each date. If negative the range is reversed and
+    ///     ``start`` should be after ``end``.
     ///
     /// Returns
     /// -------
-    /// list[datetime]
-    #[pyo3(name = "bus_date_range")]
-    fn bus_date_range_py(
+    /// ndarray of datetime64[D]
+    #[pyo3(name = "bus_date_range", signature = (start, end, step=1))]
+    fn bus_date_range_py<'py>(
         &self,
-        start: NaiveDateTime,
-        end: NaiveDateTime,
-    ) -> PyResult<Vec<NaiveDateTime>> {
-        self.bus_date_range(&start, &end)
+        py: Python<'py>,
+        #[pyo3(from_py_with = "extract_date")] start: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] end: NaiveDateTime,
+        step: i32,
+    ) -> PyResult<Bound<'py, PyArray1<Datetime<Days>>>> {
+        Ok(datetime64_array(
+            py,
+            &self.bus_date_range_step(&start, &end, step)?,
+        ))
     }
 
-    /// Return a list of calendar dates within a range.
+    /// Return the calendar dates within a range.
     ///
     /// Parameters
     /// -----------
@@ -385,17 +816,125 @@ impl Cal {
     ///     The start date of the range, inclusive.
     /// end: datetime
     ///     The end date of the range, inclusive,
+    /// step: int, optional
+    ///     The number of calendar days between each date. If negative the range is reversed and
+    ///     ``start`` should be after ``end``.
+    ///
+    /// Returns
+    /// --------
+    /// ndarray of datetime64[D]
+    #[pyo3(name = "cal_date_range", signature = (start, end, step=1))]
+    fn cal_date_range_py<'py>(
+        &self,
+        py: Python<'py>,
+        #[pyo3(from_py_with = "extract_date")] start: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] end: NaiveDateTime,
+        step: i32,
+    ) -> PyResult<Bound<'py, PyArray1<Datetime<Days>>>> {
+        Ok(datetime64_array(
+            py,
+            &self.cal_date_range_step(&start, &end, step)?,
+        ))
+    }
+
+    /// Return the first business dates after a given date.
+    ///
+    /// Parameters
+    /// -----------
+    /// date: datetime
+    ///     The date after which to take business days, exclusive.
+    /// n: int
+    ///     The number of business days to return.
+    ///
+    /// Returns
+    /// --------
+    /// ndarray of datetime64[D]
+    #[pyo3(name = "take_bus_days")]
+    fn take_bus_days_py<'py>(
+        &self,
+        py: Python<'py>,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+        n: usize,
+    ) -> Bound<'py, PyArray1<Datetime<Days>>> {
+        datetime64_array(py, &self.take_bus_days(&date, n))
+    }
+
+    /// Return the payment dates of the periods of a leg, from its adjusted accrual schedule.
+    ///
+    /// Parameters
+    /// -----------
+    /// aschedule: list[datetime]
+    ///     The adjusted accrual schedule of the leg, including the initial and final dates.
+    /// payment_lag: int
+    ///     The number of business days after each accrual end date that the period pays. May
+    ///     be negative for payment in advance.
     ///
     /// Returns
     /// --------
     /// list[datetime]
-    #[pyo3(name = "cal_date_range")]
-    fn cal_date_range_py(
+    ///
+    /// Notes
+    /// -----
+    /// Each payment date follows the lag rules of :meth:`~rateslib.calendars.Cal.lag` and
+    /// allows settlement.
+    #[pyo3(name = "payment_dates")]
+    fn payment_dates_py(
         &self,
-        start: NaiveDateTime,
-        end: NaiveDateTime,
-    ) -> PyResult<Vec<NaiveDateTime>> {
-        self.cal_date_range(&start, &end)
+        #[pyo3(from_py_with = "extract_dates")] aschedule: Vec<NaiveDateTime>,
+        payment_lag: i32,
+    ) -> Vec<NaiveDateTime> {
+        self.payment_dates(&aschedule, payment_lag)
+    }
+
+    /// Return the notional exchange dates of a leg, from its adjusted accrual schedule.
+    ///
+    /// Parameters
+    /// -----------
+    /// aschedule: list[datetime]
+    ///     The adjusted accrual schedule of the leg, including the initial and final dates.
+    /// payment_lag_exchange: int
+    ///     The number of business days after each accrual date that notional is exchanged.
+    ///
+    /// Returns
+    /// --------
+    /// list[datetime]
+    #[pyo3(name = "exchange_dates")]
+    fn exchange_dates_py(
+        &self,
+        #[pyo3(from_py_with = "extract_dates")] aschedule: Vec<NaiveDateTime>,
+        payment_lag_exchange: i32,
+    ) -> Vec<NaiveDateTime> {
+        self.exchange_dates(&aschedule, payment_lag_exchange)
+    }
+
+    /// Return whether another calendar has the same business and settlement days within a range.
+    ///
+    /// Parameters
+    /// -----------
+    /// other: Cal, UnionCal or NamedCal
+    ///     The calendar to compare against.
+    /// start: datetime
+    ///     The start date of the range, inclusive.
+    /// end: datetime
+    ///     The end date of the range, inclusive,
+    ///
+    /// Returns
+    /// --------
+    /// bool
+    ///
+    /// Notes
+    /// -----
+    /// Equality of calendars via ``==`` is structural, comparing week masks and the holidays that
+    /// fall on weekdays. This method instead compares behaviour date by date and is suitable when
+    /// calendars need only agree over a specific period.
+    #[pyo3(name = "equivalent_between")]
+    fn equivalent_between_py(
+        &self,
+        other: CalType,
+        #[pyo3(from_py_with = "extract_date")] start: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] end: NaiveDateTime,
+    ) -> bool {
+        self.equivalent_between(&other, &start, &end)
     }
 
     // Pickling
@@ -431,6 +970,20 @@ impl Cal {
         }
     }
 
+    /// Return a hash of the canonical JSON representation of the object, which is stable
+    /// across sessions.
+    ///
+    /// Returns
+    /// -------
+    /// int
+    #[pyo3(name = "canonical_hash")]
+    fn canonical_hash_py(&self) -> PyResult<u64> {
+        match DeserializedObj::Cal(self.clone()).canonical_hash() {
+            Ok(v) => Ok(v),
+            Err(_) => Err(PyValueError::new_err("Failed to serialize `Cal` to JSON.")),
+        }
+    }
+
     // Equality
     fn __eq__(&self, other: CalType) -> bool {
         match other {
@@ -439,6 +992,12 @@ impl Cal {
             CalType::NamedCal(c) => *self == c,
         }
     }
+
+    fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
 }
 
 #[pymethods]
@@ -478,11 +1037,65 @@ impl UnionCal {
         self.settlement_calendars.clone()
     }
 
+    /// Return a new *UnionCal* with additional holidays.
+    ///
+    /// See :meth:`UnionCal.add_holidays <rateslib.calendars.UnionCal.add_holidays>`.
+    #[pyo3(name = "with_holidays", signature = (holidays, settlement=false))]
+    fn with_holidays_py(
+        &self,
+        #[pyo3(from_py_with = "extract_dates")] holidays: Vec<NaiveDateTime>,
+        settlement: bool,
+    ) -> PyResult<Self> {
+        self.clone().with_holidays(holidays, settlement)
+    }
+
+    /// Add holidays to the union, e.g. for an ad-hoc market closure.
+    ///
+    /// Parameters
+    /// ----------
+    /// holidays: list[datetime]
+    ///     The dates to add as holidays, to the first of the calendars.
+    /// settlement: bool, optional
+    ///     Whether to add the holidays to the first settlement calendar instead.
+    ///
+    /// Returns
+    /// -------
+    /// None
+    #[pyo3(name = "add_holidays", signature = (holidays, settlement=false))]
+    fn add_holidays_py(
+        &mut self,
+        #[pyo3(from_py_with = "extract_dates")] holidays: Vec<NaiveDateTime>,
+        settlement: bool,
+    ) -> PyResult<()> {
+        self.add_holidays(&holidays, settlement)
+    }
+
+    /// Remove holidays from the union.
+    ///
+    /// Parameters
+    /// ----------
+    /// holidays: list[datetime]
+    ///     The dates to remove from every calendar. Any date which is not a holiday is ignored.
+    /// settlement: bool, optional
+    ///     Whether to remove the holidays from the settlement calendars instead.
+    ///
+    /// Returns
+    /// -------
+    /// None
+    #[pyo3(name = "remove_holidays", signature = (holidays, settlement=false))]
+    fn remove_holidays_py(
+        &mut self,
+        #[pyo3(from_py_with = "extract_dates")] holidays: Vec<NaiveDateTime>,
+        settlement: bool,
+    ) -> PyResult<()> {
+        self.remove_holidays(&holidays, settlement)
+    }
+
     /// Return whether the `date` is a business day.
     ///
     /// See :meth:`Cal.is_bus_day <rateslib.calendars.Cal.is_bus_day>`.
     #[pyo3(name = "is_bus_day")]
-    fn is_bus_day_py(&self, date: NaiveDateTime) -> bool {
+    fn is_bus_day_py(&self, #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime) -> bool {
         self.is_bus_day(&date)
     }
 
@@ -490,17 +1103,42 @@ impl UnionCal {
     ///
     /// See :meth:`Cal.is_non_bus_day <rateslib.calendars.Cal.is_non_bus_day>`.
     #[pyo3(name = "is_non_bus_day")]
-    fn is_non_bus_day_py(&self, date: NaiveDateTime) -> bool {
+    fn is_non_bus_day_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+    ) -> bool {
         self.is_non_bus_day(&date)
     }
 
+    /// Return whether the `date` is a business day without an early close.
+    ///
+    /// See :meth:`Cal.is_full_bus_day <rateslib.calendars.Cal.is_full_bus_day>`.
+    #[pyo3(name = "is_full_bus_day")]
+    fn is_full_bus_day_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+    ) -> bool {
+        self.is_full_bus_day(&date)
+    }
+
+    /// Return the time at which business closes on a partial business day.
+    ///
+    /// See :meth:`Cal.close_time <rateslib.calendars.Cal.close_time>`.
+    #[pyo3(name = "close_time")]
+    fn close_time_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+    ) -> Option<NaiveTime> {
+        self.close_time(&date)
+    }
+
     /// Return whether the `date` is a business day in an associated settlement calendar.
     ///
     /// If no such associated settlement calendar exists this will return *True*.
     ///
     /// See :meth:`Cal.is_settlement <rateslib.calendars.Cal.is_settlement>`.
     #[pyo3(name = "is_settlement")]
-    fn is_settlement_py(&self, date: NaiveDateTime) -> bool {
+    fn is_settlement_py(&self, #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime) -> bool {
         self.is_settlement(&date)
     }
 
@@ -510,9 +1148,9 @@ impl UnionCal {
     #[pyo3(name = "add_days")]
     fn add_days_py(
         &self,
-        date: NaiveDateTime,
-        days: i8,
-        modifier: Modifier,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+        days: i32,
+        #[pyo3(from_py_with = "extract_modifier")] modifier: Modifier,
         settlement: bool,
     ) -> PyResult<NaiveDateTime> {
         Ok(self.add_days(&date, days, &modifier, settlement))
@@ -524,8 +1162,8 @@ impl UnionCal {
     #[pyo3(name = "add_bus_days")]
     fn add_bus_days_py(
         &self,
-        date: NaiveDateTime,
-        days: i8,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+        days: i32,
         settlement: bool,
     ) -> PyResult<NaiveDateTime> {
         self.add_bus_days(&date, days, settlement)
@@ -537,23 +1175,49 @@ impl UnionCal {
     #[pyo3(name = "add_months")]
     fn add_months_py(
         &self,
-        date: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
         months: i32,
-        modifier: Modifier,
-        roll: RollDay,
+        #[pyo3(from_py_with = "extract_modifier")] modifier: Modifier,
+        #[pyo3(from_py_with = "extract_roll_day")] roll: RollDay,
         settlement: bool,
     ) -> PyResult<NaiveDateTime> {
         Ok(self.add_months(&date, months, &modifier, &roll, settlement))
     }
 
+    /// Return a date separated by years from an input date, and rolled with a modifier.
+    ///
+    /// Parameters
+    /// ----------
+    /// date: datetime
+    ///     The original date to adjust.
+    /// years: int
+    ///     The number of years to add.
+    /// modifier: Modifier
+    ///     The rule to use to roll a resultant non-business day.
+    /// roll: RollDay
+    ///     The day of the month to adjust to.
+    /// settlement: bool
+    ///     Enforce an associated settlement calendar, if *True* and if one exists.
+    #[pyo3(name = "add_years")]
+    fn add_years_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+        years: i32,
+        #[pyo3(from_py_with = "extract_modifier")] modifier: Modifier,
+        #[pyo3(from_py_with = "extract_roll_day")] roll: RollDay,
+        settlement: bool,
+    ) -> PyResult<NaiveDateTime> {
+        Ok(self.add_years(&date, years, &modifier, &roll, settlement))
+    }
+
     /// Adjust a non-business date to a business date under a specific modification rule.
     ///
     /// See :meth:`Cal.roll <rateslib.calendars.Cal.roll>`.
     #[pyo3(name = "roll")]
     fn roll_py(
         &self,
-        date: NaiveDateTime,
-        modifier: Modifier,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_modifier")] modifier: Modifier,
         settlement: bool,
     ) -> PyResult<NaiveDateTime> {
         Ok(self.roll(&date, &modifier, settlement))
@@ -563,32 +1227,97 @@ impl UnionCal {
     ///
     /// See :meth:`Cal.lag <rateslib.calendars.Cal.lag>`.
     #[pyo3(name = "lag")]
-    fn lag_py(&self, date: NaiveDateTime, days: i8, settlement: bool) -> NaiveDateTime {
+    fn lag_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+        days: i32,
+        settlement: bool,
+    ) -> NaiveDateTime {
         self.lag(&date, days, settlement)
     }
 
-    /// Return a list of business dates in a range.
+    /// Return the business dates in a range.
     ///
     /// See :meth:`Cal.bus_date_range <rateslib.calendars.Cal.bus_date_range>`.
-    #[pyo3(name = "bus_date_range")]
-    fn bus_date_range_py(
+    #[pyo3(name = "bus_date_range", signature = (start, end, step=1))]
+    fn bus_date_range_py<'py>(
         &self,
-        start: NaiveDateTime,
-        end: NaiveDateTime,
-    ) -> PyResult<Vec<NaiveDateTime>> {
-        self.bus_date_range(&start, &end)
+        py: Python<'py>,
+        #[pyo3(from_py_with = "extract_date")] start: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] end: NaiveDateTime,
+        step: i32,
+    ) -> PyResult<Bound<'py, PyArray1<Datetime<Days>>>> {
+        Ok(datetime64_array(
+            py,
+            &self.bus_date_range_step(&start, &end, step)?,
+        ))
     }
 
-    /// Return a list of calendar dates in a range.
+    /// Return the calendar dates in a range.
     ///
     /// See :meth:`Cal.cal_date_range <rateslib.calendars.Cal.cal_date_range>`.
-    #[pyo3(name = "cal_date_range")]
-    fn cal_date_range_py(
+    #[pyo3(name = "cal_date_range", signature = (start, end, step=1))]
+    fn cal_date_range_py<'py>(
         &self,
-        start: NaiveDateTime,
-        end: NaiveDateTime,
-    ) -> PyResult<Vec<NaiveDateTime>> {
-        self.cal_date_range(&start, &end)
+        py: Python<'py>,
+        #[pyo3(from_py_with = "extract_date")] start: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] end: NaiveDateTime,
+        step: i32,
+    ) -> PyResult<Bound<'py, PyArray1<Datetime<Days>>>> {
+        Ok(datetime64_array(
+            py,
+            &self.cal_date_range_step(&start, &end, step)?,
+        ))
+    }
+
+    /// Return the first business dates after a given date.
+    ///
+    /// See :meth:`Cal.take_bus_days <rateslib.calendars.Cal.take_bus_days>`.
+    #[pyo3(name = "take_bus_days")]
+    fn take_bus_days_py<'py>(
+        &self,
+        py: Python<'py>,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+        n: usize,
+    ) -> Bound<'py, PyArray1<Datetime<Days>>> {
+        datetime64_array(py, &self.take_bus_days(&date, n))
+    }
+
+    /// Return the payment dates of the periods of a leg, from its adjusted accrual schedule.
+    ///
+    /// See :meth:`Cal.payment_dates <rateslib.calendars.Cal.payment_dates>`.
+    #[pyo3(name = "payment_dates")]
+    fn payment_dates_py(
+        &self,
+        #[pyo3(from_py_with = "extract_dates")] aschedule: Vec<NaiveDateTime>,
+        payment_lag: i32,
+    ) -> Vec<NaiveDateTime> {
+        self.payment_dates(&aschedule, payment_lag)
+    }
+
+    /// Return the notional exchange dates of a leg, from its adjusted accrual schedule.
+    ///
+    /// See :meth:`Cal.exchange_dates <rateslib.calendars.Cal.exchange_dates>`.
+    #[pyo3(name = "exchange_dates")]
+    fn exchange_dates_py(
+        &self,
+        #[pyo3(from_py_with = "extract_dates")] aschedule: Vec<NaiveDateTime>,
+        payment_lag_exchange: i32,
+    ) -> Vec<NaiveDateTime> {
+        self.exchange_dates(&aschedule, payment_lag_exchange)
+    }
+
+    /// Return whether another calendar has the same business and settlement days within a range.
+    ///
+    /// See :meth:`Cal.equivalent_between <rateslib.calendars.Cal.equivalent_between>`.
+    #[pyo3(name = "equivalent_between")]
+    fn equivalent_between_py(
+        &self,
+        other: CalType,
+        #[pyo3(from_py_with = "extract_date")] start: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] end: NaiveDateTime,
+    ) -> bool {
+        self.equivalent_between(&other, &start, &end)
     }
 
     // Pickling
@@ -619,6 +1348,22 @@ impl UnionCal {
         }
     }
 
+    /// Return a hash of the canonical JSON representation of the object, which is stable
+    /// across sessions.
+    ///
+    /// Returns
+    /// -------
+    /// int
+    #[pyo3(name = "canonical_hash")]
+    fn canonical_hash_py(&self) -> PyResult<u64> {
+        match DeserializedObj::UnionCal(self.clone()).canonical_hash() {
+            Ok(v) => Ok(v),
+            Err(_) => Err(PyValueError::new_err(
+                "Failed to serialize `UnionCal` to JSON.",
+            )),
+        }
+    }
+
     // Equality
     fn __eq__(&self, other: CalType) -> bool {
         match other {
@@ -627,6 +1372,12 @@ impl UnionCal {
             CalType::NamedCal(c) => *self == c,
         }
     }
+
+    fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
 }
 
 #[pymethods]
@@ -660,7 +1411,7 @@ impl NamedCal {
     ///
     /// See :meth:`Cal.is_bus_day <rateslib.calendars.Cal.is_bus_day>`.
     #[pyo3(name = "is_bus_day")]
-    fn is_bus_day_py(&self, date: NaiveDateTime) -> bool {
+    fn is_bus_day_py(&self, #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime) -> bool {
         self.is_bus_day(&date)
     }
 
@@ -668,17 +1419,42 @@ impl NamedCal {
     ///
     /// See :meth:`Cal.is_non_bus_day <rateslib.calendars.Cal.is_non_bus_day>`.
     #[pyo3(name = "is_non_bus_day")]
-    fn is_non_bus_day_py(&self, date: NaiveDateTime) -> bool {
+    fn is_non_bus_day_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+    ) -> bool {
         self.is_non_bus_day(&date)
     }
 
+    /// Return whether the `date` is a business day without an early close.
+    ///
+    /// See :meth:`Cal.is_full_bus_day <rateslib.calendars.Cal.is_full_bus_day>`.
+    #[pyo3(name = "is_full_bus_day")]
+    fn is_full_bus_day_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+    ) -> bool {
+        self.is_full_bus_day(&date)
+    }
+
+    /// Return the time at which business closes on a partial business day.
+    ///
+    /// See :meth:`Cal.close_time <rateslib.calendars.Cal.close_time>`.
+    #[pyo3(name = "close_time")]
+    fn close_time_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+    ) -> Option<NaiveTime> {
+        self.close_time(&date)
+    }
+
     /// Return whether the `date` is a business day in an associated settlement calendar.
     ///
     /// If no such associated settlement calendar exists this will return *True*.
     ///
     /// See :meth:`Cal.is_settlement <rateslib.calendars.Cal.is_settlement>`.
     #[pyo3(name = "is_settlement")]
-    fn is_settlement_py(&self, date: NaiveDateTime) -> bool {
+    fn is_settlement_py(&self, #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime) -> bool {
         self.is_settlement(&date)
     }
 
@@ -688,9 +1464,9 @@ impl NamedCal {
     #[pyo3(name = "add_days")]
     fn add_days_py(
         &self,
-        date: NaiveDateTime,
-        days: i8,
-        modifier: Modifier,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+        days: i32,
+        #[pyo3(from_py_with = "extract_modifier")] modifier: Modifier,
         settlement: bool,
     ) -> PyResult<NaiveDateTime> {
         Ok(self.add_days(&date, days, &modifier, settlement))
@@ -702,8 +1478,8 @@ impl NamedCal {
     #[pyo3(name = "add_bus_days")]
     fn add_bus_days_py(
         &self,
-        date: NaiveDateTime,
-        days: i8,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+        days: i32,
         settlement: bool,
     ) -> PyResult<NaiveDateTime> {
         self.add_bus_days(&date, days, settlement)
@@ -715,23 +1491,49 @@ impl NamedCal {
     #[pyo3(name = "add_months")]
     fn add_months_py(
         &self,
-        date: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
         months: i32,
-        modifier: Modifier,
-        roll: RollDay,
+        #[pyo3(from_py_with = "extract_modifier")] modifier: Modifier,
+        #[pyo3(from_py_with = "extract_roll_day")] roll: RollDay,
         settlement: bool,
     ) -> PyResult<NaiveDateTime> {
         Ok(self.add_months(&date, months, &modifier, &roll, settlement))
     }
 
+    /// Return a date separated by years from an input date, and rolled with a modifier.
+    ///
+    /// Parameters
+    /// ----------
+    /// date: datetime
+    ///     The original date to adjust.
+    /// years: int
+    ///     The number of years to add.
+    /// modifier: Modifier
+    ///     The rule to use to roll a resultant non-business day.
+    /// roll: RollDay
+    ///     The day of the month to adjust to.
+    /// settlement: bool
+    ///     Enforce an associated settlement calendar, if *True* and if one exists.
+    #[pyo3(name = "add_years")]
+    fn add_years_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+        years: i32,
+        #[pyo3(from_py_with = "extract_modifier")] modifier: Modifier,
+        #[pyo3(from_py_with = "extract_roll_day")] roll: RollDay,
+        settlement: bool,
+    ) -> PyResult<NaiveDateTime> {
+        Ok(self.add_years(&date, years, &modifier, &roll, settlement))
+    }
+
     /// Adjust a non-business date to a business date under a specific modification rule.
     ///
     /// See :meth:`Cal.roll <rateslib.calendars.Cal.roll>`.
     #[pyo3(name = "roll")]
     fn roll_py(
         &self,
-        date: NaiveDateTime,
-        modifier: Modifier,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_modifier")] modifier: Modifier,
         settlement: bool,
     ) -> PyResult<NaiveDateTime> {
         Ok(self.roll(&date, &modifier, settlement))
@@ -741,32 +1543,97 @@ impl NamedCal {
     ///
     /// See :meth:`Cal.lag <rateslib.calendars.Cal.lag>`.
     #[pyo3(name = "lag")]
-    fn lag_py(&self, date: NaiveDateTime, days: i8, settlement: bool) -> NaiveDateTime {
+    fn lag_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+        days: i32,
+        settlement: bool,
+    ) -> NaiveDateTime {
         self.lag(&date, days, settlement)
     }
 
-    /// Return a list of business dates in a range.
+    /// Return the business dates in a range.
     ///
     /// See :meth:`Cal.bus_date_range <rateslib.calendars.Cal.bus_date_range>`.
-    #[pyo3(name = "bus_date_range")]
-    fn bus_date_range_py(
+    #[pyo3(name = "bus_date_range", signature = (start, end, step=1))]
+    fn bus_date_range_py<'py>(
         &self,
-        start: NaiveDateTime,
-        end: NaiveDateTime,
-    ) -> PyResult<Vec<NaiveDateTime>> {
-        self.bus_date_range(&start, &end)
+        py: Python<'py>,
+        #[pyo3(from_py_with = "extract_date")] start: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] end: NaiveDateTime,
+        step: i32,
+    ) -> PyResult<Bound<'py, PyArray1<Datetime<Days>>>> {
+        Ok(datetime64_array(
+            py,
+            &self.bus_date_range_step(&start, &end, step)?,
+        ))
     }
 
-    /// Return a list of calendar dates in a range.
+    /// Return the calendar dates in a range.
     ///
     /// See :meth:`Cal.cal_date_range <rateslib.calendars.Cal.cal_date_range>`.
-    #[pyo3(name = "cal_date_range")]
-    fn cal_date_range_py(
+    #[pyo3(name = "cal_date_range", signature = (start, end, step=1))]
+    fn cal_date_range_py<'py>(
+        &self,
+        py: Python<'py>,
+        #[pyo3(from_py_with = "extract_date")] start: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] end: NaiveDateTime,
+        step: i32,
+    ) -> PyResult<Bound<'py, PyArray1<Datetime<Days>>>> {
+        Ok(datetime64_array(
+            py,
+            &self.cal_date_range_step(&start, &end, step)?,
+        ))
+    }
+
+    /// Return the first business dates after a given date.
+    ///
+    /// See :meth:`Cal.take_bus_days <rateslib.calendars.Cal.take_bus_days>`.
+    #[pyo3(name = "take_bus_days")]
+    fn take_bus_days_py<'py>(
+        &self,
+        py: Python<'py>,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+        n: usize,
+    ) -> Bound<'py, PyArray1<Datetime<Days>>> {
+        datetime64_array(py, &self.take_bus_days(&date, n))
+    }
+
+    /// Return the payment dates of the periods of a leg, from its adjusted accrual schedule.
+    ///
+    /// See :meth:`Cal.payment_dates <rateslib.calendars.Cal.payment_dates>`.
+    #[pyo3(name = "payment_dates")]
+    fn payment_dates_py(
+        &self,
+        #[pyo3(from_py_with = "extract_dates")] aschedule: Vec<NaiveDateTime>,
+        payment_lag: i32,
+    ) -> Vec<NaiveDateTime> {
+        self.payment_dates(&aschedule, payment_lag)
+    }
+
+    /// Return the notional exchange dates of a leg, from its adjusted accrual schedule.
+    ///
+    /// See :meth:`Cal.exchange_dates <rateslib.calendars.Cal.exchange_dates>`.
+    #[pyo3(name = "exchange_dates")]
+    fn exchange_dates_py(
         &self,
-        start: NaiveDateTime,
-        end: NaiveDateTime,
-    ) -> PyResult<Vec<NaiveDateTime>> {
-        self.cal_date_range(&start, &end)
+        #[pyo3(from_py_with = "extract_dates")] aschedule: Vec<NaiveDateTime>,
+        payment_lag_exchange: i32,
+    ) -> Vec<NaiveDateTime> {
+        self.exchange_dates(&aschedule, payment_lag_exchange)
+    }
+
+    /// Return whether another calendar has the same business and settlement days within a range.
+    ///
+    /// See :meth:`Cal.equivalent_between <rateslib.calendars.Cal.equivalent_between>`.
+    #[pyo3(name = "equivalent_between")]
+    fn equivalent_between_py(
+        &self,
+        other: CalType,
+        #[pyo3(from_py_with = "extract_date")] start: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] end: NaiveDateTime,
+    ) -> bool {
+        self.equivalent_between(&other, &start, &end)
     }
 
     // Pickling
@@ -797,6 +1664,22 @@ impl NamedCal {
         }
     }
 
+    /// Return a hash of the canonical JSON representation of the object, which is stable
+    /// across sessions.
+    ///
+    /// Returns
+    /// -------
+    /// int
+    #[pyo3(name = "canonical_hash")]
+    fn canonical_hash_py(&self) -> PyResult<u64> {
+        match DeserializedObj::NamedCal(self.clone()).canonical_hash() {
+            Ok(v) => Ok(v),
+            Err(_) => Err(PyValueError::new_err(
+                "Failed to serialize `NamedCal` to JSON.",
+            )),
+        }
+    }
+
     // Equality
     fn __eq__(&self, other: CalType) -> bool {
         match other {
@@ -805,6 +1688,12 @@ impl NamedCal {
             CalType::NamedCal(c) => *self == c,
         }
     }
+
+    fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
 }
 
 /// Return a calendar container from named identifier.
@@ -813,3 +1702,197 @@ impl NamedCal {
 pub fn get_calendar_by_name_py(name: &str) -> PyResult<Cal> {
     get_calendar_by_name(name)
 }
+
+/// Return the date of Easter Sunday in the given year of the Gregorian calendar.
+#[pyfunction]
+#[pyo3(name = "easter")]
+pub fn easter_py(year: i32) -> NaiveDateTime {
+    easter(year)
+}
+
+/// Return the date of Good Friday in the given year of the Gregorian calendar.
+#[pyfunction]
+#[pyo3(name = "good_friday")]
+pub fn good_friday_py(year: i32) -> NaiveDateTime {
+    good_friday(year)
+}
+
+/// Return the next IMM date after a given date.
+#[pyfunction]
+#[pyo3(name = "next_imm", signature = (date, serial=false))]
+pub fn next_imm_py(
+    #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+    serial: bool,
+) -> NaiveDateTime {
+    next_imm(&date, serial)
+}
+
+/// Return the previous IMM date before a given date.
+#[pyfunction]
+#[pyo3(name = "previous_imm", signature = (date, serial=false))]
+pub fn previous_imm_py(
+    #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+    serial: bool,
+) -> NaiveDateTime {
+    previous_imm(&date, serial)
+}
+
+/// Calculate the day count fraction of a period.
+///
+/// Parameters
+/// ----------
+/// start: datetime
+///     The adjusted start date of the calculation period.
+/// end: datetime
+///     The adjusted end date of the calculation period.
+/// convention: Convention
+///     The day count convention of the calculation period accrual.
+/// termination: datetime, optional
+///     The adjusted termination date of the leg. Required for *Thirty360ISDA* and *ActActICMA*.
+/// frequency: int, optional
+///     The number of months in a regular period of the leg. Required for *ActActICMA*.
+/// stub: bool, optional
+///     Whether the period is a stub period. Required for *ActActICMA*.
+/// roll: RollDay, optional
+///     Used by *ActActICMA* to project regular periods when calculating stubs.
+/// calendar: Cal, UnionCal or NamedCal, optional
+///     The business day calendar. Required for *Bus252*.
+///
+/// Returns
+/// -------
+/// float
+#[pyfunction]
+#[pyo3(name = "dcf", signature = (start, end, convention, termination=None, frequency=None, stub=None, roll=None, calendar=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn dcf_py(
+    #[pyo3(from_py_with = "extract_date")] start: NaiveDateTime,
+    #[pyo3(from_py_with = "extract_date")] end: NaiveDateTime,
+    convention: Convention,
+    #[pyo3(from_py_with = "extract_optional_date")] termination: Option<NaiveDateTime>,
+    frequency: Option<i32>,
+    stub: Option<bool>,
+    #[pyo3(from_py_with = "extract_optional_roll_day")] roll: Option<RollDay>,
+    calendar: Option<CalType>,
+) -> PyResult<f64> {
+    convention.dcf(
+        &start,
+        &end,
+        termination.as_ref(),
+        frequency,
+        stub,
+        roll.as_ref(),
+        calendar.as_ref().map(|c| c as &dyn DateRoll),
+    )
+}
+
+/// Return the fixing dates of an RFR compounded over an accrual period.
+///
+/// Parameters
+/// ----------
+/// accrual_start: datetime
+///     The adjusted start date of the accrual period.
+/// accrual_end: datetime
+///     The adjusted end date of the accrual period.
+/// calendar: Cal, UnionCal or NamedCal
+///     The business day calendar of the RFR.
+/// lookback_days: int
+///     The number of business days by which fixings are observed before the dates they accrue.
+/// obs_shift: bool
+///     Whether fixings are weighted by the calendar days of the shifted observation period
+///     rather than of the accrual period.
+/// lockout_days: int, optional
+///     The number of final fixings which are frozen at the fixing of the rate cut-off date.
+///
+/// Returns
+/// -------
+/// tuple[list[datetime], list[datetime], list[int]]
+///
+/// Notes
+/// -----
+/// The lists are aligned: the observation date of each fixing, the start of its weighting
+/// period and the number of calendar days for which it is weighted.
+#[pyfunction]
+#[pyo3(name = "fixing_dates", signature = (accrual_start, accrual_end, calendar, lookback_days, obs_shift, lockout_days=0))]
+pub fn fixing_dates_py(
+    #[pyo3(from_py_with = "extract_date")] accrual_start: NaiveDateTime,
+    #[pyo3(from_py_with = "extract_date")] accrual_end: NaiveDateTime,
+    calendar: CalType,
+    lookback_days: u32,
+    obs_shift: bool,
+    lockout_days: u32,
+) -> PyResult<(Vec<NaiveDateTime>, Vec<NaiveDateTime>, Vec<i64>)> {
+    let f = fixing_dates(
+        &accrual_start,
+        &accrual_end,
+        &calendar,
+        lookback_days,
+        obs_shift,
+        lockout_days,
+    )?;
+    Ok((f.observation_dates, f.weighting_dates, f.day_counts))
+}
+
+/// Return the rate cut-off date of an RFR observation period under a lockout.
+///
+/// Parameters
+/// ----------
+/// date: datetime
+///     The end date of the observation period, which must be a business day.
+/// n: int
+///     The number of final fixings of the period which are locked out.
+/// calendar: Cal, UnionCal or NamedCal
+///     The business day calendar of the RFR.
+///
+/// Returns
+/// -------
+/// datetime
+///
+/// Notes
+/// -----
+/// The rate cut-off date is *n + 1* business days before ``date``. It is the observation date of
+/// the last fixing which is not locked out, and each of the last *n* fixings of the period takes
+/// its value.
+#[pyfunction]
+#[pyo3(name = "lockout")]
+pub fn lockout_py(
+    #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+    n: u32,
+    calendar: CalType,
+) -> PyResult<NaiveDateTime> {
+    lockout(&date, n, &calendar)
+}
+
+/// Adjust an array of dates to business dates of a named calendar under a modification rule.
+///
+/// Parameters
+/// ----------
+/// dates: ndarray of datetime64, or list[datetime]
+///     The dates to adjust.
+/// modifier: Modifier or str
+///     The modification rule, e.g. *"MF"*.
+/// calendar_name: str
+///     The name of the calendar combination, e.g. *"tgt,ldn|fed"*, which is parsed once for the
+///     whole array.
+/// settlement: bool, optional
+///     Whether to enforce settlement against an associated settlement calendar.
+///
+/// Returns
+/// -------
+/// ndarray of datetime64[D]
+///
+/// Notes
+/// -----
+/// Dates which are already settleable, business dates are returned unchanged. Any time of day
+/// is discarded.
+#[pyfunction]
+#[pyo3(name = "adjust_dates", signature = (dates, modifier, calendar_name, settlement=false))]
+pub fn adjust_dates_py<'py>(
+    py: Python<'py>,
+    #[pyo3(from_py_with = "extract_dates")] dates: Vec<NaiveDateTime>,
+    #[pyo3(from_py_with = "extract_modifier")] modifier: Modifier,
+    calendar_name: &str,
+    settlement: bool,
+) -> PyResult<Bound<'py, PyArray1<Datetime<Days>>>> {
+    let adjusted = adjust_dates(&dates, &modifier, calendar_name, settlement)?;
+    Ok(datetime64_array(py, &adjusted))
+}
@@ -5,10 +5,12 @@ use pyo3::exceptions::PyValueError;
 use pyo3::{pyclass, PyErr};
 use serde::{Deserialize, Serialize};
 use std::cmp::{Ordering, PartialEq};
+use std::fmt;
+use std::str::FromStr;
 
 /// A roll day.
 #[pyclass(module = "rateslib.rs")]
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum RollDay {
     /// Inherit the day of the input date as the roll.
     Unspecified {},
@@ -38,6 +40,61 @@ pub enum Modifier {
     ModP,
 }
 
+impl FromStr for RollDay {
+    type Err = PyErr;
+
+    /// Parse a roll day from `"eom"`, `"som"`, `"imm"` or a day of the month, case insensitive.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "eom" => Ok(RollDay::EoM {}),
+            "som" => Ok(RollDay::SoM {}),
+            "imm" => Ok(RollDay::IMM {}),
+            value => match value.parse::<u32>() {
+                Ok(day) if (1..=31).contains(&day) => Ok(RollDay::Int { day }),
+                _ => Err(PyValueError::new_err(format!(
+                    "'{}' is not a valid roll day, expected one of: 'eom', 'som', 'imm' or a day \
+                     of the month in [1, 31].",
+                    s
+                ))),
+            },
+        }
+    }
+}
+
+impl FromStr for Modifier {
+    type Err = PyErr;
+
+    /// Parse a modifier from its code, case insensitive, where modified rules may be given as
+    /// `"MF"` or `"MODF"`, and the actual rule as `"NONE"` or `"ACT"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_uppercase().as_str() {
+            "NONE" | "ACT" => Ok(Modifier::Act),
+            "F" => Ok(Modifier::F),
+            "MF" | "MODF" => Ok(Modifier::ModF),
+            "P" => Ok(Modifier::P),
+            "MP" | "MODP" => Ok(Modifier::ModP),
+            _ => Err(PyValueError::new_err(format!(
+                "'{}' is not a valid modifier, expected one of: 'F', 'MF', 'P', 'MP', 'NONE'.",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Modifier {
+    /// Format the modifier as its code, which parses back to the same modifier.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let code = match self {
+            Modifier::F => "F",
+            Modifier::ModF => "MF",
+            Modifier::P => "P",
+            Modifier::ModP => "MP",
+            Modifier::Act => "NONE",
+        };
+        write!(f, "{}", code)
+    }
+}
+
 /// Used to control business day management and date rolling.
 pub trait DateRoll {
     /// Returns whether the date is part of the general working week.
@@ -62,6 +119,27 @@ pub trait DateRoll {
         !self.is_bus_day(date)
     }
 
+    /// Returns the time at which business closes if the date has an early close.
+    ///
+    /// Calendars without partial business days need not implement this.
+    fn early_close(&self, _date: &NaiveDateTime) -> Option<NaiveTime> {
+        None
+    }
+
+    /// Returns whether the date is a business day without an early close.
+    fn is_full_bus_day(&self, date: &NaiveDateTime) -> bool {
+        self.is_bus_day(date) && self.early_close(date).is_none()
+    }
+
+    /// Returns the early close time of a partial business day, or `None` if the date is either a
+    /// full business day or not a business day.
+    fn close_time(&self, date: &NaiveDateTime) -> Option<NaiveTime> {
+        match self.is_bus_day(date) {
+            true => self.early_close(date),
+            false => None,
+        }
+    }
+
     /// Return the `date`, if a business day, or get the next business date after `date`.
     fn roll_forward_bus_day(&self, date: &NaiveDateTime) -> NaiveDateTime {
         let mut new_date = *date;
@@ -169,19 +247,8 @@ pub trait DateRoll {
     ///
     /// *Note*: if the given `date` is a non-business date adding or subtracting 1 business
     /// day is equivalent to the rolling forwards or backwards, respectively.
-    fn lag(&self, date: &NaiveDateTime, days: i8, settlement: bool) -> NaiveDateTime {
-        if self.is_bus_day(date) {
-            return self.add_bus_days(date, days, settlement).unwrap();
-        }
-        match days.cmp(&0_i8) {
-            Ordering::Equal => self.roll_forward_bus_day(date),
-            Ordering::Less => self
-                .add_bus_days(&self.roll_backward_bus_day(date), days + 1, settlement)
-                .unwrap(),
-            Ordering::Greater => self
-                .add_bus_days(&self.roll_forward_bus_day(date), days - 1, settlement)
-                .unwrap(),
-        }
+    fn lag(&self, date: &NaiveDateTime, days: i32, settlement: bool) -> NaiveDateTime {
+        lag_bus_days(self, date, days, settlement)
     }
 
     /// Add a given number of calendar days to a `date` with the result adjusted to a business day that may or may not
@@ -193,7 +260,7 @@ pub trait DateRoll {
     fn add_days(
         &self,
         date: &NaiveDateTime,
-        days: i8,
+        days: i32,
         modifier: &Modifier,
         settlement: bool,
     ) -> NaiveDateTime
@@ -216,30 +283,10 @@ pub trait DateRoll {
     fn add_bus_days(
         &self,
         date: &NaiveDateTime,
-        days: i8,
+        days: i32,
         settlement: bool,
     ) -> Result<NaiveDateTime, PyErr> {
-        if self.is_non_bus_day(date) {
-            return Err(PyValueError::new_err(
-                "Cannot add business days to an input `date` that is not a business day.",
-            ));
-        }
-        let mut new_date = *date;
-        let mut counter: i8 = 0;
-        if days < 0 {
-            // then we subtract business days
-            while counter > days {
-                new_date = self.roll_backward_bus_day(&(new_date - Days::new(1)));
-                counter -= 1;
-            }
-        } else {
-            // add business days
-            while counter < days {
-                new_date = self.roll_forward_bus_day(&(new_date + Days::new(1)));
-                counter += 1;
-            }
-        }
-
+        let new_date = step_bus_days(self, date, days)?;
         if !settlement {
             Ok(new_date)
         } else if days < 0 {
@@ -249,6 +296,11 @@ pub trait DateRoll {
         }
     }
 
+    /// Return the number of business days from `start`, inclusive, to `end`, exclusive.
+    fn bus_day_count(&self, start: &NaiveDateTime, end: &NaiveDateTime) -> i64 {
+        step_bus_day_count(self, start, end)
+    }
+
     /// Add a given number of months to a `date`, factoring a `roll` day, with the result adjusted
     /// to a business day that may or may not allow `settlement`.
     fn add_months(
@@ -291,40 +343,222 @@ pub trait DateRoll {
         self.roll(&new_date, modifier, settlement)
     }
 
+    /// Add a given number of years to a `date`, factoring a `roll` day, with the result adjusted
+    /// to a business day that may or may not allow `settlement`.
+    ///
+    /// This is equivalent to adding twelve times as many months with
+    /// [add_months](DateRoll::add_months).
+    fn add_years(
+        &self,
+        date: &NaiveDateTime,
+        years: i32,
+        modifier: &Modifier,
+        roll: &RollDay,
+        settlement: bool,
+    ) -> NaiveDateTime
+    where
+        Self: Sized,
+    {
+        self.add_months(date, years * 12, modifier, roll, settlement)
+    }
+
     /// Return a vector of business dates between a start and end, inclusive.
     fn bus_date_range(
         &self,
         start: &NaiveDateTime,
         end: &NaiveDateTime,
+    ) -> Result<Vec<NaiveDateTime>, PyErr> {
+        self.bus_date_range_step(start, end, 1)
+    }
+
+    /// Return a vector of business dates between a start and end, inclusive, stepping by `step`
+    /// business days.
+    ///
+    /// A negative `step` returns a reverse range, in which case `start` should be after `end`.
+    /// `end` is only included if it is reached exactly by the stepping.
+    fn bus_date_range_step(
+        &self,
+        start: &NaiveDateTime,
+        end: &NaiveDateTime,
+        step: i32,
     ) -> Result<Vec<NaiveDateTime>, PyErr> {
         if self.is_non_bus_day(start) || self.is_non_bus_day(end) {
             return Err(PyValueError::new_err("`start` and `end` for a calendar `bus_date_range` must both be valid business days"));
         }
+        if step == 0 {
+            return Err(PyValueError::new_err("`step` must be non-zero."));
+        }
         let mut vec = Vec::new();
         let mut sample_date = *start;
-        while sample_date <= *end {
+        while (step > 0 && sample_date <= *end) || (step < 0 && sample_date >= *end) {
             vec.push(sample_date);
-            sample_date = self.add_bus_days(&sample_date, 1, false)?;
+            sample_date = self.add_bus_days(&sample_date, step, false)?;
         }
         Ok(vec)
     }
 
+    /// Return a vector of the first `n` business dates after `date`, excluding `date` itself.
+    fn take_bus_days(&self, date: &NaiveDateTime, n: usize) -> Vec<NaiveDateTime> {
+        let mut vec = Vec::with_capacity(n);
+        let mut sample_date = *date;
+        while vec.len() < n {
+            sample_date = self.roll_forward_bus_day(&(sample_date + Days::new(1)));
+            vec.push(sample_date);
+        }
+        vec
+    }
+
+    /// Return the payment dates of the periods of a leg, from its adjusted accrual schedule.
+    ///
+    /// Each period pays at its adjusted accrual end date lagged by `payment_lag` business days,
+    /// under lag rules, with the result allowing settlement. Negative lags pay in advance.
+    fn payment_dates(&self, aschedule: &[NaiveDateTime], payment_lag: i32) -> Vec<NaiveDateTime> {
+        aschedule
+            .iter()
+            .skip(1)
+            .map(|d| self.lag(d, payment_lag, true))
+            .collect()
+    }
+
+    /// Return the notional exchange dates of a leg, from its adjusted accrual schedule.
+    ///
+    /// Each adjusted accrual date, including the initial and final dates, is lagged by
+    /// `payment_lag_exchange` business days, under lag rules, with the result allowing
+    /// settlement.
+    fn exchange_dates(
+        &self,
+        aschedule: &[NaiveDateTime],
+        payment_lag_exchange: i32,
+    ) -> Vec<NaiveDateTime> {
+        aschedule
+            .iter()
+            .map(|d| self.lag(d, payment_lag_exchange, true))
+            .collect()
+    }
+
+    /// Returns whether the business days and settlement days of another calendar are the same
+    /// as this calendar for every date between a start and end, inclusive.
+    fn equivalent_between(
+        &self,
+        other: &dyn DateRoll,
+        start: &NaiveDateTime,
+        end: &NaiveDateTime,
+    ) -> bool {
+        let mut sample_date = *start;
+        while sample_date <= *end {
+            if self.is_bus_day(&sample_date) != other.is_bus_day(&sample_date)
+                || self.is_settlement(&sample_date) != other.is_settlement(&sample_date)
+            {
+                return false;
+            }
+            sample_date = sample_date + Days::new(1);
+        }
+        true
+    }
+
     /// Return a vector of calendar dates between a start and end, inclusive
     fn cal_date_range(
         &self,
         start: &NaiveDateTime,
         end: &NaiveDateTime,
     ) -> Result<Vec<NaiveDateTime>, PyErr> {
+        self.cal_date_range_step(start, end, 1)
+    }
+
+    /// Return a vector of calendar dates between a start and end, inclusive, stepping by `step`
+    /// calendar days.
+    ///
+    /// A negative `step` returns a reverse range, in which case `start` should be after `end`.
+    fn cal_date_range_step(
+        &self,
+        start: &NaiveDateTime,
+        end: &NaiveDateTime,
+        step: i32,
+    ) -> Result<Vec<NaiveDateTime>, PyErr> {
+        if step == 0 {
+            return Err(PyValueError::new_err("`step` must be non-zero."));
+        }
+        let days = Days::new(u64::from(step.unsigned_abs()));
         let mut vec = Vec::new();
         let mut sample_date = *start;
-        while sample_date <= *end {
+        while (step > 0 && sample_date <= *end) || (step < 0 && sample_date >= *end) {
             vec.push(sample_date);
-            sample_date = sample_date + Days::new(1);
+            sample_date = if step > 0 {
+                sample_date + days
+            } else {
+                sample_date - days
+            };
         }
         Ok(vec)
     }
 }
 
+/// Adjust a date by a number of business days under lag rules, see [DateRoll::lag].
+pub(crate) fn lag_bus_days<T: DateRoll + ?Sized>(
+    calendar: &T,
+    date: &NaiveDateTime,
+    days: i32,
+    settlement: bool,
+) -> NaiveDateTime {
+    if calendar.is_bus_day(date) {
+        return calendar.add_bus_days(date, days, settlement).unwrap();
+    }
+    match days.cmp(&0_i32) {
+        Ordering::Equal => calendar.roll_forward_bus_day(date),
+        Ordering::Less => calendar
+            .add_bus_days(&calendar.roll_backward_bus_day(date), days + 1, settlement)
+            .unwrap(),
+        Ordering::Greater => calendar
+            .add_bus_days(&calendar.roll_forward_bus_day(date), days - 1, settlement)
+            .unwrap(),
+    }
+}
+
+/// Add business days to a business day `date` by stepping over each calendar day.
+pub(crate) fn step_bus_days<T: DateRoll + ?Sized>(
+    calendar: &T,
+    date: &NaiveDateTime,
+    days: i32,
+) -> Result<NaiveDateTime, PyErr> {
+    if calendar.is_non_bus_day(date) {
+        return Err(PyValueError::new_err(
+            "Cannot add business days to an input `date` that is not a business day.",
+        ));
+    }
+    let mut new_date = *date;
+    let mut counter: i32 = 0;
+    if days < 0 {
+        // then we subtract business days
+        while counter > days {
+            new_date = calendar.roll_backward_bus_day(&(new_date - Days::new(1)));
+            counter -= 1;
+        }
+    } else {
+        // add business days
+        while counter < days {
+            new_date = calendar.roll_forward_bus_day(&(new_date + Days::new(1)));
+            counter += 1;
+        }
+    }
+    Ok(new_date)
+}
+
+/// Count the business days from `start`, inclusive, to `end`, exclusive, by stepping over each
+/// business day.
+pub(crate) fn step_bus_day_count<T: DateRoll + ?Sized>(
+    calendar: &T,
+    start: &NaiveDateTime,
+    end: &NaiveDateTime,
+) -> i64 {
+    let mut count = 0_i64;
+    let mut date = calendar.roll_forward_bus_day(start);
+    while date < *end {
+        count += 1;
+        date = calendar.roll_forward_bus_day(&(date + Days::new(1)));
+    }
+    count
+}
+
 /// Return a specific roll date given the `month`, `year` and `roll`.
 pub fn get_roll(year: i32, month: u32, roll: &RollDay) -> Result<NaiveDateTime, PyErr> {
     match roll {
@@ -403,6 +637,33 @@ mod tests {
         Cal::new(hols, vec![5, 6])
     }
 
+    #[test]
+    fn test_modifier_from_str() {
+        for modifier in [
+            Modifier::Act,
+            Modifier::F,
+            Modifier::ModF,
+            Modifier::P,
+            Modifier::ModP,
+        ] {
+            assert_eq!(Modifier::from_str(&modifier.to_string()).unwrap(), modifier);
+        }
+        assert_eq!(Modifier::from_str(" modf ").unwrap(), Modifier::ModF);
+        assert_eq!(Modifier::from_str("act").unwrap(), Modifier::Act);
+        assert!(Modifier::from_str("following").is_err());
+    }
+
+    #[test]
+    fn test_roll_day_from_str() {
+        assert_eq!(RollDay::from_str("EoM").unwrap(), RollDay::EoM {});
+        assert_eq!(RollDay::from_str("imm").unwrap(), RollDay::IMM {});
+        assert_eq!(RollDay::from_str("som").unwrap(), RollDay::SoM {});
+        assert_eq!(RollDay::from_str("15").unwrap(), RollDay::Int { day: 15 });
+        assert!(RollDay::from_str("0").is_err());
+        assert!(RollDay::from_str("32").is_err());
+        assert!(RollDay::from_str("mid").is_err());
+    }
+
     #[test]
     fn test_roll_with_settlement() {
         let cal = fixture_hol_cal();
@@ -765,4 +1026,114 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_add_days_beyond_i8() {
+        let cal = get_calendar_by_name("bus").unwrap();
+        assert_eq!(
+            cal.add_days(&ndt(2023, 1, 2), 365, &Modifier::F, false),
+            ndt(2024, 1, 2)
+        );
+        assert_eq!(
+            cal.add_days(&ndt(2024, 1, 2), -365, &Modifier::P, false),
+            ndt(2023, 1, 2)
+        );
+        // 52 weeks of 5 business days
+        assert_eq!(
+            cal.add_bus_days(&ndt(2023, 1, 2), 260, false).unwrap(),
+            ndt(2024, 1, 1)
+        );
+        assert_eq!(cal.lag(&ndt(2024, 1, 1), -260, false), ndt(2023, 1, 2));
+    }
+
+    #[test]
+    fn test_add_years() {
+        let cal = get_calendar_by_name("bus").unwrap();
+        let roll = RollDay::Int { day: 29 };
+        // a roll day of the 29th is the 28th of February in a non-leap year
+        assert_eq!(
+            cal.add_years(&ndt(2024, 2, 29), 5, &Modifier::ModF, &roll, false),
+            ndt(2029, 2, 28)
+        );
+        assert_eq!(
+            cal.add_years(&ndt(2024, 2, 29), -1, &Modifier::ModF, &roll, false),
+            ndt(2023, 2, 28)
+        );
+        assert_eq!(
+            cal.add_years(&ndt(2024, 2, 29), 3, &Modifier::Act, &roll, false),
+            cal.add_months(&ndt(2024, 2, 29), 36, &Modifier::Act, &roll, false)
+        );
+    }
+
+    #[test]
+    fn test_bus_date_range_step() {
+        let cal = fixture_hol_cal();
+        let result = cal
+            .bus_date_range_step(&ndt(2015, 9, 1), &ndt(2015, 9, 11), 2)
+            .unwrap();
+        assert_eq!(
+            result,
+            vec![
+                ndt(2015, 9, 1),
+                ndt(2015, 9, 3),
+                ndt(2015, 9, 8),
+                ndt(2015, 9, 10)
+            ]
+        );
+        let result = cal
+            .bus_date_range_step(&ndt(2015, 9, 8), &ndt(2015, 9, 3), -1)
+            .unwrap();
+        assert_eq!(
+            result,
+            vec![ndt(2015, 9, 8), ndt(2015, 9, 4), ndt(2015, 9, 3)]
+        );
+        assert!(cal
+            .bus_date_range_step(&ndt(2015, 9, 1), &ndt(2015, 9, 11), 0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_cal_date_range_step() {
+        let cal = fixture_hol_cal();
+        let result = cal
+            .cal_date_range_step(&ndt(2015, 9, 10), &ndt(2015, 9, 1), -4)
+            .unwrap();
+        assert_eq!(
+            result,
+            vec![ndt(2015, 9, 10), ndt(2015, 9, 6), ndt(2015, 9, 2)]
+        );
+    }
+
+    #[test]
+    fn test_take_bus_days() {
+        let cal = fixture_hol_cal();
+        let result = cal.take_bus_days(&ndt(2015, 9, 3), 3);
+        assert_eq!(
+            result,
+            vec![ndt(2015, 9, 4), ndt(2015, 9, 8), ndt(2015, 9, 9)]
+        );
+    }
+
+    #[test]
+    fn test_payment_dates() {
+        let cal = fixture_hol_cal();
+        let aschedule = vec![ndt(2015, 9, 1), ndt(2015, 9, 4), ndt(2015, 9, 10)];
+        let result = cal.payment_dates(&aschedule, 1);
+        assert_eq!(result, vec![ndt(2015, 9, 8), ndt(2015, 9, 11)]);
+        let result = cal.payment_dates(&aschedule, -2);
+        assert_eq!(result, vec![ndt(2015, 9, 2), ndt(2015, 9, 8)]);
+        let result = cal.payment_dates(&aschedule, 0);
+        assert_eq!(result, vec![ndt(2015, 9, 4), ndt(2015, 9, 10)]);
+    }
+
+    #[test]
+    fn test_exchange_dates() {
+        let cal = fixture_hol_cal();
+        let aschedule = vec![ndt(2015, 9, 1), ndt(2015, 9, 4), ndt(2015, 9, 10)];
+        let result = cal.exchange_dates(&aschedule, 1);
+        assert_eq!(
+            result,
+            vec![ndt(2015, 9, 2), ndt(2015, 9, 8), ndt(2015, 9, 11)]
+        );
+    }
 }
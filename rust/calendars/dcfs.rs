@@ -1,6 +1,14 @@
-use pyo3::{pyclass, pyfunction};
+use crate::calendars::calendar::{ndt, Cal, CalType};
+use crate::calendars::dateroll::{DateRoll, Modifier, RollDay};
+use crate::logging::event;
+use chrono::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, pyfunction, PyErr};
 use serde::{Deserialize, Serialize};
 use std::cmp::PartialEq;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Mutex;
 
 #[pyclass(module = "rateslib.rs", eq, eq_int)]
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
@@ -39,3 +47,572 @@ pub(crate) fn _get_convention_str(convention: Convention) -> String {
         Convention::Bus252 => "Bus252".to_string(),
     }
 }
+
+impl FromStr for Convention {
+    type Err = PyErr;
+
+    /// Parse a convention from its name, case insensitive, accepting the same aliases as the
+    /// Python `CONVENTIONS_MAP`, e.g. `"30360"`, `"BONDBASIS"` or `"ACTACTICMA"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_uppercase().as_str() {
+            "ACT365F" => Ok(Convention::Act365F),
+            "ACT365F+" => Ok(Convention::Act365FPlus),
+            "ACT360" => Ok(Convention::Act360),
+            "30360" | "360360" | "BONDBASIS" => Ok(Convention::Thirty360),
+            "30E360" | "EUROBONDBASIS" => Ok(Convention::ThirtyE360),
+            "30E360ISDA" => Ok(Convention::Thirty360ISDA),
+            "ACTACT" | "ACTACTISDA" => Ok(Convention::ActActISDA),
+            "ACTACTICMA" | "ACTACTISMA" | "ACTACTBOND" => Ok(Convention::ActActICMA),
+            "1" => Ok(Convention::One),
+            "1+" => Ok(Convention::OnePlus),
+            "BUS252" => Ok(Convention::Bus252),
+            _ => Err(PyValueError::new_err(format!(
+                "`convention`: {}, is not valid.",
+                s
+            ))),
+        }
+    }
+}
+
+impl Convention {
+    /// Calculate the day count fraction of a period under the convention.
+    ///
+    /// The additional arguments are only required by specific conventions:
+    ///
+    /// - `termination`: required by `Thirty360ISDA` and `ActActICMA`, the termination of the leg.
+    /// - `frequency_months`: required by `ActActICMA`, the number of months in a regular period.
+    /// - `stub`: required by `ActActICMA`, whether the period is a stub period.
+    /// - `roll`: used by `ActActICMA` to project regular periods when calculating stubs.
+    /// - `calendar`: required by `Bus252`, the business day calendar used to count days.
+    #[allow(clippy::too_many_arguments)]
+    pub fn dcf(
+        &self,
+        start: &NaiveDateTime,
+        end: &NaiveDateTime,
+        termination: Option<&NaiveDateTime>,
+        frequency_months: Option<i32>,
+        stub: Option<bool>,
+        roll: Option<&RollDay>,
+        calendar: Option<&dyn DateRoll>,
+    ) -> Result<f64, PyErr> {
+        match self {
+            Convention::One => Ok(1.0),
+            Convention::OnePlus => Ok(dcf_1plus(start, end)),
+            Convention::Act365F => Ok(days_between(start, end) / 365.0),
+            Convention::Act365FPlus => Ok(dcf_act365f_plus(start, end)),
+            Convention::Act360 => Ok(days_between(start, end) / 360.0),
+            Convention::ThirtyE360 => Ok(dcf_30e360(start, end)),
+            Convention::Thirty360 => Ok(dcf_30360(start, end)),
+            Convention::Thirty360ISDA => Ok(dcf_30e360_isda(
+                start,
+                end,
+                termination.ok_or_else(|| missing("termination"))?,
+            )),
+            Convention::ActActISDA => Ok(dcf_act_act_isda(start, end)),
+            Convention::ActActICMA => dcf_act_act_icma(
+                start,
+                end,
+                termination.ok_or_else(|| missing("termination"))?,
+                frequency_months.ok_or_else(|| missing("frequency_months"))?,
+                stub.ok_or_else(|| missing("stub"))?,
+                roll.unwrap_or(&RollDay::Unspecified {}),
+            ),
+            Convention::Bus252 => {
+                dcf_bus252(start, end, calendar.ok_or_else(|| missing("calendar"))?)
+            }
+        }
+    }
+}
+
+/// A sub-period of an accrual period with its contribution to the day count fraction.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SubPeriod {
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+    /// The day count fraction of the sub-period, in years.
+    pub fraction: f64,
+}
+
+/// The boundaries at which an accrual period is split into sub-periods.
+#[derive(Copy, Clone)]
+pub enum PeriodSplit<'a> {
+    /// Split on each 1st January, with each sub-period measured as actual days over the days in
+    /// its calendar year, as required by `ActActISDA`.
+    CalendarYear,
+    /// Split on the dates of regular periods, with each sub-period measured as actual days over
+    /// the days in its regular period, as required by `ActActICMA` stubs.
+    ///
+    /// Regular periods are projected backwards from the end of a `front` stub or forwards from
+    /// the start of a back stub.
+    RegularPeriod {
+        frequency_months: i32,
+        roll: &'a RollDay,
+        front: bool,
+    },
+}
+
+/// Split the accrual period from `start` to `end` into sub-periods, whose fractions sum to the
+/// day count fraction of the period.
+pub fn split_dcf(
+    start: &NaiveDateTime,
+    end: &NaiveDateTime,
+    split: &PeriodSplit,
+) -> Vec<SubPeriod> {
+    if start >= end {
+        return vec![];
+    }
+    match split {
+        PeriodSplit::CalendarYear => split_calendar_years(start, end),
+        PeriodSplit::RegularPeriod {
+            frequency_months,
+            roll,
+            front,
+        } => split_regular_periods(start, end, *frequency_months, roll, *front),
+    }
+}
+
+fn split_calendar_years(start: &NaiveDateTime, end: &NaiveDateTime) -> Vec<SubPeriod> {
+    (start.year()..=end.year())
+        .map(|y| {
+            let (year_start, year_end) = (ndt(y, 1, 1), ndt(y + 1, 1, 1));
+            let sub_start = year_start.max(*start);
+            let sub_end = year_end.min(*end);
+            SubPeriod {
+                start: sub_start,
+                end: sub_end,
+                fraction: days_between(&sub_start, &sub_end) / days_between(&year_start, &year_end),
+            }
+        })
+        .filter(|p| p.start < p.end)
+        .collect()
+}
+
+fn split_regular_periods(
+    start: &NaiveDateTime,
+    end: &NaiveDateTime,
+    frequency_months: i32,
+    roll: &RollDay,
+    front: bool,
+) -> Vec<SubPeriod> {
+    // Regular periods are projected without adjustment so any calendar is suitable.
+    let cal = Cal::new(vec![], vec![]);
+    let years = frequency_months as f64 / 12.0;
+    let mut sub_periods: Vec<SubPeriod> = vec![];
+    let mut i = 0;
+    if front {
+        let mut period_end = *end;
+        while *start < period_end {
+            i += 1;
+            let period_start =
+                cal.add_months(end, -i * frequency_months, &Modifier::Act, roll, false);
+            let sub_start = period_start.max(*start);
+            sub_periods.push(SubPeriod {
+                start: sub_start,
+                end: period_end,
+                fraction: days_between(&sub_start, &period_end)
+                    / days_between(&period_start, &period_end)
+                    * years,
+            });
+            period_end = period_start;
+        }
+        sub_periods.reverse();
+    } else {
+        let mut period_start = *start;
+        while period_start < *end {
+            i += 1;
+            let period_end =
+                cal.add_months(start, i * frequency_months, &Modifier::Act, roll, false);
+            let sub_end = period_end.min(*end);
+            sub_periods.push(SubPeriod {
+                start: period_start,
+                end: sub_end,
+                fraction: days_between(&period_start, &sub_end)
+                    / days_between(&period_start, &period_end)
+                    * years,
+            });
+            period_start = period_end;
+        }
+    }
+    sub_periods
+}
+
+fn missing(arg: &str) -> PyErr {
+    PyValueError::new_err(format!(
+        "`{}` must be supplied with specified `convention`.",
+        arg
+    ))
+}
+
+fn days_between(start: &NaiveDateTime, end: &NaiveDateTime) -> f64 {
+    (*end - *start).num_days() as f64
+}
+
+fn dcf_1plus(start: &NaiveDateTime, end: &NaiveDateTime) -> f64 {
+    (end.year() - start.year()) as f64 + (end.month() as f64 - start.month() as f64) / 12.0
+}
+
+fn dcf_act365f_plus(start: &NaiveDateTime, end: &NaiveDateTime) -> f64 {
+    let anniversary = |year: i32| {
+        NaiveDate::from_ymd_opt(year, start.month(), start.day())
+            .unwrap_or_else(|| NaiveDate::from_ymd_opt(year, start.month(), 28).unwrap())
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    };
+    if *end <= anniversary(start.year() + 1) {
+        days_between(start, end) / 365.0
+    } else if *end <= anniversary(end.year()) {
+        (end.year() - start.year()) as f64 + days_between(&anniversary(end.year()), end) / 365.0
+    } else {
+        (end.year() - start.year() - 1) as f64
+            + days_between(&anniversary(end.year() - 1), end) / 365.0
+    }
+}
+
+fn thirty_360(start: &NaiveDateTime, end: &NaiveDateTime, ds: u32, de: u32) -> f64 {
+    (end.year() - start.year()) as f64
+        + (end.month() as f64 - start.month() as f64) / 12.0
+        + (de as f64 - ds as f64) / 360.0
+}
+
+fn dcf_30360(start: &NaiveDateTime, end: &NaiveDateTime) -> f64 {
+    let ds = start.day().min(30);
+    let de = if ds == 30 {
+        end.day().min(30)
+    } else {
+        end.day()
+    };
+    thirty_360(start, end, ds, de)
+}
+
+fn dcf_30e360(start: &NaiveDateTime, end: &NaiveDateTime) -> f64 {
+    thirty_360(start, end, start.day().min(30), end.day().min(30))
+}
+
+fn dcf_30e360_isda(start: &NaiveDateTime, end: &NaiveDateTime, termination: &NaiveDateTime) -> f64 {
+    let is_end_feb = |date: &NaiveDateTime| {
+        date.month() == 2 && NaiveDate::from_ymd_opt(date.year(), 2, date.day() + 1).is_none()
+    };
+    let ds = if start.day() == 31 || is_end_feb(start) {
+        30
+    } else {
+        start.day()
+    };
+    let de = if end.day() == 31 || (is_end_feb(end) && end != termination) {
+        30
+    } else {
+        end.day()
+    };
+    thirty_360(start, end, ds, de)
+}
+
+fn dcf_act_act_isda(start: &NaiveDateTime, end: &NaiveDateTime) -> f64 {
+    split_dcf(start, end, &PeriodSplit::CalendarYear)
+        .iter()
+        .map(|p| p.fraction)
+        .sum()
+}
+
+fn dcf_act_act_icma(
+    start: &NaiveDateTime,
+    end: &NaiveDateTime,
+    termination: &NaiveDateTime,
+    frequency_months: i32,
+    stub: bool,
+    roll: &RollDay,
+) -> Result<f64, PyErr> {
+    if !stub && frequency_months < 13 {
+        return Ok(frequency_months as f64 / 12.0);
+    }
+    // Zero coupon periods are handled as stubs with an annual frequency.
+    let split = PeriodSplit::RegularPeriod {
+        frequency_months: frequency_months.min(12),
+        roll,
+        front: end != termination,
+    };
+    Ok(split_dcf(start, end, &split)
+        .iter()
+        .map(|p| p.fraction)
+        .sum())
+}
+
+fn dcf_bus252(
+    start: &NaiveDateTime,
+    end: &NaiveDateTime,
+    calendar: &dyn DateRoll,
+) -> Result<f64, PyErr> {
+    Ok(bus252_days(start, end, calendar)? as f64 / 252.0)
+}
+
+/// Count the business days, `d`, satisfying `start <= d < end`.
+///
+/// This is equivalent to including a rolled forward `start` and excluding `end` if it is a
+/// business day, or including a rolled backward `end` if it is not.
+fn bus252_days(
+    start: &NaiveDateTime,
+    end: &NaiveDateTime,
+    calendar: &dyn DateRoll,
+) -> Result<i64, PyErr> {
+    if end < start {
+        return Err(PyValueError::new_err(
+            "Cannot return negative DCF for `end` before `start`.",
+        ));
+    }
+    Ok(calendar.bus_day_count(start, end))
+}
+
+/// A calendar-aware Bus/252 accrual calculator caching business day counts per period.
+///
+/// Instruments using the *Bus252* convention often evaluate the same accrual periods many
+/// times. Counting business days is comparatively expensive so counts are stored per
+/// (`start`, `end`) pair once calculated.
+#[pyclass(module = "rateslib.rs")]
+pub struct Bus252Accrual {
+    pub(crate) calendar: CalType,
+    cache: Mutex<HashMap<(NaiveDateTime, NaiveDateTime), i64>>,
+}
+
+impl Bus252Accrual {
+    /// Create a new calculator with an empty cache.
+    pub fn new(calendar: CalType) -> Self {
+        Bus252Accrual {
+            calendar,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Return the number of business days accrued from `start` to `end`.
+    pub fn bus_days(&self, start: &NaiveDateTime, end: &NaiveDateTime) -> Result<i64, PyErr> {
+        let key = (*start, *end);
+        if let Some(count) = self.cache.lock().unwrap().get(&key) {
+            return Ok(*count);
+        }
+        let count = bus252_days(start, end, &self.calendar)?;
+        self.cache.lock().unwrap().insert(key, count);
+        Ok(count)
+    }
+
+    /// Return the *Bus252* day count fraction from `start` to `end`.
+    pub fn dcf(&self, start: &NaiveDateTime, end: &NaiveDateTime) -> Result<f64, PyErr> {
+        Ok(self.bus_days(start, end)? as f64 / 252.0)
+    }
+
+    /// Return the number of periods stored in the cache.
+    pub fn cache_len(&self) -> usize {
+        self.cache.lock().unwrap().len()
+    }
+
+    /// Remove all periods stored in the cache.
+    pub fn clear_cache(&self) {
+        event!(debug, "Bus252 accrual cache cleared");
+        self.cache.lock().unwrap().clear()
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dcf(c: Convention, start: NaiveDateTime, end: NaiveDateTime) -> f64 {
+        c.dcf(&start, &end, None, None, None, None, None).unwrap()
+    }
+
+    #[test]
+    fn test_convention_from_str() {
+        assert_eq!(Convention::from_str("act360").unwrap(), Convention::Act360);
+        assert_eq!(
+            Convention::from_str("Act365F+").unwrap(),
+            Convention::Act365FPlus
+        );
+        assert_eq!(
+            Convention::from_str("bondbasis").unwrap(),
+            Convention::Thirty360
+        );
+        assert_eq!(
+            Convention::from_str("30e360").unwrap(),
+            Convention::ThirtyE360
+        );
+        assert_eq!(
+            Convention::from_str("ACTACTICMA").unwrap(),
+            Convention::ActActICMA
+        );
+        assert!(Convention::from_str("act364").is_err());
+        for c in [
+            Convention::One,
+            Convention::OnePlus,
+            Convention::Act365F,
+            Convention::Act365FPlus,
+            Convention::Act360,
+            Convention::ThirtyE360,
+            Convention::Thirty360,
+            Convention::Thirty360ISDA,
+            Convention::ActActISDA,
+            Convention::ActActICMA,
+            Convention::Bus252,
+        ] {
+            assert_eq!(Convention::from_str(&_get_convention_str(c)).unwrap(), c);
+        }
+    }
+
+    #[test]
+    fn test_simple_conventions() {
+        let (s, e) = (ndt(2000, 1, 1), ndt(2000, 7, 1));
+        assert_eq!(dcf(Convention::One, s, e), 1.0);
+        assert_eq!(dcf(Convention::OnePlus, s, e), 0.5);
+        assert_eq!(dcf(Convention::Act360, s, e), 182.0 / 360.0);
+        assert_eq!(dcf(Convention::Act365F, s, e), 182.0 / 365.0);
+        let result = dcf(Convention::Act365FPlus, ndt(2000, 1, 1), ndt(2002, 3, 1));
+        assert!((result - (2.0 + 59.0 / 365.0)).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_thirty_360_conventions() {
+        let (s, e) = (ndt(2000, 1, 31), ndt(2000, 3, 31));
+        assert_eq!(dcf(Convention::Thirty360, s, e), 2.0 / 12.0);
+        assert_eq!(dcf(Convention::ThirtyE360, s, e), 2.0 / 12.0);
+        let (s, e) = (ndt(2000, 1, 30), ndt(2000, 2, 29));
+        assert!(Convention::Thirty360ISDA
+            .dcf(&s, &e, None, None, None, None, None)
+            .is_err());
+        let result = Convention::Thirty360ISDA
+            .dcf(&s, &e, Some(&ndt(2001, 1, 30)), None, None, None, None)
+            .unwrap();
+        assert_eq!(result, 1.0 / 12.0);
+        let result = Convention::Thirty360ISDA
+            .dcf(&s, &e, Some(&e), None, None, None, None)
+            .unwrap();
+        assert_eq!(result, 1.0 / 12.0 - 1.0 / 360.0);
+    }
+
+    #[test]
+    fn test_act_act_isda() {
+        let result = dcf(Convention::ActActISDA, ndt(1999, 7, 1), ndt(2000, 7, 1));
+        assert!((result - (184.0 / 365.0 + 182.0 / 366.0)).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_act_act_icma() {
+        let (s, e) = (ndt(1999, 2, 1), ndt(1999, 7, 1));
+        let regular = Convention::ActActICMA
+            .dcf(&s, &e, Some(&e), Some(6), Some(false), None, None)
+            .unwrap();
+        assert_eq!(regular, 0.5);
+        // short back stub
+        let result = Convention::ActActICMA
+            .dcf(&s, &e, Some(&e), Some(6), Some(true), None, None)
+            .unwrap();
+        assert!((result - 150.0 / 181.0 * 0.5).abs() < 1e-14);
+        // long front stub
+        let (s, e) = (ndt(1999, 2, 1), ndt(2000, 1, 15));
+        let result = Convention::ActActICMA
+            .dcf(
+                &s,
+                &e,
+                Some(&ndt(2001, 1, 15)),
+                Some(6),
+                Some(true),
+                None,
+                None,
+            )
+            .unwrap();
+        assert!((result - (0.5 + 164.0 / 181.0 * 0.5)).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_split_dcf_calendar_year() {
+        let result = split_dcf(
+            &ndt(1999, 7, 1),
+            &ndt(2001, 3, 1),
+            &PeriodSplit::CalendarYear,
+        );
+        assert_eq!(
+            result.iter().map(|p| (p.start, p.end)).collect::<Vec<_>>(),
+            vec![
+                (ndt(1999, 7, 1), ndt(2000, 1, 1)),
+                (ndt(2000, 1, 1), ndt(2001, 1, 1)),
+                (ndt(2001, 1, 1), ndt(2001, 3, 1)),
+            ]
+        );
+        assert_eq!(result[0].fraction, 184.0 / 365.0);
+        assert_eq!(result[1].fraction, 1.0);
+        assert_eq!(result[2].fraction, 59.0 / 365.0);
+        assert!(split_dcf(
+            &ndt(2000, 1, 1),
+            &ndt(2000, 1, 1),
+            &PeriodSplit::CalendarYear
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_split_dcf_regular_period() {
+        // long front stub with a quasi-coupon on 15th Jul 1999
+        let roll = RollDay::Unspecified {};
+        let split = PeriodSplit::RegularPeriod {
+            frequency_months: 6,
+            roll: &roll,
+            front: true,
+        };
+        let result = split_dcf(&ndt(1999, 2, 1), &ndt(2000, 1, 15), &split);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].end, ndt(1999, 7, 15));
+        assert!((result[0].fraction - 164.0 / 181.0 * 0.5).abs() < 1e-14);
+        assert_eq!(result[1].fraction, 0.5);
+
+        // short back stub
+        let split = PeriodSplit::RegularPeriod {
+            frequency_months: 6,
+            roll: &roll,
+            front: false,
+        };
+        let result = split_dcf(&ndt(1999, 2, 1), &ndt(1999, 7, 1), &split);
+        assert_eq!(result.len(), 1);
+        assert!((result[0].fraction - 150.0 / 181.0 * 0.5).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_bus252() {
+        let cal = Cal::new(vec![ndt(2024, 1, 3)], vec![5, 6]);
+        let dcf_ = |s: NaiveDateTime, e: NaiveDateTime| {
+            Convention::Bus252
+                .dcf(&s, &e, None, None, None, None, Some(&cal))
+                .unwrap()
+        };
+        // Mon to Mon, excluding Wed holiday
+        assert_eq!(dcf_(ndt(2024, 1, 1), ndt(2024, 1, 8)), 4.0 / 252.0);
+        // Sat to Sun contains no business days
+        assert_eq!(dcf_(ndt(2024, 1, 6), ndt(2024, 1, 7)), 0.0);
+        // Fri to Sun includes Fri
+        assert_eq!(dcf_(ndt(2024, 1, 5), ndt(2024, 1, 7)), 1.0 / 252.0);
+        // Sat to Tue includes Mon
+        assert_eq!(dcf_(ndt(2024, 1, 6), ndt(2024, 1, 9)), 1.0 / 252.0);
+        assert!(Convention::Bus252
+            .dcf(
+                &ndt(2024, 1, 6),
+                &ndt(2024, 1, 9),
+                None,
+                None,
+                None,
+                None,
+                None
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_bus252_accrual_cache() {
+        let cal = CalType::Cal(Cal::new(vec![ndt(2024, 1, 3)], vec![5, 6]));
+        let accrual = Bus252Accrual::new(cal.clone());
+        let (s, e) = (ndt(2024, 1, 1), ndt(2024, 1, 8));
+        assert_eq!(accrual.bus_days(&s, &e).unwrap(), 4);
+        assert_eq!(accrual.cache_len(), 1);
+        let expected = Convention::Bus252
+            .dcf(&s, &e, None, None, None, None, Some(&cal))
+            .unwrap();
+        assert_eq!(accrual.dcf(&s, &e).unwrap(), expected);
+        assert_eq!(accrual.cache_len(), 1);
+        assert!(accrual.dcf(&e, &s).is_err());
+        accrual.clear_cache();
+        assert_eq!(accrual.cache_len(), 0);
+    }
+}
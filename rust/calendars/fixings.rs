@@ -0,0 +1,204 @@
+use crate::calendars::dateroll::DateRoll;
+use chrono::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::PyErr;
+
+/// The daily observations of an RFR compounded over an accrual period.
+///
+/// The vectors are aligned: the rate observed on `observation_dates[i]` is weighted by
+/// `day_counts[i]` calendar days from `weighting_dates[i]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FixingDates {
+    /// The date of each rate fixing.
+    pub observation_dates: Vec<NaiveDateTime>,
+    /// The start of the period over which each fixing is weighted.
+    pub weighting_dates: Vec<NaiveDateTime>,
+    /// The number of calendar days over which each fixing is weighted.
+    pub day_counts: Vec<i64>,
+}
+
+/// Return the rate cut-off date of an RFR observation period ending on `date` with a lockout of
+/// `lockout_days` fixings.
+///
+/// This is the observation date of the last fixing which is not locked out, `lockout_days + 1`
+/// business days before `date`. Each of the last `lockout_days` fixings of the period is frozen
+/// at the fixing of the rate cut-off date. A lockout of zero days returns the business day before
+/// `date`, the observation date of the last fixing of the period.
+///
+/// # Errors
+///
+/// If `date` is not a business day of the `calendar`.
+pub fn lockout(
+    date: &NaiveDateTime,
+    lockout_days: u32,
+    calendar: &impl DateRoll,
+) -> Result<NaiveDateTime, PyErr> {
+    if !calendar.is_bus_day(date) {
+        return Err(PyValueError::new_err(
+            "`date` must be a business day of the calendar.",
+        ));
+    }
+    Ok(calendar.lag(date, -(lockout_days as i32) - 1, false))
+}
+
+/// Return the fixing dates of an RFR compounded over the accrual period from `accrual_start` to
+/// `accrual_end`, which must be business days of the `calendar`.
+///
+/// With a lookback each business day of the accrual period observes the fixing `lookback_days`
+/// business days earlier, and is weighted by the calendar days to the next business day of the
+/// accrual period. With an observation shift the observation period is the accrual period with
+/// both dates shifted back `lookback_days` business days, and each of its business days is
+/// weighted by the calendar days to the next business day of the observation period.
+///
+/// With a lockout the last `lockout_days` fixings observe the fixing of the rate cut-off date
+/// instead, see [lockout].
+///
+/// # Errors
+///
+/// If `accrual_start` is not before `accrual_end`, either is not a business day, or the lockout
+/// leaves no fixing of the period unfrozen.
+pub fn fixing_dates(
+    accrual_start: &NaiveDateTime,
+    accrual_end: &NaiveDateTime,
+    calendar: &impl DateRoll,
+    lookback_days: u32,
+    obs_shift: bool,
+    lockout_days: u32,
+) -> Result<FixingDates, PyErr> {
+    if accrual_start >= accrual_end {
+        return Err(PyValueError::new_err(
+            "`accrual_start` must be before `accrual_end`.",
+        ));
+    }
+    let lookback = -(lookback_days as i32);
+    let weighting = match obs_shift {
+        false => calendar.bus_date_range(accrual_start, accrual_end)?,
+        true => calendar.bus_date_range(
+            &calendar.lag(accrual_start, lookback, false),
+            &calendar.lag(accrual_end, lookback, false),
+        )?,
+    };
+    let n = weighting.len() - 1;
+    if lockout_days as usize >= n {
+        return Err(PyValueError::new_err(
+            "`lockout_days` must be fewer than the number of fixings in the period.",
+        ));
+    }
+    // the observation period ends `lookback_days` before the accrual period in either case
+    let cutoff = lockout(
+        &calendar.lag(accrual_end, lookback, false),
+        lockout_days,
+        calendar,
+    )?;
+    let observation_dates = match obs_shift {
+        false => weighting[..n]
+            .iter()
+            .map(|d| calendar.lag(d, lookback, false).min(cutoff))
+            .collect(),
+        true => weighting[..n].iter().map(|d| *d.min(&cutoff)).collect(),
+    };
+    let day_counts = weighting
+        .windows(2)
+        .map(|w| (w[1] - w[0]).num_days())
+        .collect();
+    Ok(FixingDates {
+        observation_dates,
+        weighting_dates: weighting[..n].to_vec(),
+        day_counts,
+    })
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, Cal};
+
+    fn cal() -> Cal {
+        // Monday 1st May 2017 is a holiday
+        Cal::new(vec![ndt(2017, 5, 1)], vec![5, 6])
+    }
+
+    #[test]
+    fn test_lookback() {
+        let f = fixing_dates(&ndt(2017, 4, 27), &ndt(2017, 5, 3), &cal(), 2, false, 0).unwrap();
+        assert_eq!(
+            f.weighting_dates,
+            vec![ndt(2017, 4, 27), ndt(2017, 4, 28), ndt(2017, 5, 2)]
+        );
+        assert_eq!(
+            f.observation_dates,
+            vec![ndt(2017, 4, 25), ndt(2017, 4, 26), ndt(2017, 4, 27)]
+        );
+        assert_eq!(f.day_counts, vec![1, 4, 1]);
+    }
+
+    #[test]
+    fn test_observation_shift() {
+        let f = fixing_dates(&ndt(2017, 5, 2), &ndt(2017, 5, 4), &cal(), 2, true, 0).unwrap();
+        // the observation period is Thursday 27th April to Tuesday 2nd May
+        assert_eq!(
+            f.observation_dates,
+            vec![ndt(2017, 4, 27), ndt(2017, 4, 28)]
+        );
+        assert_eq!(f.weighting_dates, f.observation_dates);
+        assert_eq!(f.day_counts, vec![1, 4]);
+
+        let f = fixing_dates(&ndt(2017, 5, 2), &ndt(2017, 5, 4), &cal(), 0, true, 0).unwrap();
+        assert_eq!(
+            f,
+            fixing_dates(&ndt(2017, 5, 2), &ndt(2017, 5, 4), &cal(), 0, false, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_lockout() {
+        // Tuesday 2nd May 2017 is two business days after Thursday 27th April
+        assert_eq!(
+            lockout(&ndt(2017, 5, 2), 1, &cal()).unwrap(),
+            ndt(2017, 4, 27)
+        );
+        assert_eq!(
+            lockout(&ndt(2017, 5, 2), 0, &cal()).unwrap(),
+            ndt(2017, 4, 28)
+        );
+        assert!(lockout(&ndt(2017, 5, 1), 1, &cal()).is_err());
+
+        let f = fixing_dates(&ndt(2017, 4, 25), &ndt(2017, 5, 4), &cal(), 0, false, 2).unwrap();
+        assert_eq!(
+            f.observation_dates,
+            vec![
+                ndt(2017, 4, 25),
+                ndt(2017, 4, 26),
+                ndt(2017, 4, 27),
+                ndt(2017, 4, 28),
+                ndt(2017, 4, 28),
+                ndt(2017, 4, 28)
+            ]
+        );
+        assert_eq!(f.day_counts, vec![1, 1, 1, 4, 1, 1]);
+
+        // with a lookback the frozen fixing is also observed earlier
+        let f = fixing_dates(&ndt(2017, 4, 27), &ndt(2017, 5, 4), &cal(), 1, false, 1).unwrap();
+        assert_eq!(
+            f.observation_dates,
+            vec![
+                ndt(2017, 4, 26),
+                ndt(2017, 4, 27),
+                ndt(2017, 4, 28),
+                ndt(2017, 4, 28)
+            ]
+        );
+        let shifted =
+            fixing_dates(&ndt(2017, 4, 27), &ndt(2017, 5, 4), &cal(), 1, true, 1).unwrap();
+        assert_eq!(shifted.observation_dates, f.observation_dates);
+
+        assert!(fixing_dates(&ndt(2017, 4, 27), &ndt(2017, 5, 2), &cal(), 0, false, 2).is_err());
+    }
+
+    #[test]
+    fn test_invalid_period() {
+        assert!(fixing_dates(&ndt(2017, 5, 2), &ndt(2017, 5, 2), &cal(), 2, false, 0).is_err());
+        assert!(fixing_dates(&ndt(2017, 4, 28), &ndt(2017, 5, 1), &cal(), 2, false, 0).is_err());
+    }
+}
@@ -0,0 +1,189 @@
+use crate::calendars::calendar::ndt;
+use crate::calendars::dateroll::{get_imm, DateRoll};
+use chrono::prelude::*;
+use chrono::Days;
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, PyErr};
+use serde::{Deserialize, Serialize};
+
+/// The exchange rules determining the reference period and expiry of a STIR futures contract.
+#[pyclass(module = "rateslib.rs", eq, eq_int)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum FuturesExpiry {
+    /// CME 3M SOFR: reference quarter from the IMM date of the contract month to the IMM date
+    /// three months later. Expires the business day before the end of the reference quarter.
+    Sofr3M,
+    /// CME 1M SOFR: reference period is the contract calendar month. Expires the last business
+    /// day of the contract month.
+    Sofr1M,
+    /// ICE 3M SONIA: reference quarter from the IMM date of the contract month to the IMM date
+    /// three months later. Expires the business day before the end of the reference quarter.
+    Sonia3M,
+    /// ICE 1M SONIA: reference period is the contract calendar month. Expires the last business
+    /// day of the contract month.
+    Sonia1M,
+    /// ICE 3M Euribor: reference quarter from the IMM date of the contract month to the IMM
+    /// date three months later. Expires two business days before the start of the reference
+    /// quarter.
+    Euribor3M,
+}
+
+impl FuturesExpiry {
+    /// Return the start and end of the reference period of the contract for the given month.
+    pub fn reference_period(&self, year: i32, month: u32) -> (NaiveDateTime, NaiveDateTime) {
+        match self {
+            FuturesExpiry::Sofr3M | FuturesExpiry::Sonia3M | FuturesExpiry::Euribor3M => {
+                let (end_year, end_month) = add_months(year, month, 3);
+                (get_imm(year, month), get_imm(end_year, end_month))
+            }
+            FuturesExpiry::Sofr1M | FuturesExpiry::Sonia1M => {
+                let (end_year, end_month) = add_months(year, month, 1);
+                (ndt(year, month, 1), ndt(end_year, end_month, 1))
+            }
+        }
+    }
+
+    /// Return the last trading date of the contract for the given month.
+    pub fn expiry(&self, year: i32, month: u32, calendar: &impl DateRoll) -> NaiveDateTime {
+        let (start, end) = self.reference_period(year, month);
+        match self {
+            FuturesExpiry::Sofr3M
+            | FuturesExpiry::Sonia3M
+            | FuturesExpiry::Sofr1M
+            | FuturesExpiry::Sonia1M => calendar.roll_backward_bus_day(&(end - Days::new(1))),
+            FuturesExpiry::Euribor3M => calendar.lag(&start, -2, false),
+        }
+    }
+}
+
+fn add_months(year: i32, month: u32, months: u32) -> (i32, u32) {
+    let m = month - 1 + months;
+    (year + (m / 12) as i32, m % 12 + 1)
+}
+
+/// Parse a futures contract code, e.g. *"H25"* or *"Z2030"*, into a year and month.
+///
+/// The month is identified by the standard futures month code letter. Two digit years are
+/// assumed to be in the 21st century.
+pub fn parse_contract_code(code: &str) -> Result<(i32, u32), PyErr> {
+    let code = code.trim().to_uppercase();
+    let err = || PyValueError::new_err(format!("Futures contract code is invalid: '{}'.", code));
+    let mut chars = code.chars();
+    let month = match chars.next().ok_or_else(err)? {
+        'F' => 1,
+        'G' => 2,
+        'H' => 3,
+        'J' => 4,
+        'K' => 5,
+        'M' => 6,
+        'N' => 7,
+        'Q' => 8,
+        'U' => 9,
+        'V' => 10,
+        'X' => 11,
+        'Z' => 12,
+        _ => return Err(err()),
+    };
+    let digits = chars.as_str();
+    let year: i32 = digits.parse().map_err(|_| err())?;
+    match digits.len() {
+        2 => Ok((2000 + year, month)),
+        4 => Ok((year, month)),
+        _ => Err(err()),
+    }
+}
+
+/// Return the next IMM date strictly after `date`.
+///
+/// If `serial` is *false* only quarterly IMM dates in March, June, September and December are
+/// considered.
+pub fn next_imm(date: &NaiveDateTime, serial: bool) -> NaiveDateTime {
+    let (mut year, mut month) = (date.year(), date.month());
+    loop {
+        let imm = get_imm(year, month);
+        if imm > *date && (serial || month % 3 == 0) {
+            return imm;
+        }
+        (year, month) = add_months(year, month, 1);
+    }
+}
+
+/// Return the previous IMM date strictly before `date`.
+///
+/// If `serial` is *false* only quarterly IMM dates in March, June, September and December are
+/// considered.
+pub fn previous_imm(date: &NaiveDateTime, serial: bool) -> NaiveDateTime {
+    let (mut year, mut month) = (date.year(), date.month());
+    loop {
+        let imm = get_imm(year, month);
+        if imm < *date && (serial || month % 3 == 0) {
+            return imm;
+        }
+        (year, month) = if month == 1 {
+            (year - 1, 12)
+        } else {
+            (year, month - 1)
+        };
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::named::get_calendar_by_name;
+
+    #[test]
+    fn test_parse_contract_code() {
+        assert_eq!(parse_contract_code("H25").unwrap(), (2025, 3));
+        assert_eq!(parse_contract_code("z2030").unwrap(), (2030, 12));
+        assert!(parse_contract_code("A25").is_err());
+        assert!(parse_contract_code("H5").is_err());
+    }
+
+    #[test]
+    fn test_next_previous_imm() {
+        assert_eq!(next_imm(&ndt(2024, 3, 20), false), ndt(2024, 6, 19));
+        assert_eq!(next_imm(&ndt(2024, 3, 20), true), ndt(2024, 4, 17));
+        assert_eq!(next_imm(&ndt(2024, 11, 30), false), ndt(2024, 12, 18));
+        assert_eq!(previous_imm(&ndt(2024, 3, 20), false), ndt(2023, 12, 20));
+        assert_eq!(previous_imm(&ndt(2024, 3, 21), false), ndt(2024, 3, 20));
+        assert_eq!(previous_imm(&ndt(2024, 1, 10), true), ndt(2023, 12, 20));
+    }
+
+    #[test]
+    fn test_sofr_3m_expiry() {
+        let nyc = get_calendar_by_name("nyc").unwrap();
+        let (start, end) = FuturesExpiry::Sofr3M.reference_period(2024, 3);
+        assert_eq!((start, end), (ndt(2024, 3, 20), ndt(2024, 6, 19)));
+        // 19th June 2024 is Juneteenth, the reference end is unadjusted.
+        assert_eq!(
+            FuturesExpiry::Sofr3M.expiry(2024, 3, &nyc),
+            ndt(2024, 6, 18)
+        );
+    }
+
+    #[test]
+    fn test_sonia_1m_expiry() {
+        let ldn = get_calendar_by_name("ldn").unwrap();
+        let (start, end) = FuturesExpiry::Sonia1M.reference_period(2024, 12);
+        assert_eq!((start, end), (ndt(2024, 12, 1), ndt(2025, 1, 1)));
+        assert_eq!(
+            FuturesExpiry::Sonia1M.expiry(2024, 12, &ldn),
+            ndt(2024, 12, 31)
+        );
+        assert_eq!(
+            FuturesExpiry::Sonia1M.expiry(2024, 8, &ldn),
+            ndt(2024, 8, 30)
+        );
+    }
+
+    #[test]
+    fn test_euribor_3m_expiry() {
+        let tgt = get_calendar_by_name("tgt").unwrap();
+        assert_eq!(
+            FuturesExpiry::Euribor3M.expiry(2024, 6, &tgt),
+            ndt(2024, 6, 17)
+        );
+    }
+}
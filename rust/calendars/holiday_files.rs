@@ -0,0 +1,273 @@
+//! Construct calendars from, and export them to, common holiday file formats.
+
+use crate::calendars::calendar::Cal;
+use chrono::prelude::*;
+use chrono::Days;
+use pyo3::exceptions::PyValueError;
+use pyo3::PyErr;
+use std::fs;
+use std::path::Path;
+
+const WEEKDAY_NAMES: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+/// Return the content of a file if `source` is the path to one, otherwise `source` itself.
+fn read_source(source: &str) -> Result<String, PyErr> {
+    if source.contains('\n') || !Path::new(source).is_file() {
+        return Ok(source.to_string());
+    }
+    fs::read_to_string(source)
+        .map_err(|e| PyValueError::new_err(format!("Cannot read holiday file `{source}`: {e}")))
+}
+
+/// Parse a date in either extended, `%Y-%m-%d`, or basic, `%Y%m%d`, ISO 8601 format.
+fn parse_date(s: &str) -> Option<NaiveDateTime> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(s, "%Y%m%d"))
+        .ok()
+        .map(|d| d.and_hms_opt(0, 0, 0).unwrap())
+}
+
+/// Parse holidays from lines containing one date, optionally followed by a label.
+fn parse_csv(content: &str) -> Result<Vec<NaiveDateTime>, PyErr> {
+    let mut holidays: Vec<NaiveDateTime> = vec![];
+    let mut header_allowed = true;
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let field = line
+            .split([',', ';', '\t', ' '])
+            .next()
+            .unwrap()
+            .trim_matches('"');
+        match parse_date(field) {
+            Some(date) => holidays.push(date),
+            None if header_allowed && field.eq_ignore_ascii_case("date") => {}
+            None => {
+                return Err(PyValueError::new_err(format!(
+                    "Cannot parse a holiday date from line {}: `{}`.",
+                    i + 1,
+                    line
+                )))
+            }
+        }
+        header_allowed = false;
+    }
+    Ok(holidays)
+}
+
+/// Parse holidays from the all-day and timed events of an iCalendar file.
+///
+/// An event with a date valued `DTEND` is a holiday on every date up to, and excluding, the
+/// end. Otherwise it is a holiday only on the date of `DTSTART`. Recurrence rules are not
+/// expanded.
+fn parse_ics(content: &str) -> Result<Vec<NaiveDateTime>, PyErr> {
+    // unfold continuation lines, which begin with a space or tab
+    let mut lines: Vec<String> = vec![];
+    for line in content.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.trim_end().to_string()),
+        }
+    }
+
+    let mut holidays: Vec<NaiveDateTime> = vec![];
+    let mut event: Option<(Option<NaiveDateTime>, Option<NaiveDateTime>)> = None;
+    for line in lines.iter() {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let property = name.split(';').next().unwrap().to_ascii_uppercase();
+        match (property.as_str(), event.as_mut()) {
+            ("BEGIN", None) if value.eq_ignore_ascii_case("VEVENT") => event = Some((None, None)),
+            ("END", Some((start, end))) if value.eq_ignore_ascii_case("VEVENT") => {
+                let start = start.ok_or_else(|| {
+                    PyValueError::new_err("iCalendar VEVENT has no valid `DTSTART`.")
+                })?;
+                let mut date = start;
+                loop {
+                    holidays.push(date);
+                    date = date + Days::new(1);
+                    if end.is_none_or(|e| date >= e) {
+                        break;
+                    }
+                }
+                event = None;
+            }
+            ("DTSTART", Some((start, _))) => {
+                *start = value.get(..8).and_then(parse_date);
+            }
+            ("DTEND", Some((_, end))) if value.len() == 8 => {
+                *end = parse_date(value);
+            }
+            _ => {}
+        }
+    }
+    if event.is_some() {
+        return Err(PyValueError::new_err(
+            "iCalendar VEVENT is not terminated by `END:VEVENT`.",
+        ));
+    }
+    Ok(holidays)
+}
+
+impl Cal {
+    /// Create a calendar from holidays listed one date per line, optionally followed by a label.
+    ///
+    /// `source` is either the path to a file or the content itself. Dates are ISO 8601, e.g.
+    /// `2024-12-25` or `20241225`, and may be followed by a comma, semicolon, tab or space
+    /// separated label, which is ignored. Blank lines, lines starting with `#` and a leading
+    /// `date` header are skipped.
+    pub fn from_csv(source: &str, week_mask: Vec<u8>) -> Result<Cal, PyErr> {
+        Ok(Cal::new(parse_csv(&read_source(source)?)?, week_mask))
+    }
+
+    /// Create a calendar from the events of an iCalendar (RFC 5545) file.
+    ///
+    /// `source` is either the path to a file or the content itself. Every date spanned by a
+    /// `VEVENT` is a holiday.
+    pub fn from_ics(source: &str, week_mask: Vec<u8>) -> Result<Cal, PyErr> {
+        Ok(Cal::new(parse_ics(&read_source(source)?)?, week_mask))
+    }
+
+    /// Return the holidays of the calendar in sorted order, one ISO 8601 date per line.
+    ///
+    /// The output can be reloaded with [Cal::from_csv]. The `week_mask` is not included.
+    pub fn to_csv(&self) -> String {
+        let mut holidays: Vec<&NaiveDateTime> = self.holidays.iter().collect();
+        holidays.sort();
+        holidays
+            .into_iter()
+            .map(|d| format!("{}\n", d.format("%Y-%m-%d")))
+            .collect()
+    }
+
+    /// Return a human readable listing of the week mask and the holidays, with weekday names,
+    /// falling in the years from `start_year` to `end_year` inclusive.
+    pub fn describe(&self, start_year: i32, end_year: i32) -> String {
+        let mut week_mask: Vec<&Weekday> = self.week_mask.iter().collect();
+        week_mask.sort_by_key(|w| w.num_days_from_monday());
+        let mut holidays: Vec<&NaiveDateTime> = self
+            .holidays
+            .iter()
+            .filter(|d| start_year <= d.year() && d.year() <= end_year)
+            .collect();
+        holidays.sort();
+
+        let mut output = format!(
+            "Week mask: {}\nHolidays {}-{}: {}\n",
+            week_mask
+                .iter()
+                .map(|w| WEEKDAY_NAMES[w.num_days_from_monday() as usize])
+                .collect::<Vec<&str>>()
+                .join(", "),
+            start_year,
+            end_year,
+            holidays.len(),
+        );
+        for date in holidays {
+            output.push_str(&format!(
+                "{}  {}\n",
+                date.format("%Y-%m-%d"),
+                date.format("%A")
+            ));
+        }
+        output
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, DateRoll};
+
+    #[test]
+    fn test_from_csv() {
+        let content = "date,name\n\
+                       # UK bank holidays\n\
+                       2024-12-25,Christmas Day\n\
+                       20241226 Boxing Day\n\
+                       \n\
+                       2025-01-01\n";
+        let cal = Cal::from_csv(content, vec![5, 6]).unwrap();
+        assert_eq!(
+            cal.holidays.into_iter().collect::<Vec<_>>(),
+            vec![ndt(2024, 12, 25), ndt(2024, 12, 26), ndt(2025, 1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_from_csv_file() {
+        let path = std::env::temp_dir().join("rateslib_test_from_csv.csv");
+        fs::write(&path, "2024-05-27;Spring Bank Holiday\n").unwrap();
+        let cal = Cal::from_csv(path.to_str().unwrap(), vec![5, 6]).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(cal.is_holiday(&ndt(2024, 5, 27)));
+    }
+
+    #[test]
+    fn test_from_csv_invalid_line() {
+        let result = Cal::from_csv("2024-12-25\n25/12/2024\n", vec![5, 6]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_from_ics() {
+        let content = "BEGIN:VCALENDAR\r\n\
+                       VERSION:2.0\r\n\
+                       BEGIN:VEVENT\r\n\
+                       DTSTART;VALUE=DATE:20241225\r\n\
+                       DTEND;VALUE=DATE:20241227\r\n\
+                       SUMMARY:Christmas and\r\n  Boxing Day\r\n\
+                       END:VEVENT\r\n\
+                       BEGIN:VEVENT\r\n\
+                       DTSTART:20250101T000000Z\r\n\
+                       DTEND:20250101T235959Z\r\n\
+                       SUMMARY:New Year's Day\r\n\
+                       END:VEVENT\r\n\
+                       END:VCALENDAR\r\n";
+        let cal = Cal::from_ics(content, vec![5, 6]).unwrap();
+        assert_eq!(
+            cal.holidays.into_iter().collect::<Vec<_>>(),
+            vec![ndt(2024, 12, 25), ndt(2024, 12, 26), ndt(2025, 1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_to_csv_roundtrip() {
+        let cal = Cal::new(vec![ndt(2025, 1, 1), ndt(2024, 12, 25)], vec![5, 6]);
+        let csv = cal.to_csv();
+        assert_eq!(csv, "2024-12-25\n2025-01-01\n");
+        let cal2 = Cal::from_csv(&csv, vec![5, 6]).unwrap();
+        assert_eq!(cal, cal2);
+    }
+
+    #[test]
+    fn test_describe() {
+        let cal = Cal::new(
+            vec![ndt(2025, 1, 1), ndt(2024, 12, 25), ndt(2023, 12, 25)],
+            vec![6, 5],
+        );
+        let expected = "Week mask: Saturday, Sunday\n\
+                        Holidays 2024-2025: 2\n\
+                        2024-12-25  Wednesday\n\
+                        2025-01-01  Wednesday\n";
+        assert_eq!(cal.describe(2024, 2025), expected);
+    }
+
+    #[test]
+    fn test_from_ics_missing_start() {
+        let content = "BEGIN:VEVENT\nSUMMARY:Holiday\nEND:VEVENT\n";
+        assert!(Cal::from_ics(content, vec![5, 6]).is_err());
+    }
+}
@@ -94,17 +94,28 @@
 //! ```
 
 mod calendar;
-pub use crate::calendars::calendar::{ndt, Cal, CalType, NamedCal, UnionCal};
+pub use crate::calendars::calendar::{adjust_dates, ndt, Cal, CalType, NamedCal, UnionCal};
 
 pub mod named;
 pub use crate::calendars::named::get_calendar_by_name;
 
+mod holiday_files;
+
 mod dateroll;
 pub use crate::calendars::dateroll::{get_imm, get_roll, DateRoll, Modifier, RollDay};
 
+mod rules;
+pub use crate::calendars::rules::{easter, easter_holidays, easter_monday, good_friday};
+
+mod futures;
+pub use crate::calendars::futures::{next_imm, parse_contract_code, previous_imm, FuturesExpiry};
+
+mod fixings;
+pub use crate::calendars::fixings::{fixing_dates, lockout, FixingDates};
+
 mod dcfs;
-pub use crate::calendars::dcfs::Convention;
 pub(crate) use crate::calendars::dcfs::_get_convention_str;
+pub use crate::calendars::dcfs::{split_dcf, Bus252Accrual, Convention, PeriodSplit, SubPeriod};
 
 mod serde;
 
@@ -1,5 +1,7 @@
 //! Define a calendar which asserts every possible date as a business day.
 
 pub const WEEKMASK: &[u8] = &[]; // all days are weekdays
+pub const EARLY_CLOSES: &[&str] = &[]; // partial business days and their close time
 pub const RULES: &[&str] = &[];
+pub const EASTER_OFFSETS: &[i64] = &[]; // days from Easter Sunday
 pub const HOLIDAYS: &[&str] = &[]; // no specific holidays
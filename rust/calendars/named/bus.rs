@@ -1,5 +1,7 @@
 //! Define a generic Western business weekday calendar without any specific holidays.
 
 pub const WEEKMASK: &[u8] = &[5, 6]; // Saturday and Sunday weekend
+pub const EARLY_CLOSES: &[&str] = &[]; // partial business days and their close time
 pub const RULES: &[&str] = &[];
+pub const EASTER_OFFSETS: &[i64] = &[]; // days from Easter Sunday
 pub const HOLIDAYS: &[&str] = &[]; // no specific holidays
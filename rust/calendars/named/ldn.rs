@@ -1,6 +1,15 @@
 //! Define a London business day holiday calendar, aligned with SONIA publication.
 
 pub const WEEKMASK: &[u8] = &[5, 6]; // Saturday and Sunday weekend
+                                     // partial business days and their close time: 12:30 half days before Christmas and New Year
+pub const EARLY_CLOSES: &[&str] = &[
+    "2023-12-22 12:30:00",
+    "2023-12-29 12:30:00",
+    "2024-12-24 12:30:00",
+    "2024-12-31 12:30:00",
+    "2025-12-24 12:30:00",
+    "2025-12-31 12:30:00",
+];
 pub const RULES: &[&str] = &[
     "Jan 1: Sat,Sun->Mon (New Year)",
     "Fri before Easter (Easter Friday)",
@@ -11,6 +20,7 @@ pub const RULES: &[&str] = &[
     "Dec 25: Sat,Sun->Mon (Christmas)",
     "Dec 26: Sat->Mon,Sun->Tue (Boxing Day)",
 ];
+pub const EASTER_OFFSETS: &[i64] = &[-2, 1]; // days from Easter Sunday: Good Friday and Easter Monday
 pub const HOLIDAYS: &[&str] = &[
     "1970-01-01 00:00:00",
     "1970-03-27 00:00:00",
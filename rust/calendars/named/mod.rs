@@ -1,5 +1,6 @@
 //! Static data for pre-existing named holiday calendars.
 //!
+//! Holidays relative to Easter are generated by rule for the years after the static data.
 
 pub mod all;
 pub mod bus;
@@ -16,7 +17,8 @@ pub mod zur;
 pub mod wlg;
 
 use crate::calendars::calendar::Cal;
-use chrono::NaiveDateTime;
+use crate::calendars::rules::{easter_holidays, RULES_HORIZON};
+use chrono::{Datelike, NaiveDateTime, NaiveTime};
 use pyo3::exceptions::PyValueError;
 use pyo3::PyErr;
 use std::collections::HashMap;
@@ -74,6 +76,78 @@ fn get_holidays_by_name(name: &str) -> Result<Vec<NaiveDateTime>, PyErr> {
     }
 }
 
+fn get_early_closes_by_name(name: &str) -> Result<Vec<(NaiveDateTime, NaiveTime)>, PyErr> {
+    let hmap: HashMap<&str, &[&str]> = HashMap::from([
+        ("all", all::EARLY_CLOSES),
+        ("bus", bus::EARLY_CLOSES),
+        ("nyc", nyc::EARLY_CLOSES),
+        ("fed", nyc::EARLY_CLOSES),
+        ("tgt", tgt::EARLY_CLOSES),
+        ("ldn", ldn::EARLY_CLOSES),
+        ("stk", stk::EARLY_CLOSES),
+        ("osl", osl::EARLY_CLOSES),
+        ("zur", zur::EARLY_CLOSES),
+        ("tro", tro::EARLY_CLOSES),
+        ("tyo", tyo::EARLY_CLOSES),
+        ("syd", syd::EARLY_CLOSES),
+        ("wlg", wlg::EARLY_CLOSES),
+    ]);
+    match hmap.get(name) {
+        None => Err(PyValueError::new_err(format!(
+            "'{}' is not found in list of existing calendars.",
+            name
+        ))),
+        Some(value) => value
+            .iter()
+            .map(
+                |x| match NaiveDateTime::parse_from_str(x, "%Y-%m-%d %H:%M:%S") {
+                    Ok(dt) => Ok((dt.date().into(), dt.time())),
+                    Err(_) => Err(PyValueError::new_err(format!(
+                        "Early close '{}' of calendar '{}' is not a valid datetime.",
+                        x, name
+                    ))),
+                },
+            )
+            .collect(),
+    }
+}
+
+fn get_easter_offsets_by_name(name: &str) -> Result<&'static [i64], PyErr> {
+    let hmap: HashMap<&str, &[i64]> = HashMap::from([
+        ("all", all::EASTER_OFFSETS),
+        ("bus", bus::EASTER_OFFSETS),
+        ("nyc", nyc::EASTER_OFFSETS),
+        ("fed", nyc::EASTER_OFFSETS),
+        ("tgt", tgt::EASTER_OFFSETS),
+        ("ldn", ldn::EASTER_OFFSETS),
+        ("stk", stk::EASTER_OFFSETS),
+        ("osl", osl::EASTER_OFFSETS),
+        ("zur", zur::EASTER_OFFSETS),
+        ("tro", tro::EASTER_OFFSETS),
+        ("tyo", tyo::EASTER_OFFSETS),
+        ("syd", syd::EASTER_OFFSETS),
+        ("wlg", wlg::EASTER_OFFSETS),
+    ]);
+    match hmap.get(name) {
+        None => Err(PyValueError::new_err(format!(
+            "'{}' is not found in list of existing calendars.",
+            name
+        ))),
+        Some(value) => Ok(value),
+    }
+}
+
+/// Return the holidays of a named calendar, being its static data extended by the holidays
+/// relative to Easter in each year after the static data, up to [RULES_HORIZON].
+fn get_holidays_with_rules_by_name(name: &str) -> Result<Vec<NaiveDateTime>, PyErr> {
+    let mut holidays = get_holidays_by_name(name)?;
+    let offsets = get_easter_offsets_by_name(name)?;
+    if let Some(last) = holidays.iter().map(|d| d.year()).max() {
+        holidays.extend(easter_holidays(offsets, last + 1..=RULES_HORIZON));
+    }
+    Ok(holidays)
+}
+
 // fn get_rules_by_name(name: &str) -> Result<Vec<&str>, PyErr> {
 //     let hmap: HashMap<&str, &[&str]> = HashMap::from([
 //         ("all", all::RULES),
@@ -108,17 +182,19 @@ fn get_holidays_by_name(name: &str) -> Result<Vec<NaiveDateTime>, PyErr> {
 /// ```
 pub fn get_calendar_by_name(name: &str) -> Result<Cal, PyErr> {
     Ok(Cal::new(
-        get_holidays_by_name(name)?,
+        get_holidays_with_rules_by_name(name)?,
         get_weekmask_by_name(name)?,
         // get_rules_by_name(name)?
-    ))
+    )
+    .with_early_closes(get_early_closes_by_name(name)?))
 }
 
 // UNIT TESTS
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::calendars::DateRoll;
+    use crate::calendars::{easter, easter_monday, good_friday, ndt, DateRoll, NamedCal};
+    use chrono::Days;
 
     #[test]
     fn test_get_weekmask() {
@@ -126,6 +202,30 @@ mod tests {
         assert_eq!(result, vec![5, 6]);
     }
 
+    #[test]
+    fn test_get_early_closes() {
+        let result = get_early_closes_by_name("bus").unwrap();
+        assert_eq!(result, vec![]);
+    }
+
+    #[test]
+    fn test_named_early_closes() {
+        let two_pm = NaiveTime::from_hms_opt(14, 0, 0).unwrap();
+        let half_day = NaiveTime::from_hms_opt(12, 30, 0).unwrap();
+        let nyc = NamedCal::try_new("nyc").unwrap();
+        assert_eq!(nyc.close_time(&ndt(2024, 11, 29)), Some(two_pm));
+        assert_eq!(nyc.close_time(&ndt(2024, 12, 24)), Some(two_pm));
+        assert_eq!(nyc.close_time(&ndt(2024, 12, 23)), None);
+        let fed = NamedCal::try_new("fed").unwrap();
+        assert_eq!(fed.close_time(&ndt(2025, 7, 3)), Some(two_pm));
+        let ldn = NamedCal::try_new("ldn").unwrap();
+        assert_eq!(ldn.close_time(&ndt(2024, 12, 31)), Some(half_day));
+        // a union observes the earliest close
+        let union = NamedCal::try_new("ldn,nyc").unwrap();
+        assert_eq!(union.close_time(&ndt(2024, 12, 24)), Some(half_day));
+        assert_eq!(union.close_time(&ndt(2024, 11, 29)), Some(two_pm));
+    }
+
     #[test]
     fn test_get_holidays() {
         let result = get_holidays_by_name("bus").unwrap();
@@ -169,6 +269,46 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_easter_rules_agree_with_static_data() {
+        for name in ["ldn", "tgt"] {
+            let cal = get_calendar_by_name(name).unwrap();
+            for year in 1970..2200 {
+                assert!(cal.is_holiday(&good_friday(year)));
+                assert!(cal.is_holiday(&easter_monday(year)));
+            }
+        }
+        for name in [
+            "nyc", "tgt", "ldn", "stk", "osl", "zur", "tro", "syd", "wlg",
+        ] {
+            let holidays = get_holidays_by_name(name).unwrap();
+            let offsets = get_easter_offsets_by_name(name).unwrap();
+            for date in easter_holidays(offsets, 1970..=2200) {
+                assert!(holidays.contains(&date), "{} {}", name, date);
+            }
+        }
+    }
+
+    #[test]
+    fn test_easter_rules_beyond_static_data() {
+        let holidays = get_holidays_by_name("tgt").unwrap();
+        assert!(holidays.iter().all(|d| d.year() <= 2200));
+        let tgt = get_calendar_by_name("tgt").unwrap();
+        let osl = get_calendar_by_name("osl").unwrap();
+        for year in [2201, 2250, RULES_HORIZON] {
+            assert!(tgt.is_holiday(&good_friday(year)));
+            assert!(tgt.is_holiday(&easter_monday(year)));
+            assert!(osl.is_holiday(&(easter(year) + Days::new(50))));
+        }
+        // 2230-04-05 is the Monday after Easter, otherwise a business day
+        assert_eq!(easter_monday(2230), ndt(2230, 4, 5));
+        assert!(!tgt.is_bus_day(&ndt(2230, 4, 5)));
+        assert!(tgt.is_bus_day(&ndt(2230, 4, 6)));
+        assert!(get_calendar_by_name("tyo")
+            .unwrap()
+            .is_bus_day(&ndt(2230, 4, 5)));
+    }
+
     #[test]
     fn test_ldn() {
         let cal = get_calendar_by_name("ldn").unwrap();
@@ -1,6 +1,27 @@
 //! Define a New York business day calendar, aligned with SOFR publication.
 
 pub const WEEKMASK: &[u8] = &[5, 6]; // Saturday and Sunday weekend
+                                     // partial business days and their close time: SIFMA recommended 2pm early closes
+pub const EARLY_CLOSES: &[&str] = &[
+    "2023-04-06 14:00:00",
+    "2023-05-26 14:00:00",
+    "2023-07-03 14:00:00",
+    "2023-11-24 14:00:00",
+    "2023-12-22 14:00:00",
+    "2023-12-29 14:00:00",
+    "2024-03-28 14:00:00",
+    "2024-05-24 14:00:00",
+    "2024-07-03 14:00:00",
+    "2024-11-29 14:00:00",
+    "2024-12-24 14:00:00",
+    "2024-12-31 14:00:00",
+    "2025-04-17 14:00:00",
+    "2025-05-23 14:00:00",
+    "2025-07-03 14:00:00",
+    "2025-11-28 14:00:00",
+    "2025-12-24 14:00:00",
+    "2025-12-31 14:00:00",
+];
 pub const RULES: &[&str] = &[
     "Jan 1: Sun->Mon (New Year)",
     "Jan 3rd Mon (Martin Luther King Jr.)",
@@ -16,6 +37,7 @@ pub const RULES: &[&str] = &[
     "Dec 25: Sat->Fri,Sun->Mon (Christmas)",
     "Note: Special additional dates.",
 ];
+pub const EASTER_OFFSETS: &[i64] = &[-2]; // days from Easter Sunday: Good Friday
 pub const HOLIDAYS: &[&str] = &[
     "1970-01-01 00:00:00",
     "1970-02-16 00:00:00",
@@ -1,6 +1,7 @@
 //! Define an Oslo business day calendar, aligned with NOWA publication.
 
 pub const WEEKMASK: &[u8] = &[5, 6]; // Saturday and Sunday weekend
+pub const EARLY_CLOSES: &[&str] = &[]; // partial business days and their close time
 pub const RULES: &[&str] = &[
     "Jan 1 (New Year)",
     "Thu before Easter (Maundy Thursday)",
@@ -14,6 +15,7 @@ pub const RULES: &[&str] = &[
     "Dec 25 (Christmas)",
     "Dec 26 (Boxing Day)",
 ];
+pub const EASTER_OFFSETS: &[i64] = &[-3, -2, 1, 39, 50]; // days from Easter Sunday: Maundy Thursday, Good Friday, Easter Monday, Ascension and Whit Monday
 pub const HOLIDAYS: &[&str] = &[
     "1970-01-01 00:00:00",
     "1970-03-26 00:00:00",
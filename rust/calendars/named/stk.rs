@@ -1,6 +1,7 @@
 //! Define a Stockholm business day calendar, aligned with SWESTR publication.
 
 pub const WEEKMASK: &[u8] = &[5, 6]; // Saturday and Sunday weekend
+pub const EARLY_CLOSES: &[&str] = &[]; // partial business days and their close time
 pub const RULES: &[&str] = &[
     "Jan 1 (New Year)",
     "Jan 6 (Epiphany)",
@@ -15,6 +16,7 @@ pub const RULES: &[&str] = &[
     "Dec 26 (Boxing Day)",
     "Dec 31 (New Year's Eve)",
 ];
+pub const EASTER_OFFSETS: &[i64] = &[-2, 1, 39]; // days from Easter Sunday: Good Friday, Easter Monday and Ascension
 pub const HOLIDAYS: &[&str] = &[
     "1970-01-01 00:00:00",
     "1970-01-06 00:00:00",
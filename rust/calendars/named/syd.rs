@@ -1,6 +1,7 @@
 //! Define a Sydney business day calendar, aligned with AONIA publication.
 
 pub const WEEKMASK: &[u8] = &[5, 6]; // Saturday and Sunday weekend
+pub const EARLY_CLOSES: &[&str] = &[]; // partial business days and their close time
 pub const RULES: &[&str] = &[
     "Jan 1 (New Year)",
     "Jan 26 (Australia)",
@@ -11,6 +12,7 @@ pub const RULES: &[&str] = &[
     "Dec 25 (Christmas)",
     "Dec 26 (Boxing Day)",
 ];
+pub const EASTER_OFFSETS: &[i64] = &[-2, 1]; // days from Easter Sunday: Good Friday and Easter Monday
 pub const HOLIDAYS: &[&str] = &[
     "1970-01-01 00:00:00",
     "1970-01-26 00:00:00",
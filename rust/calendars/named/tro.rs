@@ -1,6 +1,7 @@
 //! Define a Toronto business day calendar, aligned with CORRA publication.
 
 pub const WEEKMASK: &[u8] = &[5, 6]; // Saturday and Sunday weekend
+pub const EARLY_CLOSES: &[&str] = &[]; // partial business days and their close time
 pub const RULES: &[&str] = &[
     "Jan 1: Sat,Sun->Mon (New Year)",
     "Feb 3rd Mon (Family)",
@@ -15,6 +16,7 @@ pub const RULES: &[&str] = &[
     "Dec 25: Sat,Sun->Mon (Christmas)",
     "Dec 26: Sat->Mon,Sun->Tue (Boxing Day)",
 ];
+pub const EASTER_OFFSETS: &[i64] = &[-2]; // days from Easter Sunday: Good Friday
 pub const HOLIDAYS: &[&str] = &[
     "1970-01-01 00:00:00",
     "1970-02-16 00:00:00",
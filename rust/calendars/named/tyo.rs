@@ -1,6 +1,7 @@
 //! Define a Tokyo business day calendar, aligned with TONA publication.
 
 pub const WEEKMASK: &[u8] = &[5, 6]; // Saturday and Sunday weekend
+pub const EARLY_CLOSES: &[&str] = &[]; // partial business days and their close time
 pub const RULES: &[&str] = &[
     "Jan 1 (New Year)",
     "Jan 2 (New Year)",
@@ -24,6 +25,7 @@ pub const RULES: &[&str] = &[
     "Dec 31 (New Year)",
     "Note: 2020 Olympics adjustments.",
 ];
+pub const EASTER_OFFSETS: &[i64] = &[]; // days from Easter Sunday
 pub const HOLIDAYS: &[&str] = &[
     "1970-01-01 00:00:00",
     "1970-01-02 00:00:00",
@@ -1,6 +1,7 @@
 //! Define a Zurich business day calendar, aligned with SARON publication.
 
 pub const WEEKMASK: &[u8] = &[5, 6]; // Saturday and Sunday weekend
+pub const EARLY_CLOSES: &[&str] = &[]; // partial business days and their close time
 pub const RULES: &[&str] = &[
     "Jan 1 (New Year)",
     "Jan 2 (Berchtoldstag)",
@@ -13,6 +14,7 @@ pub const RULES: &[&str] = &[
     "Dec 25 (Christmas)",
     "Dec 26 (Boxing)",
 ];
+pub const EASTER_OFFSETS: &[i64] = &[-2, 1, 39, 50]; // days from Easter Sunday: Good Friday, Easter Monday, Ascension and Whit Monday
 pub const HOLIDAYS: &[&str] = &[
     "1970-01-01 00:00:00",
     "1970-01-02 00:00:00",
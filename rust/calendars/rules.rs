@@ -0,0 +1,92 @@
+use crate::calendars::calendar::ndt;
+use chrono::prelude::*;
+use chrono::Days;
+use std::ops::RangeInclusive;
+
+/// Return the date of Easter Sunday in the given year of the Gregorian calendar.
+///
+/// Uses the anonymous Gregorian computus (Meeus/Jones/Butcher algorithm).
+///
+/// ```rust
+/// # use rateslib::calendars::{easter, ndt};
+/// assert_eq!(easter(2024), ndt(2024, 3, 31));
+/// ```
+pub fn easter(year: i32) -> NaiveDateTime {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    ndt(year, month as u32, day as u32)
+}
+
+/// Return the date of Good Friday, two days before Easter Sunday, in the given year.
+pub fn good_friday(year: i32) -> NaiveDateTime {
+    easter(year) - Days::new(2)
+}
+
+/// Return the date of Easter Monday, the day after Easter Sunday, in the given year.
+pub fn easter_monday(year: i32) -> NaiveDateTime {
+    easter(year) + Days::new(1)
+}
+
+/// The last year for which the holidays of named calendars are generated by rules beyond their
+/// static data.
+pub(crate) const RULES_HORIZON: i32 = 2300;
+
+/// Return the holidays at each of `offsets` days from Easter Sunday in each of `years`, e.g. an
+/// offset of -2 for Good Friday and 1 for Easter Monday.
+///
+/// ```rust
+/// # use rateslib::calendars::{easter_holidays, ndt};
+/// let holidays = easter_holidays(&[-2, 1], 2024..=2025);
+/// assert_eq!(holidays[1], ndt(2024, 4, 1));
+/// assert_eq!(holidays[2], ndt(2025, 4, 18));
+/// ```
+pub fn easter_holidays(offsets: &[i64], years: RangeInclusive<i32>) -> Vec<NaiveDateTime> {
+    years
+        .flat_map(|year| {
+            let sunday = easter(year);
+            offsets.iter().map(move |offset| match *offset >= 0 {
+                true => sunday + Days::new(*offset as u64),
+                false => sunday - Days::new(offset.unsigned_abs()),
+            })
+        })
+        .collect()
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_easter() {
+        let expected = [
+            (1970, ndt(1970, 3, 29)),
+            (2000, ndt(2000, 4, 23)),
+            (2019, ndt(2019, 4, 21)),
+            (2038, ndt(2038, 4, 25)),
+            (2285, ndt(2285, 3, 22)),
+        ];
+        for (year, date) in expected {
+            assert_eq!(easter(year), date);
+        }
+    }
+
+    #[test]
+    fn test_good_friday_and_easter_monday() {
+        assert_eq!(good_friday(2024), ndt(2024, 3, 29));
+        assert_eq!(easter_monday(2024), ndt(2024, 4, 1));
+        assert_eq!(good_friday(2016), ndt(2016, 3, 25));
+    }
+}
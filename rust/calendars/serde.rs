@@ -21,6 +21,34 @@ mod tests {
         assert_eq!(hcal, hcal2);
     }
 
+    #[test]
+    fn test_cal_json_early_closes() {
+        let hcal = Cal::new(vec![ndt(2015, 9, 8)], vec![5, 6]).with_early_closes(vec![(
+            ndt(2015, 9, 9),
+            chrono::NaiveTime::from_hms_opt(13, 0, 0).unwrap(),
+        )]);
+        let js = hcal.to_json().unwrap();
+        let hcal2 = Cal::from_json(&js).unwrap();
+        assert_eq!(hcal, hcal2);
+        // calendars serialized without early closes remain valid
+        let hcal3 = Cal::from_json(r#"{"holidays":[],"week_mask":[]}"#).unwrap();
+        assert!(hcal3.early_closes.is_empty());
+    }
+
+    #[test]
+    fn test_cal_json_is_canonical() {
+        let hcal = Cal::new(vec![ndt(2015, 9, 10), ndt(2015, 9, 8)], vec![6, 5]);
+        let hcal2 = Cal::new(vec![ndt(2015, 9, 8), ndt(2015, 9, 10)], vec![5, 6]);
+        assert_eq!(hcal.to_json().unwrap(), hcal2.to_json().unwrap());
+        assert_eq!(
+            hcal.canonical_hash().unwrap(),
+            hcal2.canonical_hash().unwrap()
+        );
+        assert!(hcal.to_json().unwrap().starts_with(
+            r#"{"holidays":["2015-09-08T00:00:00","2015-09-10T00:00:00"],"week_mask":["Sat","Sun"]"#
+        ));
+    }
+
     #[test]
     fn test_union_cal_json() {
         let hols = vec![ndt(2015, 9, 8), ndt(2015, 9, 10)];
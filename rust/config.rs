@@ -0,0 +1,246 @@
+//! Configure the numeric tolerances used throughout the library.
+//!
+//! A single set of [Tolerances] is shared by all threads, and may be overridden on any thread,
+//! e.g. to apply a stricter precision policy to one calculation without affecting others.
+//! The override of a thread takes precedence over the shared tolerances until it is cleared.
+
+use crate::solver::DEFAULT_TOLERANCE;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::cell::Cell;
+use std::sync::RwLock;
+
+/// The numeric tolerances consulted by equality, solvers and curve diagnostics.
+#[pyclass(module = "rateslib.rs", get_all)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Tolerances {
+    /// The absolute tolerance of each component in the equality of
+    /// [Dual](crate::dual::Dual) and [Dual2](crate::dual::Dual2) values, where zero is exact.
+    pub dual_equality: f64,
+    /// The default tolerance of the residual norm at which a solve has converged.
+    pub solver: f64,
+    /// The change in a forward rate, in percent, which curve diagnostics treat as rounding error.
+    pub curve: f64,
+}
+
+impl Tolerances {
+    /// The tolerances compiled into the library.
+    pub const DEFAULT: Tolerances = Tolerances {
+        dual_equality: 0.0,
+        solver: DEFAULT_TOLERANCE,
+        curve: 1e-10,
+    };
+
+    /// Create tolerances, which must be finite and non-negative, and positive for the solver.
+    pub fn try_new(dual_equality: f64, solver: f64, curve: f64) -> Result<Self, PyErr> {
+        let tolerances = Tolerances {
+            dual_equality,
+            solver,
+            curve,
+        };
+        tolerances.validate()?;
+        Ok(tolerances)
+    }
+
+    /// Check the tolerances are finite and non-negative, and positive for the solver, since the
+    /// fields are public and may be set directly.
+    pub fn validate(&self) -> Result<(), PyErr> {
+        let valid = |v: f64| v.is_finite() && v >= 0.0;
+        if !valid(self.dual_equality)
+            || !valid(self.curve)
+            || !valid(self.solver)
+            || self.solver == 0.0
+        {
+            return Err(PyValueError::new_err(
+                "Tolerances must be finite and non-negative, and the solver tolerance positive.",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for Tolerances {
+    fn default() -> Self {
+        Tolerances::DEFAULT
+    }
+}
+
+static SHARED: RwLock<Tolerances> = RwLock::new(Tolerances::DEFAULT);
+
+thread_local! {
+    static OVERRIDE: Cell<Option<Tolerances>> = const { Cell::new(None) };
+}
+
+/// Return the tolerances of the current thread, being its override or the shared tolerances.
+pub fn tolerances() -> Tolerances {
+    OVERRIDE
+        .get()
+        .unwrap_or_else(|| *SHARED.read().expect("tolerances lock is not poisoned"))
+}
+
+/// Set the tolerances shared by all threads, which must be valid, see [Tolerances::validate].
+pub fn set_tolerances(tolerances: Tolerances) -> Result<(), PyErr> {
+    tolerances.validate()?;
+    *SHARED.write().expect("tolerances lock is not poisoned") = tolerances;
+    Ok(())
+}
+
+/// Set, or clear with `None`, the override of the tolerances of the current thread, which must
+/// be valid, see [Tolerances::validate].
+pub fn set_thread_tolerances(tolerances: Option<Tolerances>) -> Result<(), PyErr> {
+    if let Some(t) = &tolerances {
+        t.validate()?;
+    }
+    OVERRIDE.set(tolerances);
+    Ok(())
+}
+
+/// Restores the previous override of the current thread when dropped, including on a panic.
+struct OverrideGuard(Option<Tolerances>);
+
+impl Drop for OverrideGuard {
+    fn drop(&mut self) {
+        OVERRIDE.set(self.0);
+    }
+}
+
+/// Perform a calculation with the tolerances of the current thread overridden, restoring the
+/// previous override afterwards, even if the calculation panics.
+///
+/// # Errors
+///
+/// If the `tolerances` are not valid, see [Tolerances::validate], in which case `f` is not
+/// called.
+pub fn with_tolerances<R, F: FnOnce() -> R>(tolerances: Tolerances, f: F) -> Result<R, PyErr> {
+    tolerances.validate()?;
+    let _guard = OverrideGuard(OVERRIDE.replace(Some(tolerances)));
+    Ok(f())
+}
+
+/// Whether two values are equal within an absolute tolerance.
+pub(crate) fn is_close(a: f64, b: f64, tol: f64) -> bool {
+    a == b || (a - b).abs() <= tol
+}
+
+#[pymethods]
+impl Tolerances {
+    #[new]
+    #[pyo3(signature = (dual_equality=Tolerances::DEFAULT.dual_equality, solver=Tolerances::DEFAULT.solver, curve=Tolerances::DEFAULT.curve))]
+    fn new_py(dual_equality: f64, solver: f64, curve: f64) -> PyResult<Self> {
+        Tolerances::try_new(dual_equality, solver, curve)
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self == other
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<Tolerances: dual_equality={}, solver={}, curve={}>",
+            self.dual_equality, self.solver, self.curve
+        )
+    }
+}
+
+/// Return the tolerances of the current thread.
+///
+/// Returns
+/// -------
+/// Tolerances
+#[pyfunction]
+#[pyo3(name = "get_tolerances")]
+pub fn get_tolerances_py() -> Tolerances {
+    tolerances()
+}
+
+/// Set the tolerances shared by all threads.
+///
+/// Parameters
+/// ----------
+/// tolerances: Tolerances
+///     The tolerances, which a thread override takes precedence over.
+///
+/// Returns
+/// -------
+/// None
+#[pyfunction]
+#[pyo3(name = "set_tolerances")]
+pub fn set_tolerances_py(tolerances: Tolerances) -> PyResult<()> {
+    set_tolerances(tolerances)
+}
+
+/// Set or clear the override of the tolerances of the current thread.
+///
+/// Parameters
+/// ----------
+/// tolerances: Tolerances, optional
+///     The tolerances of the current thread, or *None* to use the shared tolerances.
+///
+/// Returns
+/// -------
+/// None
+#[pyfunction]
+#[pyo3(name = "set_thread_tolerances", signature = (tolerances=None))]
+pub fn set_thread_tolerances_py(tolerances: Option<Tolerances>) -> PyResult<()> {
+    set_thread_tolerances(tolerances)
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dual::Dual;
+
+    #[test]
+    fn test_thread_override() {
+        let strict = Tolerances::try_new(0.0, 1e-14, 0.0).unwrap();
+        let inner = with_tolerances(strict, || {
+            // other threads see the shared tolerances
+            let other = std::thread::spawn(tolerances).join().unwrap();
+            assert_ne!(other, strict);
+            tolerances()
+        })
+        .unwrap();
+        assert_eq!(inner, strict);
+        assert_ne!(tolerances(), strict);
+
+        set_thread_tolerances(Some(strict)).unwrap();
+        assert_eq!(tolerances(), strict);
+        set_thread_tolerances(None).unwrap();
+        assert_ne!(tolerances(), strict);
+    }
+
+    #[test]
+    fn test_override_restored_after_panic() {
+        let strict = Tolerances::try_new(0.0, 1e-14, 0.0).unwrap();
+        let result = std::panic::catch_unwind(|| {
+            with_tolerances(strict, || panic!("calculation failed")).unwrap()
+        });
+        assert!(result.is_err());
+        assert_ne!(tolerances(), strict);
+    }
+
+    #[test]
+    fn test_dual_equality_tolerance() {
+        let x = Dual::new(1.0, vec!["x".to_string()]);
+        let y = &x + 1e-9;
+        assert_ne!(x, y);
+        let loose = Tolerances::try_new(1e-8, 1e-10, 1e-10).unwrap();
+        with_tolerances(loose, || assert_eq!(x, y)).unwrap();
+        with_tolerances(loose, || assert_ne!(x, &x * 2.0)).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_tolerances() {
+        assert!(Tolerances::try_new(-1.0, 1e-10, 0.0).is_err());
+        assert!(Tolerances::try_new(0.0, 0.0, 0.0).is_err());
+        assert!(Tolerances::try_new(0.0, 1e-10, f64::NAN).is_err());
+
+        // fields set directly are validated before use
+        let mut invalid = Tolerances::DEFAULT;
+        invalid.solver = -1.0;
+        assert!(set_thread_tolerances(Some(invalid)).is_err());
+        assert!(with_tolerances(invalid, || ()).is_err());
+        assert_eq!(tolerances(), Tolerances::DEFAULT);
+    }
+}
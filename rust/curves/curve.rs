@@ -3,7 +3,10 @@ use crate::calendars::{Convention, Modifier};
 use crate::curves::interpolation::utils::index_left;
 use crate::curves::nodes::{Nodes, NodesTimestamp};
 use crate::dual::{get_variable_tags, ADOrder, Dual, Dual2, Number};
-use chrono::NaiveDateTime;
+use crate::logging::event;
+use crate::maths::sampling::adaptive_sample;
+use crate::state::next_state_id;
+use chrono::{NaiveDateTime, TimeDelta};
 use indexmap::IndexMap;
 use pyo3::exceptions::PyValueError;
 use pyo3::PyErr;
@@ -11,7 +14,7 @@ use serde::{Deserialize, Serialize};
 use std::cmp::PartialEq;
 
 /// Default struct for storing datetime indexed discount factors (DFs).
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CurveDF<T: CurveInterpolation, U: DateRoll> {
     pub(crate) nodes: NodesTimestamp,
     pub(crate) interpolator: T,
@@ -20,6 +23,25 @@ pub struct CurveDF<T: CurveInterpolation, U: DateRoll> {
     pub(crate) modifier: Modifier,
     pub(crate) index_base: Option<f64>,
     pub(crate) calendar: U,
+    #[serde(skip, default = "next_state_id")]
+    pub(crate) state_id: u64,
+}
+
+/// Equality is determined by content and does not consider the `state_id`.
+impl<T, U> PartialEq for CurveDF<T, U>
+where
+    T: CurveInterpolation + PartialEq,
+    U: DateRoll + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes == other.nodes
+            && self.interpolator == other.interpolator
+            && self.id == other.id
+            && self.convention == other.convention
+            && self.modifier == other.modifier
+            && self.index_base == other.index_base
+            && self.calendar == other.calendar
+    }
 }
 
 /// Assigns methods for returning values from datetime indexed Curves.
@@ -32,6 +54,19 @@ pub trait CurveInterpolation {
         // let timestamp = date.and_utc().timestamp();
         index_left(&nodes.keys(), &date_timestamp, None)
     }
+
+    /// Get a value from the curve's `Nodes`, which are in the state identified by `state_id`.
+    ///
+    /// Interpolators may cache quantities derived from the nodes for as long as the state is
+    /// unchanged. By default nothing is cached.
+    fn interpolated_value_by_state(
+        &self,
+        nodes: &NodesTimestamp,
+        date: &NaiveDateTime,
+        _state_id: u64,
+    ) -> Number {
+        self.interpolated_value(nodes, date)
+    }
 }
 
 impl<T: CurveInterpolation, U: DateRoll> CurveDF<T, U> {
@@ -54,9 +89,15 @@ impl<T: CurveInterpolation, U: DateRoll> CurveDF<T, U> {
             modifier,
             index_base,
             calendar,
+            state_id: next_state_id(),
         })
     }
 
+    /// Get the id of the current state of the `Curve`, which changes whenever it is mutated.
+    pub fn state_id(&self) -> u64 {
+        self.state_id
+    }
+
     /// Get the `ADOrder` of the `Curve`.
     pub fn ad(&self) -> ADOrder {
         match self.nodes {
@@ -67,14 +108,37 @@ impl<T: CurveInterpolation, U: DateRoll> CurveDF<T, U> {
     }
 
     pub fn interpolated_value(&self, date: &NaiveDateTime) -> Number {
-        self.interpolator.interpolated_value(&self.nodes, date)
+        self.interpolator
+            .interpolated_value_by_state(&self.nodes, date, self.state_id)
     }
 
     pub fn node_index(&self, date_timestamp: i64) -> usize {
         self.interpolator.node_index(&self.nodes, date_timestamp)
     }
 
+    /// Sample the real DFs of the curve from `start` to `end`, with more dates where its
+    /// curvature is high, e.g. for plotting.
+    ///
+    /// Intervals are bisected, to no less than one day, until linear interpolation of the
+    /// samples is within `tol` of the DF at each midpoint, see [adaptive_sample].
+    pub fn sample(
+        &self,
+        start: &NaiveDateTime,
+        end: &NaiveDateTime,
+        tol: f64,
+    ) -> Result<(Vec<NaiveDateTime>, Vec<f64>), PyErr> {
+        let to_date = |days: f64| *start + TimeDelta::seconds((days * 86400.0).round() as i64);
+        let f = |days: f64| Ok(f64::from(self.interpolated_value(&to_date(days))));
+        let days = (*end - *start).num_seconds() as f64 / 86400.0;
+        let (x, y) = adaptive_sample(f, 0.0, days, tol, 1.0)?;
+        Ok((x.into_iter().map(to_date).collect(), y))
+    }
+
     pub fn set_ad_order(&mut self, ad: ADOrder) -> Result<(), PyErr> {
+        if ad != self.ad() {
+            event!(debug, curve = self.id.as_str(), ad = ?ad, "curve rebuilt with new AD order");
+            self.state_id = next_state_id();
+        }
         let vars: Vec<String> = get_variable_tags(&self.id, self.nodes.keys().len());
         match (ad, &self.nodes) {
             (ADOrder::Zero, NodesTimestamp::F64(_))
@@ -278,4 +342,59 @@ mod tests {
         let result = index_curve.index_value(&ndt(1980, 1, 1)).unwrap();
         assert_eq!(result, Number::F64(0.0))
     }
+
+    #[test]
+    fn test_state_id() {
+        let mut curve = curve_fixture();
+        let id = curve.state_id();
+        let _ = curve.set_ad_order(ADOrder::Zero);
+        assert_eq!(id, curve.state_id());
+        let _ = curve.set_ad_order(ADOrder::One);
+        assert_ne!(id, curve.state_id());
+        assert_ne!(curve_fixture().state_id(), curve_fixture().state_id());
+        assert_eq!(curve_fixture(), curve_fixture());
+    }
+
+    #[test]
+    fn test_nodes_before_1970_and_after_2262() {
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(1960, 1, 1), 1.0_f64),
+            (ndt(2300, 1, 1), 0.5_f64),
+            (ndt(3000, 1, 1), 0.1_f64),
+        ]));
+        let cal = NamedCal::try_new("all").unwrap();
+        let c = CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            "crv",
+            Convention::Act360,
+            Modifier::ModF,
+            None,
+            cal,
+        )
+        .unwrap();
+        assert_eq!(c.node_index(ndt(1969, 12, 31).and_utc().timestamp()), 0);
+        assert_eq!(c.node_index(ndt(2500, 1, 1).and_utc().timestamp()), 1);
+        assert_eq!(c.interpolated_value(&ndt(2300, 1, 1)), Number::F64(0.5));
+        let result = f64::from(c.interpolated_value(&ndt(2650, 1, 1)));
+        let days = (ndt(2650, 1, 1) - ndt(2300, 1, 1)).num_days() as f64;
+        let total = (ndt(3000, 1, 1) - ndt(2300, 1, 1)).num_days() as f64;
+        let expected = (0.5_f64.ln() + days / total * (0.1_f64.ln() - 0.5_f64.ln())).exp();
+        assert!((result - expected).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_sample() {
+        let c = curve_fixture();
+        let (dates, dfs) = c.sample(&ndt(2000, 1, 1), &ndt(2002, 1, 1), 1e-6).unwrap();
+        assert_eq!(
+            (dates[0], dates[dates.len() - 1]),
+            (ndt(2000, 1, 1), ndt(2002, 1, 1))
+        );
+        assert!(dates.windows(2).all(|w| w[0] < w[1]));
+        for (date, df) in dates.iter().zip(dfs) {
+            assert_eq!(f64::from(c.interpolated_value(date)), df);
+        }
+        assert!(c.sample(&ndt(2002, 1, 1), &ndt(2000, 1, 1), 1e-6).is_err());
+    }
 }
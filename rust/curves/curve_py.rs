@@ -1,22 +1,29 @@
 //! Wrapper module to export Rust curve data types to Python using pyo3 bindings.
 
-use crate::calendars::CalType;
+use crate::calendars::calendar_py::extract_modifier;
+use crate::calendars::{CalType, NamedCal};
 use crate::calendars::{Convention, Modifier};
 use crate::curves::nodes::{Nodes, NodesTimestamp};
+use crate::curves::registry::lock_error;
 use crate::curves::{
-    CurveDF, CurveInterpolation, FlatBackwardInterpolator, FlatForwardInterpolator,
-    LinearInterpolator, LinearZeroRateInterpolator, LogLinearInterpolator, NullInterpolator,
+    CubicZeroRateInterpolator, CurveDF, CurveDiagnostics, CurveEvaluation, CurveInterpolation,
+    CurveRegistry, CurveSplice, Curves, FlatBackwardInterpolator, FlatCurve,
+    FlatForwardInterpolator, LinearInterpolator, LinearZeroRateInterpolator, LogCubicInterpolator,
+    LogLinearInterpolator, NullInterpolator, SpliceAdjustment, StepFunction,
 };
+use crate::dates::dates_py::{extract_date, extract_dates};
 use crate::dual::{get_variable_tags, set_order, ADOrder, Dual, Dual2, Number};
+use crate::instruments::{CurveInstrument, InstrumentType};
 use crate::json::json_py::DeserializedObj;
 use crate::json::JSON;
 use bincode::{deserialize, serialize};
 use chrono::NaiveDateTime;
 use indexmap::IndexMap;
-use pyo3::exceptions::PyValueError;
+use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::PyBytes;
 use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock, RwLockReadGuard};
 
 /// Interpolation
 #[derive(Debug, Clone, PartialEq, FromPyObject, Deserialize, Serialize)]
@@ -27,6 +34,8 @@ pub(crate) enum CurveInterpolator {
     FlatForward(FlatForwardInterpolator),
     FlatBackward(FlatBackwardInterpolator),
     Null(NullInterpolator),
+    LogCubic(LogCubicInterpolator),
+    CubicZeroRate(CubicZeroRateInterpolator),
 }
 
 impl IntoPy<PyObject> for CurveInterpolator {
@@ -44,10 +53,18 @@ impl IntoPy<PyObject> for CurveInterpolator {
             CurveInterpolator::FlatForward(i) => into_py!(i),
             CurveInterpolator::FlatBackward(i) => into_py!(i),
             CurveInterpolator::Null(i) => into_py!(i),
+            CurveInterpolator::LogCubic(i) => into_py!(i),
+            CurveInterpolator::CubicZeroRate(i) => into_py!(i),
         }
     }
 }
 
+impl From<LogLinearInterpolator> for CurveInterpolator {
+    fn from(value: LogLinearInterpolator) -> Self {
+        CurveInterpolator::LogLinear(value)
+    }
+}
+
 impl CurveInterpolation for CurveInterpolator {
     fn interpolated_value(&self, nodes: &NodesTimestamp, date: &NaiveDateTime) -> Number {
         match self {
@@ -57,6 +74,20 @@ impl CurveInterpolation for CurveInterpolator {
             CurveInterpolator::FlatBackward(i) => i.interpolated_value(nodes, date),
             CurveInterpolator::FlatForward(i) => i.interpolated_value(nodes, date),
             CurveInterpolator::Null(i) => i.interpolated_value(nodes, date),
+            CurveInterpolator::LogCubic(i) => i.interpolated_value(nodes, date),
+            CurveInterpolator::CubicZeroRate(i) => i.interpolated_value(nodes, date),
+        }
+    }
+
+    fn interpolated_value_by_state(
+        &self,
+        nodes: &NodesTimestamp,
+        date: &NaiveDateTime,
+        state_id: u64,
+    ) -> Number {
+        match self {
+            CurveInterpolator::LogLinear(i) => i.interpolated_value_by_state(nodes, date, state_id),
+            _ => self.interpolated_value(nodes, date),
         }
     }
 }
@@ -64,7 +95,7 @@ impl CurveInterpolation for CurveInterpolator {
 #[pyclass(module = "rateslib.rs")]
 #[derive(Clone, Deserialize, Serialize)]
 pub(crate) struct Curve {
-    inner: CurveDF<CurveInterpolator, CalType>,
+    pub(crate) inner: CurveDF<CurveInterpolator, CalType>,
 }
 
 #[pymethods]
@@ -77,7 +108,7 @@ impl Curve {
         ad: ADOrder,
         id: String,
         convention: Convention,
-        modifier: Modifier,
+        #[pyo3(from_py_with = "extract_modifier")] modifier: Modifier,
         calendar: CalType,
         index_base: Option<f64>,
     ) -> PyResult<Self> {
@@ -94,21 +125,79 @@ impl Curve {
         Ok(Self { inner })
     }
 
+    /// Create a *Curve* with a constant continuously compounded rate.
+    ///
+    /// Parameters
+    /// ----------
+    /// rate: float
+    ///     The continuously compounded rate, in percent.
+    /// convention: Convention
+    ///     The day count convention used to measure the rate.
+    /// start: datetime
+    ///     The initial node date of the curve.
+    /// end: datetime
+    ///     The final node date of the curve.
+    /// id: str, optional
+    ///     The identifier of the curve.
+    /// ad: ADOrder, optional
+    ///     The automatic differentiation order of the nodes.
+    /// modifier: Modifier, optional
+    ///     The business day modifier of the curve.
+    /// calendar: Cal, UnionCal or NamedCal, optional
+    ///     The calendar of the curve. If not given, all days are business days.
+    ///
+    /// Returns
+    /// -------
+    /// Curve
+    ///
+    /// Notes
+    /// -----
+    /// The curve is log-linearly interpolated between its two nodes so the rate is exactly
+    /// constant for conventions linear in calendar days, such as *Act365F* and *Act360*.
+    #[staticmethod]
+    #[pyo3(
+        name = "constant_rate",
+        signature = (rate, convention, start, end, id="constant".to_string(), ad=ADOrder::Zero, modifier=Modifier::ModF, calendar=None)
+    )]
+    #[allow(clippy::too_many_arguments)]
+    fn constant_rate_py(
+        rate: f64,
+        convention: Convention,
+        #[pyo3(from_py_with = "extract_date")] start: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] end: NaiveDateTime,
+        id: String,
+        ad: ADOrder,
+        #[pyo3(from_py_with = "extract_modifier")] modifier: Modifier,
+        calendar: Option<CalType>,
+    ) -> PyResult<Self> {
+        let calendar = match calendar {
+            Some(c) => c,
+            None => CalType::NamedCal(NamedCal::try_new("all")?),
+        };
+        let mut inner =
+            CurveDF::constant_rate(rate, convention, start, end, &id, modifier, calendar)?;
+        inner.set_ad_order(ad)?;
+        Ok(Self { inner })
+    }
+
     #[getter]
     fn id(&self) -> String {
         self.inner.id.clone()
     }
 
+    #[getter]
+    fn state_id(&self) -> u64 {
+        self.inner.state_id()
+    }
+
     #[getter]
     fn nodes(&self) -> IndexMap<NaiveDateTime, Number> {
-        let nodes = Nodes::from(self.inner.nodes.clone());
-        match nodes {
-            Nodes::F64(i) => IndexMap::from_iter(i.into_iter().map(|(k, v)| (k, Number::F64(v)))),
-            Nodes::Dual(i) => IndexMap::from_iter(i.into_iter().map(|(k, v)| (k, Number::Dual(v)))),
-            Nodes::Dual2(i) => {
-                IndexMap::from_iter(i.into_iter().map(|(k, v)| (k, Number::Dual2(v))))
-            }
-        }
+        self.inner.nodes.index_map()
+    }
+
+    #[getter]
+    fn node_dates(&self) -> Vec<NaiveDateTime> {
+        self.inner.nodes.dates()
     }
 
     #[getter]
@@ -125,6 +214,8 @@ impl Curve {
             CurveInterpolator::FlatForward(_) => "flat_forward".to_string(),
             CurveInterpolator::FlatBackward(_) => "flat_backward".to_string(),
             CurveInterpolator::Null(_) => "null".to_string(),
+            CurveInterpolator::LogCubic(_) => "log_cubic".to_string(),
+            CurveInterpolator::CubicZeroRate(_) => "cubic_zero_rate".to_string(),
         }
     }
 
@@ -139,7 +230,10 @@ impl Curve {
     }
 
     #[pyo3(name = "index_value")]
-    fn index_value_py(&self, date: NaiveDateTime) -> PyResult<Number> {
+    fn index_value_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+    ) -> PyResult<Number> {
         self.inner.index_value(&date)
     }
 
@@ -148,10 +242,145 @@ impl Curve {
         Ok(())
     }
 
-    fn __getitem__(&self, date: NaiveDateTime) -> Number {
+    fn __getitem__(&self, #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime) -> Number {
         self.inner.interpolated_value(&date)
     }
 
+    /// Create a composite curve using one curve up to, and including, a cutoff date and another
+    /// thereafter.
+    ///
+    /// Parameters
+    /// ----------
+    /// short_end: Curve
+    ///     The curve used up to, and including, the ``cutoff``.
+    /// long_end: Curve
+    ///     The curve used after the ``cutoff``.
+    /// cutoff: datetime
+    ///     The date separating the curves, within the nodes of the ``short_end``.
+    /// adjustment: SpliceAdjustment, optional
+    ///     The adjustment of the ``long_end`` for continuity at the ``cutoff``.
+    ///
+    /// Returns
+    /// -------
+    /// SplicedCurve
+    #[staticmethod]
+    #[pyo3(name = "splice", signature = (short_end, long_end, cutoff, adjustment=SpliceAdjustment::Scaled))]
+    fn splice_py(
+        short_end: Curve,
+        long_end: Curve,
+        #[pyo3(from_py_with = "extract_date")] cutoff: NaiveDateTime,
+        adjustment: SpliceAdjustment,
+    ) -> PyResult<SplicedCurve> {
+        SplicedCurve::new_py(short_end, long_end, cutoff, adjustment)
+    }
+
+    /// Return the DF at a date of the curve with an additional continuously compounded spread.
+    ///
+    /// Parameters
+    /// ----------
+    /// spread: StepFunction
+    ///     The spread, in percent, integrated from the initial node under the convention of the
+    ///     curve.
+    /// date: datetime
+    ///     The date of the DF.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "spread_value")]
+    fn spread_value_py(
+        &self,
+        spread: &StepFunction,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+    ) -> PyResult<Number> {
+        self.inner.spread_value(spread, &date)
+    }
+
+    /// Return smoothness diagnostics of the forward rates of the curve.
+    ///
+    /// Parameters
+    /// ----------
+    /// step_days: int, optional
+    ///     The number of calendar days between the dates of the sampling grid, which spans the
+    ///     initial to the final node.
+    ///
+    /// Returns
+    /// -------
+    /// CurveDiagnostics
+    #[pyo3(name = "diagnostics", signature = (step_days=1))]
+    fn diagnostics_py(&self, step_days: i64) -> PyResult<CurveDiagnostics> {
+        self.inner.diagnostics(step_days)
+    }
+
+    /// Sample the real DFs of the curve for plotting, with more dates where its curvature is
+    /// high.
+    ///
+    /// Parameters
+    /// ----------
+    /// start: datetime
+    ///     The first date.
+    /// end: datetime
+    ///     The last date, after ``start``.
+    /// tol: float, optional
+    ///     The maximum difference, at the midpoint of each interval, between the DF and linear
+    ///     interpolation of the samples.
+    ///
+    /// Returns
+    /// -------
+    /// tuple[list[datetime], list[float]]
+    ///
+    /// Notes
+    /// -----
+    /// Each interval is bisected until within ``tol``, but not to less than one day.
+    #[pyo3(name = "sample", signature = (start, end, tol=1e-6))]
+    fn sample_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] start: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] end: NaiveDateTime,
+        tol: f64,
+    ) -> PyResult<(Vec<NaiveDateTime>, Vec<f64>)> {
+        self.inner.sample(&start, &end, tol)
+    }
+
+    /// Return the curve as seen at a horizon if its forward rates are realised.
+    ///
+    /// Parameters
+    /// ----------
+    /// horizon: datetime
+    ///     The date of the initial node of the forward curve, after the initial node and before
+    ///     the final node of the curve.
+    ///
+    /// Returns
+    /// -------
+    /// Curve
+    #[pyo3(name = "forward_curve")]
+    fn forward_curve_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] horizon: NaiveDateTime,
+    ) -> PyResult<Curve> {
+        Ok(Curve {
+            inner: self.inner.forward_curve(&horizon)?,
+        })
+    }
+
+    /// Return the curve as seen at a horizon if the market is unchanged, with every node moved
+    /// forward by the horizon and keeping its value.
+    ///
+    /// Parameters
+    /// ----------
+    /// horizon: str
+    ///     The tenor of the horizon from the initial node, e.g. *"3M"* or *"1Y"*.
+    ///
+    /// Returns
+    /// -------
+    /// Curve
+    #[pyo3(name = "rolldown")]
+    fn rolldown_py(&self, horizon: &str) -> PyResult<Curve> {
+        Ok(Curve {
+            inner: self.inner.rolldown(horizon)?,
+        })
+    }
+
     fn __eq__(&self, other: Curve) -> bool {
         self.inner.eq(&other.inner)
     }
@@ -172,6 +401,22 @@ impl Curve {
         }
     }
 
+    /// Return a hash of the canonical JSON representation of the object, which is stable
+    /// across sessions.
+    ///
+    /// Returns
+    /// -------
+    /// int
+    #[pyo3(name = "canonical_hash")]
+    fn canonical_hash_py(&self) -> PyResult<u64> {
+        match DeserializedObj::Curve(self.clone()).canonical_hash() {
+            Ok(v) => Ok(v),
+            Err(_) => Err(PyValueError::new_err(
+                "Failed to serialize `Curve` to JSON.",
+            )),
+        }
+    }
+
     // Pickling
     pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
         *self = deserialize(state.as_bytes()).unwrap();
@@ -205,6 +450,577 @@ impl Curve {
     }
 }
 
+/// A composite of two curves, one before and one after a cutoff date.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Deserialize, Serialize)]
+pub(crate) struct SplicedCurve {
+    pub(crate) inner: CurveSplice<CurveInterpolator, CalType>,
+}
+
+#[pymethods]
+impl SplicedCurve {
+    #[new]
+    fn new_py(
+        short_end: Curve,
+        long_end: Curve,
+        #[pyo3(from_py_with = "extract_date")] cutoff: NaiveDateTime,
+        adjustment: SpliceAdjustment,
+    ) -> PyResult<Self> {
+        Ok(Self {
+            inner: CurveSplice::try_new(short_end.inner, long_end.inner, cutoff, adjustment)?,
+        })
+    }
+
+    #[getter]
+    fn short_end(&self) -> Curve {
+        Curve {
+            inner: self.inner.short_end.clone(),
+        }
+    }
+
+    #[getter]
+    fn long_end(&self) -> Curve {
+        Curve {
+            inner: self.inner.long_end.clone(),
+        }
+    }
+
+    #[getter]
+    fn cutoff(&self) -> NaiveDateTime {
+        self.inner.cutoff
+    }
+
+    #[getter]
+    fn state_id(&self) -> u64 {
+        self.inner.state_id()
+    }
+
+    #[getter]
+    fn adjustment(&self) -> SpliceAdjustment {
+        self.inner.adjustment
+    }
+
+    #[getter]
+    fn nodes(&self) -> IndexMap<NaiveDateTime, Number> {
+        IndexMap::from_iter(
+            self.node_dates()
+                .into_iter()
+                .map(|d| (d, self.inner.interpolated_value(&d))),
+        )
+    }
+
+    #[getter]
+    fn node_dates(&self) -> Vec<NaiveDateTime> {
+        self.inner.node_dates()
+    }
+
+    fn __getitem__(&self, #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime) -> Number {
+        self.inner.interpolated_value(&date)
+    }
+
+    // Pickling
+    pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
+        *self = deserialize(state.as_bytes()).unwrap();
+        Ok(())
+    }
+    pub fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new_bound(py, &serialize(&self).unwrap()))
+    }
+    pub fn __getnewargs__(&self) -> PyResult<(Curve, Curve, NaiveDateTime, SpliceAdjustment)> {
+        Ok((
+            self.short_end(),
+            self.long_end(),
+            self.inner.cutoff,
+            self.inner.adjustment,
+        ))
+    }
+}
+
+#[pymethods]
+impl SpliceAdjustment {
+    // Pickling
+    #[new]
+    fn new_py(item: u8) -> PyResult<SpliceAdjustment> {
+        match item {
+            0_u8 => Ok(SpliceAdjustment::Unadjusted),
+            1_u8 => Ok(SpliceAdjustment::Scaled),
+            _ => Err(PyValueError::new_err(
+                "unreachable code on SpliceAdjustment pickle.",
+            )),
+        }
+    }
+    pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
+        *self = deserialize(state.as_bytes()).unwrap();
+        Ok(())
+    }
+    pub fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new_bound(py, &serialize(&self).unwrap()))
+    }
+    pub fn __getnewargs__(&self) -> PyResult<(u8,)> {
+        match self {
+            SpliceAdjustment::Unadjusted => Ok((0_u8,)),
+            SpliceAdjustment::Scaled => Ok((1_u8,)),
+        }
+    }
+}
+
+#[pymethods]
+impl FlatCurve {
+    /// Create a curve with a constant continuously compounded rate.
+    ///
+    /// Parameters
+    /// ----------
+    /// rate: float, Dual or Dual2
+    ///     The continuously compounded rate, in percent.
+    /// convention: Convention
+    ///     The day count convention used to measure the rate.
+    /// start: datetime
+    ///     The initial date of the curve.
+    /// id: str, optional
+    ///     The identifier of the curve.
+    #[new]
+    #[pyo3(signature = (rate, convention, start, id="flat".to_string()))]
+    fn new_py(
+        rate: Number,
+        convention: Convention,
+        #[pyo3(from_py_with = "extract_date")] start: NaiveDateTime,
+        id: String,
+    ) -> PyResult<Self> {
+        FlatCurve::try_new(rate, convention, start, &id)
+    }
+
+    #[getter]
+    fn rate(&self) -> Number {
+        self.rate.clone()
+    }
+
+    #[getter]
+    fn convention(&self) -> Convention {
+        self.convention
+    }
+
+    #[getter]
+    fn start(&self) -> NaiveDateTime {
+        self.start
+    }
+
+    #[getter]
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    #[getter]
+    fn state_id(&self) -> u64 {
+        self.state_id
+    }
+
+    fn __getitem__(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+    ) -> PyResult<Number> {
+        self.df(&date)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<FlatCurve: '{}' {:.6}>", self.id, f64::from(&self.rate))
+    }
+
+    // Pickling
+    pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
+        *self = deserialize(state.as_bytes()).unwrap();
+        Ok(())
+    }
+    pub fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new_bound(py, &serialize(&self).unwrap()))
+    }
+    pub fn __getnewargs__(&self) -> PyResult<(Number, Convention, NaiveDateTime, String)> {
+        Ok((
+            self.rate.clone(),
+            self.convention,
+            self.start,
+            self.id.clone(),
+        ))
+    }
+}
+
+#[pymethods]
+impl StepFunction {
+    /// Create a piecewise constant function of date, which is zero before its first date.
+    ///
+    /// Parameters
+    /// ----------
+    /// dates: list[datetime]
+    ///     The strictly increasing dates from which each level applies.
+    /// levels: list[float | Dual | Dual2]
+    ///     The level from each date. Must not mix *Dual* and *Dual2*.
+    #[new]
+    fn new_py(
+        #[pyo3(from_py_with = "extract_dates")] dates: Vec<NaiveDateTime>,
+        levels: Vec<Number>,
+    ) -> PyResult<Self> {
+        StepFunction::try_new(dates, levels)
+    }
+
+    #[getter]
+    fn dates(&self) -> Vec<NaiveDateTime> {
+        self.dates.clone()
+    }
+
+    #[getter]
+    fn levels(&self) -> Vec<Number> {
+        self.levels.clone()
+    }
+
+    /// Return the integral of the function between two dates.
+    ///
+    /// Parameters
+    /// ----------
+    /// start: datetime
+    ///     The start of the interval.
+    /// end: datetime
+    ///     The end of the interval, not before ``start``.
+    /// convention: Convention
+    ///     The day count convention measuring time.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "integral")]
+    fn integral_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] start: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] end: NaiveDateTime,
+        convention: Convention,
+    ) -> PyResult<Number> {
+        self.integral(&start, &end, convention)
+    }
+
+    fn __getitem__(&self, #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime) -> Number {
+        self.value(&date)
+    }
+
+    fn __add__(&self, other: &StepFunction) -> PyResult<StepFunction> {
+        Ok(self.try_add(other)?)
+    }
+
+    fn __sub__(&self, other: &StepFunction) -> PyResult<StepFunction> {
+        Ok(self.try_sub(other)?)
+    }
+
+    fn __mul__(&self, other: Number) -> PyResult<StepFunction> {
+        Ok(self.try_mul(&other)?)
+    }
+
+    fn __rmul__(&self, other: Number) -> PyResult<StepFunction> {
+        Ok(self.try_mul(&other)?)
+    }
+
+    fn __neg__(&self) -> StepFunction {
+        -self
+    }
+
+    fn __eq__(&self, other: &StepFunction) -> PyResult<bool> {
+        Ok(self.try_eq(other)?)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<StepFunction: {} steps>", self.dates.len())
+    }
+
+    // Pickling
+    pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
+        *self = deserialize(state.as_bytes()).unwrap();
+        Ok(())
+    }
+    pub fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new_bound(py, &serialize(&self).unwrap()))
+    }
+    pub fn __getnewargs__(&self) -> PyResult<(Vec<NaiveDateTime>, Vec<Number>)> {
+        Ok((self.dates.clone(), self.levels.clone()))
+    }
+}
+
+#[pymethods]
+impl CurveDiagnostics {
+    #[getter]
+    fn dates(&self) -> Vec<NaiveDateTime> {
+        self.dates.clone()
+    }
+
+    #[getter]
+    fn forwards(&self) -> Vec<f64> {
+        self.forwards.clone()
+    }
+
+    #[getter]
+    fn max_forward_jump(&self) -> f64 {
+        self.max_forward_jump
+    }
+
+    #[getter]
+    fn max_forward_jump_date(&self) -> Option<NaiveDateTime> {
+        self.max_forward_jump_date
+    }
+
+    #[getter]
+    fn mean_forward_jump(&self) -> f64 {
+        self.mean_forward_jump
+    }
+
+    #[getter]
+    fn total_variation(&self) -> f64 {
+        self.total_variation
+    }
+
+    #[getter]
+    fn roughness(&self) -> f64 {
+        self.roughness
+    }
+
+    #[getter]
+    fn oscillations(&self) -> usize {
+        self.oscillations
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<CurveDiagnostics: max_forward_jump={:.6}, total_variation={:.6}, oscillations={}>",
+            self.max_forward_jump, self.total_variation, self.oscillations
+        )
+    }
+}
+
+impl<'py> FromPyObject<'py> for Curves<CurveInterpolator, CalType> {
+    fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(curve) = obj.extract::<Curve>() {
+            Ok(Curves::DF(curve.inner))
+        } else if let Ok(curve) = obj.extract::<FlatCurve>() {
+            Ok(Curves::Flat(curve))
+        } else if let Ok(curve) = obj.extract::<SplicedCurve>() {
+            Ok(Curves::Splice(curve.inner))
+        } else {
+            Err(PyTypeError::new_err(format!(
+                "'{}' is not a Curve, FlatCurve or SplicedCurve.",
+                obj.get_type().name()?
+            )))
+        }
+    }
+}
+
+impl IntoPy<PyObject> for Curves<CurveInterpolator, CalType> {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        match self {
+            Curves::DF(inner) => Curve { inner }.into_py(py),
+            Curves::Flat(curve) => curve.into_py(py),
+            Curves::Splice(inner) => SplicedCurve { inner }.into_py(py),
+        }
+    }
+}
+
+/// A thread-safe store of curves resolvable by id.
+#[pyclass(module = "rateslib.rs", name = "CurveRegistry")]
+pub(crate) struct CurveRegistryPy {
+    inner: CurveRegistry<Curves<CurveInterpolator, CalType>>,
+}
+
+#[pymethods]
+impl CurveRegistryPy {
+    #[new]
+    fn new_py() -> Self {
+        CurveRegistryPy {
+            inner: CurveRegistry::new(),
+        }
+    }
+
+    /// The ids of all curves in the registry.
+    #[getter]
+    fn ids(&self) -> PyResult<Vec<String>> {
+        self.inner.ids()
+    }
+
+    /// Insert a curve into the registry, replacing any existing curve.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve, FlatCurve or SplicedCurve
+    ///     The curve to insert.
+    /// id: str, optional
+    ///     The id under which to insert the curve, by default its ``id``. Required for a
+    ///     *SplicedCurve*, which has no ``id``.
+    ///
+    /// Returns
+    /// -------
+    /// CurveHandle
+    #[pyo3(signature = (curve, id=None))]
+    fn insert(
+        &self,
+        curve: Curves<CurveInterpolator, CalType>,
+        id: Option<String>,
+    ) -> PyResult<CurveHandle> {
+        let id = match (id, curve.id()) {
+            (Some(id), _) => id,
+            (None, Some(id)) => id.to_string(),
+            (None, None) => {
+                return Err(PyValueError::new_err(
+                    "An `id` is required to insert a curve which has no `id`.",
+                ))
+            }
+        };
+        Ok(CurveHandle {
+            inner: self.inner.insert(&id, curve)?,
+        })
+    }
+
+    /// Return a handle to the curve with the given id, shared with the registry.
+    ///
+    /// Parameters
+    /// ----------
+    /// id: str
+    ///     The id of the curve.
+    ///
+    /// Returns
+    /// -------
+    /// CurveHandle
+    fn get(&self, id: &str) -> PyResult<CurveHandle> {
+        Ok(CurveHandle {
+            inner: self.inner.try_get(id)?,
+        })
+    }
+
+    /// Remove the curve with the given id from the registry.
+    fn remove(&self, id: &str) -> PyResult<()> {
+        match self.inner.remove(id)? {
+            Some(_) => Ok(()),
+            None => Err(PyValueError::new_err(format!(
+                "Curve id '{}' is not found in the registry.",
+                id
+            ))),
+        }
+    }
+
+    /// Set the order of automatic differentiation of the curve with the given id.
+    fn set_ad_order(&self, id: &str, ad: ADOrder) -> PyResult<()> {
+        self.inner.update(id, |c| c.set_ad_order(ad))?
+    }
+
+    /// Return the net present value of an instrument from the curve with the given id.
+    ///
+    /// The id is resolved when called, so the instrument is priced from the curve as most
+    /// recently inserted or updated.
+    ///
+    /// Parameters
+    /// ----------
+    /// instrument: IRS, Spread or Fly
+    ///     The instrument to price.
+    /// id: str
+    ///     The id of a *Curve* in the registry, discounting and forecasting the instrument.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    fn npv(&self, instrument: InstrumentType, id: &str) -> PyResult<Number> {
+        instrument.npv_from_registry(&self.inner, id)
+    }
+
+    /// Return the mid-market rate of an instrument from the curve with the given id.
+    ///
+    /// The id is resolved when called, so the instrument is priced from the curve as most
+    /// recently inserted or updated.
+    ///
+    /// Parameters
+    /// ----------
+    /// instrument: IRS, Spread or Fly
+    ///     The instrument to price.
+    /// id: str
+    ///     The id of a *Curve* in the registry, discounting and forecasting the instrument.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    fn rate(&self, instrument: InstrumentType, id: &str) -> PyResult<Number> {
+        instrument.rate_from_registry(&self.inner, id)
+    }
+
+    fn __contains__(&self, id: &str) -> PyResult<bool> {
+        self.inner.contains(id)
+    }
+
+    fn __len__(&self) -> PyResult<usize> {
+        self.inner.len()
+    }
+}
+
+/// A handle to a curve shared with a :class:`CurveRegistry`.
+///
+/// Mutations of the curve via the registry or via any handle are visible to every holder. A
+/// curve replaced in the registry under the same id is not, for which see
+/// :meth:`CurveRegistry.rate`.
+#[pyclass(module = "rateslib.rs")]
+pub(crate) struct CurveHandle {
+    inner: Arc<RwLock<Curves<CurveInterpolator, CalType>>>,
+}
+
+impl CurveHandle {
+    fn read(&self) -> PyResult<RwLockReadGuard<'_, Curves<CurveInterpolator, CalType>>> {
+        self.inner.read().map_err(lock_error)
+    }
+}
+
+#[pymethods]
+impl CurveHandle {
+    /// The id of the curve, which a *SplicedCurve* does not have.
+    #[getter]
+    fn id(&self) -> PyResult<Option<String>> {
+        Ok(self.read()?.id().map(|id| id.to_string()))
+    }
+
+    #[getter]
+    fn state_id(&self) -> PyResult<u64> {
+        Ok(self.read()?.state_id())
+    }
+
+    /// Return a copy of the curve, which is not shared with the registry.
+    ///
+    /// Returns
+    /// -------
+    /// Curve, FlatCurve or SplicedCurve
+    fn to_curve(&self) -> PyResult<Curves<CurveInterpolator, CalType>> {
+        Ok(self.read()?.clone())
+    }
+
+    /// Return the simple rate, in percent, between two dates.
+    ///
+    /// Parameters
+    /// ----------
+    /// start: datetime
+    ///     The start date of the rate.
+    /// end: datetime
+    ///     The end date of the rate.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    fn rate(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] start: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] end: NaiveDateTime,
+    ) -> PyResult<Number> {
+        self.read()?.rate(&start, &end)
+    }
+
+    /// Set the order of automatic differentiation of the curve.
+    fn set_ad_order(&self, ad: ADOrder) -> PyResult<()> {
+        self.inner.write().map_err(lock_error)?.set_ad_order(ad)
+    }
+
+    fn __getitem__(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+    ) -> PyResult<Number> {
+        self.read()?.df(&date)
+    }
+}
+
 // /// Convert the `nodes`of a `Curve` from a `HashMap` input form into the local data model.
 // /// Will upcast f64 values to a new ADOrder adding curve variable tags by id.
 // fn hashmap_into_nodes_timestamp(
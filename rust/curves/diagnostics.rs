@@ -0,0 +1,160 @@
+use crate::calendars::DateRoll;
+use crate::config::tolerances;
+use crate::curves::nodes::node_date;
+use crate::curves::{CurveDF, CurveInterpolation};
+use chrono::prelude::*;
+use chrono::TimeDelta;
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, PyErr};
+
+/// Measures of the smoothness of the forward rates of a curve over a sampling grid.
+///
+/// Forward rates are continuously compounded, in percent, on Act365F, between consecutive dates
+/// of a grid spanning the nodes of the curve.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CurveDiagnostics {
+    /// The start date of each sampled forward period.
+    pub dates: Vec<NaiveDateTime>,
+    /// The sampled forward rates.
+    pub forwards: Vec<f64>,
+    /// The largest absolute change between consecutive forward rates.
+    pub max_forward_jump: f64,
+    /// The date at which the largest change occurs.
+    pub max_forward_jump_date: Option<NaiveDateTime>,
+    /// The average absolute change between consecutive forward rates.
+    pub mean_forward_jump: f64,
+    /// The sum of absolute changes between consecutive forward rates.
+    pub total_variation: f64,
+    /// The sum of squared second differences of forward rates.
+    pub roughness: f64,
+    /// The number of times the direction of change of the forward rates reverses.
+    pub oscillations: usize,
+}
+
+impl<T: CurveInterpolation, U: DateRoll> CurveDF<T, U> {
+    /// Return smoothness diagnostics of the forward rates of the curve, sampled every
+    /// `step_days` calendar days from its initial to its final node.
+    pub fn diagnostics(&self, step_days: i64) -> Result<CurveDiagnostics, PyErr> {
+        if step_days < 1 {
+            return Err(PyValueError::new_err("`step_days` must be positive."));
+        }
+        let keys = self.nodes.keys();
+        let to_date = node_date;
+        let (first, last) = (to_date(keys[0]), to_date(keys[keys.len() - 1]));
+        let mut grid = vec![first];
+        while *grid.last().unwrap() < last {
+            let next = *grid.last().unwrap() + TimeDelta::days(step_days);
+            grid.push(next.min(last));
+        }
+
+        let dates: Vec<NaiveDateTime> = grid[..grid.len() - 1].to_vec();
+        let forwards: Vec<f64> = grid
+            .windows(2)
+            .map(|w| {
+                let ratio = f64::from(self.interpolated_value(&w[0]))
+                    / f64::from(self.interpolated_value(&w[1]));
+                let t = (w[1] - w[0]).num_seconds() as f64 / (365.0 * 86400.0);
+                100.0 * ratio.ln() / t
+            })
+            .collect();
+        let jumps: Vec<f64> = forwards.windows(2).map(|w| w[1] - w[0]).collect();
+
+        let (mut max_forward_jump, mut max_forward_jump_date) = (0.0, None);
+        for (i, jump) in jumps.iter().enumerate() {
+            if jump.abs() > max_forward_jump {
+                max_forward_jump = jump.abs();
+                max_forward_jump_date = Some(dates[i + 1]);
+            }
+        }
+        let total_variation: f64 = jumps.iter().map(|j| j.abs()).sum();
+        let mean_forward_jump = if jumps.is_empty() {
+            0.0
+        } else {
+            total_variation / jumps.len() as f64
+        };
+        let roughness = jumps.windows(2).map(|w| (w[1] - w[0]).powi(2)).sum();
+        // ignore changes within rounding error when counting reversals
+        let mut oscillations = 0;
+        let mut direction = 0.0;
+        let tol = tolerances().curve;
+        for jump in jumps.iter().filter(|j| j.abs() > tol) {
+            if direction * jump < 0.0 {
+                oscillations += 1;
+            }
+            direction = jump.signum();
+        }
+        Ok(CurveDiagnostics {
+            dates,
+            forwards,
+            max_forward_jump,
+            max_forward_jump_date,
+            mean_forward_jump,
+            total_variation,
+            roughness,
+            oscillations,
+        })
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, Convention, Modifier, NamedCal};
+    use crate::curves::{LinearInterpolator, LogLinearInterpolator, Nodes};
+    use indexmap::IndexMap;
+
+    fn nodes() -> Nodes {
+        Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2022, 1, 1), 1.0_f64),
+            (ndt(2023, 1, 1), 0.97_f64),
+            (ndt(2024, 1, 1), 0.935_f64),
+            (ndt(2025, 1, 1), 0.91_f64),
+        ]))
+    }
+
+    fn curve<T: CurveInterpolation>(interpolator: T) -> CurveDF<T, NamedCal> {
+        CurveDF::try_new(
+            nodes(),
+            interpolator,
+            "sofr",
+            Convention::Act365F,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_log_linear_forwards_are_piecewise_flat() {
+        let d = curve(LogLinearInterpolator::new()).diagnostics(1).unwrap();
+        assert_eq!(d.forwards.len(), 1096);
+        assert_eq!(d.dates[0], ndt(2022, 1, 1));
+        // forwards only jump at the two interior nodes
+        let jumps = d
+            .forwards
+            .windows(2)
+            .filter(|w| (w[1] - w[0]).abs() > 1e-8)
+            .count();
+        assert_eq!(jumps, 2);
+        // 2024 has 366 days
+        let (f2, f3) = (
+            (0.97_f64 / 0.935).ln(),
+            (0.935_f64 / 0.91).ln() * 365.0 / 366.0,
+        );
+        assert_eq!(d.max_forward_jump_date, Some(ndt(2024, 1, 1)));
+        assert!((d.max_forward_jump - 100.0 * (f2 - f3)).abs() < 1e-8);
+        assert_eq!(d.oscillations, 1);
+    }
+
+    #[test]
+    fn test_compare_interpolation_schemes() {
+        let log_linear = curve(LogLinearInterpolator::new()).diagnostics(7).unwrap();
+        let linear = curve(LinearInterpolator::new()).diagnostics(7).unwrap();
+        assert_eq!(log_linear.forwards.len(), linear.forwards.len());
+        assert!(linear.mean_forward_jump > 0.0);
+        assert!(curve(LinearInterpolator::new()).diagnostics(0).is_err());
+    }
+}
@@ -0,0 +1,198 @@
+use crate::calendars::{Convention, DateRoll, Modifier};
+use crate::curves::nodes::Nodes;
+use crate::curves::{CurveDF, CurveInterpolation, LogLinearInterpolator};
+use crate::dual::{get_variable_tags, set_order_clone, ADOrder, MathFuncs, Number};
+use crate::state::next_state_id;
+use chrono::NaiveDateTime;
+use indexmap::IndexMap;
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, PyErr};
+use serde::{Deserialize, Serialize};
+
+/// A curve of discount factors (DFs) with a constant continuously compounded rate.
+///
+/// DFs are calculated analytically as `exp(-rate / 100 * dcf(start, date))`, where the day
+/// count fraction is measured under `convention`, rather than interpolated from nodes.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FlatCurve {
+    pub(crate) rate: Number,
+    pub(crate) convention: Convention,
+    pub(crate) start: NaiveDateTime,
+    pub(crate) id: String,
+    #[serde(skip, default = "next_state_id")]
+    pub(crate) state_id: u64,
+}
+
+impl FlatCurve {
+    /// Create a flat curve with a continuously compounded `rate`, in percent, from `start`.
+    ///
+    /// The `convention` must not require additional arguments to measure day count fractions.
+    pub fn try_new(
+        rate: Number,
+        convention: Convention,
+        start: NaiveDateTime,
+        id: &str,
+    ) -> Result<Self, PyErr> {
+        convention.dcf(&start, &start, None, None, None, None, None)?;
+        Ok(FlatCurve {
+            rate,
+            convention,
+            start,
+            id: id.to_string(),
+            state_id: next_state_id(),
+        })
+    }
+
+    /// Return the DF at `date`, which must not be before the `start` of the curve.
+    pub fn df(&self, date: &NaiveDateTime) -> Result<Number, PyErr> {
+        if *date < self.start {
+            return Err(PyValueError::new_err(
+                "`date` must not be before the start of the curve.",
+            ));
+        }
+        let dcf = self
+            .convention
+            .dcf(&self.start, date, None, None, None, None, None)?;
+        Ok((&self.rate * (-dcf / 100.0)).exp())
+    }
+
+    /// Set the order of automatic differentiation of the rate, which is tagged with the id of
+    /// the curve.
+    pub fn set_ad_order(&mut self, ad: ADOrder) -> Result<(), PyErr> {
+        self.rate = set_order_clone(&self.rate, ad, get_variable_tags(&self.id, 1));
+        self.state_id = next_state_id();
+        Ok(())
+    }
+}
+
+impl<T, U> CurveDF<T, U>
+where
+    T: CurveInterpolation + From<LogLinearInterpolator>,
+    U: DateRoll,
+{
+    /// Create a curve with a constant continuously compounded `rate`, in percent, from `start` to
+    /// `end`, measured under `convention`.
+    ///
+    /// The curve has log-linearly interpolated nodes at `start` and `end`, so it matches a
+    /// [FlatCurve] exactly for conventions linear in calendar days, e.g. `Act365F` and `Act360`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn constant_rate(
+        rate: f64,
+        convention: Convention,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+        id: &str,
+        modifier: Modifier,
+        calendar: U,
+    ) -> Result<Self, PyErr> {
+        if end <= start {
+            return Err(PyValueError::new_err("`end` must be after `start`."));
+        }
+        let dcf = convention.dcf(&start, &end, None, None, None, None, None)?;
+        let nodes = Nodes::F64(IndexMap::from_iter([
+            (start, 1.0),
+            (end, (-rate / 100.0 * dcf).exp()),
+        ]));
+        CurveDF::try_new(
+            nodes,
+            T::from(LogLinearInterpolator::new()),
+            id,
+            convention,
+            modifier,
+            None,
+            calendar,
+        )
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, NamedCal};
+    use crate::dual::{Dual, Dual2};
+
+    #[test]
+    fn test_flat_curve_df() {
+        let curve = FlatCurve::try_new(
+            Number::F64(3.0),
+            Convention::Act365F,
+            ndt(2024, 1, 1),
+            "flat",
+        )
+        .unwrap();
+        let result = f64::from(curve.df(&ndt(2025, 1, 1)).unwrap());
+        assert!((result - (-0.03 * 366.0 / 365.0_f64).exp()).abs() < 1e-15);
+        assert!(curve.df(&ndt(2023, 12, 31)).is_err());
+    }
+
+    #[test]
+    fn test_flat_curve_dual_rate() {
+        let rate = Dual::new(3.0, vec!["r".to_string()]);
+        let curve = FlatCurve::try_new(
+            Number::Dual(rate),
+            Convention::Act360,
+            ndt(2024, 1, 1),
+            "flat",
+        )
+        .unwrap();
+        let Number::Dual(df) = curve.df(&ndt(2024, 7, 1)).unwrap() else {
+            panic!("expected a dual DF")
+        };
+        let t = 182.0 / 360.0;
+        assert!((df.real - (-0.03 * t).exp()).abs() < 1e-15);
+        assert!((df.dual[0] + t / 100.0 * df.real).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_flat_curve_set_ad_order() {
+        let mut curve = FlatCurve::try_new(
+            Number::F64(3.0),
+            Convention::Act365F,
+            ndt(2024, 1, 1),
+            "flat",
+        )
+        .unwrap();
+        let id = curve.state_id;
+        curve.set_ad_order(ADOrder::Two).unwrap();
+        assert_ne!(id, curve.state_id);
+        assert_eq!(
+            curve.rate,
+            Number::Dual2(Dual2::new(3.0, vec!["flat0".to_string()]))
+        );
+    }
+
+    #[test]
+    fn test_flat_curve_invalid_convention() {
+        let result = FlatCurve::try_new(
+            Number::F64(3.0),
+            Convention::ActActICMA,
+            ndt(2024, 1, 1),
+            "flat",
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_constant_rate_matches_flat_curve() {
+        let (start, end) = (ndt(2024, 1, 1), ndt(2034, 1, 1));
+        let curve = CurveDF::<LogLinearInterpolator, NamedCal>::constant_rate(
+            4.0,
+            Convention::Act365F,
+            start,
+            end,
+            "const",
+            Modifier::ModF,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap();
+        let flat =
+            FlatCurve::try_new(Number::F64(4.0), Convention::Act365F, start, "flat").unwrap();
+        for date in [ndt(2024, 3, 15), ndt(2027, 6, 30), ndt(2034, 1, 1)] {
+            let expected = f64::from(flat.df(&date).unwrap());
+            let result = f64::from(curve.interpolated_value(&date));
+            assert!((result - expected).abs() < 1e-14);
+        }
+    }
+}
@@ -1,5 +1,8 @@
-use crate::curves::interpolation::utils::index_left;
-use pyo3::pyfunction;
+use crate::curves::interpolation::utils::{index_left, interpolate, InterpolationKind};
+use crate::dual::{Dual, Dual2, Number};
+use crate::splines::check_number_types;
+use pyo3::{pyfunction, PyResult};
+use std::str::FromStr;
 
 macro_rules! create_interface {
     ($name: ident, $type: ident) => {
@@ -12,3 +15,42 @@ macro_rules! create_interface {
 }
 
 create_interface!(index_left_f64, f64);
+
+/// Interpolate coordinates at a point.
+///
+/// Parameters
+/// ----------
+/// x: float
+///     The point at which to interpolate.
+/// xs: list[float]
+///     The strictly increasing coordinates, of at least two points.
+/// ys: list[float | Dual | Dual2]
+///     The values at each coordinate. Cannot mix *Dual* and *Dual2*.
+/// kind: str, optional
+///     One of *"linear"*, *"log_linear"*, *"cubic"* or *"log_cubic"*. Cubic kinds use a natural
+///     cubic spline.
+///
+/// Returns
+/// -------
+/// float, Dual or Dual2
+///
+/// Notes
+/// -----
+/// Beyond the first and last coordinates linear kinds extrapolate the first and last intervals,
+/// and cubic kinds extrapolate linearly. Log kinds require positive ``ys``.
+#[pyfunction]
+#[pyo3(name = "interpolate", signature = (x, xs, ys, kind="linear"))]
+pub fn interpolate_py(x: f64, xs: Vec<f64>, ys: Vec<Number>, kind: &str) -> PyResult<Number> {
+    let kind = InterpolationKind::from_str(kind)?;
+    check_number_types(ys.iter())?;
+    if ys.iter().any(|y| matches!(y, Number::Dual2(_))) {
+        let ys: Vec<Dual2> = ys.iter().map(Dual2::from).collect();
+        Ok(Number::Dual2(interpolate(x, &xs, &ys, kind)?))
+    } else if ys.iter().any(|y| matches!(y, Number::Dual(_))) {
+        let ys: Vec<Dual> = ys.iter().map(Dual::from).collect();
+        Ok(Number::Dual(interpolate(x, &xs, &ys, kind)?))
+    } else {
+        let ys: Vec<f64> = ys.iter().map(f64::from).collect();
+        Ok(Number::F64(interpolate(x, &xs, &ys, kind)?))
+    }
+}
@@ -0,0 +1,168 @@
+use crate::curves::interpolation::utils::natural_spline_interp;
+use crate::curves::nodes::NodesTimestamp;
+use crate::curves::CurveInterpolation;
+use crate::dual::{MathFuncs, Number};
+use bincode::{deserialize, serialize};
+use chrono::NaiveDateTime;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyTuple};
+use pyo3::{pyclass, pymethods, Bound, PyResult, Python};
+use serde::{Deserialize, Serialize};
+use std::cmp::PartialEq;
+
+/// Define cubic zero rate interpolation of nodes.
+///
+/// A natural cubic spline is fitted to the continuously compounded zero rates of the nodes,
+/// measured from the first node, and extrapolated linearly beyond the first and last nodes. The
+/// zero rate of the first node is undefined and taken as that of the second node.
+///
+/// This interpolation can only be used with discount factors node values.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CubicZeroRateInterpolator {}
+
+#[pymethods]
+impl CubicZeroRateInterpolator {
+    #[new]
+    pub fn new() -> Self {
+        CubicZeroRateInterpolator {}
+    }
+
+    // Pickling
+    pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
+        *self = deserialize(state.as_bytes()).unwrap();
+        Ok(())
+    }
+    pub fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new_bound(py, &serialize(&self).unwrap()))
+    }
+    pub fn __getnewargs__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyTuple>> {
+        Ok(PyTuple::empty_bound(py))
+    }
+}
+
+impl CurveInterpolation for CubicZeroRateInterpolator {
+    fn interpolated_value(&self, nodes: &NodesTimestamp, date: &NaiveDateTime) -> Number {
+        let keys = nodes.keys();
+        let t: Vec<f64> = keys.iter().map(|k| (k - keys[0]) as f64).collect();
+        let t_ = (date.and_utc().timestamp() - keys[0]) as f64;
+
+        macro_rules! interp {
+            ($Variant: ident, $indexmap: expr) => {{
+                let mut rates: Vec<_> = $indexmap
+                    .values()
+                    .zip(t.iter())
+                    .skip(1)
+                    .map(|(v, t)| v.log() * (-1_f64 / t))
+                    .collect();
+                rates.insert(0, rates[0].clone());
+                Number::$Variant((natural_spline_interp(&t, &rates, t_) * -t_).exp())
+            }};
+        }
+        match nodes {
+            NodesTimestamp::F64(m) => interp!(F64, m),
+            NodesTimestamp::Dual(m) => interp!(Dual, m),
+            NodesTimestamp::Dual2(m) => interp!(Dual2, m),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, Convention, Modifier, NamedCal};
+    use crate::curves::nodes::Nodes;
+    use crate::curves::{CurveDF, LogCubicInterpolator, LogLinearInterpolator};
+    use crate::dual::{ADOrder, Gradient1};
+    use indexmap::IndexMap;
+
+    fn nodes_fixture() -> Nodes {
+        Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2022, 1, 1), 1.0_f64),
+            (ndt(2023, 1, 1), 0.97_f64),
+            (ndt(2024, 1, 1), 0.935_f64),
+            (ndt(2025, 1, 1), 0.91_f64),
+            (ndt(2027, 1, 1), 0.86_f64),
+        ]))
+    }
+
+    fn curve<T: CurveInterpolation>(interpolator: T) -> CurveDF<T, NamedCal> {
+        CurveDF::try_new(
+            nodes_fixture(),
+            interpolator,
+            "sofr",
+            Convention::Act365F,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_cubic_zero_rate_reprices_nodes() {
+        let nts = NodesTimestamp::from(nodes_fixture());
+        let cz = CubicZeroRateInterpolator::new();
+        for (date, df) in [
+            (ndt(2022, 1, 1), 1.0),
+            (ndt(2024, 1, 1), 0.935),
+            (ndt(2027, 1, 1), 0.86),
+        ] {
+            assert!((f64::from(cz.interpolated_value(&nts, &date)) - df).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_cubic_zero_rate_ad() {
+        let mut curve = curve(CubicZeroRateInterpolator::new());
+        let date = ndt(2024, 7, 1);
+        let value = f64::from(curve.interpolated_value(&date));
+        curve.set_ad_order(ADOrder::One).unwrap();
+        let Number::Dual(d) = curve.interpolated_value(&date) else {
+            panic!("expected a Dual value")
+        };
+        assert!((d.real() - value).abs() < 1e-14);
+
+        // compare the sensitivity to a node with a finite difference
+        let bumped = |h: f64| {
+            let mut nodes = nodes_fixture();
+            if let Nodes::F64(m) = &mut nodes {
+                *m.get_mut(&ndt(2025, 1, 1)).unwrap() += h;
+            }
+            let nts = NodesTimestamp::from(nodes);
+            f64::from(CubicZeroRateInterpolator::new().interpolated_value(&nts, &date))
+        };
+        let fd = (bumped(1e-6) - bumped(-1e-6)) / 2e-6;
+        let grad = d.gradient1(vec!["sofr3".to_string()]);
+        assert!((grad[0] - fd).abs() < 1e-7);
+
+        curve.set_ad_order(ADOrder::Two).unwrap();
+        let Number::Dual2(d2) = curve.interpolated_value(&date) else {
+            panic!("expected a Dual2 value")
+        };
+        assert!((d2.gradient1(vec!["sofr3".to_string()])[0] - grad[0]).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_forward_smoothness_against_log_cubic() {
+        let zero = curve(CubicZeroRateInterpolator::new())
+            .diagnostics(1)
+            .unwrap();
+        let log_cubic = curve(LogCubicInterpolator::new()).diagnostics(1).unwrap();
+        let log_linear = curve(LogLinearInterpolator::new()).diagnostics(1).unwrap();
+        assert_eq!(zero.forwards.len(), log_cubic.forwards.len());
+        // both splines have continuous forwards, unlike the piecewise flat log-linear forwards
+        assert!(zero.max_forward_jump < 0.01);
+        assert!(log_cubic.max_forward_jump < 0.01);
+        assert!(log_linear.max_forward_jump > 0.1);
+        // forwards of either spline remain close to one another
+        let max_diff = zero
+            .forwards
+            .iter()
+            .zip(log_cubic.forwards.iter())
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0, f64::max);
+        assert!(max_diff < 0.5);
+        assert!(zero.roughness < 1e-4 && log_cubic.roughness < 1e-4);
+    }
+}
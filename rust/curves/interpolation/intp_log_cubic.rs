@@ -1,7 +1,7 @@
-use crate::curves::interpolation::utils::log_linear_interp;
+use crate::curves::interpolation::utils::natural_spline_interp;
 use crate::curves::nodes::NodesTimestamp;
 use crate::curves::CurveInterpolation;
-use crate::dual::DualsOrF64;
+use crate::dual::{MathFuncs, Number};
 use bincode::{deserialize, serialize};
 use chrono::NaiveDateTime;
 use pyo3::prelude::*;
@@ -10,25 +10,19 @@ use pyo3::{pyclass, pymethods, Bound, PyResult, Python};
 use serde::{Deserialize, Serialize};
 use std::cmp::PartialEq;
 
-/// Define log-linear interpolation of nodes.
+/// Define log-cubic interpolation of nodes.
+///
+/// A natural cubic spline is fitted to the logarithms of the node values at every node, and
+/// extrapolated linearly beyond the first and last nodes.
 #[pyclass(module = "rateslib.rs")]
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-pub struct LogCubicInterpolator<T: NumberMapping> {
-    spline: T
-}
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LogCubicInterpolator {}
 
 #[pymethods]
-impl<T> LogCubicInterpolator
-where          T: PartialOrd + Signed + Clone + Sum + Zero,
-   for<'a> &'a T: Sub<&'a T, Output = T>,
-   for<'a> &'a f64: Mul<&'a T, Output = T>,
-{
+impl LogCubicInterpolator {
     #[new]
-    pub fn new(t: Vec<f64>, c: Option<Vec<T>>) -> Self {
-        let spline: PPSpline<T> = PPSpline.new(3_usize, t, c);
-        LogCubicInterpolator {
-            spline
-        }
+    pub fn new() -> Self {
+        LogCubicInterpolator {}
     }
 
     // Pickling
@@ -39,21 +33,20 @@ where          T: PartialOrd + Signed + Clone + Sum + Zero,
     pub fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
         Ok(PyBytes::new_bound(py, &serialize(&self).unwrap()))
     }
-    pub fn __getnewargs__<'py>(&self, py: Python<'py>) -> PyResult<(Vec<f64>, Option<Vec<T>>)> {
-        Ok((self.t.clone(), ))
+    pub fn __getnewargs__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyTuple>> {
+        Ok(PyTuple::empty_bound(py))
     }
 }
 
-impl CurveInterpolation for LogLinearInterpolator {
-    fn interpolated_value(&self, nodes: &NodesTimestamp, date: &NaiveDateTime) -> DualsOrF64 {
-        let x = date.and_utc().timestamp();
-        let index = self.node_index(nodes, x);
+impl CurveInterpolation for LogCubicInterpolator {
+    fn interpolated_value(&self, nodes: &NodesTimestamp, date: &NaiveDateTime) -> Number {
+        let x = date.and_utc().timestamp() as f64;
+        let keys: Vec<f64> = nodes.keys().into_iter().map(|k| k as f64).collect();
 
         macro_rules! interp {
             ($Variant: ident, $indexmap: expr) => {{
-                let (x1, y1) = $indexmap.get_index(index).unwrap();
-                let (x2, y2) = $indexmap.get_index(index + 1_usize).unwrap();
-                DualsOrF64::$Variant(log_linear_interp(*x1 as f64, y1, *x2 as f64, y2, x as f64))
+                let logs: Vec<_> = $indexmap.values().map(|v| v.log()).collect();
+                Number::$Variant(natural_spline_interp(&keys, &logs, x).exp())
             }};
         }
         match nodes {
@@ -69,6 +62,7 @@ mod tests {
     use super::*;
     use crate::calendars::ndt;
     use crate::curves::nodes::Nodes;
+    use crate::curves::LogLinearInterpolator;
     use indexmap::IndexMap;
 
     fn nodes_timestamp_fixture() -> NodesTimestamp {
@@ -81,11 +75,18 @@ mod tests {
     }
 
     #[test]
-    fn test_log_linear() {
+    fn test_log_cubic() {
         let nts = nodes_timestamp_fixture();
+        let lc = LogCubicInterpolator::new();
         let ll = LogLinearInterpolator::new();
-        let result = ll.interpolated_value(&nts, &ndt(2000, 7, 1));
-        // expected = exp(0 + (182 / 366) * (ln(0.99) - ln(1.0)) = 0.995015
-        assert_eq!(result, DualsOrF64::F64(0.9950147597711371));
+        for date in [ndt(2000, 1, 1), ndt(2001, 1, 1), ndt(2002, 1, 1)] {
+            let (a, b) = (
+                lc.interpolated_value(&nts, &date),
+                ll.interpolated_value(&nts, &date),
+            );
+            assert!((f64::from(a) - f64::from(b)).abs() < 1e-12);
+        }
+        let result = f64::from(lc.interpolated_value(&nts, &ndt(2000, 7, 1)));
+        assert!(result > 0.99 && result < 1.0);
     }
 }
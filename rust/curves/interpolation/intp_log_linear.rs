@@ -1,7 +1,8 @@
-use crate::curves::interpolation::utils::log_linear_interp;
+use crate::curves::interpolation::utils::{index_left, linear_interp, log_linear_interp};
 use crate::curves::nodes::NodesTimestamp;
 use crate::curves::CurveInterpolation;
-use crate::dual::Number;
+use crate::dual::{Dual, MathFuncs, Number};
+use crate::logging::event;
 use bincode::{deserialize, serialize};
 use chrono::NaiveDateTime;
 use pyo3::prelude::*;
@@ -9,17 +10,91 @@ use pyo3::types::{PyBytes, PyTuple};
 use pyo3::{pyclass, pymethods, Bound, PyResult, Python};
 use serde::{Deserialize, Serialize};
 use std::cmp::PartialEq;
+use std::sync::RwLock;
+
+/// The keys and logarithms of node values, for the curve state in which they were calculated.
+#[derive(Clone, Debug)]
+struct LogNodes {
+    state_id: u64,
+    keys: Vec<i64>,
+    logs: LogValues,
+}
+
+#[derive(Clone, Debug)]
+enum LogValues {
+    F64(Vec<f64>),
+    Dual(Vec<Dual>),
+}
+
+impl LogNodes {
+    /// Calculate the logarithms of the nodes, unless they are `Dual2`.
+    fn try_new(nodes: &NodesTimestamp, state_id: u64) -> Option<Box<Self>> {
+        let logs = match nodes {
+            NodesTimestamp::F64(m) => LogValues::F64(m.values().map(|v| v.ln()).collect()),
+            NodesTimestamp::Dual(m) => LogValues::Dual(m.values().map(|v| v.log()).collect()),
+            NodesTimestamp::Dual2(_) => return None,
+        };
+        Some(Box::new(LogNodes {
+            state_id,
+            keys: nodes.keys(),
+            logs,
+        }))
+    }
+
+    fn is_valid(&self, nodes: &NodesTimestamp, state_id: u64) -> bool {
+        self.state_id == state_id
+            && matches!(
+                (&self.logs, nodes),
+                (LogValues::F64(_), NodesTimestamp::F64(_))
+                    | (LogValues::Dual(_), NodesTimestamp::Dual(_))
+            )
+    }
+
+    fn interpolated_value(&self, x: i64) -> Number {
+        let index = index_left(&self.keys, &x, None);
+        let (x1, x2) = (self.keys[index] as f64, self.keys[index + 1] as f64);
+        match &self.logs {
+            LogValues::F64(l) => {
+                Number::F64(linear_interp(x1, &l[index], x2, &l[index + 1], x as f64).exp())
+            }
+            LogValues::Dual(l) => {
+                Number::Dual(linear_interp(x1, &l[index], x2, &l[index + 1], x as f64).exp())
+            }
+        }
+    }
+}
 
 /// Define log-linear interpolation of nodes.
+///
+/// When evaluated for a curve state the logarithms of the node values are cached, except for
+/// `Dual2` nodes, and reused until the state changes.
 #[pyclass(module = "rateslib.rs")]
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-pub struct LogLinearInterpolator {}
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct LogLinearInterpolator {
+    #[serde(skip)]
+    cache: RwLock<Option<Box<LogNodes>>>,
+}
+
+impl Clone for LogLinearInterpolator {
+    fn clone(&self) -> Self {
+        LogLinearInterpolator {
+            cache: RwLock::new(self.cache.read().unwrap().clone()),
+        }
+    }
+}
+
+/// Equality does not consider the cache.
+impl PartialEq for LogLinearInterpolator {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
 
 #[pymethods]
 impl LogLinearInterpolator {
     #[new]
     pub fn new() -> Self {
-        LogLinearInterpolator {}
+        LogLinearInterpolator::default()
     }
 
     // Pickling
@@ -53,6 +128,29 @@ impl CurveInterpolation for LogLinearInterpolator {
             NodesTimestamp::Dual2(m) => interp!(Dual2, m),
         }
     }
+
+    fn interpolated_value_by_state(
+        &self,
+        nodes: &NodesTimestamp,
+        date: &NaiveDateTime,
+        state_id: u64,
+    ) -> Number {
+        let x = date.and_utc().timestamp();
+        if let Some(cache) = &*self.cache.read().unwrap() {
+            if cache.is_valid(nodes, state_id) {
+                return cache.interpolated_value(x);
+            }
+        }
+        match LogNodes::try_new(nodes, state_id) {
+            None => self.interpolated_value(nodes, date),
+            Some(cache) => {
+                event!(trace, state_id, "log-linear cache rebuilt");
+                let value = cache.interpolated_value(x);
+                *self.cache.write().unwrap() = Some(cache);
+                value
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -79,4 +177,25 @@ mod tests {
         // expected = exp(0 + (182 / 366) * (ln(0.99) - ln(1.0)) = 0.995015
         assert_eq!(result, Number::F64(0.9950147597711371));
     }
+
+    #[test]
+    fn test_log_linear_cached_by_state() {
+        let nts = nodes_timestamp_fixture();
+        let ll = LogLinearInterpolator::new();
+        for date in [ndt(2000, 7, 1), ndt(2001, 7, 1), ndt(2003, 1, 1)] {
+            let expected = ll.interpolated_value(&nts, &date);
+            assert_eq!(ll.interpolated_value_by_state(&nts, &date, 1), expected);
+            assert_eq!(ll.interpolated_value_by_state(&nts, &date, 1), expected);
+        }
+
+        // a new state recalculates the logarithms of the nodes
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2000, 1, 1), 1.0_f64),
+            (ndt(2001, 1, 1), 0.98_f64),
+            (ndt(2002, 1, 1), 0.96_f64),
+        ]));
+        let nts2 = NodesTimestamp::from(nodes);
+        let result = ll.interpolated_value_by_state(&nts2, &ndt(2000, 7, 1), 2);
+        assert_eq!(result, ll.interpolated_value(&nts2, &ndt(2000, 7, 1)));
+    }
 }
@@ -1,9 +1,11 @@
 pub(crate) mod interpolation_py;
 
+pub(crate) mod intp_cubic_zero_rate;
 pub(crate) mod intp_flat_backward;
 pub(crate) mod intp_flat_forward;
 pub(crate) mod intp_linear;
 pub(crate) mod intp_linear_zero_rate;
+pub(crate) mod intp_log_cubic;
 pub(crate) mod intp_log_linear;
 pub(crate) mod intp_null;
 
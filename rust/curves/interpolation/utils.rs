@@ -1,7 +1,15 @@
+//! Interpolation of coordinates, independent of any curve.
+
 use crate::dual::{MathFuncs, NumberOps};
+use crate::splines::{PPSpline, SplineBoundary};
+use num_traits::{Signed, Zero};
+use pyo3::exceptions::PyValueError;
+use pyo3::PyErr;
 use std::{
     cmp::{PartialEq, PartialOrd},
-    ops::{Mul, Sub},
+    iter::Sum,
+    ops::{Add, Mul, Sub},
+    str::FromStr,
 };
 
 // pub(crate) fn linear_interp<T, U>(x1: &T, y1: &U, x2: &T, y2: &U, x: &T) -> U
@@ -14,7 +22,7 @@ use std::{
 // }
 
 /// Calculate the linear interpolation between two coordinates.
-pub(crate) fn linear_interp<T>(x1: f64, y1: &T, x2: f64, y2: &T, x: f64) -> T
+pub fn linear_interp<T>(x1: f64, y1: &T, x2: f64, y2: &T, x: f64) -> T
 where
     for<'a> &'a T: NumberOps<T>,
     T: Mul<f64, Output = T>,
@@ -23,7 +31,7 @@ where
 }
 
 /// Calculate the log-linear interpolation between two coordinates.
-pub(crate) fn log_linear_interp<T>(x1: f64, y1: &T, x2: f64, y2: &T, x: f64) -> T
+pub fn log_linear_interp<T>(x1: f64, y1: &T, x2: f64, y2: &T, x: f64) -> T
 where
     for<'a> &'a T: NumberOps<T>,
     T: Mul<f64, Output = T> + MathFuncs,
@@ -34,7 +42,7 @@ where
 }
 
 /// Calculate the linear zero rate interpolation between two coordinates.
-pub(crate) fn linear_zero_interp<T>(x0: f64, x1: f64, y1: &T, x2: f64, y2: &T, x: f64) -> T
+pub fn linear_zero_interp<T>(x0: f64, x1: f64, y1: &T, x2: f64, y2: &T, x: f64) -> T
 where
     for<'a> &'a T: NumberOps<T>,
     T: Mul<f64, Output = T> + MathFuncs + Sub + Clone,
@@ -52,6 +60,92 @@ where
     (r * -t).exp()
 }
 
+/// Calculate the natural cubic spline interpolation of the coordinates `x` and `y` at `x_`.
+///
+/// Beyond the first and last coordinates the spline is extrapolated linearly, continuing its
+/// value and first derivative, which, with zero second derivatives at the end points, is
+/// twice continuously differentiable.
+pub fn natural_spline_interp<T>(x: &[f64], y: &[T], x_: f64) -> T
+where
+    T: PartialOrd + Signed + Clone + Sum + Zero + Add<T, Output = T>,
+    for<'a> &'a T: Sub<&'a T, Output = T>,
+    for<'a> &'a f64: Mul<&'a T, Output = T>,
+{
+    let spline = PPSpline::from_points(x, y, 3, SplineBoundary::Natural).unwrap();
+    let end = x_.clamp(x[0], x[x.len() - 1]);
+    let value = spline.ppdnev_single(&end, 0).unwrap();
+    if end == x_ {
+        value
+    } else {
+        value + &(x_ - end) * &spline.ppdnev_single(&end, 1).unwrap()
+    }
+}
+
+/// A method of interpolation of coordinates, see [interpolate].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum InterpolationKind {
+    /// Linear interpolation of the values.
+    Linear,
+    /// Linear interpolation of the logarithms of the values.
+    LogLinear,
+    /// Natural cubic spline interpolation of the values.
+    Cubic,
+    /// Natural cubic spline interpolation of the logarithms of the values.
+    LogCubic,
+}
+
+impl FromStr for InterpolationKind {
+    type Err = PyErr;
+
+    /// Parse a kind from `"linear"`, `"log_linear"`, `"cubic"` or `"log_cubic"`, case
+    /// insensitive.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "linear" => Ok(InterpolationKind::Linear),
+            "log_linear" => Ok(InterpolationKind::LogLinear),
+            "cubic" => Ok(InterpolationKind::Cubic),
+            "log_cubic" => Ok(InterpolationKind::LogCubic),
+            _ => Err(PyValueError::new_err(format!(
+                "'{}' is not a valid interpolation, expected one of: 'linear', 'log_linear', \
+                 'cubic', 'log_cubic'.",
+                s
+            ))),
+        }
+    }
+}
+
+/// Interpolate the coordinates `xs` and `ys` at `x`.
+///
+/// `xs` must be strictly increasing with at least two coordinates. Beyond the first and last
+/// coordinates values are extrapolated from the first and last intervals for linear kinds, and
+/// linearly for cubic kinds. Log kinds require positive `ys`.
+pub fn interpolate<T>(x: f64, xs: &[f64], ys: &[T], kind: InterpolationKind) -> Result<T, PyErr>
+where
+    T: PartialOrd + Signed + Clone + Sum + Zero + MathFuncs + Mul<f64, Output = T>,
+    for<'a> &'a T: NumberOps<T>,
+    for<'a> &'a f64: Mul<&'a T, Output = T>,
+{
+    if xs.len() != ys.len() || xs.len() < 2 {
+        return Err(PyValueError::new_err(
+            "`xs` and `ys` must have the same length of at least two.",
+        ));
+    }
+    if xs.windows(2).any(|w| w[0] >= w[1]) {
+        return Err(PyValueError::new_err("`xs` must be strictly increasing."));
+    }
+    let i = index_left(xs, &x, None);
+    let value = match kind {
+        InterpolationKind::Linear => linear_interp(xs[i], &ys[i], xs[i + 1], &ys[i + 1], x),
+        InterpolationKind::LogLinear => log_linear_interp(xs[i], &ys[i], xs[i + 1], &ys[i + 1], x),
+        InterpolationKind::Cubic => natural_spline_interp(xs, ys, x),
+        InterpolationKind::LogCubic => {
+            let logs: Vec<T> = ys.iter().map(|y| y.log()).collect();
+            natural_spline_interp(xs, &logs, x).exp()
+        }
+    };
+    Ok(value)
+}
+
 /// Calculate the left sided index for a given value in a sorted list.
 /// `left_count` is used recursively; it should always be entered as None intially.
 /// Examples
@@ -64,7 +158,7 @@ where
 /// - 1.71: returns 1 (within second interval)
 /// - 2.8: returns 2 (closed right side of third interval)
 /// - 3.5: returns 2 (extrapolated out of range)
-pub(crate) fn index_left<T>(list_input: &[T], value: &T, left_count: Option<usize>) -> usize
+pub fn index_left<T>(list_input: &[T], value: &T, left_count: Option<usize>) -> usize
 where
     for<'a> &'a T: PartialOrd + PartialEq,
 {
@@ -89,7 +183,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::dual::Dual;
+    use crate::dual::{Dual, Gradient1};
 
     #[test]
     fn index_left_() {
@@ -122,6 +216,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_natural_spline_interp() {
+        let x = [0.0, 1.0, 2.0];
+        let y = [1.0, 3.0, 2.0];
+        for (xi, yi) in x.iter().zip(y) {
+            assert!((natural_spline_interp(&x, &y, *xi) - yi).abs() < 1e-12);
+        }
+        // linear extrapolation continues the end slopes
+        let slope = natural_spline_interp(&x, &y, 2.0) - natural_spline_interp(&x, &y, 1.999);
+        let extrapolated = natural_spline_interp(&x, &y, 3.0) - 2.0;
+        assert!((extrapolated - slope * 1000.0).abs() < 1e-5);
+
+        // two points are interpolated linearly
+        let y = [
+            Dual::new(1.0, vec!["a".to_string()]),
+            Dual::new(3.0, vec!["b".to_string()]),
+        ];
+        let result = natural_spline_interp(&x[..2], &y, 0.25);
+        assert!((result.real() - 1.5).abs() < 1e-12);
+        let grad = result.gradient1(vec!["a".to_string(), "b".to_string()]);
+        assert!((grad[0] - 0.75).abs() < 1e-12 && (grad[1] - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let xs = [0.0, 1.0, 2.0];
+        let ys = [1.0, 0.5, 0.25];
+        let kind = InterpolationKind::from_str("Log_Linear").unwrap();
+        assert!((interpolate(1.5, &xs, &ys, kind).unwrap() - 0.125_f64.sqrt()).abs() < 1e-15);
+        assert!((interpolate(3.0, &xs, &ys, kind).unwrap() - 0.125).abs() < 1e-15);
+        let linear = interpolate(-1.0, &xs, &ys, InterpolationKind::Linear).unwrap();
+        assert!((linear - 1.5).abs() < 1e-15);
+        for (xi, yi) in xs.iter().zip(ys) {
+            let cubic = interpolate(*xi, &xs, &ys, InterpolationKind::Cubic).unwrap();
+            let log_cubic = interpolate(*xi, &xs, &ys, InterpolationKind::LogCubic).unwrap();
+            assert!((cubic - yi).abs() < 1e-12 && (log_cubic - yi).abs() < 1e-12);
+        }
+
+        // Dual values carry their sensitivities
+        let ys: Vec<Dual> = ["a", "b", "c"]
+            .iter()
+            .zip(ys)
+            .map(|(v, y)| Dual::new(y, vec![v.to_string()]))
+            .collect();
+        let result = interpolate(0.25, &xs, &ys, InterpolationKind::Linear).unwrap();
+        assert_eq!(result.gradient1(vec!["a".to_string()])[0], 0.75);
+
+        assert!(interpolate(0.5, &xs[..1], &ys[..1], InterpolationKind::Linear).is_err());
+        assert!(interpolate(0.5, &[0.0, 0.0], &ys[..2], InterpolationKind::Cubic).is_err());
+        assert!(InterpolationKind::from_str("quadratic").is_err());
+    }
+
     #[test]
     fn test_log_linear_interp() {
         // float linear_interp
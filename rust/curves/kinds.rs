@@ -0,0 +1,303 @@
+use crate::calendars::{Convention, DateRoll};
+use crate::curves::{CurveDF, CurveInterpolation, CurveSplice, FlatCurve};
+use crate::dual::{ADOrder, Number};
+use crate::state::CompositeState;
+use chrono::NaiveDateTime;
+use pyo3::exceptions::PyValueError;
+use pyo3::PyErr;
+
+/// Evaluation shared by every kind of curve.
+pub trait CurveEvaluation {
+    /// The day count convention of the rates of the curve.
+    fn convention(&self) -> Convention;
+
+    /// Return the discount factor (DF) at `date`.
+    fn df(&self, date: &NaiveDateTime) -> Result<Number, PyErr>;
+
+    /// Return the index value at `date`, for a curve with an index base.
+    fn index_value(&self, date: &NaiveDateTime) -> Result<Number, PyErr>;
+
+    /// The id of the current state of the curve, which changes whenever it is mutated.
+    fn state_id(&self) -> u64;
+
+    /// Return the simple rate, in percent, from `start` to `end` under the convention of the
+    /// curve.
+    ///
+    /// The `convention` must not require additional arguments to measure day count fractions.
+    fn rate(&self, start: &NaiveDateTime, end: &NaiveDateTime) -> Result<Number, PyErr> {
+        if end <= start {
+            return Err(PyValueError::new_err("`end` must be after `start`."));
+        }
+        let dcf = self
+            .convention()
+            .dcf(start, end, None, None, None, None, None)?;
+        Ok((self.df(start)? / self.df(end)? - 1.0) * (100.0 / dcf))
+    }
+}
+
+fn no_index_base() -> PyErr {
+    PyValueError::new_err("Can only calculate `index_value` for a curve with an `index_base`.")
+}
+
+impl<T: CurveInterpolation, U: DateRoll> CurveEvaluation for CurveDF<T, U> {
+    fn convention(&self) -> Convention {
+        self.convention
+    }
+
+    fn df(&self, date: &NaiveDateTime) -> Result<Number, PyErr> {
+        Ok(self.interpolated_value(date))
+    }
+
+    fn index_value(&self, date: &NaiveDateTime) -> Result<Number, PyErr> {
+        CurveDF::index_value(self, date)
+    }
+
+    fn state_id(&self) -> u64 {
+        CurveDF::state_id(self)
+    }
+}
+
+impl CurveEvaluation for FlatCurve {
+    fn convention(&self) -> Convention {
+        self.convention
+    }
+
+    fn df(&self, date: &NaiveDateTime) -> Result<Number, PyErr> {
+        FlatCurve::df(self, date)
+    }
+
+    fn index_value(&self, _date: &NaiveDateTime) -> Result<Number, PyErr> {
+        Err(no_index_base())
+    }
+
+    fn state_id(&self) -> u64 {
+        self.state_id
+    }
+}
+
+impl<T: CurveInterpolation, U: DateRoll> CurveEvaluation for CurveSplice<T, U> {
+    /// The convention of the short end curve.
+    fn convention(&self) -> Convention {
+        self.short_end.convention
+    }
+
+    fn df(&self, date: &NaiveDateTime) -> Result<Number, PyErr> {
+        Ok(self.interpolated_value(date))
+    }
+
+    fn index_value(&self, _date: &NaiveDateTime) -> Result<Number, PyErr> {
+        Err(no_index_base())
+    }
+
+    /// The combined state id of the short and long end curves.
+    fn state_id(&self) -> u64 {
+        CompositeState::new(vec![self.short_end.state_id(), self.long_end.state_id()]).state_id()
+    }
+}
+
+/// Container for each kind of curve, so that a single type can hold any of them.
+///
+/// A curve of DFs interpolated from nodes is also an index curve if it has an index base.
+#[derive(Clone, Debug)]
+pub enum Curves<T: CurveInterpolation, U: DateRoll> {
+    /// A curve of DFs interpolated from nodes.
+    DF(CurveDF<T, U>),
+    /// A curve with a constant continuously compounded rate.
+    Flat(FlatCurve),
+    /// A composite of two curves, one before and one after a cutoff date.
+    Splice(CurveSplice<T, U>),
+}
+
+impl<T: CurveInterpolation, U: DateRoll> Curves<T, U> {
+    /// The identifier of the curve, which a spliced curve does not have.
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            Curves::DF(c) => Some(&c.id),
+            Curves::Flat(c) => Some(&c.id),
+            Curves::Splice(_) => None,
+        }
+    }
+
+    /// Set the order of automatic differentiation of the curve.
+    ///
+    /// The rate of a flat curve is tagged with the id of the curve, and both curves of a
+    /// spliced curve are set.
+    pub fn set_ad_order(&mut self, ad: ADOrder) -> Result<(), PyErr> {
+        match self {
+            Curves::DF(c) => c.set_ad_order(ad),
+            Curves::Flat(c) => c.set_ad_order(ad),
+            Curves::Splice(c) => {
+                c.short_end.set_ad_order(ad)?;
+                c.long_end.set_ad_order(ad)
+            }
+        }
+    }
+}
+
+impl<T: CurveInterpolation, U: DateRoll> CurveEvaluation for Curves<T, U> {
+    fn convention(&self) -> Convention {
+        match self {
+            Curves::DF(c) => c.convention,
+            Curves::Flat(c) => c.convention,
+            Curves::Splice(c) => c.short_end.convention,
+        }
+    }
+
+    fn df(&self, date: &NaiveDateTime) -> Result<Number, PyErr> {
+        match self {
+            Curves::DF(c) => CurveEvaluation::df(c, date),
+            Curves::Flat(c) => CurveEvaluation::df(c, date),
+            Curves::Splice(c) => CurveEvaluation::df(c, date),
+        }
+    }
+
+    fn index_value(&self, date: &NaiveDateTime) -> Result<Number, PyErr> {
+        match self {
+            Curves::DF(c) => CurveEvaluation::index_value(c, date),
+            Curves::Flat(c) => CurveEvaluation::index_value(c, date),
+            Curves::Splice(c) => CurveEvaluation::index_value(c, date),
+        }
+    }
+
+    fn state_id(&self) -> u64 {
+        match self {
+            Curves::DF(c) => CurveEvaluation::state_id(c),
+            Curves::Flat(c) => CurveEvaluation::state_id(c),
+            Curves::Splice(c) => CurveEvaluation::state_id(c),
+        }
+    }
+}
+
+impl<T: CurveInterpolation, U: DateRoll> From<CurveDF<T, U>> for Curves<T, U> {
+    fn from(value: CurveDF<T, U>) -> Self {
+        Curves::DF(value)
+    }
+}
+
+impl<T: CurveInterpolation, U: DateRoll> From<FlatCurve> for Curves<T, U> {
+    fn from(value: FlatCurve) -> Self {
+        Curves::Flat(value)
+    }
+}
+
+impl<T: CurveInterpolation, U: DateRoll> From<CurveSplice<T, U>> for Curves<T, U> {
+    fn from(value: CurveSplice<T, U>) -> Self {
+        Curves::Splice(value)
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, Modifier, NamedCal};
+    use crate::curves::nodes::Nodes;
+    use crate::curves::{LogLinearInterpolator, SpliceAdjustment};
+    use crate::dual::{Dual, Gradient1};
+    use indexmap::IndexMap;
+
+    fn curve(id: &str, df: f64) -> CurveDF<LogLinearInterpolator, NamedCal> {
+        CurveDF::try_new(
+            Nodes::F64(IndexMap::from_iter(vec![
+                (ndt(2024, 1, 1), 1.0),
+                (ndt(2026, 1, 1), df),
+            ])),
+            LogLinearInterpolator::new(),
+            id,
+            Convention::Act365F,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_curves_dispatch() {
+        let flat = FlatCurve::try_new(
+            Number::F64(3.0),
+            Convention::Act365F,
+            ndt(2024, 1, 1),
+            "flat",
+        )
+        .unwrap();
+        let splice = CurveSplice::try_new(
+            curve("a", 0.95),
+            curve("b", 0.9),
+            ndt(2025, 1, 1),
+            SpliceAdjustment::Scaled,
+        )
+        .unwrap();
+        let curves: Vec<Curves<LogLinearInterpolator, NamedCal>> = vec![
+            curve("a", 0.95).into(),
+            flat.clone().into(),
+            splice.clone().into(),
+        ];
+        let date = ndt(2025, 7, 1);
+        let expected = [
+            curve("a", 0.95).interpolated_value(&date),
+            flat.df(&date).unwrap(),
+            splice.interpolated_value(&date),
+        ];
+        for (c, e) in curves.iter().zip(expected) {
+            assert_eq!(c.df(&date).unwrap(), e);
+            assert!(c.index_value(&date).is_err());
+        }
+
+        // the simple rate of a flat curve compounds to the continuously compounded rate
+        let (start, end) = (ndt(2024, 1, 1), ndt(2025, 1, 1));
+        let rate = f64::from(curves[1].rate(&start, &end).unwrap());
+        let dcf = 366.0_f64 / 365.0;
+        assert!((rate - ((0.03 * dcf).exp() - 1.0) / dcf * 100.0).abs() < 1e-12);
+        assert!(curves[1].rate(&end, &start).is_err());
+        assert_eq!(curves[2].id(), None);
+    }
+
+    #[test]
+    fn test_curves_state_and_ad_order() {
+        let mut flat: Curves<LogLinearInterpolator, NamedCal> = FlatCurve::try_new(
+            Number::F64(3.0),
+            Convention::Act365F,
+            ndt(2024, 1, 1),
+            "flat",
+        )
+        .unwrap()
+        .into();
+        let id = flat.state_id();
+        flat.set_ad_order(ADOrder::One).unwrap();
+        assert_ne!(id, flat.state_id());
+        let Number::Dual(df) = flat.df(&ndt(2025, 1, 1)).unwrap() else {
+            panic!("expected a Dual")
+        };
+        assert!(df.gradient1(vec!["flat0".to_string()])[0] < 0.0);
+        assert!(matches!(
+            Curves::<LogLinearInterpolator, NamedCal>::from(
+                FlatCurve::try_new(
+                    Number::Dual(Dual::new(3.0, vec!["r".to_string()])),
+                    Convention::Act365F,
+                    ndt(2024, 1, 1),
+                    "flat",
+                )
+                .unwrap()
+            ),
+            Curves::Flat(_)
+        ));
+
+        let mut splice: Curves<_, _> = CurveSplice::try_new(
+            curve("a", 0.95),
+            curve("b", 0.9),
+            ndt(2025, 1, 1),
+            SpliceAdjustment::Scaled,
+        )
+        .unwrap()
+        .into();
+        let id = splice.state_id();
+        assert_eq!(id, splice.state_id());
+        splice.set_ad_order(ADOrder::Two).unwrap();
+        assert_ne!(id, splice.state_id());
+        assert!(matches!(
+            splice.df(&ndt(2025, 7, 1)).unwrap(),
+            Number::Dual2(_)
+        ));
+    }
+}
@@ -1,19 +1,45 @@
 //! Create curves for calculating interest rates and discount factors.
 
 pub(crate) mod nodes;
-pub use crate::curves::nodes::Nodes;
+pub use crate::curves::nodes::{Nodes, NodesTimestamp};
 
 pub(crate) mod interpolation;
+pub use crate::curves::interpolation::intp_cubic_zero_rate::CubicZeroRateInterpolator;
 pub use crate::curves::interpolation::intp_flat_backward::FlatBackwardInterpolator;
 pub use crate::curves::interpolation::intp_flat_forward::FlatForwardInterpolator;
 pub use crate::curves::interpolation::intp_linear::LinearInterpolator;
 pub use crate::curves::interpolation::intp_linear_zero_rate::LinearZeroRateInterpolator;
+pub use crate::curves::interpolation::intp_log_cubic::LogCubicInterpolator;
 pub use crate::curves::interpolation::intp_log_linear::LogLinearInterpolator;
 pub use crate::curves::interpolation::intp_null::NullInterpolator;
+pub use crate::curves::interpolation::utils::{
+    index_left, interpolate, linear_interp, linear_zero_interp, log_linear_interp,
+    natural_spline_interp, InterpolationKind,
+};
 
 pub(crate) mod curve;
 pub use crate::curves::curve::{CurveDF, CurveInterpolation};
 
+mod flat;
+pub use crate::curves::flat::FlatCurve;
+
+mod splice;
+pub use crate::curves::splice::{CurveSplice, SpliceAdjustment};
+
+mod kinds;
+pub use crate::curves::kinds::{CurveEvaluation, Curves};
+
+mod rolldown;
+
+mod step;
+pub use crate::curves::step::StepFunction;
+
+mod diagnostics;
+pub use crate::curves::diagnostics::CurveDiagnostics;
+
+pub(crate) mod registry;
+pub use crate::curves::registry::CurveRegistry;
+
 pub(crate) mod curve_py;
 
 mod serde;
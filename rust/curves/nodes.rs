@@ -1,3 +1,4 @@
+use crate::dates::{epoch_seconds, from_epoch_seconds};
 use crate::dual::{Dual, Dual2, Number};
 use chrono::{DateTime, NaiveDateTime};
 use indexmap::IndexMap;
@@ -12,13 +13,53 @@ pub enum Nodes {
     Dual2(IndexMap<NaiveDateTime, Dual2>),
 }
 
+/// Unix timestamp indexed values of a specific [ADOrder](`crate::dual::ADOrder`).
+///
+/// Keys are whole seconds from the Unix epoch, see [epoch_seconds], so nodes may be before 1970
+/// or far in the future. Every key is the timestamp of a [NaiveDateTime], which is validated
+/// when nodes are deserialized.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(try_from = "NodesTimestampDataModel")]
 pub enum NodesTimestamp {
     F64(IndexMap<i64, f64>),
     Dual(IndexMap<i64, Dual>),
     Dual2(IndexMap<i64, Dual2>),
 }
 
+#[derive(Deserialize)]
+enum NodesTimestampDataModel {
+    F64(IndexMap<i64, f64>),
+    Dual(IndexMap<i64, Dual>),
+    Dual2(IndexMap<i64, Dual2>),
+}
+
+impl TryFrom<NodesTimestampDataModel> for NodesTimestamp {
+    type Error = String;
+
+    fn try_from(model: NodesTimestampDataModel) -> Result<Self, Self::Error> {
+        let nodes = match model {
+            NodesTimestampDataModel::F64(m) => NodesTimestamp::F64(m),
+            NodesTimestampDataModel::Dual(m) => NodesTimestamp::Dual(m),
+            NodesTimestampDataModel::Dual2(m) => NodesTimestamp::Dual2(m),
+        };
+        match nodes
+            .keys()
+            .into_iter()
+            .find(|k| DateTime::from_timestamp(*k, 0).is_none())
+        {
+            Some(k) => Err(format!("Node key {} is out of the range of dates.", k)),
+            None => Ok(nodes),
+        }
+    }
+}
+
+/// Return the date of a node key.
+///
+/// Panics if the key is not the timestamp of a [NaiveDateTime], which [NodesTimestamp] prevents.
+pub(crate) fn node_date(key: i64) -> NaiveDateTime {
+    from_epoch_seconds(key).expect("Node keys are the timestamps of valid dates.")
+}
+
 impl NodesTimestamp {
     pub fn first_key(&self) -> i64 {
         match self {
@@ -33,13 +74,13 @@ impl From<Nodes> for NodesTimestamp {
     fn from(value: Nodes) -> Self {
         match value {
             Nodes::F64(m) => NodesTimestamp::F64(IndexMap::from_iter(
-                m.into_iter().map(|(k, v)| (k.and_utc().timestamp(), v)),
+                m.into_iter().map(|(k, v)| (epoch_seconds(&k), v)),
             )),
             Nodes::Dual(m) => NodesTimestamp::Dual(IndexMap::from_iter(
-                m.into_iter().map(|(k, v)| (k.and_utc().timestamp(), v)),
+                m.into_iter().map(|(k, v)| (epoch_seconds(&k), v)),
             )),
             Nodes::Dual2(m) => NodesTimestamp::Dual2(IndexMap::from_iter(
-                m.into_iter().map(|(k, v)| (k.and_utc().timestamp(), v)),
+                m.into_iter().map(|(k, v)| (epoch_seconds(&k), v)),
             )),
         }
     }
@@ -48,21 +89,15 @@ impl From<Nodes> for NodesTimestamp {
 impl From<NodesTimestamp> for Nodes {
     fn from(value: NodesTimestamp) -> Self {
         match value {
-            NodesTimestamp::F64(m) => {
-                Nodes::F64(IndexMap::from_iter(m.into_iter().map(|(k, v)| {
-                    (DateTime::from_timestamp(k, 0).unwrap().naive_utc(), v)
-                })))
-            }
-            NodesTimestamp::Dual(m) => {
-                Nodes::Dual(IndexMap::from_iter(m.into_iter().map(|(k, v)| {
-                    (DateTime::from_timestamp(k, 0).unwrap().naive_utc(), v)
-                })))
-            }
-            NodesTimestamp::Dual2(m) => {
-                Nodes::Dual2(IndexMap::from_iter(m.into_iter().map(|(k, v)| {
-                    (DateTime::from_timestamp(k, 0).unwrap().naive_utc(), v)
-                })))
-            }
+            NodesTimestamp::F64(m) => Nodes::F64(IndexMap::from_iter(
+                m.into_iter().map(|(k, v)| (node_date(k), v)),
+            )),
+            NodesTimestamp::Dual(m) => Nodes::Dual(IndexMap::from_iter(
+                m.into_iter().map(|(k, v)| (node_date(k), v)),
+            )),
+            NodesTimestamp::Dual2(m) => Nodes::Dual2(IndexMap::from_iter(
+                m.into_iter().map(|(k, v)| (node_date(k), v)),
+            )),
         }
     }
 }
@@ -93,23 +128,46 @@ impl NodesTimestamp {
     }
 
     pub(crate) fn index_map(&self) -> IndexMap<NaiveDateTime, Number> {
-        macro_rules! create_map {
-            ($map:ident, $Variant:ident) => {
-                IndexMap::from_iter($map.clone().into_iter().map(|(k, v)| {
-                    (
-                        DateTime::from_timestamp(k, 0).unwrap().naive_utc(),
-                        Number::$Variant(v),
-                    )
-                }))
-            };
+        IndexMap::from_iter(self.iter())
+    }
+
+    /// The number of nodes.
+    pub fn len(&self) -> usize {
+        match self {
+            NodesTimestamp::F64(m) => m.len(),
+            NodesTimestamp::Dual(m) => m.len(),
+            NodesTimestamp::Dual2(m) => m.len(),
         }
+    }
+
+    /// Whether there are no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The dates of the nodes, in order.
+    pub fn dates(&self) -> Vec<NaiveDateTime> {
+        self.keys().into_iter().map(node_date).collect()
+    }
 
+    /// The date and value of the node at `index`, or `None` if out of bounds.
+    pub fn get_index(&self, index: usize) -> Option<(NaiveDateTime, Number)> {
+        let date = |k: &i64| node_date(*k);
         match self {
-            NodesTimestamp::F64(m) => create_map!(m, F64),
-            NodesTimestamp::Dual(m) => create_map!(m, Dual),
-            NodesTimestamp::Dual2(m) => create_map!(m, Dual2),
+            NodesTimestamp::F64(m) => m.get_index(index).map(|(k, v)| (date(k), Number::F64(*v))),
+            NodesTimestamp::Dual(m) => m
+                .get_index(index)
+                .map(|(k, v)| (date(k), Number::Dual(v.clone()))),
+            NodesTimestamp::Dual2(m) => m
+                .get_index(index)
+                .map(|(k, v)| (date(k), Number::Dual2(v.clone()))),
         }
     }
+
+    /// Iterate over the dates and values of the nodes, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (NaiveDateTime, Number)> + '_ {
+        (0..self.len()).map(|i| self.get_index(i).unwrap())
+    }
 }
 
 //     /// Refactors the `get_index` method of an IndexMap and type casts the return values.
@@ -129,3 +187,42 @@ impl NodesTimestamp {
 //             },
 //         }
 //     }
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::ndt;
+
+    #[test]
+    fn test_nodes_timestamp_iteration() {
+        let d = Dual::new(0.99, vec!["v1".to_string()]);
+        let nodes = NodesTimestamp::from(Nodes::Dual(IndexMap::from_iter(vec![
+            (ndt(2000, 1, 1), Dual::new(1.0, vec!["v0".to_string()])),
+            (ndt(2001, 1, 1), d.clone()),
+        ])));
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes.dates(), vec![ndt(2000, 1, 1), ndt(2001, 1, 1)]);
+        assert_eq!(nodes.get_index(1), Some((ndt(2001, 1, 1), Number::Dual(d))));
+        assert_eq!(nodes.get_index(2), None);
+        assert_eq!(nodes.iter().count(), 2);
+        assert_eq!(
+            nodes.index_map().keys().cloned().collect::<Vec<_>>(),
+            nodes.dates()
+        );
+    }
+
+    #[test]
+    fn test_nodes_timestamp_rejects_invalid_keys() {
+        let nodes = NodesTimestamp::from(Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(1900, 1, 1), 1.0_f64),
+            (ndt(2500, 1, 1), 0.5_f64),
+        ])));
+        let json = serde_json::to_string(&nodes).unwrap();
+        let result: NodesTimestamp = serde_json::from_str(&json).unwrap();
+        assert_eq!(result.dates(), vec![ndt(1900, 1, 1), ndt(2500, 1, 1)]);
+        let key = epoch_seconds(&ndt(2500, 1, 1)).to_string();
+        let invalid = json.replace(&key, &i64::MAX.to_string());
+        assert!(serde_json::from_str::<NodesTimestamp>(&invalid).is_err());
+    }
+}
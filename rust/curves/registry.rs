@@ -0,0 +1,232 @@
+use crate::calendars::DateRoll;
+use crate::curves::{CurveDF, CurveInterpolation, Curves};
+use pyo3::exceptions::{PyRuntimeError, PyTypeError, PyValueError};
+use pyo3::PyErr;
+use std::collections::HashMap;
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// Map the poisoned lock of a registry, or of a curve within it, to an error.
+pub(crate) fn lock_error<G>(_: PoisonError<G>) -> PyErr {
+    PyRuntimeError::new_err("A curve registry lock was poisoned by a panic in another thread.")
+}
+
+/// A thread-safe store of shared curves, resolvable by string id.
+///
+/// Each curve is held in an `Arc<RwLock<T>>` so that any number of dependent objects may hold a
+/// handle to the same curve. Mutating the curve via the registry, or via any handle, is
+/// immediately visible to every other holder, allowing a curve to be updated in one place
+/// before everything dependent upon it is repriced.
+///
+/// Every method which locks the registry or a curve returns an error if that lock has been
+/// poisoned by a panic in another thread.
+#[derive(Debug, Default)]
+pub struct CurveRegistry<T> {
+    curves: RwLock<HashMap<String, Arc<RwLock<T>>>>,
+}
+
+impl<T> CurveRegistry<T> {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        CurveRegistry {
+            curves: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Insert a curve under the given id, replacing any existing curve, and return its handle.
+    ///
+    /// Handles obtained previously for a replaced curve remain valid but are no longer shared
+    /// with the registry.
+    pub fn insert(&self, id: &str, curve: T) -> Result<Arc<RwLock<T>>, PyErr> {
+        let handle = Arc::new(RwLock::new(curve));
+        self.curves
+            .write()
+            .map_err(lock_error)?
+            .insert(id.to_string(), Arc::clone(&handle));
+        Ok(handle)
+    }
+
+    /// Return the shared handle to the curve with the given id, if it exists.
+    pub fn get(&self, id: &str) -> Result<Option<Arc<RwLock<T>>>, PyErr> {
+        Ok(self
+            .curves
+            .read()
+            .map_err(lock_error)?
+            .get(id)
+            .map(Arc::clone))
+    }
+
+    /// Return the shared handle to the curve with the given id, or an error if it does not exist.
+    pub fn try_get(&self, id: &str) -> Result<Arc<RwLock<T>>, PyErr> {
+        self.get(id)?.ok_or_else(|| {
+            PyValueError::new_err(format!("Curve id '{}' is not found in the registry.", id))
+        })
+    }
+
+    /// Remove the curve with the given id from the registry, returning its handle if it existed.
+    pub fn remove(&self, id: &str) -> Result<Option<Arc<RwLock<T>>>, PyErr> {
+        Ok(self.curves.write().map_err(lock_error)?.remove(id))
+    }
+
+    /// Read the curve with the given id, as it is at the time of the call.
+    pub fn read<F, R>(&self, id: &str, f: F) -> Result<R, PyErr>
+    where
+        F: FnOnce(&T) -> Result<R, PyErr>,
+    {
+        let handle = self.try_get(id)?;
+        let curve = handle.read().map_err(lock_error)?;
+        f(&curve)
+    }
+
+    /// Mutate the curve with the given id in place.
+    pub fn update<F, R>(&self, id: &str, f: F) -> Result<R, PyErr>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let handle = self.try_get(id)?;
+        let mut curve = handle.write().map_err(lock_error)?;
+        Ok(f(&mut curve))
+    }
+
+    /// Return whether a curve with the given id exists in the registry.
+    pub fn contains(&self, id: &str) -> Result<bool, PyErr> {
+        Ok(self.curves.read().map_err(lock_error)?.contains_key(id))
+    }
+
+    /// Return the ids of all curves in the registry, sorted.
+    pub fn ids(&self) -> Result<Vec<String>, PyErr> {
+        let mut ids: Vec<String> = self
+            .curves
+            .read()
+            .map_err(lock_error)?
+            .keys()
+            .cloned()
+            .collect();
+        ids.sort();
+        Ok(ids)
+    }
+
+    /// Return the number of curves in the registry.
+    pub fn len(&self) -> Result<usize, PyErr> {
+        Ok(self.curves.read().map_err(lock_error)?.len())
+    }
+
+    /// Return whether the registry contains no curves.
+    pub fn is_empty(&self) -> Result<bool, PyErr> {
+        Ok(self.len()? == 0)
+    }
+}
+
+impl<T: CurveInterpolation, U: DateRoll> CurveRegistry<Curves<T, U>> {
+    /// Read the curve of DFs with the given id, as it is at the time of the call.
+    ///
+    /// Returns an error if the curve with the given id is not a curve of DFs.
+    pub fn read_df<F, R>(&self, id: &str, f: F) -> Result<R, PyErr>
+    where
+        F: FnOnce(&CurveDF<T, U>) -> Result<R, PyErr>,
+    {
+        self.read(id, |curve| match curve {
+            Curves::DF(c) => f(c),
+            _ => Err(PyTypeError::new_err(format!(
+                "Curve id '{}' in the registry is not a curve of DFs.",
+                id
+            ))),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, Convention, Modifier, NamedCal};
+    use crate::curves::{CurveDF, FlatCurve, LogLinearInterpolator, Nodes};
+    use crate::dual::{ADOrder, Number};
+    use indexmap::IndexMap;
+    use std::thread;
+
+    fn curve_fixture(id: &str) -> CurveDF<LogLinearInterpolator, NamedCal> {
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2000, 1, 1), 1.0_f64),
+            (ndt(2001, 1, 1), 0.99_f64),
+        ]));
+        CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            id,
+            Convention::Act360,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_registry_shared_update() {
+        let registry = CurveRegistry::new();
+        let handle = registry.insert("sofr", curve_fixture("sofr")).unwrap();
+        registry
+            .update("sofr", |c| c.set_ad_order(ADOrder::One))
+            .unwrap()
+            .unwrap();
+        assert_eq!(handle.read().unwrap().ad(), ADOrder::One);
+        assert!(registry.update("estr", |_| ()).is_err());
+    }
+
+    #[test]
+    fn test_registry_ids_and_remove() {
+        let registry = CurveRegistry::new();
+        registry.insert("b", curve_fixture("b")).unwrap();
+        registry.insert("a", curve_fixture("a")).unwrap();
+        assert_eq!(
+            registry.ids().unwrap(),
+            vec!["a".to_string(), "b".to_string()]
+        );
+        assert!(registry.remove("a").unwrap().is_some());
+        assert!(!registry.contains("a").unwrap());
+        assert_eq!(registry.len().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_registry_poisoned_curve_errors() {
+        let registry = Arc::new(CurveRegistry::new());
+        registry.insert("sofr", curve_fixture("sofr")).unwrap();
+        let r = Arc::clone(&registry);
+        let result = thread::spawn(move || r.update("sofr", |_| panic!("mutation failed"))).join();
+        assert!(result.is_err());
+        // the registry itself is intact, but the curve can no longer be read or mutated
+        assert!(registry.contains("sofr").unwrap());
+        assert!(registry.read("sofr", |c| Ok(c.ad())).is_err());
+        assert!(registry.update("sofr", |_| ()).is_err());
+    }
+
+    #[test]
+    fn test_registry_read_df() {
+        let registry: CurveRegistry<Curves<LogLinearInterpolator, NamedCal>> = CurveRegistry::new();
+        registry
+            .insert("sofr", curve_fixture("sofr").into())
+            .unwrap();
+        let flat = FlatCurve::try_new(Number::F64(3.0), Convention::Act365F, ndt(2000, 1, 1), "f");
+        registry.insert("flat", flat.unwrap().into()).unwrap();
+        let df = registry
+            .read_df("sofr", |c| Ok(c.interpolated_value(&ndt(2001, 1, 1))))
+            .unwrap();
+        assert_eq!(df, Number::F64(0.99));
+        assert!(registry.read_df("flat", |_| Ok(())).is_err());
+        assert!(registry.read_df("estr", |_| Ok(())).is_err());
+    }
+
+    #[test]
+    fn test_registry_threads() {
+        let registry = Arc::new(CurveRegistry::new());
+        registry.insert("sofr", curve_fixture("sofr")).unwrap();
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let r = Arc::clone(&registry);
+                thread::spawn(move || r.try_get("sofr").unwrap().read().unwrap().ad())
+            })
+            .collect();
+        for h in handles {
+            assert_eq!(h.join().unwrap(), ADOrder::Zero);
+        }
+    }
+}
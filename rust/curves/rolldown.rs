@@ -0,0 +1,154 @@
+use crate::calendars::DateRoll;
+use crate::curves::nodes::{node_date, NodesTimestamp};
+use crate::curves::{CurveDF, CurveInterpolation};
+use crate::dual::Number;
+use crate::scheduling::add_tenor;
+use crate::state::next_state_id;
+use chrono::NaiveDateTime;
+use indexmap::IndexMap;
+use pyo3::exceptions::PyValueError;
+use pyo3::PyErr;
+
+impl<T, U> CurveDF<T, U>
+where
+    T: CurveInterpolation + Clone,
+    U: DateRoll + Clone,
+{
+    /// The horizon date a tenor after the initial node of the curve, which must be before its
+    /// final node.
+    pub fn horizon(&self, tenor: &str) -> Result<NaiveDateTime, PyErr> {
+        let keys = self.nodes.keys();
+        let initial = node_date(keys[0]);
+        let horizon = add_tenor(&initial, tenor)?;
+        if horizon <= initial || horizon.and_utc().timestamp() >= keys[keys.len() - 1] {
+            return Err(PyValueError::new_err(
+                "The horizon must be after the initial node and before the final node of the curve.",
+            ));
+        }
+        Ok(horizon)
+    }
+
+    /// The curve as seen at `horizon` if its forward rates are realised.
+    ///
+    /// The initial node is at `horizon` with value one and every later node has the discount
+    /// factor relative to `horizon`, `v(t) / v(h)`, carrying the sensitivities of the nodes. For
+    /// log-linear interpolation the forward rates are unchanged exactly.
+    pub fn forward_curve(&self, horizon: &NaiveDateTime) -> Result<Self, PyErr> {
+        let h = horizon.and_utc().timestamp();
+        let keys = self.nodes.keys();
+        if h <= keys[0] || h >= keys[keys.len() - 1] {
+            return Err(PyValueError::new_err(
+                "The horizon must be after the initial node and before the final node of the curve.",
+            ));
+        }
+        let v_h = self.interpolated_value(horizon);
+        let values: Vec<(i64, Number)> = std::iter::once((h, Number::F64(1.0)))
+            .chain(
+                self.nodes
+                    .iter()
+                    .filter(|(d, _)| d.and_utc().timestamp() > h)
+                    .map(|(d, v)| (d.and_utc().timestamp(), v / &v_h)),
+            )
+            .collect();
+        let mut curve = self.clone();
+        curve.nodes = nodes_like(&self.nodes, values);
+        curve.state_id = next_state_id();
+        Ok(curve)
+    }
+
+    /// The curve as seen a `tenor` in the future if the market is unchanged, i.e. the curve
+    /// rolls down.
+    ///
+    /// Every node is moved forward by the time from the initial node to the horizon and keeps
+    /// its value, so that discount factors, and rates, of the same tenor are unchanged.
+    pub fn rolldown(&self, tenor: &str) -> Result<Self, PyErr> {
+        let shift = self.horizon(tenor)?.and_utc().timestamp() - self.nodes.first_key();
+        let values: Vec<(i64, Number)> = self
+            .nodes
+            .iter()
+            .map(|(d, v)| (d.and_utc().timestamp() + shift, v))
+            .collect();
+        let mut curve = self.clone();
+        curve.nodes = nodes_like(&self.nodes, values);
+        curve.state_id = next_state_id();
+        Ok(curve)
+    }
+}
+
+/// Timestamp indexed nodes of the same AD order as `nodes` from values of that order.
+fn nodes_like(nodes: &NodesTimestamp, values: Vec<(i64, Number)>) -> NodesTimestamp {
+    match nodes {
+        NodesTimestamp::F64(_) => NodesTimestamp::F64(IndexMap::from_iter(
+            values.into_iter().map(|(k, v)| (k, f64::from(v))),
+        )),
+        NodesTimestamp::Dual(_) => NodesTimestamp::Dual(IndexMap::from_iter(
+            values.into_iter().map(|(k, v)| (k, v.into())),
+        )),
+        NodesTimestamp::Dual2(_) => NodesTimestamp::Dual2(IndexMap::from_iter(
+            values.into_iter().map(|(k, v)| (k, v.into())),
+        )),
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, Convention, Modifier, NamedCal};
+    use crate::curves::{LogLinearInterpolator, Nodes};
+    use crate::dual::{ADOrder, Gradient1};
+
+    fn curve() -> CurveDF<LogLinearInterpolator, NamedCal> {
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2022, 1, 1), 1.0_f64),
+            (ndt(2023, 1, 1), 0.97_f64),
+            (ndt(2027, 1, 1), 0.85_f64),
+        ]));
+        CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            "sofr",
+            Convention::Act365F,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_forward_curve_preserves_forwards() {
+        let mut curve = curve();
+        curve.set_ad_order(ADOrder::One).unwrap();
+        let horizon = curve.horizon("6M").unwrap();
+        assert_eq!(horizon, ndt(2022, 7, 1));
+        let fwd = curve.forward_curve(&horizon).unwrap();
+        assert_eq!(fwd.nodes.dates()[0], horizon);
+        assert_eq!(fwd.ad(), ADOrder::One);
+        for d in [ndt(2022, 11, 1), ndt(2024, 6, 1), ndt(2026, 3, 1)] {
+            let expected = curve.interpolated_value(&d) / curve.interpolated_value(&horizon);
+            let result = fwd.interpolated_value(&d);
+            assert!((f64::from(&result) - f64::from(&expected)).abs() < 1e-14);
+            let vars = vec!["sofr1".to_string(), "sofr2".to_string()];
+            let (Number::Dual(r), Number::Dual(e)) = (result, expected) else {
+                panic!("expected Dual values")
+            };
+            let (r, e) = (r.gradient1(vars.clone()), e.gradient1(vars));
+            assert!((&r - &e).iter().all(|x| x.abs() < 1e-12));
+        }
+    }
+
+    #[test]
+    fn test_rolldown_keeps_tenor_values() {
+        let curve = curve();
+        let rolled = curve.rolldown("1Y").unwrap();
+        assert_eq!(
+            rolled.nodes.dates(),
+            vec![ndt(2023, 1, 1), ndt(2024, 1, 1), ndt(2028, 1, 1)]
+        );
+        assert_eq!(f64::from(rolled.interpolated_value(&ndt(2024, 1, 1))), 0.97);
+        assert!(curve.rolldown("5Y").is_err());
+        assert!(curve.rolldown("0D").is_err());
+        assert!(curve.forward_curve(&ndt(2021, 1, 1)).is_err());
+    }
+}
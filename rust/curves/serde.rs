@@ -19,8 +19,9 @@ mod tests {
     use crate::calendars::{ndt, Convention, Modifier, NamedCal};
     use crate::curves::curve_py::CurveInterpolator;
     use crate::curves::{
-        FlatBackwardInterpolator, FlatForwardInterpolator, LinearInterpolator,
-        LinearZeroRateInterpolator, LogLinearInterpolator, Nodes,
+        CubicZeroRateInterpolator, FlatBackwardInterpolator, FlatForwardInterpolator,
+        LinearInterpolator, LinearZeroRateInterpolator, LogCubicInterpolator,
+        LogLinearInterpolator, Nodes,
     };
     use indexmap::IndexMap;
 
@@ -53,6 +54,8 @@ mod tests {
         test_interpolator!(LogLinearInterpolator);
         test_interpolator!(LinearInterpolator);
         test_interpolator!(LinearZeroRateInterpolator);
+        test_interpolator!(LogCubicInterpolator);
+        test_interpolator!(CubicZeroRateInterpolator);
     }
 
     #[test]
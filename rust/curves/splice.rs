@@ -0,0 +1,198 @@
+use crate::calendars::DateRoll;
+use crate::curves::{CurveDF, CurveInterpolation};
+use crate::dual::{ADOrder, Number};
+use chrono::NaiveDateTime;
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, PyErr};
+use serde::{Deserialize, Serialize};
+
+/// The adjustment applied to the long end curve of a [CurveSplice] at the cutoff.
+#[pyclass(module = "rateslib.rs", eq, eq_int)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SpliceAdjustment {
+    /// Discount factors (DFs) after the cutoff are those of the long end curve, so the spliced
+    /// DFs may be discontinuous at the cutoff.
+    Unadjusted,
+    /// DFs after the cutoff are those of the long end curve scaled to equal the DF of the short
+    /// end curve at the cutoff, so the spliced DFs are continuous and forward rates after the
+    /// cutoff are those of the long end curve.
+    Scaled,
+}
+
+/// A composite curve using one curve up to, and including, a cutoff date and another thereafter.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CurveSplice<T: CurveInterpolation, U: DateRoll> {
+    pub(crate) short_end: CurveDF<T, U>,
+    pub(crate) long_end: CurveDF<T, U>,
+    pub(crate) cutoff: NaiveDateTime,
+    pub(crate) adjustment: SpliceAdjustment,
+}
+
+impl<T: CurveInterpolation, U: DateRoll> CurveSplice<T, U> {
+    pub fn try_new(
+        short_end: CurveDF<T, U>,
+        long_end: CurveDF<T, U>,
+        cutoff: NaiveDateTime,
+        adjustment: SpliceAdjustment,
+    ) -> Result<Self, PyErr> {
+        let ts = cutoff.and_utc().timestamp();
+        let short_keys = short_end.nodes.keys();
+        if ts < short_keys[0] || ts > short_keys[short_keys.len() - 1] {
+            return Err(PyValueError::new_err(
+                "`cutoff` must be within the nodes of the short end curve.",
+            ));
+        }
+        if ts < long_end.nodes.first_key() {
+            return Err(PyValueError::new_err(
+                "`cutoff` must not be before the initial node of the long end curve.",
+            ));
+        }
+        match (short_end.ad(), long_end.ad()) {
+            (ADOrder::One, ADOrder::Two) | (ADOrder::Two, ADOrder::One) => {
+                return Err(PyValueError::new_err(
+                    "Spliced curves must not have different, non-zero, AD orders.",
+                ))
+            }
+            _ => {}
+        }
+        Ok(CurveSplice {
+            short_end,
+            long_end,
+            cutoff,
+            adjustment,
+        })
+    }
+
+    /// Return the node dates of the short end curve up to, and including, the cutoff and of the
+    /// long end curve thereafter.
+    pub fn node_dates(&self) -> Vec<NaiveDateTime> {
+        let short = self.short_end.nodes.dates();
+        let long = self.long_end.nodes.dates();
+        short
+            .into_iter()
+            .filter(|d| *d <= self.cutoff)
+            .chain(long.into_iter().filter(|d| *d > self.cutoff))
+            .collect()
+    }
+
+    /// Return the DF of the composite curve at `date`.
+    pub fn interpolated_value(&self, date: &NaiveDateTime) -> Number {
+        if *date <= self.cutoff {
+            return self.short_end.interpolated_value(date);
+        }
+        let value = self.long_end.interpolated_value(date);
+        match self.adjustment {
+            SpliceAdjustment::Unadjusted => value,
+            SpliceAdjustment::Scaled => {
+                value * self.short_end.interpolated_value(&self.cutoff)
+                    / self.long_end.interpolated_value(&self.cutoff)
+            }
+        }
+    }
+}
+
+impl<T: CurveInterpolation, U: DateRoll> CurveDF<T, U> {
+    /// Create a composite curve using `short_end` up to, and including, `cutoff` and `long_end`
+    /// thereafter, with an `adjustment` for continuity at the cutoff.
+    pub fn splice(
+        short_end: CurveDF<T, U>,
+        long_end: CurveDF<T, U>,
+        cutoff: NaiveDateTime,
+        adjustment: SpliceAdjustment,
+    ) -> Result<CurveSplice<T, U>, PyErr> {
+        CurveSplice::try_new(short_end, long_end, cutoff, adjustment)
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, Convention, Modifier, NamedCal};
+    use crate::curves::{LogLinearInterpolator, Nodes};
+    use indexmap::IndexMap;
+
+    fn curve(
+        nodes: Vec<(NaiveDateTime, f64)>,
+        id: &str,
+    ) -> CurveDF<LogLinearInterpolator, NamedCal> {
+        CurveDF::try_new(
+            Nodes::F64(IndexMap::from_iter(nodes)),
+            LogLinearInterpolator::new(),
+            id,
+            Convention::Act365F,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn fixture(adjustment: SpliceAdjustment) -> CurveSplice<LogLinearInterpolator, NamedCal> {
+        let short = curve(
+            vec![(ndt(2024, 1, 1), 1.0), (ndt(2024, 7, 1), 0.98)],
+            "short",
+        );
+        let long = curve(
+            vec![
+                (ndt(2024, 1, 1), 1.0),
+                (ndt(2025, 1, 1), 0.97),
+                (ndt(2030, 1, 1), 0.85),
+            ],
+            "long",
+        );
+        CurveDF::splice(short, long, ndt(2024, 7, 1), adjustment).unwrap()
+    }
+
+    #[test]
+    fn test_splice_unadjusted() {
+        let spliced = fixture(SpliceAdjustment::Unadjusted);
+        assert_eq!(
+            spliced.interpolated_value(&ndt(2024, 7, 1)),
+            spliced.short_end.interpolated_value(&ndt(2024, 7, 1))
+        );
+        assert_eq!(
+            spliced.interpolated_value(&ndt(2027, 1, 1)),
+            spliced.long_end.interpolated_value(&ndt(2027, 1, 1))
+        );
+    }
+
+    #[test]
+    fn test_splice_scaled_is_continuous() {
+        let spliced = fixture(SpliceAdjustment::Scaled);
+        let at_cutoff = f64::from(spliced.interpolated_value(&ndt(2024, 7, 1)));
+        let after_cutoff = f64::from(spliced.interpolated_value(&ndt(2024, 7, 2)));
+        assert_eq!(at_cutoff, 0.98);
+        assert!((after_cutoff - at_cutoff).abs() < 1e-4);
+
+        // forward DFs after the cutoff are those of the long end curve
+        let (d1, d2) = (ndt(2026, 1, 1), ndt(2028, 1, 1));
+        let expected = f64::from(spliced.long_end.interpolated_value(&d2))
+            / f64::from(spliced.long_end.interpolated_value(&d1));
+        let result =
+            f64::from(spliced.interpolated_value(&d2)) / f64::from(spliced.interpolated_value(&d1));
+        assert!((result - expected).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_splice_node_dates() {
+        let spliced = fixture(SpliceAdjustment::Scaled);
+        assert_eq!(
+            spliced.node_dates(),
+            vec![
+                ndt(2024, 1, 1),
+                ndt(2024, 7, 1),
+                ndt(2025, 1, 1),
+                ndt(2030, 1, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_splice_cutoff_outside_short_end() {
+        let short = curve(vec![(ndt(2024, 1, 1), 1.0), (ndt(2024, 7, 1), 0.98)], "s");
+        let long = curve(vec![(ndt(2024, 1, 1), 1.0), (ndt(2030, 1, 1), 0.85)], "l");
+        let result = CurveDF::splice(short, long, ndt(2025, 1, 1), SpliceAdjustment::Scaled);
+        assert!(result.is_err());
+    }
+}
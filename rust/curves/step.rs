@@ -0,0 +1,319 @@
+use crate::calendars::{Convention, DateRoll};
+use crate::curves::nodes::node_date;
+use crate::curves::{CurveDF, CurveInterpolation};
+use crate::dual::{DualError, MathFuncs, Number};
+use chrono::NaiveDateTime;
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, PyErr};
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// A piecewise constant function of date, e.g. a fee schedule, a margin or a seasonal
+/// adjustment.
+///
+/// The function has the level of the latest of its dates on or before a date, and is zero before
+/// its first date. Levels may be dual numbers, whose sensitivities are carried through evaluation,
+/// integration and arithmetic.
+///
+/// Addition and subtraction of two step functions have the union of their dates. The operators
+/// panic if they mix [Dual](crate::dual::Dual) and [Dual2](crate::dual::Dual2) levels, as with
+/// [Number], whereas the `try_` methods return a [DualError].
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StepFunction {
+    pub(crate) dates: Vec<NaiveDateTime>,
+    pub(crate) levels: Vec<Number>,
+}
+
+impl StepFunction {
+    /// Create a step function from strictly increasing `dates` and a level from each date.
+    pub fn try_new(dates: Vec<NaiveDateTime>, levels: Vec<Number>) -> Result<Self, PyErr> {
+        if dates.is_empty() || dates.len() != levels.len() {
+            return Err(PyValueError::new_err(
+                "`dates` and `levels` must be non-empty and of the same length.",
+            ));
+        }
+        if dates.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(PyValueError::new_err(
+                "`dates` must be strictly increasing.",
+            ));
+        }
+        let has_dual = levels.iter().any(|l| matches!(l, Number::Dual(_)));
+        let has_dual2 = levels.iter().any(|l| matches!(l, Number::Dual2(_)));
+        if has_dual && has_dual2 {
+            return Err(PyValueError::new_err(
+                "`levels` cannot mix `Dual` and `Dual2` values.",
+            ));
+        }
+        Ok(StepFunction { dates, levels })
+    }
+
+    /// Return the level at `date`.
+    pub fn value(&self, date: &NaiveDateTime) -> Number {
+        match self.dates.partition_point(|d| d <= date) {
+            0 => Number::F64(0.0),
+            i => self.levels[i - 1].clone(),
+        }
+    }
+
+    /// Return the integral of the function from `start` to `end`, with time measured in day
+    /// count fractions under `convention`.
+    ///
+    /// The `convention` must not require additional arguments to measure day count fractions.
+    pub fn integral(
+        &self,
+        start: &NaiveDateTime,
+        end: &NaiveDateTime,
+        convention: Convention,
+    ) -> Result<Number, PyErr> {
+        if end < start {
+            return Err(PyValueError::new_err("`end` must not be before `start`."));
+        }
+        // the pieces of [start, end] on which the function is constant
+        let bounds: Vec<NaiveDateTime> = std::iter::once(*start)
+            .chain(
+                self.dates
+                    .iter()
+                    .filter(|d| *d > start && *d < end)
+                    .cloned(),
+            )
+            .chain(std::iter::once(*end))
+            .collect();
+        let mut total = Number::F64(0.0);
+        for w in bounds.windows(2) {
+            let dcf = convention.dcf(&w[0], &w[1], None, None, None, None, None)?;
+            total = total + self.value(&w[0]) * dcf;
+        }
+        Ok(total)
+    }
+
+    /// Combine the levels of two step functions over the union of their dates.
+    fn try_combine<F>(&self, other: &StepFunction, f: F) -> Result<Self, DualError>
+    where
+        F: Fn(&Number, &Number) -> Result<Number, DualError>,
+    {
+        let mut dates: Vec<NaiveDateTime> =
+            self.dates.iter().chain(&other.dates).cloned().collect();
+        dates.sort();
+        dates.dedup();
+        let levels = dates
+            .iter()
+            .map(|d| f(&self.value(d), &other.value(d)))
+            .collect::<Result<Vec<Number>, DualError>>()?;
+        Ok(StepFunction { dates, levels })
+    }
+
+    /// Return the sum of two step functions, or an error if their levels mix dual types.
+    pub fn try_add(&self, other: &StepFunction) -> Result<Self, DualError> {
+        self.try_combine(other, Number::try_add)
+    }
+
+    /// Return the difference of two step functions, or an error if their levels mix dual types.
+    pub fn try_sub(&self, other: &StepFunction) -> Result<Self, DualError> {
+        self.try_combine(other, Number::try_sub)
+    }
+
+    /// Return the step function scaled by a [Number], or an error if it mixes dual types with
+    /// the levels.
+    pub fn try_mul(&self, other: &Number) -> Result<Self, DualError> {
+        let levels = self
+            .levels
+            .iter()
+            .map(|l| l.try_mul(other))
+            .collect::<Result<Vec<Number>, DualError>>()?;
+        Ok(StepFunction {
+            dates: self.dates.clone(),
+            levels,
+        })
+    }
+
+    /// Return whether two step functions are equal, or an error if their levels mix dual types.
+    pub fn try_eq(&self, other: &StepFunction) -> Result<bool, DualError> {
+        if self.dates != other.dates {
+            return Ok(false);
+        }
+        for (a, b) in self.levels.iter().zip(other.levels.iter()) {
+            if !a.try_eq(b)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn map<F: Fn(&Number) -> Number>(&self, f: F) -> Self {
+        StepFunction {
+            dates: self.dates.clone(),
+            levels: self.levels.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Add<&StepFunction> for &StepFunction {
+    type Output = StepFunction;
+    fn add(self, other: &StepFunction) -> StepFunction {
+        self.try_add(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl Sub<&StepFunction> for &StepFunction {
+    type Output = StepFunction;
+    fn sub(self, other: &StepFunction) -> StepFunction {
+        self.try_sub(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl Mul<&Number> for &StepFunction {
+    type Output = StepFunction;
+    fn mul(self, other: &Number) -> StepFunction {
+        self.try_mul(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl Mul<f64> for &StepFunction {
+    type Output = StepFunction;
+    fn mul(self, other: f64) -> StepFunction {
+        self.map(|l| l * other)
+    }
+}
+
+impl Neg for &StepFunction {
+    type Output = StepFunction;
+    fn neg(self) -> StepFunction {
+        self.map(|l| -l)
+    }
+}
+
+impl<T: CurveInterpolation, U: DateRoll> CurveDF<T, U> {
+    /// Return the DF at `date` of the curve with an additional continuously compounded `spread`,
+    /// in percent, from its initial node.
+    ///
+    /// The DF is `v(t) * exp(-S(t) / 100)` where `S(t)` is the integral of the spread from the
+    /// initial node to `date` under the convention of the curve.
+    pub fn spread_value(
+        &self,
+        spread: &StepFunction,
+        date: &NaiveDateTime,
+    ) -> Result<Number, PyErr> {
+        let initial = node_date(self.nodes.first_key());
+        let integral = spread.integral(&initial, date, self.convention)?;
+        Ok(self
+            .interpolated_value(date)
+            .try_mul(&(integral * -0.01).exp())?)
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, Modifier, NamedCal};
+    use crate::curves::{LogLinearInterpolator, Nodes};
+    use crate::dual::{Dual, Dual2, Gradient1};
+    use indexmap::IndexMap;
+
+    fn step() -> StepFunction {
+        StepFunction::try_new(
+            vec![ndt(2024, 1, 1), ndt(2024, 7, 1)],
+            vec![Number::F64(1.0), Number::F64(3.0)],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_value_and_integral() {
+        let s = step();
+        assert_eq!(f64::from(s.value(&ndt(2023, 12, 31))), 0.0);
+        assert_eq!(f64::from(s.value(&ndt(2024, 1, 1))), 1.0);
+        assert_eq!(f64::from(s.value(&ndt(2024, 7, 1))), 3.0);
+        // 31 days at zero, 182 days at 1 and 184 days at 3
+        let result = s
+            .integral(&ndt(2023, 12, 1), &ndt(2025, 1, 1), Convention::Act360)
+            .unwrap();
+        assert!((f64::from(result) - (182.0 + 3.0 * 184.0) / 360.0).abs() < 1e-14);
+        assert!(s
+            .integral(&ndt(2024, 2, 1), &ndt(2024, 1, 1), Convention::Act360)
+            .is_err());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let other = StepFunction::try_new(
+            vec![ndt(2024, 4, 1), ndt(2024, 7, 1)],
+            vec![Number::F64(10.0), Number::F64(0.0)],
+        )
+        .unwrap();
+        let sum = &step() + &other;
+        assert_eq!(
+            sum.dates,
+            vec![ndt(2024, 1, 1), ndt(2024, 4, 1), ndt(2024, 7, 1)]
+        );
+        assert_eq!(f64::from(sum.value(&ndt(2024, 5, 1))), 11.0);
+        assert_eq!(f64::from(sum.value(&ndt(2024, 8, 1))), 3.0);
+        let diff = &(&step() * 2.0) - &step();
+        assert_eq!(diff, step());
+        assert_eq!(f64::from((-&step()).value(&ndt(2024, 8, 1))), -3.0);
+    }
+
+    #[test]
+    fn test_mixed_dual_levels_error() {
+        let dual = &step() * &Number::Dual(Dual::new(1.0, vec!["x".to_string()]));
+        let dual2 = &step() * &Number::Dual2(Dual2::new(1.0, vec!["y".to_string()]));
+        assert!(matches!(
+            dual.try_add(&dual2),
+            Err(DualError::MixedTypes { op: "+", .. })
+        ));
+        assert!(dual.try_sub(&dual2).is_err());
+        assert!(dual.try_eq(&dual2).is_err());
+        assert!(dual
+            .try_mul(&Number::Dual2(Dual2::new(2.0, vec![])))
+            .is_err());
+        assert_eq!(dual.try_add(&step()).unwrap(), &dual + &step());
+        assert!(dual.try_eq(&dual).unwrap());
+    }
+
+    #[test]
+    fn test_spread_value_sensitivity() {
+        let curve = CurveDF::try_new(
+            Nodes::F64(IndexMap::from_iter(vec![
+                (ndt(2024, 1, 1), 1.0_f64),
+                (ndt(2026, 1, 1), 0.95_f64),
+            ])),
+            LogLinearInterpolator::new(),
+            "curve",
+            Convention::Act365F,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap();
+        let margin = Dual::new(0.5, vec!["m".to_string()]);
+        let spread =
+            StepFunction::try_new(vec![ndt(2024, 1, 1)], vec![Number::Dual(margin)]).unwrap();
+        let date = ndt(2025, 1, 1);
+        let Number::Dual(result) = curve.spread_value(&spread, &date).unwrap() else {
+            panic!("expected a Dual")
+        };
+        let dcf = 366.0 / 365.0;
+        let expected = f64::from(curve.interpolated_value(&date)) * (-0.005 * dcf).exp();
+        assert!((result.real() - expected).abs() < 1e-14);
+        assert!((result.gradient1(vec!["m".to_string()])[0] + 0.01 * dcf * expected).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_invalid_step_functions() {
+        assert!(StepFunction::try_new(vec![], vec![]).is_err());
+        assert!(StepFunction::try_new(
+            vec![ndt(2024, 7, 1), ndt(2024, 1, 1)],
+            vec![Number::F64(1.0), Number::F64(2.0)]
+        )
+        .is_err());
+        assert!(StepFunction::try_new(
+            vec![ndt(2024, 1, 1), ndt(2024, 7, 1)],
+            vec![
+                Number::Dual(Dual::new(1.0, vec!["x".to_string()])),
+                Number::Dual2(Dual2::new(1.0, vec!["y".to_string()]))
+            ]
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,97 @@
+//! Conversion of Python date representations for use by pyo3 bindings.
+//!
+//! Arguments of pyo3 entry points which are dates are converted with these functions, using
+//! `#[pyo3(from_py_with = "...")]`, so that every binding accepts the same inputs: an ISO string,
+//! a Python `date` or `datetime` and a numpy `datetime64`, as well as sequences and numpy arrays
+//! of these for lists of dates.
+
+use crate::dates::{epoch_days, from_epoch_seconds, midnight, parse_iso};
+use chrono::{NaiveDate, NaiveDateTime};
+use numpy::datetime::{units::Days, Datetime};
+use numpy::{PyArray1, ToPyArray};
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+
+/// Whether an object is a numpy `datetime64` scalar or array.
+fn is_datetime64(obj: &Bound<'_, PyAny>) -> PyResult<bool> {
+    match obj.getattr("dtype") {
+        Ok(dtype) => Ok(dtype.getattr("kind")?.extract::<String>()? == "M"),
+        Err(_) => Ok(false),
+    }
+}
+
+/// Convert a numpy `datetime64` scalar or array to whole seconds from the Unix epoch.
+fn datetime64_seconds<'py>(obj: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    obj.call_method1("astype", ("datetime64[s]",))?
+        .call_method1("astype", ("int64",))
+}
+
+/// Convert seconds from the Unix epoch of a numpy `datetime64`, which may be `NaT`.
+fn from_datetime64_seconds(seconds: i64) -> PyResult<NaiveDateTime> {
+    if seconds == i64::MIN {
+        return Err(PyValueError::new_err("`NaT` is not a valid date."));
+    }
+    from_epoch_seconds(seconds)
+}
+
+/// Extract a date from an ISO string, a `date`, a naive `datetime` or a numpy `datetime64`.
+///
+/// A `date` is normalised to midnight. A timezone aware `datetime` is rejected.
+pub(crate) fn extract_date(obj: &Bound<'_, PyAny>) -> PyResult<NaiveDateTime> {
+    if let Ok(s) = obj.downcast::<PyString>() {
+        parse_iso(&s.to_cow()?)
+    } else if obj.hasattr("hour")? {
+        // a `datetime`, which must be checked before `date` of which it is a subclass
+        obj.extract::<NaiveDateTime>()
+    } else if let Ok(date) = obj.extract::<NaiveDate>() {
+        Ok(midnight(&date))
+    } else if is_datetime64(obj)? {
+        from_datetime64_seconds(datetime64_seconds(obj)?.extract::<i64>()?)
+    } else {
+        Err(PyTypeError::new_err(format!(
+            "'{}' cannot be converted to a date.",
+            obj.get_type().name()?
+        )))
+    }
+}
+
+/// Extract an optional date, see [extract_date], where `None` is no date.
+pub(crate) fn extract_optional_date(obj: &Bound<'_, PyAny>) -> PyResult<Option<NaiveDateTime>> {
+    if obj.is_none() {
+        Ok(None)
+    } else {
+        extract_date(obj).map(Some)
+    }
+}
+
+/// Extract dates from a sequence of objects each converted by [extract_date], or from a numpy
+/// `datetime64` array.
+pub(crate) fn extract_dates(obj: &Bound<'_, PyAny>) -> PyResult<Vec<NaiveDateTime>> {
+    if obj.is_instance_of::<PyString>() {
+        return Err(PyTypeError::new_err(
+            "A sequence of dates is required, not a single string.",
+        ));
+    }
+    if is_datetime64(obj)? {
+        return datetime64_seconds(obj)?
+            .call_method0("tolist")?
+            .extract::<Vec<i64>>()?
+            .into_iter()
+            .map(from_datetime64_seconds)
+            .collect();
+    }
+    obj.iter()?.map(|item| extract_date(&item?)).collect()
+}
+
+/// Convert dates to a numpy datetime64[D] array, discarding any time of day.
+pub(crate) fn datetime64_array<'py>(
+    py: Python<'py>,
+    dates: &[NaiveDateTime],
+) -> Bound<'py, PyArray1<Datetime<Days>>> {
+    let days: Vec<Datetime<Days>> = dates
+        .iter()
+        .map(|d| Datetime::from(epoch_days(d)))
+        .collect();
+    days.to_pyarray_bound(py)
+}
@@ -0,0 +1,125 @@
+//! Conversion of date representations into [NaiveDateTime].
+//!
+//! Every date of the library is a [NaiveDateTime]. Dates given without a time, such as an ISO
+//! date string, a Python `date` or a numpy `datetime64[D]`, are normalised to midnight. Dates
+//! given with a time of day keep it, and times are represented to the second.
+//!
+//! Where dates are stored as numbers, e.g. the nodes of a curve, they are whole seconds from the
+//! Unix epoch as an [i64], see [epoch_seconds]. These are negative before 1970 and represent
+//! every [NaiveDateTime], so curves are not limited to the range of a numpy `datetime64[ns]`,
+//! 1678 to 2262. Inputs which are not in the range of [NaiveDateTime], roughly 262,000 years
+//! either side of year zero, are rejected with an error. Dates are returned to Python as
+//! `datetime`, which is limited to the years 1 to 9999, or as a `datetime64[D]` array.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use pyo3::exceptions::PyValueError;
+use pyo3::PyErr;
+
+pub(crate) mod dates_py;
+
+/// The formats accepted by [parse_iso] for a date with a time of day.
+const DATETIME_FORMATS: [&str; 2] = ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"];
+
+/// Return a date at midnight.
+pub fn midnight(date: &NaiveDate) -> NaiveDateTime {
+    date.and_hms_opt(0, 0, 0).unwrap()
+}
+
+/// Parse an ISO 8601 date, `"YYYY-MM-DD"`, or date and time, `"YYYY-MM-DDTHH:MM:SS"`, where
+/// the separator may also be a space and the seconds may have a fraction.
+///
+/// # Errors
+///
+/// If the string is not in one of the accepted formats or is not a valid date.
+pub fn parse_iso(s: &str) -> Result<NaiveDateTime, PyErr> {
+    let s = s.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return Ok(midnight(&date));
+    }
+    DATETIME_FORMATS
+        .iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(s, f).ok())
+        .ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "'{}' is not an ISO date of the form 'YYYY-MM-DD' or 'YYYY-MM-DDTHH:MM:SS'.",
+                s
+            ))
+        })
+}
+
+/// Return the date of a number of seconds from the Unix epoch.
+///
+/// # Errors
+///
+/// If the date is out of the range of [NaiveDateTime].
+pub fn from_epoch_seconds(seconds: i64) -> Result<NaiveDateTime, PyErr> {
+    DateTime::from_timestamp(seconds, 0)
+        .map(|d| d.naive_utc())
+        .ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "{} seconds from the Unix epoch is out of the range of dates.",
+                seconds
+            ))
+        })
+}
+
+/// Return the number of whole seconds of a date from the Unix epoch, which is negative before
+/// 1970.
+///
+/// Any fraction of a second is discarded, rounding towards the earlier second, so that
+/// [from_epoch_seconds] is its inverse for dates without a fraction of a second.
+pub fn epoch_seconds(date: &NaiveDateTime) -> i64 {
+    date.and_utc().timestamp()
+}
+
+/// Return the number of whole days of a date from the Unix epoch, discarding the time of day.
+pub fn epoch_days(date: &NaiveDateTime) -> i64 {
+    date.and_utc().timestamp().div_euclid(86400)
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::ndt;
+    use chrono::Timelike;
+
+    #[test]
+    fn test_parse_iso() {
+        assert_eq!(parse_iso("2024-02-29").unwrap(), ndt(2024, 2, 29));
+        assert_eq!(
+            parse_iso("2024-02-29T10:30:00").unwrap(),
+            ndt(2024, 2, 29)
+                .with_hour(10)
+                .unwrap()
+                .with_minute(30)
+                .unwrap()
+        );
+        assert_eq!(
+            parse_iso(" 2024-02-29 10:30:00.0 ").unwrap(),
+            parse_iso("2024-02-29T10:30:00").unwrap()
+        );
+        assert!(parse_iso("2023-02-29").is_err());
+        assert!(parse_iso("29/02/2024").is_err());
+    }
+
+    #[test]
+    fn test_epoch_round_trip() {
+        let date = ndt(1969, 12, 31);
+        assert_eq!(epoch_days(&date), -1);
+        assert_eq!(from_epoch_seconds(-86400).unwrap(), date);
+        assert_eq!(epoch_days(&(date + chrono::Duration::hours(23))), -1);
+        for date in [
+            ndt(1066, 10, 14),
+            ndt(2262, 4, 12),
+            ndt(9999, 12, 31),
+            ndt(-500, 1, 1),
+        ] {
+            assert_eq!(from_epoch_seconds(epoch_seconds(&date)).unwrap(), date);
+        }
+        let before = date - chrono::Duration::milliseconds(500);
+        assert_eq!(epoch_seconds(&before), -86401);
+        assert!(from_epoch_seconds(i64::MAX).is_err());
+        assert!(from_epoch_seconds(i64::MIN).is_err());
+    }
+}
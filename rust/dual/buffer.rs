@@ -0,0 +1,145 @@
+//! Contiguous storage of a vector of first order dual numbers.
+
+use crate::dual::dual::{Dual, Vars};
+use crate::dual::enums::Number;
+use indexmap::set::IndexSet;
+use ndarray::{Array1, Array2};
+use pyo3::exceptions::PyValueError;
+use pyo3::PyErr;
+use std::sync::Arc;
+
+/// A vector of [Dual] stored as an array of real values and a Jacobian with respect to a single
+/// shared set of `vars`.
+///
+/// Row `i` of the `jacobian` contains the gradient of value `i` with respect to each of the
+/// `vars`, in order. This allows a vector of dual numbers to cross the Python boundary as two
+/// arrays, rather than one object per element.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DualBuffer {
+    pub(crate) values: Array1<f64>,
+    pub(crate) vars: Arc<IndexSet<String>>,
+    pub(crate) jacobian: Array2<f64>,
+}
+
+impl DualBuffer {
+    /// Create a buffer from values, `vars` and a Jacobian of shape (values, vars).
+    pub fn try_new(
+        values: Array1<f64>,
+        vars: Vec<String>,
+        jacobian: Array2<f64>,
+    ) -> Result<Self, PyErr> {
+        let vars = IndexSet::<String>::from_iter(vars);
+        if jacobian.dim() != (values.len(), vars.len()) {
+            return Err(PyValueError::new_err(
+                "`jacobian` must have a row for each value and a column for each unique var.",
+            ));
+        }
+        Ok(DualBuffer {
+            values,
+            vars: Arc::new(vars),
+            jacobian,
+        })
+    }
+
+    /// Create a buffer from numbers, whose `vars` are the union of the `vars` of each, in the
+    /// order first seen.
+    ///
+    /// An [f64] has a gradient of zero. [Dual2] numbers are not supported.
+    pub fn try_from_numbers(numbers: &[Number]) -> Result<Self, PyErr> {
+        let mut vars: IndexSet<String> = IndexSet::new();
+        for n in numbers {
+            match n {
+                Number::F64(_) => {}
+                Number::Dual(d) => vars.extend(d.vars().iter().cloned()),
+                Number::Dual2(_) => {
+                    return Err(PyValueError::new_err(
+                        "A `DualBuffer` stores first order gradients and cannot contain `Dual2`.",
+                    ))
+                }
+            }
+        }
+        let mut jacobian = Array2::<f64>::zeros((numbers.len(), vars.len()));
+        for (i, n) in numbers.iter().enumerate() {
+            if let Number::Dual(d) = n {
+                for (var, g) in d.vars().iter().zip(d.dual.iter()) {
+                    jacobian[[i, vars.get_index_of(var).unwrap()]] = *g;
+                }
+            }
+        }
+        Ok(DualBuffer {
+            values: numbers.iter().map(f64::from).collect(),
+            vars: Arc::new(vars),
+            jacobian,
+        })
+    }
+
+    /// Return the dual numbers of the buffer, which share the Arc pointer of its `vars`.
+    pub fn to_duals(&self) -> Vec<Dual> {
+        self.values
+            .iter()
+            .zip(self.jacobian.rows())
+            .map(|(v, row)| Dual {
+                real: *v,
+                vars: Arc::clone(&self.vars),
+                dual: row.to_owned(),
+            })
+            .collect()
+    }
+
+    /// The real values of the dual numbers.
+    pub fn values(&self) -> &Array1<f64> {
+        &self.values
+    }
+
+    /// The shared variables of the dual numbers.
+    pub fn vars(&self) -> &Arc<IndexSet<String>> {
+        &self.vars
+    }
+
+    /// The gradients of the dual numbers, by row, with respect to the `vars`.
+    pub fn jacobian(&self) -> &Array2<f64> {
+        &self.jacobian
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dual::Dual2;
+    use ndarray::arr2;
+
+    #[test]
+    fn test_round_trip() {
+        let x = Dual::try_new(1.0, vec!["x".to_string()], vec![2.0]).unwrap();
+        let y = Dual::try_new(3.0, vec!["y".to_string(), "x".to_string()], vec![4.0, 5.0]).unwrap();
+        let numbers = vec![
+            Number::Dual(x.clone()),
+            Number::F64(2.5),
+            Number::Dual(y.clone()),
+        ];
+        let buffer = DualBuffer::try_from_numbers(&numbers).unwrap();
+        assert_eq!(buffer.values(), &Array1::from_vec(vec![1.0, 2.5, 3.0]));
+        assert_eq!(
+            buffer.jacobian(),
+            &arr2(&[[2.0, 0.0], [0.0, 0.0], [5.0, 4.0]])
+        );
+        let duals = buffer.to_duals();
+        assert_eq!(duals[0], x);
+        assert_eq!(duals[1], Dual::new(2.5, vec![]));
+        assert_eq!(duals[2], y);
+        assert!(Arc::ptr_eq(duals[0].vars(), duals[2].vars()));
+    }
+
+    #[test]
+    fn test_invalid_buffers() {
+        let numbers = vec![Number::Dual2(Dual2::new(1.0, vec!["x".to_string()]))];
+        assert!(DualBuffer::try_from_numbers(&numbers).is_err());
+        let result = DualBuffer::try_new(
+            Array1::from_vec(vec![1.0, 2.0]),
+            vec!["x".to_string()],
+            Array2::zeros((2, 2)),
+        );
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,69 @@
+//! Wrapper module to export the bulk exchange of dual numbers to Python using pyo3 bindings.
+
+use crate::dual::buffer::DualBuffer;
+use crate::dual::dual::Dual;
+use crate::dual::enums::Number;
+use numpy::{PyArray1, PyArray2, PyArrayMethods, ToPyArray};
+use pyo3::prelude::*;
+
+/// The real values, variables and Jacobian of a [DualBuffer] as Python objects.
+type BufferPy<'py> = (
+    Bound<'py, PyArray1<f64>>,
+    Vec<String>,
+    Bound<'py, PyArray2<f64>>,
+);
+
+/// Convert a sequence of dual numbers to an array of real values and a Jacobian.
+///
+/// Parameters
+/// ----------
+/// x: list[float | Dual] or ndarray[object]
+///     The dual numbers. Floats have a gradient of zero.
+///
+/// Returns
+/// -------
+/// tuple[ndarray[float], list[str], ndarray[float]]
+///
+/// Notes
+/// -----
+/// The variables are the union of the variables of each dual number, in the order first seen.
+/// The Jacobian has a row for each dual number and a column for each variable.
+#[pyfunction]
+#[pyo3(name = "_duals_to_buffer")]
+pub fn duals_to_buffer_py<'py>(py: Python<'py>, x: Vec<Number>) -> PyResult<BufferPy<'py>> {
+    let buffer = DualBuffer::try_from_numbers(&x)?;
+    Ok((
+        buffer.values().to_pyarray_bound(py),
+        buffer.vars().iter().cloned().collect(),
+        buffer.jacobian().to_pyarray_bound(py),
+    ))
+}
+
+/// Convert an array of real values and a Jacobian to a list of dual numbers.
+///
+/// Parameters
+/// ----------
+/// values: ndarray[float] of length m
+///     The real values of the dual numbers.
+/// vars: list[str] of length n
+///     The variables of the dual numbers.
+/// jacobian: ndarray[float] of shape (m, n)
+///     The gradient of each dual number, by row, with respect to the variables.
+///
+/// Returns
+/// -------
+/// list[Dual]
+///
+/// Notes
+/// -----
+/// The dual numbers share their variables, so operations between them are efficient.
+#[pyfunction]
+#[pyo3(name = "_buffer_to_duals")]
+pub fn buffer_to_duals_py(
+    values: &Bound<'_, PyArray1<f64>>,
+    vars: Vec<String>,
+    jacobian: &Bound<'_, PyArray2<f64>>,
+) -> PyResult<Vec<Dual>> {
+    let buffer = DualBuffer::try_new(values.to_owned_array(), vars, jacobian.to_owned_array())?;
+    Ok(buffer.to_duals())
+}
@@ -1,17 +1,19 @@
 pub use crate::dual::dual_ops::convert::{set_order, set_order_clone};
 pub use crate::dual::dual_ops::math_funcs::MathFuncs;
 pub use crate::dual::dual_ops::numeric_ops::NumberOps;
+use crate::dual::errors::DualError;
 use indexmap::set::IndexSet;
 use ndarray::{Array, Array1, Array2, Axis};
-use pyo3::exceptions::PyValueError;
-use pyo3::{pyclass, PyErr};
+use pyo3::pyclass;
 use serde::{Deserialize, Serialize};
 use std::cmp::PartialEq;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// A dual number data type supporting first order derivatives.
 #[pyclass(module = "rateslib.rs")]
 #[derive(Clone, Default, Debug, Deserialize, Serialize)]
+#[serde(into = "DualDataModel")]
 pub struct Dual {
     pub(crate) real: f64,
     pub(crate) vars: Arc<IndexSet<String>>,
@@ -21,6 +23,7 @@ pub struct Dual {
 /// A dual number data type supporting second order derivatives.
 #[pyclass(module = "rateslib.rs")]
 #[derive(Clone, Default, Debug, Serialize, Deserialize)]
+#[serde(into = "Dual2DataModel")]
 pub struct Dual2 {
     pub(crate) real: f64,
     pub(crate) vars: Arc<IndexSet<String>>,
@@ -28,6 +31,53 @@ pub struct Dual2 {
     pub(crate) dual2: Array2<f64>,
 }
 
+/// The serialized form of a [Dual], with its `vars` sorted so that equal dual numbers serialize
+/// identically.
+#[derive(Serialize)]
+struct DualDataModel {
+    real: f64,
+    vars: Arc<IndexSet<String>>,
+    dual: Array1<f64>,
+}
+
+impl std::convert::From<Dual> for DualDataModel {
+    fn from(d: Dual) -> Self {
+        let d = d.to_new_vars(&sorted_vars(&d.vars), None);
+        Self {
+            real: d.real,
+            vars: d.vars,
+            dual: d.dual,
+        }
+    }
+}
+
+/// The serialized form of a [Dual2], with its `vars` sorted.
+#[derive(Serialize)]
+struct Dual2DataModel {
+    real: f64,
+    vars: Arc<IndexSet<String>>,
+    dual: Array1<f64>,
+    dual2: Array2<f64>,
+}
+
+impl std::convert::From<Dual2> for Dual2DataModel {
+    fn from(d: Dual2) -> Self {
+        let d = d.to_new_vars(&sorted_vars(&d.vars), None);
+        Self {
+            real: d.real,
+            vars: d.vars,
+            dual: d.dual,
+            dual2: d.dual2,
+        }
+    }
+}
+
+fn sorted_vars(vars: &Arc<IndexSet<String>>) -> Arc<IndexSet<String>> {
+    let mut sorted = vars.as_ref().clone();
+    sorted.sort();
+    Arc::new(sorted)
+}
+
 /// The state of the `vars` measured between two dual number type structs; a LHS relative to a RHS.
 #[derive(Clone, Debug, PartialEq)]
 pub enum VarsRelationship {
@@ -380,6 +430,20 @@ impl Gradient2 for Dual2 {
     }
 }
 
+/// Map each of `vars` to its new label and return the new unique labels with the index of each
+/// original variable within them.
+fn renamed_vars(
+    vars: &IndexSet<String>,
+    mapping: &HashMap<String, String>,
+) -> (IndexSet<String>, Vec<usize>) {
+    let mut new_vars: IndexSet<String> = IndexSet::with_capacity(vars.len());
+    let indices = vars
+        .iter()
+        .map(|v| new_vars.insert_full(mapping.get(v).unwrap_or(v).clone()).0)
+        .collect();
+    (new_vars, indices)
+}
+
 impl Dual {
     /// Constructs a new `Dual`.
     ///
@@ -422,7 +486,7 @@ impl Dual {
     /// let x = Dual::try_new(2.5, vec!["x".to_string()], vec![4.2]).unwrap();
     /// // x: <Dual: 2.5, (x), [4.2]>
     /// ```
-    pub fn try_new(real: f64, vars: Vec<String>, dual: Vec<f64>) -> Result<Self, PyErr> {
+    pub fn try_new(real: f64, vars: Vec<String>, dual: Vec<f64>) -> Result<Self, DualError> {
         let unique_vars_ = Arc::new(IndexSet::from_iter(vars));
         let dual_ = if dual.is_empty() {
             Array1::ones(unique_vars_.len())
@@ -430,9 +494,10 @@ impl Dual {
             Array1::from_vec(dual)
         };
         if unique_vars_.len() != dual_.len() {
-            Err(PyValueError::new_err(
-                "`vars` and `dual` must have the same length.",
-            ))
+            Err(DualError::DualLength {
+                vars: unique_vars_.len(),
+                dual: dual_.len(),
+            })
         } else {
             Ok(Self {
                 real,
@@ -490,7 +555,7 @@ impl Dual {
         real: f64,
         vars: Vec<String>,
         dual: Vec<f64>,
-    ) -> Result<Self, PyErr> {
+    ) -> Result<Self, DualError> {
         let new = Self::try_new(real, vars, dual)?;
         Ok(new.to_new_vars(other.vars(), None))
     }
@@ -510,6 +575,33 @@ impl Dual {
     pub fn real(&self) -> f64 {
         self.real
     }
+
+    /// Return a new `Dual` with variables relabelled according to `mapping`.
+    ///
+    /// Variables absent from `mapping` keep their label. If several variables are mapped to the
+    /// same label their gradients are summed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use rateslib::dual::Dual;
+    /// # use std::collections::HashMap;
+    /// let x = Dual::new(2.5, vec!["x".to_string()]);
+    /// let y = x.rename_vars(&HashMap::from([("x".to_string(), "y".to_string())]));
+    /// // y: <Dual: 2.5, (y), [1.0]>
+    /// ```
+    pub fn rename_vars(&self, mapping: &HashMap<String, String>) -> Self {
+        let (vars, indices) = renamed_vars(&self.vars, mapping);
+        let mut dual = Array1::zeros(vars.len());
+        for (i, j) in indices.iter().enumerate() {
+            dual[*j] += self.dual[i];
+        }
+        Dual {
+            real: self.real,
+            vars: Arc::new(vars),
+            dual,
+        }
+    }
 }
 
 impl Dual2 {
@@ -562,7 +654,7 @@ impl Dual2 {
         vars: Vec<String>,
         dual: Vec<f64>,
         dual2: Vec<f64>,
-    ) -> Result<Self, PyErr> {
+    ) -> Result<Self, DualError> {
         let unique_vars_ = Arc::new(IndexSet::from_iter(vars));
         let dual_ = if dual.is_empty() {
             Array1::ones(unique_vars_.len())
@@ -570,18 +662,20 @@ impl Dual2 {
             Array1::from_vec(dual)
         };
         if unique_vars_.len() != dual_.len() {
-            return Err(PyValueError::new_err(
-                "`vars` and `dual` must have the same length.",
-            ));
+            return Err(DualError::DualLength {
+                vars: unique_vars_.len(),
+                dual: dual_.len(),
+            });
         }
 
         let dual2_ = if dual2.is_empty() {
             Array2::zeros((unique_vars_.len(), unique_vars_.len()))
         } else {
             if dual2.len() != (unique_vars_.len() * unique_vars_.len()) {
-                return Err(PyValueError::new_err(
-                    "`vars` and `dual2` must have compatible lengths.",
-                ));
+                return Err(DualError::Dual2Length {
+                    vars: unique_vars_.len(),
+                    dual2: dual2.len(),
+                });
             }
             Array::from_vec(dual2)
                 .into_shape_with_order((unique_vars_.len(), unique_vars_.len()))
@@ -643,7 +737,7 @@ impl Dual2 {
         vars: Vec<String>,
         dual: Vec<f64>,
         dual2: Vec<f64>,
-    ) -> Result<Self, PyErr> {
+    ) -> Result<Self, DualError> {
         let new = Self::try_new(real, vars, dual, dual2)?;
         Ok(new.to_new_vars(other.vars(), None))
     }
@@ -671,6 +765,28 @@ impl Dual2 {
     pub fn real(&self) -> f64 {
         self.real
     }
+
+    /// Return a new `Dual2` with variables relabelled according to `mapping`.
+    ///
+    /// Variables absent from `mapping` keep their label. If several variables are mapped to the
+    /// same label their first and second order gradients are summed.
+    pub fn rename_vars(&self, mapping: &HashMap<String, String>) -> Self {
+        let (vars, indices) = renamed_vars(&self.vars, mapping);
+        let mut dual = Array1::zeros(vars.len());
+        let mut dual2 = Array2::zeros((vars.len(), vars.len()));
+        for (i, j) in indices.iter().enumerate() {
+            dual[*j] += self.dual[i];
+            for (k, l) in indices.iter().enumerate() {
+                dual2[[*j, *l]] += self.dual2[[i, k]];
+            }
+        }
+        Dual2 {
+            real: self.real,
+            vars: Arc::new(vars),
+            dual,
+            dual2,
+        }
+    }
 }
 
 // UNIT TESTS
@@ -1088,6 +1204,21 @@ mod tests {
         .is_err());
     }
 
+    #[test]
+    fn new_dual2_error_kinds() {
+        let vars = vec!["a".to_string(), "b".to_string()];
+        let result = Dual2::try_new(2.3, vars.clone(), vec![1.0], vec![]);
+        assert_eq!(
+            result.unwrap_err(),
+            DualError::DualLength { vars: 2, dual: 1 }
+        );
+        let result = Dual2::try_new(2.3, vars, vec![1.0, 2.3], vec![1.0, 2.4, 3.4]);
+        assert_eq!(
+            result.unwrap_err(),
+            DualError::Dual2Length { vars: 2, dual2: 3 }
+        );
+    }
+
     #[test]
     fn try_new_from2() {
         let x = Dual2::new(1.2, vec!["x".to_string(), "y".to_string()]);
@@ -1182,6 +1313,39 @@ mod tests {
         assert_eq!(result[1].dual2, Array2::<f64>::zeros((2, 2)));
     }
 
+    #[test]
+    fn rename_vars() {
+        let d = Dual::try_new(
+            2.0,
+            vec!["x".to_string(), "y".to_string(), "z".to_string()],
+            vec![1., 2., 3.],
+        )
+        .unwrap();
+        let mapping = HashMap::from([
+            ("x".to_string(), "a".to_string()),
+            ("z".to_string(), "a".to_string()),
+        ]);
+        let result = d.rename_vars(&mapping);
+        let expected =
+            Dual::try_new(2.0, vec!["a".to_string(), "y".to_string()], vec![4., 2.]).unwrap();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn rename_vars_dual2() {
+        let d = Dual2::try_new(
+            2.0,
+            vec!["x".to_string(), "y".to_string()],
+            vec![1., 2.],
+            vec![2., 3., 3., 5.],
+        )
+        .unwrap();
+        let mapping = HashMap::from([("y".to_string(), "x".to_string())]);
+        let result = d.rename_vars(&mapping);
+        let expected = Dual2::try_new(2.0, vec!["x".to_string()], vec![3.], vec![13.]).unwrap();
+        assert_eq!(result, expected);
+    }
+
     // #[test]
     // #[should_panic]
     // fn no_dual_cross(){
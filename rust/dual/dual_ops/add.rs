@@ -1,5 +1,6 @@
 use crate::dual::dual::{Dual, Dual2, Vars, VarsRelationship};
 use crate::dual::enums::Number;
+use crate::dual::errors::DualError;
 use auto_ops::{impl_op_ex, impl_op_ex_commutative};
 use std::sync::Arc;
 
@@ -11,6 +12,13 @@ impl_op_ex_commutative!(+ |a: &Dual2, b: &f64| -> Dual2 {
 
 // Add for Dual
 impl_op_ex!(+ |a: &Dual, b: &Dual| -> Dual {
+    // an operand without vars is a constant and needs no union of vars
+    if b.vars.is_empty() {
+        return a + b.real;
+    }
+    if a.vars.is_empty() {
+        return a.real + b;
+    }
     let state = a.vars_cmp(b.vars());
     match state {
         VarsRelationship::ArcEquivalent | VarsRelationship::ValueEquivalent => {
@@ -25,6 +33,12 @@ impl_op_ex!(+ |a: &Dual, b: &Dual| -> Dual {
 
 // Add for Dual2
 impl_op_ex!(+ |a: &Dual2, b: &Dual2| -> Dual2 {
+    if b.vars.is_empty() {
+        return a + b.real;
+    }
+    if a.vars.is_empty() {
+        return a.real + b;
+    }
     let state = a.vars_cmp(b.vars());
     match state {
         VarsRelationship::ArcEquivalent | VarsRelationship::ValueEquivalent => {
@@ -47,19 +61,27 @@ impl_op_ex!(+ |a: &Dual2, b: &Dual2| -> Dual2 {
 
 // Add for Number
 impl_op_ex!(+ |a: &Number, b: &Number| -> Number {
-    match (a,b) {
-        (Number::F64(f), Number::F64(f2)) => Number::F64(f + f2),
-        (Number::F64(f), Number::Dual(d2)) => Number::Dual(f + d2),
-        (Number::F64(f), Number::Dual2(d2)) => Number::Dual2(f + d2),
-        (Number::Dual(d), Number::F64(f2)) => Number::Dual(d + f2),
-        (Number::Dual(d), Number::Dual(d2)) => Number::Dual(d + d2),
-        (Number::Dual(_), Number::Dual2(_)) => panic!("Cannot mix dual types: Dual + Dual2"),
-        (Number::Dual2(d), Number::F64(f2)) => Number::Dual2(d + f2),
-        (Number::Dual2(_), Number::Dual(_)) => panic!("Cannot mix dual types: Dual2 + Dual"),
-        (Number::Dual2(d), Number::Dual2(d2)) => Number::Dual2(d + d2),
-    }
+    a.try_add(b).unwrap_or_else(|e| panic!("{}", e))
 });
 
+impl Number {
+    /// Add a [Number], returning an error rather than panicking if the types mix [Dual]
+    /// and [Dual2].
+    pub fn try_add(&self, other: &Number) -> Result<Number, DualError> {
+        match (self, other) {
+            (Number::F64(f), Number::F64(f2)) => Ok(Number::F64(f + f2)),
+            (Number::F64(f), Number::Dual(d2)) => Ok(Number::Dual(f + d2)),
+            (Number::F64(f), Number::Dual2(d2)) => Ok(Number::Dual2(f + d2)),
+            (Number::Dual(d), Number::F64(f2)) => Ok(Number::Dual(d + f2)),
+            (Number::Dual(d), Number::Dual(d2)) => Ok(Number::Dual(d + d2)),
+            (Number::Dual(_), Number::Dual2(_)) => Err(DualError::mixed("+", self, other)),
+            (Number::Dual2(d), Number::F64(f2)) => Ok(Number::Dual2(d + f2)),
+            (Number::Dual2(_), Number::Dual(_)) => Err(DualError::mixed("+", self, other)),
+            (Number::Dual2(d), Number::Dual2(d2)) => Ok(Number::Dual2(d + d2)),
+        }
+    }
+}
+
 // Add for Number
 impl_op_ex_commutative!(+ |a: &Number, b: &f64| -> Number {
     match a {
@@ -182,10 +204,35 @@ mod tests {
         let _ = d + d2;
     }
 
+    #[test]
+    fn test_enum_try_add() {
+        let d = Number::Dual2(Dual2::new(2.0, vec!["y".to_string()]));
+        let d2 = Number::Dual(Dual::new(3.0, vec!["x".to_string()]));
+        let expected = DualError::MixedTypes {
+            op: "+",
+            lhs: "Dual2",
+            rhs: "Dual",
+        };
+        assert_eq!(d.try_add(&d2), Err(expected));
+        assert!(d.try_add(&Number::F64(1.5)).is_ok());
+    }
+
     #[test]
     fn test_enum_f64() {
         let d = Number::Dual(Dual::new(3.0, vec!["x".to_string()]));
         let res = 2.5_f64 + d;
         assert_eq!(res, Number::Dual(Dual::new(5.5, vec!["x".to_string()])));
     }
+
+    #[test]
+    fn add_empty_vars_shares_vars() {
+        let d = Dual::try_new(1.0, vec!["x".to_string()], vec![2.0]).unwrap();
+        let c = Dual::new(3.0, vec![]);
+        let result = &c + &d;
+        assert!(Arc::ptr_eq(result.vars(), d.vars()));
+        assert_eq!(
+            result,
+            Dual::try_new(4.0, vec!["x".to_string()], vec![2.0]).unwrap()
+        );
+    }
 }
@@ -0,0 +1,41 @@
+use crate::dual::dual::{Dual, Dual2};
+use auto_ops::impl_op_ex;
+
+// Compound assignment, e.g. for accumulation in generic numeric code such as matrix products.
+impl_op_ex!(+= |a: &mut Dual, b: &Dual| { *a = &*a + b; });
+impl_op_ex!(-= |a: &mut Dual, b: &Dual| { *a = &*a - b; });
+impl_op_ex!(*= |a: &mut Dual, b: &Dual| { *a = &*a * b; });
+impl_op_ex!(/= |a: &mut Dual, b: &Dual| { *a = &*a / b; });
+
+impl_op_ex!(+= |a: &mut Dual2, b: &Dual2| { *a = &*a + b; });
+impl_op_ex!(-= |a: &mut Dual2, b: &Dual2| { *a = &*a - b; });
+impl_op_ex!(*= |a: &mut Dual2, b: &Dual2| { *a = &*a * b; });
+impl_op_ex!(/= |a: &mut Dual2, b: &Dual2| { *a = &*a / b; });
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assign_ops_match_binary_ops() {
+        let x = Dual::new(2.0, vec!["x".to_string()]);
+        let y = Dual::new(3.0, vec!["y".to_string()]);
+        let mut z = x.clone();
+        z += &y;
+        assert_eq!(z, &x + &y);
+        z *= y.clone();
+        assert_eq!(z, (&x + &y) * &y);
+        z -= &x;
+        z /= &y;
+        assert_eq!(z, ((&x + &y) * &y - &x) / &y);
+    }
+
+    #[test]
+    fn test_assign_ops_dual2() {
+        let x = Dual2::new(2.0, vec!["x".to_string()]);
+        let mut z = x.clone();
+        z *= &x;
+        assert_eq!(z, &x * &x);
+    }
+}
@@ -0,0 +1,85 @@
+use crate::dual::dual::{Dual, Vars};
+use indexmap::set::IndexSet;
+use ndarray::{Array1, Array2};
+use std::sync::Arc;
+
+/// Stack the real values and gradients of duals over the union of their variables.
+///
+/// Returns the vector of real values, the Jacobian with a row for each dual and a column for
+/// each variable, and the variables labelling the columns in order of first appearance. Each
+/// gradient is scattered directly into its row so that no intermediate duals with aligned
+/// `vars` are created. If all duals share the same `vars` Arc pointer the rows are copied
+/// without any index lookup.
+///
+/// # Examples
+///
+/// ```rust
+/// # use rateslib::dual::{combine, Dual};
+/// let x = Dual::new(1.0, vec!["x".to_string()]);
+/// let y = Dual::try_new(2.0, vec!["y".to_string(), "x".to_string()], vec![3.0, 4.0]).unwrap();
+/// let (values, jac, vars) = combine(vec![x, y]);
+/// // values: [1.0, 2.0], jac: [[1.0, 0.0], [4.0, 3.0]], vars: ["x", "y"]
+/// ```
+pub fn combine(duals: Vec<Dual>) -> (Array1<f64>, Array2<f64>, Vec<String>) {
+    let values = Array1::from_iter(duals.iter().map(|d| d.real));
+    let Some(first) = duals.first() else {
+        return (values, Array2::zeros((0, 0)), vec![]);
+    };
+
+    if duals.iter().all(|d| Arc::ptr_eq(d.vars(), first.vars())) {
+        let mut jac = Array2::zeros((duals.len(), first.vars.len()));
+        for (mut row, d) in jac.rows_mut().into_iter().zip(duals.iter()) {
+            row.assign(&d.dual);
+        }
+        return (values, jac, first.vars.iter().cloned().collect());
+    }
+
+    let mut vars: IndexSet<String> = IndexSet::new();
+    let columns: Vec<Vec<usize>> = duals
+        .iter()
+        .map(|d| {
+            d.vars
+                .iter()
+                .map(|v| match vars.get_index_of(v) {
+                    Some(j) => j,
+                    None => vars.insert_full(v.clone()).0,
+                })
+                .collect()
+        })
+        .collect();
+    let mut jac = Array2::zeros((duals.len(), vars.len()));
+    for (i, (d, cols)) in duals.iter().zip(columns.iter()).enumerate() {
+        for (k, j) in cols.iter().enumerate() {
+            jac[[i, *j]] = d.dual[k];
+        }
+    }
+    (values, jac, vars.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr2;
+
+    #[test]
+    fn test_combine() {
+        let x = Dual::new(1.0, vec!["x".to_string()]);
+        let y = Dual::try_new(2.0, vec!["y".to_string(), "x".to_string()], vec![3.0, 4.0]).unwrap();
+        let z = Dual::new(5.0, vec![]);
+        let (values, jac, vars) = combine(vec![x, y, z]);
+        assert_eq!(values, Array1::from_vec(vec![1.0, 2.0, 5.0]));
+        assert_eq!(jac, arr2(&[[1.0, 0.0], [4.0, 3.0], [0.0, 0.0]]));
+        assert_eq!(vars, vec!["x".to_string(), "y".to_string()]);
+    }
+
+    #[test]
+    fn test_combine_shared_vars() {
+        let x = Dual::try_new(1.0, vec!["a".to_string(), "b".to_string()], vec![1., 2.]).unwrap();
+        let y = Dual::clone_from(&x, 3.0, Array1::from_vec(vec![5., 6.]));
+        let (values, jac, vars) = combine(vec![x, y]);
+        assert_eq!(values, Array1::from_vec(vec![1.0, 3.0]));
+        assert_eq!(jac, arr2(&[[1.0, 2.0], [5.0, 6.0]]));
+        assert_eq!(vars, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(combine(vec![]).1.dim(), (0, 0));
+    }
+}
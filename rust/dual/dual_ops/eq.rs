@@ -1,5 +1,12 @@
+use crate::config::{is_close, tolerances};
 use crate::dual::dual::{Dual, Dual2, Vars, VarsRelationship};
 use crate::dual::enums::Number;
+use crate::dual::errors::DualError;
+
+/// Whether the elements of two arrays of equal length are equal within a tolerance.
+fn all_close<'a, I: IntoIterator<Item = &'a f64>>(a: I, b: I, tol: f64) -> bool {
+    a.into_iter().zip(b).all(|(x, y)| is_close(*x, *y, tol))
+}
 
 /// Measures value equivalence of `Dual`.
 ///
@@ -7,19 +14,23 @@ use crate::dual::enums::Number;
 ///
 /// - `real` components are equal: `lhs.real == rhs.real`.
 /// - `dual` components are equal after aligning `vars`.
+///
+/// Components are compared within the `dual_equality` tolerance of the
+/// [config](crate::config), which is zero, i.e. exact, by default.
 impl PartialEq<Dual> for Dual {
     fn eq(&self, other: &Dual) -> bool {
-        if self.real != other.real {
+        let tol = tolerances().dual_equality;
+        if !is_close(self.real, other.real, tol) {
             false
         } else {
             let state = self.vars_cmp(other.vars());
             match state {
                 VarsRelationship::ArcEquivalent | VarsRelationship::ValueEquivalent => {
-                    self.dual.iter().eq(other.dual.iter())
+                    all_close(&self.dual, &other.dual, tol)
                 }
                 _ => {
                     let (x, y) = self.to_union_vars(other, Some(state));
-                    x.dual.iter().eq(y.dual.iter())
+                    all_close(&x.dual, &y.dual, tol)
                 }
             }
         }
@@ -52,18 +63,19 @@ impl PartialEq<Dual2> for f64 {
 
 impl PartialEq<Dual2> for Dual2 {
     fn eq(&self, other: &Dual2) -> bool {
-        if self.real != other.real {
+        let tol = tolerances().dual_equality;
+        if !is_close(self.real, other.real, tol) {
             false
         } else {
             let state = self.vars_cmp(other.vars());
             match state {
                 VarsRelationship::ArcEquivalent | VarsRelationship::ValueEquivalent => {
-                    self.dual.iter().eq(other.dual.iter())
-                        && self.dual2.iter().eq(other.dual2.iter())
+                    all_close(&self.dual, &other.dual, tol)
+                        && all_close(&self.dual2, &other.dual2, tol)
                 }
                 _ => {
                     let (x, y) = self.to_union_vars(other, Some(state));
-                    x.dual.iter().eq(y.dual.iter()) && x.dual2.iter().eq(y.dual2.iter())
+                    all_close(&x.dual, &y.dual, tol) && all_close(&x.dual2, &y.dual2, tol)
                 }
             }
         }
@@ -72,20 +84,24 @@ impl PartialEq<Dual2> for Dual2 {
 
 impl PartialEq<Number> for Number {
     fn eq(&self, other: &Number) -> bool {
+        self.try_eq(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl Number {
+    /// Compare equality with a [Number], returning an error rather than panicking if the types
+    /// mix [Dual] and [Dual2].
+    pub fn try_eq(&self, other: &Number) -> Result<bool, DualError> {
         match (self, other) {
-            (Number::F64(f), Number::F64(f2)) => f == f2,
-            (Number::F64(f), Number::Dual(d2)) => f == d2,
-            (Number::F64(f), Number::Dual2(d2)) => f == d2,
-            (Number::Dual(d), Number::F64(f2)) => d == f2,
-            (Number::Dual(d), Number::Dual(d2)) => d == d2,
-            (Number::Dual(_), Number::Dual2(_)) => {
-                panic!("Cannot mix dual types: Dual == Dual2")
-            }
-            (Number::Dual2(d), Number::F64(f2)) => d == f2,
-            (Number::Dual2(_), Number::Dual(_)) => {
-                panic!("Cannot mix dual types: Dual2 == Dual")
-            }
-            (Number::Dual2(d), Number::Dual2(d2)) => d == d2,
+            (Number::F64(f), Number::F64(f2)) => Ok(f == f2),
+            (Number::F64(f), Number::Dual(d2)) => Ok(f == d2),
+            (Number::F64(f), Number::Dual2(d2)) => Ok(f == d2),
+            (Number::Dual(d), Number::F64(f2)) => Ok(d == f2),
+            (Number::Dual(d), Number::Dual(d2)) => Ok(d == d2),
+            (Number::Dual(_), Number::Dual2(_)) => Err(DualError::mixed("==", self, other)),
+            (Number::Dual2(d), Number::F64(f2)) => Ok(d == f2),
+            (Number::Dual2(_), Number::Dual(_)) => Err(DualError::mixed("==", self, other)),
+            (Number::Dual2(d), Number::Dual2(d2)) => Ok(d == d2),
         }
     }
 }
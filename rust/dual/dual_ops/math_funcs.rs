@@ -16,9 +16,41 @@ pub trait MathFuncs {
     fn norm_cdf(&self) -> Self;
     /// Return the inverse standard normal cumulative distribution function of a value.
     fn inv_norm_cdf(&self) -> Self;
+    /// Return the square root of a value.
+    fn sqrt(&self) -> Self;
+    /// Return the natural logarithm of one plus a value, accurate for values near zero.
+    fn log1p(&self) -> Self;
+}
+
+/// Apply a function of a real variable to a [Dual] from its value `f0` and first derivative `f1`
+/// at the real part.
+fn chain(x: &Dual, f0: f64, f1: f64) -> Dual {
+    Dual {
+        real: f0,
+        vars: Arc::clone(&x.vars),
+        dual: f1 * &x.dual,
+    }
+}
+
+/// Apply a function of a real variable to a [Dual2] from its value `f0`, first derivative `f1`
+/// and second derivative `f2` at the real part.
+fn chain2(x: &Dual2, f0: f64, f1: f64, f2: f64) -> Dual2 {
+    Dual2 {
+        real: f0,
+        vars: Arc::clone(&x.vars),
+        dual: f1 * &x.dual,
+        dual2: f1 * &x.dual2 + 0.5 * f2 * fouter11_(&x.dual.view(), &x.dual.view()),
+    }
 }
 
 impl MathFuncs for Dual {
+    fn sqrt(&self) -> Self {
+        let c = self.real.sqrt();
+        chain(self, c, 0.5 / c)
+    }
+    fn log1p(&self) -> Self {
+        chain(self, self.real.ln_1p(), 1.0 / (1.0 + self.real))
+    }
     fn exp(&self) -> Self {
         let c = self.real.exp();
         Dual {
@@ -57,6 +89,14 @@ impl MathFuncs for Dual {
 }
 
 impl MathFuncs for Dual2 {
+    fn sqrt(&self) -> Self {
+        let c = self.real.sqrt();
+        chain2(self, c, 0.5 / c, -0.25 / (c * self.real))
+    }
+    fn log1p(&self) -> Self {
+        let scalar = 1.0 / (1.0 + self.real);
+        chain2(self, self.real.ln_1p(), scalar, -scalar * scalar)
+    }
     fn exp(&self) -> Self {
         let c = self.real.exp();
         Dual2 {
@@ -105,6 +145,12 @@ impl MathFuncs for Dual2 {
 }
 
 impl MathFuncs for f64 {
+    fn sqrt(&self) -> Self {
+        f64::sqrt(*self)
+    }
+    fn log1p(&self) -> Self {
+        f64::ln_1p(*self)
+    }
     fn inv_norm_cdf(&self) -> Self {
         Normal::new(0.0, 1.0).unwrap().inverse_cdf(*self)
     }
@@ -130,6 +176,12 @@ macro_rules! math_func {
 }
 
 impl MathFuncs for Number {
+    fn sqrt(&self) -> Self {
+        math_func!(self, sqrt)
+    }
+    fn log1p(&self) -> Self {
+        math_func!(self, log1p)
+    }
     fn inv_norm_cdf(&self) -> Self {
         math_func!(self, inv_norm_cdf)
     }
@@ -1,4 +1,6 @@
 mod add;
+mod assign;
+pub mod combine;
 pub mod convert;
 mod div;
 mod eq;
@@ -11,8 +13,9 @@ pub mod numeric_ops;
 mod one;
 mod ord;
 mod pow;
+mod product;
 mod rem;
 mod signed;
 mod sub;
-mod sum;
+pub mod sum;
 mod zero;
@@ -1,5 +1,6 @@
 use crate::dual::dual::{Dual, Dual2, Vars, VarsRelationship};
 use crate::dual::enums::Number;
+use crate::dual::errors::DualError;
 use crate::dual::linalg::fouter11_;
 use auto_ops::{impl_op_ex, impl_op_ex_commutative};
 use ndarray::Array2;
@@ -24,6 +25,12 @@ impl_op_ex_commutative!(*|a: &Dual2, b: &f64| -> Dual2 {
 
 // impl Mul for Dual
 impl_op_ex!(*|a: &Dual, b: &Dual| -> Dual {
+    if b.vars.is_empty() {
+        return a * b.real;
+    }
+    if a.vars.is_empty() {
+        return a.real * b;
+    }
     let state = a.vars_cmp(b.vars());
     match state {
         VarsRelationship::ArcEquivalent | VarsRelationship::ValueEquivalent => Dual {
@@ -44,6 +51,12 @@ impl_op_ex!(*|a: &Dual, b: &Dual| -> Dual {
 
 // impl Mul for Dual2
 impl_op_ex!(*|a: &Dual2, b: &Dual2| -> Dual2 {
+    if b.vars.is_empty() {
+        return a * b.real;
+    }
+    if a.vars.is_empty() {
+        return a.real * b;
+    }
     let state = a.vars_cmp(b.vars());
     match state {
         VarsRelationship::ArcEquivalent | VarsRelationship::ValueEquivalent => {
@@ -74,22 +87,26 @@ impl_op_ex!(*|a: &Dual2, b: &Dual2| -> Dual2 {
 
 // Mul for Number
 impl_op_ex!(*|a: &Number, b: &Number| -> Number {
-    match (a, b) {
-        (Number::F64(f), Number::F64(f2)) => Number::F64(f * f2),
-        (Number::F64(f), Number::Dual(d2)) => Number::Dual(f * d2),
-        (Number::F64(f), Number::Dual2(d2)) => Number::Dual2(f * d2),
-        (Number::Dual(d), Number::F64(f2)) => Number::Dual(d * f2),
-        (Number::Dual(d), Number::Dual(d2)) => Number::Dual(d * d2),
-        (Number::Dual(_), Number::Dual2(_)) => {
-            panic!("Cannot mix dual types: Dual * Dual2")
-        }
-        (Number::Dual2(d), Number::F64(f2)) => Number::Dual2(d * f2),
-        (Number::Dual2(_), Number::Dual(_)) => {
-            panic!("Cannot mix dual types: Dual2 * Dual")
+    a.try_mul(b).unwrap_or_else(|e| panic!("{}", e))
+});
+
+impl Number {
+    /// Multiply by a [Number], returning an error rather than panicking if the types mix [Dual]
+    /// and [Dual2].
+    pub fn try_mul(&self, other: &Number) -> Result<Number, DualError> {
+        match (self, other) {
+            (Number::F64(f), Number::F64(f2)) => Ok(Number::F64(f * f2)),
+            (Number::F64(f), Number::Dual(d2)) => Ok(Number::Dual(f * d2)),
+            (Number::F64(f), Number::Dual2(d2)) => Ok(Number::Dual2(f * d2)),
+            (Number::Dual(d), Number::F64(f2)) => Ok(Number::Dual(d * f2)),
+            (Number::Dual(d), Number::Dual(d2)) => Ok(Number::Dual(d * d2)),
+            (Number::Dual(_), Number::Dual2(_)) => Err(DualError::mixed("*", self, other)),
+            (Number::Dual2(d), Number::F64(f2)) => Ok(Number::Dual2(d * f2)),
+            (Number::Dual2(_), Number::Dual(_)) => Err(DualError::mixed("*", self, other)),
+            (Number::Dual2(d), Number::Dual2(d2)) => Ok(Number::Dual2(d * d2)),
         }
-        (Number::Dual2(d), Number::Dual2(d2)) => Number::Dual2(d * d2),
     }
-});
+}
 
 // Mul for Number
 impl_op_ex_commutative!(*|a: &Number, b: &f64| -> Number {
@@ -216,6 +233,19 @@ mod tests {
         let _ = d * d2;
     }
 
+    #[test]
+    fn test_enum_try_mul() {
+        let d = Number::Dual2(Dual2::new(2.0, vec!["y".to_string()]));
+        let d2 = Number::Dual(Dual::new(3.0, vec!["x".to_string()]));
+        let expected = DualError::MixedTypes {
+            op: "*",
+            lhs: "Dual2",
+            rhs: "Dual",
+        };
+        assert_eq!(d.try_mul(&d2), Err(expected));
+        assert!(d.try_mul(&Number::F64(1.5)).is_ok());
+    }
+
     #[test]
     fn test_enum_f64() {
         let d = Number::Dual(Dual::new(3.0, vec!["x".to_string()]));
@@ -225,4 +255,13 @@ mod tests {
             Number::Dual(Dual::new(3.0, vec!["x".to_string()]) * 2.0)
         );
     }
+
+    #[test]
+    fn mul_empty_vars() {
+        let d = Dual2::try_new(2.0, vec!["x".to_string()], vec![1.0], vec![]).unwrap();
+        let c = Dual2::new(3.0, vec![]);
+        assert_eq!(&d * &c, &c * &d);
+        assert_eq!(&d * &c, &d * 3.0);
+        assert!(Arc::ptr_eq((&c * &d).vars(), d.vars()));
+    }
 }
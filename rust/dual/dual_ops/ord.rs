@@ -1,5 +1,6 @@
 use crate::dual::dual::{Dual, Dual2};
 use crate::dual::enums::Number;
+use crate::dual::errors::DualError;
 use std::cmp::Ordering;
 
 /// Compares `Dual` by `real` component only.
@@ -41,20 +42,25 @@ impl PartialOrd<Dual2> for f64 {
 
 impl PartialOrd<Number> for Number {
     fn partial_cmp(&self, other: &Number) -> Option<Ordering> {
+        self.try_partial_cmp(other)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl Number {
+    /// Compare with a [Number], returning an error rather than panicking if the types mix
+    /// [Dual] and [Dual2].
+    pub fn try_partial_cmp(&self, other: &Number) -> Result<Option<Ordering>, DualError> {
         match (self, other) {
-            (Number::F64(f), Number::F64(f2)) => f.partial_cmp(f2),
-            (Number::F64(f), Number::Dual(d2)) => f.partial_cmp(d2),
-            (Number::F64(f), Number::Dual2(d2)) => f.partial_cmp(d2),
-            (Number::Dual(d), Number::F64(f2)) => d.partial_cmp(f2),
-            (Number::Dual(d), Number::Dual(d2)) => d.partial_cmp(d2),
-            (Number::Dual(_), Number::Dual2(_)) => {
-                panic!("Cannot mix dual types: Dual compare Dual2")
-            }
-            (Number::Dual2(d), Number::F64(f2)) => d.partial_cmp(f2),
-            (Number::Dual2(_), Number::Dual(_)) => {
-                panic!("Cannot mix dual types: Dual2 compare Dual")
-            }
-            (Number::Dual2(d), Number::Dual2(d2)) => d.partial_cmp(d2),
+            (Number::F64(f), Number::F64(f2)) => Ok(f.partial_cmp(f2)),
+            (Number::F64(f), Number::Dual(d2)) => Ok(f.partial_cmp(d2)),
+            (Number::F64(f), Number::Dual2(d2)) => Ok(f.partial_cmp(d2)),
+            (Number::Dual(d), Number::F64(f2)) => Ok(d.partial_cmp(f2)),
+            (Number::Dual(d), Number::Dual(d2)) => Ok(d.partial_cmp(d2)),
+            (Number::Dual(_), Number::Dual2(_)) => Err(DualError::mixed("compare", self, other)),
+            (Number::Dual2(d), Number::F64(f2)) => Ok(d.partial_cmp(f2)),
+            (Number::Dual2(_), Number::Dual(_)) => Err(DualError::mixed("compare", self, other)),
+            (Number::Dual2(d), Number::Dual2(d2)) => Ok(d.partial_cmp(d2)),
         }
     }
 }
@@ -1,6 +1,8 @@
 use crate::dual::dual::{Dual, Dual2, Vars};
+use crate::dual::dual_ops::math_funcs::MathFuncs;
 use crate::dual::enums::Number;
 use crate::dual::linalg::fouter11_;
+use ndarray::{Array1, Array2};
 use num_traits::Pow;
 use std::sync::Arc;
 
@@ -78,10 +80,107 @@ impl Pow<f64> for &Number {
     }
 }
 
+/// Raise a dual base to a dual exponent as `exp(y * ln(x))`, for a positive base.
+macro_rules! pow_dual_exponent {
+    ($T: ident) => {
+        impl Pow<&$T> for &$T {
+            type Output = $T;
+            fn pow(self, power: &$T) -> Self::Output {
+                (power * &self.log()).exp()
+            }
+        }
+
+        impl Pow<&$T> for f64 {
+            type Output = $T;
+            fn pow(self, power: &$T) -> Self::Output {
+                (power * self.ln()).exp()
+            }
+        }
+    };
+}
+pow_dual_exponent!(Dual);
+pow_dual_exponent!(Dual2);
+
+impl Pow<&Number> for &Number {
+    type Output = Number;
+
+    /// Raise to a [Number] power, which panics if it mixes [Dual] and [Dual2] types.
+    fn pow(self, power: &Number) -> Self::Output {
+        match power {
+            Number::F64(f) => self.pow(*f),
+            _ => (power * &self.log()).exp(),
+        }
+    }
+}
+
+/// Raise to an integer power by repeated squaring.
+///
+/// Every intermediate value is a product, so derivatives follow exact product rule chains and
+/// avoid the rounding of `powf`, e.g. `x.powi(2)` equals `x * x`. Negative powers are the
+/// reciprocal of the positive power.
+macro_rules! powi_by_squaring {
+    ($x: expr, $n: expr, $one: expr) => {{
+        let mut result = None;
+        let mut base = $x.clone();
+        let mut k = $n.unsigned_abs();
+        while k > 0 {
+            if k & 1 == 1 {
+                result = Some(match result {
+                    None => base.clone(),
+                    Some(r) => &r * &base,
+                });
+            }
+            k >>= 1;
+            if k > 0 {
+                base = &base * &base;
+            }
+        }
+        let result = result.unwrap_or_else(|| $one);
+        if $n < 0 {
+            1.0_f64 / result
+        } else {
+            result
+        }
+    }};
+}
+
+impl Dual {
+    /// Raise to an integer power by repeated squaring with exact derivatives.
+    pub fn powi(&self, n: i32) -> Dual {
+        powi_by_squaring!(
+            self,
+            n,
+            Dual::clone_from(self, 1.0, Array1::zeros(self.dual.len()))
+        )
+    }
+}
+
+impl Dual2 {
+    /// Raise to an integer power by repeated squaring with exact derivatives.
+    pub fn powi(&self, n: i32) -> Dual2 {
+        let k = self.dual.len();
+        powi_by_squaring!(
+            self,
+            n,
+            Dual2::clone_from(self, 1.0, Array1::zeros(k), Array2::zeros((k, k)))
+        )
+    }
+}
+
+impl Number {
+    /// Raise to an integer power by repeated squaring with exact derivatives.
+    pub fn powi(&self, n: i32) -> Number {
+        match self {
+            Number::F64(f) => Number::F64(f.powi(n)),
+            Number::Dual(d) => Number::Dual(d.powi(n)),
+            Number::Dual2(d) => Number::Dual2(d.powi(n)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ndarray::Array1;
 
     #[test]
     fn inv() {
@@ -126,6 +225,41 @@ mod tests {
         assert_eq!(result, expected)
     }
 
+    #[test]
+    fn powi_exact_products() {
+        let x = Dual::try_new(1.1, vec!["x".to_string(), "y".to_string()], vec![1.0, 0.3]).unwrap();
+        assert_eq!(x.powi(2), &x * &x);
+        assert_eq!(x.powi(3), &(&x * &x) * &x);
+        let expected = &x.dual * 5.0 * 1.1_f64.powi(4);
+        assert!((x.powi(5).dual - expected).iter().all(|e| e.abs() < 1e-14));
+        assert_eq!(
+            x.powi(0),
+            Dual::try_new(1.0, vec!["x".to_string(), "y".to_string()], vec![0.0, 0.0]).unwrap()
+        );
+        let inv = x.powi(-2);
+        assert!((inv.real - 1.0 / 1.21).abs() < 1e-15);
+        assert!((inv.dual[0] + 2.0 / 1.1_f64.powi(3)).abs() < 1e-14);
+    }
+
+    #[test]
+    fn powi_dual2() {
+        let x = Dual2::new(3.0, vec!["x".to_string()]);
+        let result = x.powi(3);
+        assert_eq!(result.real, 27.0);
+        assert_eq!(result.dual, Array1::from_vec(vec![27.0]));
+        assert_eq!(result.dual2, Array2::from_elem((1, 1), 9.0));
+        assert_eq!(x.powi(2), &x * &x);
+        let result = x.powi(-1);
+        assert!((result.dual2[[0, 0]] - 1.0 / 27.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn powi_number() {
+        assert_eq!(Number::F64(2.0).powi(-2), Number::F64(0.25));
+        let d = Dual::new(2.0, vec!["x".to_string()]);
+        assert_eq!(Number::Dual(d.clone()).powi(3), Number::Dual(d.powi(3)));
+    }
+
     #[test]
     fn test_enum() {
         let f = Number::F64(2.0);
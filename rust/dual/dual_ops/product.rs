@@ -0,0 +1,96 @@
+use crate::dual::dual::{Dual, Dual2};
+use crate::dual::enums::Number;
+use std::iter::Product;
+
+impl Product for Dual {
+    fn product<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = Dual>,
+    {
+        iter.fold(Dual::new(1.0, Vec::new()), |acc, x| acc * x)
+    }
+}
+
+impl<'a> Product<&'a Dual> for Dual {
+    fn product<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = &'a Dual>,
+    {
+        iter.fold(Dual::new(1.0, Vec::new()), |acc, x| &acc * x)
+    }
+}
+
+impl Product for Dual2 {
+    fn product<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = Dual2>,
+    {
+        iter.fold(Dual2::new(1.0, Vec::new()), |acc, x| acc * x)
+    }
+}
+
+impl<'a> Product<&'a Dual2> for Dual2 {
+    fn product<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = &'a Dual2>,
+    {
+        iter.fold(Dual2::new(1.0, Vec::new()), |acc, x| &acc * x)
+    }
+}
+
+impl Product for Number {
+    fn product<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = Number>,
+    {
+        iter.fold(Number::F64(1.0_f64), |acc, x| acc * x)
+    }
+}
+
+impl<'a> Product<&'a Number> for Number {
+    fn product<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = &'a Number>,
+    {
+        iter.fold(Number::F64(1.0_f64), |acc, x| &acc * x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dual::dual::Gradient2;
+
+    #[test]
+    fn test_product() {
+        let v = vec![
+            Dual::new(2.0, vec!["x".to_string()]),
+            Dual::new(3.0, vec!["y".to_string()]),
+        ];
+        let p: Dual = v.iter().product();
+        assert_eq!(
+            p,
+            Dual::try_new(6.0, vec!["x".to_string(), "y".to_string()], vec![3.0, 2.0]).unwrap()
+        );
+        assert_eq!(p, v.into_iter().product::<Dual>());
+    }
+
+    #[test]
+    fn test_product_dual2() {
+        let v = vec![
+            Dual2::new(2.0, vec!["x".to_string()]),
+            Dual2::new(3.0, vec!["y".to_string()]),
+        ];
+        let p: Dual2 = v.iter().product();
+        let hess = p.gradient2(vec!["x".to_string(), "y".to_string()]);
+        assert_eq!(hess[[0, 1]], 1.0);
+        assert_eq!(p, v.into_iter().product::<Dual2>());
+    }
+
+    #[test]
+    fn test_product_enum() {
+        let v = vec![Number::F64(2.5_f64), Number::F64(2.0_f64)];
+        let p: Number = v.iter().product();
+        assert_eq!(p, Number::F64(5.0));
+    }
+}
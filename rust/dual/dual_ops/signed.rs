@@ -1,5 +1,6 @@
 use crate::dual::dual::{Dual, Dual2};
 use crate::dual::enums::Number;
+use crate::dual::errors::DualError;
 use num_traits::Signed;
 use std::sync::Arc;
 
@@ -99,25 +100,7 @@ impl Signed for Number {
     }
 
     fn abs_sub(&self, other: &Self) -> Self {
-        match (self, other) {
-            (Number::F64(f), Number::F64(f2)) => Number::F64(f.abs_sub(f2)),
-            (Number::F64(f), Number::Dual(d2)) => Number::Dual(Dual::new(*f, vec![]).abs_sub(d2)),
-            (Number::F64(f), Number::Dual2(d2)) => {
-                Number::Dual2(Dual2::new(*f, vec![]).abs_sub(d2))
-            }
-            (Number::Dual(d), Number::F64(f2)) => Number::Dual(d.abs_sub(&Dual::new(*f2, vec![]))),
-            (Number::Dual(d), Number::Dual(d2)) => Number::Dual(d.abs_sub(d2)),
-            (Number::Dual(_), Number::Dual2(_)) => {
-                panic!("Cannot mix dual types: Dual / Dual2")
-            }
-            (Number::Dual2(d), Number::F64(f2)) => {
-                Number::Dual2(d.abs_sub(&Dual2::new(*f2, vec![])))
-            }
-            (Number::Dual2(_), Number::Dual(_)) => {
-                panic!("Cannot mix dual types: Dual2 / Dual")
-            }
-            (Number::Dual2(d), Number::Dual2(d2)) => Number::Dual2(d.abs_sub(d2)),
-        }
+        self.try_abs_sub(other).unwrap_or_else(|e| panic!("{}", e))
     }
 
     fn signum(&self) -> Self {
@@ -145,6 +128,32 @@ impl Signed for Number {
     }
 }
 
+impl Number {
+    /// Return the positive difference with a [Number], returning an error rather than panicking
+    /// if the types mix [Dual] and [Dual2].
+    pub fn try_abs_sub(&self, other: &Number) -> Result<Number, DualError> {
+        match (self, other) {
+            (Number::F64(f), Number::F64(f2)) => Ok(Number::F64(f.abs_sub(f2))),
+            (Number::F64(f), Number::Dual(d2)) => {
+                Ok(Number::Dual(Dual::new(*f, vec![]).abs_sub(d2)))
+            }
+            (Number::F64(f), Number::Dual2(d2)) => {
+                Ok(Number::Dual2(Dual2::new(*f, vec![]).abs_sub(d2)))
+            }
+            (Number::Dual(d), Number::F64(f2)) => {
+                Ok(Number::Dual(d.abs_sub(&Dual::new(*f2, vec![]))))
+            }
+            (Number::Dual(d), Number::Dual(d2)) => Ok(Number::Dual(d.abs_sub(d2))),
+            (Number::Dual(_), Number::Dual2(_)) => Err(DualError::mixed("abs_sub", self, other)),
+            (Number::Dual2(d), Number::F64(f2)) => {
+                Ok(Number::Dual2(d.abs_sub(&Dual2::new(*f2, vec![]))))
+            }
+            (Number::Dual2(_), Number::Dual(_)) => Err(DualError::mixed("abs_sub", self, other)),
+            (Number::Dual2(d), Number::Dual2(d2)) => Ok(Number::Dual2(d.abs_sub(d2))),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
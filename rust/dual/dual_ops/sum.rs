@@ -1,6 +1,8 @@
 use crate::dual::dual::{Dual, Dual2};
 use crate::dual::enums::Number;
+use num_traits::Zero;
 use std::iter::Sum;
+use std::ops::Add;
 
 impl Sum for Dual {
     fn sum<I>(iter: I) -> Self
@@ -29,6 +31,51 @@ impl Sum for Number {
     }
 }
 
+impl<'a> Sum<&'a Dual> for Dual {
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = &'a Dual>,
+    {
+        iter.fold(Dual::new(0.0, Vec::new()), |acc, x| &acc + x)
+    }
+}
+
+impl<'a> Sum<&'a Dual2> for Dual2 {
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = &'a Dual2>,
+    {
+        iter.fold(Dual2::new(0.0, Vec::new()), |acc, x| &acc + x)
+    }
+}
+
+impl<'a> Sum<&'a Number> for Number {
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = &'a Number>,
+    {
+        iter.fold(Number::F64(0.0_f64), |acc, x| &acc + x)
+    }
+}
+
+/// Sum a slice of values by pairwise summation.
+///
+/// The rounding error grows with the logarithm of the number of values rather than linearly, as
+/// it does for sequential summation, which benefits long sums such as those of cashflows.
+pub fn sum_pairwise<T>(values: &[T]) -> T
+where
+    T: Zero,
+    for<'a> &'a T: Add<&'a T, Output = T>,
+{
+    const BLOCK: usize = 8;
+    if values.len() <= BLOCK {
+        values.iter().fold(T::zero(), |acc, x| &acc + x)
+    } else {
+        let (left, right) = values.split_at(values.len() / 2);
+        &sum_pairwise(left) + &sum_pairwise(right)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -46,4 +93,32 @@ mod tests {
             Number::Dual(Dual::try_new(7.5, vec!["x".to_string()], vec![2.0]).unwrap())
         );
     }
+
+    #[test]
+    fn test_sum_refs() {
+        let v = vec![
+            Dual::new(1.5, vec!["x".to_string()]),
+            Dual::new(3.5, vec!["y".to_string()]),
+        ];
+        let s: Dual = v.iter().sum();
+        assert_eq!(s, v.into_iter().sum::<Dual>());
+        let v = vec![Number::F64(2.5_f64), Number::F64(1.5_f64)];
+        let s: Number = v.iter().sum();
+        assert_eq!(s, Number::F64(4.0));
+    }
+
+    #[test]
+    fn test_sum_pairwise() {
+        let v: Vec<f64> = vec![0.1; 100000];
+        let sequential: f64 = v.iter().sum();
+        let pairwise = sum_pairwise(&v);
+        assert!((pairwise - 10000.0).abs() < (sequential - 10000.0).abs());
+
+        let d: Vec<Dual> = (0..20)
+            .map(|i| Dual::new(i as f64, vec![format!("x{}", i % 3)]))
+            .collect();
+        let expected: Dual = d.iter().sum();
+        assert_eq!(sum_pairwise(&d), expected);
+        assert_eq!(sum_pairwise::<Number>(&[]), Number::F64(0.0));
+    }
 }
@@ -3,11 +3,13 @@
 use crate::dual::dual::{Dual, Dual2, Gradient1, Gradient2, Vars};
 use crate::dual::dual_ops::math_funcs::MathFuncs;
 use crate::dual::enums::{ADOrder, Number};
+use crate::dual::errors::DualError;
 use bincode::{deserialize, serialize};
 use num_traits::{Pow, Signed};
 use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::{PyBytes, PyFloat};
+use std::collections::HashMap;
 use std::sync::Arc;
 // use pyo3::types::PyFloat;
 use crate::json::json_py::DeserializedObj;
@@ -45,6 +47,36 @@ impl IntoPy<PyObject> for Number {
     }
 }
 
+/// Extract a [Dual], a [Dual2], or a float from any real scalar with `__float__` or `__index__`,
+/// e.g. an int, a numpy scalar such as `np.float32` or `np.int64`, or a `Decimal`.
+///
+/// Arrays of one or more dimensions are rejected, rather than converted if they have one element,
+/// so that binary operations with them return `NotImplemented` and numpy broadcasts over them.
+impl<'py> FromPyObject<'py> for Number {
+    fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(f) = obj.downcast_exact::<PyFloat>() {
+            return Ok(Number::F64(f.value()));
+        }
+        if let Ok(d) = obj.downcast::<Dual>() {
+            return Ok(Number::Dual(d.borrow().clone()));
+        }
+        if let Ok(d) = obj.downcast::<Dual2>() {
+            return Ok(Number::Dual2(d.borrow().clone()));
+        }
+        let is_scalar = match obj.getattr("ndim") {
+            Ok(ndim) => ndim.extract::<usize>().map_or(true, |n| n == 0),
+            Err(_) => true,
+        };
+        if is_scalar && (obj.hasattr("__float__")? || obj.hasattr("__index__")?) {
+            return Ok(Number::F64(obj.extract::<f64>()?));
+        }
+        Err(PyTypeError::new_err(format!(
+            "Expected a float, int, Dual or Dual2, not '{}'.",
+            obj.get_type().name()?
+        )))
+    }
+}
+
 // https://github.com/PyO3/pyo3/discussions/3911
 // #[derive(Debug, Clone, PartialEq, PartialOrd, FromPyObject)]
 // pub enum DualsOrPyFloat<'py> {
@@ -58,12 +90,11 @@ impl ADOrder {
     // Pickling
     #[new]
     fn new_py(ad: u8) -> PyResult<ADOrder> {
-        match ad {
-            0_u8 => Ok(ADOrder::Zero),
-            1_u8 => Ok(ADOrder::One),
-            2_u8 => Ok(ADOrder::Two),
-            _ => Err(PyValueError::new_err("unreachable code on ADOrder pickle.")),
-        }
+        ADOrder::try_from_int(ad)
+    }
+
+    fn __int__(&self) -> u8 {
+        u8::from(*self)
     }
     pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
         *self = deserialize(state.as_bytes()).unwrap();
@@ -85,7 +116,7 @@ impl ADOrder {
 impl Dual {
     #[new]
     fn new_py(real: f64, vars: Vec<String>, dual: Vec<f64>) -> PyResult<Self> {
-        Dual::try_new(real, vars, dual)
+        Ok(Dual::try_new(real, vars, dual)?)
     }
 
     /// Create a :class:`~rateslib.dual.Dual` object with ``vars`` linked with another.
@@ -128,7 +159,7 @@ impl Dual {
     ///    x1.ptr_eq(x3)
     #[staticmethod]
     fn vars_from(other: &Dual, real: f64, vars: Vec<String>, dual: Vec<f64>) -> PyResult<Self> {
-        Dual::try_new_from(other, real, vars, dual)
+        Ok(Dual::try_new_from(other, real, vars, dual)?)
     }
 
     #[getter]
@@ -188,6 +219,33 @@ impl Dual {
         Ok(Arc::ptr_eq(self.vars(), other.vars()))
     }
 
+    /// Return a new :class:`~rateslib.dual.Dual` with variables relabelled.
+    ///
+    /// Parameters
+    /// ----------
+    /// mapping: dict[str, str]
+    ///     The new label of each variable to rename. Variables not in ``mapping`` keep their
+    ///     label.
+    ///
+    /// Returns
+    /// -------
+    /// Dual
+    ///
+    /// Notes
+    /// -----
+    /// If several variables are renamed to the same label their gradients are summed.
+    ///
+    /// .. ipython:: python
+    ///
+    ///    from rateslib import Dual
+    ///
+    ///    x = Dual(1.0, ["x", "y"], [2.0, 3.0])
+    ///    x.rename_vars({"x": "scenario1_x"})
+    #[pyo3(name = "rename_vars")]
+    fn rename_vars_py(&self, mapping: HashMap<String, String>) -> PyResult<Self> {
+        Ok(self.rename_vars(&mapping))
+    }
+
     fn __repr__(&self) -> PyResult<String> {
         let mut _vars = Vec::from_iter(self.vars().iter().take(3).map(String::as_str)).join(", ");
         let mut _dual =
@@ -336,19 +394,27 @@ impl Dual {
 
     fn __pow__(&self, power: Number, modulo: Option<i32>) -> PyResult<Self> {
         if modulo.unwrap_or(0) != 0 {
-            panic!("Power function with mod not available for Dual.")
+            return Err(DualError::PowerModulo.into());
         }
         match power {
+            Number::F64(f) if f.fract() == 0.0 && f.abs() <= i32::MAX as f64 => {
+                Ok(self.powi(f as i32))
+            }
             Number::F64(f) => Ok(self.clone().pow(f)),
-            Number::Dual(_d) => Err(PyTypeError::new_err(
-                "Power operation not defined with Dual type exponent.",
-            )),
+            Number::Dual(d) => Ok(self.pow(&d)),
             Number::Dual2(_) => Err(PyTypeError::new_err(
-                "Power operation not defined with Dual type exponent.",
+                "Cannot mix `Dual2` and `Dual` types in a power operation.",
             )),
         }
     }
 
+    fn __rpow__(&self, other: f64, modulo: Option<i32>) -> PyResult<Self> {
+        if modulo.unwrap_or(0) != 0 {
+            return Err(DualError::PowerModulo.into());
+        }
+        Ok(other.pow(self))
+    }
+
     fn __exp__(&self) -> Self {
         self.exp()
     }
@@ -387,6 +453,20 @@ impl Dual {
         }
     }
 
+    /// Return a hash of the canonical JSON representation of the object, which is stable
+    /// across sessions.
+    ///
+    /// Returns
+    /// -------
+    /// int
+    #[pyo3(name = "canonical_hash")]
+    fn canonical_hash_py(&self) -> PyResult<u64> {
+        match DeserializedObj::Dual(self.clone()).canonical_hash() {
+            Ok(v) => Ok(v),
+            Err(_) => Err(PyValueError::new_err("Failed to serialize `Dual` to JSON.")),
+        }
+    }
+
     // Pickling
     pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
         *self = deserialize(state.as_bytes()).unwrap();
@@ -415,7 +495,7 @@ impl Dual2 {
     /// Python wrapper to construct a new `Dual2`.
     #[new]
     pub fn new_py(real: f64, vars: Vec<String>, dual: Vec<f64>, dual2: Vec<f64>) -> PyResult<Self> {
-        Dual2::try_new(real, vars, dual, dual2)
+        Ok(Dual2::try_new(real, vars, dual, dual2)?)
     }
 
     /// Create a :class:`~rateslib.dual.Dual2` object with ``vars`` linked with another.
@@ -458,7 +538,7 @@ impl Dual2 {
         dual: Vec<f64>,
         dual2: Vec<f64>,
     ) -> PyResult<Self> {
-        Dual2::try_new_from(other, real, vars, dual, dual2)
+        Ok(Dual2::try_new_from(other, real, vars, dual, dual2)?)
     }
 
     #[getter]
@@ -520,6 +600,13 @@ impl Dual2 {
         Ok(self.ptr_eq(other))
     }
 
+    /// Return a new :class:`~rateslib.dual.Dual2` with variables relabelled. See
+    /// :meth:`~rateslib.dual.Dual.rename_vars`.
+    #[pyo3(name = "rename_vars")]
+    fn rename_vars_py(&self, mapping: HashMap<String, String>) -> PyResult<Self> {
+        Ok(self.rename_vars(&mapping))
+    }
+
     fn __repr__(&self) -> PyResult<String> {
         let mut _vars = Vec::from_iter(self.vars.iter().take(3).map(String::as_str)).join(", ");
         let mut _dual =
@@ -671,19 +758,27 @@ impl Dual2 {
 
     fn __pow__(&self, power: Number, modulo: Option<i32>) -> PyResult<Self> {
         if modulo.unwrap_or(0) != 0 {
-            panic!("Power function with mod not available for Dual.")
+            return Err(DualError::PowerModulo.into());
         }
         match power {
+            Number::F64(f) if f.fract() == 0.0 && f.abs() <= i32::MAX as f64 => {
+                Ok(self.powi(f as i32))
+            }
             Number::F64(f) => Ok(self.clone().pow(f)),
-            Number::Dual(_d) => Err(PyTypeError::new_err(
-                "Power operation not defined with Dual type exponent.",
-            )),
-            Number::Dual2(_d) => Err(PyTypeError::new_err(
-                "Power operation not defined with Dual type exponent.",
+            Number::Dual(_) => Err(PyTypeError::new_err(
+                "Cannot mix `Dual` and `Dual2` types in a power operation.",
             )),
+            Number::Dual2(d) => Ok(self.pow(&d)),
         }
     }
 
+    fn __rpow__(&self, other: f64, modulo: Option<i32>) -> PyResult<Self> {
+        if modulo.unwrap_or(0) != 0 {
+            return Err(DualError::PowerModulo.into());
+        }
+        Ok(other.pow(self))
+    }
+
     fn __exp__(&self) -> Self {
         self.exp()
     }
@@ -724,6 +819,22 @@ impl Dual2 {
         }
     }
 
+    /// Return a hash of the canonical JSON representation of the object, which is stable
+    /// across sessions.
+    ///
+    /// Returns
+    /// -------
+    /// int
+    #[pyo3(name = "canonical_hash")]
+    fn canonical_hash_py(&self) -> PyResult<u64> {
+        match DeserializedObj::Dual2(self.clone()).canonical_hash() {
+            Ok(v) => Ok(v),
+            Err(_) => Err(PyValueError::new_err(
+                "Failed to serialize `Dual2` to JSON.",
+            )),
+        }
+    }
+
     // Pickling
     fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
         *self = deserialize(state.as_bytes()).unwrap();
@@ -1,7 +1,8 @@
 use crate::dual::{Dual, Dual2};
 use crate::splines::{PPSplineDual, PPSplineDual2, PPSplineF64};
 use ndarray::{Array1, Array2};
-use pyo3::{pyclass, FromPyObject, PyErr};
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, PyErr};
 use serde::{Deserialize, Serialize};
 
 /// Defines the order of gradients available in a calculation with AD.
@@ -16,8 +17,42 @@ pub enum ADOrder {
     Two,
 }
 
+impl ADOrder {
+    /// Return the order from its integer value, 0, 1 or 2.
+    pub fn try_from_int(order: u8) -> Result<Self, PyErr> {
+        match order {
+            0_u8 => Ok(ADOrder::Zero),
+            1_u8 => Ok(ADOrder::One),
+            2_u8 => Ok(ADOrder::Two),
+            _ => Err(PyValueError::new_err("Order for AD can only be in {0,1,2}")),
+        }
+    }
+
+    /// Return the order of a [Number].
+    pub fn of(value: &Number) -> Self {
+        match value {
+            Number::F64(_) => ADOrder::Zero,
+            Number::Dual(_) => ADOrder::One,
+            Number::Dual2(_) => ADOrder::Two,
+        }
+    }
+}
+
+impl From<ADOrder> for u8 {
+    fn from(value: ADOrder) -> Self {
+        match value {
+            ADOrder::Zero => 0,
+            ADOrder::One => 1,
+            ADOrder::Two => 2,
+        }
+    }
+}
+
 /// Container for the three core numeric types; [f64], [Dual] and [Dual2].
-#[derive(Debug, Clone, FromPyObject, Serialize, Deserialize)]
+///
+/// From Python a float is extracted from any real scalar, e.g. an int, a numpy scalar or a
+/// `Decimal`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Number {
     Dual(Dual),
     Dual2(Dual2),
@@ -63,3 +98,22 @@ pub enum NumberPPSpline {
 pub trait NumberMapping {
     fn mapped_value(&self, x: &Number) -> Result<Number, PyErr>;
 }
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adorder_conversions() {
+        for ad in [ADOrder::Zero, ADOrder::One, ADOrder::Two] {
+            assert_eq!(ADOrder::try_from_int(u8::from(ad)).unwrap(), ad);
+        }
+        assert!(ADOrder::try_from_int(3).is_err());
+        assert_eq!(ADOrder::of(&Number::F64(1.0)), ADOrder::Zero);
+        assert_eq!(
+            ADOrder::of(&Number::Dual2(Dual2::new(1.0, vec![]))),
+            ADOrder::Two
+        );
+    }
+}
@@ -0,0 +1,88 @@
+//! Typed errors of the construction of, and the operations on, dual numbers.
+
+use crate::dual::enums::Number;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::PyErr;
+use std::fmt;
+
+/// The reason a dual number cannot be constructed or an operation on dual numbers fails.
+///
+/// Converts to a Python `ValueError` for invalid gradients and to a `TypeError` for invalid
+/// operations.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DualError {
+    /// The first order gradients `dual` do not have one value for each unique variable.
+    DualLength { vars: usize, dual: usize },
+    /// The second order gradients `dual2` do not have one value for each pair of unique
+    /// variables.
+    Dual2Length { vars: usize, dual2: usize },
+    /// A binary operation, e.g. `"+"`, between a [Dual](crate::dual::Dual) and a
+    /// [Dual2](crate::dual::Dual2).
+    MixedTypes {
+        op: &'static str,
+        lhs: &'static str,
+        rhs: &'static str,
+    },
+    /// A power operation with a modulus, which is not available for dual numbers.
+    PowerModulo,
+}
+
+impl DualError {
+    /// Return the error of the operation `op` between two [Number]s of mixed dual types.
+    pub(crate) fn mixed(op: &'static str, lhs: &Number, rhs: &Number) -> Self {
+        DualError::MixedTypes {
+            op,
+            lhs: type_name(lhs),
+            rhs: type_name(rhs),
+        }
+    }
+}
+
+fn type_name(value: &Number) -> &'static str {
+    match value {
+        Number::F64(_) => "f64",
+        Number::Dual(_) => "Dual",
+        Number::Dual2(_) => "Dual2",
+    }
+}
+
+impl fmt::Display for DualError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DualError::DualLength { vars, dual } => write!(
+                f,
+                "`vars` and `dual` must have the same length, got {} unique vars and {} values.",
+                vars, dual
+            ),
+            DualError::Dual2Length { vars, dual2 } => write!(
+                f,
+                "`vars` and `dual2` must have compatible lengths, got {} unique vars and {} \
+                 values, expected {}.",
+                vars,
+                dual2,
+                vars * vars
+            ),
+            DualError::MixedTypes { op, lhs, rhs } => {
+                write!(f, "Cannot mix dual types: {} {} {}", lhs, op, rhs)
+            }
+            DualError::PowerModulo => {
+                write!(f, "Power function with mod not available for dual numbers.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DualError {}
+
+impl From<DualError> for PyErr {
+    fn from(err: DualError) -> Self {
+        match err {
+            DualError::DualLength { .. } | DualError::Dual2Length { .. } => {
+                PyValueError::new_err(err.to_string())
+            }
+            DualError::MixedTypes { .. } | DualError::PowerModulo => {
+                PyTypeError::new_err(err.to_string())
+            }
+        }
+    }
+}
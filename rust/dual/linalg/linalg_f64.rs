@@ -216,6 +216,22 @@ mod tests {
         assert!(Arc::ptr_eq(&result[0].vars(), &result[1].vars()));
     }
 
+    #[test]
+    fn fdmul21_number() {
+        use crate::dual::Number;
+        let a = arr2(&[[1.0, 2.0], [0.0, 3.0]]);
+        let b = arr1(&[
+            Number::Dual(Dual::new(2.0, vec!["x".to_string()])),
+            Number::F64(5.0),
+        ]);
+        let result = fdmul21_(&a.view(), &b.view());
+        let expected = arr1(&[
+            Number::Dual(Dual::new(12.0, vec!["x".to_string()])),
+            Number::F64(15.0),
+        ]);
+        assert_eq!(result, expected);
+    }
+
     #[test]
     #[should_panic]
     fn fdmul11_p() {
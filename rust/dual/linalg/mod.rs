@@ -3,6 +3,9 @@
 mod linalg_dual;
 mod linalg_f64;
 
+#[cfg(feature = "nalgebra")]
+pub mod nalgebra_interop;
+
 pub use crate::dual::linalg::linalg_dual::{dmul11_, dmul21_, dmul22_, douter11_, dsolve};
 pub use crate::dual::linalg::linalg_f64::{
     dfmul21_, dfmul22_, fdmul11_, fdmul21_, fdmul22_, fdsolve, fouter11_,
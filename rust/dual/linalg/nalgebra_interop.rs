@@ -0,0 +1,113 @@
+//! Conversions between the [ndarray] arrays of this crate and [nalgebra] matrices and vectors.
+//!
+//! Requires the `nalgebra` feature. Conversions apply to arrays of any element type, including
+//! [Dual](crate::dual::Dual) and [Dual2](crate::dual::Dual2), which support the arithmetic
+//! required by [nalgebra] for matrix products. Owned conversions move the data without copying
+//! when the memory layouts agree, i.e. from an array in column-major (Fortran) order, and
+//! always in the direction from [nalgebra] to [ndarray].
+
+use nalgebra::{DMatrix, DMatrixSlice, DVector, Dynamic, Scalar};
+use ndarray::{Array1, Array2, ArrayView2, ShapeBuilder};
+
+/// Convert an owned 2-d array into a matrix, without copying if it is in column-major order.
+pub fn array2_into_dmatrix<T: Scalar>(a: Array2<T>) -> DMatrix<T> {
+    let (nrows, ncols) = a.dim();
+    if a.t().is_standard_layout() {
+        let (data, _) = a.into_raw_vec_and_offset();
+        DMatrix::from_vec(nrows, ncols, data)
+    } else {
+        DMatrix::from_iterator(nrows, ncols, a.t().iter().cloned())
+    }
+}
+
+/// Convert an owned matrix into a 2-d array in column-major order, without copying.
+pub fn dmatrix_into_array2<T: Scalar>(m: DMatrix<T>) -> Array2<T> {
+    let shape = m.shape();
+    let data: Vec<T> = m.data.into();
+    Array2::from_shape_vec(shape.f(), data).expect("matrix data matches its shape")
+}
+
+/// Return a matrix view of the data of a 2-d array, without copying.
+///
+/// Returns `None` if the array is not contiguous in memory or has a negative stride, in which
+/// case it must be converted with [array2_into_dmatrix].
+pub fn array2_as_dmatrix_view<'a, T: Scalar>(
+    a: &ArrayView2<'a, T>,
+) -> Option<DMatrixSlice<'a, T, Dynamic, Dynamic>> {
+    let (nrows, ncols) = a.dim();
+    let strides = a.strides();
+    if strides.iter().any(|s| *s < 0) || nrows == 0 || ncols == 0 {
+        return None;
+    }
+    let data = a.to_slice_memory_order()?;
+    Some(DMatrixSlice::from_slice_with_strides_generic(
+        data,
+        Dynamic::new(nrows),
+        Dynamic::new(ncols),
+        Dynamic::new(strides[0] as usize),
+        Dynamic::new(strides[1] as usize),
+    ))
+}
+
+/// Convert an owned 1-d array into a vector, without copying if it is contiguous.
+pub fn array1_into_dvector<T: Scalar>(a: Array1<T>) -> DVector<T> {
+    if a.is_standard_layout() {
+        let (data, _) = a.into_raw_vec_and_offset();
+        DVector::from_vec(data)
+    } else {
+        DVector::from_iterator(a.len(), a.iter().cloned())
+    }
+}
+
+/// Convert an owned vector into a 1-d array, without copying.
+pub fn dvector_into_array1<T: Scalar>(v: DVector<T>) -> Array1<T> {
+    let data: Vec<T> = v.data.into();
+    Array1::from_vec(data)
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dual::Dual;
+    use ndarray::{arr1, arr2};
+
+    #[test]
+    fn test_round_trip_row_and_column_major() {
+        let a = arr2(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let m = array2_into_dmatrix(a.clone());
+        assert_eq!(m[(1, 2)], 6.0);
+        assert_eq!(dmatrix_into_array2(m), a);
+
+        let f = Array2::from_shape_vec((2, 3).f(), vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]).unwrap();
+        assert_eq!(f, a);
+        let ptr = f.as_ptr();
+        let m = array2_into_dmatrix(f);
+        assert_eq!(m.as_ptr(), ptr);
+        assert_eq!(m[(0, 1)], 2.0);
+    }
+
+    #[test]
+    fn test_view_without_copy() {
+        let a = arr2(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let view = array2_as_dmatrix_view(&a.view()).unwrap();
+        assert_eq!(view.as_ptr(), a.as_ptr());
+        assert_eq!(view[(1, 0)], 4.0);
+        assert_eq!(view.transpose()[(2, 1)], 6.0);
+        assert!(array2_as_dmatrix_view(&a.slice(ndarray::s![.., ..;2])).is_none());
+    }
+
+    #[test]
+    fn test_dual_matrix_product() {
+        let x = Dual::new(2.0, vec!["x".to_string()]);
+        let a = array2_into_dmatrix(arr2(&[
+            [x.clone(), Dual::new(1.0, vec![])],
+            [Dual::new(0.0, vec![]), x.clone()],
+        ]));
+        let v = array1_into_dvector(arr1(&[x.clone(), Dual::new(3.0, vec![])]));
+        let product = dvector_into_array1(a * v);
+        // [x^2 + 3, 3x]
+        assert_eq!(product[0], &x * &x + 3.0);
+        assert_eq!(product[1], &x * 3.0);
+    }
+}
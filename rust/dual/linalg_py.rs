@@ -1,11 +1,14 @@
 //! Wrapper module to export Rust linalg operations to Python using pyo3 bindings.
 
 use crate::dual::dual::{Dual, Dual2};
-use crate::dual::linalg::{dsolve, fdsolve};
-use ndarray::{Array1, ArrayView2};
+use crate::dual::enums::Number;
+use crate::dual::linalg::{dfmul21_, dmul11_, dsolve, fdmul21_, fdsolve};
+use crate::splines::check_number_types;
+use ndarray::{Array1, Array2, ArrayView2};
 use num_traits::identities::Zero;
 use num_traits::Signed;
-use numpy::{PyArray2, PyArrayMethods};
+use numpy::{PyArray1, PyArray2, PyArrayMethods};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use std::cmp::PartialOrd;
 use std::iter::Sum;
@@ -86,3 +89,98 @@ pub fn fdsolve2_py(
 ) -> PyResult<Vec<Dual2>> {
     unsafe { Ok(fdsolve_py(a.as_array(), b, allow_lsq)) }
 }
+
+/// Matrix multiplication of a 2d-array of floats with a 1d-array of dual numbers.
+///
+/// Parameters
+/// ----------
+/// a: ndarray[float] of shape (m, n)
+///     The matrix of floats.
+/// b: list[float | Dual | Dual2] of length n
+///     The vector of dual numbers. Must not mix *Dual* and *Dual2*.
+///
+/// Returns
+/// -------
+/// list[float | Dual | Dual2]
+///
+/// Raises
+/// ------
+/// TypeError
+///     If ``b`` mixes *Dual* and *Dual2*.
+#[pyfunction]
+#[pyo3(name = "fdmul")]
+pub fn fdmul_py(a: &Bound<'_, PyArray2<f64>>, b: Vec<Number>) -> PyResult<Vec<Number>> {
+    check_number_types(b.iter())?;
+    let a_ = a.to_owned_array();
+    if a_.ncols() != b.len() {
+        return Err(PyValueError::new_err(
+            "`a` must have as many columns as the length of `b`.",
+        ));
+    }
+    let b_ = Array1::from_vec(b);
+    Ok(fdmul21_(&a_.view(), &b_.view()).to_vec())
+}
+
+/// Matrix multiplication of a 2d-array of dual numbers with a 1d-array of floats.
+///
+/// Parameters
+/// ----------
+/// a: list[list[float | Dual | Dual2]] of shape (m, n)
+///     The matrix of dual numbers, by rows. Must not mix *Dual* and *Dual2*.
+/// b: ndarray[float] of length n
+///     The vector of floats.
+///
+/// Returns
+/// -------
+/// list[float | Dual | Dual2]
+///
+/// Raises
+/// ------
+/// TypeError
+///     If ``a`` mixes *Dual* and *Dual2*.
+#[pyfunction]
+#[pyo3(name = "dfmul")]
+pub fn dfmul_py(a: Vec<Vec<Number>>, b: &Bound<'_, PyArray1<f64>>) -> PyResult<Vec<Number>> {
+    check_number_types(a.iter().flatten())?;
+    let b_ = b.to_owned_array();
+    let n = b_.len();
+    if a.iter().any(|row| row.len() != n) {
+        return Err(PyValueError::new_err(
+            "Every row of `a` must have the length of `b`.",
+        ));
+    }
+    let m = a.len();
+    let a_ = Array2::from_shape_vec((m, n), a.into_iter().flatten().collect())
+        .expect("Dim are pre-checked");
+    Ok(dfmul21_(&a_.view(), &b_.view()).to_vec())
+}
+
+/// Inner product of two 1d-arrays of dual numbers.
+///
+/// Parameters
+/// ----------
+/// a: list[float | Dual | Dual2]
+///     The first vector.
+/// b: list[float | Dual | Dual2]
+///     The second vector, of the same length. Must not mix *Dual* and *Dual2* with ``a``.
+///
+/// Returns
+/// -------
+/// float, Dual or Dual2
+///
+/// Raises
+/// ------
+/// TypeError
+///     If ``a`` and ``b`` mix *Dual* and *Dual2*.
+#[pyfunction]
+#[pyo3(name = "ddot")]
+pub fn ddot_py(a: Vec<Number>, b: Vec<Number>) -> PyResult<Number> {
+    check_number_types(a.iter().chain(b.iter()))?;
+    if a.len() != b.len() {
+        return Err(PyValueError::new_err(
+            "`a` and `b` must have the same length.",
+        ));
+    }
+    let (a_, b_) = (Array1::from_vec(a), Array1::from_vec(b));
+    Ok(dmul11_(&a_.view(), &b_.view()))
+}
@@ -0,0 +1,289 @@
+//! Ordered maps of keys to [Number] with arithmetic broadcast over keys.
+
+use crate::dual::{DualError, Number};
+use chrono::NaiveDateTime;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::hash::Hash;
+use std::ops::{Add, Div, Index, Mul, Neg, Sub};
+
+/// An ordered map of keys to [Number], e.g. node values by date or risks by tag.
+///
+/// Addition and subtraction of two maps operate over the union of their keys, in the order of
+/// the keys of the left map followed by the keys only in the right map, where a missing key has
+/// a value of zero. Multiplication and division by an [f64] or a [Number] apply to each value.
+///
+/// The operators panic if they mix [Dual](crate::dual::Dual) and [Dual2](crate::dual::Dual2)
+/// values, as with [Number], whereas the `try_` methods return a [DualError].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "K: Serialize + Hash + Eq",
+    deserialize = "K: Deserialize<'de> + Hash + Eq"
+))]
+pub struct NumberMap<K: Hash + Eq> {
+    #[serde(with = "indexmap::map::serde_seq")]
+    values: IndexMap<K, Number>,
+}
+
+/// A [NumberMap] keyed by string, such as a variable tag or an instrument label.
+pub type StringMap = NumberMap<String>;
+
+/// A [NumberMap] keyed by date.
+pub type DateMap = NumberMap<NaiveDateTime>;
+
+impl<K: Hash + Eq> Default for NumberMap<K> {
+    fn default() -> Self {
+        Self {
+            values: IndexMap::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone> NumberMap<K> {
+    /// Create an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a value, replacing and returning any existing value of the key.
+    pub fn insert(&mut self, key: K, value: Number) -> Option<Number> {
+        self.values.insert(key, value)
+    }
+
+    /// Return the value of a key.
+    pub fn get(&self, key: &K) -> Option<&Number> {
+        self.values.get(key)
+    }
+
+    /// Return the keys, in order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.values.keys()
+    }
+
+    /// Return the pairs of keys and values, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &Number)> {
+        self.values.iter()
+    }
+
+    /// The number of keys.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether there are no keys.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Return the sum of the values, which is zero if the map is empty.
+    pub fn total(&self) -> Number {
+        self.try_total().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Return the sum of the values, or an error if they mix dual types.
+    pub fn try_total(&self) -> Result<Number, DualError> {
+        self.values
+            .values()
+            .try_fold(Number::F64(0.0), |acc, v| acc.try_add(v))
+    }
+
+    /// Return a map of the same keys with a function applied to each value.
+    pub fn map_values<F: Fn(&Number) -> Number>(&self, f: F) -> Self {
+        Self {
+            values: self.values.iter().map(|(k, v)| (k.clone(), f(v))).collect(),
+        }
+    }
+
+    /// Add `sign` times each value of `other` to the value of its key, inserting missing keys.
+    fn try_merge(&self, other: &Self, sign: f64) -> Result<Self, DualError> {
+        let mut values = self.values.clone();
+        for (k, v) in other.values.iter() {
+            match values.get_mut(k) {
+                Some(existing) => *existing = existing.try_add(&(v * sign))?,
+                None => {
+                    values.insert(k.clone(), v * sign);
+                }
+            }
+        }
+        Ok(Self { values })
+    }
+
+    /// Return the sum of two maps over the union of their keys, or an error if their values
+    /// mix dual types.
+    pub fn try_add(&self, other: &Self) -> Result<Self, DualError> {
+        self.try_merge(other, 1.0)
+    }
+
+    /// Return the difference of two maps over the union of their keys, or an error if their
+    /// values mix dual types.
+    pub fn try_sub(&self, other: &Self) -> Result<Self, DualError> {
+        self.try_merge(other, -1.0)
+    }
+
+    /// Return the map with each value multiplied by a [Number], or an error if it mixes dual
+    /// types with the values.
+    pub fn try_mul(&self, other: &Number) -> Result<Self, DualError> {
+        self.try_map_values(|v| v.try_mul(other))
+    }
+
+    /// Return the map with each value divided by a [Number], or an error if it mixes dual types
+    /// with the values.
+    pub fn try_div(&self, other: &Number) -> Result<Self, DualError> {
+        self.try_map_values(|v| v.try_div(other))
+    }
+
+    fn try_map_values<F>(&self, f: F) -> Result<Self, DualError>
+    where
+        F: Fn(&Number) -> Result<Number, DualError>,
+    {
+        let values = self
+            .values
+            .iter()
+            .map(|(k, v)| Ok((k.clone(), f(v)?)))
+            .collect::<Result<IndexMap<K, Number>, DualError>>()?;
+        Ok(Self { values })
+    }
+
+    /// Return the underlying [IndexMap].
+    pub fn into_inner(self) -> IndexMap<K, Number> {
+        self.values
+    }
+}
+
+impl<K: Hash + Eq> From<IndexMap<K, Number>> for NumberMap<K> {
+    fn from(values: IndexMap<K, Number>) -> Self {
+        Self { values }
+    }
+}
+
+impl<K: Hash + Eq> FromIterator<(K, Number)> for NumberMap<K> {
+    fn from_iter<I: IntoIterator<Item = (K, Number)>>(iter: I) -> Self {
+        Self {
+            values: IndexMap::from_iter(iter),
+        }
+    }
+}
+
+impl<K: Hash + Eq> Index<&K> for NumberMap<K> {
+    type Output = Number;
+    fn index(&self, key: &K) -> &Number {
+        &self.values[key]
+    }
+}
+
+impl<K: Hash + Eq + Clone> Add<&NumberMap<K>> for &NumberMap<K> {
+    type Output = NumberMap<K>;
+    fn add(self, other: &NumberMap<K>) -> NumberMap<K> {
+        self.try_add(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<K: Hash + Eq + Clone> Sub<&NumberMap<K>> for &NumberMap<K> {
+    type Output = NumberMap<K>;
+    fn sub(self, other: &NumberMap<K>) -> NumberMap<K> {
+        self.try_sub(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<K: Hash + Eq + Clone> Neg for &NumberMap<K> {
+    type Output = NumberMap<K>;
+    fn neg(self) -> NumberMap<K> {
+        self.map_values(|v| -v)
+    }
+}
+
+impl<K: Hash + Eq + Clone> Mul<f64> for &NumberMap<K> {
+    type Output = NumberMap<K>;
+    fn mul(self, other: f64) -> NumberMap<K> {
+        self.map_values(|v| v * other)
+    }
+}
+
+impl<K: Hash + Eq + Clone> Mul<&Number> for &NumberMap<K> {
+    type Output = NumberMap<K>;
+    fn mul(self, other: &Number) -> NumberMap<K> {
+        self.try_mul(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<K: Hash + Eq + Clone> Div<f64> for &NumberMap<K> {
+    type Output = NumberMap<K>;
+    fn div(self, other: f64) -> NumberMap<K> {
+        self.map_values(|v| v / other)
+    }
+}
+
+impl<K: Hash + Eq + Clone> Div<&Number> for &NumberMap<K> {
+    type Output = NumberMap<K>;
+    fn div(self, other: &Number) -> NumberMap<K> {
+        self.try_div(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::ndt;
+    use crate::dual::{Dual, Dual2};
+
+    fn map(pairs: &[(&str, f64)]) -> StringMap {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), Number::F64(*v)))
+            .collect()
+    }
+
+    #[test]
+    fn test_add_sub_key_union() {
+        let a = map(&[("x", 1.0), ("y", 2.0)]);
+        let b = map(&[("z", 5.0), ("y", 3.0)]);
+        assert_eq!(&a + &b, map(&[("x", 1.0), ("y", 5.0), ("z", 5.0)]));
+        assert_eq!(&a - &b, map(&[("x", 1.0), ("y", -1.0), ("z", -5.0)]));
+        assert_eq!(
+            (&a + &b).keys().cloned().collect::<Vec<_>>(),
+            vec!["x", "y", "z"]
+        );
+        assert_eq!(&a - &a, map(&[("x", 0.0), ("y", 0.0)]));
+    }
+
+    #[test]
+    fn test_scalar_ops_and_total() {
+        let a = map(&[("x", 1.0), ("y", 2.0)]);
+        assert_eq!(&a * 2.0, map(&[("x", 2.0), ("y", 4.0)]));
+        assert_eq!(&a / 2.0, map(&[("x", 0.5), ("y", 1.0)]));
+        assert_eq!(-&a, map(&[("x", -1.0), ("y", -2.0)]));
+        assert_eq!(a.total(), Number::F64(3.0));
+        assert_eq!(StringMap::new().total(), Number::F64(0.0));
+    }
+
+    #[test]
+    fn test_dual_values_by_date() {
+        let d = Number::Dual(Dual::new(2.0, vec!["v".to_string()]));
+        let mut a = DateMap::new();
+        a.insert(ndt(2024, 1, 1), Number::F64(1.0));
+        a.insert(ndt(2025, 1, 1), d.clone());
+        let scaled = &a * &d;
+        assert_eq!(scaled[&ndt(2024, 1, 1)], d);
+        assert_eq!(scaled[&ndt(2025, 1, 1)], &d * &d);
+        assert_eq!(scaled.total(), &d + &(&d * &d));
+    }
+
+    #[test]
+    fn test_mixed_dual_values_error() {
+        let d = Number::Dual(Dual::new(2.0, vec!["v".to_string()]));
+        let d2 = Number::Dual2(Dual2::new(2.0, vec!["w".to_string()]));
+        let a: StringMap = [("x".to_string(), d.clone())].into_iter().collect();
+        let b: StringMap = [("x".to_string(), d2.clone())].into_iter().collect();
+        assert!(matches!(
+            a.try_add(&b),
+            Err(DualError::MixedTypes { op: "+", .. })
+        ));
+        assert!(a.try_sub(&b).is_err());
+        assert!(a.try_mul(&d2).is_err() && a.try_div(&d2).is_err());
+        // disjoint keys do not combine values, but their total does
+        let c: StringMap = [("y".to_string(), d2)].into_iter().collect();
+        assert!(a.try_add(&c).unwrap().try_total().is_err());
+        assert_eq!(a.try_add(&a).unwrap(), &a + &a);
+    }
+}
@@ -22,20 +22,43 @@ pub use crate::dual::dual::{
     VarsRelationship,
 };
 
+mod errors;
+pub use crate::dual::errors::DualError;
+
 mod dual_ops;
+pub use crate::dual::dual_ops::combine::combine;
+pub use crate::dual::dual_ops::sum::sum_pairwise;
 pub(crate) mod dual_py;
+pub(crate) mod order_py;
 
 pub mod linalg;
+
+mod buffer;
+pub use crate::dual::buffer::DualBuffer;
+pub(crate) mod buffer_py;
+
+mod map;
+pub use crate::dual::map::{DateMap, NumberMap, StringMap};
 pub(crate) mod linalg_py;
 
+pub mod reverse;
+pub(crate) mod reverse_py;
+
 mod enums;
 pub use crate::dual::enums::{
     ADOrder, Number, NumberArray1, NumberArray2, NumberMapping, NumberPPSpline, NumberVec,
 };
 
-/// Utility for creating an ordered list of variable tags from a string and enumerator
-pub(crate) fn get_variable_tags(name: &str, range: usize) -> Vec<String> {
-    Vec::from_iter((0..range).map(|i| name.to_string() + &i.to_string()))
+mod tags;
+pub(crate) use crate::dual::tags::{
+    get_tag_template_py, get_variable_tags_py, set_tag_template_py,
+};
+pub use crate::dual::tags::{set_tag_template, tag_template, TagTemplate};
+
+/// Utility for creating an ordered list of variable tags from a string and enumerator, with the
+/// shared [TagTemplate].
+pub fn get_variable_tags(name: &str, range: usize) -> Vec<String> {
+    tag_template().tags(name, range)
 }
 
 #[cfg(test)]
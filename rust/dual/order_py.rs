@@ -0,0 +1,131 @@
+//! Wrapper module to export AD order conversion of values and objects to Python.
+
+use crate::curves::curve_py::Curve;
+use crate::dual::dual::{Dual, Dual2};
+use crate::dual::enums::{ADOrder, Number};
+use crate::fx::rates::FXRates;
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::PyInt;
+
+/// Extract an AD order given as an `ADOrder` or as an int, 0, 1 or 2.
+pub(crate) fn extract_adorder(obj: &Bound<'_, PyAny>) -> PyResult<ADOrder> {
+    match obj.downcast::<PyInt>() {
+        Ok(i) => ADOrder::try_from_int(i.extract::<u8>().unwrap_or(u8::MAX)),
+        Err(_) => obj.extract::<ADOrder>(),
+    }
+}
+
+/// Return the AD order of a value or an object.
+///
+/// Parameters
+/// ----------
+/// obj: float, Dual, Dual2, Curve, FXRates or object with an ``ad`` attribute
+///     The value or object, e.g. a curve or a volatility smile.
+///
+/// Returns
+/// -------
+/// ADOrder
+#[pyfunction]
+#[pyo3(name = "order_of")]
+pub fn order_of_py(obj: &Bound<'_, PyAny>) -> PyResult<ADOrder> {
+    if let Ok(curve) = obj.downcast::<Curve>() {
+        return Ok(curve.borrow().inner.ad());
+    }
+    if let Ok(fxr) = obj.downcast::<FXRates>() {
+        return Ok(fxr.borrow().ad());
+    }
+    if let Ok(value) = obj.extract::<Number>() {
+        return Ok(ADOrder::of(&value));
+    }
+    match obj.getattr("ad") {
+        Ok(ad) => extract_adorder(&ad),
+        Err(_) => Err(PyTypeError::new_err(format!(
+            "Cannot determine the AD order of '{}'.",
+            obj.get_type().name()?
+        ))),
+    }
+}
+
+/// Set the AD order of a value or an object.
+///
+/// Parameters
+/// ----------
+/// obj: float, Dual, Dual2, Curve, FXRates or object with a ``_set_ad_order`` method
+///     The value or object, e.g. a curve or a volatility smile.
+/// order: ADOrder or int
+///     The AD order to set.
+///
+/// Returns
+/// -------
+/// float, Dual, Dual2 or object
+///
+/// Notes
+/// -----
+/// Values are returned converted, as with :meth:`~rateslib.dual.set_order`, so floats are only
+/// changed for order zero, having no variables to tag. Objects are mutated and returned.
+#[pyfunction]
+#[pyo3(name = "set_order")]
+pub fn set_order_py(
+    obj: &Bound<'_, PyAny>,
+    #[pyo3(from_py_with = "extract_adorder")] order: ADOrder,
+) -> PyResult<PyObject> {
+    let py = obj.py();
+    if let Ok(curve) = obj.downcast::<Curve>() {
+        curve.borrow_mut().inner.set_ad_order(order)?;
+        return Ok(obj.clone().unbind());
+    }
+    if let Ok(fxr) = obj.downcast::<FXRates>() {
+        fxr.borrow_mut().set_ad_order(order)?;
+        return Ok(obj.clone().unbind());
+    }
+    if let Ok(value) = obj.extract::<Number>() {
+        return Ok(match (value, order) {
+            (Number::F64(f), _) => Number::F64(f),
+            (value, ADOrder::Zero) => Number::F64(f64::from(value)),
+            (value, ADOrder::One) => Number::Dual(Dual::from(value)),
+            (value, ADOrder::Two) => Number::Dual2(Dual2::from(value)),
+        }
+        .into_py(py));
+    }
+    if obj.hasattr("_set_ad_order")? {
+        obj.call_method1("_set_ad_order", (u8::from(order),))?;
+        return Ok(obj.clone().unbind());
+    }
+    Err(PyTypeError::new_err(format!(
+        "Cannot set the AD order of '{}'.",
+        obj.get_type().name()?
+    )))
+}
+
+/// Convert a value to a :class:`~rateslib.dual.Dual2`.
+///
+/// Parameters
+/// ----------
+/// x: float, Dual or Dual2
+///     The value. A float has no variables.
+///
+/// Returns
+/// -------
+/// Dual2
+#[pyfunction]
+#[pyo3(name = "to_dual2")]
+pub fn to_dual2_py(x: Number) -> Dual2 {
+    Dual2::from(x)
+}
+
+/// Convert a value to a float, discarding any derivatives.
+///
+/// Parameters
+/// ----------
+/// x: float, Dual or Dual2
+///     The value.
+///
+/// Returns
+/// -------
+/// float
+#[pyfunction]
+#[pyo3(name = "to_f64")]
+pub fn to_f64_py(x: Number) -> f64 {
+    f64::from(x)
+}
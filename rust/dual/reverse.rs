@@ -0,0 +1,281 @@
+//! Reverse mode automatic differentiation (AD) by recording operations on a tape.
+//!
+//! The cost of forward mode AD with [Dual] scales with the number of variables, since every
+//! intermediate value carries a gradient. A [Tape] instead records each operation with the
+//! partial derivatives to its operands, and a single backward pass accumulates the gradient of a
+//! scalar output to every recorded variable, at a cost proportional to the length of the tape.
+//!
+//! Gradients are identical to those of forward mode and can be converted to and from [Dual].
+
+use crate::dual::{Dual, Vars};
+use auto_ops::{impl_op_ex, impl_op_ex_commutative};
+use indexmap::IndexMap;
+use pyo3::{pyclass, PyErr};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// The operands of each recorded node, with the partial derivative to each.
+type Nodes = Vec<Vec<(usize, f64)>>;
+
+/// A record of operations, each with the partial derivatives to its operands.
+#[pyclass(module = "rateslib.rs", unsendable)]
+#[derive(Clone, Debug, Default)]
+pub struct Tape {
+    nodes: Rc<RefCell<Nodes>>,
+}
+
+/// A scalar value recorded on a [Tape].
+#[pyclass(module = "rateslib.rs", unsendable)]
+#[derive(Clone, Debug)]
+pub struct TapeVar {
+    tape: Tape,
+    index: usize,
+    value: f64,
+}
+
+/// The adjoints of every node of a [Tape], from a backward pass of an output.
+#[derive(Clone, Debug)]
+pub struct Adjoints(Vec<f64>);
+
+impl Adjoints {
+    /// The derivative of the output to a recorded value.
+    pub fn wrt(&self, var: &TapeVar) -> f64 {
+        self.0[var.index]
+    }
+}
+
+impl Tape {
+    pub fn new() -> Self {
+        Tape::default()
+    }
+
+    /// The number of recorded nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.borrow().len()
+    }
+
+    /// Whether the tape has no recorded nodes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether a variable is recorded on this tape.
+    pub fn records(&self, var: &TapeVar) -> bool {
+        Rc::ptr_eq(&self.nodes, &var.tape.nodes)
+    }
+
+    fn push(&self, value: f64, parents: Vec<(usize, f64)>) -> TapeVar {
+        let mut nodes = self.nodes.borrow_mut();
+        nodes.push(parents);
+        TapeVar {
+            tape: self.clone(),
+            index: nodes.len() - 1,
+            value,
+        }
+    }
+
+    /// Record an independent variable.
+    pub fn variable(&self, value: f64) -> TapeVar {
+        self.push(value, vec![])
+    }
+
+    /// Record the value of a [Dual] as a linear function of recorded variables.
+    ///
+    /// `leaves` maps the variables of the dual to recorded values. Variables of the dual without
+    /// a leaf are ignored.
+    pub fn record_dual(&self, dual: &Dual, leaves: &IndexMap<String, TapeVar>) -> TapeVar {
+        let parents = dual
+            .vars()
+            .iter()
+            .zip(dual.dual.iter())
+            .filter_map(|(v, g)| leaves.get(v).map(|leaf| (leaf.index, *g)))
+            .collect();
+        self.push(dual.real, parents)
+    }
+
+    /// Record a function of independent variables with the given values, and return its value
+    /// and gradient.
+    pub fn record<F>(values: &[f64], f: F) -> (f64, Vec<f64>)
+    where
+        F: FnOnce(&[TapeVar]) -> TapeVar,
+    {
+        let tape = Tape::new();
+        let inputs: Vec<TapeVar> = values.iter().map(|v| tape.variable(*v)).collect();
+        let output = f(&inputs);
+        let adjoints = output.backward();
+        (
+            output.value,
+            inputs.iter().map(|x| adjoints.wrt(x)).collect(),
+        )
+    }
+}
+
+impl TapeVar {
+    /// The value of the variable.
+    pub fn real(&self) -> f64 {
+        self.value
+    }
+
+    /// Whether two variables are recorded on the same tape.
+    pub fn same_tape(&self, other: &TapeVar) -> bool {
+        self.tape.records(other)
+    }
+
+    fn unary(&self, value: f64, partial: f64) -> TapeVar {
+        self.tape.push(value, vec![(self.index, partial)])
+    }
+
+    fn binary(&self, other: &TapeVar, value: f64, partials: (f64, f64)) -> TapeVar {
+        assert!(
+            self.same_tape(other),
+            "Operations require variables recorded on the same tape."
+        );
+        self.tape.push(
+            value,
+            vec![(self.index, partials.0), (other.index, partials.1)],
+        )
+    }
+
+    /// Accumulate the derivative of this variable to every node recorded before it.
+    pub fn backward(&self) -> Adjoints {
+        let nodes = self.tape.nodes.borrow();
+        let mut adjoints = vec![0.0; nodes.len()];
+        adjoints[self.index] = 1.0;
+        for i in (0..=self.index).rev() {
+            let a = adjoints[i];
+            if a == 0.0 {
+                continue;
+            }
+            for (parent, partial) in nodes[i].iter() {
+                adjoints[*parent] += a * partial;
+            }
+        }
+        Adjoints(adjoints)
+    }
+
+    /// Convert the variable to a [Dual], with gradients to the recorded values `wrt` labelled by
+    /// `vars`.
+    pub fn to_dual(&self, wrt: &[TapeVar], vars: Vec<String>) -> Result<Dual, PyErr> {
+        let adjoints = self.backward();
+        let gradient: Vec<f64> = wrt.iter().map(|x| adjoints.wrt(x)).collect();
+        Ok(Dual::try_new(self.value, vars, gradient)?)
+    }
+
+    pub fn exp(&self) -> TapeVar {
+        let value = self.value.exp();
+        self.unary(value, value)
+    }
+
+    pub fn log(&self) -> TapeVar {
+        self.unary(self.value.ln(), 1.0 / self.value)
+    }
+
+    pub fn sqrt(&self) -> TapeVar {
+        let value = self.value.sqrt();
+        self.unary(value, 0.5 / value)
+    }
+
+    pub fn powf(&self, power: f64) -> TapeVar {
+        self.unary(self.value.powf(power), power * self.value.powf(power - 1.0))
+    }
+}
+
+impl_op_ex!(-|a: &TapeVar| -> TapeVar { a.unary(-a.value, -1.0) });
+
+impl_op_ex!(+|a: &TapeVar, b: &TapeVar| -> TapeVar { a.binary(b, a.value + b.value, (1.0, 1.0)) });
+impl_op_ex!(-|a: &TapeVar, b: &TapeVar| -> TapeVar { a.binary(b, a.value - b.value, (1.0, -1.0)) });
+impl_op_ex!(*|a: &TapeVar, b: &TapeVar| -> TapeVar {
+    a.binary(b, a.value * b.value, (b.value, a.value))
+});
+impl_op_ex!(/|a: &TapeVar, b: &TapeVar| -> TapeVar {
+    a.binary(
+        b,
+        a.value / b.value,
+        (1.0 / b.value, -a.value / (b.value * b.value)),
+    )
+});
+
+impl_op_ex_commutative!(+|a: &TapeVar, b: &f64| -> TapeVar { a.unary(a.value + b, 1.0) });
+impl_op_ex!(-|a: &TapeVar, b: &f64| -> TapeVar { a.unary(a.value - b, 1.0) });
+impl_op_ex!(-|a: &f64, b: &TapeVar| -> TapeVar { b.unary(a - b.value, -1.0) });
+impl_op_ex_commutative!(*|a: &TapeVar, b: &f64| -> TapeVar { a.unary(a.value * b, *b) });
+impl_op_ex!(/|a: &TapeVar, b: &f64| -> TapeVar { a.unary(a.value / b, 1.0 / b) });
+impl_op_ex!(/|a: &f64, b: &TapeVar| -> TapeVar {
+    b.unary(a / b.value, -a / (b.value * b.value))
+});
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dual::{Gradient1, MathFuncs};
+    use num_traits::Pow;
+
+    fn f_tape(x: &[TapeVar]) -> TapeVar {
+        let mut total = (&x[0] * &x[1]).exp() / (&x[2] + 1.0);
+        for xi in x.iter() {
+            total = total + xi.powf(2.0) - 2.0 * xi.sqrt().log();
+        }
+        total
+    }
+
+    fn f_dual(x: &[Dual]) -> Dual {
+        let mut total = (&x[0] * &x[1]).exp() / (&x[2] + 1.0);
+        for xi in x.iter() {
+            total = total + xi.clone().pow(2.0) - 2.0 * xi.clone().pow(0.5).log();
+        }
+        total
+    }
+
+    #[test]
+    fn test_record_matches_forward_mode() {
+        let values: Vec<f64> = (0..50).map(|i| 0.5 + i as f64 / 100.0).collect();
+        let (value, gradient) = Tape::record(&values, f_tape);
+
+        let vars: Vec<String> = (0..50).map(|i| format!("x{}", i)).collect();
+        let x: Vec<Dual> = values
+            .iter()
+            .zip(vars.iter())
+            .map(|(v, n)| Dual::new(*v, vec![n.clone()]))
+            .collect();
+        let expected = f_dual(&x);
+        assert!((value - expected.real).abs() < 1e-12);
+        let expected_gradient = expected.gradient1(vars);
+        for (g, e) in gradient.iter().zip(expected_gradient.iter()) {
+            assert!((g - e).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_dual_conversion_roundtrip() {
+        let tape = Tape::new();
+        let (x, y) = (tape.variable(2.0), tape.variable(3.0));
+        let z = &x * &y + x.exp();
+        let dual = z
+            .to_dual(
+                &[x.clone(), y.clone()],
+                vec!["x".to_string(), "y".to_string()],
+            )
+            .unwrap();
+        assert_eq!(dual.real, 6.0 + 2.0_f64.exp());
+        assert_eq!(
+            dual.gradient1(vec!["x".to_string(), "y".to_string()])
+                .to_vec(),
+            vec![3.0 + 2.0_f64.exp(), 2.0]
+        );
+
+        // chain a dual result back onto the tape and differentiate through it
+        let leaves = IndexMap::from([("x".to_string(), x.clone()), ("y".to_string(), y.clone())]);
+        let w = tape.record_dual(&dual, &leaves) * 2.0;
+        let adjoints = w.backward();
+        assert_eq!(adjoints.wrt(&x), 2.0 * (3.0 + 2.0_f64.exp()));
+        assert_eq!(adjoints.wrt(&y), 4.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_different_tapes_panic() {
+        let (a, b) = (Tape::new(), Tape::new());
+        let _ = a.variable(1.0) + b.variable(1.0);
+    }
+}
@@ -0,0 +1,221 @@
+//! Wrapper module to export the reverse mode AD tape to Python using pyo3 bindings.
+
+use crate::dual::dual::Dual;
+use crate::dual::errors::DualError;
+use crate::dual::reverse::{Tape, TapeVar};
+use indexmap::IndexMap;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+
+/// An operand of arithmetic with a [TapeVar].
+#[derive(FromPyObject)]
+pub(crate) enum TapeOperand {
+    Var(TapeVar),
+    F64(f64),
+}
+
+impl TapeVar {
+    fn check_tape(&self, other: &TapeVar) -> PyResult<()> {
+        if self.same_tape(other) {
+            Ok(())
+        } else {
+            Err(PyValueError::new_err(
+                "Operations require variables recorded on the same tape.",
+            ))
+        }
+    }
+}
+
+#[pymethods]
+impl Tape {
+    #[new]
+    fn new_py() -> Self {
+        Tape::new()
+    }
+
+    /// Record an independent variable.
+    ///
+    /// Parameters
+    /// ----------
+    /// value: float
+    ///     The value of the variable.
+    ///
+    /// Returns
+    /// -------
+    /// TapeVar
+    #[pyo3(name = "variable")]
+    fn variable_py(&self, value: f64) -> TapeVar {
+        self.variable(value)
+    }
+
+    /// Record the value of a *Dual* as a linear function of recorded variables.
+    ///
+    /// Parameters
+    /// ----------
+    /// dual: Dual
+    ///     The dual number to record.
+    /// leaves: dict[str, TapeVar]
+    ///     The recorded values of the variables of the dual. Variables without a leaf are ignored.
+    ///
+    /// Returns
+    /// -------
+    /// TapeVar
+    #[pyo3(name = "from_dual")]
+    fn record_dual_py(&self, dual: Dual, leaves: IndexMap<String, TapeVar>) -> PyResult<TapeVar> {
+        for leaf in leaves.values() {
+            if !self.records(leaf) {
+                return Err(PyValueError::new_err(
+                    "`leaves` must be variables recorded on this tape.",
+                ));
+            }
+        }
+        Ok(self.record_dual(&dual, &leaves))
+    }
+
+    fn __len__(&self) -> usize {
+        self.len()
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("<Tape: {} nodes>", self.len()))
+    }
+}
+
+#[pymethods]
+impl TapeVar {
+    #[getter(real)]
+    fn real_py(&self) -> f64 {
+        self.real()
+    }
+
+    /// Return the derivatives of this variable to recorded values.
+    ///
+    /// Parameters
+    /// ----------
+    /// wrt: list[TapeVar]
+    ///     The recorded values, on the same tape, to differentiate with respect to.
+    ///
+    /// Returns
+    /// -------
+    /// list[float]
+    #[pyo3(name = "backward")]
+    fn backward_py(&self, wrt: Vec<TapeVar>) -> PyResult<Vec<f64>> {
+        for x in wrt.iter() {
+            self.check_tape(x)?;
+        }
+        let adjoints = self.backward();
+        Ok(wrt.iter().map(|x| adjoints.wrt(x)).collect())
+    }
+
+    /// Convert this variable to a *Dual*.
+    ///
+    /// Parameters
+    /// ----------
+    /// wrt: list[TapeVar]
+    ///     The recorded values, on the same tape, to differentiate with respect to.
+    /// vars: list[str]
+    ///     The variable tags of each of ``wrt``.
+    ///
+    /// Returns
+    /// -------
+    /// Dual
+    #[pyo3(name = "to_dual")]
+    fn to_dual_py(&self, wrt: Vec<TapeVar>, vars: Vec<String>) -> PyResult<Dual> {
+        for x in wrt.iter() {
+            self.check_tape(x)?;
+        }
+        self.to_dual(&wrt, vars)
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("<TapeVar: {:.6}>", self.real()))
+    }
+
+    fn __float__(&self) -> f64 {
+        self.real()
+    }
+
+    fn __neg__(&self) -> Self {
+        -self
+    }
+
+    fn __add__(&self, other: TapeOperand) -> PyResult<Self> {
+        match other {
+            TapeOperand::Var(v) => self.check_tape(&v).map(|_| self + v),
+            TapeOperand::F64(f) => Ok(self + f),
+        }
+    }
+
+    fn __radd__(&self, other: TapeOperand) -> PyResult<Self> {
+        match other {
+            TapeOperand::Var(v) => self.check_tape(&v).map(|_| v + self),
+            TapeOperand::F64(f) => Ok(f + self),
+        }
+    }
+
+    fn __sub__(&self, other: TapeOperand) -> PyResult<Self> {
+        match other {
+            TapeOperand::Var(v) => self.check_tape(&v).map(|_| self - v),
+            TapeOperand::F64(f) => Ok(self - f),
+        }
+    }
+
+    fn __rsub__(&self, other: TapeOperand) -> PyResult<Self> {
+        match other {
+            TapeOperand::Var(v) => self.check_tape(&v).map(|_| v - self),
+            TapeOperand::F64(f) => Ok(f - self),
+        }
+    }
+
+    fn __mul__(&self, other: TapeOperand) -> PyResult<Self> {
+        match other {
+            TapeOperand::Var(v) => self.check_tape(&v).map(|_| self * v),
+            TapeOperand::F64(f) => Ok(self * f),
+        }
+    }
+
+    fn __rmul__(&self, other: TapeOperand) -> PyResult<Self> {
+        match other {
+            TapeOperand::Var(v) => self.check_tape(&v).map(|_| v * self),
+            TapeOperand::F64(f) => Ok(f * self),
+        }
+    }
+
+    fn __truediv__(&self, other: TapeOperand) -> PyResult<Self> {
+        match other {
+            TapeOperand::Var(v) => self.check_tape(&v).map(|_| self / v),
+            TapeOperand::F64(f) => Ok(self / f),
+        }
+    }
+
+    fn __rtruediv__(&self, other: TapeOperand) -> PyResult<Self> {
+        match other {
+            TapeOperand::Var(v) => self.check_tape(&v).map(|_| v / self),
+            TapeOperand::F64(f) => Ok(f / self),
+        }
+    }
+
+    fn __pow__(&self, power: TapeOperand, modulo: Option<i32>) -> PyResult<Self> {
+        if modulo.unwrap_or(0) != 0 {
+            return Err(DualError::PowerModulo.into());
+        }
+        match power {
+            TapeOperand::F64(f) => Ok(self.powf(f)),
+            TapeOperand::Var(_) => Err(PyTypeError::new_err(
+                "Power operation not defined with TapeVar type exponent.",
+            )),
+        }
+    }
+
+    fn __exp__(&self) -> Self {
+        self.exp()
+    }
+
+    fn __log__(&self) -> Self {
+        self.log()
+    }
+
+    fn __sqrt__(&self) -> Self {
+        self.sqrt()
+    }
+}
@@ -0,0 +1,181 @@
+//! Configure the naming of the variable tags of dual numbers created by the library.
+//!
+//! The nodes of a curve with id *"sofr"* are tagged *"sofr0"*, *"sofr1"*, etc. by default. A
+//! [TagTemplate] adds a prefix, a separator between the name and the index, a suffix and a
+//! zero-padded width of the index, e.g. *"sofr_00"*. The template is shared by all threads so
+//! that tags created in Rust and in Python are identical.
+
+use pyo3::prelude::*;
+use std::sync::RwLock;
+
+/// The template of variable tags, `{prefix}{name}{separator}{index}{suffix}`, with the index
+/// zero-padded to `padding` digits.
+#[pyclass(module = "rateslib.rs", get_all, set_all)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TagTemplate {
+    pub prefix: String,
+    pub separator: String,
+    pub suffix: String,
+    pub padding: usize,
+}
+
+impl TagTemplate {
+    /// The template compiled into the library, giving tags such as *"x0"*.
+    pub const DEFAULT: TagTemplate = TagTemplate {
+        prefix: String::new(),
+        separator: String::new(),
+        suffix: String::new(),
+        padding: 0,
+    };
+
+    /// Return the tag of the `index` of `name`.
+    pub fn tag(&self, name: &str, index: usize) -> String {
+        format!(
+            "{}{}{}{:0>width$}{}",
+            self.prefix,
+            name,
+            self.separator,
+            index,
+            self.suffix,
+            width = self.padding
+        )
+    }
+
+    /// Return the ordered tags of `name` for the indices `0..range`.
+    pub fn tags(&self, name: &str, range: usize) -> Vec<String> {
+        (0..range).map(|i| self.tag(name, i)).collect()
+    }
+}
+
+impl Default for TagTemplate {
+    fn default() -> Self {
+        TagTemplate::DEFAULT
+    }
+}
+
+static SHARED: RwLock<TagTemplate> = RwLock::new(TagTemplate::DEFAULT);
+
+/// Return the tag template shared by all threads.
+pub fn tag_template() -> TagTemplate {
+    SHARED
+        .read()
+        .expect("tag template lock is not poisoned")
+        .clone()
+}
+
+/// Set the tag template shared by all threads.
+///
+/// Tags of objects created before the template is changed are not renamed.
+pub fn set_tag_template(template: TagTemplate) {
+    *SHARED.write().expect("tag template lock is not poisoned") = template;
+}
+
+#[pymethods]
+impl TagTemplate {
+    #[new]
+    #[pyo3(signature = (prefix=String::new(), separator=String::new(), suffix=String::new(), padding=0))]
+    fn new_py(prefix: String, separator: String, suffix: String, padding: usize) -> Self {
+        TagTemplate {
+            prefix,
+            separator,
+            suffix,
+            padding,
+        }
+    }
+
+    /// Return the tag of an index of a name.
+    ///
+    /// Parameters
+    /// ----------
+    /// name: str
+    ///     The name, e.g. a curve id.
+    /// index: int
+    ///     The index, e.g. of a curve node.
+    ///
+    /// Returns
+    /// -------
+    /// str
+    #[pyo3(name = "tag")]
+    fn tag_py(&self, name: &str, index: usize) -> String {
+        self.tag(name, index)
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self == other
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<TagTemplate: prefix='{}', separator='{}', suffix='{}', padding={}>",
+            self.prefix, self.separator, self.suffix, self.padding
+        )
+    }
+}
+
+/// Return the tag template shared by all threads.
+///
+/// Returns
+/// -------
+/// TagTemplate
+#[pyfunction]
+#[pyo3(name = "get_tag_template")]
+pub fn get_tag_template_py() -> TagTemplate {
+    tag_template()
+}
+
+/// Set the tag template shared by all threads.
+///
+/// Parameters
+/// ----------
+/// template: TagTemplate
+///     The template of the tags of dual numbers created subsequently.
+///
+/// Returns
+/// -------
+/// None
+#[pyfunction]
+#[pyo3(name = "set_tag_template")]
+pub fn set_tag_template_py(template: TagTemplate) {
+    set_tag_template(template)
+}
+
+/// Return the ordered variable tags of a name, with the shared tag template.
+///
+/// Parameters
+/// ----------
+/// name: str
+///     The name, e.g. a curve id.
+/// range: int
+///     The number of tags, e.g. of curve nodes.
+///
+/// Returns
+/// -------
+/// list[str]
+#[pyfunction]
+#[pyo3(name = "get_variable_tags")]
+pub fn get_variable_tags_py(name: &str, range: usize) -> Vec<String> {
+    tag_template().tags(name, range)
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_template() {
+        assert_eq!(TagTemplate::default().tag("sofr", 3), "sofr3");
+        let template = TagTemplate {
+            prefix: "crv:".to_string(),
+            separator: "_".to_string(),
+            suffix: "!".to_string(),
+            padding: 3,
+        };
+        assert_eq!(
+            template.tags("sofr", 2),
+            vec!["crv:sofr_000!".to_string(), "crv:sofr_001!".to_string()]
+        );
+        // indices wider than the padding are not truncated
+        assert_eq!(template.tag("sofr", 1234), "crv:sofr_1234!");
+    }
+}
@@ -2,6 +2,75 @@ use internment::Intern;
 use pyo3::exceptions::PyValueError;
 use pyo3::{pyclass, PyErr};
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// The ISO 4217 codes of national currencies known to *rateslib* with their number of minor unit
+/// decimal places.
+const ISO_CURRENCIES: [(&str, u32); 56] = [
+    ("aed", 2),
+    ("aud", 2),
+    ("bhd", 3),
+    ("bif", 0),
+    ("brl", 2),
+    ("cad", 2),
+    ("chf", 2),
+    ("clp", 0),
+    ("cny", 2),
+    ("cop", 2),
+    ("czk", 2),
+    ("djf", 0),
+    ("dkk", 2),
+    ("eur", 2),
+    ("gbp", 2),
+    ("gnf", 0),
+    ("hkd", 2),
+    ("huf", 2),
+    ("idr", 2),
+    ("ils", 2),
+    ("inr", 2),
+    ("iqd", 3),
+    ("isk", 0),
+    ("jod", 3),
+    ("jpy", 0),
+    ("kmf", 0),
+    ("krw", 0),
+    ("kwd", 3),
+    ("lyd", 3),
+    ("mxn", 2),
+    ("myr", 2),
+    ("nok", 2),
+    ("nzd", 2),
+    ("omr", 3),
+    ("pen", 2),
+    ("php", 2),
+    ("pln", 2),
+    ("pyg", 0),
+    ("ron", 2),
+    ("rub", 2),
+    ("rwf", 0),
+    ("sar", 2),
+    ("sek", 2),
+    ("sgd", 2),
+    ("thb", 2),
+    ("tnd", 3),
+    ("try", 2),
+    ("twd", 2),
+    ("ugx", 0),
+    ("usd", 2),
+    ("vnd", 0),
+    ("vuv", 0),
+    ("xaf", 0),
+    ("xof", 0),
+    ("xpf", 0),
+    ("zar", 2),
+];
+
+/// The currencies which are the base of any pair with a currency later in the list, or not in it.
+const BASE_PRIORITY: [&str; 11] = [
+    "xau", "xag", "xpt", "xpd", "eur", "gbp", "aud", "nzd", "usd", "cad", "chf",
+];
 
 /// A currency identified by 3-ascii ISO code.
 #[pyclass(module = "rateslib.rs")]
@@ -16,10 +85,10 @@ impl Ccy {
     /// Use **only** 3-ascii names. e.g. *"usd"*, aligned with ISO representation. `name` is converted
     /// to lowercase to promote performant equality between "USD" and "usd".
     ///
-    /// Panics if `name` is not 3 bytes in length.
+    /// Errors if `name` is not 3 ascii letters.
     pub fn try_new(name: &str) -> Result<Self, PyErr> {
         let ccy: String = name.to_string().to_lowercase();
-        if ccy.len() != 3 {
+        if ccy.len() != 3 || !ccy.bytes().all(|b| b.is_ascii_lowercase()) {
             return Err(PyValueError::new_err(
                 "`Ccy` must be 3 ascii character in length, e.g. 'usd'.",
             ));
@@ -28,6 +97,51 @@ impl Ccy {
             name: Intern::new(ccy),
         })
     }
+
+    /// Whether the currency is one of the ISO 4217 national currency codes known to *rateslib*.
+    pub fn is_iso(&self) -> bool {
+        self.minor_units().is_some()
+    }
+
+    /// The number of minor unit decimal places of the currency under ISO 4217, if known.
+    pub fn minor_units(&self) -> Option<u32> {
+        ISO_CURRENCIES
+            .iter()
+            .find(|(code, _)| *code == self.name.as_str())
+            .map(|(_, units)| *units)
+    }
+
+    /// Compare currencies by their market-standard priority as the base of a pair.
+    ///
+    /// Precious metals come first, then *EUR, GBP, AUD, NZD, USD, CAD, CHF*. Other currencies
+    /// follow alphabetically, except *JPY* which is always the quote currency, e.g. *"noksek"*
+    /// and *"sekjpy"*.
+    pub fn market_cmp(&self, other: &Ccy) -> Ordering {
+        let rank = |c: &Ccy| match c.name.as_str() {
+            "jpy" => BASE_PRIORITY.len() + 1,
+            name => BASE_PRIORITY
+                .iter()
+                .position(|p| *p == name)
+                .unwrap_or(BASE_PRIORITY.len()),
+        };
+        rank(self)
+            .cmp(&rank(other))
+            .then_with(|| self.name.cmp(&other.name))
+    }
+}
+
+impl FromStr for Ccy {
+    type Err = PyErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ccy::try_new(s.trim())
+    }
+}
+
+impl fmt::Display for Ccy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
 }
 
 #[cfg(test)]
@@ -47,5 +161,26 @@ mod tests {
             Ok(_) => assert!(false),
             Err(_) => assert!(true),
         }
+        assert!(Ccy::try_new("us1").is_err());
+    }
+
+    #[test]
+    fn ccy_parse_and_iso() {
+        let usd: Ccy = " USD".parse().unwrap();
+        assert_eq!(usd.to_string(), "usd");
+        assert!(usd.is_iso());
+        assert_eq!(Ccy::try_new("kwd").unwrap().minor_units(), Some(3));
+        assert_eq!(Ccy::try_new("abc").unwrap().minor_units(), None);
+    }
+
+    #[test]
+    fn ccy_market_cmp() {
+        let c = |name: &str| Ccy::try_new(name).unwrap();
+        assert_eq!(c("eur").market_cmp(&c("usd")), Ordering::Less);
+        assert_eq!(c("usd").market_cmp(&c("gbp")), Ordering::Greater);
+        assert_eq!(c("usd").market_cmp(&c("sek")), Ordering::Less);
+        assert_eq!(c("nok").market_cmp(&c("sek")), Ordering::Less);
+        assert_eq!(c("sek").market_cmp(&c("jpy")), Ordering::Less);
+        assert_eq!(c("xau").market_cmp(&c("eur")), Ordering::Less);
     }
 }
@@ -2,7 +2,9 @@ use crate::fx::rates::ccy::Ccy;
 use pyo3::exceptions::PyValueError;
 use pyo3::PyErr;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::fmt;
+use std::str::FromStr;
 
 /// A container of a two-pair `Ccy` cross.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -20,6 +22,39 @@ impl FXPair {
         }
         Ok(FXPair(lhs_, rhs_))
     }
+
+    /// Constructs the `FXPair` of two distinct `Ccy`s in market-standard base/quote order, see
+    /// [Ccy::market_cmp].
+    pub fn market_standard(lhs: Ccy, rhs: Ccy) -> Result<Self, PyErr> {
+        match lhs.market_cmp(&rhs) {
+            Ordering::Less => Ok(FXPair(lhs, rhs)),
+            Ordering::Greater => Ok(FXPair(rhs, lhs)),
+            Ordering::Equal => Err(PyValueError::new_err(
+                "`FXPair` must be created from two distinct currencies, not same.",
+            )),
+        }
+    }
+
+    /// Whether the pair is quoted in market-standard base/quote order.
+    pub fn is_market_standard(&self) -> bool {
+        self.0.market_cmp(&self.1).is_lt()
+    }
+}
+
+impl FromStr for FXPair {
+    type Err = PyErr;
+
+    /// Parse a pair such as *"eurusd"* or *"EUR/USD"*.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let pair = s.trim().replace('/', "");
+        match (pair.get(..3), pair.get(3..)) {
+            (Some(lhs), Some(rhs)) if rhs.len() == 3 => FXPair::try_new(lhs, rhs),
+            _ => Err(PyValueError::new_err(format!(
+                "`FXPair` must be parsed from two 3 ascii character codes, e.g. 'eurusd', not '{}'.",
+                s
+            ))),
+        }
+    }
 }
 
 impl fmt::Display for FXPair {
@@ -39,6 +74,24 @@ mod tests {
         assert_eq!(a, b)
     }
 
+    #[test]
+    fn fxpair_market_standard() {
+        let (usd, eur) = (Ccy::try_new("usd").unwrap(), Ccy::try_new("eur").unwrap());
+        let pair = FXPair::market_standard(usd, eur).unwrap();
+        assert_eq!(pair.to_string(), "eurusd");
+        assert!(pair.is_market_standard());
+        assert!(!FXPair::try_new("jpy", "usd").unwrap().is_market_standard());
+        assert!(FXPair::market_standard(usd, usd).is_err());
+    }
+
+    #[test]
+    fn fxpair_parse() {
+        let pair: FXPair = "EUR/USD".parse().unwrap();
+        assert_eq!(pair, FXPair::try_new("eur", "usd").unwrap());
+        assert!("eurus".parse::<FXPair>().is_err());
+        assert!("eurusdx".parse::<FXPair>().is_err());
+    }
+
     #[test]
     fn fxpair_creation_error() {
         match FXPair::try_new("usd", "USD") {
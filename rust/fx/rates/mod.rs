@@ -4,6 +4,7 @@
 use crate::dual::linalg::argabsmax;
 use crate::dual::{set_order_clone, ADOrder, Dual, Dual2, Number, NumberArray2};
 use crate::json::JSON;
+use crate::state::next_state_id;
 use chrono::prelude::*;
 use indexmap::set::IndexSet;
 use itertools::Itertools;
@@ -26,13 +27,24 @@ pub use crate::fx::rates::fxrate::FXRate;
 
 /// A multi-currency FX market deriving all crosses from a vector of `FXRate`s.
 #[pyclass(module = "rateslib.rs")]
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(from = "FXRatesDataModel")]
 pub struct FXRates {
     pub(crate) fx_rates: Vec<FXRate>,
     pub(crate) currencies: IndexSet<Ccy>,
     #[serde(skip)]
     pub(crate) fx_array: NumberArray2,
+    #[serde(skip)]
+    pub(crate) state_id: u64,
+}
+
+/// Equality is determined by content and does not consider the `state_id`.
+impl PartialEq for FXRates {
+    fn eq(&self, other: &Self) -> bool {
+        self.fx_rates == other.fx_rates
+            && self.currencies == other.currencies
+            && self.fx_array == other.fx_array
+    }
 }
 
 #[derive(Deserialize)]
@@ -114,9 +126,15 @@ impl FXRates {
             fx_rates,
             fx_array,
             currencies,
+            state_id: next_state_id(),
         })
     }
 
+    /// Get the id of the current state of the `FXRates`, which changes whenever it is mutated.
+    pub fn state_id(&self) -> u64 {
+        self.state_id
+    }
+
     pub fn get_ccy_index(&self, currency: &Ccy) -> Option<usize> {
         self.currencies.get_index_of(currency)
     }
@@ -156,10 +174,97 @@ impl FXRates {
         self.fx_rates.clone_from(&new_fxr.fx_rates);
         self.currencies.clone_from(&new_fxr.currencies);
         self.fx_array = new_fxr.fx_array.clone();
+        self.state_id = next_state_id();
         Ok(())
     }
 
+    /// Return the `FXRates` quoted by the given `pairs`, with the same base currency.
+    ///
+    /// The rate of each pair is derived from the existing rates, including its first and second
+    /// order sensitivities, so the restated `FXRates` remains sensitive to the originally quoted
+    /// pairs, rather than to `pairs`, and has the same `ADOrder`.
+    ///
+    /// # Errors
+    ///
+    /// If a pair contains a currency which is not one of the `currencies`, or `pairs` do not
+    /// determine the rates of every currency or are degenerate.
+    pub fn restate(&self, pairs: &[FXPair]) -> Result<FXRates, PyErr> {
+        self.restate_with_base(pairs, self.currencies[0])
+    }
+
+    /// Return the `FXRates` with the given `base` currency, quoted by a pair of the base
+    /// against every other currency.
+    ///
+    /// Sensitivities are preserved as with [FXRates::restate].
+    ///
+    /// # Errors
+    ///
+    /// If `base` is not one of the `currencies`.
+    pub fn rebase(&self, base: &Ccy) -> Result<FXRates, PyErr> {
+        if !self.currencies.contains(base) {
+            return Err(PyValueError::new_err(
+                "The new `base` must be one of the currencies of the `FXRates`.",
+            ));
+        }
+        let pairs: Vec<FXPair> = self
+            .currencies
+            .iter()
+            .filter(|c| *c != base)
+            .map(|c| FXPair(*base, *c))
+            .collect();
+        self.restate_with_base(&pairs, *base)
+    }
+
+    fn restate_with_base(&self, pairs: &[FXPair], base: Ccy) -> Result<FXRates, PyErr> {
+        // second order sensitivities to the existing pairs are preserved for any later `ADOrder`
+        let NumberArray2::Dual2(fx_array) =
+            create_fx_array(&self.currencies, &self.fx_rates, ADOrder::Two)?
+        else {
+            unreachable!("`create_fx_array` returns the requested `ADOrder`.")
+        };
+        let settlement = self.fx_rates[0].settlement;
+        let fx_rates = pairs
+            .iter()
+            .map(|pair| {
+                let (i, j) = match (self.get_ccy_index(&pair.0), self.get_ccy_index(&pair.1)) {
+                    (Some(i), Some(j)) => (i, j),
+                    _ => {
+                        return Err(PyValueError::new_err(format!(
+                            "The pair '{}' contains a currency not in the `FXRates`.",
+                            pair
+                        )))
+                    }
+                };
+                Ok(FXRate {
+                    pair: *pair,
+                    rate: Number::Dual2(fx_array[[i, j]].clone()),
+                    settlement,
+                })
+            })
+            .collect::<Result<Vec<FXRate>, PyErr>>()?;
+        let mut restated = FXRates::try_new(fx_rates, Some(base))?;
+        if restated.currencies.len() != self.currencies.len() {
+            return Err(PyValueError::new_err(
+                "The restated pairs must contain every currency of the `FXRates`.",
+            ));
+        }
+        restated.set_ad_order(self.ad())?;
+        Ok(restated)
+    }
+
+    /// Get the `ADOrder` of the FX array.
+    pub fn ad(&self) -> ADOrder {
+        match &self.fx_array {
+            NumberArray2::F64(_) => ADOrder::Zero,
+            NumberArray2::Dual(_) => ADOrder::One,
+            NumberArray2::Dual2(_) => ADOrder::Two,
+        }
+    }
+
     pub fn set_ad_order(&mut self, ad: ADOrder) -> Result<(), PyErr> {
+        if ad != self.ad() {
+            self.state_id = next_state_id();
+        }
         match (ad, &self.fx_array) {
             (ADOrder::Zero, NumberArray2::F64(_))
             | (ADOrder::One, NumberArray2::Dual(_))
@@ -385,6 +490,7 @@ impl JSON for FXRates {}
 mod tests {
     use super::*;
     use crate::calendars::ndt;
+    use crate::dual::{Gradient1, Gradient2};
     use ndarray::arr2;
 
     #[test]
@@ -506,4 +612,89 @@ mod tests {
             .into();
         assert_eq!(d3, rate)
     }
+
+    fn fxr_fixture() -> FXRates {
+        FXRates::try_new(
+            vec![
+                FXRate::try_new("eur", "usd", Number::F64(1.08), Some(ndt(2004, 1, 1))).unwrap(),
+                FXRate::try_new("usd", "jpy", Number::F64(110.0), Some(ndt(2004, 1, 1))).unwrap(),
+            ],
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn fxrates_restate() {
+        let fxr = fxr_fixture();
+        let (eur, usd, jpy) = (
+            Ccy::try_new("eur").unwrap(),
+            Ccy::try_new("usd").unwrap(),
+            Ccy::try_new("jpy").unwrap(),
+        );
+        let restated = fxr.restate(&[FXPair(eur, usd), FXPair(eur, jpy)]).unwrap();
+        assert_eq!(restated.currencies[0], eur);
+        assert_eq!(restated.fx_rates[1].settlement, Some(ndt(2004, 1, 1)));
+        let vars = vec!["fx_eurusd".to_string(), "fx_usdjpy".to_string()];
+        for (lhs, rhs) in [(eur, jpy), (usd, jpy), (jpy, usd)] {
+            let (a, b) = (
+                Dual::from(fxr.rate(&lhs, &rhs).unwrap()),
+                Dual::from(restated.rate(&lhs, &rhs).unwrap()),
+            );
+            assert!((a.real - b.real).abs() < 1e-9);
+            // sensitivities remain to the originally quoted pairs
+            let (ga, gb) = (a.gradient1(vars.clone()), b.gradient1(vars.clone()));
+            assert!(ga.iter().zip(gb.iter()).all(|(x, y)| (x - y).abs() < 1e-9));
+        }
+
+        assert!(fxr.restate(&[FXPair(eur, usd)]).is_err());
+        let gbp = Ccy::try_new("gbp").unwrap();
+        assert!(fxr.restate(&[FXPair(eur, usd), FXPair(eur, gbp)]).is_err());
+        assert!(fxr.restate(&[FXPair(eur, usd), FXPair(usd, eur)]).is_err());
+    }
+
+    #[test]
+    fn fxrates_rebase() {
+        let mut fxr = fxr_fixture();
+        let jpy = Ccy::try_new("jpy").unwrap();
+        let rebased = fxr.rebase(&jpy).unwrap();
+        assert_eq!(rebased.currencies[0], jpy);
+        assert!(rebased.fx_rates.iter().all(|r| r.pair.0 == jpy));
+        let eurjpy = Dual::from(rebased.rate(&Ccy::try_new("eur").unwrap(), &jpy).unwrap());
+        assert!((eurjpy.real - 118.8).abs() < 1e-9);
+        let grad = eurjpy.gradient1(vec!["fx_eurusd".to_string(), "fx_usdjpy".to_string()]);
+        assert!((grad[0] - 110.0).abs() < 1e-9 && (grad[1] - 1.08).abs() < 1e-9);
+
+        // the AD order is preserved, and sensitivities are restored to the original pairs
+        fxr.set_ad_order(ADOrder::Zero).unwrap();
+        let mut rebased = fxr.rebase(&jpy).unwrap();
+        assert_eq!(rebased.ad(), ADOrder::Zero);
+        rebased.set_ad_order(ADOrder::Two).unwrap();
+        let eurjpy = Dual2::from(rebased.rate(&Ccy::try_new("eur").unwrap(), &jpy).unwrap());
+        let vars = vec!["fx_eurusd".to_string(), "fx_usdjpy".to_string()];
+        assert!((eurjpy.gradient1(vars.clone())[0] - 110.0).abs() < 1e-9);
+        assert!((eurjpy.gradient2(vars)[[0, 1]] - 1.0).abs() < 1e-9);
+
+        assert!(fxr.rebase(&Ccy::try_new("gbp").unwrap()).is_err());
+    }
+
+    #[test]
+    fn fxrates_state_id() {
+        let mut fxr = FXRates::try_new(
+            vec![FXRate::try_new("eur", "usd", Number::F64(1.08), None).unwrap()],
+            None,
+        )
+        .unwrap();
+        let id = fxr.state_id();
+        let _ = fxr.set_ad_order(ADOrder::One);
+        assert_eq!(id, fxr.state_id());
+        let _ = fxr.set_ad_order(ADOrder::Two);
+        let id2 = fxr.state_id();
+        assert_ne!(id, id2);
+        let _ = fxr.update(vec![
+            FXRate::try_new("eur", "usd", Number::F64(1.09), None).unwrap()
+        ]);
+        assert_ne!(id2, fxr.state_id());
+        assert_eq!(fxr.clone(), fxr);
+    }
 }
@@ -1,7 +1,8 @@
 //! Wrapper module to export Rust FX rate data types to Python using pyo3 bindings.
 
+use crate::dates::dates_py::extract_optional_date;
 use crate::dual::{ADOrder, Number, NumberArray2};
-use crate::fx::rates::{Ccy, FXRate, FXRates};
+use crate::fx::rates::{Ccy, FXPair, FXRate, FXRates};
 use bincode::{deserialize, serialize};
 use chrono::prelude::*;
 use ndarray::Axis;
@@ -11,7 +12,24 @@ use pyo3::exceptions::PyValueError;
 // use pyo3::exceptions::PyValueError;
 use crate::json::json_py::DeserializedObj;
 use crate::json::JSON;
-use pyo3::types::PyBytes;
+use pyo3::types::{PyBytes, PyString};
+
+/// Extract a currency given as a `Ccy` or as a string, e.g. *"usd"*.
+pub(crate) fn extract_ccy(obj: &Bound<'_, PyAny>) -> PyResult<Ccy> {
+    match obj.downcast::<PyString>() {
+        Ok(s) => s.to_cow()?.parse(),
+        Err(_) => obj.extract::<Ccy>(),
+    }
+}
+
+/// Extract an optional currency, see [extract_ccy], where `None` is no currency.
+pub(crate) fn extract_optional_ccy(obj: &Bound<'_, PyAny>) -> PyResult<Option<Ccy>> {
+    if obj.is_none() {
+        Ok(None)
+    } else {
+        extract_ccy(obj).map(Some)
+    }
+}
 
 #[pymethods]
 impl Ccy {
@@ -20,6 +38,48 @@ impl Ccy {
         Ccy::try_new(name)
     }
 
+    /// Whether the currency is one of the ISO 4217 national currency codes known to *rateslib*.
+    ///
+    /// Returns
+    /// -------
+    /// bool
+    #[pyo3(name = "is_iso")]
+    fn is_iso_py(&self) -> bool {
+        self.is_iso()
+    }
+
+    /// The number of minor unit decimal places of the currency under ISO 4217.
+    ///
+    /// Returns
+    /// -------
+    /// int or None
+    #[pyo3(name = "minor_units")]
+    fn minor_units_py(&self) -> Option<u32> {
+        self.minor_units()
+    }
+
+    /// Return the name of the pair of this and another currency in market-standard base/quote
+    /// order.
+    ///
+    /// Parameters
+    /// ----------
+    /// other: Ccy or str
+    ///     The other currency, which must be distinct.
+    ///
+    /// Returns
+    /// -------
+    /// str
+    ///
+    /// Notes
+    /// -----
+    /// Precious metals are the base of any pair, then *EUR, GBP, AUD, NZD, USD, CAD, CHF*. Other
+    /// currencies are ordered alphabetically, except *JPY* which is always the quote currency,
+    /// e.g. ``Ccy("jpy").pair_with("usd")`` is *"usdjpy"*.
+    #[pyo3(name = "pair_with")]
+    fn pair_with_py(&self, #[pyo3(from_py_with = "extract_ccy")] other: Ccy) -> PyResult<String> {
+        Ok(FXPair::market_standard(*self, other)?.to_string())
+    }
+
     #[getter]
     #[pyo3(name = "name")]
     fn name_py(&self) -> PyResult<String> {
@@ -30,8 +90,21 @@ impl Ccy {
         Ok(format!("<Ccy: '{}'>", self.name))
     }
 
-    fn __eq__(&self, other: &Self) -> bool {
-        self.name == other.name
+    fn __str__(&self) -> String {
+        self.to_string()
+    }
+
+    /// Compare with another `Ccy` or a currency string, case-insensitively.
+    fn __eq__(&self, other: &Bound<'_, PyAny>) -> bool {
+        match extract_ccy(other) {
+            Ok(ccy) => self.name == ccy.name,
+            Err(_) => false,
+        }
+    }
+
+    /// The hash of the lowercase currency string, so a `Ccy` and its name are the same key.
+    fn __hash__(&self, py: Python<'_>) -> PyResult<isize> {
+        PyString::new_bound(py, &self.name).hash()
     }
 
     // Pickling
@@ -55,7 +128,7 @@ impl FXRate {
         lhs: &str,
         rhs: &str,
         rate: Number,
-        settlement: Option<NaiveDateTime>,
+        #[pyo3(from_py_with = "extract_optional_date")] settlement: Option<NaiveDateTime>,
     ) -> PyResult<Self> {
         FXRate::try_new(lhs, rhs, rate, settlement)
     }
@@ -146,7 +219,10 @@ impl FXRates {
     // }
     #[new]
     #[pyo3(signature = (fx_rates, base=None))]
-    fn new_py(fx_rates: Vec<FXRate>, base: Option<Ccy>) -> PyResult<Self> {
+    fn new_py(
+        fx_rates: Vec<FXRate>,
+        #[pyo3(from_py_with = "extract_optional_ccy")] base: Option<Ccy>,
+    ) -> PyResult<Self> {
         FXRates::try_new(fx_rates, base)
     }
 
@@ -172,6 +248,12 @@ impl FXRates {
         }
     }
 
+    #[getter]
+    #[pyo3(name = "state_id")]
+    fn state_id_py(&self) -> u64 {
+        self.state_id()
+    }
+
     #[getter]
     #[pyo3(name = "base")]
     fn base_py(&self) -> PyResult<Ccy> {
@@ -217,13 +299,20 @@ impl FXRates {
     }
 
     #[pyo3(name = "get_ccy_index")]
-    fn get_ccy_index_py(&self, currency: Ccy) -> Option<usize> {
+    fn get_ccy_index_py(
+        &self,
+        #[pyo3(from_py_with = "extract_ccy")] currency: Ccy,
+    ) -> Option<usize> {
         self.get_ccy_index(&currency)
     }
 
     #[pyo3(name = "rate")]
-    fn rate_py(&self, lhs: &Ccy, rhs: &Ccy) -> PyResult<Option<Number>> {
-        Ok(self.rate(lhs, rhs))
+    fn rate_py(
+        &self,
+        #[pyo3(from_py_with = "extract_ccy")] lhs: Ccy,
+        #[pyo3(from_py_with = "extract_ccy")] rhs: Ccy,
+    ) -> PyResult<Option<Number>> {
+        Ok(self.rate(&lhs, &rhs))
     }
 
     #[pyo3(name = "update")]
@@ -231,6 +320,51 @@ impl FXRates {
         self.update(fx_rates)
     }
 
+    /// Return the *FXRates* quoted by the given pairs, with the same base currency.
+    ///
+    /// Parameters
+    /// ----------
+    /// pairs: list[str]
+    ///     The pairs by which to quote the rates, e.g. ``["eurusd", "eurjpy"]``.
+    ///
+    /// Returns
+    /// -------
+    /// FXRates
+    ///
+    /// Notes
+    /// -----
+    /// The rate of each pair is derived from the existing rates, with its sensitivities to the
+    /// originally quoted pairs, which are retained as the variables of the restated *FXRates*.
+    /// The *fx_rates* of the result are therefore *Dual2*.
+    #[pyo3(name = "restate")]
+    fn restate_py(&self, pairs: Vec<String>) -> PyResult<Self> {
+        let pairs = pairs
+            .iter()
+            .map(|p| p.parse())
+            .collect::<Result<Vec<FXPair>, PyErr>>()?;
+        self.restate(&pairs)
+    }
+
+    /// Return the *FXRates* with a new base currency, quoted against every other currency.
+    ///
+    /// Parameters
+    /// ----------
+    /// base: Ccy or str
+    ///     The new base currency, which must be one of the *currencies*.
+    ///
+    /// Returns
+    /// -------
+    /// FXRates
+    ///
+    /// Notes
+    /// -----
+    /// Sensitivities to the originally quoted pairs are retained, as with
+    /// :meth:`~rateslib.rs.FXRates.restate`.
+    #[pyo3(name = "rebase")]
+    fn rebase_py(&self, #[pyo3(from_py_with = "extract_ccy")] base: Ccy) -> PyResult<Self> {
+        self.rebase(&base)
+    }
+
     #[pyo3(name = "set_ad_order")]
     fn set_ad_order_py(&mut self, ad: ADOrder) -> PyResult<()> {
         self.set_ad_order(ad)?;
@@ -248,6 +382,22 @@ impl FXRates {
         }
     }
 
+    /// Return a hash of the canonical JSON representation of the object, which is stable
+    /// across sessions.
+    ///
+    /// Returns
+    /// -------
+    /// int
+    #[pyo3(name = "canonical_hash")]
+    fn canonical_hash_py(&self) -> PyResult<u64> {
+        match DeserializedObj::FXRates(self.clone()).canonical_hash() {
+            Ok(v) => Ok(v),
+            Err(_) => Err(PyValueError::new_err(
+                "Failed to serialize `FXRates` to JSON.",
+            )),
+        }
+    }
+
     // Pickling
     pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
         *self = deserialize(state.as_bytes()).unwrap();
@@ -0,0 +1,294 @@
+//! Conversions between the strikes, deltas and ATM conventions of FX options.
+//!
+//! Deltas are the Black deltas of an option on the forward FX rate, with an expiry in `t` years
+//! and a volatility `vol`. A forward delta is the derivative of the forward premium by the
+//! forward, and a spot delta is that scaled by the foreign currency discount factor from the spot
+//! date to the delivery date. A premium adjusted delta is reduced by the premium when the premium
+//! is paid in the foreign currency, such that `delta = phi K / F N(phi d2)` for `phi` of 1 for a
+//! call and -1 for a put.
+//!
+//! Every conversion is dual-aware. Strikes of premium adjusted deltas, which have no closed form,
+//! are solved in [f64] and refined by Newton steps in the arithmetic of the inputs so that they
+//! carry the sensitivities of the implicit function.
+
+use crate::dual::{MathFuncs, Number};
+use crate::maths::roots::bracketed_root;
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, PyErr};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// The tolerance of the logarithm of the strike of a premium adjusted delta.
+const TOLERANCE: f64 = 1e-14;
+
+/// The convention of the at-the-money strike of an FX option.
+#[pyclass(module = "rateslib.rs", eq, eq_int)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AtmConvention {
+    /// The strike is the forward, ATMF.
+    Forward,
+    /// The strike at which the deltas of a call and a put sum to zero, DNS.
+    DeltaNeutral,
+}
+
+fn phi(call: bool) -> f64 {
+    if call {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
+/// Return `d1` and `d2` and the total volatility.
+fn d1_d2(forward: &Number, strike: &Number, vol: &Number, t: f64) -> (Number, Number, Number) {
+    let sigma = vol * t.sqrt();
+    let d1 = (forward / strike).log() / &sigma + &sigma * 0.5;
+    let d2 = &d1 - &sigma;
+    (d1, d2, sigma)
+}
+
+/// Return the standard normal density.
+fn norm_pdf(x: &Number) -> Number {
+    (x * x * -0.5).exp() / (2.0 * PI).sqrt()
+}
+
+/// Return the forward delta of a call or put, which may be premium adjusted.
+pub fn forward_delta(
+    forward: &Number,
+    strike: &Number,
+    vol: &Number,
+    t: f64,
+    call: bool,
+    premium_adjusted: bool,
+) -> Number {
+    let p = phi(call);
+    let (d1, d2, _) = d1_d2(forward, strike, vol, t);
+    match premium_adjusted {
+        false => (d1 * p).norm_cdf() * p,
+        true => strike / forward * (d2 * p).norm_cdf() * p,
+    }
+}
+
+/// Return the spot delta of a call or put, which may be premium adjusted, with `df_foreign` the
+/// foreign currency discount factor from the spot date to the delivery date.
+pub fn spot_delta(
+    forward: &Number,
+    strike: &Number,
+    vol: &Number,
+    t: f64,
+    df_foreign: &Number,
+    call: bool,
+    premium_adjusted: bool,
+) -> Number {
+    forward_delta(forward, strike, vol, t, call, premium_adjusted) * df_foreign
+}
+
+/// Return the strike of a call or put with a forward delta, which may be premium adjusted.
+///
+/// The premium adjusted delta of a call is not monotonic in the strike and attains a maximum,
+/// above which no strike exists. The strike returned is the one above that of the maximum.
+///
+/// # Errors
+///
+/// If the delta does not have the sign of the option, is not less than one in magnitude or, for
+/// a premium adjusted call, exceeds the maximum delta.
+pub fn strike_from_forward_delta(
+    delta: &Number,
+    forward: &Number,
+    vol: &Number,
+    t: f64,
+    call: bool,
+    premium_adjusted: bool,
+) -> Result<Number, PyErr> {
+    let p = phi(call);
+    let target = f64::from(delta);
+    if !(target * p > 0.0 && target * p < 1.0) {
+        return Err(PyValueError::new_err(
+            "`delta` must be in (0, 1) for a call and in (-1, 0) for a put.",
+        ));
+    }
+    let sigma = vol * t.sqrt();
+    // The unadjusted strike inverts the delta directly.
+    let unadjusted =
+        forward * ((&sigma * &sigma * 0.5) - (delta * p).inv_norm_cdf() * &sigma * p).exp();
+    if !premium_adjusted {
+        return Ok(unadjusted);
+    }
+
+    // The premium adjusted strike is below the unadjusted strike, where the delta decreases with
+    // the strike, and is found in its logarithm.
+    let (f, s) = (f64::from(forward), f64::from(&sigma));
+    let pa_delta = |x: f64| -> (f64, f64) {
+        let k = x.exp();
+        let d2 = ((f / k).ln() - 0.5 * s * s) / s;
+        let n_pdf = (-0.5 * d2 * d2).exp() / (2.0 * PI).sqrt();
+        (
+            p * k / f * (p * d2).norm_cdf(),
+            k / f * ((p * d2).norm_cdf() * p - n_pdf / s),
+        )
+    };
+    let hi = f64::from(&unadjusted).ln();
+    let lo = match call {
+        true => {
+            let k_max = max_delta_strike(f, s)?;
+            if pa_delta(k_max.ln()).0 < target {
+                return Err(PyValueError::new_err(
+                    "`delta` exceeds the maximum premium adjusted delta of a call.",
+                ));
+            }
+            k_max.ln()
+        }
+        false => hi - 50.0 * s,
+    };
+    let x = bracketed_root(
+        |x| {
+            let (delta, gradient) = pa_delta(x);
+            Ok((delta - target, Some(gradient)))
+        },
+        0.5 * (lo + hi),
+        (lo, hi),
+        TOLERANCE,
+    )?;
+
+    let mut strike = Number::F64(x.exp());
+    for _ in 0..2 {
+        let (_, d2, sigma) = d1_d2(forward, &strike, vol, t);
+        let error = forward_delta(forward, &strike, vol, t, call, true) - delta;
+        let gradient = ((&d2 * p).norm_cdf() * p - norm_pdf(&d2) / &sigma) / forward;
+        strike = &strike - error / gradient;
+    }
+    Ok(strike)
+}
+
+/// Return the strike of the maximum premium adjusted delta of a call, where
+/// `s N(d2) = n(d2)` for a total volatility `s`.
+fn max_delta_strike(forward: f64, s: f64) -> Result<f64, PyErr> {
+    let d2 = bracketed_root(
+        |d2| {
+            let n = (-0.5 * d2 * d2).exp() / (2.0 * PI).sqrt();
+            Ok((s * d2.norm_cdf() - n, Some((s + d2) * n)))
+        },
+        0.0,
+        (-s, 10.0),
+        TOLERANCE,
+    )?;
+    Ok(forward * (-d2 * s - 0.5 * s * s).exp())
+}
+
+/// Return the strike of a call or put with a spot delta, which may be premium adjusted, with
+/// `df_foreign` the foreign currency discount factor from the spot date to the delivery date.
+///
+/// # Errors
+///
+/// As [strike_from_forward_delta] for the equivalent forward delta.
+pub fn strike_from_spot_delta(
+    delta: &Number,
+    forward: &Number,
+    vol: &Number,
+    t: f64,
+    df_foreign: &Number,
+    call: bool,
+    premium_adjusted: bool,
+) -> Result<Number, PyErr> {
+    strike_from_forward_delta(
+        &(delta / df_foreign),
+        forward,
+        vol,
+        t,
+        call,
+        premium_adjusted,
+    )
+}
+
+/// Return the at-the-money strike of a convention, where the delta neutral strike depends on
+/// whether deltas are premium adjusted.
+pub fn atm_strike(
+    forward: &Number,
+    vol: &Number,
+    t: f64,
+    convention: AtmConvention,
+    premium_adjusted: bool,
+) -> Number {
+    match convention {
+        AtmConvention::Forward => forward.clone(),
+        AtmConvention::DeltaNeutral => {
+            let half_variance = vol * vol * (0.5 * t);
+            match premium_adjusted {
+                false => forward * half_variance.exp(),
+                true => forward * (-half_variance).exp(),
+            }
+        }
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dual::Dual;
+
+    fn f(v: f64) -> Number {
+        Number::F64(v)
+    }
+
+    #[test]
+    fn test_strike_delta_round_trip() {
+        let (fwd, vol, t, w) = (f(1.1), f(0.12), 0.75, f(0.98));
+        for call in [true, false] {
+            for pa in [false, true] {
+                // above the strike of the maximum premium adjusted call delta, near 0.92
+                for strike in [0.95, 1.05, 1.1, 1.2] {
+                    let k = f(strike);
+                    let delta = forward_delta(&fwd, &k, &vol, t, call, pa);
+                    let solved = strike_from_forward_delta(&delta, &fwd, &vol, t, call, pa);
+                    assert!((f64::from(&solved.unwrap()) - strike).abs() < 1e-10);
+
+                    let delta = spot_delta(&fwd, &k, &vol, t, &w, call, pa);
+                    let solved = strike_from_spot_delta(&delta, &fwd, &vol, t, &w, call, pa);
+                    assert!((f64::from(&solved.unwrap()) - strike).abs() < 1e-10);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_premium_adjusted_call_maximum() {
+        let (fwd, vol, t) = (f(1.1), f(0.5), 4.0);
+        let s = 1.0;
+        let k_max = max_delta_strike(1.1, s).unwrap();
+        let max = f64::from(&forward_delta(&fwd, &f(k_max), &vol, t, true, true));
+        for k in [k_max * 0.99, k_max * 1.01] {
+            assert!(f64::from(&forward_delta(&fwd, &f(k), &vol, t, true, true)) < max);
+        }
+        assert!(strike_from_forward_delta(&f(max + 1e-3), &fwd, &vol, t, true, true).is_err());
+        assert!(strike_from_forward_delta(&f(-0.25), &fwd, &vol, t, true, false).is_err());
+        assert!(strike_from_forward_delta(&f(-1.0), &fwd, &vol, t, false, false).is_err());
+    }
+
+    #[test]
+    fn test_atm_conventions() {
+        let (fwd, vol, t) = (f(1.1), f(0.12), 0.75);
+        assert_eq!(atm_strike(&fwd, &vol, t, AtmConvention::Forward, true), fwd);
+        for pa in [false, true] {
+            let k = atm_strike(&fwd, &vol, t, AtmConvention::DeltaNeutral, pa);
+            let straddle = forward_delta(&fwd, &k, &vol, t, true, pa)
+                + forward_delta(&fwd, &k, &vol, t, false, pa);
+            assert!(f64::from(&straddle).abs() < 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_strike_sensitivity_to_vol() {
+        let vol = Number::Dual(Dual::new(0.12, vec!["vol".to_string()]));
+        let (fwd, t, delta) = (f(1.1), 0.75, f(-0.25));
+        let strike = |v: &Number| strike_from_forward_delta(&delta, &fwd, v, t, false, true);
+        let Number::Dual(k) = strike(&vol).unwrap() else {
+            panic!("a dual volatility gives a dual strike")
+        };
+        let h = 1e-6;
+        let fd = (f64::from(&strike(&f(0.12 + h)).unwrap())
+            - f64::from(&strike(&f(0.12 - h)).unwrap()))
+            / (2.0 * h);
+        assert!((k.dual[0] - fd).abs() < 1e-7);
+    }
+}
@@ -0,0 +1,535 @@
+use crate::dual::{MathFuncs, Number};
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, PyErr};
+use serde::{Deserialize, Serialize};
+
+/// The kind of a single barrier, being whether it is below or above spot and whether touching
+/// it knocks the option in or out.
+#[pyclass(module = "rateslib.rs", eq, eq_int)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum BarrierKind {
+    DownAndIn,
+    DownAndOut,
+    UpAndIn,
+    UpAndOut,
+}
+
+impl BarrierKind {
+    fn is_down(&self) -> bool {
+        matches!(self, BarrierKind::DownAndIn | BarrierKind::DownAndOut)
+    }
+
+    fn is_in(&self) -> bool {
+        matches!(self, BarrierKind::DownAndIn | BarrierKind::UpAndIn)
+    }
+}
+
+/// A source of the term volatility of an FX option by the level of its strike or barrier, such
+/// as a smile, or a surface at a given expiry.
+pub trait VolSmile {
+    /// Return the volatility at a strike, or a barrier, given the forward FX rate.
+    fn vol(&self, strike: &Number, forward: &Number) -> Result<Number, PyErr>;
+}
+
+/// A flat volatility, the same at every strike.
+impl VolSmile for Number {
+    fn vol(&self, _strike: &Number, _forward: &Number) -> Result<Number, PyErr> {
+        Ok(self.clone())
+    }
+}
+
+/// An FX option priced by [GarmanKohlhagen::price_from_smile].
+#[derive(Clone, Debug)]
+pub enum FXOption {
+    Vanilla {
+        strike: Number,
+        call: bool,
+    },
+    Digital {
+        strike: Number,
+        call: bool,
+    },
+    OneTouch {
+        barrier: Number,
+    },
+    NoTouch {
+        barrier: Number,
+    },
+    Barrier {
+        strike: Number,
+        barrier: Number,
+        call: bool,
+        kind: BarrierKind,
+    },
+}
+
+impl FXOption {
+    /// The level at which the volatility is taken from a smile, being the barrier for touch
+    /// options and otherwise the strike.
+    pub fn vol_level(&self) -> &Number {
+        match self {
+            FXOption::OneTouch { barrier } | FXOption::NoTouch { barrier } => barrier,
+            FXOption::Vanilla { strike, .. }
+            | FXOption::Digital { strike, .. }
+            | FXOption::Barrier { strike, .. } => strike,
+        }
+    }
+}
+
+/// The Garman-Kohlhagen market of an FX option expiring in `t` years.
+///
+/// Prices are values at the valuation date in the domestic currency per unit of foreign notional.
+/// The domestic and foreign discount factors to the payment date imply constant continuously
+/// compounded rates over the term, and the volatility given to each price is the term volatility
+/// to expiry, taken from a smile at the strike, or at the barrier for touch options. Barriers are
+/// monitored continuously and pay no rebate, and touch options pay at expiry.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Debug)]
+pub struct GarmanKohlhagen {
+    pub spot: Number,
+    pub df_domestic: Number,
+    pub df_foreign: Number,
+    pub t: f64,
+}
+
+/// The terms of the Reiner-Rubinstein single barrier formulae which are shared between options.
+struct BarrierTerms {
+    sigma: Number,
+    mu: Number,
+    log_hs: Number,
+}
+
+impl GarmanKohlhagen {
+    /// Create a market from spot, the discount factors of each currency and the time to expiry.
+    ///
+    /// # Errors
+    ///
+    /// If the spot, discount factors or time to expiry are not positive.
+    pub fn try_new(
+        spot: Number,
+        df_domestic: Number,
+        df_foreign: Number,
+        t: f64,
+    ) -> Result<Self, PyErr> {
+        if [&spot, &df_domestic, &df_foreign]
+            .iter()
+            .any(|v| f64::from(*v) <= 0.0)
+            || t <= 0.0
+        {
+            return Err(PyValueError::new_err(
+                "`spot`, discount factors and `t` must be positive.",
+            ));
+        }
+        Ok(GarmanKohlhagen {
+            spot,
+            df_domestic,
+            df_foreign,
+            t,
+        })
+    }
+
+    /// The forward FX rate at the payment date.
+    pub fn forward(&self) -> Number {
+        &self.spot * &self.df_foreign / &self.df_domestic
+    }
+
+    /// Return the price of an option with the volatility of a smile at its
+    /// [vol_level](FXOption::vol_level) against the forward.
+    ///
+    /// # Errors
+    ///
+    /// If the smile cannot return a volatility, or the option is invalid, e.g. a barrier which
+    /// is not positive.
+    pub fn price_from_smile<S: VolSmile + ?Sized>(
+        &self,
+        option: &FXOption,
+        smile: &S,
+    ) -> Result<Number, PyErr> {
+        let vol = smile.vol(option.vol_level(), &self.forward())?;
+        match option {
+            FXOption::Vanilla { strike, call } => Ok(self.vanilla(strike, &vol, *call)),
+            FXOption::Digital { strike, call } => Ok(self.digital(strike, &vol, *call)),
+            FXOption::OneTouch { barrier } => Ok(self.one_touch(barrier, &vol)),
+            FXOption::NoTouch { barrier } => Ok(self.no_touch(barrier, &vol)),
+            FXOption::Barrier {
+                strike,
+                barrier,
+                call,
+                kind,
+            } => self.barrier(strike, barrier, &vol, *call, *kind),
+        }
+    }
+
+    /// The continuously compounded cost of carry, being the domestic less the foreign rate.
+    fn carry(&self) -> Number {
+        (&self.df_foreign / &self.df_domestic).log() / self.t
+    }
+
+    /// Return `d1` and `d2` of the forward against a level.
+    fn d1_d2(&self, level: &Number, vol: &Number) -> (Number, Number) {
+        let sigma = vol * self.t.sqrt();
+        let d1 = (self.forward() / level).log() / &sigma + &sigma * 0.5;
+        let d2 = &d1 - &sigma;
+        (d1, d2)
+    }
+
+    /// Return the price of a vanilla European call or put.
+    pub fn vanilla(&self, strike: &Number, vol: &Number, call: bool) -> Number {
+        let phi = if call { 1.0 } else { -1.0 };
+        let (d1, d2) = self.d1_d2(strike, vol);
+        (&self.spot * &self.df_foreign * (&d1 * phi).norm_cdf()
+            - strike * &self.df_domestic * (&d2 * phi).norm_cdf())
+            * phi
+    }
+
+    /// Return the price of a cash-or-nothing digital, paying one unit of the domestic currency if
+    /// spot at expiry is above the strike for a call, or below it for a put.
+    pub fn digital(&self, strike: &Number, vol: &Number, call: bool) -> Number {
+        let phi = if call { 1.0 } else { -1.0 };
+        let (_, d2) = self.d1_d2(strike, vol);
+        &self.df_domestic * (d2 * phi).norm_cdf()
+    }
+
+    /// Return the risk neutral probability that spot touches the barrier before expiry, which is
+    /// one if the barrier is already touched.
+    pub fn touch_probability(&self, barrier: &Number, vol: &Number) -> Number {
+        let down = f64::from(barrier) < f64::from(&self.spot);
+        if self.is_touched(barrier, down) {
+            return Number::F64(1.0);
+        }
+        let eta = if down { 1.0 } else { -1.0 };
+        let sigma = vol * self.t.sqrt();
+        let drift = (self.carry() - vol * vol * 0.5) * self.t;
+        let h = (barrier / &self.spot).log();
+        let reflection = (&h * &drift / (&sigma * &sigma) * 2.0).exp();
+        ((&h - &drift) / &sigma * eta).norm_cdf()
+            + reflection * ((&h + &drift) / &sigma * eta).norm_cdf()
+    }
+
+    /// Return the price of a one-touch, paying one unit of the domestic currency at expiry if
+    /// spot touches the barrier before expiry.
+    pub fn one_touch(&self, barrier: &Number, vol: &Number) -> Number {
+        &self.df_domestic * self.touch_probability(barrier, vol)
+    }
+
+    /// Return the price of a no-touch, paying one unit of the domestic currency at expiry if
+    /// spot does not touch the barrier before expiry.
+    pub fn no_touch(&self, barrier: &Number, vol: &Number) -> Number {
+        &self.df_domestic * (1.0 - self.touch_probability(barrier, vol))
+    }
+
+    /// Return the price of a single barrier call or put.
+    ///
+    /// Knock-out options are priced by the formulae of Reiner and Rubinstein, and knock-in options
+    /// by the parity of a knock-in and knock-out with a vanilla.
+    ///
+    /// # Errors
+    ///
+    /// If the barrier is not positive.
+    pub fn barrier(
+        &self,
+        strike: &Number,
+        barrier: &Number,
+        vol: &Number,
+        call: bool,
+        kind: BarrierKind,
+    ) -> Result<Number, PyErr> {
+        if f64::from(barrier) <= 0.0 {
+            return Err(PyValueError::new_err("`barrier` must be positive."));
+        }
+        let vanilla = self.vanilla(strike, vol, call);
+        let knock_out = match self.is_touched(barrier, kind.is_down()) {
+            true => Number::F64(0.0),
+            false => self.knock_out(strike, barrier, vol, call, kind.is_down()),
+        };
+        match kind.is_in() {
+            true => Ok(vanilla - knock_out),
+            false => Ok(knock_out),
+        }
+    }
+
+    /// Whether spot is at or beyond a barrier.
+    fn is_touched(&self, barrier: &Number, down: bool) -> bool {
+        match down {
+            true => f64::from(&self.spot) <= f64::from(barrier),
+            false => f64::from(&self.spot) >= f64::from(barrier),
+        }
+    }
+
+    /// Return the price of a knock-out call or put whose barrier is not touched.
+    fn knock_out(
+        &self,
+        strike: &Number,
+        barrier: &Number,
+        vol: &Number,
+        call: bool,
+        down: bool,
+    ) -> Number {
+        let terms = BarrierTerms {
+            sigma: vol * self.t.sqrt(),
+            mu: (self.carry() - vol * vol * 0.5) / (vol * vol),
+            log_hs: (barrier / &self.spot).log(),
+        };
+        let (phi, eta) = (if call { 1.0 } else { -1.0 }, if down { 1.0 } else { -1.0 });
+        let above = f64::from(strike) > f64::from(barrier);
+        let a = || self.rr_direct(&terms, strike, strike, phi);
+        let b = || self.rr_direct(&terms, strike, barrier, phi);
+        let c = || self.rr_reflected(&terms, strike, &(barrier * barrier / strike), phi, eta);
+        let d = || self.rr_reflected(&terms, strike, barrier, phi, eta);
+        match (call, down, above) {
+            (true, true, true) => a() - c(),
+            (true, true, false) => b() - d(),
+            (true, false, true) => Number::F64(0.0),
+            (true, false, false) => a() - b() + c() - d(),
+            (false, true, true) => a() - b() + c() - d(),
+            (false, true, false) => Number::F64(0.0),
+            (false, false, true) => b() - d(),
+            (false, false, false) => a() - c(),
+        }
+    }
+
+    /// Return the Reiner-Rubinstein term of spot against a level, being `A` for the strike and
+    /// `B` for the barrier.
+    fn rr_direct(&self, terms: &BarrierTerms, strike: &Number, level: &Number, phi: f64) -> Number {
+        let x = (&self.spot / level).log() / &terms.sigma + (&terms.mu + 1.0) * &terms.sigma;
+        (&self.spot * &self.df_foreign * (&x * phi).norm_cdf()
+            - strike * &self.df_domestic * ((&x - &terms.sigma) * phi).norm_cdf())
+            * phi
+    }
+
+    /// Return the Reiner-Rubinstein term reflected in the barrier, being `C` for a level of
+    /// `H^2 / K` and `D` for the barrier.
+    fn rr_reflected(
+        &self,
+        terms: &BarrierTerms,
+        strike: &Number,
+        level: &Number,
+        phi: f64,
+        eta: f64,
+    ) -> Number {
+        let y = (level / &self.spot).log() / &terms.sigma + (&terms.mu + 1.0) * &terms.sigma;
+        let hs_mu = (&terms.log_hs * &terms.mu * 2.0).exp();
+        let hs_mu1 = (&terms.log_hs * (&terms.mu + 1.0) * 2.0).exp();
+        (&self.spot * &self.df_foreign * hs_mu1 * (&y * eta).norm_cdf()
+            - strike * &self.df_domestic * hs_mu * ((&y - &terms.sigma) * eta).norm_cdf())
+            * phi
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dual::Dual;
+    use crate::models::implied_vol::black_price;
+
+    fn f(v: f64) -> Number {
+        Number::F64(v)
+    }
+
+    fn market(spot: f64) -> GarmanKohlhagen {
+        GarmanKohlhagen::try_new(f(spot), f(0.97), f(0.99), 1.0).unwrap()
+    }
+
+    #[test]
+    fn test_vanilla_and_digital() {
+        let m = market(1.10);
+        let (k, vol) = (f(1.12), f(0.1));
+        let expected = black_price(&m.forward(), &k, &vol, 1.0, true) * 0.97;
+        assert!((f64::from(&(m.vanilla(&k, &vol, true) - expected))).abs() < 1e-14);
+
+        // the digital is the negative derivative of the vanilla by the strike
+        let h = 1e-6;
+        let fd = (f64::from(&m.vanilla(&f(1.12 - h), &vol, true))
+            - f64::from(&m.vanilla(&f(1.12 + h), &vol, true)))
+            / (2.0 * h);
+        assert!((f64::from(&m.digital(&k, &vol, true)) - fd).abs() < 1e-8);
+        let both = m.digital(&k, &vol, true) + m.digital(&k, &vol, false);
+        assert!((f64::from(&both) - 0.97).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_one_touch_is_twice_digital_without_drift() {
+        // with no drift of log spot the reflection principle doubles the probability at expiry
+        let vol = 0.15_f64;
+        let m = GarmanKohlhagen::try_new(f(1.1), f(0.97), f(0.97 * (0.5 * vol * vol).exp()), 1.0)
+            .unwrap();
+        for barrier in [1.0, 1.25] {
+            let digital = m.digital(&f(barrier), &f(vol), barrier > 1.1);
+            let touch = m.one_touch(&f(barrier), &f(vol));
+            assert!((f64::from(&touch) - 2.0 * f64::from(&digital)).abs() < 1e-14);
+            let total = touch + m.no_touch(&f(barrier), &f(vol));
+            assert!((f64::from(&total) - 0.97).abs() < 1e-14);
+        }
+        assert_eq!(m.touch_probability(&f(1.1), &f(vol)), f(1.0));
+    }
+
+    #[test]
+    fn test_barrier_limits_and_parity() {
+        let m = market(1.10);
+        let vol = f(0.1);
+        for call in [true, false] {
+            let vanilla = f64::from(&m.vanilla(&f(1.1), &vol, call));
+            // a remote barrier does not affect a knock-out
+            let out = m
+                .barrier(&f(1.1), &f(0.5), &vol, call, BarrierKind::DownAndOut)
+                .unwrap();
+            assert!((f64::from(&out) - vanilla).abs() < 1e-12);
+            let out = m
+                .barrier(&f(1.1), &f(2.5), &vol, call, BarrierKind::UpAndOut)
+                .unwrap();
+            assert!((f64::from(&out) - vanilla).abs() < 1e-12);
+            // a knock-out vanishes as spot approaches the barrier
+            let near = market(1.0 + 1e-9);
+            let out = near
+                .barrier(&f(1.1), &f(1.0), &vol, call, BarrierKind::DownAndOut)
+                .unwrap();
+            assert!(f64::from(&out).abs() < 1e-6);
+            // a touched knock-in is a vanilla
+            let touched = m
+                .barrier(&f(1.1), &f(1.2), &vol, call, BarrierKind::DownAndIn)
+                .unwrap();
+            assert!((f64::from(&touched) - vanilla).abs() < 1e-14);
+        }
+        // an up-and-out call struck above its barrier is worthless
+        let out = m
+            .barrier(&f(1.3), &f(1.2), &vol, true, BarrierKind::UpAndOut)
+            .unwrap();
+        assert_eq!(out, f(0.0));
+        assert!(m
+            .barrier(&f(1.1), &f(0.0), &vol, true, BarrierKind::UpAndOut)
+            .is_err());
+    }
+
+    #[test]
+    fn test_barrier_continuous_in_strike_across_barrier() {
+        let m = market(1.10);
+        let vol = f(0.12);
+        let cases = [
+            (true, 1.0, BarrierKind::DownAndOut),
+            (true, 1.2, BarrierKind::UpAndOut),
+            (false, 1.0, BarrierKind::DownAndOut),
+            (false, 1.2, BarrierKind::UpAndOut),
+            (true, 1.0, BarrierKind::DownAndIn),
+            (false, 1.2, BarrierKind::UpAndIn),
+        ];
+        for (call, barrier, kind) in cases {
+            let price =
+                |k: f64| f64::from(&m.barrier(&f(k), &f(barrier), &vol, call, kind).unwrap());
+            let (below, above) = (price(barrier - 1e-9), price(barrier + 1e-9));
+            assert!((below - above).abs() < 1e-8, "{call} {kind:?}");
+            assert!(below >= -1e-14);
+        }
+    }
+
+    /// A smile linear in strike, recording the levels at which it is queried.
+    struct LinearSmile(std::cell::RefCell<Vec<f64>>);
+
+    impl VolSmile for LinearSmile {
+        fn vol(&self, strike: &Number, forward: &Number) -> Result<Number, PyErr> {
+            self.0.borrow_mut().push(f64::from(strike));
+            Ok((strike - forward) * 0.5 + 0.1)
+        }
+    }
+
+    #[test]
+    fn test_price_from_smile_uses_vol_at_strike() {
+        let m = market(1.10);
+        let smile = LinearSmile(Default::default());
+        let fwd = f64::from(&m.forward());
+        let vol = |level: f64| (level - fwd) * 0.5 + 0.1;
+        let price = m
+            .price_from_smile(
+                &FXOption::Vanilla {
+                    strike: f(1.2),
+                    call: true,
+                },
+                &smile,
+            )
+            .unwrap();
+        assert_eq!(price, m.vanilla(&f(1.2), &f(vol(1.2)), true));
+        let price = m
+            .price_from_smile(
+                &FXOption::Barrier {
+                    strike: f(1.05),
+                    barrier: f(1.25),
+                    call: true,
+                    kind: BarrierKind::UpAndOut,
+                },
+                &smile,
+            )
+            .unwrap();
+        let expected = m
+            .barrier(
+                &f(1.05),
+                &f(1.25),
+                &f(vol(1.05)),
+                true,
+                BarrierKind::UpAndOut,
+            )
+            .unwrap();
+        assert!((f64::from(&(price - expected))).abs() < 1e-15);
+        assert_eq!(*smile.0.borrow(), vec![1.2, 1.05]);
+    }
+
+    #[test]
+    fn test_price_from_smile_uses_vol_at_barrier_for_touches() {
+        let m = market(1.10);
+        let smile = LinearSmile(Default::default());
+        let vol = f64::from(&((f(1.25) - m.forward()) * 0.5 + 0.1));
+        let one = m
+            .price_from_smile(&FXOption::OneTouch { barrier: f(1.25) }, &smile)
+            .unwrap();
+        assert_eq!(one, m.one_touch(&f(1.25), &f(vol)));
+        let no = m
+            .price_from_smile(&FXOption::NoTouch { barrier: f(1.25) }, &smile)
+            .unwrap();
+        assert!((f64::from(&(one + no)) - 0.97).abs() < 1e-14);
+        assert_eq!(*smile.0.borrow(), vec![1.25, 1.25]);
+        // a flat volatility is the raw formula
+        let digital = FXOption::Digital {
+            strike: f(1.1),
+            call: false,
+        };
+        assert_eq!(
+            m.price_from_smile(&digital, &f(0.1)).unwrap(),
+            m.digital(&f(1.1), &f(0.1), false)
+        );
+    }
+
+    #[test]
+    fn test_price_from_smile_errors() {
+        struct NoVol;
+        impl VolSmile for NoVol {
+            fn vol(&self, _: &Number, _: &Number) -> Result<Number, PyErr> {
+                Err(PyValueError::new_err("no vol"))
+            }
+        }
+        let m = market(1.10);
+        let vanilla = FXOption::Vanilla {
+            strike: f(1.1),
+            call: true,
+        };
+        assert!(m.price_from_smile(&vanilla, &NoVol).is_err());
+        let barrier = FXOption::Barrier {
+            strike: f(1.1),
+            barrier: f(0.0),
+            call: true,
+            kind: BarrierKind::DownAndOut,
+        };
+        assert!(m.price_from_smile(&barrier, &f(0.1)).is_err());
+    }
+
+    #[test]
+    fn test_spot_delta_of_vanilla() {
+        let spot = Number::Dual(Dual::new(1.10, vec!["spot".to_string()]));
+        let m = GarmanKohlhagen::try_new(spot, f(0.97), f(0.99), 1.0).unwrap();
+        let (k, vol) = (f(1.12), f(0.1));
+        let Number::Dual(price) = m.vanilla(&k, &vol, true) else {
+            panic!("a dual spot gives a dual price")
+        };
+        let (d1, _) = market(1.10).d1_d2(&k, &vol);
+        let expected = 0.99 * f64::from(&d1.norm_cdf());
+        assert!((price.dual[0] - expected).abs() < 1e-12);
+    }
+}
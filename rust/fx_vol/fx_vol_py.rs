@@ -0,0 +1,796 @@
+//! Wrapper module to export Rust FX volatility tools to Python using pyo3 bindings.
+
+use crate::calendars::CalType;
+use crate::dates::dates_py::{extract_date, extract_optional_date};
+use crate::dual::Number;
+use crate::fx::rates::{Ccy, FXRates};
+use crate::fx::rates_py::extract_ccy;
+use crate::fx_vol::conversions::{
+    atm_strike, forward_delta, spot_delta, strike_from_forward_delta, strike_from_spot_delta,
+};
+use crate::fx_vol::premium::premium_fx_rate;
+use crate::fx_vol::{
+    AtmConvention, BarrierKind, FXOption, GarmanKohlhagen, PremiumCurrency, VolSmile, VolWeights,
+};
+use crate::json::json_py::DeserializedObj;
+use crate::json::JSON;
+use crate::splines::check_number_types;
+use bincode::{deserialize, serialize};
+use chrono::NaiveDateTime;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+
+#[pymethods]
+impl VolWeights {
+    /// Create per calendar day weights of variance from a business day calendar.
+    ///
+    /// Parameters
+    /// ----------
+    /// start: datetime
+    ///     The evaluation date, from which weighted time is measured.
+    /// end: datetime
+    ///     The last date to assign a weight.
+    /// calendar: Cal, UnionCal or NamedCal
+    ///     The calendar whose business days have a weight of 1.0.
+    /// non_bus_day_weight: float
+    ///     The weight of weekends and holidays of the ``calendar``.
+    /// overrides: list[tuple[datetime, float]], optional
+    ///     Weights of specific dates, e.g. economic events, replacing those of the ``calendar``.
+    #[new]
+    #[pyo3(signature = (start, end, calendar, non_bus_day_weight, overrides=None))]
+    fn new_py(
+        #[pyo3(from_py_with = "extract_date")] start: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] end: NaiveDateTime,
+        calendar: CalType,
+        non_bus_day_weight: f64,
+        overrides: Option<Vec<(NaiveDateTime, f64)>>,
+    ) -> PyResult<Self> {
+        VolWeights::try_new(
+            start,
+            end,
+            &calendar,
+            non_bus_day_weight,
+            &overrides.unwrap_or_default(),
+        )
+    }
+
+    #[getter]
+    fn start(&self) -> NaiveDateTime {
+        self.start
+    }
+
+    #[getter]
+    #[pyo3(name = "end")]
+    fn end_py(&self) -> NaiveDateTime {
+        self.end()
+    }
+
+    /// The dates after ``start`` and their weights.
+    #[getter]
+    fn weights(&self) -> Vec<(NaiveDateTime, f64)> {
+        self.dates_and_weights()
+    }
+
+    /// Return the weighted number of days from ``start`` to a date.
+    ///
+    /// Parameters
+    /// ----------
+    /// date: datetime
+    ///     The date, between ``start`` and ``end``.
+    ///
+    /// Returns
+    /// -------
+    /// float
+    #[pyo3(name = "weighted_time")]
+    fn weighted_time_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] date: NaiveDateTime,
+    ) -> PyResult<f64> {
+        self.weighted_time(&date)
+    }
+
+    /// Return weights rescaled so that the weighted time between expiries equals calendar time.
+    ///
+    /// Parameters
+    /// ----------
+    /// expiries: list[datetime]
+    ///     The increasing expiries of a volatility surface.
+    ///
+    /// Returns
+    /// -------
+    /// VolWeights
+    #[pyo3(name = "scaled_to_expiries")]
+    fn scaled_to_expiries_py(&self, expiries: Vec<NaiveDateTime>) -> PyResult<Self> {
+        self.scaled_to_expiries(&expiries)
+    }
+
+    /// Return the volatility at an expiry by total variance interpolation in weighted time.
+    ///
+    /// Parameters
+    /// ----------
+    /// expiry: datetime
+    ///     The expiry at which to interpolate.
+    /// left_expiry: datetime
+    ///     The expiry of the left volatility, which may be ``start``.
+    /// left_vol: float, Dual or Dual2
+    ///     The volatility at ``left_expiry``.
+    /// right_expiry: datetime
+    ///     The expiry of the right volatility.
+    /// right_vol: float, Dual or Dual2
+    ///     The volatility at ``right_expiry``.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "interpolate_vol")]
+    fn interpolate_vol_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] expiry: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] left_expiry: NaiveDateTime,
+        left_vol: Number,
+        #[pyo3(from_py_with = "extract_date")] right_expiry: NaiveDateTime,
+        right_vol: Number,
+    ) -> PyResult<Number> {
+        self.interpolate_vol(
+            &expiry,
+            (&left_expiry, &left_vol),
+            (&right_expiry, &right_vol),
+        )
+    }
+
+    fn __eq__(&self, other: VolWeights) -> bool {
+        *self == other
+    }
+
+    // JSON
+    /// Create a JSON string representation of the object, with a schema version.
+    ///
+    /// Returns
+    /// -------
+    /// str
+    #[pyo3(name = "to_json")]
+    fn to_json_py(&self) -> PyResult<String> {
+        match DeserializedObj::VolWeights(self.clone()).to_json() {
+            Ok(v) => Ok(v),
+            Err(_) => Err(PyValueError::new_err(
+                "Failed to serialize `VolWeights` to JSON.",
+            )),
+        }
+    }
+
+    /// Return a hash of the canonical JSON representation of the object, which is stable
+    /// across sessions.
+    ///
+    /// Returns
+    /// -------
+    /// int
+    #[pyo3(name = "canonical_hash")]
+    fn canonical_hash_py(&self) -> PyResult<u64> {
+        match DeserializedObj::VolWeights(self.clone()).canonical_hash() {
+            Ok(v) => Ok(v),
+            Err(_) => Err(PyValueError::new_err(
+                "Failed to serialize `VolWeights` to JSON.",
+            )),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<VolWeights: {} to {}>",
+            self.start.format("%Y-%m-%d"),
+            self.end().format("%Y-%m-%d")
+        )
+    }
+}
+
+#[pymethods]
+impl AtmConvention {
+    // Pickling
+    #[new]
+    fn new_py(item: u8) -> PyResult<AtmConvention> {
+        match item {
+            0_u8 => Ok(AtmConvention::Forward),
+            1_u8 => Ok(AtmConvention::DeltaNeutral),
+            _ => Err(PyValueError::new_err(
+                "unreachable code on AtmConvention pickle.",
+            )),
+        }
+    }
+    pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
+        *self = deserialize(state.as_bytes()).unwrap();
+        Ok(())
+    }
+    pub fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new_bound(py, &serialize(&self).unwrap()))
+    }
+    pub fn __getnewargs__(&self) -> PyResult<(u8,)> {
+        match self {
+            AtmConvention::Forward => Ok((0_u8,)),
+            AtmConvention::DeltaNeutral => Ok((1_u8,)),
+        }
+    }
+}
+
+#[pymethods]
+impl BarrierKind {
+    // Pickling
+    #[new]
+    fn new_py(item: u8) -> PyResult<BarrierKind> {
+        match item {
+            0_u8 => Ok(BarrierKind::DownAndIn),
+            1_u8 => Ok(BarrierKind::DownAndOut),
+            2_u8 => Ok(BarrierKind::UpAndIn),
+            3_u8 => Ok(BarrierKind::UpAndOut),
+            _ => Err(PyValueError::new_err(
+                "unreachable code on BarrierKind pickle.",
+            )),
+        }
+    }
+    pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
+        *self = deserialize(state.as_bytes()).unwrap();
+        Ok(())
+    }
+    pub fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new_bound(py, &serialize(&self).unwrap()))
+    }
+    pub fn __getnewargs__(&self) -> PyResult<(u8,)> {
+        match self {
+            BarrierKind::DownAndIn => Ok((0_u8,)),
+            BarrierKind::DownAndOut => Ok((1_u8,)),
+            BarrierKind::UpAndIn => Ok((2_u8,)),
+            BarrierKind::UpAndOut => Ok((3_u8,)),
+        }
+    }
+}
+
+/// A *rateslib (Python)* smile or surface, whose volatility at a strike is returned by its
+/// ``get_from_strike`` method in percent.
+struct PyVolSmile<'py> {
+    smile: Bound<'py, PyAny>,
+    expiry: Option<NaiveDateTime>,
+}
+
+impl VolSmile for PyVolSmile<'_> {
+    fn vol(&self, strike: &Number, forward: &Number) -> Result<Number, PyErr> {
+        let py = self.smile.py();
+        let kwargs = PyDict::new_bound(py);
+        if let Some(expiry) = self.expiry {
+            kwargs.set_item("expiry", expiry)?;
+        }
+        let (_, vol, _): (Bound<'_, PyAny>, Number, Bound<'_, PyAny>) = self
+            .smile
+            .call_method(
+                "get_from_strike",
+                (strike.clone(), forward.clone()),
+                Some(&kwargs),
+            )?
+            .extract()?;
+        Ok(vol / 100.0)
+    }
+}
+
+#[pymethods]
+impl GarmanKohlhagen {
+    /// Create the Garman-Kohlhagen market of an FX option.
+    ///
+    /// Parameters
+    /// ----------
+    /// spot: float, Dual or Dual2
+    ///     The spot FX rate.
+    /// df_domestic: float, Dual or Dual2
+    ///     The discount factor of the domestic currency to the payment date.
+    /// df_foreign: float, Dual or Dual2
+    ///     The discount factor of the foreign currency to the payment date.
+    /// t: float
+    ///     The time to expiry in years.
+    #[new]
+    fn new_py(spot: Number, df_domestic: Number, df_foreign: Number, t: f64) -> PyResult<Self> {
+        check_number_types([&spot, &df_domestic, &df_foreign].into_iter())?;
+        GarmanKohlhagen::try_new(spot, df_domestic, df_foreign, t)
+    }
+
+    /// Return the forward FX rate at the payment date.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "forward")]
+    fn forward_py(&self) -> Number {
+        self.forward()
+    }
+
+    /// Return the price of a vanilla European option with the volatility of a smile at the
+    /// strike.
+    ///
+    /// Parameters
+    /// ----------
+    /// strike: float, Dual or Dual2
+    ///     The strike of the option.
+    /// smile: FXDeltaVolSmile or FXDeltaVolSurface
+    ///     The smile, or surface, of the option.
+    /// call: bool, optional
+    ///     Whether the option is a call or a put.
+    /// expiry: datetime, optional
+    ///     The expiry of the option, required by a surface.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "vanilla", signature = (strike, smile, call=true, expiry=None))]
+    fn vanilla_py(
+        &self,
+        strike: Number,
+        smile: Bound<'_, PyAny>,
+        call: bool,
+        #[pyo3(from_py_with = "extract_optional_date")] expiry: Option<NaiveDateTime>,
+    ) -> PyResult<Number> {
+        self.price_from_smile(
+            &FXOption::Vanilla { strike, call },
+            &PyVolSmile { smile, expiry },
+        )
+    }
+
+    /// Return the price of a cash-or-nothing digital option with the volatility of a smile at
+    /// the strike.
+    ///
+    /// Parameters
+    /// ----------
+    /// strike: float, Dual or Dual2
+    ///     The strike of the option.
+    /// smile: FXDeltaVolSmile or FXDeltaVolSurface
+    ///     The smile, or surface, of the option.
+    /// call: bool, optional
+    ///     Whether the option pays above the strike or below it.
+    /// expiry: datetime, optional
+    ///     The expiry of the option, required by a surface.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "digital", signature = (strike, smile, call=true, expiry=None))]
+    fn digital_py(
+        &self,
+        strike: Number,
+        smile: Bound<'_, PyAny>,
+        call: bool,
+        #[pyo3(from_py_with = "extract_optional_date")] expiry: Option<NaiveDateTime>,
+    ) -> PyResult<Number> {
+        self.price_from_smile(
+            &FXOption::Digital { strike, call },
+            &PyVolSmile { smile, expiry },
+        )
+    }
+
+    /// Return the price of a one-touch option with the volatility of a smile at the barrier.
+    ///
+    /// Parameters
+    /// ----------
+    /// barrier: float, Dual or Dual2
+    ///     The barrier of the option.
+    /// smile: FXDeltaVolSmile or FXDeltaVolSurface
+    ///     The smile, or surface, of the option.
+    /// expiry: datetime, optional
+    ///     The expiry of the option, required by a surface.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "one_touch", signature = (barrier, smile, expiry=None))]
+    fn one_touch_py(
+        &self,
+        barrier: Number,
+        smile: Bound<'_, PyAny>,
+        #[pyo3(from_py_with = "extract_optional_date")] expiry: Option<NaiveDateTime>,
+    ) -> PyResult<Number> {
+        self.price_from_smile(
+            &FXOption::OneTouch { barrier },
+            &PyVolSmile { smile, expiry },
+        )
+    }
+
+    /// Return the price of a no-touch option with the volatility of a smile at the barrier.
+    ///
+    /// Parameters
+    /// ----------
+    /// barrier: float, Dual or Dual2
+    ///     The barrier of the option.
+    /// smile: FXDeltaVolSmile or FXDeltaVolSurface
+    ///     The smile, or surface, of the option.
+    /// expiry: datetime, optional
+    ///     The expiry of the option, required by a surface.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "no_touch", signature = (barrier, smile, expiry=None))]
+    fn no_touch_py(
+        &self,
+        barrier: Number,
+        smile: Bound<'_, PyAny>,
+        #[pyo3(from_py_with = "extract_optional_date")] expiry: Option<NaiveDateTime>,
+    ) -> PyResult<Number> {
+        self.price_from_smile(
+            &FXOption::NoTouch { barrier },
+            &PyVolSmile { smile, expiry },
+        )
+    }
+
+    /// Return the price of a single barrier option with the volatility of a smile at the
+    /// strike.
+    ///
+    /// Parameters
+    /// ----------
+    /// strike: float, Dual or Dual2
+    ///     The strike of the option.
+    /// barrier: float, Dual or Dual2
+    ///     The barrier of the option.
+    /// kind: BarrierKind
+    ///     Whether the barrier is below or above spot and knocks the option in or out.
+    /// smile: FXDeltaVolSmile or FXDeltaVolSurface
+    ///     The smile, or surface, of the option.
+    /// call: bool, optional
+    ///     Whether the option is a call or a put.
+    /// expiry: datetime, optional
+    ///     The expiry of the option, required by a surface.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "barrier", signature = (strike, barrier, kind, smile, call=true, expiry=None))]
+    fn barrier_py(
+        &self,
+        strike: Number,
+        barrier: Number,
+        kind: BarrierKind,
+        smile: Bound<'_, PyAny>,
+        call: bool,
+        #[pyo3(from_py_with = "extract_optional_date")] expiry: Option<NaiveDateTime>,
+    ) -> PyResult<Number> {
+        let option = FXOption::Barrier {
+            strike,
+            barrier,
+            call,
+            kind,
+        };
+        self.price_from_smile(&option, &PyVolSmile { smile, expiry })
+    }
+}
+
+/// Return the forward delta of an FX option.
+///
+/// Parameters
+/// ----------
+/// forward: float, Dual or Dual2
+///     The forward FX rate at delivery.
+/// strike: float, Dual or Dual2
+///     The strike of the option.
+/// vol: float, Dual or Dual2
+///     The volatility of the option, as a decimal.
+/// t: float
+///     The time to expiry in years.
+/// call: bool
+///     Whether the option is a call or a put.
+/// premium_adjusted: bool, optional
+///     Whether the delta is adjusted for a premium paid in the foreign currency.
+///
+/// Returns
+/// -------
+/// float, Dual or Dual2
+#[pyfunction]
+#[pyo3(name = "forward_delta", signature = (forward, strike, vol, t, call, premium_adjusted=false))]
+pub fn forward_delta_py(
+    forward: Number,
+    strike: Number,
+    vol: Number,
+    t: f64,
+    call: bool,
+    premium_adjusted: bool,
+) -> Number {
+    forward_delta(&forward, &strike, &vol, t, call, premium_adjusted)
+}
+
+/// Return the spot delta of an FX option.
+///
+/// Parameters
+/// ----------
+/// forward: float, Dual or Dual2
+///     The forward FX rate at delivery.
+/// strike: float, Dual or Dual2
+///     The strike of the option.
+/// vol: float, Dual or Dual2
+///     The volatility of the option, as a decimal.
+/// t: float
+///     The time to expiry in years.
+/// df_foreign: float, Dual or Dual2
+///     The foreign currency discount factor from the spot date to delivery.
+/// call: bool
+///     Whether the option is a call or a put.
+/// premium_adjusted: bool, optional
+///     Whether the delta is adjusted for a premium paid in the foreign currency.
+///
+/// Returns
+/// -------
+/// float, Dual or Dual2
+#[pyfunction]
+#[pyo3(name = "spot_delta", signature = (forward, strike, vol, t, df_foreign, call, premium_adjusted=false))]
+pub fn spot_delta_py(
+    forward: Number,
+    strike: Number,
+    vol: Number,
+    t: f64,
+    df_foreign: Number,
+    call: bool,
+    premium_adjusted: bool,
+) -> Number {
+    spot_delta(
+        &forward,
+        &strike,
+        &vol,
+        t,
+        &df_foreign,
+        call,
+        premium_adjusted,
+    )
+}
+
+/// Return the strike of an FX option with a forward delta.
+///
+/// Parameters
+/// ----------
+/// delta: float, Dual or Dual2
+///     The forward delta, positive for a call and negative for a put.
+/// forward: float, Dual or Dual2
+///     The forward FX rate at delivery.
+/// vol: float, Dual or Dual2
+///     The volatility of the option, as a decimal.
+/// t: float
+///     The time to expiry in years.
+/// call: bool
+///     Whether the option is a call or a put.
+/// premium_adjusted: bool, optional
+///     Whether the delta is adjusted for a premium paid in the foreign currency.
+///
+/// Returns
+/// -------
+/// float, Dual or Dual2
+#[pyfunction]
+#[pyo3(name = "strike_from_forward_delta", signature = (delta, forward, vol, t, call, premium_adjusted=false))]
+pub fn strike_from_forward_delta_py(
+    delta: Number,
+    forward: Number,
+    vol: Number,
+    t: f64,
+    call: bool,
+    premium_adjusted: bool,
+) -> PyResult<Number> {
+    strike_from_forward_delta(&delta, &forward, &vol, t, call, premium_adjusted)
+}
+
+/// Return the strike of an FX option with a spot delta.
+///
+/// Parameters
+/// ----------
+/// delta: float, Dual or Dual2
+///     The spot delta, positive for a call and negative for a put.
+/// forward: float, Dual or Dual2
+///     The forward FX rate at delivery.
+/// vol: float, Dual or Dual2
+///     The volatility of the option, as a decimal.
+/// t: float
+///     The time to expiry in years.
+/// df_foreign: float, Dual or Dual2
+///     The foreign currency discount factor from the spot date to delivery.
+/// call: bool
+///     Whether the option is a call or a put.
+/// premium_adjusted: bool, optional
+///     Whether the delta is adjusted for a premium paid in the foreign currency.
+///
+/// Returns
+/// -------
+/// float, Dual or Dual2
+#[pyfunction]
+#[pyo3(name = "strike_from_spot_delta", signature = (delta, forward, vol, t, df_foreign, call, premium_adjusted=false))]
+pub fn strike_from_spot_delta_py(
+    delta: Number,
+    forward: Number,
+    vol: Number,
+    t: f64,
+    df_foreign: Number,
+    call: bool,
+    premium_adjusted: bool,
+) -> PyResult<Number> {
+    strike_from_spot_delta(
+        &delta,
+        &forward,
+        &vol,
+        t,
+        &df_foreign,
+        call,
+        premium_adjusted,
+    )
+}
+
+/// Return the at-the-money strike of an FX option.
+///
+/// Parameters
+/// ----------
+/// forward: float, Dual or Dual2
+///     The forward FX rate at delivery.
+/// vol: float, Dual or Dual2
+///     The at-the-money volatility, as a decimal.
+/// t: float
+///     The time to expiry in years.
+/// convention: AtmConvention
+///     The at-the-money convention.
+/// premium_adjusted: bool, optional
+///     Whether deltas are adjusted for a premium paid in the foreign currency, which determines
+///     the delta neutral strike.
+///
+/// Returns
+/// -------
+/// float, Dual or Dual2
+#[pyfunction]
+#[pyo3(name = "atm_strike", signature = (forward, vol, t, convention, premium_adjusted=false))]
+pub fn atm_strike_py(
+    forward: Number,
+    vol: Number,
+    t: f64,
+    convention: AtmConvention,
+    premium_adjusted: bool,
+) -> Number {
+    atm_strike(&forward, &vol, t, convention, premium_adjusted)
+}
+
+#[pymethods]
+impl PremiumCurrency {
+    /// Return the premium currency of an FX option whose premium is paid in a given currency.
+    ///
+    /// Parameters
+    /// ----------
+    /// pair: str
+    ///     The currency pair of the option, e.g. *"eurusd"*.
+    /// ccy: Ccy or str
+    ///     The currency of the premium, which must be one of the currencies of the ``pair``.
+    ///
+    /// Returns
+    /// -------
+    /// PremiumCurrency
+    #[staticmethod]
+    #[pyo3(name = "from_pair")]
+    fn from_pair_py(pair: &str, #[pyo3(from_py_with = "extract_ccy")] ccy: Ccy) -> PyResult<Self> {
+        PremiumCurrency::try_new(&pair.parse()?, &ccy)
+    }
+
+    /// Return the currency of the premium of an FX option on a pair.
+    ///
+    /// Parameters
+    /// ----------
+    /// pair: str
+    ///     The currency pair of the option, e.g. *"eurusd"*.
+    ///
+    /// Returns
+    /// -------
+    /// Ccy
+    #[pyo3(name = "ccy")]
+    fn ccy_py(&self, pair: &str) -> PyResult<Ccy> {
+        Ok(self.ccy(&pair.parse()?))
+    }
+
+    /// Whether deltas are premium adjusted, which they are for a premium in the foreign
+    /// currency.
+    #[getter]
+    #[pyo3(name = "premium_adjusted")]
+    fn premium_adjusted_py(&self) -> bool {
+        self.premium_adjusted()
+    }
+
+    /// Return the premium, in the premium currency, of a price in the domestic currency.
+    ///
+    /// Parameters
+    /// ----------
+    /// price: float, Dual or Dual2
+    ///     The price in the domestic currency, the RHS of the pair, paid on the premium date.
+    /// fx: float, Dual or Dual2
+    ///     The FX rate of the pair for settlement on the premium date.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "premium")]
+    fn premium_py(&self, price: Number, fx: Number) -> PyResult<Number> {
+        check_number_types([&price, &fx].into_iter())?;
+        Ok(self.premium(&price, &fx))
+    }
+
+    /// Return the price, in the domestic currency, of a premium in the premium currency.
+    ///
+    /// Parameters
+    /// ----------
+    /// premium: float, Dual or Dual2
+    ///     The premium in the premium currency, paid on the premium date.
+    /// fx: float, Dual or Dual2
+    ///     The FX rate of the pair for settlement on the premium date.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "price")]
+    fn price_py(&self, premium: Number, fx: Number) -> PyResult<Number> {
+        check_number_types([&premium, &fx].into_iter())?;
+        Ok(self.price(&premium, &fx))
+    }
+
+    /// Return the market quote of a price in the domestic currency, in pips for a domestic
+    /// premium or in percent of the notional for a foreign premium.
+    ///
+    /// Parameters
+    /// ----------
+    /// price: float, Dual or Dual2
+    ///     The price in the domestic currency, the RHS of the pair, per unit of notional.
+    /// fx: float, Dual or Dual2
+    ///     The FX rate of the pair for settlement on the premium date.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "quote")]
+    fn quote_py(&self, price: Number, fx: Number) -> PyResult<Number> {
+        check_number_types([&price, &fx].into_iter())?;
+        Ok(self.quote(&price, &fx))
+    }
+
+    // Pickling
+    #[new]
+    fn new_py(item: u8) -> PyResult<PremiumCurrency> {
+        match item {
+            0_u8 => Ok(PremiumCurrency::Domestic),
+            1_u8 => Ok(PremiumCurrency::Foreign),
+            _ => Err(PyValueError::new_err(
+                "unreachable code on PremiumCurrency pickle.",
+            )),
+        }
+    }
+    pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
+        *self = deserialize(state.as_bytes()).unwrap();
+        Ok(())
+    }
+    pub fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new_bound(py, &serialize(&self).unwrap()))
+    }
+    pub fn __getnewargs__(&self) -> PyResult<(u8,)> {
+        match self {
+            PremiumCurrency::Domestic => Ok((0_u8,)),
+            PremiumCurrency::Foreign => Ok((1_u8,)),
+        }
+    }
+}
+
+/// Return the FX rate of a pair for settlement on the premium date of an FX option.
+///
+/// Parameters
+/// ----------
+/// fx: FXRates
+///     The FX rates, which must settle on the premium date, or have no settlement date.
+/// pair: str
+///     The currency pair of the option, e.g. *"eurusd"*.
+/// payment: datetime
+///     The premium date.
+///
+/// Returns
+/// -------
+/// float, Dual or Dual2
+///
+/// Notes
+/// -----
+/// *FXRates* settling on another date, such as spot, raise an error rather than silently
+/// misstating a premium paid on the premium date.
+#[pyfunction]
+#[pyo3(name = "premium_fx_rate")]
+pub fn premium_fx_rate_py(
+    fx: &FXRates,
+    pair: &str,
+    #[pyo3(from_py_with = "extract_date")] payment: NaiveDateTime,
+) -> PyResult<Number> {
+    premium_fx_rate(fx, &pair.parse()?, &payment)
+}
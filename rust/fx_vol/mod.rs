@@ -0,0 +1,21 @@
+//! Tools for the volatility of FX options.
+//!
+//! Volatility smiles and surfaces are constructed in *rateslib (Python)*. This module provides
+//! supporting calculations, such as the calendar day weights used when interpolating
+//! volatility between the expiries of a surface, the analytic prices of digital, touch and
+//! barrier options given a term volatility or a smile, and the conversion of premiums between the
+//! currencies of a pair.
+
+pub mod conversions;
+pub use crate::fx_vol::conversions::AtmConvention;
+
+mod exotics;
+pub use crate::fx_vol::exotics::{BarrierKind, FXOption, GarmanKohlhagen, VolSmile};
+
+pub mod premium;
+pub use crate::fx_vol::premium::PremiumCurrency;
+
+mod weights;
+pub use crate::fx_vol::weights::{VolWeights, VOL_WEIGHTS_SCHEMA_VERSION};
+
+pub(crate) mod fx_vol_py;
@@ -0,0 +1,178 @@
+//! The currency in which the premium of an FX option is paid.
+//!
+//! An option on a pair, e.g. *"eurusd"*, is priced in the domestic currency, the RHS of the pair,
+//! per unit of notional in the foreign currency, the LHS. A premium paid in the domestic currency
+//! is quoted in pips, and a premium paid in the foreign currency is quoted in percent of the
+//! notional, for which deltas are premium adjusted. A price is converted to a foreign premium by
+//! the FX rate for settlement on the premium date, which is not the spot rate in general.
+
+use crate::dual::Number;
+use crate::fx::rates::{Ccy, FXPair, FXRates};
+use chrono::NaiveDateTime;
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, PyErr};
+use serde::{Deserialize, Serialize};
+
+/// The currency of the premium of an FX option, relative to its pair.
+#[pyclass(module = "rateslib.rs", eq, eq_int)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PremiumCurrency {
+    /// The RHS currency of the pair, with a premium quoted in pips.
+    Domestic,
+    /// The LHS currency of the pair, with a premium quoted in percent of the notional and premium
+    /// adjusted deltas.
+    Foreign,
+}
+
+impl PremiumCurrency {
+    /// Return the premium currency of an option on `pair` whose premium is paid in `ccy`.
+    ///
+    /// # Errors
+    ///
+    /// If `ccy` is not a currency of the `pair`.
+    pub fn try_new(pair: &FXPair, ccy: &Ccy) -> Result<Self, PyErr> {
+        if *ccy == pair.1 {
+            Ok(PremiumCurrency::Domestic)
+        } else if *ccy == pair.0 {
+            Ok(PremiumCurrency::Foreign)
+        } else {
+            Err(PyValueError::new_err(format!(
+                "`premium_ccy`: '{}' must be one of option currency pair: '{}'.",
+                ccy.name, pair
+            )))
+        }
+    }
+
+    /// Return the currency of the premium of an option on `pair`.
+    pub fn ccy(&self, pair: &FXPair) -> Ccy {
+        match self {
+            PremiumCurrency::Domestic => pair.1,
+            PremiumCurrency::Foreign => pair.0,
+        }
+    }
+
+    /// Whether deltas are premium adjusted, which they are for a premium in the foreign currency.
+    pub fn premium_adjusted(&self) -> bool {
+        matches!(self, PremiumCurrency::Foreign)
+    }
+
+    /// Return the premium, in the premium currency per unit of notional, of a `price` in the
+    /// domestic currency per unit of notional, with `fx` the rate of the pair for settlement on
+    /// the premium date.
+    pub fn premium(&self, price: &Number, fx: &Number) -> Number {
+        match self {
+            PremiumCurrency::Domestic => price.clone(),
+            PremiumCurrency::Foreign => price / fx,
+        }
+    }
+
+    /// Return the price, in the domestic currency per unit of notional, of a `premium` in the
+    /// premium currency per unit of notional, which is the inverse of [PremiumCurrency::premium].
+    pub fn price(&self, premium: &Number, fx: &Number) -> Number {
+        match self {
+            PremiumCurrency::Domestic => premium.clone(),
+            PremiumCurrency::Foreign => premium * fx,
+        }
+    }
+
+    /// Return the market quote of a `price`, which is pips for a domestic premium and percent
+    /// of the notional for a foreign premium.
+    pub fn quote(&self, price: &Number, fx: &Number) -> Number {
+        match self {
+            PremiumCurrency::Domestic => price * 10000.0,
+            PremiumCurrency::Foreign => self.premium(price, fx) * 100.0,
+        }
+    }
+}
+
+/// Return the rate of `pair` from `fxr` for settlement on the premium date, `payment`.
+///
+/// Rates without a settlement date are taken to settle on `payment`.
+///
+/// # Errors
+///
+/// If a currency of the `pair` is not one of the currencies of `fxr`, or its rates settle on a
+/// date other than `payment`, since a spot rate would misstate a premium paid on a later date.
+pub fn premium_fx_rate(
+    fxr: &FXRates,
+    pair: &FXPair,
+    payment: &NaiveDateTime,
+) -> Result<Number, PyErr> {
+    if let Some(settlement) = fxr.fx_rates[0].settlement {
+        if settlement != *payment {
+            return Err(PyValueError::new_err(format!(
+                "`fx` settles on {} but the premium is paid on {}. The FX rate must be for \
+                 settlement on the premium date.",
+                settlement.format("%Y-%m-%d"),
+                payment.format("%Y-%m-%d")
+            )));
+        }
+    }
+    fxr.rate(&pair.0, &pair.1).ok_or_else(|| {
+        PyValueError::new_err(format!(
+            "`fx` does not contain the currencies of the pair '{}'.",
+            pair
+        ))
+    })
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::ndt;
+    use crate::fx::rates::FXRate;
+
+    fn pair() -> FXPair {
+        FXPair::try_new("eur", "usd").unwrap()
+    }
+
+    #[test]
+    fn test_premium_currency_of_pair() {
+        let usd = Ccy::try_new("usd").unwrap();
+        let eur = Ccy::try_new("eur").unwrap();
+        let domestic = PremiumCurrency::try_new(&pair(), &usd).unwrap();
+        let foreign = PremiumCurrency::try_new(&pair(), &eur).unwrap();
+        assert_eq!(domestic, PremiumCurrency::Domestic);
+        assert_eq!(foreign, PremiumCurrency::Foreign);
+        assert_eq!((domestic.ccy(&pair()), foreign.ccy(&pair())), (usd, eur));
+        assert!(!domestic.premium_adjusted() && foreign.premium_adjusted());
+        assert!(PremiumCurrency::try_new(&pair(), &Ccy::try_new("chf").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_premium_conversion_and_quote() {
+        let (price, fx) = (Number::F64(0.0125), Number::F64(1.25));
+        let domestic = PremiumCurrency::Domestic;
+        let foreign = PremiumCurrency::Foreign;
+        assert_eq!(domestic.premium(&price, &fx), price);
+        assert_eq!(foreign.premium(&price, &fx), Number::F64(0.01));
+        assert_eq!(foreign.price(&foreign.premium(&price, &fx), &fx), price);
+        assert!((f64::from(domestic.quote(&price, &fx)) - 125.0).abs() < 1e-12);
+        assert!((f64::from(foreign.quote(&price, &fx)) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_premium_fx_rate_settlement() {
+        let payment = ndt(2024, 7, 3);
+        let fxr = FXRates::try_new(
+            vec![FXRate::try_new("eur", "usd", Number::F64(1.1), Some(payment)).unwrap()],
+            None,
+        )
+        .unwrap();
+        let result = premium_fx_rate(&fxr, &pair(), &payment).unwrap();
+        assert_eq!(f64::from(result), 1.1);
+        let inverse = premium_fx_rate(&fxr, &FXPair::try_new("usd", "eur").unwrap(), &payment);
+        assert!((f64::from(inverse.unwrap()) - 1.0 / 1.1).abs() < 1e-15);
+
+        assert!(premium_fx_rate(&fxr, &pair(), &ndt(2024, 7, 1)).is_err());
+        assert!(premium_fx_rate(&fxr, &FXPair::try_new("eur", "gbp").unwrap(), &payment).is_err());
+
+        let undated = FXRates::try_new(
+            vec![FXRate::try_new("eur", "usd", Number::F64(1.1), None).unwrap()],
+            None,
+        )
+        .unwrap();
+        assert!(premium_fx_rate(&undated, &pair(), &payment).is_ok());
+    }
+}
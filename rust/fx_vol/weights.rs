@@ -0,0 +1,318 @@
+use crate::calendars::DateRoll;
+use crate::dual::Number;
+use crate::json::JSON;
+use chrono::prelude::*;
+use chrono::Days;
+use num_traits::Pow;
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, PyErr};
+use serde::{Deserialize, Serialize};
+
+/// Per calendar day weights of variance for the temporal interpolation of volatility.
+///
+/// Each day after `start` up to and including `end` is assigned a weight, being 1.0 for a
+/// business day of a calendar, a lower weight for weekends and holidays, and any override for
+/// specific dates, e.g. higher weights for central bank meetings. The weighted time to a date is
+/// the sum of the weights of the days up to it, which replaces calendar time in total variance
+/// interpolation between expiries, following the scaling approach of forward volatility in
+/// Clark's *FX Option Pricing*.
+///
+/// Serialization stores the `start` and the daily weights with a schema version, see
+/// [VOL_WEIGHTS_SCHEMA_VERSION], and data of an earlier version is migrated on deserialization.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(into = "VolWeightsDataModel", try_from = "VolWeightsDataModel")]
+pub struct VolWeights {
+    pub(crate) start: NaiveDateTime,
+    pub(crate) weights: Vec<f64>,
+    pub(crate) cumulative: Vec<f64>,
+}
+
+/// The schema version of serialized [VolWeights].
+///
+/// Version 0 is the unversioned data which also stored the cumulative weights. Version 1 stores
+/// only the daily weights, from which the cumulative weights are derived.
+pub const VOL_WEIGHTS_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct VolWeightsDataModel {
+    #[serde(default)]
+    version: u32,
+    start: NaiveDateTime,
+    weights: Vec<f64>,
+}
+
+impl VolWeightsDataModel {
+    /// Migrate data of any earlier schema version to the current version.
+    fn migrate(self) -> Result<Self, String> {
+        match self.version {
+            // the stored cumulative weights are ignored and derived again
+            0 => Ok(Self { version: 1, ..self }),
+            VOL_WEIGHTS_SCHEMA_VERSION => Ok(self),
+            v => Err(format!(
+                "VolWeights schema version {} is newer than the supported version {}.",
+                v, VOL_WEIGHTS_SCHEMA_VERSION
+            )),
+        }
+    }
+}
+
+impl From<VolWeights> for VolWeightsDataModel {
+    fn from(weights: VolWeights) -> Self {
+        Self {
+            version: VOL_WEIGHTS_SCHEMA_VERSION,
+            start: weights.start,
+            weights: weights.weights,
+        }
+    }
+}
+
+impl TryFrom<VolWeightsDataModel> for VolWeights {
+    type Error = String;
+    fn try_from(model: VolWeightsDataModel) -> Result<Self, String> {
+        let model = model.migrate()?;
+        VolWeights::try_from_weights(model.start, model.weights)
+            .map_err(|_| "VolWeights data model contains bad data.".to_string())
+    }
+}
+
+impl JSON for VolWeights {}
+
+impl VolWeights {
+    /// Create weights from the business days of a calendar.
+    ///
+    /// Overrides of dates not after `start`, or after `end`, are ignored.
+    ///
+    /// # Errors
+    ///
+    /// If `end` is not after `start` or any weight is negative.
+    pub fn try_new<T: DateRoll>(
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+        calendar: &T,
+        non_bus_day_weight: f64,
+        overrides: &[(NaiveDateTime, f64)],
+    ) -> Result<Self, PyErr> {
+        if end <= start {
+            return Err(PyValueError::new_err("`end` must be after `start`."));
+        }
+        let mut weights: Vec<f64> = calendar
+            .cal_date_range(&(start + Days::new(1)), &end)?
+            .iter()
+            .map(|d| match calendar.is_bus_day(d) {
+                true => 1.0,
+                false => non_bus_day_weight,
+            })
+            .collect();
+        for (date, weight) in overrides {
+            if *date > start && *date <= end {
+                weights[(*date - start).num_days() as usize - 1] = *weight;
+            }
+        }
+        Self::try_from_weights(start, weights)
+    }
+
+    fn try_from_weights(start: NaiveDateTime, weights: Vec<f64>) -> Result<Self, PyErr> {
+        if weights.iter().any(|w| *w < 0.0 || !w.is_finite()) {
+            return Err(PyValueError::new_err(
+                "Volatility weights must be finite and non-negative.",
+            ));
+        }
+        let cumulative = std::iter::once(0.0)
+            .chain(weights.iter().scan(0.0, |acc, w| {
+                *acc += w;
+                Some(*acc)
+            }))
+            .collect();
+        Ok(VolWeights {
+            start,
+            weights,
+            cumulative,
+        })
+    }
+
+    /// The last date with a weight.
+    pub fn end(&self) -> NaiveDateTime {
+        self.start + Days::new(self.weights.len() as u64)
+    }
+
+    /// The dates after `start` and their weights.
+    pub fn dates_and_weights(&self) -> Vec<(NaiveDateTime, f64)> {
+        self.weights
+            .iter()
+            .enumerate()
+            .map(|(i, w)| (self.start + Days::new(i as u64 + 1), *w))
+            .collect()
+    }
+
+    /// Return the index of `date` as the number of days after `start`.
+    fn index(&self, date: &NaiveDateTime) -> Result<usize, PyErr> {
+        let days = (*date - self.start).num_days();
+        if days < 0 || days as usize > self.weights.len() {
+            return Err(PyValueError::new_err(format!(
+                "Date {} is outside the range of the volatility weights.",
+                date.format("%Y-%m-%d")
+            )));
+        }
+        Ok(days as usize)
+    }
+
+    /// The weighted number of days from `start` to `date`.
+    pub fn weighted_time(&self, date: &NaiveDateTime) -> Result<f64, PyErr> {
+        Ok(self.cumulative[self.index(date)?])
+    }
+
+    /// Return weights rescaled so that the weighted time between consecutive `expiries` equals
+    /// the calendar time between them.
+    ///
+    /// Volatilities at the expiries are then unaffected by the weights, which only redistribute
+    /// variance between them. The final interval ends at the last weighted date.
+    ///
+    /// # Errors
+    ///
+    /// If `expiries` are not increasing dates after `start` and not after the end, or an interval
+    /// has a total weight of zero.
+    pub fn scaled_to_expiries(&self, expiries: &[NaiveDateTime]) -> Result<Self, PyErr> {
+        let mut bounds = vec![0_usize];
+        for expiry in expiries {
+            bounds.push(self.index(expiry)?);
+        }
+        bounds.push(self.weights.len());
+        if bounds[1..bounds.len() - 1].windows(2).any(|w| w[0] >= w[1]) || bounds[1] == 0 {
+            return Err(PyValueError::new_err(
+                "`expiries` must be increasing and after the start of the volatility weights.",
+            ));
+        }
+        let mut weights = self.weights.clone();
+        for w in bounds.windows(2).filter(|w| w[1] > w[0]) {
+            let total: f64 = weights[w[0]..w[1]].iter().sum();
+            if total == 0.0 {
+                return Err(PyValueError::new_err(
+                    "Volatility weights between expiries cannot all be zero.",
+                ));
+            }
+            let scale = (w[1] - w[0]) as f64 / total;
+            weights[w[0]..w[1]].iter_mut().for_each(|x| *x *= scale);
+        }
+        Self::try_from_weights(self.start, weights)
+    }
+
+    /// Return the volatility at `expiry` by linear interpolation of total variance in weighted
+    /// time between the volatilities of two expiries.
+    ///
+    /// The total variance at `start` is zero, so `left` may be `start` with any volatility. The
+    /// result is annualised by calendar time, so the weights should be scaled with
+    /// [VolWeights::scaled_to_expiries] to return the given volatilities at the expiries.
+    pub fn interpolate_vol(
+        &self,
+        expiry: &NaiveDateTime,
+        left: (&NaiveDateTime, &Number),
+        right: (&NaiveDateTime, &Number),
+    ) -> Result<Number, PyErr> {
+        if left.0 >= right.0 || *expiry <= self.start {
+            return Err(PyValueError::new_err(
+                "Interpolation requires `left` before `right` and `expiry` after `start`.",
+            ));
+        }
+        let (t1, t2, t) = (
+            self.weighted_time(left.0)?,
+            self.weighted_time(right.0)?,
+            self.weighted_time(expiry)?,
+        );
+        if t2 == t1 {
+            return Err(PyValueError::new_err(
+                "Volatility weights between interpolating expiries cannot all be zero.",
+            ));
+        }
+        let var1 = left.1 * left.1 * t1;
+        let var2 = right.1 * right.1 * t2;
+        let var = &var1 + (var2 - &var1) * ((t - t1) / (t2 - t1));
+        Ok((var / ((*expiry - self.start).num_days() as f64)).pow(0.5))
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, Cal};
+    use crate::dual::Dual;
+
+    fn weights() -> VolWeights {
+        // Friday 5th Jan 2024 with a holiday on Wednesday 10th
+        let cal = Cal::new(vec![ndt(2024, 1, 10)], vec![5, 6]);
+        VolWeights::try_new(
+            ndt(2024, 1, 5),
+            ndt(2024, 1, 19),
+            &cal,
+            0.2,
+            &[(ndt(2024, 1, 11), 3.0)],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_weights() {
+        let w = weights();
+        assert_eq!(w.end(), ndt(2024, 1, 19));
+        let expected = [0.2, 0.2, 1.0, 1.0, 0.2, 3.0, 1.0];
+        assert_eq!(w.weights[..7], expected);
+        assert_eq!(w.weighted_time(&ndt(2024, 1, 5)).unwrap(), 0.0);
+        assert!((w.weighted_time(&ndt(2024, 1, 11)).unwrap() - 5.6).abs() < 1e-12);
+        assert!(w.weighted_time(&ndt(2024, 1, 20)).is_err());
+    }
+
+    #[test]
+    fn test_scaled_to_expiries() {
+        let w = weights()
+            .scaled_to_expiries(&[ndt(2024, 1, 8), ndt(2024, 1, 12)])
+            .unwrap();
+        assert!((w.weighted_time(&ndt(2024, 1, 8)).unwrap() - 3.0).abs() < 1e-12);
+        assert!((w.weighted_time(&ndt(2024, 1, 12)).unwrap() - 7.0).abs() < 1e-12);
+        assert!((w.weighted_time(&ndt(2024, 1, 19)).unwrap() - 14.0).abs() < 1e-12);
+        assert!(weights()
+            .scaled_to_expiries(&[ndt(2024, 1, 12), ndt(2024, 1, 8)])
+            .is_err());
+    }
+
+    #[test]
+    fn test_interpolate_vol_reprices_expiries() {
+        let (e1, e2) = (ndt(2024, 1, 8), ndt(2024, 1, 15));
+        let w = weights().scaled_to_expiries(&[e1, e2]).unwrap();
+        let v1 = Number::Dual(Dual::new(10.0, vec!["v1".to_string()]));
+        let v2 = Number::Dual(Dual::new(12.0, vec!["v2".to_string()]));
+        let at_e1 = w.interpolate_vol(&e1, (&e1, &v1), (&e2, &v2)).unwrap();
+        let at_e2 = w.interpolate_vol(&e2, (&e1, &v1), (&e2, &v2)).unwrap();
+        assert!((f64::from(&at_e1) - 10.0).abs() < 1e-12);
+        assert!((f64::from(&at_e2) - 12.0).abs() < 1e-12);
+
+        // the holiday carries less variance than the heavily weighted day after it
+        let before = w
+            .interpolate_vol(&ndt(2024, 1, 10), (&e1, &v1), (&e2, &v2))
+            .unwrap();
+        let after = w
+            .interpolate_vol(&ndt(2024, 1, 11), (&e1, &v1), (&e2, &v2))
+            .unwrap();
+        assert!(f64::from(&after) > f64::from(&before));
+    }
+
+    #[test]
+    fn test_json_round_trip_and_migration() {
+        let w = weights();
+        let json = w.to_json().unwrap();
+        assert!(json.starts_with("{\"version\":1,"));
+        assert!(!json.contains("cumulative"));
+        assert_eq!(VolWeights::from_json(&json).unwrap(), w);
+
+        // unversioned data with the cumulative weights
+        let v0 =
+            r#"{"start":"2024-01-05T00:00:00","weights":[0.5,1.0],"cumulative":[0.0,0.5,1.5]}"#;
+        let migrated = VolWeights::from_json(v0).unwrap();
+        assert_eq!(migrated.weighted_time(&ndt(2024, 1, 7)).unwrap(), 1.5);
+
+        let future = r#"{"version":99,"start":"2024-01-05T00:00:00","weights":[1.0]}"#;
+        assert!(VolWeights::from_json(future).is_err());
+        let bad = r#"{"version":1,"start":"2024-01-05T00:00:00","weights":[-1.0]}"#;
+        assert!(VolWeights::from_json(bad).is_err());
+    }
+}
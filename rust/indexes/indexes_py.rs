@@ -0,0 +1,208 @@
+//! Wrapper module to export Rust index definitions to Python using pyo3 bindings.
+
+use crate::calendars::{Convention, NamedCal};
+use crate::curves::curve_py::Curve;
+use crate::dates::dates_py::extract_date;
+use crate::fx::rates::Ccy;
+use crate::indexes::{get_index, index_names, register_index, IndexDefinition};
+use chrono::NaiveDateTime;
+use pyo3::prelude::*;
+
+#[pymethods]
+impl IndexDefinition {
+    #[new]
+    #[pyo3(signature = (currency, calendar, convention, tenor=None, fixing_lag=0, publication_lag=1))]
+    fn new_py(
+        currency: Ccy,
+        calendar: &str,
+        convention: Convention,
+        tenor: Option<&str>,
+        fixing_lag: u32,
+        publication_lag: u32,
+    ) -> PyResult<Self> {
+        IndexDefinition::try_new(
+            currency,
+            calendar,
+            convention,
+            tenor,
+            fixing_lag,
+            publication_lag,
+        )
+    }
+
+    /// The currency of the index.
+    #[getter(currency)]
+    fn currency_py(&self) -> Ccy {
+        self.currency
+    }
+
+    /// The named calendar of the business days of the index.
+    #[getter(calendar)]
+    fn calendar_py(&self) -> PyResult<NamedCal> {
+        self.calendar()
+    }
+
+    /// The day count convention of the index.
+    #[getter(convention)]
+    fn convention_py(&self) -> Convention {
+        self.convention
+    }
+
+    /// The tenor of a term index, or *None* for an overnight index.
+    #[getter(tenor)]
+    fn tenor_py(&self) -> Option<String> {
+        self.tenor.clone()
+    }
+
+    /// The number of business days a term index fixes before the start of its period.
+    #[getter]
+    fn fixing_lag(&self) -> u32 {
+        self.fixing_lag
+    }
+
+    /// The number of business days after its fixing date a fixing is published.
+    #[getter]
+    fn publication_lag(&self) -> u32 {
+        self.publication_lag
+    }
+
+    /// Return the fixing date of a term index for a period.
+    ///
+    /// Parameters
+    /// ----------
+    /// accrual_start: datetime
+    ///     The start date of the period.
+    ///
+    /// Returns
+    /// -------
+    /// datetime
+    #[pyo3(name = "fixing_date")]
+    fn fixing_date_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] accrual_start: NaiveDateTime,
+    ) -> PyResult<NaiveDateTime> {
+        self.fixing_date(&accrual_start)
+    }
+
+    /// Return the date on which a fixing is published.
+    ///
+    /// Parameters
+    /// ----------
+    /// fixing_date: datetime
+    ///     The fixing date.
+    ///
+    /// Returns
+    /// -------
+    /// datetime
+    #[pyo3(name = "publication_date")]
+    fn publication_date_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] fixing_date: NaiveDateTime,
+    ) -> PyResult<NaiveDateTime> {
+        self.publication_date(&fixing_date)
+    }
+
+    /// Return the fixing dates of an overnight index compounded over an accrual period.
+    ///
+    /// Parameters
+    /// ----------
+    /// accrual_start: datetime
+    ///     The adjusted start date of the accrual period.
+    /// accrual_end: datetime
+    ///     The adjusted end date of the accrual period.
+    /// lookback_days: int, optional
+    ///     The number of business days by which fixings are observed before the dates they
+    ///     accrue.
+    /// obs_shift: bool, optional
+    ///     Whether fixings are weighted by the calendar days of the shifted observation period
+    ///     rather than of the accrual period.
+    /// lockout_days: int, optional
+    ///     The number of final fixings which are frozen at the fixing of the rate cut-off date.
+    ///
+    /// Returns
+    /// -------
+    /// tuple[list[datetime], list[datetime], list[int]]
+    #[pyo3(name = "fixing_dates", signature = (accrual_start, accrual_end, lookback_days=0, obs_shift=false, lockout_days=0))]
+    fn fixing_dates_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] accrual_start: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] accrual_end: NaiveDateTime,
+        lookback_days: u32,
+        obs_shift: bool,
+        lockout_days: u32,
+    ) -> PyResult<(Vec<NaiveDateTime>, Vec<NaiveDateTime>, Vec<i64>)> {
+        let f = self.fixing_dates(
+            &accrual_start,
+            &accrual_end,
+            lookback_days,
+            obs_shift,
+            lockout_days,
+        )?;
+        Ok((f.observation_dates, f.weighting_dates, f.day_counts))
+    }
+
+    /// Validate that a curve forecasting the index has the convention of the index, raising a
+    /// *ValueError* otherwise.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The forecasting curve.
+    ///
+    /// Returns
+    /// -------
+    /// None
+    #[pyo3(name = "validate_curve")]
+    fn validate_curve_py(&self, curve: &Curve) -> PyResult<()> {
+        self.validate_curve(&curve.inner)
+    }
+
+    fn __eq__(&self, other: IndexDefinition) -> bool {
+        *self == other
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<IndexDefinition: {} {}>",
+            self.currency.name,
+            self.tenor.as_deref().unwrap_or("overnight")
+        )
+    }
+}
+
+/// Return a named index definition.
+///
+/// Parameters
+/// ----------
+/// name: str
+///     The name of the index, e.g. *"sofr"* or *"euribor3m"*.
+///
+/// Returns
+/// -------
+/// IndexDefinition
+#[pyfunction]
+#[pyo3(name = "get_index")]
+pub(crate) fn get_index_py(name: &str) -> PyResult<IndexDefinition> {
+    get_index(name)
+}
+
+/// Register a named index definition, replacing any existing index of the same name.
+///
+/// Parameters
+/// ----------
+/// name: str
+///     The name of the index.
+/// index: IndexDefinition
+///     The definition of the index.
+#[pyfunction]
+#[pyo3(name = "register_index")]
+pub(crate) fn register_index_py(name: &str, index: IndexDefinition) {
+    register_index(name, index)
+}
+
+/// Return the names of all registered indexes.
+#[pyfunction]
+#[pyo3(name = "index_names")]
+pub(crate) fn index_names_py() -> Vec<String> {
+    index_names()
+}
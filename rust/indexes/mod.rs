@@ -0,0 +1,371 @@
+//! Definitions of interest rate indexes, resolvable by name.
+//!
+//! Each index, e.g. *"sofr"* or *"euribor3m"*, defines the currency, calendar, day count
+//! convention, tenor and lags of its fixings. Objects which forecast or observe an index refer to
+//! it by name rather than each holding these parameters, so that they are defined once and
+//! objects may be validated against them.
+//!
+//! Additional indexes may be registered at runtime, either from Rust or from Python.
+//!
+//! ```rust
+//! # use rateslib::indexes::get_index;
+//! # use rateslib::calendars::Convention;
+//! let sofr = get_index("SOFR").unwrap();
+//! assert_eq!(sofr.convention(), Convention::Act360);
+//! assert_eq!(sofr.tenor(), None);
+//! ```
+
+use crate::calendars::{fixing_dates, Convention, DateRoll, FixingDates, NamedCal};
+use crate::curves::{CurveDF, CurveInterpolation};
+use crate::fx::rates::Ccy;
+use crate::scheduling::add_tenor;
+use chrono::prelude::*;
+use indexmap::IndexMap;
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, PyErr};
+use serde::{Deserialize, Serialize};
+use std::sync::{OnceLock, RwLock};
+
+pub(crate) mod indexes_py;
+
+static INDEXES: OnceLock<RwLock<IndexMap<String, IndexDefinition>>> = OnceLock::new();
+
+/// The conventions of an interest rate index.
+///
+/// An overnight index has no tenor and its daily fixings are compounded over a period. A term
+/// index has a tenor and fixes `fixing_lag` business days before the start of its period. Each
+/// fixing is published `publication_lag` business days after its fixing date.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexDefinition {
+    pub(crate) currency: Ccy,
+    pub(crate) calendar: String,
+    pub(crate) convention: Convention,
+    pub(crate) tenor: Option<String>,
+    pub(crate) fixing_lag: u32,
+    pub(crate) publication_lag: u32,
+}
+
+impl IndexDefinition {
+    /// # Errors
+    ///
+    /// If the `calendar` is not a named calendar or the `tenor` is not a valid tenor.
+    pub fn try_new(
+        currency: Ccy,
+        calendar: &str,
+        convention: Convention,
+        tenor: Option<&str>,
+        fixing_lag: u32,
+        publication_lag: u32,
+    ) -> Result<Self, PyErr> {
+        NamedCal::try_new(calendar)?;
+        if let Some(tenor) = tenor {
+            add_tenor(
+                &NaiveDate::from_ymd_opt(2000, 1, 1)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap(),
+                tenor,
+            )?;
+        }
+        Ok(IndexDefinition {
+            currency,
+            calendar: calendar.to_lowercase(),
+            convention,
+            tenor: tenor.map(|t| t.to_uppercase()),
+            fixing_lag,
+            publication_lag,
+        })
+    }
+
+    pub fn currency(&self) -> Ccy {
+        self.currency
+    }
+
+    pub fn convention(&self) -> Convention {
+        self.convention
+    }
+
+    /// The tenor of a term index, or `None` for an overnight index.
+    pub fn tenor(&self) -> Option<&str> {
+        self.tenor.as_deref()
+    }
+
+    /// The named calendar of the business days of the index.
+    pub fn calendar(&self) -> Result<NamedCal, PyErr> {
+        NamedCal::try_new(&self.calendar)
+    }
+
+    /// The fixing date of a term index for a period starting on `accrual_start`.
+    ///
+    /// # Errors
+    ///
+    /// If the index is an overnight index.
+    pub fn fixing_date(&self, accrual_start: &NaiveDateTime) -> Result<NaiveDateTime, PyErr> {
+        if self.tenor.is_none() {
+            return Err(PyValueError::new_err(
+                "An overnight index has a fixing for each business day of a period, not one fixing date.",
+            ));
+        }
+        Ok(self
+            .calendar()?
+            .lag(accrual_start, -(self.fixing_lag as i32), true))
+    }
+
+    /// The date on which the fixing of `fixing_date` is published.
+    pub fn publication_date(&self, fixing_date: &NaiveDateTime) -> Result<NaiveDateTime, PyErr> {
+        Ok(self
+            .calendar()?
+            .lag(fixing_date, self.publication_lag as i32, true))
+    }
+
+    /// The fixing dates of an overnight index compounded over an accrual period, see
+    /// [fixing_dates].
+    ///
+    /// # Errors
+    ///
+    /// If the index is a term index, or as [fixing_dates].
+    pub fn fixing_dates(
+        &self,
+        accrual_start: &NaiveDateTime,
+        accrual_end: &NaiveDateTime,
+        lookback_days: u32,
+        obs_shift: bool,
+        lockout_days: u32,
+    ) -> Result<FixingDates, PyErr> {
+        if self.tenor.is_some() {
+            return Err(PyValueError::new_err(
+                "A term index has one fixing date for a period, not compounded fixings.",
+            ));
+        }
+        fixing_dates(
+            accrual_start,
+            accrual_end,
+            &self.calendar()?,
+            lookback_days,
+            obs_shift,
+            lockout_days,
+        )
+    }
+
+    /// Validate that a curve forecasting the index has the convention of the index.
+    pub fn validate_curve<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<(), PyErr> {
+        if curve.convention != self.convention {
+            return Err(PyValueError::new_err(format!(
+                "The convention of curve '{}', {:?}, is not that of the index, {:?}.",
+                curve.id, curve.convention, self.convention
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn indexes() -> &'static RwLock<IndexMap<String, IndexDefinition>> {
+    INDEXES.get_or_init(|| RwLock::new(default_indexes()))
+}
+
+/// Return the index definition registered under `name`.
+pub fn get_index(name: &str) -> Result<IndexDefinition, PyErr> {
+    indexes()
+        .read()
+        .unwrap()
+        .get(&name.to_lowercase())
+        .cloned()
+        .ok_or_else(|| PyValueError::new_err(format!("Index '{}' not found.", name)))
+}
+
+/// Register an index definition under `name`, replacing any existing index of the same name.
+pub fn register_index(name: &str, index: IndexDefinition) {
+    indexes()
+        .write()
+        .unwrap()
+        .insert(name.to_lowercase(), index);
+}
+
+/// Return the names of all registered indexes.
+pub fn index_names() -> Vec<String> {
+    indexes().read().unwrap().keys().cloned().collect()
+}
+
+/// The name, currency, calendar, convention, tenor, fixing lag and publication lag of an index.
+type IndexData = (
+    &'static str,
+    &'static str,
+    &'static str,
+    Convention,
+    Option<&'static str>,
+    u32,
+    u32,
+);
+
+/// The indexes registered by default, with the overnight indexes followed by the term indexes.
+const DEFAULT_INDEXES: &[IndexData] = &[
+    ("sofr", "usd", "nyc", Convention::Act360, None, 0, 1),
+    ("estr", "eur", "tgt", Convention::Act360, None, 0, 1),
+    ("sonia", "gbp", "ldn", Convention::Act365F, None, 0, 1),
+    ("tona", "jpy", "tyo", Convention::Act365F, None, 0, 1),
+    ("saron", "chf", "zur", Convention::Act360, None, 0, 0),
+    ("corra", "cad", "tro", Convention::Act365F, None, 0, 1),
+    ("swestr", "sek", "stk", Convention::Act360, None, 0, 1),
+    (
+        "euribor1m",
+        "eur",
+        "tgt",
+        Convention::Act360,
+        Some("1M"),
+        2,
+        0,
+    ),
+    (
+        "euribor3m",
+        "eur",
+        "tgt",
+        Convention::Act360,
+        Some("3M"),
+        2,
+        0,
+    ),
+    (
+        "euribor6m",
+        "eur",
+        "tgt",
+        Convention::Act360,
+        Some("6M"),
+        2,
+        0,
+    ),
+    (
+        "stibor3m",
+        "sek",
+        "stk",
+        Convention::Act360,
+        Some("3M"),
+        2,
+        0,
+    ),
+    (
+        "nibor3m",
+        "nok",
+        "osl",
+        Convention::Act360,
+        Some("3M"),
+        2,
+        0,
+    ),
+    (
+        "bbsw3m",
+        "aud",
+        "syd",
+        Convention::Act365F,
+        Some("3M"),
+        0,
+        0,
+    ),
+];
+
+fn default_indexes() -> IndexMap<String, IndexDefinition> {
+    DEFAULT_INDEXES
+        .iter()
+        .map(
+            |(name, ccy, cal, convention, tenor, fixing_lag, publication_lag)| {
+                let index = IndexDefinition::try_new(
+                    Ccy::try_new(ccy).unwrap(),
+                    cal,
+                    *convention,
+                    *tenor,
+                    *fixing_lag,
+                    *publication_lag,
+                )
+                .unwrap();
+                (name.to_string(), index)
+            },
+        )
+        .collect()
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, Modifier};
+    use crate::curves::{LogLinearInterpolator, Nodes};
+
+    #[test]
+    fn test_default_indexes() {
+        let estr = get_index("ESTR").unwrap();
+        assert_eq!(estr.currency(), Ccy::try_new("eur").unwrap());
+        assert_eq!(estr.tenor(), None);
+        let euribor = get_index("euribor3m").unwrap();
+        assert_eq!(euribor.tenor(), Some("3M"));
+        assert!(get_index("libor").is_err());
+        assert!(index_names().contains(&"sonia".to_string()));
+    }
+
+    #[test]
+    fn test_fixing_and_publication_dates() {
+        let euribor = get_index("euribor3m").unwrap();
+        // Monday 3rd April 2023 fixes on the Thursday before
+        assert_eq!(
+            euribor.fixing_date(&ndt(2023, 4, 3)).unwrap(),
+            ndt(2023, 3, 30)
+        );
+        assert!(euribor
+            .fixing_dates(&ndt(2023, 4, 3), &ndt(2023, 7, 3), 0, false, 0)
+            .is_err());
+        let sofr = get_index("sofr").unwrap();
+        // published the next business day, after the weekend
+        assert_eq!(
+            sofr.publication_date(&ndt(2023, 3, 31)).unwrap(),
+            ndt(2023, 4, 3)
+        );
+        assert!(sofr.fixing_date(&ndt(2023, 4, 3)).is_err());
+        let fixings = sofr
+            .fixing_dates(&ndt(2023, 4, 3), &ndt(2023, 4, 10), 2, false, 0)
+            .unwrap();
+        assert_eq!(fixings.observation_dates[0], ndt(2023, 3, 30));
+    }
+
+    #[test]
+    fn test_validate_curve_and_registration() {
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2022, 1, 1), 1.0_f64),
+            (ndt(2027, 1, 1), 0.9_f64),
+        ]));
+        let curve = CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            "sonia",
+            Convention::Act360,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("ldn").unwrap(),
+        )
+        .unwrap();
+        assert!(get_index("sofr").unwrap().validate_curve(&curve).is_ok());
+        assert!(get_index("sonia").unwrap().validate_curve(&curve).is_err());
+
+        let index = IndexDefinition::try_new(
+            Ccy::try_new("nzd").unwrap(),
+            "wlg",
+            Convention::Act365F,
+            Some("3m"),
+            0,
+            0,
+        )
+        .unwrap();
+        register_index("BKBM3M", index.clone());
+        assert_eq!(get_index("bkbm3m").unwrap(), index);
+        assert!(IndexDefinition::try_new(
+            Ccy::try_new("nzd").unwrap(),
+            "bad",
+            Convention::Act365F,
+            None,
+            0,
+            0
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,618 @@
+use crate::accrued::{is_ex_div, AccrualConvention, AccrualPeriod};
+use crate::calendars::{CalType, Convention, DateRoll, RollDay};
+use crate::curves::{CurveDF, CurveInterpolation};
+use crate::dual::{Dual, MathFuncs, Number};
+use crate::instruments::leg::{unadjusted_schedule, year_fraction};
+use crate::maths::roots::bracketed_root;
+use crate::scheduling::unadjusted_add_months;
+use crate::specs::{
+    fill_from_spec, fill_from_spec_or, get_optional_spec, spec_calendar, spec_days,
+    spec_frequency_months,
+};
+use chrono::prelude::*;
+use num_traits::Pow;
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, PyErr};
+use serde::{Deserialize, Serialize};
+
+/// The bracket, in percent, of the yield-to-maturity.
+const YTM_BRACKET: (f64, f64) = (-50.0, 500.0);
+
+/// The bracket, in basis points, of the z-spread.
+const Z_SPREAD_BRACKET: (f64, f64) = (-10000.0, 10000.0);
+
+/// A fixed rate bond with a regular schedule rolled backwards from termination, per 100 nominal.
+///
+/// Any period between `effective` and the first regular coupon date is a short front stub.
+/// Coupon dates are unadjusted and coupons are paid on the following settleable business day.
+///
+/// Any of the frequency, calendar and ex-dividend days which is not given is filled from an
+/// instrument [spec](crate::specs), e.g. *"us_gb"*. Ex-dividend days which are neither given nor
+/// specified default to zero.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FixedRateBond {
+    pub(crate) effective: NaiveDateTime,
+    pub(crate) termination: NaiveDateTime,
+    pub(crate) fixed_rate: f64,
+    pub(crate) frequency_months: i32,
+    pub(crate) calendar: CalType,
+    pub(crate) accrual: AccrualConvention,
+    pub(crate) ex_div: i32,
+    pub(crate) uschedule: Vec<NaiveDateTime>,
+}
+
+impl FixedRateBond {
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_new(
+        effective: NaiveDateTime,
+        termination: NaiveDateTime,
+        fixed_rate: f64,
+        frequency_months: Option<i32>,
+        calendar: Option<CalType>,
+        accrual: AccrualConvention,
+        ex_div: Option<i32>,
+        spec: Option<&str>,
+    ) -> Result<Self, PyErr> {
+        let spec = get_optional_spec(spec)?;
+        let spec = spec.as_ref();
+        let frequency_months =
+            fill_from_spec(frequency_months, spec, "frequency", spec_frequency_months)?;
+        let calendar = fill_from_spec(calendar, spec, "calendar", spec_calendar)?;
+        let ex_div = fill_from_spec_or(ex_div, spec, "ex_div", spec_days, 0)?;
+        if ![1, 2, 3, 4, 6, 12].contains(&frequency_months) {
+            return Err(PyValueError::new_err(
+                "`frequency_months` must be in {1, 2, 3, 4, 6, 12}.",
+            ));
+        }
+        if effective >= termination {
+            return Err(PyValueError::new_err(
+                "`effective` must be before `termination`.",
+            ));
+        }
+        let uschedule = unadjusted_schedule(&effective, &termination, frequency_months);
+        Ok(FixedRateBond {
+            effective,
+            termination,
+            fixed_rate,
+            frequency_months,
+            calendar,
+            accrual,
+            ex_div,
+            uschedule,
+        })
+    }
+
+    /// The number of coupon periods.
+    pub fn n_periods(&self) -> usize {
+        self.uschedule.len() - 1
+    }
+
+    fn frequency(&self) -> f64 {
+        12.0 / self.frequency_months as f64
+    }
+
+    fn roll(&self) -> RollDay {
+        RollDay::Int {
+            day: self.termination.day(),
+        }
+    }
+
+    /// Whether the coupon period is a stub period.
+    pub fn stub(&self, period: usize) -> bool {
+        period == 0
+            && self.uschedule[0]
+                != unadjusted_add_months(&self.uschedule[1], -self.frequency_months, &self.roll())
+    }
+
+    /// The day count fraction of the coupon period under *ActActICMA*.
+    pub fn dcf(&self, period: usize) -> Result<f64, PyErr> {
+        Convention::ActActICMA.dcf(
+            &self.uschedule[period],
+            &self.uschedule[period + 1],
+            Some(&self.termination),
+            Some(self.frequency_months),
+            Some(self.stub(period)),
+            Some(&self.roll()),
+            None,
+        )
+    }
+
+    /// The coupon cashflow of the period per 100 nominal.
+    pub fn cashflow(&self, period: usize) -> Result<f64, PyErr> {
+        Ok(self.fixed_rate * self.dcf(period)?)
+    }
+
+    /// The payment date of the coupon period.
+    pub fn payment(&self, period: usize) -> NaiveDateTime {
+        self.calendar.lag(&self.uschedule[period + 1], 0, true)
+    }
+
+    /// The index of the coupon period containing `settlement`.
+    ///
+    /// Settlement on a coupon date is contained by the period ending on that date.
+    pub fn period_index(&self, settlement: &NaiveDateTime) -> usize {
+        let i = self.uschedule.partition_point(|d| d < settlement);
+        i.saturating_sub(1).min(self.n_periods() - 1)
+    }
+
+    fn accrual_period(&self, period: usize) -> AccrualPeriod {
+        AccrualPeriod::new(
+            self.uschedule[period],
+            self.uschedule[period + 1],
+            self.frequency_months,
+            self.stub(period),
+            self.roll(),
+        )
+    }
+
+    /// Whether `settlement` is in the ex-dividend period of its coupon.
+    pub fn is_ex_div(&self, settlement: &NaiveDateTime) -> bool {
+        let i = self.period_index(settlement);
+        is_ex_div(
+            settlement,
+            &self.uschedule[i + 1],
+            self.ex_div,
+            &self.calendar,
+            false,
+        )
+    }
+
+    /// The accrued interest at `settlement` per 100 nominal.
+    pub fn accrued(&self, settlement: &NaiveDateTime) -> Result<f64, PyErr> {
+        let i = self.period_index(settlement);
+        self.accrual.accrued(
+            settlement,
+            &self.accrual_period(i),
+            self.cashflow(i)?,
+            self.is_ex_div(settlement),
+        )
+    }
+
+    /// The price of the bond from its yield-to-maturity, compounded at the coupon frequency.
+    ///
+    /// Stub periods are discounted by exponents scaled by their size relative to a regular period.
+    pub fn price(
+        &self,
+        ytm: &Number,
+        settlement: &NaiveDateTime,
+        dirty: bool,
+    ) -> Result<Number, PyErr> {
+        let f = self.frequency();
+        let n = self.n_periods();
+        let acc_idx = self.period_index(settlement);
+        let v2 = 1.0_f64 / (ytm / (100.0 * f) + 1.0);
+        let acc_frac = self
+            .accrual
+            .fraction(settlement, &self.accrual_period(acc_idx))?;
+        let fd0 = if self.stub(acc_idx) {
+            self.dcf(acc_idx)? * f * (1.0 - acc_frac)
+        } else {
+            1.0 - acc_frac
+        };
+        let v1 = (&v2).pow(fd0);
+        let v3 = if self.stub(n - 1) {
+            (&v2).pow(self.dcf(n - 1)? * f)
+        } else {
+            v2.clone()
+        };
+        let discount = |i: usize| -> Number {
+            if i == 0 {
+                v1.clone()
+            } else {
+                &v1 * (&v2).pow((i - 1) as f64) * &v3
+            }
+        };
+
+        let ex_div = self.is_ex_div(settlement);
+        let mut d = Number::F64(0.0);
+        for (i, p_idx) in (acc_idx..n).enumerate() {
+            if i == 0 && ex_div {
+                // no coupon cashflow is receivable
+                continue;
+            }
+            let v = if p_idx == n - 1 {
+                discount(i)
+            } else {
+                &v1 * (&v2).pow(i as f64)
+            };
+            d = d + v * self.cashflow(p_idx)?;
+        }
+        d = d + discount(n - 1 - acc_idx) * 100.0;
+
+        if dirty {
+            Ok(d)
+        } else {
+            Ok(d - self.accrued(settlement)?)
+        }
+    }
+
+    /// The yield-to-maturity of the bond from its price, solved by [bracketed_root].
+    pub fn ytm(&self, price: f64, settlement: &NaiveDateTime, dirty: bool) -> Result<f64, PyErr> {
+        let f = |ytm: f64| -> Result<(f64, Option<f64>), PyErr> {
+            let y = Number::Dual(Dual::new(ytm, vec!["y".to_string()]));
+            match self.price(&y, settlement, dirty)? {
+                Number::Dual(d) => Ok((d.real - price, Some(d.dual[0]))),
+                _ => unreachable!("price of a Dual ytm is a Dual"),
+            }
+        };
+        bracketed_root(f, self.fixed_rate, YTM_BRACKET, 1e-12)
+    }
+
+    /// The coupon periods paid between `settlement` and `forward_settlement`, excluding any
+    /// coupon not received at `settlement` and including any received at `forward_settlement`.
+    fn interim_periods(
+        &self,
+        settlement: &NaiveDateTime,
+        forward_settlement: &NaiveDateTime,
+    ) -> std::ops::Range<usize> {
+        let start = self.period_index(settlement) + usize::from(self.is_ex_div(settlement));
+        let end =
+            self.period_index(forward_settlement) + usize::from(self.is_ex_div(forward_settlement));
+        start..end
+    }
+
+    /// The forward price of the bond implied by a repo rate, under the proceeds method.
+    #[allow(clippy::too_many_arguments)]
+    pub fn fwd_from_repo(
+        &self,
+        price: f64,
+        settlement: &NaiveDateTime,
+        forward_settlement: &NaiveDateTime,
+        repo_rate: f64,
+        convention: &Convention,
+        dirty: bool,
+    ) -> Result<f64, PyErr> {
+        let dirty_price = if dirty {
+            price
+        } else {
+            price + self.accrued(settlement)?
+        };
+        let dcf = convention.dcf(settlement, forward_settlement, None, None, None, None, None)?;
+        let mut forward_price = dirty_price * (1.0 + repo_rate * dcf / 100.0);
+        for p_idx in self.interim_periods(settlement, forward_settlement) {
+            let dcf = convention.dcf(
+                &self.payment(p_idx),
+                forward_settlement,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )?;
+            forward_price -= self.cashflow(p_idx)? * (1.0 + dcf * repo_rate / 100.0);
+        }
+        if dirty {
+            Ok(forward_price)
+        } else {
+            Ok(forward_price - self.accrued(forward_settlement)?)
+        }
+    }
+
+    /// The repo rate implied by a forward price of the bond, under the proceeds method.
+    #[allow(clippy::too_many_arguments)]
+    pub fn repo_from_fwd(
+        &self,
+        price: f64,
+        settlement: &NaiveDateTime,
+        forward_settlement: &NaiveDateTime,
+        forward_price: f64,
+        convention: &Convention,
+        dirty: bool,
+    ) -> Result<f64, PyErr> {
+        let (p_t, p_0) = if dirty {
+            (forward_price, price)
+        } else {
+            (
+                forward_price + self.accrued(forward_settlement)?,
+                price + self.accrued(settlement)?,
+            )
+        };
+        let dcf = convention.dcf(settlement, forward_settlement, None, None, None, None, None)?;
+        let mut numerator = p_t - p_0;
+        let mut denominator = p_0 * dcf;
+        for p_idx in self.interim_periods(settlement, forward_settlement) {
+            let dcf = convention.dcf(
+                &self.payment(p_idx),
+                forward_settlement,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )?;
+            numerator += self.cashflow(p_idx)?;
+            denominator -= dcf * self.cashflow(p_idx)?;
+        }
+        Ok(numerator / denominator * 100.0)
+    }
+
+    /// The price of the bond at `settlement` implied by discount factors of a curve.
+    ///
+    /// The result carries any sensitivities to the curve's nodes.
+    pub fn price_from_curve<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+        settlement: &NaiveDateTime,
+        dirty: bool,
+    ) -> Result<Number, PyErr> {
+        let (price, _) = self.spread_price(curve, settlement, &Number::F64(0.0))?;
+        if dirty {
+            Ok(price)
+        } else {
+            Ok(price - self.accrued(settlement)?)
+        }
+    }
+
+    /// The dirty price of the bond implied by discount factors of a curve with the continuously
+    /// compounded zero rates shifted by `z_spread` basis points, and its derivative by the
+    /// spread.
+    ///
+    /// Times are measured from `settlement` in years of 365 days.
+    fn spread_price<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+        settlement: &NaiveDateTime,
+        z_spread: &Number,
+    ) -> Result<(Number, f64), PyErr> {
+        let n = self.n_periods();
+        let start = self.period_index(settlement) + usize::from(self.is_ex_div(settlement));
+        let t_s = year_fraction(curve, settlement);
+        let mut npv = Number::F64(0.0);
+        let mut slope = 0.0;
+        for p_idx in start..n {
+            let mut cashflow = self.cashflow(p_idx)?;
+            if p_idx == n - 1 {
+                cashflow += 100.0;
+            }
+            let payment = self.payment(p_idx);
+            let tau = year_fraction(curve, &payment) - t_s;
+            let pv =
+                curve.interpolated_value(&payment) * (z_spread * (-tau / 10000.0)).exp() * cashflow;
+            slope -= f64::from(&pv) * tau / 10000.0;
+            npv = npv + pv;
+        }
+        let v_s = curve.interpolated_value(settlement);
+        let slope = slope / f64::from(&v_s);
+        Ok((npv / v_s, slope))
+    }
+
+    /// The z-spread, in basis points, by which the continuously compounded zero rates of a curve
+    /// are shifted for its discount factors to imply the `price` of the bond.
+    ///
+    /// The spread is solved by [bracketed_root] in [f64] and then refined by a Newton step in the
+    /// arithmetic of the curve, so that it carries the sensitivities to the curve's nodes.
+    pub fn z_spread<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        price: f64,
+        curve: &CurveDF<T, U>,
+        settlement: &NaiveDateTime,
+        dirty: bool,
+    ) -> Result<Number, PyErr> {
+        let target = if dirty {
+            price
+        } else {
+            price + self.accrued(settlement)?
+        };
+        let f = |z: f64| -> Result<(f64, Option<f64>), PyErr> {
+            let (p, slope) = self.spread_price(curve, settlement, &Number::F64(z))?;
+            Ok((f64::from(&p) - target, Some(slope)))
+        };
+        let z = bracketed_root(f, 0.0, Z_SPREAD_BRACKET, 1e-10)?;
+        let (p, slope) = self.spread_price(curve, settlement, &Number::F64(z))?;
+        Ok((p - target) * (-1.0 / slope) + z)
+    }
+
+    /// The par rate of the bond, being the fixed rate, in percent, for which its clean price at
+    /// `settlement` implied by discount factors of a curve is 100.
+    ///
+    /// The clean price is linear in the fixed rate so the rate is solved exactly, and carries the
+    /// sensitivities to the curve's nodes.
+    pub fn par_rate<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+        settlement: &NaiveDateTime,
+    ) -> Result<Number, PyErr> {
+        let with_rate = |fixed_rate: f64| -> Result<Number, PyErr> {
+            FixedRateBond {
+                fixed_rate,
+                ..self.clone()
+            }
+            .price_from_curve(curve, settlement, false)
+        };
+        let (p0, p1) = (with_rate(0.0)?, with_rate(1.0)?);
+        Ok((&p0 * -1.0 + 100.0) / (p1 - p0))
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, Cal, Modifier, NamedCal};
+    use crate::curves::{LogLinearInterpolator, Nodes};
+    use crate::dual::{ADOrder, Gradient1};
+    use indexmap::IndexMap;
+
+    fn gilt(termination: NaiveDateTime, fixed_rate: f64) -> FixedRateBond {
+        FixedRateBond::try_new(
+            ndt(1999, 1, 1),
+            termination,
+            fixed_rate,
+            Some(6),
+            Some(CalType::Cal(Cal::new(vec![], vec![]))),
+            AccrualConvention::LinearDays,
+            Some(7),
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_schedule() {
+        let bond = gilt(ndt(2001, 12, 7), 5.75);
+        assert_eq!(
+            bond.uschedule,
+            vec![
+                ndt(1999, 1, 1),
+                ndt(1999, 6, 7),
+                ndt(1999, 12, 7),
+                ndt(2000, 6, 7),
+                ndt(2000, 12, 7),
+                ndt(2001, 6, 7),
+                ndt(2001, 12, 7),
+            ]
+        );
+        assert!(bond.stub(0));
+        assert!(!bond.stub(1));
+        assert_eq!(bond.period_index(&ndt(1999, 12, 7)), 1);
+        assert_eq!(bond.period_index(&ndt(1999, 12, 8)), 2);
+    }
+
+    #[test]
+    fn test_bond_conventions_from_spec() {
+        let bond = FixedRateBond::try_new(
+            ndt(2020, 2, 15),
+            ndt(2030, 2, 15),
+            1.5,
+            None,
+            None,
+            AccrualConvention::LinearDaysLongFrontSplit,
+            None,
+            Some("us_gb"),
+        )
+        .unwrap();
+        assert_eq!(bond.frequency_months, 6);
+        assert_eq!(bond.ex_div, 1);
+        assert_eq!(
+            bond.calendar,
+            CalType::NamedCal(NamedCal::try_new("nyc").unwrap())
+        );
+        assert!(FixedRateBond::try_new(
+            ndt(2020, 2, 15),
+            ndt(2030, 2, 15),
+            1.5,
+            None,
+            None,
+            AccrualConvention::LinearDays,
+            None,
+            Some("bad_spec"),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_invalid_bond_raises() {
+        let cal = CalType::NamedCal(NamedCal::try_new("ldn").unwrap());
+        let result = FixedRateBond::try_new(
+            ndt(2000, 1, 1),
+            ndt(2010, 1, 1),
+            5.0,
+            Some(5),
+            Some(cal),
+            AccrualConvention::LinearDays,
+            Some(7),
+            None,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_price_ytm_roundtrip() {
+        let bond = gilt(ndt(2009, 12, 7), 5.75);
+        let price = bond
+            .price(&Number::F64(5.384243), &ndt(2000, 3, 16), false)
+            .unwrap();
+        assert!((f64::from(&price) - 102.732).abs() < 1e-4);
+        let ytm = bond.ytm(102.732, &ndt(2000, 3, 16), false).unwrap();
+        assert!((ytm - 5.384243).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_ex_div_accrued() {
+        let bond = gilt(ndt(2009, 12, 7), 5.75);
+        // ex-div date is 7 calendar days before the coupon with no holidays
+        assert!(!bond.is_ex_div(&ndt(2000, 5, 31)));
+        assert!(bond.is_ex_div(&ndt(2000, 6, 1)));
+        let accrued = bond.accrued(&ndt(2000, 6, 1)).unwrap();
+        assert!((accrued - (-6.0 / 183.0 * 2.875)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_repo_roundtrip() {
+        let bond = gilt(ndt(2009, 12, 7), 5.75);
+        let (s, f) = (ndt(2000, 3, 16), ndt(2000, 6, 30));
+        let fwd = bond
+            .fwd_from_repo(102.732, &s, &f, 6.24, &Convention::Act365F, false)
+            .unwrap();
+        let repo = bond
+            .repo_from_fwd(102.732, &s, &f, fwd, &Convention::Act365F, false)
+            .unwrap();
+        assert!((repo - 6.24).abs() < 1e-10);
+    }
+
+    fn curve(df: f64) -> CurveDF<LogLinearInterpolator, NamedCal> {
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2000, 1, 1), 1.0_f64),
+            (ndt(2005, 1, 1), df),
+            (ndt(2011, 1, 1), 0.55_f64),
+        ]));
+        CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            "gilt",
+            Convention::Act365F,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_z_spread() {
+        let bond = gilt(ndt(2009, 12, 7), 5.75);
+        let s = ndt(2000, 3, 16);
+        let (dirty, _) = bond
+            .spread_price(&curve(0.78), &s, &Number::F64(30.0))
+            .unwrap();
+        let clean = f64::from(dirty) - bond.accrued(&s).unwrap();
+        let z = bond.z_spread(clean, &curve(0.78), &s, false).unwrap();
+        assert!((f64::from(z) - 30.0).abs() < 1e-8);
+        let price = f64::from(bond.price_from_curve(&curve(0.78), &s, true).unwrap());
+        let z = bond.z_spread(price, &curve(0.78), &s, true).unwrap();
+        assert!(f64::from(z).abs() < 1e-8);
+
+        let mut c = curve(0.78);
+        c.set_ad_order(ADOrder::One).unwrap();
+        let Number::Dual(z) = bond.z_spread(clean, &c, &s, false).unwrap() else {
+            panic!("expected a Dual z-spread")
+        };
+        let h = 1e-6;
+        let fd = (f64::from(bond.z_spread(clean, &curve(0.78 + h), &s, false).unwrap())
+            - f64::from(bond.z_spread(clean, &curve(0.78 - h), &s, false).unwrap()))
+            / (2.0 * h);
+        assert!((z.gradient1(vec!["gilt1".to_string()])[0] - fd).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_unattainable_price_errors() {
+        let bond = gilt(ndt(2009, 12, 7), 5.75);
+        let s = ndt(2000, 3, 16);
+        assert!(bond.ytm(-1.0, &s, false).is_err());
+        assert!(bond.z_spread(-1.0, &curve(0.78), &s, false).is_err());
+    }
+
+    #[test]
+    fn test_par_rate() {
+        let bond = gilt(ndt(2009, 12, 7), 5.75);
+        let s = ndt(2000, 3, 16);
+        let mut c = curve(0.78);
+        c.set_ad_order(ADOrder::One).unwrap();
+        let par = bond.par_rate(&c, &s).unwrap();
+        let par_bond = gilt(ndt(2009, 12, 7), f64::from(&par));
+        let price = par_bond.price_from_curve(&curve(0.78), &s, false).unwrap();
+        assert!((f64::from(price) - 100.0).abs() < 1e-10);
+        assert!(matches!(par, Number::Dual(_)));
+    }
+}
@@ -0,0 +1,514 @@
+use crate::calendars::Convention;
+use crate::calendars::DateRoll;
+use crate::curves::{CurveDF, CurveInterpolation};
+use crate::dual::Number;
+use crate::instruments::bond::FixedRateBond;
+use chrono::prelude::*;
+use chrono::Months;
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, PyErr};
+use serde::{Deserialize, Serialize};
+
+/// The exchange method for calculating the conversion factors of a bond future's basket.
+#[pyclass(module = "rateslib.rs", eq, eq_int)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ConversionMethod {
+    /// The clean price, per unit, of the bond on the first delivery day with a yield-to-maturity
+    /// equal to the notional coupon of the future, e.g. ICE Long Gilt.
+    Ytm,
+    /// The CME formula for short dated treasury futures, e.g. 2Y, 3Y and 5Y.
+    UstShort,
+    /// The CME formula for long dated treasury futures, with months to maturity rounded down to
+    /// whole quarters, e.g. 10Y, Bond and Ultra.
+    UstLong,
+    /// The EUREX formula for annual coupon bonds, rounded to 6 decimal places, e.g. Schatz, Bobl,
+    /// Bund and Buxl. Coupon dates are the anniversaries of maturity, so a long or short first
+    /// coupon is not reflected.
+    Eurex,
+}
+
+/// A bond future with a delivery window and a basket of deliverable bonds.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BondFuture {
+    pub(crate) coupon: f64,
+    pub(crate) delivery: (NaiveDateTime, NaiveDateTime),
+    pub(crate) basket: Vec<FixedRateBond>,
+    pub(crate) method: ConversionMethod,
+    pub(crate) cfs: Vec<f64>,
+}
+
+impl BondFuture {
+    pub fn try_new(
+        coupon: f64,
+        delivery: (NaiveDateTime, NaiveDateTime),
+        basket: Vec<FixedRateBond>,
+        method: ConversionMethod,
+    ) -> Result<Self, PyErr> {
+        if delivery.0 > delivery.1 {
+            return Err(PyValueError::new_err(
+                "The first `delivery` day must not be after the last.",
+            ));
+        }
+        let cfs = basket
+            .iter()
+            .map(|bond| conversion_factor(bond, coupon, &delivery.0, &method))
+            .collect::<Result<Vec<f64>, PyErr>>()?;
+        Ok(BondFuture {
+            coupon,
+            delivery,
+            basket,
+            method,
+            cfs,
+        })
+    }
+
+    /// The conversion factors of each bond in the basket.
+    pub fn conversion_factors(&self) -> &[f64] {
+        &self.cfs
+    }
+
+    fn validate_prices(&self, prices: &[f64]) -> Result<(), PyErr> {
+        if prices.len() != self.basket.len() {
+            return Err(PyValueError::new_err(
+                "`prices` must have the same length as the `basket`.",
+            ));
+        }
+        Ok(())
+    }
+
+    /// The gross basis of each bond in the basket from clean prices.
+    pub fn gross_basis(&self, future_price: f64, prices: &[f64]) -> Result<Vec<f64>, PyErr> {
+        self.validate_prices(prices)?;
+        Ok(prices
+            .iter()
+            .zip(self.cfs.iter())
+            .map(|(p, cf)| p - cf * future_price)
+            .collect())
+    }
+
+    /// The net basis of each bond in the basket from clean prices, via the proceeds method of
+    /// repo to the `delivery` day, which defaults to the last delivery day.
+    pub fn net_basis(
+        &self,
+        future_price: f64,
+        prices: &[f64],
+        repo_rates: &[f64],
+        settlement: &NaiveDateTime,
+        delivery: Option<&NaiveDateTime>,
+        convention: &Convention,
+    ) -> Result<Vec<f64>, PyErr> {
+        self.validate_prices(prices)?;
+        self.validate_prices(repo_rates)?;
+        let delivery = delivery.unwrap_or(&self.delivery.1);
+        self.basket
+            .iter()
+            .enumerate()
+            .map(|(i, bond)| {
+                Ok(bond.fwd_from_repo(
+                    prices[i],
+                    settlement,
+                    delivery,
+                    repo_rates[i],
+                    convention,
+                    false,
+                )? - self.cfs[i] * future_price)
+            })
+            .collect()
+    }
+
+    /// The implied repo rate of each bond in the basket from clean prices, via the proceeds
+    /// method to the `delivery` day, which defaults to the last delivery day.
+    pub fn implied_repo(
+        &self,
+        future_price: f64,
+        prices: &[f64],
+        settlement: &NaiveDateTime,
+        delivery: Option<&NaiveDateTime>,
+        convention: &Convention,
+    ) -> Result<Vec<f64>, PyErr> {
+        self.validate_prices(prices)?;
+        let delivery = delivery.unwrap_or(&self.delivery.1);
+        self.basket
+            .iter()
+            .enumerate()
+            .map(|(i, bond)| {
+                bond.repo_from_fwd(
+                    prices[i],
+                    settlement,
+                    delivery,
+                    future_price * self.cfs[i],
+                    convention,
+                    false,
+                )
+            })
+            .collect()
+    }
+
+    /// The index of the cheapest-to-deliver bond in the basket, having the highest implied repo.
+    pub fn ctd_index(
+        &self,
+        future_price: f64,
+        prices: &[f64],
+        settlement: &NaiveDateTime,
+        delivery: Option<&NaiveDateTime>,
+        convention: &Convention,
+    ) -> Result<usize, PyErr> {
+        let implied_repo =
+            self.implied_repo(future_price, prices, settlement, delivery, convention)?;
+        implied_repo
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(i, _)| i)
+            .ok_or_else(|| PyValueError::new_err("The `basket` is empty."))
+    }
+
+    /// The price of the future implied by a curve, being the lowest of each bond's forward clean
+    /// price on the `delivery` day divided by its conversion factor.
+    ///
+    /// The result carries any sensitivities to the curve's nodes.
+    pub fn price_from_curve<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+        delivery: Option<&NaiveDateTime>,
+    ) -> Result<Number, PyErr> {
+        let delivery = delivery.unwrap_or(&self.delivery.1);
+        let mut future_price: Option<Number> = None;
+        for (i, bond) in self.basket.iter().enumerate() {
+            let price = bond.price_from_curve(curve, delivery, false)? / self.cfs[i];
+            future_price = match future_price {
+                Some(p) if f64::from(&p) <= f64::from(&price) => Some(p),
+                _ => Some(price),
+            };
+        }
+        future_price.ok_or_else(|| PyValueError::new_err("The `basket` is empty."))
+    }
+}
+
+fn conversion_factor(
+    bond: &FixedRateBond,
+    coupon: f64,
+    delivery: &NaiveDateTime,
+    method: &ConversionMethod,
+) -> Result<f64, PyErr> {
+    match method {
+        ConversionMethod::Ytm => {
+            Ok(f64::from(bond.price(&Number::F64(coupon), delivery, false)?) / 100.0)
+        }
+        ConversionMethod::UstShort => Ok(conversion_factor_ust(bond, delivery, true)),
+        ConversionMethod::UstLong => Ok(conversion_factor_ust(bond, delivery, false)),
+        ConversionMethod::Eurex => conversion_factor_eurex(bond, coupon, delivery),
+    }
+}
+
+/// The EUREX conversion factor formula, rounded to 6 decimal places.
+fn conversion_factor_eurex(
+    bond: &FixedRateBond,
+    coupon: f64,
+    delivery: &NaiveDateTime,
+) -> Result<f64, PyErr> {
+    if bond.frequency_months != 12 {
+        return Err(PyValueError::new_err(
+            "The EUREX conversion factor requires a bond with annual coupons.",
+        ));
+    }
+    if *delivery >= bond.termination {
+        return Err(PyValueError::new_err(
+            "The `delivery` day must be before the maturity of the bond.",
+        ));
+    }
+    // the number of whole years, `n`, from the next coupon date to maturity
+    let anniversary = |years: u32| bond.termination - Months::new(12 * years);
+    let mut n = 0_u32;
+    while anniversary(n + 1) > *delivery {
+        n += 1;
+    }
+    let (ncd, lcd) = (anniversary(n), anniversary(n + 1));
+    let act = (ncd - lcd).num_days() as f64;
+    let f = (ncd - *delivery).num_days() as f64 / act;
+    let accrued = (*delivery - lcd).num_days() as f64 / act;
+    let c = bond.fixed_rate / 100.0;
+    let v = 1.0 / (1.0 + coupon / 100.0);
+    let vn = v.powi(n as i32);
+    let factor = v.powf(f) * (c + c / (coupon / 100.0) * (1.0 - vn) + vn) - c * accrued;
+    Ok((factor * 1e6).round() / 1e6)
+}
+
+/// The CME treasury conversion factor formula, rounded to 4 decimal places.
+fn conversion_factor_ust(bond: &FixedRateBond, delivery: &NaiveDateTime, short: bool) -> f64 {
+    let coupon = bond.fixed_rate / 100.0;
+    let (n, mut z) = years_and_months(delivery, &bond.termination);
+    if !short {
+        z = z / 3 * 3; // round down number of months to quarters
+    }
+    let v = if z < 7 {
+        z
+    } else if short {
+        z - 6
+    } else {
+        3
+    };
+    let a = 1.0 / 1.03_f64.powf(v as f64 / 6.0);
+    let b = (coupon / 2.0) * (6 - v) as f64 / 6.0;
+    let c = if z < 7 {
+        1.0 / 1.03_f64.powi(2 * n)
+    } else {
+        1.0 / 1.03_f64.powi(2 * n + 1)
+    };
+    let d = (coupon / 0.06) * (1.0 - c);
+    let factor = a * ((coupon / 2.0) + c + d) - b;
+    (factor * 1e4).round() / 1e4
+}
+
+/// The whole number of years and remaining months between two dates.
+fn years_and_months(d1: &NaiveDateTime, d2: &NaiveDateTime) -> (i32, i32) {
+    let mut years = d2.year() - d1.year();
+    if (d2.month() == d1.month() && d2.day() < d1.day()) || d2.month() < d1.month() {
+        years -= 1;
+    }
+    let months = (d2.month() as i32 - d1.month() as i32).rem_euclid(12);
+    (years, months)
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accrued::AccrualConvention;
+    use crate::calendars::{ndt, Cal, CalType, NamedCal};
+
+    fn bond(
+        effective: NaiveDateTime,
+        termination: NaiveDateTime,
+        fixed_rate: f64,
+        frequency_months: i32,
+        calendar: CalType,
+        ex_div: i32,
+    ) -> FixedRateBond {
+        FixedRateBond::try_new(
+            effective,
+            termination,
+            fixed_rate,
+            Some(frequency_months),
+            Some(calendar),
+            AccrualConvention::LinearDays,
+            Some(ex_div),
+            None,
+        )
+        .unwrap()
+    }
+
+    fn dlv_future() -> BondFuture {
+        let cal = || CalType::Cal(Cal::new(vec![], vec![]));
+        let basket = vec![
+            bond(ndt(1999, 1, 1), ndt(2009, 12, 7), 5.75, 6, cal(), 7),
+            bond(ndt(1999, 1, 1), ndt(2011, 7, 12), 9.00, 6, cal(), 7),
+            bond(ndt(1999, 1, 1), ndt(2010, 11, 25), 6.25, 6, cal(), 7),
+            bond(ndt(1999, 1, 1), ndt(2012, 8, 6), 9.00, 6, cal(), 7),
+        ];
+        BondFuture::try_new(
+            7.0,
+            (ndt(2000, 6, 1), ndt(2000, 6, 30)),
+            basket,
+            ConversionMethod::Ytm,
+        )
+        .unwrap()
+    }
+
+    const PRICES: [f64; 4] = [102.732, 131.461, 107.877, 134.455];
+
+    fn assert_close(result: &[f64], expected: &[f64], tol: f64) {
+        for (r, e) in result.iter().zip(expected.iter()) {
+            assert!((r - e).abs() < tol, "{} != {}", r, e);
+        }
+    }
+
+    #[test]
+    fn test_dlv_conversion_factors() {
+        let future = dlv_future();
+        assert_close(
+            future.conversion_factors(),
+            &[0.914225, 1.152571, 0.944931, 1.161956],
+            1e-6,
+        );
+    }
+
+    #[test]
+    fn test_dlv_basis_and_repo() {
+        let future = dlv_future();
+        let s = ndt(2000, 3, 16);
+        let gross = future.gross_basis(112.98, &PRICES).unwrap();
+        assert_close(&gross, &[-0.557192, 1.243582, 1.118677, 3.177230], 1e-6);
+        let repo = future
+            .implied_repo(112.98, &PRICES, &s, None, &Convention::Act365F)
+            .unwrap();
+        assert_close(&repo, &[7.381345, 3.564685, 2.199755, -1.414670], 1e-6);
+        let net = future
+            .net_basis(112.98, &PRICES, &[6.24; 4], &s, None, &Convention::Act365F)
+            .unwrap();
+        assert_close(&net, &[-0.343654, 1.033668, 1.275866, 3.010371], 1e-6);
+        assert_eq!(
+            future
+                .ctd_index(112.98, &PRICES, &s, None, &Convention::Act365F)
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_dlv_ytm() {
+        let future = dlv_future();
+        let s = ndt(2000, 3, 16);
+        let expected = [5.384243, 5.273217, 5.275481, 5.193851];
+        for (i, bond) in future.basket.iter().enumerate() {
+            let ytm = bond.ytm(PRICES[i], &s, false).unwrap();
+            assert!((ytm - expected[i]).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_conversion_factors_ice_gilt() {
+        let ldn = || CalType::NamedCal(NamedCal::try_new("ldn").unwrap());
+        for (mat, coupon, expected) in [
+            (ndt(2032, 6, 7), 4.25, 1.0187757),
+            (ndt(2033, 7, 31), 0.875, 0.7410593),
+            (ndt(2034, 9, 7), 4.5, 1.0449380),
+            (ndt(2035, 7, 31), 0.625, 0.6773884),
+            (ndt(2036, 3, 7), 4.25, 1.0247516),
+        ] {
+            let basket = vec![bond(ndt(2020, 1, 1), mat, coupon, 6, ldn(), 7)];
+            let future = BondFuture::try_new(
+                4.0,
+                (ndt(2023, 6, 1), ndt(2023, 6, 30)),
+                basket,
+                ConversionMethod::Ytm,
+            )
+            .unwrap();
+            assert!((future.conversion_factors()[0] - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_conversion_factors_ytm_approximates_eurex_bund() {
+        let tgt = || CalType::NamedCal(NamedCal::try_new("tgt").unwrap());
+        // the generic yield method is close to, but not exactly, the factors published by EUREX
+        for (delivery, mat, coupon, expected) in [
+            (ndt(2023, 6, 12), ndt(2032, 2, 15), 0.0, 0.603058),
+            (ndt(2023, 6, 12), ndt(2032, 8, 15), 1.7, 0.703125),
+            (ndt(2023, 9, 11), ndt(2033, 2, 15), 2.3, 0.739087),
+            (ndt(2023, 12, 11), ndt(2032, 8, 15), 1.7, 0.715464),
+        ] {
+            let basket = vec![bond(ndt(2020, 1, 1), mat, coupon, 12, tgt(), 0)];
+            let future =
+                BondFuture::try_new(6.0, (delivery, delivery), basket, ConversionMethod::Ytm)
+                    .unwrap();
+            assert!((future.conversion_factors()[0] - expected).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_conversion_factors_eurex_published() {
+        let tgt = || CalType::NamedCal(NamedCal::try_new("tgt").unwrap());
+        // the EUREX method reproduces exactly the factors published for the Bund, Schatz and Buxl
+        for (notional, delivery, mat, coupon, expected) in [
+            (6.0, ndt(2023, 6, 12), ndt(2032, 2, 15), 0.0, 0.603058),
+            (6.0, ndt(2023, 9, 11), ndt(2032, 8, 15), 1.7, 0.709321),
+            (6.0, ndt(2023, 12, 11), ndt(2026, 2, 15), 0.5, 0.890591),
+            (4.0, ndt(2023, 6, 12), ndt(2053, 8, 15), 1.8, 0.618362),
+        ] {
+            let basket = vec![bond(ndt(2020, 1, 1), mat, coupon, 12, tgt(), 0)];
+            let future = BondFuture::try_new(
+                notional,
+                (delivery, delivery),
+                basket,
+                ConversionMethod::Eurex,
+            )
+            .unwrap();
+            assert_eq!(future.conversion_factors()[0], expected);
+        }
+    }
+
+    #[test]
+    fn test_conversion_factors_eurex_semi_annual_errors() {
+        let tgt = CalType::NamedCal(NamedCal::try_new("tgt").unwrap());
+        let basket = vec![bond(ndt(2020, 1, 1), ndt(2030, 2, 1), 2.0, 6, tgt, 0)];
+        let delivery = (ndt(2023, 6, 12), ndt(2023, 6, 12));
+        assert!(BondFuture::try_new(6.0, delivery, basket, ConversionMethod::Eurex).is_err());
+    }
+
+    #[test]
+    fn test_ctd_index_convention() {
+        let future = dlv_future();
+        let s = ndt(2000, 3, 16);
+        for convention in [Convention::Act365F, Convention::Act360] {
+            let repo = future
+                .implied_repo(112.98, &PRICES, &s, None, &convention)
+                .unwrap();
+            let ctd = future
+                .ctd_index(112.98, &PRICES, &s, None, &convention)
+                .unwrap();
+            assert!(repo.iter().all(|r| *r <= repo[ctd]));
+        }
+    }
+
+    #[test]
+    fn test_conversion_factors_cme_treasury() {
+        let nyc = || CalType::NamedCal(NamedCal::try_new("nyc").unwrap());
+        for (mat, coupon, method, expected) in [
+            (ndt(2010, 10, 31), 1.5, ConversionMethod::UstShort, 0.9229),
+            (ndt(2013, 10, 31), 2.75, ConversionMethod::UstShort, 0.8653),
+            (ndt(2018, 11, 15), 3.75, ConversionMethod::UstLong, 0.8357),
+            (ndt(2038, 5, 15), 4.5, ConversionMethod::UstLong, 0.7943),
+        ] {
+            let basket = vec![bond(ndt(2005, 1, 1), mat, coupon, 6, nyc(), 1)];
+            let future =
+                BondFuture::try_new(6.0, (ndt(2008, 12, 1), ndt(2008, 12, 29)), basket, method)
+                    .unwrap();
+            assert!((future.conversion_factors()[0] - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_price_from_curve_sensitivity() {
+        use crate::calendars::Modifier;
+        use crate::curves::{LogLinearInterpolator, Nodes};
+        use crate::dual::{ADOrder, Gradient1};
+        use indexmap::IndexMap;
+
+        let future = dlv_future();
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2000, 3, 15), 1.0_f64),
+            (ndt(2005, 3, 15), 0.78_f64),
+            (ndt(2015, 3, 15), 0.45_f64),
+        ]));
+        let mut curve = CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            "gilt",
+            Convention::Act365F,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap();
+        curve.set_ad_order(ADOrder::One).unwrap();
+        let result = future.price_from_curve(&curve, None).unwrap();
+        let expected = future
+            .basket
+            .iter()
+            .enumerate()
+            .map(|(i, b)| {
+                f64::from(
+                    b.price_from_curve(&curve, &ndt(2000, 6, 30), false)
+                        .unwrap(),
+                ) / future.cfs[i]
+            })
+            .fold(f64::INFINITY, f64::min);
+        assert!((f64::from(&result) - expected).abs() < 1e-12);
+        match result {
+            Number::Dual(d) => assert!(d.gradient1(vec!["gilt2".to_string()])[0] != 0.0),
+            _ => panic!("expected a Dual price"),
+        }
+    }
+}
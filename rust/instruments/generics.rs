@@ -0,0 +1,462 @@
+use crate::calendars::DateRoll;
+use crate::curves::{CurveDF, CurveInterpolation, CurveRegistry, Curves};
+use crate::dual::{Gradient1, Number};
+use crate::instruments::irs::IRS;
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, FromPyObject, PyErr};
+use serde::{Deserialize, Serialize};
+
+/// Metrics of an instrument priced from a single curve.
+pub trait CurveInstrument {
+    /// The net present value of the instrument.
+    fn npv<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr>;
+
+    /// The mid-market rate of the instrument, in its quoted units.
+    fn rate<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr>;
+
+    /// The change in net present value of the instrument for a one basis point increase in
+    /// its fixed rates, calculated analytically.
+    fn analytic_pv01<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr>;
+
+    /// The change in net present value of the instrument for a one basis point parallel
+    /// increase in the continuously compounded zero rates of the curve, calculated analytically.
+    fn analytic_dv01<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr>;
+
+    /// The sensitivities of the net present value of the instrument to each of `vars`.
+    ///
+    /// Every sensitivity is zero if the curve has no AD order.
+    fn delta<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+        vars: &[String],
+    ) -> Result<Vec<f64>, PyErr> {
+        match self.npv(curve)? {
+            Number::F64(_) => Ok(vec![0.0; vars.len()]),
+            Number::Dual(d) => Ok(d.gradient1(vars.to_vec()).to_vec()),
+            Number::Dual2(d) => Ok(d.gradient1(vars.to_vec()).to_vec()),
+        }
+    }
+
+    /// The net present value of the instrument from the curve with the given id in a registry.
+    ///
+    /// The id is resolved when called, so the curve is as most recently updated in the
+    /// registry.
+    fn npv_from_registry<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        registry: &CurveRegistry<Curves<T, U>>,
+        id: &str,
+    ) -> Result<Number, PyErr> {
+        registry.read_df(id, |curve| self.npv(curve))
+    }
+
+    /// The mid-market rate of the instrument from the curve with the given id in a registry.
+    ///
+    /// The id is resolved when called, so the curve is as most recently updated in the
+    /// registry.
+    fn rate_from_registry<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        registry: &CurveRegistry<Curves<T, U>>,
+        id: &str,
+    ) -> Result<Number, PyErr> {
+        registry.read_df(id, |curve| self.rate(curve))
+    }
+}
+
+impl CurveInstrument for IRS {
+    fn npv<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        self.npv(curve)
+    }
+
+    fn rate<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        self.rate(curve)
+    }
+
+    fn analytic_pv01<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        self.analytic_pv01(curve)
+    }
+
+    fn analytic_dv01<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        self.analytic_dv01(curve)
+    }
+}
+
+/// Container for the instruments composable into a [Spread] or a [Fly].
+#[derive(Clone, FromPyObject, Serialize, Deserialize)]
+pub enum InstrumentType {
+    IRS(IRS),
+    Spread(Spread),
+    Fly(Fly),
+}
+
+impl CurveInstrument for InstrumentType {
+    fn npv<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        match self {
+            InstrumentType::IRS(i) => CurveInstrument::npv(i, curve),
+            InstrumentType::Spread(i) => i.npv(curve),
+            InstrumentType::Fly(i) => i.npv(curve),
+        }
+    }
+
+    fn rate<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        match self {
+            InstrumentType::IRS(i) => CurveInstrument::rate(i, curve),
+            InstrumentType::Spread(i) => i.rate(curve),
+            InstrumentType::Fly(i) => i.rate(curve),
+        }
+    }
+
+    fn analytic_pv01<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        match self {
+            InstrumentType::IRS(i) => CurveInstrument::analytic_pv01(i, curve),
+            InstrumentType::Spread(i) => i.analytic_pv01(curve),
+            InstrumentType::Fly(i) => i.analytic_pv01(curve),
+        }
+    }
+
+    fn analytic_dv01<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        match self {
+            InstrumentType::IRS(i) => CurveInstrument::analytic_dv01(i, curve),
+            InstrumentType::Spread(i) => i.analytic_dv01(curve),
+            InstrumentType::Fly(i) => i.analytic_dv01(curve),
+        }
+    }
+}
+
+/// The sum of the NPVs of a sequence of instruments scaled by their weights.
+fn weighted_npv<T: CurveInterpolation, U: DateRoll>(
+    instruments: &[InstrumentType],
+    weights: &[f64],
+    curve: &CurveDF<T, U>,
+) -> Result<Number, PyErr> {
+    weighted_sum(instruments, weights, |i| i.npv(curve))
+}
+
+/// The sum of a measure of a sequence of instruments scaled by their weights.
+fn weighted_sum<F>(instruments: &[InstrumentType], weights: &[f64], f: F) -> Result<Number, PyErr>
+where
+    F: Fn(&InstrumentType) -> Result<Number, PyErr>,
+{
+    let mut total = Number::F64(0.0);
+    for (instrument, w) in instruments.iter().zip(weights.iter()) {
+        total = total + f(instrument)? * *w;
+    }
+    Ok(total)
+}
+
+/// The combination of the rates of a sequence of instruments by `coefficients`, scaled by their
+/// weights, in basis points.
+///
+/// The rates of swaps are in percent and are converted to basis points, whereas those of nested
+/// spreads and flies are already in basis points.
+fn weighted_rate<T: CurveInterpolation, U: DateRoll>(
+    instruments: &[InstrumentType],
+    weights: &[f64],
+    coefficients: &[f64],
+    curve: &CurveDF<T, U>,
+) -> Result<Number, PyErr> {
+    let mut rate = Number::F64(0.0);
+    for ((instrument, w), c) in instruments.iter().zip(weights.iter()).zip(coefficients) {
+        let bp = match instrument {
+            InstrumentType::IRS(_) => 100.0,
+            InstrumentType::Spread(_) | InstrumentType::Fly(_) => 1.0,
+        };
+        rate = rate + instrument.rate(curve)? * (w * c * bp);
+    }
+    Ok(rate)
+}
+
+fn validate_weights(weights: &[f64]) -> Result<(), PyErr> {
+    if weights.iter().any(|w| !w.is_finite()) {
+        return Err(PyValueError::new_err("`weights` must be finite."));
+    }
+    Ok(())
+}
+
+/// The spread of two instruments, usually in tenor order, e.g. 5s10s.
+///
+/// Each instrument contributes its NPV and delta scaled by its weight. The rate is the weighted
+/// difference of the second instrument's rate to the first's, in basis points, so with unit
+/// weights is the conventional spread.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Spread {
+    pub(crate) instruments: Vec<InstrumentType>,
+    pub(crate) weights: Vec<f64>,
+}
+
+impl Spread {
+    pub fn try_new(
+        instrument1: InstrumentType,
+        instrument2: InstrumentType,
+        weights: (f64, f64),
+    ) -> Result<Self, PyErr> {
+        let weights = vec![weights.0, weights.1];
+        validate_weights(&weights)?;
+        Ok(Spread {
+            instruments: vec![instrument1, instrument2],
+            weights,
+        })
+    }
+}
+
+impl CurveInstrument for Spread {
+    fn npv<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        weighted_npv(&self.instruments, &self.weights, curve)
+    }
+
+    fn rate<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        weighted_rate(&self.instruments, &self.weights, &[-1.0, 1.0], curve)
+    }
+
+    fn analytic_pv01<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        weighted_sum(&self.instruments, &self.weights, |i| i.analytic_pv01(curve))
+    }
+
+    fn analytic_dv01<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        weighted_sum(&self.instruments, &self.weights, |i| i.analytic_dv01(curve))
+    }
+}
+
+/// The butterfly of three instruments, usually in tenor order, e.g. 5s10s15s.
+///
+/// Each instrument contributes its NPV and delta scaled by its weight. The rate is the weighted
+/// combination of rates, `-r1 + 2 r2 - r3`, in basis points, so with unit weights is the
+/// conventional butterfly.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Fly {
+    pub(crate) instruments: Vec<InstrumentType>,
+    pub(crate) weights: Vec<f64>,
+}
+
+impl Fly {
+    pub fn try_new(
+        instrument1: InstrumentType,
+        instrument2: InstrumentType,
+        instrument3: InstrumentType,
+        weights: (f64, f64, f64),
+    ) -> Result<Self, PyErr> {
+        let weights = vec![weights.0, weights.1, weights.2];
+        validate_weights(&weights)?;
+        Ok(Fly {
+            instruments: vec![instrument1, instrument2, instrument3],
+            weights,
+        })
+    }
+}
+
+impl CurveInstrument for Fly {
+    fn npv<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        weighted_npv(&self.instruments, &self.weights, curve)
+    }
+
+    fn rate<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        weighted_rate(&self.instruments, &self.weights, &[-1.0, 2.0, -1.0], curve)
+    }
+
+    fn analytic_pv01<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        weighted_sum(&self.instruments, &self.weights, |i| i.analytic_pv01(curve))
+    }
+
+    fn analytic_dv01<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        weighted_sum(&self.instruments, &self.weights, |i| i.analytic_dv01(curve))
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, CalType, Convention, Modifier, NamedCal};
+    use crate::curves::{LogLinearInterpolator, Nodes};
+    use crate::dual::ADOrder;
+    use indexmap::IndexMap;
+
+    fn curve() -> CurveDF<LogLinearInterpolator, NamedCal> {
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2022, 1, 1), 1.0_f64),
+            (ndt(2027, 1, 1), 0.88_f64),
+            (ndt(2032, 1, 1), 0.74_f64),
+            (ndt(2037, 1, 1), 0.62_f64),
+        ]));
+        let mut curve = CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            "sofr",
+            Convention::Act360,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap();
+        curve.set_ad_order(ADOrder::One).unwrap();
+        curve
+    }
+
+    fn irs(years: i32, notional: f64) -> InstrumentType {
+        InstrumentType::IRS(
+            IRS::try_new(
+                ndt(2022, 1, 1),
+                ndt(2022 + years, 1, 1),
+                Some(12),
+                notional,
+                2.5,
+                Some(Convention::Act360),
+                Some(&CalType::NamedCal(NamedCal::try_new("all").unwrap())),
+                Some(Modifier::ModF),
+                Some(0),
+                None,
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_spread_rate_and_npv() {
+        let curve = curve();
+        let spread = Spread::try_new(irs(5, 1e6), irs(10, -0.5e6), (1.0, 2.0)).unwrap();
+        let (r1, r2) = (
+            f64::from(irs(5, 1e6).rate(&curve).unwrap()),
+            f64::from(irs(10, -0.5e6).rate(&curve).unwrap()),
+        );
+        let result = f64::from(spread.rate(&curve).unwrap());
+        assert!((result - (2.0 * r2 - r1) * 100.0).abs() < 1e-10);
+        let npv = f64::from(spread.npv(&curve).unwrap());
+        let expected = f64::from(irs(5, 1e6).npv(&curve).unwrap())
+            + 2.0 * f64::from(irs(10, -0.5e6).npv(&curve).unwrap());
+        assert!((npv - expected).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_fly_of_spreads_delta() {
+        let curve = curve();
+        let fly = Fly::try_new(irs(5, 1e6), irs(10, -2e6), irs(15, 1e6), (1.0, 1.0, 1.0)).unwrap();
+        let vars: Vec<String> = (0..4).map(|i| format!("sofr{}", i)).collect();
+        let delta = fly.delta(&curve, &vars).unwrap();
+        let expected: Vec<f64> = [irs(5, 1e6), irs(10, -2e6), irs(15, 1e6)]
+            .iter()
+            .map(|i| i.delta(&curve, &vars).unwrap())
+            .fold(vec![0.0; 4], |acc, d| {
+                acc.iter().zip(d.iter()).map(|(a, b)| a + b).collect()
+            });
+        for (d, e) in delta.iter().zip(expected.iter()) {
+            assert!((d - e).abs() < 1e-8);
+        }
+        assert!(delta[3] != 0.0);
+
+        // instruments compose, so a fly is the spread of two spreads
+        let spread_of_spreads = Spread::try_new(
+            InstrumentType::Spread(
+                Spread::try_new(irs(10, 1e6), irs(15, 1e6), (1.0, 1.0)).unwrap(),
+            ),
+            InstrumentType::Spread(Spread::try_new(irs(5, 1e6), irs(10, 1e6), (1.0, 1.0)).unwrap()),
+            (1.0, 1.0),
+        )
+        .unwrap();
+        let fly = Fly::try_new(irs(5, 1e6), irs(10, 1e6), irs(15, 1e6), (1.0, 1.0, 1.0)).unwrap();
+        let (a, b) = (
+            f64::from(spread_of_spreads.rate(&curve).unwrap()),
+            f64::from(fly.rate(&curve).unwrap()),
+        );
+        assert!((a - b).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_rate_from_registry_resolves_at_price_time() {
+        let registry: CurveRegistry<Curves<LogLinearInterpolator, NamedCal>> = CurveRegistry::new();
+        registry.insert("sofr", curve().into()).unwrap();
+        let spread = Spread::try_new(irs(5, 1e6), irs(10, 1e6), (1.0, 1.0)).unwrap();
+        let before = f64::from(spread.rate_from_registry(&registry, "sofr").unwrap());
+        assert_eq!(before, f64::from(spread.rate(&curve()).unwrap()));
+
+        // a curve replaced under the same id is priced without re-binding the instrument
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2022, 1, 1), 1.0_f64),
+            (ndt(2027, 1, 1), 0.88_f64),
+            (ndt(2032, 1, 1), 0.70_f64),
+            (ndt(2037, 1, 1), 0.55_f64),
+        ]));
+        let steeper = CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            "sofr",
+            Convention::Act360,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap();
+        registry.insert("sofr", steeper.clone().into()).unwrap();
+        let after = f64::from(spread.rate_from_registry(&registry, "sofr").unwrap());
+        assert_eq!(after, f64::from(spread.rate(&steeper).unwrap()));
+        assert!(after > before);
+        let npv = spread.npv_from_registry(&registry, "sofr").unwrap();
+        assert_eq!(npv, spread.npv(&steeper).unwrap());
+        assert!(spread.rate_from_registry(&registry, "estr").is_err());
+    }
+
+    #[test]
+    fn test_non_finite_weights_raise() {
+        assert!(Spread::try_new(irs(5, 1e6), irs(10, 1e6), (1.0, f64::NAN)).is_err());
+    }
+}
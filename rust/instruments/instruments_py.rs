@@ -0,0 +1,1350 @@
+//! Wrapper module to export Rust instruments to Python using pyo3 bindings.
+
+use crate::accrued::AccrualConvention;
+use crate::calendars::calendar_py::extract_optional_modifier;
+use crate::calendars::{CalType, Convention, Modifier};
+use crate::curves::curve_py::{Curve, CurveInterpolator};
+use crate::curves::Curves;
+use crate::dates::dates_py::{extract_date, extract_optional_date};
+use crate::dual::Number;
+use crate::fx::rates::Ccy;
+use crate::fx::rates_py::extract_ccy;
+use crate::instruments::{
+    net_cashflows, BondFuture, Cashflow, ConversionMethod, CurveInstrument, FixedLeg,
+    FixedRateBond, Fly, InstrumentType, RoundingMode, SettlementRounding, Spread, StepSchedule,
+    ZeroFixedLeg, IRS, SBS,
+};
+use bincode::{deserialize, serialize};
+use chrono::NaiveDateTime;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use std::collections::HashMap;
+
+#[pymethods]
+impl FixedLeg {
+    #[new]
+    #[pyo3(signature = (effective, termination, frequency, notional, fixed_rate, convention=None, calendar=None, modifier=None, payment_lag=None, final_exchange=false, amortization=None, rate_steps=None, spec=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn new_py(
+        #[pyo3(from_py_with = "extract_date")] effective: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] termination: NaiveDateTime,
+        frequency: Option<i32>,
+        notional: f64,
+        fixed_rate: f64,
+        convention: Option<Convention>,
+        calendar: Option<CalType>,
+        #[pyo3(from_py_with = "extract_optional_modifier")] modifier: Option<Modifier>,
+        payment_lag: Option<i32>,
+        final_exchange: bool,
+        amortization: Option<(Vec<NaiveDateTime>, Vec<f64>)>,
+        rate_steps: Option<(Vec<NaiveDateTime>, Vec<f64>)>,
+        spec: Option<&str>,
+    ) -> PyResult<Self> {
+        let amortization = amortization
+            .map(|(dates, values)| StepSchedule::try_new(dates, values))
+            .transpose()?;
+        let rate_steps = rate_steps
+            .map(|(dates, values)| StepSchedule::try_new(dates, values))
+            .transpose()?;
+        FixedLeg::try_new(
+            effective,
+            termination,
+            frequency,
+            notional,
+            fixed_rate,
+            convention,
+            calendar.as_ref(),
+            modifier,
+            payment_lag,
+            final_exchange,
+            amortization,
+            rate_steps,
+            spec,
+        )
+    }
+
+    /// The unadjusted accrual dates of the leg.
+    #[getter]
+    fn uschedule(&self) -> Vec<NaiveDateTime> {
+        self.uschedule.clone()
+    }
+
+    /// The payment dates of each period.
+    #[getter]
+    fn payments(&self) -> Vec<NaiveDateTime> {
+        self.payments.clone()
+    }
+
+    /// The notional of each period.
+    #[getter]
+    fn notionals(&self) -> Vec<f64> {
+        self.notionals.clone()
+    }
+
+    /// The fixed rate of each period, in percent.
+    #[getter]
+    fn rates(&self) -> Vec<f64> {
+        self.rates.clone()
+    }
+
+    /// The fixed rate cashflow of each period.
+    #[getter(cashflows)]
+    fn cashflows_py(&self) -> PyResult<Vec<f64>> {
+        self.cashflows()
+    }
+
+    /// The notional exchanged on the payment date of each period.
+    #[getter(exchanges)]
+    fn exchanges_py(&self) -> Vec<f64> {
+        self.exchanges()
+    }
+
+    /// Return the net present value of the leg from the discount factors of a curve.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The discount curve.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "npv")]
+    fn npv_py(&self, curve: &Curve) -> PyResult<Number> {
+        self.npv(&curve.inner)
+    }
+
+    /// Return the change in net present value of a period for a one basis point increase in
+    /// its fixed rate, calculated analytically.
+    ///
+    /// Parameters
+    /// ----------
+    /// period: int
+    ///     The index of the period.
+    /// curve: Curve
+    ///     The discount curve.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "period_pv01")]
+    fn period_pv01_py(&self, period: usize, curve: &Curve) -> PyResult<Number> {
+        self.period_pv01(period, &curve.inner)
+    }
+
+    /// Return the change in net present value of a period for a one basis point parallel
+    /// increase in the continuously compounded zero rates of a curve, calculated analytically.
+    ///
+    /// Parameters
+    /// ----------
+    /// period: int
+    ///     The index of the period.
+    /// curve: Curve
+    ///     The discount curve.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "period_dv01")]
+    fn period_dv01_py(&self, period: usize, curve: &Curve) -> PyResult<Number> {
+        self.period_dv01(period, &curve.inner)
+    }
+
+    /// Return the change in net present value of the leg for a one basis point increase in the
+    /// fixed rate of every period, calculated analytically.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The discount curve.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "analytic_pv01")]
+    fn analytic_pv01_py(&self, curve: &Curve) -> PyResult<Number> {
+        self.analytic_pv01(&curve.inner)
+    }
+
+    /// Return the change in net present value of the leg for a one basis point parallel
+    /// increase in the continuously compounded zero rates of a curve, calculated analytically.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The discount curve.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "analytic_dv01")]
+    fn analytic_dv01_py(&self, curve: &Curve) -> PyResult<Number> {
+        self.analytic_dv01(&curve.inner)
+    }
+    /// Return the cashflows of the leg, including notional exchanges.
+    ///
+    /// Parameters
+    /// ----------
+    /// currency: Ccy or str
+    ///     The currency of the leg.
+    ///
+    /// Returns
+    /// -------
+    /// list[tuple[Ccy, datetime, float]]
+    #[pyo3(name = "cashflow_table")]
+    fn cashflow_table_py(
+        &self,
+        #[pyo3(from_py_with = "extract_ccy")] currency: Ccy,
+    ) -> PyResult<Vec<(Ccy, NaiveDateTime, Number)>> {
+        Ok(self
+            .cashflow_table(currency)?
+            .into_iter()
+            .map(|c| (c.currency, c.payment, c.amount))
+            .collect())
+    }
+}
+
+#[pymethods]
+impl ZeroFixedLeg {
+    #[new]
+    #[pyo3(signature = (effective, termination, frequency, notional, convention=None, calendar=None, modifier=None, payment_lag=None, spec=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn new_py(
+        #[pyo3(from_py_with = "extract_date")] effective: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] termination: NaiveDateTime,
+        frequency: Option<i32>,
+        notional: f64,
+        convention: Option<Convention>,
+        calendar: Option<CalType>,
+        #[pyo3(from_py_with = "extract_optional_modifier")] modifier: Option<Modifier>,
+        payment_lag: Option<i32>,
+        spec: Option<&str>,
+    ) -> PyResult<Self> {
+        ZeroFixedLeg::try_new(
+            effective,
+            termination,
+            frequency,
+            notional,
+            convention,
+            calendar.as_ref(),
+            modifier,
+            payment_lag,
+            spec,
+        )
+    }
+
+    /// The day count fraction of the leg.
+    #[getter]
+    fn dcf(&self) -> f64 {
+        self.dcf
+    }
+
+    /// The payment date of the leg.
+    #[getter]
+    fn payment(&self) -> NaiveDateTime {
+        self.payment
+    }
+
+    /// Return the cashflow of the leg from an IRR fixed rate.
+    ///
+    /// Parameters
+    /// ----------
+    /// fixed_rate: float, Dual or Dual2
+    ///     The fixed rate, in percent, compounded at the frequency.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "cashflow")]
+    fn cashflow_py(&self, fixed_rate: Number) -> Number {
+        self.cashflow(&fixed_rate)
+    }
+
+    /// Return the net present value of the leg from the discount factors of a curve.
+    ///
+    /// Parameters
+    /// ----------
+    /// fixed_rate: float, Dual or Dual2
+    ///     The fixed rate, in percent, compounded at the frequency.
+    /// curve: Curve, FlatCurve or SplicedCurve
+    ///     The discount curve.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "npv")]
+    fn npv_py(
+        &self,
+        fixed_rate: Number,
+        curve: Curves<CurveInterpolator, CalType>,
+    ) -> PyResult<Number> {
+        self.npv(&fixed_rate, &curve)
+    }
+
+    /// Return the analytic delta of the leg, being the derivative of its net present value
+    /// with respect to the fixed rate, per basis point.
+    ///
+    /// Parameters
+    /// ----------
+    /// fixed_rate: float, Dual or Dual2
+    ///     The fixed rate, in percent, compounded at the frequency.
+    /// curve: Curve, FlatCurve or SplicedCurve
+    ///     The discount curve.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "analytic_delta")]
+    fn analytic_delta_py(
+        &self,
+        fixed_rate: Number,
+        curve: Curves<CurveInterpolator, CalType>,
+    ) -> PyResult<Number> {
+        self.analytic_delta(&fixed_rate, &curve)
+    }
+
+    /// Return the fixed rate for which the net present value of the leg equals a target.
+    ///
+    /// Parameters
+    /// ----------
+    /// target_npv: float, Dual or Dual2
+    ///     The target net present value, e.g. the negated value of another leg.
+    /// curve: Curve, FlatCurve or SplicedCurve
+    ///     The discount curve.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If no fixed rate attains the target net present value.
+    #[pyo3(name = "rate")]
+    fn rate_py(
+        &self,
+        target_npv: Number,
+        curve: Curves<CurveInterpolator, CalType>,
+    ) -> PyResult<Number> {
+        self.rate(&target_npv, &curve)
+    }
+}
+
+#[pymethods]
+impl IRS {
+    #[new]
+    #[pyo3(signature = (effective, termination, frequency, notional, fixed_rate, convention=None, calendar=None, modifier=None, payment_lag=None, spec=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn new_py(
+        #[pyo3(from_py_with = "extract_date")] effective: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] termination: NaiveDateTime,
+        frequency: Option<i32>,
+        notional: f64,
+        fixed_rate: f64,
+        convention: Option<Convention>,
+        calendar: Option<CalType>,
+        #[pyo3(from_py_with = "extract_optional_modifier")] modifier: Option<Modifier>,
+        payment_lag: Option<i32>,
+        spec: Option<&str>,
+    ) -> PyResult<Self> {
+        IRS::try_new(
+            effective,
+            termination,
+            frequency,
+            notional,
+            fixed_rate,
+            convention,
+            calendar.as_ref(),
+            modifier,
+            payment_lag,
+            spec,
+        )
+    }
+
+    /// Return the cashflows of both legs of the swap.
+    ///
+    /// Parameters
+    /// ----------
+    /// currency: Ccy or str
+    ///     The currency of the swap.
+    /// curve: Curve
+    ///     The curve forecasting the float leg.
+    ///
+    /// Returns
+    /// -------
+    /// list[tuple[Ccy, datetime, float | Dual | Dual2]]
+    #[pyo3(name = "cashflow_table")]
+    fn cashflow_table_py(
+        &self,
+        #[pyo3(from_py_with = "extract_ccy")] currency: Ccy,
+        curve: &Curve,
+    ) -> PyResult<Vec<(Ccy, NaiveDateTime, Number)>> {
+        Ok(self
+            .cashflow_table(currency, &curve.inner)?
+            .into_iter()
+            .map(|c| (c.currency, c.payment, c.amount))
+            .collect())
+    }
+
+    /// Return the net present value of the swap.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The discount curve, also forecasting the float leg of any swaps.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "npv")]
+    fn npv_py(&self, curve: &Curve) -> PyResult<Number> {
+        CurveInstrument::npv(self, &curve.inner)
+    }
+
+    /// Return the mid-market rate of the swap.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The discount curve, also forecasting the float leg of any swaps.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "rate")]
+    fn rate_py(&self, curve: &Curve) -> PyResult<Number> {
+        CurveInstrument::rate(self, &curve.inner)
+    }
+
+    /// Return the sensitivities of the net present value of the swap to variables.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The discount curve, with an AD order to carry sensitivities to its nodes.
+    /// vars: list[str]
+    ///     The variables, e.g. the node variables of the curve.
+    ///
+    /// Returns
+    /// -------
+    /// list[float]
+    #[pyo3(name = "delta")]
+    fn delta_py(&self, curve: &Curve, vars: Vec<String>) -> PyResult<Vec<f64>> {
+        CurveInstrument::delta(self, &curve.inner, &vars)
+    }
+
+    /// Return the change in net present value for a one basis point increase in fixed rates,
+    /// calculated analytically.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The discount curve, also forecasting the float leg of any swaps.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "analytic_pv01")]
+    fn analytic_pv01_py(&self, curve: &Curve) -> PyResult<Number> {
+        CurveInstrument::analytic_pv01(self, &curve.inner)
+    }
+
+    /// Return the change in net present value for a one basis point parallel increase in the
+    /// continuously compounded zero rates of the curve, calculated analytically.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The discount curve, also forecasting the float leg of any swaps.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "analytic_dv01")]
+    fn analytic_dv01_py(&self, curve: &Curve) -> PyResult<Number> {
+        CurveInstrument::analytic_dv01(self, &curve.inner)
+    }
+}
+
+#[pymethods]
+impl SBS {
+    #[new]
+    #[pyo3(signature = (effective, termination, frequency, notional, float_spread, convention=None, calendar=None, modifier=None, payment_lag=None, spec=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn new_py(
+        #[pyo3(from_py_with = "extract_date")] effective: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] termination: NaiveDateTime,
+        frequency: Option<(Option<i32>, Option<i32>)>,
+        notional: f64,
+        float_spread: f64,
+        convention: Option<Convention>,
+        calendar: Option<CalType>,
+        #[pyo3(from_py_with = "extract_optional_modifier")] modifier: Option<Modifier>,
+        payment_lag: Option<i32>,
+        spec: Option<&str>,
+    ) -> PyResult<Self> {
+        SBS::try_new(
+            effective,
+            termination,
+            frequency.unwrap_or((None, None)),
+            notional,
+            float_spread,
+            convention,
+            calendar.as_ref(),
+            modifier,
+            payment_lag,
+            spec,
+        )
+    }
+
+    /// The spread of the first leg, in basis points.
+    #[getter(float_spread)]
+    fn float_spread_py(&self) -> f64 {
+        self.float_spread()
+    }
+
+    /// Return the net present value of the basis swap.
+    ///
+    /// Parameters
+    /// ----------
+    /// forecast1: Curve
+    ///     The curve forecasting the first leg.
+    /// forecast2: Curve
+    ///     The curve forecasting the second leg.
+    /// discount: Curve
+    ///     The discount curve of both legs.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "npv")]
+    fn npv_py(&self, forecast1: &Curve, forecast2: &Curve, discount: &Curve) -> PyResult<Number> {
+        self.npv(&forecast1.inner, &forecast2.inner, &discount.inner)
+    }
+
+    /// Return the mid-market spread of the first leg, in basis points.
+    ///
+    /// Parameters
+    /// ----------
+    /// forecast1: Curve
+    ///     The curve forecasting the first leg.
+    /// forecast2: Curve
+    ///     The curve forecasting the second leg.
+    /// discount: Curve
+    ///     The discount curve of both legs.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "rate")]
+    fn rate_py(&self, forecast1: &Curve, forecast2: &Curve, discount: &Curve) -> PyResult<Number> {
+        self.rate(&forecast1.inner, &forecast2.inner, &discount.inner)
+    }
+}
+
+#[pymethods]
+impl Spread {
+    #[new]
+    #[pyo3(signature = (instrument1, instrument2, weights=(1.0, 1.0)))]
+    fn new_py(
+        instrument1: InstrumentType,
+        instrument2: InstrumentType,
+        weights: (f64, f64),
+    ) -> PyResult<Self> {
+        Spread::try_new(instrument1, instrument2, weights)
+    }
+
+    /// The weights of each instrument.
+    #[getter]
+    fn weights(&self) -> Vec<f64> {
+        self.weights.clone()
+    }
+
+    /// Return the net present value of the spread.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The discount curve, also forecasting the float leg of any swaps.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "npv")]
+    fn npv_py(&self, curve: &Curve) -> PyResult<Number> {
+        CurveInstrument::npv(self, &curve.inner)
+    }
+
+    /// Return the mid-market rate of the spread.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The discount curve, also forecasting the float leg of any swaps.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "rate")]
+    fn rate_py(&self, curve: &Curve) -> PyResult<Number> {
+        CurveInstrument::rate(self, &curve.inner)
+    }
+
+    /// Return the sensitivities of the net present value of the spread to variables.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The discount curve, with an AD order to carry sensitivities to its nodes.
+    /// vars: list[str]
+    ///     The variables, e.g. the node variables of the curve.
+    ///
+    /// Returns
+    /// -------
+    /// list[float]
+    #[pyo3(name = "delta")]
+    fn delta_py(&self, curve: &Curve, vars: Vec<String>) -> PyResult<Vec<f64>> {
+        CurveInstrument::delta(self, &curve.inner, &vars)
+    }
+
+    /// Return the change in net present value for a one basis point increase in fixed rates,
+    /// calculated analytically.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The discount curve, also forecasting the float leg of any swaps.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "analytic_pv01")]
+    fn analytic_pv01_py(&self, curve: &Curve) -> PyResult<Number> {
+        CurveInstrument::analytic_pv01(self, &curve.inner)
+    }
+
+    /// Return the change in net present value for a one basis point parallel increase in the
+    /// continuously compounded zero rates of the curve, calculated analytically.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The discount curve, also forecasting the float leg of any swaps.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "analytic_dv01")]
+    fn analytic_dv01_py(&self, curve: &Curve) -> PyResult<Number> {
+        CurveInstrument::analytic_dv01(self, &curve.inner)
+    }
+}
+
+#[pymethods]
+impl Fly {
+    #[new]
+    #[pyo3(signature = (instrument1, instrument2, instrument3, weights=(1.0, 1.0, 1.0)))]
+    fn new_py(
+        instrument1: InstrumentType,
+        instrument2: InstrumentType,
+        instrument3: InstrumentType,
+        weights: (f64, f64, f64),
+    ) -> PyResult<Self> {
+        Fly::try_new(instrument1, instrument2, instrument3, weights)
+    }
+
+    /// The weights of each instrument.
+    #[getter]
+    fn weights(&self) -> Vec<f64> {
+        self.weights.clone()
+    }
+
+    /// Return the net present value of the butterfly.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The discount curve, also forecasting the float leg of any swaps.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "npv")]
+    fn npv_py(&self, curve: &Curve) -> PyResult<Number> {
+        CurveInstrument::npv(self, &curve.inner)
+    }
+
+    /// Return the mid-market rate of the butterfly.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The discount curve, also forecasting the float leg of any swaps.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "rate")]
+    fn rate_py(&self, curve: &Curve) -> PyResult<Number> {
+        CurveInstrument::rate(self, &curve.inner)
+    }
+
+    /// Return the sensitivities of the net present value of the butterfly to variables.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The discount curve, with an AD order to carry sensitivities to its nodes.
+    /// vars: list[str]
+    ///     The variables, e.g. the node variables of the curve.
+    ///
+    /// Returns
+    /// -------
+    /// list[float]
+    #[pyo3(name = "delta")]
+    fn delta_py(&self, curve: &Curve, vars: Vec<String>) -> PyResult<Vec<f64>> {
+        CurveInstrument::delta(self, &curve.inner, &vars)
+    }
+
+    /// Return the change in net present value for a one basis point increase in fixed rates,
+    /// calculated analytically.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The discount curve, also forecasting the float leg of any swaps.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "analytic_pv01")]
+    fn analytic_pv01_py(&self, curve: &Curve) -> PyResult<Number> {
+        CurveInstrument::analytic_pv01(self, &curve.inner)
+    }
+
+    /// Return the change in net present value for a one basis point parallel increase in the
+    /// continuously compounded zero rates of the curve, calculated analytically.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The discount curve, also forecasting the float leg of any swaps.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "analytic_dv01")]
+    fn analytic_dv01_py(&self, curve: &Curve) -> PyResult<Number> {
+        CurveInstrument::analytic_dv01(self, &curve.inner)
+    }
+}
+
+#[pymethods]
+impl FixedRateBond {
+    #[new]
+    #[pyo3(signature = (effective, termination, fixed_rate, frequency=None, calendar=None, accrual=AccrualConvention::LinearDays, ex_div=None, spec=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn new_py(
+        #[pyo3(from_py_with = "extract_date")] effective: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] termination: NaiveDateTime,
+        fixed_rate: f64,
+        frequency: Option<i32>,
+        calendar: Option<CalType>,
+        accrual: AccrualConvention,
+        ex_div: Option<i32>,
+        spec: Option<&str>,
+    ) -> PyResult<Self> {
+        FixedRateBond::try_new(
+            effective,
+            termination,
+            fixed_rate,
+            frequency,
+            calendar,
+            accrual,
+            ex_div,
+            spec,
+        )
+    }
+
+    /// The unadjusted coupon dates of the bond, including the effective date.
+    #[getter]
+    fn uschedule(&self) -> Vec<NaiveDateTime> {
+        self.uschedule.clone()
+    }
+
+    /// The coupon cashflows of each period per 100 nominal.
+    #[getter]
+    fn cashflows(&self) -> PyResult<Vec<f64>> {
+        (0..self.n_periods()).map(|i| self.cashflow(i)).collect()
+    }
+
+    /// Return whether the bond is ex-dividend at the given settlement.
+    ///
+    /// Parameters
+    /// ----------
+    /// settlement: datetime
+    ///     The settlement date to test.
+    ///
+    /// Returns
+    /// -------
+    /// bool
+    #[pyo3(name = "ex_div")]
+    fn ex_div_py(&self, #[pyo3(from_py_with = "extract_date")] settlement: NaiveDateTime) -> bool {
+        self.is_ex_div(&settlement)
+    }
+
+    /// Return the accrued interest per 100 nominal at the given settlement.
+    ///
+    /// Parameters
+    /// ----------
+    /// settlement: datetime
+    ///     The settlement date.
+    ///
+    /// Returns
+    /// -------
+    /// float
+    #[pyo3(name = "accrued")]
+    fn accrued_py(
+        &self,
+        #[pyo3(from_py_with = "extract_date")] settlement: NaiveDateTime,
+    ) -> PyResult<f64> {
+        self.accrued(&settlement)
+    }
+
+    /// Return the price of the bond from its yield-to-maturity.
+    ///
+    /// Parameters
+    /// ----------
+    /// ytm: float, Dual or Dual2
+    ///     The yield-to-maturity, in percent, compounded at the coupon frequency.
+    /// settlement: datetime
+    ///     The settlement date.
+    /// dirty: bool, optional
+    ///     Whether the price includes accrued interest.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "price", signature = (ytm, settlement, dirty=false))]
+    fn price_py(
+        &self,
+        ytm: Number,
+        #[pyo3(from_py_with = "extract_date")] settlement: NaiveDateTime,
+        dirty: bool,
+    ) -> PyResult<Number> {
+        self.price(&ytm, &settlement, dirty)
+    }
+
+    /// Return the yield-to-maturity of the bond from its price.
+    ///
+    /// Parameters
+    /// ----------
+    /// price: float
+    ///     The price per 100 nominal.
+    /// settlement: datetime
+    ///     The settlement date.
+    /// dirty: bool, optional
+    ///     Whether the price includes accrued interest.
+    ///
+    /// Returns
+    /// -------
+    /// float
+    #[pyo3(name = "ytm", signature = (price, settlement, dirty=false))]
+    fn ytm_py(
+        &self,
+        price: f64,
+        #[pyo3(from_py_with = "extract_date")] settlement: NaiveDateTime,
+        dirty: bool,
+    ) -> PyResult<f64> {
+        self.ytm(price, &settlement, dirty)
+    }
+
+    /// Return the forward price of the bond implied by a repo rate, under the proceeds method.
+    ///
+    /// Parameters
+    /// ----------
+    /// price: float
+    ///     The price per 100 nominal at settlement.
+    /// settlement: datetime
+    ///     The settlement date.
+    /// forward_settlement: datetime
+    ///     The forward settlement date.
+    /// repo_rate: float
+    ///     The repo rate, in percent.
+    /// convention: Convention
+    ///     The day count convention of the repo rate.
+    /// dirty: bool, optional
+    ///     Whether the prices include accrued interest.
+    ///
+    /// Returns
+    /// -------
+    /// float
+    #[pyo3(name = "fwd_from_repo", signature = (price, settlement, forward_settlement, repo_rate, convention, dirty=false))]
+    fn fwd_from_repo_py(
+        &self,
+        price: f64,
+        #[pyo3(from_py_with = "extract_date")] settlement: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] forward_settlement: NaiveDateTime,
+        repo_rate: f64,
+        convention: Convention,
+        dirty: bool,
+    ) -> PyResult<f64> {
+        self.fwd_from_repo(
+            price,
+            &settlement,
+            &forward_settlement,
+            repo_rate,
+            &convention,
+            dirty,
+        )
+    }
+
+    /// Return the repo rate implied by a forward price of the bond, under the proceeds method.
+    ///
+    /// Parameters
+    /// ----------
+    /// price: float
+    ///     The price per 100 nominal at settlement.
+    /// settlement: datetime
+    ///     The settlement date.
+    /// forward_settlement: datetime
+    ///     The forward settlement date.
+    /// forward_price: float
+    ///     The price per 100 nominal at forward settlement.
+    /// convention: Convention
+    ///     The day count convention of the repo rate.
+    /// dirty: bool, optional
+    ///     Whether the prices include accrued interest.
+    ///
+    /// Returns
+    /// -------
+    /// float
+    #[pyo3(name = "repo_from_fwd", signature = (price, settlement, forward_settlement, forward_price, convention, dirty=false))]
+    fn repo_from_fwd_py(
+        &self,
+        price: f64,
+        #[pyo3(from_py_with = "extract_date")] settlement: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_date")] forward_settlement: NaiveDateTime,
+        forward_price: f64,
+        convention: Convention,
+        dirty: bool,
+    ) -> PyResult<f64> {
+        self.repo_from_fwd(
+            price,
+            &settlement,
+            &forward_settlement,
+            forward_price,
+            &convention,
+            dirty,
+        )
+    }
+
+    /// Return the price of the bond implied by the discount factors of a curve.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The discount curve.
+    /// settlement: datetime
+    ///     The settlement date.
+    /// dirty: bool, optional
+    ///     Whether the price includes accrued interest.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "price_from_curve", signature = (curve, settlement, dirty=false))]
+    fn price_from_curve_py(
+        &self,
+        curve: &Curve,
+        #[pyo3(from_py_with = "extract_date")] settlement: NaiveDateTime,
+        dirty: bool,
+    ) -> PyResult<Number> {
+        self.price_from_curve(&curve.inner, &settlement, dirty)
+    }
+
+    /// Return the z-spread by which the zero rates of a curve are shifted to imply the price of
+    /// the bond.
+    ///
+    /// Parameters
+    /// ----------
+    /// price: float
+    ///     The price per 100 nominal.
+    /// curve: Curve
+    ///     The discount curve.
+    /// settlement: datetime
+    ///     The settlement date.
+    /// dirty: bool, optional
+    ///     Whether the price includes accrued interest.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2: the spread of the continuously compounded zero rates, in basis
+    /// points, with sensitivities to the nodes of the curve.
+    #[pyo3(name = "z_spread", signature = (price, curve, settlement, dirty=false))]
+    fn z_spread_py(
+        &self,
+        price: f64,
+        curve: &Curve,
+        #[pyo3(from_py_with = "extract_date")] settlement: NaiveDateTime,
+        dirty: bool,
+    ) -> PyResult<Number> {
+        self.z_spread(price, &curve.inner, &settlement, dirty)
+    }
+
+    /// Return the fixed rate for which the clean price of the bond implied by the discount
+    /// factors of a curve is 100.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The discount curve.
+    /// settlement: datetime
+    ///     The settlement date.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "par_rate")]
+    fn par_rate_py(
+        &self,
+        curve: &Curve,
+        #[pyo3(from_py_with = "extract_date")] settlement: NaiveDateTime,
+    ) -> PyResult<Number> {
+        self.par_rate(&curve.inner, &settlement)
+    }
+}
+
+#[pymethods]
+impl ConversionMethod {
+    // Pickling
+    #[new]
+    fn new_py(item: u8) -> PyResult<ConversionMethod> {
+        match item {
+            0_u8 => Ok(ConversionMethod::Ytm),
+            1_u8 => Ok(ConversionMethod::UstShort),
+            2_u8 => Ok(ConversionMethod::UstLong),
+            3_u8 => Ok(ConversionMethod::Eurex),
+            _ => Err(PyValueError::new_err(
+                "unreachable code on ConversionMethod pickle.",
+            )),
+        }
+    }
+    pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
+        *self = deserialize(state.as_bytes()).unwrap();
+        Ok(())
+    }
+    pub fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new_bound(py, &serialize(&self).unwrap()))
+    }
+    pub fn __getnewargs__(&self) -> PyResult<(u8,)> {
+        match self {
+            ConversionMethod::Ytm => Ok((0_u8,)),
+            ConversionMethod::UstShort => Ok((1_u8,)),
+            ConversionMethod::UstLong => Ok((2_u8,)),
+            ConversionMethod::Eurex => Ok((3_u8,)),
+        }
+    }
+}
+
+#[pymethods]
+impl BondFuture {
+    #[new]
+    #[pyo3(signature = (coupon, delivery, basket, method=ConversionMethod::Ytm))]
+    fn new_py(
+        coupon: f64,
+        delivery: (NaiveDateTime, NaiveDateTime),
+        basket: Vec<FixedRateBond>,
+        method: ConversionMethod,
+    ) -> PyResult<Self> {
+        BondFuture::try_new(coupon, delivery, basket, method)
+    }
+
+    /// The conversion factors of each bond in the basket.
+    #[getter]
+    fn cfs(&self) -> Vec<f64> {
+        self.cfs.clone()
+    }
+
+    /// Return the gross basis of each bond in the basket.
+    ///
+    /// Parameters
+    /// ----------
+    /// future_price: float
+    ///     The price of the future.
+    /// prices: list[float]
+    ///     The clean prices of the bonds in the basket.
+    ///
+    /// Returns
+    /// -------
+    /// list[float]
+    #[pyo3(name = "gross_basis")]
+    fn gross_basis_py(&self, future_price: f64, prices: Vec<f64>) -> PyResult<Vec<f64>> {
+        self.gross_basis(future_price, &prices)
+    }
+
+    /// Return the net basis of each bond in the basket via the proceeds method of repo.
+    ///
+    /// Parameters
+    /// ----------
+    /// future_price: float
+    ///     The price of the future.
+    /// prices: list[float]
+    ///     The clean prices of the bonds in the basket.
+    /// repo_rates: list[float]
+    ///     The repo rates of the bonds to delivery.
+    /// settlement: datetime
+    ///     The settlement date of the bonds.
+    /// delivery: datetime, optional
+    ///     The delivery date. If not given uses the last delivery day.
+    /// convention: Convention, optional
+    ///     The day count convention of the repo rates.
+    ///
+    /// Returns
+    /// -------
+    /// list[float]
+    #[pyo3(name = "net_basis", signature = (future_price, prices, repo_rates, settlement, delivery=None, convention=Convention::Act365F))]
+    fn net_basis_py(
+        &self,
+        future_price: f64,
+        prices: Vec<f64>,
+        repo_rates: Vec<f64>,
+        #[pyo3(from_py_with = "extract_date")] settlement: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_optional_date")] delivery: Option<NaiveDateTime>,
+        convention: Convention,
+    ) -> PyResult<Vec<f64>> {
+        self.net_basis(
+            future_price,
+            &prices,
+            &repo_rates,
+            &settlement,
+            delivery.as_ref(),
+            &convention,
+        )
+    }
+
+    /// Return the implied repo rate of each bond in the basket via the proceeds method.
+    ///
+    /// Parameters
+    /// ----------
+    /// future_price: float
+    ///     The price of the future.
+    /// prices: list[float]
+    ///     The clean prices of the bonds in the basket.
+    /// settlement: datetime
+    ///     The settlement date of the bonds.
+    /// delivery: datetime, optional
+    ///     The delivery date. If not given uses the last delivery day.
+    /// convention: Convention, optional
+    ///     The day count convention of the repo rates.
+    ///
+    /// Returns
+    /// -------
+    /// list[float]
+    #[pyo3(name = "implied_repo", signature = (future_price, prices, settlement, delivery=None, convention=Convention::Act365F))]
+    fn implied_repo_py(
+        &self,
+        future_price: f64,
+        prices: Vec<f64>,
+        #[pyo3(from_py_with = "extract_date")] settlement: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_optional_date")] delivery: Option<NaiveDateTime>,
+        convention: Convention,
+    ) -> PyResult<Vec<f64>> {
+        self.implied_repo(
+            future_price,
+            &prices,
+            &settlement,
+            delivery.as_ref(),
+            &convention,
+        )
+    }
+
+    /// Return the index of the cheapest-to-deliver bond in the basket.
+    ///
+    /// Parameters
+    /// ----------
+    /// future_price: float
+    ///     The price of the future.
+    /// prices: list[float]
+    ///     The clean prices of the bonds in the basket.
+    /// settlement: datetime
+    ///     The settlement date of the bonds.
+    /// delivery: datetime, optional
+    ///     The delivery date. If not given uses the last delivery day.
+    /// convention: Convention, optional
+    ///     The day count convention of the repo rates from which the cheapest is selected.
+    ///
+    /// Returns
+    /// -------
+    /// int
+    #[pyo3(name = "ctd_index", signature = (future_price, prices, settlement, delivery=None, convention=Convention::Act365F))]
+    fn ctd_index_py(
+        &self,
+        future_price: f64,
+        prices: Vec<f64>,
+        #[pyo3(from_py_with = "extract_date")] settlement: NaiveDateTime,
+        #[pyo3(from_py_with = "extract_optional_date")] delivery: Option<NaiveDateTime>,
+        convention: Convention,
+    ) -> PyResult<usize> {
+        self.ctd_index(
+            future_price,
+            &prices,
+            &settlement,
+            delivery.as_ref(),
+            &convention,
+        )
+    }
+
+    /// Return the price of the future implied by a curve, with sensitivities to its nodes.
+    ///
+    /// Parameters
+    /// ----------
+    /// curve: Curve
+    ///     The discount curve of the bonds in the basket.
+    /// delivery: datetime, optional
+    ///     The delivery date. If not given uses the last delivery day.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "price_from_curve", signature = (curve, delivery=None))]
+    fn price_from_curve_py(
+        &self,
+        curve: &Curve,
+        #[pyo3(from_py_with = "extract_optional_date")] delivery: Option<NaiveDateTime>,
+    ) -> PyResult<Number> {
+        self.price_from_curve(&curve.inner, delivery.as_ref())
+    }
+}
+
+/// Return the settlement ladder of cashflows, netted by currency and payment date.
+///
+/// Parameters
+/// ----------
+/// cashflows: list[tuple[Ccy, datetime, float | Dual | Dual2]]
+///     The cashflows of a portfolio, e.g. from the ``cashflow_table`` of each instrument.
+///
+/// Returns
+/// -------
+/// list[tuple[Ccy, datetime, float | Dual | Dual2]]
+#[pyfunction]
+#[pyo3(name = "net_cashflows")]
+pub(crate) fn net_cashflows_py(
+    cashflows: Vec<(Ccy, NaiveDateTime, Number)>,
+) -> Vec<(Ccy, NaiveDateTime, Number)> {
+    let cashflows: Vec<Cashflow> = cashflows
+        .into_iter()
+        .map(|(currency, payment, amount)| Cashflow::new(currency, payment, amount))
+        .collect();
+    net_cashflows(&cashflows)
+        .into_iter()
+        .map(|c| (c.currency, c.payment, c.amount))
+        .collect()
+}
+
+#[pymethods]
+impl RoundingMode {
+    // Pickling
+    #[new]
+    fn new_py(item: u8) -> PyResult<RoundingMode> {
+        match item {
+            0_u8 => Ok(RoundingMode::HalfUp),
+            1_u8 => Ok(RoundingMode::HalfDown),
+            2_u8 => Ok(RoundingMode::HalfEven),
+            3_u8 => Ok(RoundingMode::Up),
+            4_u8 => Ok(RoundingMode::Down),
+            _ => Err(PyValueError::new_err(
+                "unreachable code on RoundingMode pickle.",
+            )),
+        }
+    }
+    pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
+        *self = deserialize(state.as_bytes()).unwrap();
+        Ok(())
+    }
+    pub fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new_bound(py, &serialize(&self).unwrap()))
+    }
+    pub fn __getnewargs__(&self) -> PyResult<(u8,)> {
+        match self {
+            RoundingMode::HalfUp => Ok((0_u8,)),
+            RoundingMode::HalfDown => Ok((1_u8,)),
+            RoundingMode::HalfEven => Ok((2_u8,)),
+            RoundingMode::Up => Ok((3_u8,)),
+            RoundingMode::Down => Ok((4_u8,)),
+        }
+    }
+}
+
+#[pymethods]
+impl SettlementRounding {
+    #[new]
+    #[pyo3(signature = (mode=RoundingMode::HalfUp, precisions=None))]
+    fn new_py(mode: RoundingMode, precisions: Option<HashMap<Ccy, u32>>) -> Self {
+        SettlementRounding::new(mode, precisions.unwrap_or_default())
+    }
+
+    /// The rule for rounding amounts.
+    #[getter(mode)]
+    fn mode_py(&self) -> RoundingMode {
+        self.mode
+    }
+
+    /// Return the number of decimal places to which amounts of a currency are settled.
+    ///
+    /// Parameters
+    /// ----------
+    /// currency: Ccy or str
+    ///     The currency of the amounts.
+    ///
+    /// Returns
+    /// -------
+    /// int
+    #[pyo3(name = "decimals")]
+    fn decimals_py(&self, #[pyo3(from_py_with = "extract_ccy")] currency: Ccy) -> u32 {
+        self.decimals(&currency)
+    }
+
+    /// Return an amount rounded to the settlement precision of its currency.
+    ///
+    /// Parameters
+    /// ----------
+    /// amount: float, Dual or Dual2
+    ///     The exact amount, whose real value is rounded.
+    /// currency: Ccy or str
+    ///     The currency of the amount.
+    ///
+    /// Returns
+    /// -------
+    /// float
+    #[pyo3(name = "round")]
+    fn round_py(&self, amount: Number, #[pyo3(from_py_with = "extract_ccy")] currency: Ccy) -> f64 {
+        self.round(&amount, &currency)
+    }
+
+    /// Return cashflows with both their exact amounts and their rounded settlement figures.
+    ///
+    /// Parameters
+    /// ----------
+    /// cashflows: list[tuple[Ccy, datetime, float | Dual | Dual2]]
+    ///     The cashflows, e.g. from :meth:`~rateslib.rs.net_cashflows`.
+    ///
+    /// Returns
+    /// -------
+    /// list[tuple[Ccy, datetime, float | Dual | Dual2, float]]
+    #[pyo3(name = "settle")]
+    fn settle_py(
+        &self,
+        cashflows: Vec<(Ccy, NaiveDateTime, Number)>,
+    ) -> Vec<(Ccy, NaiveDateTime, Number, f64)> {
+        let cashflows: Vec<Cashflow> = cashflows
+            .into_iter()
+            .map(|(currency, payment, amount)| Cashflow::new(currency, payment, amount))
+            .collect();
+        self.settle(&cashflows)
+            .into_iter()
+            .map(|c| (c.currency, c.payment, c.amount, c.settlement))
+            .collect()
+    }
+}
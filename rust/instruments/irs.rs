@@ -0,0 +1,389 @@
+use crate::calendars::{CalType, Convention, DateRoll, Modifier};
+use crate::curves::{CurveDF, CurveInterpolation};
+use crate::dual::Number;
+use crate::fx::rates::Ccy;
+use crate::instruments::leg::{year_fraction, FixedLeg};
+use crate::instruments::portfolio::Cashflow;
+use chrono::prelude::*;
+use pyo3::{pyclass, PyErr};
+use serde::{Deserialize, Serialize};
+
+/// An interest rate swap exchanging a fixed leg against a float leg, priced from a single curve.
+///
+/// Both legs share a schedule and a notional. A positive notional pays the fixed rate and
+/// receives the float rate. The float leg is forecast from the discount factors of the same
+/// curve, so each period's float cashflow is `N (v(start) / v(end) - 1)`.
+///
+/// Any of the frequency, convention, calendar, modifier and payment lag which is not given is
+/// filled from an instrument [spec](crate::specs), e.g. *"usd_irs"*. A modifier or payment lag
+/// which is neither given nor specified defaults to *"MF"* and zero.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct IRS {
+    pub(crate) fixed_leg: FixedLeg,
+}
+
+impl IRS {
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_new(
+        effective: NaiveDateTime,
+        termination: NaiveDateTime,
+        frequency_months: Option<i32>,
+        notional: f64,
+        fixed_rate: f64,
+        convention: Option<Convention>,
+        calendar: Option<&CalType>,
+        modifier: Option<Modifier>,
+        payment_lag: Option<i32>,
+        spec: Option<&str>,
+    ) -> Result<Self, PyErr> {
+        let fixed_leg = FixedLeg::try_new(
+            effective,
+            termination,
+            frequency_months,
+            notional,
+            fixed_rate,
+            convention,
+            calendar,
+            modifier,
+            payment_lag,
+            false,
+            None,
+            None,
+            spec,
+        )?;
+        Ok(IRS { fixed_leg })
+    }
+
+    /// The float cashflow of each period, forecast from the discount factors of a curve.
+    pub fn float_cashflows<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Vec<Number> {
+        float_cashflows(&self.fixed_leg, curve)
+    }
+
+    /// The net present value of the float leg from the discount factors of a curve.
+    ///
+    /// Cashflows paid on or before the curve's initial node are excluded.
+    pub fn float_npv<T: CurveInterpolation, U: DateRoll>(&self, curve: &CurveDF<T, U>) -> Number {
+        self.float_npv_discounted(curve, curve)
+    }
+
+    /// The net present value of the float leg forecast from one curve and discounted by another.
+    ///
+    /// Cashflows paid on or before the discount curve's initial node are excluded.
+    pub fn float_npv_discounted<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        forecast: &CurveDF<T, U>,
+        discount: &CurveDF<T, U>,
+    ) -> Number {
+        float_npv(&self.fixed_leg, forecast, discount)
+    }
+
+    /// The cashflows of both legs of the swap, with the float leg forecast from a curve.
+    pub fn cashflow_table<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        currency: Ccy,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Vec<Cashflow>, PyErr> {
+        let mut table = self.fixed_leg.cashflow_table(currency)?;
+        table.extend(
+            self.fixed_leg
+                .payments
+                .iter()
+                .zip(self.float_cashflows(curve))
+                .map(|(payment, amount)| Cashflow::new(currency, *payment, amount)),
+        );
+        Ok(table)
+    }
+
+    /// The net present value of the swap from the discount factors of a curve.
+    pub fn npv<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        Ok(self.fixed_leg.npv(curve)? + self.float_npv(curve))
+    }
+
+    /// The mid-market fixed rate of the swap, in percent, for which its net present value is
+    /// zero.
+    pub fn rate<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        self.rate_discounted(curve, curve)
+    }
+
+    /// The mid-market fixed rate of the swap, in percent, with the float leg forecast from one
+    /// curve and both legs discounted by another.
+    pub fn rate_discounted<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        forecast: &CurveDF<T, U>,
+        discount: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        Ok(self.float_npv_discounted(forecast, discount)
+            / (self.fixed_leg.analytic_delta(discount)? * 100.0))
+    }
+
+    /// The change in net present value of the swap for a one basis point increase in its fixed
+    /// rate, calculated analytically from the annuity of the fixed leg.
+    pub fn analytic_pv01<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        self.fixed_leg.analytic_pv01(curve)
+    }
+
+    /// The change in net present value of the swap for a one basis point parallel increase in
+    /// the continuously compounded zero rates of a curve, calculated analytically.
+    ///
+    /// Each float cashflow `N (v(s) / v(e) - 1)` paid at `p` contributes
+    /// `N v(p) (v(s) / v(e) (t_e - t_s) - (v(s) / v(e) - 1) t_p)` per unit shift, with times
+    /// measured as [FixedLeg::period_dv01].
+    pub fn analytic_dv01<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        let leg = &self.fixed_leg;
+        let mut dv01 = leg.analytic_dv01(curve)?;
+        for i in 0..leg.n_periods() {
+            let t_p = year_fraction(curve, &leg.payments[i]);
+            if t_p <= 0.0 {
+                continue;
+            }
+            let (t_s, t_e) = (
+                year_fraction(curve, &leg.aschedule[i]),
+                year_fraction(curve, &leg.aschedule[i + 1]),
+            );
+            let growth = curve.interpolated_value(&leg.aschedule[i])
+                / curve.interpolated_value(&leg.aschedule[i + 1]);
+            let v_p = curve.interpolated_value(&leg.payments[i]);
+            dv01 = dv01
+                + (&growth * (t_e - t_s) - (growth - 1.0) * t_p)
+                    * v_p
+                    * (leg.notionals[i] / 10000.0);
+        }
+        Ok(dv01)
+    }
+}
+
+/// The float cashflow of each period of a leg's schedule, `N (v(start) / v(end) - 1)`, forecast
+/// from the discount factors of a curve.
+pub(crate) fn float_cashflows<T: CurveInterpolation, U: DateRoll>(
+    leg: &FixedLeg,
+    curve: &CurveDF<T, U>,
+) -> Vec<Number> {
+    (0..leg.n_periods())
+        .map(|i| {
+            let growth = curve.interpolated_value(&leg.aschedule[i])
+                / curve.interpolated_value(&leg.aschedule[i + 1]);
+            (growth - 1.0) * leg.notionals[i]
+        })
+        .collect()
+}
+
+/// The net present value of the float cashflows of a leg's schedule forecast from one curve and
+/// discounted by another.
+///
+/// Cashflows paid on or before the discount curve's initial node are excluded.
+pub(crate) fn float_npv<T: CurveInterpolation, U: DateRoll>(
+    leg: &FixedLeg,
+    forecast: &CurveDF<T, U>,
+    discount: &CurveDF<T, U>,
+) -> Number {
+    let initial = discount.nodes.first_key();
+    let mut npv = Number::F64(0.0);
+    for (payment, cashflow) in leg.payments.iter().zip(float_cashflows(leg, forecast)) {
+        if payment.and_utc().timestamp() <= initial {
+            continue;
+        }
+        npv = npv + cashflow * discount.interpolated_value(payment);
+    }
+    npv
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, NamedCal};
+    use crate::curves::{LogLinearInterpolator, Nodes};
+    use crate::risk::var::bumped_curve;
+    use indexmap::IndexMap;
+    use ndarray::Array1;
+
+    #[test]
+    fn test_irs_at_mid_market_rate_has_zero_npv() {
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2022, 1, 1), 1.0_f64),
+            (ndt(2027, 1, 1), 0.85_f64),
+        ]));
+        let curve = CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            "sofr",
+            Convention::Act360,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("nyc").unwrap(),
+        )
+        .unwrap();
+        let cal = CalType::NamedCal(NamedCal::try_new("nyc").unwrap());
+        let irs = |fixed_rate: f64| {
+            IRS::try_new(
+                ndt(2022, 1, 3),
+                ndt(2025, 1, 3),
+                Some(12),
+                1e6,
+                fixed_rate,
+                Some(Convention::Act360),
+                Some(&cal),
+                Some(Modifier::ModF),
+                Some(2),
+                None,
+            )
+            .unwrap()
+        };
+        let rate = f64::from(irs(1.0).rate(&curve).unwrap());
+        // continuously compounded rate of the curve is 3.25% on Act365F
+        assert!(rate > 3.0 && rate < 3.5);
+        let npv = irs(rate).npv(&curve).unwrap();
+        assert!(f64::from(npv).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_irs_conventions_from_spec() {
+        let (effective, termination) = (ndt(2024, 1, 10), ndt(2027, 1, 10));
+        let irs = IRS::try_new(
+            effective,
+            termination,
+            None,
+            1e6,
+            3.0,
+            None,
+            None,
+            None,
+            None,
+            Some("usd_irs"),
+        )
+        .unwrap();
+        let nyc = CalType::NamedCal(NamedCal::try_new("nyc").unwrap());
+        let expected = IRS::try_new(
+            effective,
+            termination,
+            Some(12),
+            1e6,
+            3.0,
+            Some(Convention::Act360),
+            Some(&nyc),
+            Some(Modifier::ModF),
+            Some(2),
+            None,
+        )
+        .unwrap();
+        assert_eq!(irs.fixed_leg.frequency_months, 12);
+        assert_eq!(irs.fixed_leg.convention, Convention::Act360);
+        assert_eq!(irs.fixed_leg.aschedule, expected.fixed_leg.aschedule);
+        assert_eq!(irs.fixed_leg.payments, expected.fixed_leg.payments);
+        // 10th January 2026 is a Saturday, rolled to Monday 12th and paid two days later
+        assert_eq!(irs.fixed_leg.payments[1], ndt(2026, 1, 14));
+
+        // given values take precedence over the spec
+        let irs = IRS::try_new(
+            effective,
+            termination,
+            Some(3),
+            1e6,
+            3.0,
+            Some(Convention::Act365F),
+            None,
+            None,
+            Some(0),
+            Some("usd_irs"),
+        )
+        .unwrap();
+        assert_eq!(irs.fixed_leg.frequency_months, 3);
+        assert_eq!(irs.fixed_leg.convention, Convention::Act365F);
+
+        // without a spec the modifier and payment lag default, as in rateslib (Python)
+        let irs = IRS::try_new(
+            effective,
+            termination,
+            Some(12),
+            1e6,
+            3.0,
+            Some(Convention::Act360),
+            Some(&nyc),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(irs.fixed_leg.payments[1], ndt(2026, 1, 12));
+
+        // without a spec every other convention must be given
+        let result = IRS::try_new(
+            effective,
+            termination,
+            None,
+            1e6,
+            3.0,
+            Some(Convention::Act360),
+            Some(&nyc),
+            Some(Modifier::ModF),
+            Some(2),
+            None,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_irs_analytic_pv01_and_dv01() {
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2022, 1, 1), 1.0_f64),
+            (ndt(2025, 1, 1), 0.92_f64),
+            (ndt(2032, 1, 1), 0.74_f64),
+        ]));
+        let curve = CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            "sofr",
+            Convention::Act360,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap();
+        let cal = CalType::NamedCal(NamedCal::try_new("all").unwrap());
+        let irs = |fixed_rate: f64| {
+            IRS::try_new(
+                ndt(2022, 1, 1),
+                ndt(2029, 7, 1),
+                Some(6),
+                1e6,
+                fixed_rate,
+                Some(Convention::Act360),
+                Some(&cal),
+                Some(Modifier::ModF),
+                Some(2),
+                None,
+            )
+            .unwrap()
+        };
+        let npv = |irs: &IRS, curve: &CurveDF<_, _>| f64::from(irs.npv(curve).unwrap());
+
+        let pv01 = f64::from(irs(3.0).analytic_pv01(&curve).unwrap());
+        let fd = npv(&irs(3.01), &curve) - npv(&irs(3.0), &curve);
+        assert!((pv01 - fd).abs() < 1e-8);
+        assert!(pv01 < 0.0);
+
+        let dv01 = f64::from(irs(3.0).analytic_dv01(&curve).unwrap());
+        let up = bumped_curve(&curve, Array1::from_elem(3, 0.01).view()).unwrap();
+        let down = bumped_curve(&curve, Array1::from_elem(3, -0.01).view()).unwrap();
+        let fd = (npv(&irs(3.0), &up) - npv(&irs(3.0), &down)) / 0.02;
+        assert!((dv01 - fd).abs() < 1e-6 * dv01.abs());
+        // the payer of fixed gains as rates rise
+        assert!(dv01 > 0.0);
+    }
+}
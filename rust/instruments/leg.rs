@@ -0,0 +1,746 @@
+use crate::calendars::{CalType, Convention, DateRoll, Modifier, RollDay};
+use crate::curves::{CurveDF, CurveEvaluation, CurveInterpolation};
+use crate::dual::Number;
+use crate::fx::rates::Ccy;
+use crate::instruments::portfolio::Cashflow;
+use crate::scheduling::{split_dates, unadjusted_add_months, Stub};
+use crate::specs::{
+    fill_from_spec, fill_from_spec_or, get_optional_spec, spec_calendar, spec_convention,
+    spec_days, spec_frequency_months, spec_modifier,
+};
+use chrono::prelude::*;
+use num_traits::Pow;
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, PyErr};
+use serde::{Deserialize, Serialize};
+
+/// A schedule of values each taking effect from a date, e.g. an amortising notional or a
+/// step-up coupon.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StepSchedule {
+    pub(crate) dates: Vec<NaiveDateTime>,
+    pub(crate) values: Vec<f64>,
+}
+
+impl StepSchedule {
+    pub fn try_new(dates: Vec<NaiveDateTime>, values: Vec<f64>) -> Result<Self, PyErr> {
+        if dates.len() != values.len() {
+            return Err(PyValueError::new_err(
+                "A step schedule must have the same number of `dates` and `values`.",
+            ));
+        }
+        if dates.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(PyValueError::new_err(
+                "The `dates` of a step schedule must be strictly increasing.",
+            ));
+        }
+        Ok(StepSchedule { dates, values })
+    }
+
+    /// The value in effect on `date`, or `initial` if `date` precedes every step.
+    pub fn value(&self, date: &NaiveDateTime, initial: f64) -> f64 {
+        match self.dates.partition_point(|d| d <= date) {
+            0 => initial,
+            i => self.values[i - 1],
+        }
+    }
+
+    /// Check every step takes effect on the start date of a period of an unadjusted schedule.
+    fn validate(&self, uschedule: &[NaiveDateTime], name: &str) -> Result<(), PyErr> {
+        let starts = &uschedule[..uschedule.len() - 1];
+        match self.dates.iter().find(|d| !starts.contains(d)) {
+            Some(d) => Err(PyValueError::new_err(format!(
+                "`{}` date {} is not the start date of a period of the schedule.",
+                name,
+                d.format("%Y-%m-%d")
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A fixed rate leg with a regular schedule rolled backwards from termination.
+///
+/// The notional and fixed rate of each period are set by optional step schedules, whose dates
+/// must be start dates of periods. A positive notional pays the fixed rate, and therefore has
+/// negative cashflows. With a final exchange, amortised notional is repaid on each period's
+/// payment date and the outstanding notional on the last.
+///
+/// Any of the frequency, convention, calendar, modifier and payment lag which is not given is
+/// filled from an instrument [spec](crate::specs), e.g. *"usd_irs"*. A modifier or payment lag
+/// which is neither given nor specified defaults to *"MF"* and zero.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FixedLeg {
+    pub(crate) uschedule: Vec<NaiveDateTime>,
+    pub(crate) aschedule: Vec<NaiveDateTime>,
+    pub(crate) payments: Vec<NaiveDateTime>,
+    pub(crate) frequency_months: i32,
+    pub(crate) convention: Convention,
+    pub(crate) notionals: Vec<f64>,
+    pub(crate) rates: Vec<f64>,
+    pub(crate) final_exchange: bool,
+}
+
+impl FixedLeg {
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_new(
+        effective: NaiveDateTime,
+        termination: NaiveDateTime,
+        frequency_months: Option<i32>,
+        notional: f64,
+        fixed_rate: f64,
+        convention: Option<Convention>,
+        calendar: Option<&CalType>,
+        modifier: Option<Modifier>,
+        payment_lag: Option<i32>,
+        final_exchange: bool,
+        amortization: Option<StepSchedule>,
+        rate_steps: Option<StepSchedule>,
+        spec: Option<&str>,
+    ) -> Result<Self, PyErr> {
+        let spec = get_optional_spec(spec)?;
+        let spec = spec.as_ref();
+        let frequency_months =
+            fill_from_spec(frequency_months, spec, "frequency", spec_frequency_months)?;
+        let convention = fill_from_spec(convention, spec, "convention", spec_convention)?;
+        let calendar = fill_from_spec(calendar.cloned(), spec, "calendar", spec_calendar)?;
+        let modifier =
+            fill_from_spec_or(modifier, spec, "modifier", spec_modifier, Modifier::ModF)?;
+        let payment_lag = fill_from_spec_or(payment_lag, spec, "payment_lag", spec_days, 0)?;
+        if ![1, 2, 3, 4, 6, 12].contains(&frequency_months) {
+            return Err(PyValueError::new_err(
+                "`frequency_months` must be in {1, 2, 3, 4, 6, 12}.",
+            ));
+        }
+        if effective >= termination {
+            return Err(PyValueError::new_err(
+                "`effective` must be before `termination`.",
+            ));
+        }
+        let uschedule = unadjusted_schedule(&effective, &termination, frequency_months);
+        let starts = &uschedule[..uschedule.len() - 1];
+        let notionals: Vec<f64> = match amortization {
+            Some(steps) => {
+                steps.validate(&uschedule, "amortization")?;
+                starts.iter().map(|d| steps.value(d, notional)).collect()
+            }
+            None => vec![notional; starts.len()],
+        };
+        let rates: Vec<f64> = match rate_steps {
+            Some(steps) => {
+                steps.validate(&uschedule, "rate_steps")?;
+                starts.iter().map(|d| steps.value(d, fixed_rate)).collect()
+            }
+            None => vec![fixed_rate; starts.len()],
+        };
+        let aschedule: Vec<NaiveDateTime> = uschedule
+            .iter()
+            .map(|d| calendar.roll(d, &modifier, false))
+            .collect();
+        let payments = calendar.payment_dates(&aschedule, payment_lag);
+        Ok(FixedLeg {
+            uschedule,
+            aschedule,
+            payments,
+            frequency_months,
+            convention,
+            notionals,
+            rates,
+            final_exchange,
+        })
+    }
+
+    /// The number of periods.
+    pub fn n_periods(&self) -> usize {
+        self.notionals.len()
+    }
+
+    /// The day count fraction of the period from its adjusted accrual dates.
+    pub fn dcf(&self, period: usize) -> Result<f64, PyErr> {
+        let termination = self.aschedule[self.n_periods()];
+        let roll = RollDay::Int {
+            day: self.uschedule[self.n_periods()].day(),
+        };
+        let stub = period == 0
+            && self.uschedule[0]
+                != unadjusted_add_months(&self.uschedule[1], -self.frequency_months, &roll);
+        self.convention.dcf(
+            &self.aschedule[period],
+            &self.aschedule[period + 1],
+            Some(&termination),
+            Some(self.frequency_months),
+            Some(stub),
+            Some(&roll),
+            None,
+        )
+    }
+
+    /// The fixed rate cashflow of each period.
+    pub fn cashflows(&self) -> Result<Vec<f64>, PyErr> {
+        (0..self.n_periods())
+            .map(|i| Ok(-self.notionals[i] * self.rates[i] / 100.0 * self.dcf(i)?))
+            .collect()
+    }
+
+    /// The notional exchanged on the payment date of each period, being the amortised notional
+    /// and the outstanding notional on the last. All zero without a final exchange.
+    pub fn exchanges(&self) -> Vec<f64> {
+        let n = self.n_periods();
+        if !self.final_exchange {
+            return vec![0.0; n];
+        }
+        (0..n)
+            .map(|i| match i == n - 1 {
+                true => -self.notionals[i],
+                false => -(self.notionals[i] - self.notionals[i + 1]),
+            })
+            .collect()
+    }
+
+    /// The cashflows of the leg, being the fixed rate cashflow and any notional exchanged on the
+    /// payment date of each period.
+    pub fn cashflow_table(&self, currency: Ccy) -> Result<Vec<Cashflow>, PyErr> {
+        let exchanges = self.exchanges();
+        Ok(self
+            .cashflows()?
+            .into_iter()
+            .enumerate()
+            .map(|(i, c)| Cashflow::new(currency, self.payments[i], Number::F64(c + exchanges[i])))
+            .collect())
+    }
+
+    /// The net present value of the leg from the discount factors of a curve.
+    ///
+    /// Cashflows paid on or before the curve's initial node are excluded. The result carries
+    /// any sensitivities to the curve's nodes.
+    pub fn npv<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        let initial = curve.nodes.first_key();
+        let cashflows = self.cashflows()?;
+        let exchanges = self.exchanges();
+        let mut npv = Number::F64(0.0);
+        for (i, payment) in self.payments.iter().enumerate() {
+            if payment.and_utc().timestamp() <= initial {
+                continue;
+            }
+            npv = npv + curve.interpolated_value(payment) * (cashflows[i] + exchanges[i]);
+        }
+        Ok(npv)
+    }
+
+    /// The change in net present value of the leg for a one basis point increase in the fixed
+    /// rate of every period, with the sign of the notional.
+    ///
+    /// Cashflows paid on or before the curve's initial node are excluded.
+    pub fn analytic_delta<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        let initial = curve.nodes.first_key();
+        let mut delta = Number::F64(0.0);
+        for (i, payment) in self.payments.iter().enumerate() {
+            if payment.and_utc().timestamp() <= initial {
+                continue;
+            }
+            delta = delta
+                + curve.interpolated_value(payment) * (self.notionals[i] * self.dcf(i)? / 10000.0);
+        }
+        Ok(delta)
+    }
+
+    fn check_period(&self, period: usize) -> Result<(), PyErr> {
+        if period >= self.n_periods() {
+            return Err(PyValueError::new_err(format!(
+                "`period` {} is out of range for a leg of {} periods.",
+                period,
+                self.n_periods()
+            )));
+        }
+        Ok(())
+    }
+
+    /// The change in net present value of a period for a one basis point increase in its fixed
+    /// rate, calculated analytically from its annuity.
+    ///
+    /// # Errors
+    ///
+    /// If `period` is not a period of the leg.
+    ///
+    /// A period paid on or before the curve's initial node has zero PV01.
+    pub fn period_pv01<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        period: usize,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        self.check_period(period)?;
+        let payment = &self.payments[period];
+        if payment.and_utc().timestamp() <= curve.nodes.first_key() {
+            return Ok(Number::F64(0.0));
+        }
+        Ok(curve.interpolated_value(payment)
+            * (-self.notionals[period] * self.dcf(period)? / 10000.0))
+    }
+
+    /// The change in net present value of a period for a one basis point parallel increase in
+    /// the continuously compounded zero rates of a curve, calculated analytically from the time
+    /// to its payment.
+    ///
+    /// Times are measured from the curve's initial node in years of 365 days, consistently
+    /// with [bumped_curve](crate::risk::var::bumped_curve). A period paid on or before the
+    /// initial node has zero DV01.
+    ///
+    /// # Errors
+    ///
+    /// If `period` is not a period of the leg.
+    pub fn period_dv01<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        period: usize,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        self.check_period(period)?;
+        let payment = &self.payments[period];
+        let t = year_fraction(curve, payment);
+        if t <= 0.0 {
+            return Ok(Number::F64(0.0));
+        }
+        let cashflow = -self.notionals[period] * self.rates[period] / 100.0 * self.dcf(period)?
+            + self.exchanges()[period];
+        Ok(curve.interpolated_value(payment) * (-cashflow * t / 10000.0))
+    }
+
+    /// The PV01 of the leg, being the sum of the [FixedLeg::period_pv01] of each period.
+    pub fn analytic_pv01<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        (0..self.n_periods()).try_fold(Number::F64(0.0), |acc, i| {
+            Ok(acc + self.period_pv01(i, curve)?)
+        })
+    }
+
+    /// The DV01 of the leg, being the sum of the [FixedLeg::period_dv01] of each period.
+    pub fn analytic_dv01<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        (0..self.n_periods()).try_fold(Number::F64(0.0), |acc, i| {
+            Ok(acc + self.period_dv01(i, curve)?)
+        })
+    }
+}
+
+/// The time from the initial node of a curve to a date, in years of 365 days.
+pub(crate) fn year_fraction<T: CurveInterpolation, U: DateRoll>(
+    curve: &CurveDF<T, U>,
+    date: &NaiveDateTime,
+) -> f64 {
+    (date.and_utc().timestamp() - curve.nodes.first_key()) as f64 / (365.0 * 86400.0)
+}
+
+/// A zero coupon fixed leg with a single period paying a fixed rate compounded to maturity.
+///
+/// The fixed rate is an IRR, compounded at the frequency, so the cashflow depends nonlinearly on
+/// it:
+///
+/// ```text
+/// C = -N ((1 + R / 100f)^(df) - 1)
+/// ```
+///
+/// where `d` is the day count fraction of the leg and `f` the number of periods per year. All
+/// measures of the leg are analytic and carry sensitivities of `Dual` or `Dual2` fixed rates.
+///
+/// Any of the frequency, convention, calendar, modifier and payment lag which is not given is
+/// filled from an instrument [spec](crate::specs), e.g. *"gbp_zcs"*. A modifier or payment lag
+/// which is neither given nor specified defaults to *"MF"* and zero.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ZeroFixedLeg {
+    pub(crate) effective: NaiveDateTime,
+    pub(crate) termination: NaiveDateTime,
+    pub(crate) payment: NaiveDateTime,
+    pub(crate) frequency_months: i32,
+    pub(crate) notional: f64,
+    pub(crate) dcf: f64,
+}
+
+impl ZeroFixedLeg {
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_new(
+        effective: NaiveDateTime,
+        termination: NaiveDateTime,
+        frequency_months: Option<i32>,
+        notional: f64,
+        convention: Option<Convention>,
+        calendar: Option<&CalType>,
+        modifier: Option<Modifier>,
+        payment_lag: Option<i32>,
+        spec: Option<&str>,
+    ) -> Result<Self, PyErr> {
+        let spec = get_optional_spec(spec)?;
+        let spec = spec.as_ref();
+        let frequency_months =
+            fill_from_spec(frequency_months, spec, "frequency", spec_frequency_months)?;
+        let convention = fill_from_spec(convention, spec, "convention", spec_convention)?;
+        let calendar = fill_from_spec(calendar.cloned(), spec, "calendar", spec_calendar)?;
+        let modifier =
+            fill_from_spec_or(modifier, spec, "modifier", spec_modifier, Modifier::ModF)?;
+        let payment_lag = fill_from_spec_or(payment_lag, spec, "payment_lag", spec_days, 0)?;
+        if ![1, 2, 3, 4, 6, 12].contains(&frequency_months) {
+            return Err(PyValueError::new_err(
+                "`frequency_months` must be in {1, 2, 3, 4, 6, 12}.",
+            ));
+        }
+        if effective >= termination {
+            return Err(PyValueError::new_err(
+                "`effective` must be before `termination`.",
+            ));
+        }
+        let (start, end) = (
+            calendar.roll(&effective, &modifier, false),
+            calendar.roll(&termination, &modifier, false),
+        );
+        let dcf = convention.dcf(
+            &start,
+            &end,
+            Some(&end),
+            Some(frequency_months),
+            Some(false),
+            Some(&RollDay::Int {
+                day: termination.day(),
+            }),
+            None,
+        )?;
+        Ok(ZeroFixedLeg {
+            effective: start,
+            termination: end,
+            payment: calendar.lag(&end, payment_lag, true),
+            frequency_months,
+            notional,
+            dcf,
+        })
+    }
+
+    fn frequency(&self) -> f64 {
+        12.0 / self.frequency_months as f64
+    }
+
+    /// The growth of a unit invested at the fixed rate, compounded to maturity.
+    fn compounded(&self, fixed_rate: &Number) -> Number {
+        let f = self.frequency();
+        (fixed_rate / (100.0 * f) + 1.0).pow(self.dcf * f)
+    }
+
+    /// The single cashflow of the leg.
+    pub fn cashflow(&self, fixed_rate: &Number) -> Number {
+        (self.compounded(fixed_rate) - 1.0) * -self.notional
+    }
+
+    /// The net present value of the leg from the discount factors of any kind of curve.
+    pub fn npv(&self, fixed_rate: &Number, curve: &impl CurveEvaluation) -> Result<Number, PyErr> {
+        Ok(curve
+            .df(&self.payment)?
+            .try_mul(&self.cashflow(fixed_rate))?)
+    }
+
+    /// The change in net present value of the leg for a one basis point decrease in the fixed
+    /// rate, being the derivative of the net present value, at the fixed rate.
+    pub fn analytic_delta(
+        &self,
+        fixed_rate: &Number,
+        curve: &impl CurveEvaluation,
+    ) -> Result<Number, PyErr> {
+        let f = self.frequency();
+        let v = curve.df(&self.payment)?;
+        Ok((fixed_rate / (100.0 * f) + 1.0)
+            .pow(self.dcf * f - 1.0)
+            .try_mul(&v)?
+            * (self.notional * self.dcf / 10000.0))
+    }
+
+    /// The fixed rate for which the net present value of the leg equals `target_npv`.
+    ///
+    /// # Errors
+    ///
+    /// If no fixed rate attains `target_npv`, being a cashflow that repays at least the
+    /// discounted notional, for which the compounded growth would not be positive.
+    pub fn rate(&self, target_npv: &Number, curve: &impl CurveEvaluation) -> Result<Number, PyErr> {
+        let f = self.frequency();
+        let v = curve.df(&self.payment)?;
+        let compounded = 1.0_f64 - target_npv.try_div(&(v * self.notional))?;
+        if f64::from(&compounded) <= 0.0 {
+            return Err(PyValueError::new_err(
+                "`target_npv` is not attainable by any fixed rate of the ZeroFixedLeg.",
+            ));
+        }
+        Ok((compounded.pow(1.0 / (self.dcf * f)) - 1.0) * (100.0 * f))
+    }
+}
+
+/// Return the dates of a regular schedule rolled backwards from `termination` on its day of the
+/// month, with any short front stub ending at `effective`.
+pub(crate) fn unadjusted_schedule(
+    effective: &NaiveDateTime,
+    termination: &NaiveDateTime,
+    frequency_months: i32,
+) -> Vec<NaiveDateTime> {
+    split_dates(effective, termination, frequency_months, Stub::ShortFront)
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, NamedCal};
+    use crate::curves::{FlatCurve, LogLinearInterpolator, Nodes};
+    use crate::dual::{ADOrder, Dual2, Gradient1, Gradient2};
+    use crate::risk::var::bumped_curve;
+    use indexmap::IndexMap;
+    use ndarray::Array1;
+
+    fn loan(amortization: Option<StepSchedule>, rate_steps: Option<StepSchedule>) -> FixedLeg {
+        FixedLeg::try_new(
+            ndt(2022, 1, 1),
+            ndt(2024, 1, 1),
+            Some(6),
+            1e6,
+            4.0,
+            Some(Convention::Act365F),
+            Some(&CalType::NamedCal(NamedCal::try_new("all").unwrap())),
+            Some(Modifier::ModF),
+            Some(0),
+            true,
+            amortization,
+            rate_steps,
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_step_schedule_value() {
+        let steps =
+            StepSchedule::try_new(vec![ndt(2022, 7, 1), ndt(2023, 1, 1)], vec![5.0, 6.0]).unwrap();
+        assert_eq!(steps.value(&ndt(2022, 1, 1), 4.0), 4.0);
+        assert_eq!(steps.value(&ndt(2022, 7, 1), 4.0), 5.0);
+        assert_eq!(steps.value(&ndt(2023, 6, 1), 4.0), 6.0);
+    }
+
+    #[test]
+    fn test_step_schedule_invalid_raises() {
+        assert!(StepSchedule::try_new(vec![ndt(2022, 7, 1)], vec![]).is_err());
+        assert!(
+            StepSchedule::try_new(vec![ndt(2022, 7, 1), ndt(2022, 7, 1)], vec![1.0, 2.0]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_amortising_notionals_and_exchanges() {
+        let amortization = StepSchedule::try_new(
+            vec![ndt(2022, 7, 1), ndt(2023, 1, 1), ndt(2023, 7, 1)],
+            vec![0.75e6, 0.5e6, 0.25e6],
+        )
+        .unwrap();
+        let leg = loan(Some(amortization), None);
+        assert_eq!(leg.notionals, vec![1e6, 0.75e6, 0.5e6, 0.25e6]);
+        assert_eq!(leg.exchanges(), vec![-0.25e6; 4]);
+        let cashflows = leg.cashflows().unwrap();
+        let expected = -1e6 * 0.04 * 181.0 / 365.0;
+        assert!((cashflows[0] - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rate_steps() {
+        let rate_steps = StepSchedule::try_new(vec![ndt(2023, 1, 1)], vec![5.0]).unwrap();
+        let leg = loan(None, Some(rate_steps));
+        assert_eq!(leg.rates, vec![4.0, 4.0, 5.0, 5.0]);
+        assert_eq!(leg.exchanges(), vec![0.0, 0.0, 0.0, -1e6]);
+    }
+
+    #[test]
+    fn test_analytic_pv01_and_dv01() {
+        let amortization = StepSchedule::try_new(vec![ndt(2023, 1, 1)], vec![0.5e6]).unwrap();
+        let leg = loan(Some(amortization), None);
+        let curve = zero_curve();
+        let pv01 = f64::from(leg.analytic_pv01(&curve).unwrap());
+        let delta = f64::from(leg.analytic_delta(&curve).unwrap());
+        assert!((pv01 + delta).abs() < 1e-9);
+        let periods: f64 = (0..leg.n_periods())
+            .map(|i| f64::from(leg.period_pv01(i, &curve).unwrap()))
+            .sum();
+        assert!((pv01 - periods).abs() < 1e-9);
+        assert!(leg.period_pv01(leg.n_periods(), &curve).is_err());
+
+        let dv01 = f64::from(leg.analytic_dv01(&curve).unwrap());
+        let bumped = bumped_curve(&curve, Array1::ones(2).view()).unwrap();
+        let fd = f64::from(leg.npv(&bumped).unwrap()) - f64::from(leg.npv(&curve).unwrap());
+        assert!((dv01 - fd).abs() < 1e-3 * dv01.abs());
+        // the payer of fixed cashflows and exchanges gains as rates rise
+        assert!(dv01 > 0.0);
+    }
+
+    #[test]
+    fn test_steps_not_on_schedule_raises() {
+        let rate_steps = StepSchedule::try_new(vec![ndt(2023, 2, 1)], vec![5.0]).unwrap();
+        let result = FixedLeg::try_new(
+            ndt(2022, 1, 1),
+            ndt(2024, 1, 1),
+            Some(6),
+            1e6,
+            4.0,
+            Some(Convention::Act365F),
+            Some(&CalType::NamedCal(NamedCal::try_new("all").unwrap())),
+            Some(Modifier::ModF),
+            Some(0),
+            true,
+            None,
+            Some(rate_steps),
+            None,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_npv() {
+        let amortization = StepSchedule::try_new(vec![ndt(2023, 1, 1)], vec![0.5e6]).unwrap();
+        let leg = loan(Some(amortization), None);
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2022, 1, 1), 1.0_f64),
+            (ndt(2025, 1, 1), 0.88_f64),
+        ]));
+        let curve = CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            "sofr",
+            Convention::Act365F,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap();
+        let cashflows = leg.cashflows().unwrap();
+        let exchanges = leg.exchanges();
+        let expected: f64 = (0..4)
+            .map(|i| {
+                f64::from(curve.interpolated_value(&leg.payments[i]))
+                    * (cashflows[i] + exchanges[i])
+            })
+            .sum();
+        let result = leg.npv(&curve).unwrap();
+        assert!((f64::from(&result) - expected).abs() < 1e-9);
+    }
+
+    fn zero_leg() -> ZeroFixedLeg {
+        ZeroFixedLeg::try_new(
+            ndt(2022, 1, 1),
+            ndt(2025, 1, 1),
+            Some(6),
+            1e6,
+            Some(Convention::OnePlus),
+            Some(&CalType::NamedCal(NamedCal::try_new("all").unwrap())),
+            Some(Modifier::ModF),
+            Some(0),
+            None,
+        )
+        .unwrap()
+    }
+
+    fn zero_curve() -> CurveDF<LogLinearInterpolator, NamedCal> {
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2022, 1, 1), 1.0_f64),
+            (ndt(2026, 1, 1), 0.85_f64),
+        ]));
+        CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            "sofr",
+            Convention::Act365F,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_zero_fixed_leg_cashflow() {
+        let leg = zero_leg();
+        assert_eq!(leg.dcf, 3.0);
+        let cashflow = leg.cashflow(&Number::F64(5.0));
+        let expected = -1e6 * (1.025_f64.powf(6.0) - 1.0);
+        assert!((f64::from(cashflow) - expected).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_zero_fixed_leg_rate_roundtrip() {
+        let (leg, curve) = (zero_leg(), zero_curve());
+        let npv = leg.npv(&Number::F64(5.0), &curve).unwrap();
+        let rate = leg.rate(&npv, &curve).unwrap();
+        assert!((f64::from(rate) - 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_zero_fixed_leg_rate_unattainable_target() {
+        let (leg, curve) = (zero_leg(), zero_curve());
+        let v = f64::from(curve.interpolated_value(&leg.payment));
+        assert!(leg.rate(&Number::F64(v * 1e6), &curve).is_err());
+        assert!(leg.rate(&Number::F64(v * 2e6), &curve).is_err());
+        assert!(leg.rate(&Number::F64(v * 0.5e6), &curve).is_ok());
+    }
+
+    #[test]
+    fn test_zero_fixed_leg_mixed_dual_types_error() {
+        let (leg, mut curve) = (zero_leg(), zero_curve());
+        curve.set_ad_order(ADOrder::One).unwrap();
+        let fixed_rate = Number::Dual2(Dual2::new(5.0, vec!["r".to_string()]));
+        assert!(leg.npv(&fixed_rate, &curve).is_err());
+        assert!(leg.analytic_delta(&fixed_rate, &curve).is_err());
+        assert!(leg.rate(&fixed_rate, &curve).is_err());
+    }
+
+    #[test]
+    fn test_zero_fixed_leg_prices_any_curve_kind() {
+        let leg = zero_leg();
+        let (start, end) = (ndt(2022, 1, 1), ndt(2026, 1, 1));
+        let flat =
+            FlatCurve::try_new(Number::F64(4.0), Convention::Act365F, start, "flat").unwrap();
+        let nodal: CurveDF<LogLinearInterpolator, NamedCal> = CurveDF::constant_rate(
+            4.0,
+            Convention::Act365F,
+            start,
+            end,
+            "nodal",
+            Modifier::ModF,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap();
+        let fixed_rate = Number::F64(5.0);
+        let a = f64::from(leg.npv(&fixed_rate, &flat).unwrap());
+        let b = f64::from(leg.npv(&fixed_rate, &nodal).unwrap());
+        assert!((a - b).abs() < 1e-6);
+        let rate = leg.rate(&Number::F64(a), &flat).unwrap();
+        assert!((f64::from(rate) - 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_zero_fixed_leg_analytic_delta_is_derivative() {
+        let (leg, curve) = (zero_leg(), zero_curve());
+        let fixed_rate = Number::Dual2(Dual2::new(5.0, vec!["r".to_string()]));
+        let npv = leg.npv(&fixed_rate, &curve).unwrap();
+        let delta = leg.analytic_delta(&fixed_rate, &curve).unwrap();
+        match (npv, delta) {
+            (Number::Dual2(npv), Number::Dual2(delta)) => {
+                let grad = npv.gradient1(vec!["r".to_string()])[0];
+                assert!((grad + delta.real * 100.0).abs() < 1e-8);
+                // the nonlinear dependence on the fixed rate gives a second order sensitivity
+                let grad2 = npv.gradient2(vec!["r".to_string()])[[0, 0]];
+                let delta_grad = delta.gradient1(vec!["r".to_string()])[0];
+                assert!((grad2 + delta_grad * 100.0).abs() < 1e-8);
+                assert!(grad2 != 0.0);
+            }
+            _ => panic!("expected Dual2 results"),
+        }
+    }
+}
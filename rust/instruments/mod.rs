@@ -0,0 +1,30 @@
+//! Financial instruments priced from market conventions, quotes and curves.
+//!
+//! Instruments are constructed from the calendar, day count and accrual conventions of other
+//! modules. Prices derived from curves carry the sensitivities of the curve's nodes.
+
+pub(crate) mod leg;
+pub use crate::instruments::leg::{FixedLeg, StepSchedule, ZeroFixedLeg};
+
+pub(crate) mod irs;
+pub use crate::instruments::irs::IRS;
+
+pub(crate) mod sbs;
+pub use crate::instruments::sbs::SBS;
+
+pub(crate) mod generics;
+pub use crate::instruments::generics::{CurveInstrument, Fly, InstrumentType, Spread};
+
+pub(crate) mod portfolio;
+pub use crate::instruments::portfolio::{net_cashflows, Cashflow};
+
+pub(crate) mod settlement;
+pub use crate::instruments::settlement::{RoundingMode, SettledCashflow, SettlementRounding};
+
+pub(crate) mod bond;
+pub use crate::instruments::bond::FixedRateBond;
+
+pub(crate) mod bond_future;
+pub use crate::instruments::bond_future::{BondFuture, ConversionMethod};
+
+pub(crate) mod instruments_py;
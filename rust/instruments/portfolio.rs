@@ -0,0 +1,154 @@
+use crate::dual::Number;
+use crate::fx::rates::Ccy;
+use chrono::prelude::*;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+/// A cashflow of an amount of a currency paid on a date.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Cashflow {
+    pub currency: Ccy,
+    pub payment: NaiveDateTime,
+    pub amount: Number,
+}
+
+impl Cashflow {
+    pub fn new(currency: Ccy, payment: NaiveDateTime, amount: Number) -> Self {
+        Cashflow {
+            currency,
+            payment,
+            amount,
+        }
+    }
+}
+
+/// Return the settlement ladder of cashflows, netted by currency and payment date.
+///
+/// The ladder is sorted by currency, then payment date. Netted amounts retain the sensitivities
+/// of every cashflow, and are retained even if zero.
+pub fn net_cashflows(cashflows: &[Cashflow]) -> Vec<Cashflow> {
+    let mut ladder: IndexMap<(Ccy, NaiveDateTime), Number> = IndexMap::new();
+    for cf in cashflows {
+        ladder
+            .entry((cf.currency, cf.payment))
+            .and_modify(|a| *a = &*a + &cf.amount)
+            .or_insert_with(|| cf.amount.clone());
+    }
+    let mut ladder: Vec<Cashflow> = ladder
+        .into_iter()
+        .map(|((currency, payment), amount)| Cashflow::new(currency, payment, amount))
+        .collect();
+    ladder.sort_by(|a, b| {
+        (a.currency.name.as_str(), a.payment).cmp(&(b.currency.name.as_str(), b.payment))
+    });
+    ladder
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::ndt;
+    use crate::dual::{Dual, Gradient1};
+
+    #[test]
+    fn test_net_cashflows() {
+        let (usd, eur) = (Ccy::try_new("usd").unwrap(), Ccy::try_new("EUR").unwrap());
+        let cashflows = vec![
+            Cashflow::new(usd, ndt(2022, 7, 1), Number::F64(100.0)),
+            Cashflow::new(eur, ndt(2022, 7, 1), Number::F64(50.0)),
+            Cashflow::new(usd, ndt(2022, 4, 1), Number::F64(20.0)),
+            Cashflow::new(usd, ndt(2022, 7, 1), Number::F64(-30.0)),
+            Cashflow::new(eur, ndt(2022, 7, 1), Number::F64(-50.0)),
+        ];
+        let ladder = net_cashflows(&cashflows);
+        let result: Vec<(&str, NaiveDateTime, f64)> = ladder
+            .iter()
+            .map(|c| (c.currency.name.as_str(), c.payment, f64::from(&c.amount)))
+            .collect();
+        assert_eq!(
+            result,
+            vec![
+                ("eur", ndt(2022, 7, 1), 0.0),
+                ("usd", ndt(2022, 4, 1), 20.0),
+                ("usd", ndt(2022, 7, 1), 70.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_net_cashflows_retains_sensitivities() {
+        let usd = Ccy::try_new("usd").unwrap();
+        let cashflows = vec![
+            Cashflow::new(
+                usd,
+                ndt(2022, 7, 1),
+                Number::Dual(Dual::new(100.0, vec!["x".to_string()])),
+            ),
+            Cashflow::new(
+                usd,
+                ndt(2022, 7, 1),
+                Number::Dual(Dual::new(-30.0, vec!["y".to_string()])),
+            ),
+        ];
+        let ladder = net_cashflows(&cashflows);
+        match &ladder[0].amount {
+            Number::Dual(d) => {
+                assert_eq!(d.real, 70.0);
+                assert_eq!(
+                    d.gradient1(vec!["x".to_string(), "y".to_string()]).to_vec(),
+                    vec![1.0, 1.0]
+                );
+            }
+            _ => panic!("expected a Dual amount"),
+        }
+    }
+
+    #[test]
+    fn test_net_offsetting_swaps() {
+        use crate::calendars::{CalType, Convention, Modifier, NamedCal};
+        use crate::curves::{CurveDF, LogLinearInterpolator, Nodes};
+        use crate::instruments::IRS;
+
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2022, 1, 1), 1.0_f64),
+            (ndt(2025, 1, 1), 0.9_f64),
+        ]));
+        let curve = CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            "sofr",
+            Convention::Act360,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap();
+        let usd = Ccy::try_new("usd").unwrap();
+        let cal = CalType::NamedCal(NamedCal::try_new("all").unwrap());
+        let mut cashflows = vec![];
+        for (notional, effective) in [(1e6, ndt(2022, 1, 1)), (-1e6, ndt(2022, 7, 1))] {
+            let irs = IRS::try_new(
+                effective,
+                ndt(2024, 1, 1),
+                Some(6),
+                notional,
+                3.0,
+                Some(Convention::Act360),
+                Some(&cal),
+                Some(Modifier::ModF),
+                Some(0),
+                None,
+            )
+            .unwrap();
+            cashflows.extend(irs.cashflow_table(usd, &curve).unwrap());
+        }
+        let ladder = net_cashflows(&cashflows);
+        // only the first period of the longer swap remains after netting
+        assert_eq!(ladder.len(), 4);
+        assert!(f64::from(&ladder[0].amount).abs() > 1.0);
+        for cf in &ladder[1..] {
+            assert!(f64::from(&cf.amount).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use crate::calendars::{CalType, Convention, DateRoll, Modifier};
+use crate::curves::{CurveDF, CurveInterpolation};
+use crate::dual::Number;
+use crate::instruments::irs::float_npv;
+use crate::instruments::leg::FixedLeg;
+use crate::specs::{
+    fill_from_spec, fill_from_spec_or, get_optional_spec, spec_calendar, spec_convention,
+    spec_days, spec_frequency_months, spec_modifier,
+};
+use chrono::prelude::*;
+use pyo3::{pyclass, PyErr};
+use serde::{Deserialize, Serialize};
+
+/// A single currency basis swap exchanging two float legs, each forecast from its own curve, e.g.
+/// an IBOR leg against an OIS leg.
+///
+/// The legs share a notional and effective and termination dates but may have different
+/// frequencies. A positive notional pays the float rate of the first leg plus a spread, in basis
+/// points, and receives the float rate of the second leg. Float cashflows are forecast as
+/// [IRS](crate::instruments::IRS) cashflows and both legs are discounted by a single curve.
+///
+/// Any of the frequencies, convention, calendar, modifier and payment lag which is not given is
+/// filled from an instrument [spec](crate::specs), e.g. *"eur_sbs36"*, where the frequency of
+/// the second leg is its *"leg2_frequency"*. A modifier or payment lag which is neither given nor
+/// specified defaults to *"MF"* and zero.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SBS {
+    pub(crate) leg1: FixedLeg,
+    pub(crate) leg2: FixedLeg,
+}
+
+impl SBS {
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_new(
+        effective: NaiveDateTime,
+        termination: NaiveDateTime,
+        frequency_months: (Option<i32>, Option<i32>),
+        notional: f64,
+        float_spread: f64,
+        convention: Option<Convention>,
+        calendar: Option<&CalType>,
+        modifier: Option<Modifier>,
+        payment_lag: Option<i32>,
+        spec: Option<&str>,
+    ) -> Result<Self, PyErr> {
+        let spec = get_optional_spec(spec)?;
+        let spec = spec.as_ref();
+        let frequency_months = (
+            fill_from_spec(frequency_months.0, spec, "frequency", spec_frequency_months)?,
+            fill_from_spec(
+                frequency_months.1,
+                spec,
+                "leg2_frequency",
+                spec_frequency_months,
+            )?,
+        );
+        let convention = fill_from_spec(convention, spec, "convention", spec_convention)?;
+        let calendar = fill_from_spec(calendar.cloned(), spec, "calendar", spec_calendar)?;
+        let modifier =
+            fill_from_spec_or(modifier, spec, "modifier", spec_modifier, Modifier::ModF)?;
+        let payment_lag = fill_from_spec_or(payment_lag, spec, "payment_lag", spec_days, 0)?;
+        // the spread of the first leg is a fixed rate, in percent, on its schedule
+        let leg = |frequency: i32, fixed_rate: f64| {
+            FixedLeg::try_new(
+                effective,
+                termination,
+                Some(frequency),
+                notional,
+                fixed_rate,
+                Some(convention),
+                Some(&calendar),
+                Some(modifier),
+                Some(payment_lag),
+                false,
+                None,
+                None,
+                None,
+            )
+        };
+        Ok(SBS {
+            leg1: leg(frequency_months.0, float_spread / 100.0)?,
+            leg2: leg(frequency_months.1, 0.0)?,
+        })
+    }
+
+    /// The spread of the first leg, in basis points.
+    pub fn float_spread(&self) -> f64 {
+        self.leg1.rates[0] * 100.0
+    }
+
+    /// The net present value of the swap with each float leg forecast from its curve and both
+    /// discounted by another.
+    ///
+    /// Cashflows paid on or before the discount curve's initial node are excluded.
+    pub fn npv<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        forecast1: &CurveDF<T, U>,
+        forecast2: &CurveDF<T, U>,
+        discount: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        Ok(
+            self.leg1.npv(discount)? - float_npv(&self.leg1, forecast1, discount)
+                + float_npv(&self.leg2, forecast2, discount),
+        )
+    }
+
+    /// The mid-market spread of the first leg, in basis points, for which the net present value
+    /// of the swap is zero.
+    pub fn rate<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        forecast1: &CurveDF<T, U>,
+        forecast2: &CurveDF<T, U>,
+        discount: &CurveDF<T, U>,
+    ) -> Result<Number, PyErr> {
+        Ok((float_npv(&self.leg2, forecast2, discount)
+            - float_npv(&self.leg1, forecast1, discount))
+            / self.leg1.analytic_delta(discount)?)
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, NamedCal};
+    use crate::curves::{LogLinearInterpolator, Nodes};
+    use indexmap::IndexMap;
+
+    fn curve(id: &str, df: f64) -> CurveDF<LogLinearInterpolator, NamedCal> {
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2022, 1, 1), 1.0_f64),
+            (ndt(2027, 1, 1), df),
+        ]));
+        CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            id,
+            Convention::Act360,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn sbs(float_spread: f64) -> SBS {
+        let cal = CalType::NamedCal(NamedCal::try_new("all").unwrap());
+        SBS::try_new(
+            ndt(2022, 1, 1),
+            ndt(2025, 1, 1),
+            (Some(3), Some(12)),
+            1e6,
+            float_spread,
+            Some(Convention::Act360),
+            Some(&cal),
+            Some(Modifier::ModF),
+            Some(0),
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_sbs_conventions_from_spec() {
+        let sbs = SBS::try_new(
+            ndt(2022, 1, 1),
+            ndt(2025, 1, 1),
+            (None, None),
+            1e6,
+            5.0,
+            None,
+            None,
+            None,
+            None,
+            Some("eur_sbs36"),
+        )
+        .unwrap();
+        assert_eq!(sbs.leg1.frequency_months, 3);
+        assert_eq!(sbs.leg2.frequency_months, 6);
+        assert_eq!(sbs.leg1.convention, Convention::Act360);
+        // 1st January is a TARGET holiday, rolled modified following to the 2nd
+        assert_eq!(sbs.leg1.aschedule[4], ndt(2023, 1, 2));
+    }
+
+    #[test]
+    fn test_sbs_at_mid_market_spread_has_zero_npv() {
+        let (ibor, ois) = (curve("ibor", 0.86), curve("ois", 0.88));
+        let rate = f64::from(sbs(0.0).rate(&ibor, &ois, &ois).unwrap());
+        // the first leg is forecast at higher rates so its spread is negative
+        assert!(rate < 0.0);
+        let npv = sbs(rate).npv(&ibor, &ois, &ois).unwrap();
+        assert!(f64::from(npv).abs() < 1e-8);
+        assert!((sbs(rate).float_spread() - rate).abs() < 1e-12);
+        // legs forecast from the same curve differ only by the compounding of their frequencies
+        let same = f64::from(sbs(0.0).rate(&ois, &ois, &ois).unwrap());
+        assert!(same.abs() < 1.0);
+    }
+}
@@ -0,0 +1,188 @@
+use crate::dual::Number;
+use crate::fx::rates::Ccy;
+use crate::instruments::portfolio::Cashflow;
+use chrono::prelude::*;
+use pyo3::pyclass;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The rule for rounding an amount to the settlement precision of its currency.
+#[pyclass(module = "rateslib.rs", eq, eq_int)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum RoundingMode {
+    /// Round to nearest, with ties away from zero.
+    HalfUp,
+    /// Round to nearest, with ties towards zero.
+    HalfDown,
+    /// Round to nearest, with ties to the even digit, i.e. banker's rounding.
+    HalfEven,
+    /// Round away from zero.
+    Up,
+    /// Round towards zero, i.e. truncate.
+    Down,
+}
+
+/// Round `value` to `decimals` places according to its shortest decimal representation.
+///
+/// Rounding the decimal digits, rather than the binary value scaled by a power of ten, gives the
+/// result expected of the written amount, e.g. 2.675 rounds half up to 2.68 even though its
+/// nearest `f64` is slightly less than 2.675.
+fn round_decimal(value: f64, decimals: u32, mode: RoundingMode) -> f64 {
+    if !value.is_finite() || value == 0.0 {
+        return value;
+    }
+    // the shortest representation is `d.ddd...e<exp>` with at most 17 significant digits
+    let repr = format!("{:e}", value.abs());
+    let (mantissa, exponent) = repr.split_once('e').unwrap();
+    let exponent: i32 = exponent.parse().unwrap();
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let m: u128 = digits.parse().unwrap();
+
+    // the value is m * 10^-places and `drop` places must be removed
+    let places = digits.len() as i32 - 1 - exponent;
+    let drop = places - decimals as i32;
+    if drop <= 0 {
+        return value;
+    }
+    let (q, remainder) = match drop {
+        // m < 10^17 is less than half of 10^drop
+        19.. => (0_u128, std::cmp::Ordering::Less),
+        _ => {
+            let scale = 10_u128.pow(drop as u32);
+            (m / scale, (2 * (m % scale)).cmp(&scale))
+        }
+    };
+    let exact = drop <= 18 && m.is_multiple_of(10_u128.pow(drop as u32));
+    let round_up = match mode {
+        RoundingMode::HalfUp => remainder.is_ge(),
+        RoundingMode::HalfDown => remainder.is_gt(),
+        RoundingMode::HalfEven => remainder.is_gt() || (remainder.is_eq() && q % 2 == 1),
+        RoundingMode::Up => !exact,
+        RoundingMode::Down => false,
+    };
+    let q = if round_up { q + 1 } else { q };
+    let rounded: f64 = format!("{}e-{}", q, decimals).parse().unwrap();
+    rounded.copysign(value)
+}
+
+/// The settlement precision of each currency and the rule for rounding cashflows to it.
+///
+/// Currencies without a specified precision use their ISO 4217 minor units, e.g. 2 for USD, 0
+/// for JPY and 3 for KWD.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SettlementRounding {
+    pub(crate) mode: RoundingMode,
+    pub(crate) precisions: HashMap<Ccy, u32>,
+}
+
+/// A cashflow with both its exact amount and the figure paid after settlement rounding.
+#[derive(Clone, Debug)]
+pub struct SettledCashflow {
+    pub currency: Ccy,
+    pub payment: NaiveDateTime,
+    pub amount: Number,
+    pub settlement: f64,
+}
+
+impl SettlementRounding {
+    pub fn new(mode: RoundingMode, precisions: HashMap<Ccy, u32>) -> Self {
+        SettlementRounding { mode, precisions }
+    }
+
+    /// The number of decimal places to which amounts of `currency` are settled, by default its
+    /// ISO 4217 minor units or 2 if these are not known.
+    pub fn decimals(&self, currency: &Ccy) -> u32 {
+        self.precisions
+            .get(currency)
+            .copied()
+            .or(currency.minor_units())
+            .unwrap_or(2)
+    }
+
+    /// Round the real value of an amount of `currency` to its settlement precision.
+    pub fn round(&self, amount: &Number, currency: &Ccy) -> f64 {
+        round_decimal(f64::from(amount), self.decimals(currency), self.mode)
+    }
+
+    /// Return each cashflow with its exact amount, retaining any sensitivities, and its rounded
+    /// settlement figure.
+    ///
+    /// Rounding is applied per cashflow, so cashflows should be netted, e.g. by
+    /// [net_cashflows](crate::instruments::net_cashflows), before settlement where payments are
+    /// netted.
+    pub fn settle(&self, cashflows: &[Cashflow]) -> Vec<SettledCashflow> {
+        cashflows
+            .iter()
+            .map(|c| SettledCashflow {
+                currency: c.currency,
+                payment: c.payment,
+                amount: c.amount.clone(),
+                settlement: self.round(&c.amount, &c.currency),
+            })
+            .collect()
+    }
+}
+
+impl Default for SettlementRounding {
+    fn default() -> Self {
+        SettlementRounding::new(RoundingMode::HalfUp, HashMap::new())
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::ndt;
+    use crate::dual::Dual;
+
+    #[test]
+    fn test_round_decimal_modes() {
+        let cases = [
+            (2.675, RoundingMode::HalfUp, 2.68),
+            (2.665, RoundingMode::HalfDown, 2.66),
+            (2.665, RoundingMode::HalfEven, 2.66),
+            (2.675, RoundingMode::HalfEven, 2.68),
+            (-2.675, RoundingMode::HalfUp, -2.68),
+            (1.001, RoundingMode::Up, 1.01),
+            (1.009, RoundingMode::Down, 1.0),
+            (-1.009, RoundingMode::Down, -1.0),
+            (1.1, RoundingMode::Up, 1.1),
+            (1e-30, RoundingMode::Up, 0.01),
+            (1e-30, RoundingMode::HalfUp, 0.0),
+        ];
+        for (value, mode, expected) in cases {
+            assert_eq!(round_decimal(value, 2, mode), expected, "{value} {mode:?}");
+        }
+        assert_eq!(round_decimal(12345.5, 0, RoundingMode::HalfEven), 12346.0);
+        assert_eq!(
+            round_decimal(1234567.891, 2, RoundingMode::HalfUp),
+            1234567.89
+        );
+    }
+
+    #[test]
+    fn test_decimals() {
+        let usd = Ccy::try_new("usd").unwrap();
+        let rounding = SettlementRounding::new(RoundingMode::HalfUp, HashMap::from([(usd, 0_u32)]));
+        assert_eq!(rounding.decimals(&usd), 0);
+        assert_eq!(rounding.decimals(&Ccy::try_new("eur").unwrap()), 2);
+        assert_eq!(rounding.decimals(&Ccy::try_new("jpy").unwrap()), 0);
+        assert_eq!(rounding.decimals(&Ccy::try_new("kwd").unwrap()), 3);
+    }
+
+    #[test]
+    fn test_settle_retains_exact_amount() {
+        let (usd, jpy) = (Ccy::try_new("usd").unwrap(), Ccy::try_new("jpy").unwrap());
+        let amount = Number::Dual(Dual::new(-1234.5678, vec!["x".to_string()]));
+        let cashflows = vec![
+            Cashflow::new(usd, ndt(2022, 7, 1), amount.clone()),
+            Cashflow::new(jpy, ndt(2022, 7, 1), Number::F64(100000.5)),
+        ];
+        let settled = SettlementRounding::default().settle(&cashflows);
+        assert_eq!(settled[0].amount, amount);
+        assert_eq!(settled[0].settlement, -1234.57);
+        assert_eq!(settled[1].settlement, 100001.0);
+    }
+}
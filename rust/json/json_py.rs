@@ -9,10 +9,11 @@ use crate::calendars::{Cal, NamedCal, UnionCal};
 use crate::curves::curve_py::Curve;
 use crate::dual::{Dual, Dual2};
 use crate::fx::rates::FXRates;
-use crate::json::JSON;
+use crate::fx_vol::VolWeights;
+use crate::json::{JSONError, JSON};
+use crate::market::market_py::MarketPy;
 use crate::splines::{PPSplineDual, PPSplineDual2, PPSplineF64};
 use pyo3::conversion::ToPyObject;
-use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -29,9 +30,11 @@ pub(crate) enum DeserializedObj {
     NamedCal(NamedCal),
     FXRates(FXRates),
     Curve(Curve),
+    Market(MarketPy),
     PPSplineF64(PPSplineF64),
     PPSplineDual(PPSplineDual),
     PPSplineDual2(PPSplineDual2),
+    VolWeights(VolWeights),
 }
 
 impl IntoPy<PyObject> for DeserializedObj {
@@ -44,25 +47,53 @@ impl IntoPy<PyObject> for DeserializedObj {
             DeserializedObj::NamedCal(v) => Py::new(py, v).unwrap().to_object(py),
             DeserializedObj::FXRates(v) => Py::new(py, v).unwrap().to_object(py),
             DeserializedObj::Curve(v) => Py::new(py, v).unwrap().to_object(py),
+            DeserializedObj::Market(v) => Py::new(py, v).unwrap().to_object(py),
             DeserializedObj::PPSplineF64(v) => Py::new(py, v).unwrap().to_object(py),
             DeserializedObj::PPSplineDual(v) => Py::new(py, v).unwrap().to_object(py),
             DeserializedObj::PPSplineDual2(v) => Py::new(py, v).unwrap().to_object(py),
+            DeserializedObj::VolWeights(v) => Py::new(py, v).unwrap().to_object(py),
         }
     }
 }
 
 impl JSON for DeserializedObj {}
 
+// The expansion of `create_exception!` checks a `gil-refs` feature which this crate does not define.
+#[allow(unexpected_cfgs)]
+mod exceptions {
+    use pyo3::create_exception;
+    use pyo3::exceptions::PyValueError;
+
+    create_exception!(
+        rateslib.rs,
+        JSONDecodeError,
+        PyValueError,
+        "Raised when JSON cannot be deserialized, with the `path` within the JSON at which it \
+         failed and the type `expected` there, or *None*."
+    );
+}
+pub(crate) use exceptions::JSONDecodeError;
+
+/// Convert a deserialization error to a `JSONDecodeError` with `path` and `expected` attributes.
+fn json_decode_error(py: Python<'_>, e: JSONError) -> PyErr {
+    let err = JSONDecodeError::new_err(format!(
+        "Could not create Class or Struct from given JSON.\n{}",
+        e
+    ));
+    let value = err.value_bound(py);
+    match value
+        .setattr("path", &e.path)
+        .and_then(|_| value.setattr("expected", &e.expected))
+    {
+        Ok(_) => err,
+        Err(set_err) => set_err,
+    }
+}
+
 #[pyfunction]
 #[pyo3(name = "from_json")]
-pub(crate) fn from_json_py(_py: Python<'_>, json: &str) -> PyResult<DeserializedObj> {
-    match DeserializedObj::from_json(json) {
-        Ok(v) => Ok(v),
-        Err(e) => Err(PyValueError::new_err(format!(
-            "Could not create Class or Struct from given JSON.\n{}",
-            e
-        ))),
-    }
+pub(crate) fn from_json_py(py: Python<'_>, json: &str) -> PyResult<DeserializedObj> {
+    DeserializedObj::from_json(json).map_err(|e| json_decode_error(py, e))
 }
 
 #[cfg(test)]
@@ -82,4 +113,18 @@ mod tests {
             _ => assert!(false),
         }
     }
+
+    #[test]
+    fn test_dual_json_sorts_vars() {
+        let x = Dual::try_new(1.5, vec!["y".to_string(), "x".to_string()], vec![2.0, 3.0]).unwrap();
+        let json = DeserializedObj::Dual(x.clone()).to_json().unwrap();
+        assert_eq!(
+            json,
+            "{\"Dual\":{\"real\":1.5,\"vars\":[\"x\",\"y\"],\"dual\":{\"v\":1,\"dim\":[2],\"data\":[3.0,2.0]}}}"
+        );
+        match DeserializedObj::from_json(&json).unwrap() {
+            DeserializedObj::Dual(d) => assert_eq!(x, d),
+            _ => assert!(false),
+        }
+    }
 }
@@ -1,19 +1,167 @@
 //! Allows serialization and deserialization to JSON, with the ``serde`` crate.
+//!
+//! JSON output is canonical: equal objects produce identical strings, which may be diffed or
+//! hashed, see [JSON::canonical_hash]. Struct fields are written in their declared order, the
+//! entries of unordered collections, such as the holidays of a calendar or the `vars` of a dual
+//! number, are written in sorted order, and floats are written in their shortest round-trip
+//! representation with negative zero written as zero.
 
 pub mod json_py;
 
 use serde::{Deserialize, Serialize};
 use serde_json;
+use serde_json::Value;
+use std::fmt;
 
 /// Handles the `to` and `from` JSON conversion.
 pub trait JSON: Serialize + for<'de> Deserialize<'de> {
-    /// Return a JSON string representing the object.
+    /// Return a canonical JSON string representing the object.
     fn to_json(&self) -> serde_json::Result<String> {
-        serde_json::to_string(self)
+        let mut value = serde_json::to_value(self)?;
+        normalize_floats(&mut value);
+        serde_json::to_string(&value)
     }
 
     /// Create an object from a JSON string representation.
-    fn from_json(json: &str) -> serde_json::Result<Self> {
-        serde_json::from_str(json)
+    ///
+    /// On failure the error reports the path within the JSON at which deserialization failed,
+    /// and the type expected there.
+    fn from_json(json: &str) -> Result<Self, JSONError> {
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        let value: Self = serde_path_to_error::deserialize(&mut deserializer)?;
+        deserializer.end()?;
+        Ok(value)
+    }
+
+    /// Return a hash of the canonical JSON representing the object.
+    ///
+    /// The hash is the 64-bit FNV-1a hash of the JSON string, so it is stable across processes
+    /// and versions of Rust and may be used as a persistent cache key.
+    fn canonical_hash(&self) -> serde_json::Result<u64> {
+        Ok(fnv1a(self.to_json()?.as_bytes()))
+    }
+}
+
+/// An error deserializing an object from JSON.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JSONError {
+    /// The path within the JSON at which deserialization failed, e.g. `Dual.dual.data[0]`, or
+    /// `.` for the root.
+    pub path: String,
+    /// The type expected at `path`, if the error is of an unexpected type or value.
+    pub expected: Option<String>,
+    /// The description of the error, with its line and column.
+    pub message: String,
+}
+
+impl fmt::Display for JSONError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+impl std::error::Error for JSONError {}
+
+impl From<serde_path_to_error::Error<serde_json::Error>> for JSONError {
+    fn from(e: serde_path_to_error::Error<serde_json::Error>) -> Self {
+        let path = e.path().to_string();
+        let message = e.into_inner().to_string();
+        JSONError {
+            path,
+            expected: expected_type(&message),
+            message,
+        }
+    }
+}
+
+/// Trailing characters after a complete value are an error at the root.
+impl From<serde_json::Error> for JSONError {
+    fn from(e: serde_json::Error) -> Self {
+        let message = e.to_string();
+        JSONError {
+            path: ".".to_string(),
+            expected: expected_type(&message),
+            message,
+        }
+    }
+}
+
+/// The expected type of a serde error message, e.g. *"f64"* from *"invalid type: string "a",
+/// expected f64 at line 1 column 9"*.
+fn expected_type(message: &str) -> Option<String> {
+    let (_, expected) = message.split_once(", expected ")?;
+    let expected = match expected.rfind(" at line ") {
+        Some(i) => &expected[..i],
+        None => expected,
+    };
+    Some(expected.to_string())
+}
+
+/// Replace negative zero floats with zero, recursively.
+fn normalize_floats(value: &mut Value) {
+    match value {
+        Value::Number(n) if n.as_f64() == Some(0.0) && n.is_f64() => {
+            *value = Value::from(0.0_f64);
+        }
+        Value::Array(items) => items.iter_mut().for_each(normalize_floats),
+        Value::Object(map) => map.values_mut().for_each(normalize_floats),
+        _ => {}
+    }
+}
+
+/// The 64-bit FNV-1a hash of bytes.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325_u64, |hash, b| {
+        (hash ^ u64::from(*b)).wrapping_mul(0x100000001b3)
+    })
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Point {
+        y: f64,
+        x: f64,
+    }
+
+    impl JSON for Point {}
+
+    #[test]
+    fn test_canonical_json() {
+        let p = Point { y: -0.0, x: 0.1 };
+        // fields keep their declared order and negative zero is written as zero
+        assert_eq!(p.to_json().unwrap(), r#"{"y":0.0,"x":0.1}"#);
+        let q = Point { y: 0.0, x: 0.1 };
+        assert_eq!(p.canonical_hash().unwrap(), q.canonical_hash().unwrap());
+        assert_ne!(
+            p.canonical_hash().unwrap(),
+            Point { y: 0.0, x: 0.2 }.canonical_hash().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_from_json_error_path() {
+        let e = Point::from_json(r#"{"y":1.0,"x":"a"}"#).unwrap_err();
+        assert_eq!(e.path, "x");
+        assert_eq!(e.expected, Some("f64".to_string()));
+        assert!(e.to_string().starts_with("x: invalid type"));
+
+        let e = Point::from_json(r#"{"y":1.0}"#).unwrap_err();
+        assert_eq!(e.path, ".");
+        assert_eq!(e.expected, None);
+        assert!(e.message.starts_with("missing field `x`"));
+
+        let e = Point::from_json(r#"{"y":1.0,"x":2.0} x"#).unwrap_err();
+        assert_eq!(e.path, ".");
+        assert!(e.message.starts_with("trailing characters"));
+    }
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
     }
 }
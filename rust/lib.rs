@@ -9,8 +9,22 @@
 #[cfg(test)]
 mod tests;
 
+pub mod build_info;
+use build_info::build_info_py;
+
+pub mod config;
+use config::{get_tolerances_py, set_thread_tolerances_py, set_tolerances_py, Tolerances};
+
+pub mod logging;
+use logging::set_log_level_py;
+
+pub mod progress;
+use progress::CancellationToken;
+
 pub mod json;
-use crate::json::json_py::from_json_py;
+use crate::json::json_py::{from_json_py, JSONDecodeError};
+
+pub mod dates;
 
 // type GenericError = Box<dyn std::error::Error + Send + Sync + 'static>;
 // type GenericResult<T> = Result<T, GenericError>;
@@ -18,61 +32,170 @@ use crate::json::json_py::from_json_py;
 use pyo3::prelude::*;
 
 pub mod dual;
-use dual::linalg_py::{dsolve1_py, dsolve2_py, fdsolve1_py, fdsolve2_py};
+use dual::buffer_py::{buffer_to_duals_py, duals_to_buffer_py};
+use dual::linalg_py::{
+    ddot_py, dfmul_py, dsolve1_py, dsolve2_py, fdmul_py, fdsolve1_py, fdsolve2_py,
+};
+use dual::order_py::{order_of_py, set_order_py, to_dual2_py, to_f64_py};
+use dual::reverse::{Tape, TapeVar};
+use dual::{get_tag_template_py, get_variable_tags_py, set_tag_template_py, TagTemplate};
 use dual::{ADOrder, Dual, Dual2};
 
 pub mod splines;
 use splines::spline_py::{bspldnev_single, bsplev_single};
-use splines::{PPSplineDual, PPSplineDual2, PPSplineF64};
+use splines::{PPSplineDual, PPSplineDual2, PPSplineF64, SplineBoundary};
 
 pub mod curves;
-use curves::curve_py::Curve;
-use curves::interpolation::interpolation_py::index_left_f64;
+use curves::curve_py::{Curve, CurveHandle, CurveRegistryPy, SplicedCurve};
+use curves::interpolation::interpolation_py::{index_left_f64, interpolate_py};
 use curves::{
-    FlatBackwardInterpolator, FlatForwardInterpolator, LinearInterpolator,
-    LinearZeroRateInterpolator, LogLinearInterpolator, NullInterpolator,
+    CubicZeroRateInterpolator, CurveDiagnostics, FlatBackwardInterpolator, FlatCurve,
+    FlatForwardInterpolator, LinearInterpolator, LinearZeroRateInterpolator, LogCubicInterpolator,
+    LogLinearInterpolator, NullInterpolator, SpliceAdjustment, StepFunction,
 };
 
 pub mod calendars;
-use calendars::calendar_py::get_calendar_by_name_py;
+use calendars::calendar_py::{
+    adjust_dates_py, dcf_py, easter_py, fixing_dates_py, get_calendar_by_name_py, good_friday_py,
+    lockout_py, next_imm_py, previous_imm_py,
+};
 use calendars::{
-    Cal, Convention, Modifier, NamedCal, RollDay, UnionCal, _get_convention_str, _get_modifier_str,
+    _get_convention_str, _get_modifier_str, Bus252Accrual, Cal, Convention, FuturesExpiry,
+    Modifier, NamedCal, RollDay, UnionCal,
 };
 
+pub mod scheduling;
+use scheduling::scheduling_py::{infer_roll_py, split_period_py};
+use scheduling::Stub;
+
 pub mod fx;
 use fx::rates::ccy::Ccy;
 use fx::rates::{FXRate, FXRates};
 
+pub mod fx_vol;
+use fx_vol::fx_vol_py::{
+    atm_strike_py, forward_delta_py, premium_fx_rate_py, spot_delta_py,
+    strike_from_forward_delta_py, strike_from_spot_delta_py,
+};
+use fx_vol::{AtmConvention, BarrierKind, GarmanKohlhagen, PremiumCurrency, VolWeights};
+
+pub mod state;
+use state::CompositeState;
+
+pub mod maths;
+use maths::expression::Expression;
+
+pub mod mc;
+use mc::mc_py::mc_simulate_py;
+
+pub mod models;
+
+pub mod solver;
+use solver::solver_py::{solve_curve_py, solve_curves_py, solve_xccy_curve_py};
+use solver::{InitialGuess, SolverResult};
+
+pub mod risk;
+use risk::pnl::PnlExplain;
+use risk::risk_py::{historical_var_py, keyrate_ladder_py, pnl_explain_py, to_quote_space_py};
+
+pub mod specs;
+use specs::specs_py::{get_spec_py, register_spec_py, spec_names_py};
+
+pub mod indexes;
+use indexes::indexes_py::{get_index_py, index_names_py, register_index_py};
+use indexes::IndexDefinition;
+
+pub mod accrued;
+use accrued::accrued_py::{is_ex_div_py, simple_yield_py};
+use accrued::AccrualConvention;
+
+pub mod instruments;
+use instruments::instruments_py::net_cashflows_py;
+use instruments::{
+    BondFuture, ConversionMethod, FixedLeg, FixedRateBond, Fly, RoundingMode, SettlementRounding,
+    Spread, ZeroFixedLeg, IRS, SBS,
+};
+
+pub mod market;
+use market::market_py::{revalue_py, MarketPy, MarketStatePy};
+use market::{ADSnapshot, CarryRolldown, DeltaReport, GammaReport};
+
 #[pymodule]
 fn rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    // Build Info
+    m.add("__version__", build_info::VERSION)?;
+    m.add_function(wrap_pyfunction!(build_info_py, m)?)?;
+
+    // Config
+    m.add_class::<Tolerances>()?;
+    m.add_function(wrap_pyfunction!(get_tolerances_py, m)?)?;
+    m.add_function(wrap_pyfunction!(set_tolerances_py, m)?)?;
+    m.add_function(wrap_pyfunction!(set_thread_tolerances_py, m)?)?;
+
+    // Logging
+    m.add_function(wrap_pyfunction!(set_log_level_py, m)?)?;
+
+    // Progress
+    m.add_class::<CancellationToken>()?;
+
     // JSON
     m.add_function(wrap_pyfunction!(from_json_py, m)?)?;
+    m.add(
+        "JSONDecodeError",
+        m.py().get_type_bound::<JSONDecodeError>(),
+    )?;
 
     // Automatic Differentiation
     m.add_class::<Dual>()?;
     m.add_class::<Dual2>()?;
     m.add_class::<ADOrder>()?;
+    m.add_function(wrap_pyfunction!(order_of_py, m)?)?;
+    m.add_function(wrap_pyfunction!(set_order_py, m)?)?;
+    m.add_function(wrap_pyfunction!(to_dual2_py, m)?)?;
+    m.add_function(wrap_pyfunction!(to_f64_py, m)?)?;
+    m.add_class::<Tape>()?;
+    m.add_class::<TapeVar>()?;
     m.add_function(wrap_pyfunction!(dsolve1_py, m)?)?;
     m.add_function(wrap_pyfunction!(dsolve2_py, m)?)?;
     m.add_function(wrap_pyfunction!(fdsolve1_py, m)?)?;
     m.add_function(wrap_pyfunction!(fdsolve2_py, m)?)?;
+    m.add_function(wrap_pyfunction!(fdmul_py, m)?)?;
+    m.add_function(wrap_pyfunction!(dfmul_py, m)?)?;
+    m.add_function(wrap_pyfunction!(ddot_py, m)?)?;
+    m.add_function(wrap_pyfunction!(duals_to_buffer_py, m)?)?;
+    m.add_function(wrap_pyfunction!(buffer_to_duals_py, m)?)?;
+    m.add_class::<TagTemplate>()?;
+    m.add_function(wrap_pyfunction!(get_tag_template_py, m)?)?;
+    m.add_function(wrap_pyfunction!(set_tag_template_py, m)?)?;
+    m.add_function(wrap_pyfunction!(get_variable_tags_py, m)?)?;
 
     // Splines
     m.add_class::<PPSplineF64>()?;
     m.add_class::<PPSplineDual>()?;
     m.add_class::<PPSplineDual2>()?;
+    m.add_class::<SplineBoundary>()?;
     m.add_function(wrap_pyfunction!(bsplev_single, m)?)?;
     m.add_function(wrap_pyfunction!(bspldnev_single, m)?)?;
 
     // Curves
     m.add_class::<Curve>()?;
+    m.add_class::<CurveDiagnostics>()?;
+    m.add_class::<FlatCurve>()?;
+    m.add_class::<SplicedCurve>()?;
+    m.add_class::<SpliceAdjustment>()?;
+    m.add_class::<StepFunction>()?;
+    m.add_class::<CurveRegistryPy>()?;
+    m.add_class::<CurveHandle>()?;
     m.add_function(wrap_pyfunction!(index_left_f64, m)?)?;
+    m.add_function(wrap_pyfunction!(interpolate_py, m)?)?;
     m.add_class::<FlatBackwardInterpolator>()?;
     m.add_class::<FlatForwardInterpolator>()?;
     m.add_class::<LinearInterpolator>()?;
     m.add_class::<LogLinearInterpolator>()?;
     m.add_class::<LinearZeroRateInterpolator>()?;
     m.add_class::<NullInterpolator>()?;
+    m.add_class::<LogCubicInterpolator>()?;
+    m.add_class::<CubicZeroRateInterpolator>()?;
 
     // Calendars
     m.add_class::<Cal>()?;
@@ -81,14 +204,104 @@ fn rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<Modifier>()?;
     m.add_class::<RollDay>()?;
     m.add_class::<Convention>()?;
+    m.add_class::<Bus252Accrual>()?;
+    m.add_class::<FuturesExpiry>()?;
     m.add_function(wrap_pyfunction!(get_calendar_by_name_py, m)?)?;
     m.add_function(wrap_pyfunction!(_get_convention_str, m)?)?;
     m.add_function(wrap_pyfunction!(_get_modifier_str, m)?)?;
+    m.add_function(wrap_pyfunction!(dcf_py, m)?)?;
+    m.add_function(wrap_pyfunction!(fixing_dates_py, m)?)?;
+    m.add_function(wrap_pyfunction!(lockout_py, m)?)?;
+    m.add_function(wrap_pyfunction!(adjust_dates_py, m)?)?;
+    m.add_function(wrap_pyfunction!(easter_py, m)?)?;
+    m.add_function(wrap_pyfunction!(good_friday_py, m)?)?;
+    m.add_function(wrap_pyfunction!(next_imm_py, m)?)?;
+    m.add_function(wrap_pyfunction!(previous_imm_py, m)?)?;
+
+    // Scheduling
+    m.add_class::<Stub>()?;
+    m.add_function(wrap_pyfunction!(infer_roll_py, m)?)?;
+    m.add_function(wrap_pyfunction!(split_period_py, m)?)?;
 
     // FX
     m.add_class::<Ccy>()?;
     m.add_class::<FXRate>()?;
     m.add_class::<FXRates>()?;
 
+    // FX Volatility
+    m.add_class::<VolWeights>()?;
+    m.add_class::<AtmConvention>()?;
+    m.add_class::<PremiumCurrency>()?;
+    m.add_class::<BarrierKind>()?;
+    m.add_class::<GarmanKohlhagen>()?;
+    m.add_function(wrap_pyfunction!(forward_delta_py, m)?)?;
+    m.add_function(wrap_pyfunction!(spot_delta_py, m)?)?;
+    m.add_function(wrap_pyfunction!(strike_from_forward_delta_py, m)?)?;
+    m.add_function(wrap_pyfunction!(strike_from_spot_delta_py, m)?)?;
+    m.add_function(wrap_pyfunction!(atm_strike_py, m)?)?;
+    m.add_function(wrap_pyfunction!(premium_fx_rate_py, m)?)?;
+
+    // State
+    m.add_class::<CompositeState>()?;
+
+    // Solver
+    m.add_class::<SolverResult>()?;
+    m.add_class::<InitialGuess>()?;
+    m.add_function(wrap_pyfunction!(solve_curve_py, m)?)?;
+    m.add_function(wrap_pyfunction!(solve_curves_py, m)?)?;
+    m.add_function(wrap_pyfunction!(solve_xccy_curve_py, m)?)?;
+
+    // Risk
+    m.add_function(wrap_pyfunction!(keyrate_ladder_py, m)?)?;
+    m.add_function(wrap_pyfunction!(to_quote_space_py, m)?)?;
+    m.add_function(wrap_pyfunction!(historical_var_py, m)?)?;
+    m.add_class::<PnlExplain>()?;
+    m.add_function(wrap_pyfunction!(pnl_explain_py, m)?)?;
+
+    // Maths
+    m.add_class::<Expression>()?;
+
+    // Monte Carlo
+    m.add_function(wrap_pyfunction!(mc_simulate_py, m)?)?;
+
+    // Specs
+    m.add_function(wrap_pyfunction!(get_spec_py, m)?)?;
+    m.add_function(wrap_pyfunction!(register_spec_py, m)?)?;
+    m.add_function(wrap_pyfunction!(spec_names_py, m)?)?;
+
+    // Indexes
+    m.add_class::<IndexDefinition>()?;
+    m.add_function(wrap_pyfunction!(get_index_py, m)?)?;
+    m.add_function(wrap_pyfunction!(register_index_py, m)?)?;
+    m.add_function(wrap_pyfunction!(index_names_py, m)?)?;
+
+    // Accrued
+    m.add_class::<AccrualConvention>()?;
+    m.add_function(wrap_pyfunction!(is_ex_div_py, m)?)?;
+    m.add_function(wrap_pyfunction!(simple_yield_py, m)?)?;
+
+    // Instruments
+    m.add_class::<FixedLeg>()?;
+    m.add_class::<ZeroFixedLeg>()?;
+    m.add_class::<IRS>()?;
+    m.add_class::<SBS>()?;
+    m.add_class::<Spread>()?;
+    m.add_class::<Fly>()?;
+    m.add_function(wrap_pyfunction!(net_cashflows_py, m)?)?;
+    m.add_class::<RoundingMode>()?;
+    m.add_class::<SettlementRounding>()?;
+    m.add_class::<FixedRateBond>()?;
+    m.add_class::<ConversionMethod>()?;
+    m.add_class::<BondFuture>()?;
+
+    // Market
+    m.add_class::<MarketPy>()?;
+    m.add_class::<MarketStatePy>()?;
+    m.add_class::<ADSnapshot>()?;
+    m.add_class::<DeltaReport>()?;
+    m.add_class::<GammaReport>()?;
+    m.add_class::<CarryRolldown>()?;
+    m.add_function(wrap_pyfunction!(revalue_py, m)?)?;
+
     Ok(())
 }
@@ -0,0 +1,278 @@
+//! Instrumentation of solvers, curve rebuilds and cache invalidations with the `tracing` crate.
+//!
+//! Requires the `tracing` feature, without which the instrumentation compiles to nothing. Spans
+//! and events are emitted with the module path of the code as their target, e.g.
+//! `rateslib::solver`, and [set_log_level_py] routes them to the Python `logging` module, with
+//! the target as the logger name, e.g. `rateslib.solver`, and the messages prefixed by their
+//! enclosing spans.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// Emit a `tracing` event at a level, e.g. `event!(debug, n = 3, "message")`, when the
+/// `tracing` feature is enabled.
+macro_rules! event {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!($($arg)+);
+    };
+}
+pub(crate) use event;
+
+/// Enter a `tracing` span at a level until the end of the enclosing block, e.g.
+/// `span!(info_span, "solve", n = 3)`, when the `tracing` feature is enabled.
+macro_rules! span {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        let _entered = tracing::$level!($($arg)+).entered();
+    };
+}
+pub(crate) use span;
+
+#[cfg(feature = "tracing")]
+mod python {
+    use pyo3::prelude::*;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::fmt::{Debug, Write};
+    use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+    use std::sync::Mutex;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::subscriber::Interest;
+    use tracing::{Event, Level, Metadata, Subscriber};
+
+    /// The most verbose level emitted, from 0 for none to 5 for `TRACE`.
+    pub(super) static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+
+    thread_local! {
+        static ENTERED: RefCell<Vec<u64>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn verbosity(level: &Level) -> u8 {
+        match *level {
+            Level::ERROR => 1,
+            Level::WARN => 2,
+            Level::INFO => 3,
+            Level::DEBUG => 4,
+            Level::TRACE => 5,
+        }
+    }
+
+    /// The level of the Python `logging` module equivalent to a `tracing` level.
+    fn python_level(level: &Level) -> u8 {
+        match *level {
+            Level::ERROR => 40,
+            Level::WARN => 30,
+            Level::INFO => 20,
+            Level::DEBUG => 10,
+            Level::TRACE => 5,
+        }
+    }
+
+    /// Format the fields of a span or event, with any message first.
+    #[derive(Default)]
+    struct Fields {
+        message: String,
+        values: Vec<String>,
+    }
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            match field.name() {
+                "message" => write!(self.message, "{:?}", value).unwrap(),
+                name => self.values.push(format!("{}={:?}", name, value)),
+            }
+        }
+    }
+
+    impl Fields {
+        fn join(&self) -> String {
+            match (self.message.is_empty(), self.values.is_empty()) {
+                (_, true) => self.message.clone(),
+                (true, false) => self.values.join(" "),
+                (false, false) => format!("{} {}", self.message, self.values.join(" ")),
+            }
+        }
+    }
+
+    /// A subscriber which forwards events to the Python `logging` module.
+    #[derive(Default)]
+    pub(super) struct PythonLogger {
+        next_id: AtomicU64,
+        /// The description and number of handles of each open span.
+        spans: Mutex<HashMap<u64, (String, usize)>>,
+    }
+
+    impl Subscriber for PythonLogger {
+        fn register_callsite(&self, _metadata: &'static Metadata<'static>) -> Interest {
+            // the level may change, so every callsite is checked when reached
+            Interest::sometimes()
+        }
+
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            verbosity(metadata.level()) <= MAX_LEVEL.load(Ordering::Relaxed)
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+            let mut fields = Fields::default();
+            span.record(&mut fields);
+            let description = match fields.values.is_empty() {
+                true => span.metadata().name().to_string(),
+                false => format!("{}{{{}}}", span.metadata().name(), fields.values.join(" ")),
+            };
+            self.spans.lock().unwrap().insert(id, (description, 1));
+            Id::from_u64(id)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = Fields::default();
+            event.record(&mut fields);
+            let context: Vec<String> = {
+                let spans = self.spans.lock().unwrap();
+                ENTERED.with(|entered| {
+                    entered
+                        .borrow()
+                        .iter()
+                        .filter_map(|id| spans.get(id).map(|(d, _)| d.clone()))
+                        .collect()
+                })
+            };
+            let message = match context.is_empty() {
+                true => fields.join(),
+                false => format!("{}: {}", context.join(":"), fields.join()),
+            };
+            let metadata = event.metadata();
+            let logger = metadata.target().replace("::", ".");
+            Python::with_gil(|py| {
+                let _ = py
+                    .import_bound("logging")
+                    .and_then(|logging| logging.call_method1("getLogger", (logger,)))
+                    .and_then(|logger| {
+                        logger.call_method1("log", (python_level(metadata.level()), message))
+                    });
+            });
+        }
+
+        fn enter(&self, span: &Id) {
+            ENTERED.with(|entered| entered.borrow_mut().push(span.into_u64()));
+        }
+
+        fn exit(&self, span: &Id) {
+            ENTERED.with(|entered| {
+                let mut entered = entered.borrow_mut();
+                if let Some(i) = entered.iter().rposition(|id| *id == span.into_u64()) {
+                    entered.remove(i);
+                }
+            });
+        }
+
+        fn clone_span(&self, span: &Id) -> Id {
+            if let Some((_, handles)) = self.spans.lock().unwrap().get_mut(&span.into_u64()) {
+                *handles += 1;
+            }
+            span.clone()
+        }
+
+        fn try_close(&self, span: Id) -> bool {
+            let mut spans = self.spans.lock().unwrap();
+            let id = span.into_u64();
+            match spans.get_mut(&id) {
+                Some((_, handles)) if *handles > 1 => {
+                    *handles -= 1;
+                    false
+                }
+                _ => {
+                    spans.remove(&id);
+                    true
+                }
+            }
+        }
+    }
+}
+
+/// Parse the name of a level into its verbosity, from 0 for `"off"` to 5 for `"trace"`.
+fn parse_level(level: &str) -> Result<u8, PyErr> {
+    match level.to_lowercase().as_str() {
+        "off" => Ok(0),
+        "error" => Ok(1),
+        "warn" | "warning" => Ok(2),
+        "info" => Ok(3),
+        "debug" => Ok(4),
+        "trace" => Ok(5),
+        _ => Err(PyValueError::new_err(format!(
+            "'{}' is not a log level, expected one of 'off', 'error', 'warn', 'info', 'debug' or \
+             'trace'.",
+            level
+        ))),
+    }
+}
+
+/// Set the most verbose level of the records of the library, routed to Python's ``logging``.
+///
+/// Records are logged with the module path of the Rust code as the logger name, e.g.
+/// ``rateslib.solver``, and the level of ``logging`` equivalent to their own, where ``"trace"``
+/// has a level of 5. The loggers must also be configured to output the records, e.g. by
+/// ``logging.basicConfig(level=logging.DEBUG)``.
+///
+/// Parameters
+/// ----------
+/// level: str
+///     One of ``"off"``, ``"error"``, ``"warn"``, ``"info"``, ``"debug"`` or ``"trace"``.
+///
+/// Returns
+/// -------
+/// None
+///
+/// Raises
+/// ------
+/// ValueError
+///     If the library is built without the ``tracing`` feature, or if another ``tracing``
+///     subscriber is already installed in the process.
+#[pyfunction]
+#[pyo3(name = "set_log_level")]
+pub fn set_log_level_py(level: &str) -> PyResult<()> {
+    let verbosity = parse_level(level)?;
+    #[cfg(feature = "tracing")]
+    {
+        use std::sync::atomic::Ordering;
+        use std::sync::OnceLock;
+        static INSTALLED: OnceLock<bool> = OnceLock::new();
+        let installed = INSTALLED.get_or_init(|| {
+            tracing::subscriber::set_global_default(python::PythonLogger::default()).is_ok()
+        });
+        if !installed {
+            return Err(PyValueError::new_err(
+                "Another `tracing` subscriber is already installed in the process.",
+            ));
+        }
+        python::MAX_LEVEL.store(verbosity, Ordering::Relaxed);
+        Ok(())
+    }
+    #[cfg(not(feature = "tracing"))]
+    {
+        let _ = verbosity;
+        Err(PyValueError::new_err(
+            "rateslib is built without the `tracing` feature so cannot log.",
+        ))
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_level() {
+        assert_eq!(parse_level("OFF").unwrap(), 0);
+        assert_eq!(parse_level("warning").unwrap(), 2);
+        assert_eq!(parse_level("trace").unwrap(), 5);
+        assert!(parse_level("verbose").is_err());
+    }
+}
@@ -0,0 +1,590 @@
+//! Wrapper module to export the Rust market container to Python using pyo3 bindings.
+
+use crate::calendars::CalType;
+use crate::curves::curve_py::{Curve, CurveInterpolator};
+use crate::dual::{ADOrder, Number};
+use crate::fx::rates::{Ccy, FXRate, FXRates};
+use crate::fx::rates_py::{extract_ccy, extract_optional_ccy};
+use crate::instruments::InstrumentType;
+use crate::json::json_py::DeserializedObj;
+use crate::json::JSON;
+use crate::market::{
+    revalue_f64, ADSnapshot, CarryRolldown, CurveKey, DeltaReport, GammaReport, Market, MarketState,
+};
+use numpy::{PyArray1, PyArray2, ToPyArray};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Instruments with the currency, as a `Ccy` or a string, and index name of the curve each is
+/// priced from.
+type PortfolioPy<'py> = Vec<(InstrumentType, Bound<'py, PyAny>, String)>;
+
+/// A collection of curves, keyed by currency and index, with the FX rates between currencies.
+#[pyclass(module = "rateslib.rs", name = "Market")]
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct MarketPy {
+    pub(crate) inner: Market<CurveInterpolator, CalType>,
+}
+
+impl JSON for MarketPy {}
+
+/// The curves and FX rates of a market at a point in time, as returned by
+/// :meth:`~rateslib.rs.Market.snapshot`.
+#[pyclass(module = "rateslib.rs", name = "MarketState")]
+#[derive(Clone)]
+pub(crate) struct MarketStatePy {
+    pub(crate) inner: MarketState<CurveInterpolator, CalType>,
+}
+
+#[pymethods]
+impl MarketPy {
+    /// Create a market of curves and FX rates.
+    ///
+    /// Parameters
+    /// ----------
+    /// curves: list[tuple[Ccy | str, str, Curve]]
+    ///     The curves with the currency and index name by which each is looked up. Index names
+    ///     are case insensitive.
+    /// fx: FXRates, optional
+    ///     The FX rates, which must contain the currency of every curve.
+    #[new]
+    #[pyo3(signature = (curves, fx=None))]
+    fn new_py(
+        curves: Vec<(Bound<'_, PyAny>, String, Curve)>,
+        fx: Option<FXRates>,
+    ) -> PyResult<Self> {
+        let curves = curves
+            .into_iter()
+            .map(|(ccy, index, curve)| {
+                Ok((CurveKey::try_new(extract_ccy(&ccy)?, &index)?, curve.inner))
+            })
+            .collect::<Result<Vec<_>, PyErr>>()?;
+        Ok(MarketPy {
+            inner: Market::try_new(curves, fx)?,
+        })
+    }
+
+    /// The currency and index name of each curve in the market.
+    #[getter]
+    fn keys(&self) -> Vec<(Ccy, String)> {
+        self.inner
+            .keys()
+            .into_iter()
+            .map(|k| (k.currency, k.index))
+            .collect()
+    }
+
+    /// The FX rates of the market, if any.
+    #[getter]
+    fn fx(&self) -> Option<FXRates> {
+        self.inner.fx().cloned()
+    }
+
+    /// Return the curve of a currency and index.
+    ///
+    /// Parameters
+    /// ----------
+    /// currency: Ccy or str
+    ///     The currency of the curve.
+    /// index: str
+    ///     The index name of the curve.
+    ///
+    /// Returns
+    /// -------
+    /// Curve
+    fn curve(
+        &self,
+        #[pyo3(from_py_with = "extract_ccy")] currency: Ccy,
+        index: &str,
+    ) -> PyResult<Curve> {
+        Ok(Curve {
+            inner: self
+                .inner
+                .curve(&CurveKey::try_new(currency, index)?)?
+                .clone(),
+        })
+    }
+
+    /// Return the FX rate between two currencies, in units of ``rhs`` per unit of ``lhs``.
+    ///
+    /// Parameters
+    /// ----------
+    /// lhs: Ccy or str
+    ///     The currency being priced.
+    /// rhs: Ccy or str
+    ///     The currency of the price.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    fn fx_rate(
+        &self,
+        #[pyo3(from_py_with = "extract_ccy")] lhs: Ccy,
+        #[pyo3(from_py_with = "extract_ccy")] rhs: Ccy,
+    ) -> PyResult<Number> {
+        self.inner.fx_rate(&lhs, &rhs)
+    }
+
+    /// Return the net present value of an instrument priced from a curve of the market.
+    ///
+    /// Parameters
+    /// ----------
+    /// instrument: IRS, Spread or Fly
+    ///     The instrument to price.
+    /// currency: Ccy or str
+    ///     The currency of the curve, and of the net present value.
+    /// index: str
+    ///     The index name of the curve.
+    /// base: Ccy or str, optional
+    ///     The currency to convert the net present value into with the FX rates of the market.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(signature = (instrument, currency, index, base=None))]
+    fn npv(
+        &self,
+        instrument: InstrumentType,
+        #[pyo3(from_py_with = "extract_ccy")] currency: Ccy,
+        index: &str,
+        #[pyo3(from_py_with = "extract_optional_ccy")] base: Option<Ccy>,
+    ) -> PyResult<Number> {
+        self.inner.npv(
+            &instrument,
+            &CurveKey::try_new(currency, index)?,
+            base.as_ref(),
+        )
+    }
+
+    /// Return the mid-market rate of an instrument priced from a curve of the market.
+    ///
+    /// Parameters
+    /// ----------
+    /// instrument: IRS, Spread or Fly
+    ///     The instrument to price.
+    /// currency: Ccy or str
+    ///     The currency of the curve.
+    /// index: str
+    ///     The index name of the curve.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    fn rate(
+        &self,
+        instrument: InstrumentType,
+        #[pyo3(from_py_with = "extract_ccy")] currency: Ccy,
+        index: &str,
+    ) -> PyResult<Number> {
+        self.inner
+            .rate(&instrument, &CurveKey::try_new(currency, index)?)
+    }
+
+    /// Return the AD order of every curve and of the FX rates.
+    ///
+    /// Returns
+    /// -------
+    /// ADSnapshot
+    fn ad_snapshot(&self) -> ADSnapshot {
+        self.inner.ad_snapshot()
+    }
+
+    /// Set the AD order of every curve and of the FX rates.
+    ///
+    /// The market is unchanged if any object fails to be set.
+    ///
+    /// Parameters
+    /// ----------
+    /// ad: ADOrder
+    ///     The AD order to set.
+    ///
+    /// Returns
+    /// -------
+    /// ADSnapshot: the previous AD orders, to be restored with
+    /// :meth:`~rateslib.rs.Market.restore_ad_order`.
+    fn set_ad_order(&mut self, ad: ADOrder) -> PyResult<ADSnapshot> {
+        self.inner.set_ad_order(ad)
+    }
+
+    /// Restore the AD order of every curve and of the FX rates from a snapshot.
+    ///
+    /// Parameters
+    /// ----------
+    /// snapshot: ADSnapshot
+    ///     The AD orders, as returned by :meth:`~rateslib.rs.Market.set_ad_order`.
+    ///
+    /// Returns
+    /// -------
+    /// None
+    fn restore_ad_order(&mut self, snapshot: ADSnapshot) -> PyResult<()> {
+        self.inner.restore_ad_order(&snapshot)
+    }
+
+    /// Return the delta of a portfolio against the node values of every curve and the FX rates.
+    ///
+    /// The market is priced with AD order one and its AD orders are restored afterwards.
+    ///
+    /// Parameters
+    /// ----------
+    /// portfolio: list[tuple[IRS | Spread | Fly, Ccy | str, str]]
+    ///     Each instrument with the currency and index name of the curve it is priced from.
+    /// base: Ccy or str, optional
+    ///     The currency to convert every net present value into.
+    ///
+    /// Returns
+    /// -------
+    /// DeltaReport
+    #[pyo3(signature = (portfolio, base=None))]
+    fn delta(
+        &mut self,
+        portfolio: PortfolioPy<'_>,
+        #[pyo3(from_py_with = "extract_optional_ccy")] base: Option<Ccy>,
+    ) -> PyResult<DeltaReport> {
+        self.inner
+            .delta(&keyed_portfolio(portfolio)?, base.as_ref())
+    }
+
+    /// Return the gamma of a portfolio against the node values of every curve and the FX rates.
+    ///
+    /// The market is priced with AD order two and its AD orders are restored afterwards.
+    ///
+    /// Parameters
+    /// ----------
+    /// portfolio: list[tuple[IRS | Spread | Fly, Ccy | str, str]]
+    ///     Each instrument with the currency and index name of the curve it is priced from.
+    /// base: Ccy or str, optional
+    ///     The currency to convert every net present value into.
+    ///
+    /// Returns
+    /// -------
+    /// GammaReport
+    #[pyo3(signature = (portfolio, base=None))]
+    fn gamma(
+        &mut self,
+        portfolio: PortfolioPy<'_>,
+        #[pyo3(from_py_with = "extract_optional_ccy")] base: Option<Ccy>,
+    ) -> PyResult<GammaReport> {
+        self.inner
+            .gamma(&keyed_portfolio(portfolio)?, base.as_ref())
+    }
+
+    /// Return the carry and roll-down of an instrument priced from a curve of the market over a
+    /// horizon.
+    ///
+    /// Parameters
+    /// ----------
+    /// instrument: IRS, Spread or Fly
+    ///     The instrument to price.
+    /// currency: Ccy or str
+    ///     The currency of the curve.
+    /// index: str
+    ///     The index name of the curve.
+    /// horizon: str
+    ///     The tenor of the horizon from the initial node of the curve, e.g. *"3M"*.
+    ///
+    /// Returns
+    /// -------
+    /// CarryRolldown
+    fn carry_rolldown(
+        &self,
+        instrument: InstrumentType,
+        #[pyo3(from_py_with = "extract_ccy")] currency: Ccy,
+        index: &str,
+        horizon: &str,
+    ) -> PyResult<CarryRolldown> {
+        self.inner
+            .carry_rolldown(&instrument, &CurveKey::try_new(currency, index)?, horizon)
+    }
+
+    /// Return a snapshot of the curves and FX rates of the market.
+    ///
+    /// Returns
+    /// -------
+    /// MarketState
+    ///
+    /// Notes
+    /// -----
+    /// The snapshot shares the curves and FX rates of the market, which are only copied when
+    /// one is later modified, so taking a snapshot is cheap. Snapshots may be nested, e.g.
+    ///
+    /// .. code-block:: python
+    ///
+    ///    state = market.snapshot()
+    ///    market.update_fx([FXRate("eur", "usd", 1.10)])
+    ///    ...  # price the what-if scenario
+    ///    market.restore(state)
+    fn snapshot(&self) -> MarketStatePy {
+        MarketStatePy {
+            inner: self.inner.snapshot(),
+        }
+    }
+
+    /// Restore the curves and FX rates of the market from a snapshot.
+    ///
+    /// Parameters
+    /// ----------
+    /// state: MarketState
+    ///     The snapshot, as returned by :meth:`~rateslib.rs.Market.snapshot`.
+    ///
+    /// Returns
+    /// -------
+    /// None
+    fn restore(&mut self, state: &MarketStatePy) {
+        self.inner.restore(&state.inner)
+    }
+
+    /// Set the curve of a currency and index, replacing any existing curve.
+    ///
+    /// Parameters
+    /// ----------
+    /// currency: Ccy or str
+    ///     The currency of the curve, which must be one of the currencies of the FX rates, if any.
+    /// index: str
+    ///     The index name of the curve.
+    /// curve: Curve
+    ///     The curve.
+    ///
+    /// Returns
+    /// -------
+    /// None
+    fn set_curve(
+        &mut self,
+        #[pyo3(from_py_with = "extract_ccy")] currency: Ccy,
+        index: &str,
+        curve: Curve,
+    ) -> PyResult<()> {
+        self.inner
+            .set_curve(CurveKey::try_new(currency, index)?, curve.inner)
+    }
+
+    /// Update the FX rates of the market with new rates for some of their pairs.
+    ///
+    /// Parameters
+    /// ----------
+    /// fx_rates: list[FXRate]
+    ///     The new rates, whose pairs must be pairs of the FX rates of the market.
+    ///
+    /// Returns
+    /// -------
+    /// None
+    fn update_fx(&mut self, fx_rates: Vec<FXRate>) -> PyResult<()> {
+        self.inner.update_fx(fx_rates)
+    }
+
+    fn __eq__(&self, other: MarketPy) -> bool {
+        self.inner.eq(&other.inner)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Market: {} curves>", self.inner.curves.len())
+    }
+
+    // JSON
+    /// Create a JSON string representation of the object.
+    ///
+    /// Returns
+    /// -------
+    /// str
+    #[pyo3(name = "to_json")]
+    fn to_json_py(&self) -> PyResult<String> {
+        match DeserializedObj::Market(self.clone()).to_json() {
+            Ok(v) => Ok(v),
+            Err(_) => Err(PyValueError::new_err(
+                "Failed to serialize `Market` to JSON.",
+            )),
+        }
+    }
+
+    /// Return a hash of the canonical JSON representation of the object, which is stable
+    /// across sessions.
+    ///
+    /// Returns
+    /// -------
+    /// int
+    #[pyo3(name = "canonical_hash")]
+    fn canonical_hash_py(&self) -> PyResult<u64> {
+        match DeserializedObj::Market(self.clone()).canonical_hash() {
+            Ok(v) => Ok(v),
+            Err(_) => Err(PyValueError::new_err(
+                "Failed to serialize `Market` to JSON.",
+            )),
+        }
+    }
+}
+
+#[pymethods]
+impl ADSnapshot {
+    /// The AD order of each curve, in the order of the market's keys.
+    #[getter]
+    fn curves(&self) -> Vec<ADOrder> {
+        self.curves.clone()
+    }
+
+    /// The AD order of the FX rates, if the market has any.
+    #[getter]
+    fn fx(&self) -> Option<ADOrder> {
+        self.fx
+    }
+}
+
+#[pymethods]
+impl DeltaReport {
+    /// The net present value of the portfolio.
+    #[getter]
+    fn npv(&self) -> f64 {
+        self.npv
+    }
+
+    /// The variable of each sensitivity.
+    #[getter]
+    fn vars(&self) -> Vec<String> {
+        self.vars.clone()
+    }
+
+    /// The bucket of each variable, being its curve key, e.g. *"usd_sofr"*, or *"fx"*.
+    #[getter]
+    fn buckets(&self) -> Vec<String> {
+        self.buckets.clone()
+    }
+
+    /// The derivative of the net present value to each variable.
+    #[getter]
+    fn delta<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f64>> {
+        self.delta.to_pyarray_bound(py)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<DeltaReport: {} vars>", self.vars.len())
+    }
+}
+
+#[pymethods]
+impl GammaReport {
+    /// The net present value of the portfolio.
+    #[getter]
+    fn npv(&self) -> f64 {
+        self.npv
+    }
+
+    /// The variable of each row and column of the sensitivities.
+    #[getter]
+    fn vars(&self) -> Vec<String> {
+        self.vars.clone()
+    }
+
+    /// The bucket of each variable, being its curve key, e.g. *"usd_sofr"*, or *"fx"*.
+    #[getter]
+    fn buckets(&self) -> Vec<String> {
+        self.buckets.clone()
+    }
+
+    /// The second derivative of the net present value to each pair of variables.
+    #[getter]
+    fn gamma<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray2<f64>> {
+        self.gamma.to_pyarray_bound(py)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<GammaReport: {} vars>", self.vars.len())
+    }
+}
+
+#[pymethods]
+impl CarryRolldown {
+    /// The net present value today.
+    #[getter]
+    fn npv(&self) -> f64 {
+        self.npv
+    }
+
+    /// The net present value at the horizon if forward rates are realised.
+    #[getter]
+    fn forward_npv(&self) -> f64 {
+        self.forward_npv
+    }
+
+    /// The net present value at the horizon if the curve is unchanged.
+    #[getter]
+    fn rolldown_npv(&self) -> f64 {
+        self.rolldown_npv
+    }
+
+    /// The mid-market rate today.
+    #[getter]
+    fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// The mid-market rate at the horizon if forward rates are realised.
+    #[getter]
+    fn forward_rate(&self) -> f64 {
+        self.forward_rate
+    }
+
+    /// The mid-market rate at the horizon if the curve is unchanged.
+    #[getter]
+    fn rolldown_rate(&self) -> f64 {
+        self.rolldown_rate
+    }
+
+    /// The change in net present value from today to the horizon if forward rates are realised.
+    #[getter(carry)]
+    fn carry_py(&self) -> f64 {
+        self.carry()
+    }
+
+    /// The change in net present value at the horizon from an unchanged, rather than a realised
+    /// forward, curve.
+    #[getter(rolldown)]
+    fn rolldown_py(&self) -> f64 {
+        self.rolldown()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<CarryRolldown: carry {:.2}, rolldown {:.2}>",
+            self.carry(),
+            self.rolldown()
+        )
+    }
+}
+
+/// Key each instrument of a portfolio by the currency and index name of its curve.
+fn keyed_portfolio(portfolio: PortfolioPy<'_>) -> PyResult<Vec<(InstrumentType, CurveKey)>> {
+    portfolio
+        .into_iter()
+        .map(|(instrument, ccy, index)| {
+            Ok((instrument, CurveKey::try_new(extract_ccy(&ccy)?, &index)?))
+        })
+        .collect()
+}
+
+/// Return the net present value of each instrument under each market of a scenario grid,
+/// revalued in parallel without AD.
+///
+/// Parameters
+/// ----------
+/// markets: list[Market]
+///     The scenario markets.
+/// portfolio: list[tuple[IRS | Spread | Fly, Ccy | str, str]]
+///     Each instrument with the currency and index name of the curve it is priced from.
+/// base: Ccy or str, optional
+///     The currency to convert every net present value into.
+///
+/// Returns
+/// -------
+/// 2d-ndarray of shape (markets, instruments)
+#[pyfunction]
+#[pyo3(name = "revalue", signature = (markets, portfolio, base=None))]
+pub(crate) fn revalue_py<'py>(
+    py: Python<'py>,
+    markets: Vec<MarketPy>,
+    portfolio: PortfolioPy<'py>,
+    #[pyo3(from_py_with = "extract_optional_ccy")] base: Option<Ccy>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let markets: Vec<_> = markets.into_iter().map(|m| m.inner).collect();
+    let portfolio = keyed_portfolio(portfolio)?;
+    let values = py.allow_threads(|| revalue_f64(&markets, &portfolio, base.as_ref()))?;
+    Ok(values.to_pyarray_bound(py))
+}
@@ -0,0 +1,486 @@
+//! A market of curves and FX rates against which instruments are priced.
+//!
+//! Curves are keyed by the currency of the cashflows they discount and an index name, e.g.
+//! `("usd", "sofr")`, so that pricing methods receive a single market rather than loose curves
+//! and FX rates as separate arguments.
+
+use crate::calendars::DateRoll;
+use crate::curves::{CurveDF, CurveInterpolation};
+use crate::dual::{ADOrder, Number};
+use crate::fx::rates::{Ccy, FXRate, FXRates};
+use crate::instruments::CurveInstrument;
+use crate::json::JSON;
+use indexmap::IndexMap;
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, PyErr};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+pub(crate) mod market_py;
+
+mod revalue;
+pub use crate::market::revalue::revalue_f64;
+
+mod risk;
+pub use crate::market::risk::{CarryRolldown, DeltaReport, GammaReport};
+
+/// The key of a curve in a [Market], being a currency and a lowercase index name.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CurveKey {
+    pub currency: Ccy,
+    pub index: String,
+}
+
+impl CurveKey {
+    pub fn try_new(currency: Ccy, index: &str) -> Result<Self, PyErr> {
+        let index = index.trim().to_lowercase();
+        if index.is_empty() {
+            return Err(PyValueError::new_err("Curve `index` cannot be empty."));
+        }
+        Ok(CurveKey { currency, index })
+    }
+}
+
+/// The AD order of each curve, in key order, and of the FX rates of a [Market].
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ADSnapshot {
+    pub curves: Vec<ADOrder>,
+    pub fx: Option<ADOrder>,
+}
+
+/// A collection of curves, keyed by currency and index, with the FX rates between currencies.
+///
+/// Curves and FX rates are shared between clones of a market, and with its [MarketState]
+/// snapshots, until they are modified, when only the modified object is copied.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "T: Serialize, U: Serialize",
+    deserialize = "T: Deserialize<'de>, U: Deserialize<'de>"
+))]
+pub struct Market<T: CurveInterpolation, U: DateRoll> {
+    #[serde(with = "indexmap::map::serde_seq")]
+    pub(crate) curves: IndexMap<CurveKey, Arc<CurveDF<T, U>>>,
+    pub(crate) fx: Option<Arc<FXRates>>,
+}
+
+/// The curves and FX rates of a [Market] at a point in time, to be restored with
+/// [Market::restore].
+///
+/// A snapshot shares the objects of the market rather than copying them.
+#[derive(Clone, Debug)]
+pub struct MarketState<T: CurveInterpolation, U: DateRoll> {
+    curves: IndexMap<CurveKey, Arc<CurveDF<T, U>>>,
+    fx: Option<Arc<FXRates>>,
+}
+
+impl<T, U> PartialEq for Market<T, U>
+where
+    T: CurveInterpolation + PartialEq,
+    U: DateRoll + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.curves == other.curves && self.fx == other.fx
+    }
+}
+
+impl<T: CurveInterpolation, U: DateRoll> Market<T, U> {
+    /// Create a market from keyed curves and optional FX rates.
+    ///
+    /// # Errors
+    ///
+    /// If a key is duplicated or, when FX rates are given, the currency of a curve is not one of
+    /// the currencies of the FX rates.
+    pub fn try_new(
+        curves: Vec<(CurveKey, CurveDF<T, U>)>,
+        fx: Option<FXRates>,
+    ) -> Result<Self, PyErr> {
+        let mut map: IndexMap<CurveKey, Arc<CurveDF<T, U>>> = IndexMap::with_capacity(curves.len());
+        for (key, curve) in curves {
+            validate_currency(&key, fx.as_ref())?;
+            if map.contains_key(&key) {
+                return Err(PyValueError::new_err(format!(
+                    "Curve key ('{}', '{}') is duplicated in the market.",
+                    key.currency.name, key.index
+                )));
+            }
+            map.insert(key, Arc::new(curve));
+        }
+        Ok(Market {
+            curves: map,
+            fx: fx.map(Arc::new),
+        })
+    }
+
+    /// The keys of the curves in the market, in insertion order.
+    pub fn keys(&self) -> Vec<CurveKey> {
+        self.curves.keys().cloned().collect()
+    }
+
+    /// Return the curve with the given key.
+    pub fn curve(&self, key: &CurveKey) -> Result<&CurveDF<T, U>, PyErr> {
+        self.curves.get(key).map(|c| c.as_ref()).ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "Curve key ('{}', '{}') is not found in the market.",
+                key.currency.name, key.index
+            ))
+        })
+    }
+
+    /// The FX rates of the market, if any.
+    pub fn fx(&self) -> Option<&FXRates> {
+        self.fx.as_deref()
+    }
+
+    /// Return the FX rate between two currencies, in units of `rhs` per unit of `lhs`.
+    ///
+    /// The rate of a currency to itself is 1.0 whether or not the market has FX rates.
+    pub fn fx_rate(&self, lhs: &Ccy, rhs: &Ccy) -> Result<Number, PyErr> {
+        if lhs == rhs {
+            return Ok(Number::F64(1.0));
+        }
+        let fx = self.fx.as_ref().ok_or_else(|| {
+            PyValueError::new_err("The market has no FX rates to convert between currencies.")
+        })?;
+        fx.rate(lhs, rhs).ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "The FX rates of the market do not contain '{}{}'.",
+                lhs.name, rhs.name
+            ))
+        })
+    }
+
+    /// The net present value of an instrument priced from the curve with the given key,
+    /// converted to the `base` currency if given.
+    pub fn npv<I: CurveInstrument>(
+        &self,
+        instrument: &I,
+        key: &CurveKey,
+        base: Option<&Ccy>,
+    ) -> Result<Number, PyErr> {
+        let npv = instrument.npv(self.curve(key)?)?;
+        match base {
+            Some(base) if *base != key.currency => Ok(npv * self.fx_rate(&key.currency, base)?),
+            _ => Ok(npv),
+        }
+    }
+
+    /// The AD order of every curve and of the FX rates.
+    pub fn ad_snapshot(&self) -> ADSnapshot {
+        ADSnapshot {
+            curves: self.curves.values().map(|c| c.ad()).collect(),
+            fx: self.fx.as_ref().map(|f| f.ad()),
+        }
+    }
+
+    /// The mid-market rate of an instrument priced from the curve with the given key.
+    pub fn rate<I: CurveInstrument>(
+        &self,
+        instrument: &I,
+        key: &CurveKey,
+    ) -> Result<Number, PyErr> {
+        instrument.rate(self.curve(key)?)
+    }
+
+    /// Return a snapshot of the curves and FX rates of the market.
+    ///
+    /// This copies a pointer to each object rather than the object itself.
+    pub fn snapshot(&self) -> MarketState<T, U> {
+        MarketState {
+            curves: self.curves.clone(),
+            fx: self.fx.clone(),
+        }
+    }
+
+    /// Restore the curves and FX rates of the market from a snapshot.
+    pub fn restore(&mut self, state: &MarketState<T, U>) {
+        self.curves = state.curves.clone();
+        self.fx = state.fx.clone();
+    }
+
+    /// Set the curve with the given key, replacing any existing curve with the key.
+    ///
+    /// # Errors
+    ///
+    /// If the market has FX rates and the currency of the key is not one of their currencies.
+    pub fn set_curve(&mut self, key: CurveKey, curve: CurveDF<T, U>) -> Result<(), PyErr> {
+        validate_currency(&key, self.fx())?;
+        self.curves.insert(key, Arc::new(curve));
+        Ok(())
+    }
+}
+
+/// Validate that the currency of a curve key is one of the currencies of the FX rates, if any.
+fn validate_currency(key: &CurveKey, fx: Option<&FXRates>) -> Result<(), PyErr> {
+    match fx {
+        Some(fx) if fx.get_ccy_index(&key.currency).is_none() => {
+            Err(PyValueError::new_err(format!(
+                "Curve currency '{}' is not a currency of the FX rates.",
+                key.currency.name
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+impl<T, U> Market<T, U>
+where
+    T: CurveInterpolation + Clone,
+    U: DateRoll + Clone,
+{
+    /// Set the AD order of every curve and of the FX rates, returning the snapshot of their
+    /// previous orders.
+    ///
+    /// The market is unchanged if any object fails to be set.
+    pub fn set_ad_order(&mut self, ad: ADOrder) -> Result<ADSnapshot, PyErr> {
+        let snapshot = self.ad_snapshot();
+        let n = self.curves.len();
+        self.restore_ad_order(&ADSnapshot {
+            curves: vec![ad; n],
+            fx: self.fx.as_ref().map(|_| ad),
+        })?;
+        Ok(snapshot)
+    }
+
+    /// Restore the AD order of every curve and of the FX rates from a snapshot.
+    ///
+    /// The market is unchanged if the snapshot does not match its objects or any object fails
+    /// to be set.
+    pub fn restore_ad_order(&mut self, snapshot: &ADSnapshot) -> Result<(), PyErr> {
+        if snapshot.curves.len() != self.curves.len() || snapshot.fx.is_some() != self.fx.is_some()
+        {
+            return Err(PyValueError::new_err(
+                "The AD snapshot does not match the curves and FX rates of the market.",
+            ));
+        }
+        let mut curves = self.curves.clone();
+        for (curve, ad) in curves.values_mut().zip(&snapshot.curves) {
+            if curve.ad() != *ad {
+                Arc::make_mut(curve).set_ad_order(*ad)?;
+            }
+        }
+        let mut fx = self.fx.clone();
+        if let (Some(fx), Some(ad)) = (fx.as_mut(), snapshot.fx) {
+            if fx.ad() != ad {
+                Arc::make_mut(fx).set_ad_order(ad)?;
+            }
+        }
+        self.curves = curves;
+        self.fx = fx;
+        Ok(())
+    }
+
+    /// Update the FX rates of the market, see [FXRates::update].
+    ///
+    /// # Errors
+    ///
+    /// If the market has no FX rates or the update fails, when the market is unchanged.
+    pub fn update_fx(&mut self, fx_rates: Vec<FXRate>) -> Result<(), PyErr> {
+        let mut fx = self
+            .fx
+            .clone()
+            .ok_or_else(|| PyValueError::new_err("The market has no FX rates to update."))?;
+        Arc::make_mut(&mut fx).update(fx_rates)?;
+        self.fx = Some(fx);
+        Ok(())
+    }
+}
+
+impl<T, U> JSON for Market<T, U>
+where
+    T: CurveInterpolation + for<'a> Deserialize<'a> + Serialize,
+    U: DateRoll + for<'a> Deserialize<'a> + Serialize,
+{
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, CalType, Convention, Modifier, NamedCal};
+    use crate::curves::{LogLinearInterpolator, Nodes};
+    use crate::dual::ADOrder;
+    use crate::instruments::IRS;
+
+    fn curve(id: &str) -> CurveDF<LogLinearInterpolator, NamedCal> {
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2022, 1, 1), 1.0_f64),
+            (ndt(2027, 1, 1), 0.88_f64),
+            (ndt(2032, 1, 1), 0.74_f64),
+        ]));
+        let mut curve = CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            id,
+            Convention::Act360,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap();
+        curve.set_ad_order(ADOrder::One).unwrap();
+        curve
+    }
+
+    fn ccy(name: &str) -> Ccy {
+        Ccy::try_new(name).unwrap()
+    }
+
+    fn key(currency: &str, index: &str) -> CurveKey {
+        CurveKey::try_new(ccy(currency), index).unwrap()
+    }
+
+    fn fx() -> FXRates {
+        FXRates::try_new(
+            vec![FXRate::try_new("eur", "usd", Number::F64(1.08), None).unwrap()],
+            None,
+        )
+        .unwrap()
+    }
+
+    fn market() -> Market<LogLinearInterpolator, NamedCal> {
+        Market::try_new(
+            vec![
+                (key("usd", "SOFR"), curve("sofr")),
+                (key("eur", "estr"), curve("estr")),
+            ],
+            Some(fx()),
+        )
+        .unwrap()
+    }
+
+    fn irs() -> IRS {
+        let cal = CalType::NamedCal(NamedCal::try_new("all").unwrap());
+        IRS::try_new(
+            ndt(2022, 1, 1),
+            ndt(2027, 1, 1),
+            Some(12),
+            1e6,
+            2.5,
+            Some(Convention::Act360),
+            Some(&cal),
+            Some(Modifier::ModF),
+            Some(0),
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_curve_lookup() {
+        let market = market();
+        assert_eq!(market.keys(), vec![key("usd", "sofr"), key("eur", "estr")]);
+        assert_eq!(market.curve(&key("usd", "sofr")).unwrap().id, "sofr");
+        assert!(market.curve(&key("usd", "estr")).is_err());
+        assert!(CurveKey::try_new(ccy("usd"), " ").is_err());
+    }
+
+    #[test]
+    fn test_validation() {
+        let duplicated = Market::try_new(
+            vec![
+                (key("usd", "sofr"), curve("a")),
+                (key("usd", "Sofr"), curve("b")),
+            ],
+            None,
+        );
+        assert!(duplicated.is_err());
+        let missing_ccy = Market::try_new(vec![(key("gbp", "sonia"), curve("a"))], Some(fx()));
+        assert!(missing_ccy.is_err());
+    }
+
+    #[test]
+    fn test_npv_in_base_currency() {
+        let (market, irs) = (market(), irs());
+        let local = market.npv(&irs, &key("eur", "estr"), None).unwrap();
+        let direct = CurveInstrument::npv(&irs, &curve("estr")).unwrap();
+        assert_eq!(local, direct);
+
+        let base = market
+            .npv(&irs, &key("eur", "estr"), Some(&ccy("usd")))
+            .unwrap();
+        assert!((f64::from(&base) - f64::from(&local) * 1.08).abs() < 1e-8);
+        assert!(
+            Market::try_new(vec![(key("eur", "estr"), curve("estr"))], None)
+                .unwrap()
+                .npv(&irs, &key("eur", "estr"), Some(&ccy("usd")))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_set_and_restore_ad_order() {
+        let mut market = market();
+        let snapshot = market.set_ad_order(ADOrder::Zero).unwrap();
+        assert_eq!(snapshot.curves, vec![ADOrder::One, ADOrder::One]);
+        assert_eq!(snapshot.fx, Some(ADOrder::One));
+        assert_eq!(
+            market.ad_snapshot(),
+            ADSnapshot {
+                curves: vec![ADOrder::Zero; 2],
+                fx: Some(ADOrder::Zero)
+            }
+        );
+        market.restore_ad_order(&snapshot).unwrap();
+        assert_eq!(market.ad_snapshot(), snapshot);
+
+        let mismatched = ADSnapshot {
+            curves: vec![ADOrder::Zero],
+            fx: None,
+        };
+        assert!(market.restore_ad_order(&mismatched).is_err());
+        assert_eq!(market.ad_snapshot(), snapshot);
+    }
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        let mut market = market();
+        let (eur, usd) = (ccy("eur"), ccy("usd"));
+        let outer = market.snapshot();
+        market
+            .update_fx(vec![
+                FXRate::try_new("eur", "usd", Number::F64(1.10), None).unwrap()
+            ])
+            .unwrap();
+        // a nested bump of one curve copies that curve only
+        let inner = market.snapshot();
+        let mut bumped = curve("sofr");
+        bumped.set_ad_order(ADOrder::Zero).unwrap();
+        market.set_curve(key("usd", "sofr"), bumped).unwrap();
+        assert!(Arc::ptr_eq(
+            &market.curves[&key("eur", "estr")],
+            &outer.curves[&key("eur", "estr")]
+        ));
+        assert_eq!(
+            market.curve(&key("usd", "sofr")).unwrap().ad(),
+            ADOrder::Zero
+        );
+        assert!(market
+            .set_curve(key("gbp", "sonia"), curve("sonia"))
+            .is_err());
+
+        market.restore(&inner);
+        assert_eq!(
+            market.curve(&key("usd", "sofr")).unwrap().ad(),
+            ADOrder::One
+        );
+        assert_eq!(f64::from(market.fx_rate(&eur, &usd).unwrap()), 1.10);
+        market.restore(&outer);
+        assert_eq!(market, self::market());
+        let mut without_fx =
+            Market::try_new(vec![(key("usd", "sofr"), curve("sofr"))], None).unwrap();
+        assert!(without_fx.update_fx(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let market = market();
+        let js = market.to_json().unwrap();
+        let market2 = Market::from_json(&js).unwrap();
+        assert_eq!(market, market2);
+        assert_eq!(
+            market2.fx_rate(&ccy("eur"), &ccy("usd")).unwrap(),
+            market.fx_rate(&ccy("eur"), &ccy("usd")).unwrap()
+        );
+    }
+}
@@ -0,0 +1,369 @@
+use crate::calendars::DateRoll;
+use crate::curves::CurveInterpolation;
+use crate::dual::{get_variable_tags, ADOrder, Gradient1, Gradient2, Number};
+use crate::fx::rates::Ccy;
+use crate::instruments::CurveInstrument;
+use crate::market::{CurveKey, Market};
+use indexmap::IndexMap;
+use ndarray::{Array1, Array2};
+use pyo3::{pyclass, PyErr};
+
+/// The first order sensitivities of the net present value of a portfolio to the variables of a
+/// [Market], each labelled by its bucket, being the curve key, e.g. *"usd_sofr"*, or *"fx"*.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeltaReport {
+    pub npv: f64,
+    pub vars: Vec<String>,
+    pub buckets: Vec<String>,
+    pub delta: Array1<f64>,
+}
+
+/// The second order sensitivities of the net present value of a portfolio to the variables of a
+/// [Market], labelled as a [DeltaReport].
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GammaReport {
+    pub npv: f64,
+    pub vars: Vec<String>,
+    pub buckets: Vec<String>,
+    pub gamma: Array2<f64>,
+}
+
+/// The net present value and mid-market rate of an instrument today and at a horizon, with the
+/// curve at the horizon either implied by today's forward rates or rolled down unchanged.
+///
+/// Values at the horizon are in money of the horizon date and exclude cashflows paid up to
+/// and including it.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CarryRolldown {
+    pub npv: f64,
+    pub forward_npv: f64,
+    pub rolldown_npv: f64,
+    pub rate: f64,
+    pub forward_rate: f64,
+    pub rolldown_rate: f64,
+}
+
+impl CarryRolldown {
+    /// The change in net present value from today to the horizon if forward rates are realised.
+    pub fn carry(&self) -> f64 {
+        self.forward_npv - self.npv
+    }
+
+    /// The change in net present value at the horizon from an unchanged, rather than a realised
+    /// forward, curve.
+    pub fn rolldown(&self) -> f64 {
+        self.rolldown_npv - self.forward_npv
+    }
+}
+
+impl<T, U> Market<T, U>
+where
+    T: CurveInterpolation + Clone,
+    U: DateRoll + Clone,
+{
+    /// The variables of the nodes of every curve, in key order, followed by those of the FX
+    /// rates, each with its bucket. A variable shared by curves is reported once.
+    fn risk_vars(&self) -> IndexMap<String, String> {
+        let mut vars = IndexMap::new();
+        for (key, curve) in self.curves.iter() {
+            let bucket = format!("{}_{}", key.currency.name, key.index);
+            for var in get_variable_tags(&curve.id, curve.nodes.len()) {
+                vars.entry(var).or_insert_with(|| bucket.clone());
+            }
+        }
+        if let Some(fx) = &self.fx {
+            for rate in fx.fx_rates.iter() {
+                vars.entry(format!("fx_{}", rate.pair))
+                    .or_insert_with(|| "fx".to_string());
+            }
+        }
+        vars
+    }
+
+    /// Price a portfolio with every curve and the FX rates set to an AD order, restoring their
+    /// previous orders afterwards, whether or not the pricing succeeds.
+    fn portfolio_npv_at<I: CurveInstrument>(
+        &mut self,
+        portfolio: &[(I, CurveKey)],
+        base: Option<&Ccy>,
+        ad: ADOrder,
+    ) -> Result<Number, PyErr> {
+        let snapshot = self.set_ad_order(ad)?;
+        let npv = portfolio
+            .iter()
+            .try_fold(Number::F64(0.0), |acc, (instrument, key)| {
+                Ok::<Number, PyErr>(acc + self.npv(instrument, key, base)?)
+            });
+        self.restore_ad_order(&snapshot)?;
+        npv
+    }
+
+    /// The delta of a portfolio, each instrument priced from the curve with its key and
+    /// converted to `base`, if given, against the node values of every curve and the FX rates.
+    ///
+    /// The market is priced with AD order one and its AD orders are restored afterwards.
+    pub fn delta<I: CurveInstrument>(
+        &mut self,
+        portfolio: &[(I, CurveKey)],
+        base: Option<&Ccy>,
+    ) -> Result<DeltaReport, PyErr> {
+        let npv = self.portfolio_npv_at(portfolio, base, ADOrder::One)?;
+        let (vars, buckets): (Vec<String>, Vec<String>) = self.risk_vars().into_iter().unzip();
+        let delta = match &npv {
+            Number::Dual(d) => d.gradient1(vars.clone()),
+            _ => Array1::zeros(vars.len()),
+        };
+        Ok(DeltaReport {
+            npv: f64::from(npv),
+            vars,
+            buckets,
+            delta,
+        })
+    }
+
+    /// The gamma of a portfolio, priced as [Market::delta], against the node values of every
+    /// curve and the FX rates.
+    ///
+    /// The market is priced with AD order two and its AD orders are restored afterwards.
+    pub fn gamma<I: CurveInstrument>(
+        &mut self,
+        portfolio: &[(I, CurveKey)],
+        base: Option<&Ccy>,
+    ) -> Result<GammaReport, PyErr> {
+        let npv = self.portfolio_npv_at(portfolio, base, ADOrder::Two)?;
+        let (vars, buckets): (Vec<String>, Vec<String>) = self.risk_vars().into_iter().unzip();
+        let gamma = match &npv {
+            Number::Dual2(d) => d.gradient2(vars.clone()),
+            _ => Array2::zeros((vars.len(), vars.len())),
+        };
+        Ok(GammaReport {
+            npv: f64::from(npv),
+            vars,
+            buckets,
+            gamma,
+        })
+    }
+
+    /// The carry and roll-down of an instrument priced from the curve with the given key over a
+    /// `horizon` tenor, e.g. *"3M"*, from
+    /// [CurveDF::forward_curve](crate::curves::CurveDF::forward_curve) and
+    /// [CurveDF::rolldown](crate::curves::CurveDF::rolldown).
+    ///
+    /// Periods that accrue over the horizon are forecast from an extrapolated curve, so the
+    /// horizon is expected to fall on a period date of the instrument.
+    pub fn carry_rolldown<I: CurveInstrument>(
+        &self,
+        instrument: &I,
+        key: &CurveKey,
+        horizon: &str,
+    ) -> Result<CarryRolldown, PyErr> {
+        let curve = self.curve(key)?;
+        let forward = curve.forward_curve(&curve.horizon(horizon)?)?;
+        let rolled = curve.rolldown(horizon)?;
+        Ok(CarryRolldown {
+            npv: f64::from(instrument.npv(curve)?),
+            forward_npv: f64::from(instrument.npv(&forward)?),
+            rolldown_npv: f64::from(instrument.npv(&rolled)?),
+            rate: f64::from(instrument.rate(curve)?),
+            forward_rate: f64::from(instrument.rate(&forward)?),
+            rolldown_rate: f64::from(instrument.rate(&rolled)?),
+        })
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, CalType, Convention, Modifier, NamedCal};
+    use crate::curves::{CurveDF, LogLinearInterpolator, Nodes};
+    use crate::fx::rates::{FXRate, FXRates};
+    use crate::instruments::IRS;
+    use crate::market::ADSnapshot;
+    use chrono::NaiveDateTime;
+
+    fn curve(id: &str, df: f64) -> CurveDF<LogLinearInterpolator, NamedCal> {
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2022, 1, 1), 1.0_f64),
+            (ndt(2027, 1, 1), df),
+        ]));
+        CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            id,
+            Convention::Act360,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn key(currency: &str, index: &str) -> CurveKey {
+        CurveKey::try_new(Ccy::try_new(currency).unwrap(), index).unwrap()
+    }
+
+    fn market(eurusd: f64, df: f64) -> Market<LogLinearInterpolator, NamedCal> {
+        let fx = FXRates::try_new(
+            vec![FXRate::try_new("eur", "usd", Number::F64(eurusd), None).unwrap()],
+            None,
+        )
+        .unwrap();
+        Market::try_new(
+            vec![
+                (key("usd", "sofr"), curve("sofr", 0.88)),
+                (key("eur", "estr"), curve("estr", df)),
+            ],
+            Some(fx),
+        )
+        .unwrap()
+    }
+
+    fn portfolio() -> Vec<(IRS, CurveKey)> {
+        let cal = CalType::NamedCal(NamedCal::try_new("all").unwrap());
+        let irs = IRS::try_new(
+            ndt(2022, 1, 1),
+            ndt(2027, 1, 1),
+            Some(12),
+            1e6,
+            2.0,
+            Some(Convention::Act360),
+            Some(&cal),
+            Some(Modifier::ModF),
+            Some(0),
+            None,
+        )
+        .unwrap();
+        vec![(irs.clone(), key("usd", "sofr")), (irs, key("eur", "estr"))]
+    }
+
+    fn npv(eurusd: f64, df: f64) -> f64 {
+        let mut m = market(eurusd, df);
+        f64::from(
+            m.portfolio_npv_at(
+                &portfolio(),
+                Some(&Ccy::try_new("usd").unwrap()),
+                ADOrder::Zero,
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_delta_report() {
+        let mut m = market(1.08, 0.9);
+        let before = m.ad_snapshot();
+        let usd = Ccy::try_new("usd").unwrap();
+        let report = m.delta(&portfolio(), Some(&usd)).unwrap();
+        assert_eq!(m.ad_snapshot(), before);
+        assert_eq!(
+            report.vars,
+            vec!["sofr0", "sofr1", "estr0", "estr1", "fx_eurusd"]
+        );
+        assert_eq!(
+            report.buckets,
+            vec!["usd_sofr", "usd_sofr", "eur_estr", "eur_estr", "fx"]
+        );
+        assert!((report.npv - npv(1.08, 0.9)).abs() < 1e-8);
+        let h = 1e-6;
+        let fd_df = (npv(1.08, 0.9 + h) - npv(1.08, 0.9 - h)) / (2.0 * h);
+        let fd_fx = (npv(1.08 + h, 0.9) - npv(1.08 - h, 0.9)) / (2.0 * h);
+        assert!((report.delta[3] - fd_df).abs() < 1e-3);
+        assert!((report.delta[4] - fd_fx).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_gamma_report() {
+        let mut m = market(1.08, 0.9);
+        m.set_ad_order(ADOrder::One).unwrap();
+        let report = m.gamma(&portfolio(), None).unwrap();
+        assert_eq!(
+            m.ad_snapshot(),
+            ADSnapshot {
+                curves: vec![ADOrder::One; 2],
+                fx: Some(ADOrder::One)
+            }
+        );
+        assert_eq!(report.gamma.dim(), (5, 5));
+        assert_eq!(report.gamma, report.gamma.t());
+        // without conversion to a base currency the FX rates carry no risk
+        assert!(report.gamma.row(4).iter().all(|g| *g == 0.0));
+        let h = 1e-4;
+        let delta = |df: f64| {
+            let mut m = market(1.08, df);
+            m.delta(&portfolio(), None).unwrap().delta[3]
+        };
+        let fd = (delta(0.9 + h) - delta(0.9 - h)) / (2.0 * h);
+        assert!((report.gamma[[3, 3]] - fd).abs() < 1e-2);
+    }
+
+    fn swap(termination: NaiveDateTime) -> IRS {
+        let cal = CalType::NamedCal(NamedCal::try_new("all").unwrap());
+        IRS::try_new(
+            ndt(2022, 1, 1),
+            termination,
+            Some(3),
+            1e6,
+            2.0,
+            Some(Convention::Act360),
+            Some(&cal),
+            Some(Modifier::ModF),
+            Some(0),
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_carry_rolldown() {
+        // a flat curve is unchanged by rolling down so the roll-down is zero
+        let m = market(1.08, 0.9);
+        let irs = swap(ndt(2025, 1, 1));
+        let flat = m.carry_rolldown(&irs, &key("eur", "estr"), "3M").unwrap();
+        assert!(
+            (flat.npv - f64::from(m.npv(&irs, &key("eur", "estr"), None).unwrap())).abs() < 1e-8
+        );
+        assert!((flat.rolldown_rate - flat.forward_rate).abs() < 1e-10);
+        assert!(flat.rolldown().abs() < 1e-6);
+        assert!((flat.carry() + flat.rolldown() - (flat.rolldown_npv - flat.npv)).abs() < 1e-8);
+
+        // an upward sloping curve rolls down to lower rates, to the loss of the fixed rate payer
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2022, 1, 1), 1.0_f64),
+            (ndt(2023, 1, 1), 0.99_f64),
+            (ndt(2027, 1, 1), 0.85_f64),
+        ]));
+        let sloped = CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            "sofr",
+            Convention::Act360,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap();
+        let m = Market::try_new(vec![(key("usd", "sofr"), sloped)], None).unwrap();
+        let report = m
+            .carry_rolldown(&swap(ndt(2024, 1, 1)), &key("usd", "sofr"), "3M")
+            .unwrap();
+        assert!(report.rolldown_rate < report.forward_rate);
+        assert!(report.rolldown() < 0.0);
+        assert!(m
+            .carry_rolldown(&swap(ndt(2024, 1, 1)), &key("usd", "sofr"), "10Y")
+            .is_err());
+    }
+
+    #[test]
+    fn test_delta_restores_ad_order_on_error() {
+        let mut m = market(1.08, 0.9);
+        let before = m.ad_snapshot();
+        let mut bad = portfolio();
+        bad[0].1 = key("usd", "missing");
+        assert!(m.delta(&bad, None).is_err());
+        assert_eq!(m.ad_snapshot(), before);
+    }
+}
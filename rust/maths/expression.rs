@@ -0,0 +1,500 @@
+//! Parse and evaluate arithmetic expressions over [Number], e.g. payoff or spread formulas
+//! supplied as strings in configuration.
+//!
+//! An expression combines numeric literals and named variables with `+`, `-`, `*`, `/` and `^`
+//! (or `**`), parentheses and the functions `exp`, `log`, `pow`, `min` and `max`. Variables are
+//! bound to [Number] values on evaluation, so the result carries the sensitivities of any dual
+//! numbers.
+//!
+//! ```rust
+//! # use rateslib::maths::expression::Expression;
+//! # use rateslib::dual::{Dual, Number};
+//! # use std::collections::HashMap;
+//! let expr = Expression::parse("max(fixing - strike, 0) * 100").unwrap();
+//! let x = Dual::new(2.5, vec!["fixing".to_string()]);
+//! let values = HashMap::from([
+//!     ("fixing".to_string(), Number::Dual(x)),
+//!     ("strike".to_string(), Number::F64(2.0)),
+//! ]);
+//! let result = expr.evaluate(&values).unwrap();
+//! assert_eq!(f64::from(result), 50.0);
+//! ```
+
+use crate::dual::{MathFuncs, Number};
+use indexmap::IndexSet;
+use num_traits::Pow;
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, PyErr};
+use std::collections::HashMap;
+
+/// A parsed arithmetic expression which may be evaluated for values of its variables.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expression {
+    pub(crate) source: String,
+    node: Node,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    Constant(f64),
+    Variable(String),
+    Neg(Box<Node>),
+    Binary(BinaryOp, Box<Node>, Box<Node>),
+    Call(Function, Vec<Node>),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Function {
+    Exp,
+    Log,
+    Pow,
+    Min,
+    Max,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "exp" => Some(Function::Exp),
+            "log" => Some(Function::Log),
+            "pow" => Some(Function::Pow),
+            "min" => Some(Function::Min),
+            "max" => Some(Function::Max),
+            _ => None,
+        }
+    }
+
+    /// Whether the function accepts `n` arguments; `min` and `max` accept two or more.
+    fn accepts(&self, n: usize) -> bool {
+        match self {
+            Function::Exp | Function::Log => n == 1,
+            Function::Pow => n == 2,
+            Function::Min | Function::Max => n >= 2,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Op(char),
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn parse_error(position: usize, message: &str) -> PyErr {
+    PyValueError::new_err(format!(
+        "Invalid expression at position {}: {}.",
+        position, message
+    ))
+}
+
+/// Split the source into tokens, each with its character position.
+fn tokenize(source: &str) -> Result<Vec<(usize, Token)>, PyErr> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let token = if c.is_ascii_digit() || c == '.' {
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            // an exponent, e.g. 1.5e-3
+            if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                let mut j = i + 1;
+                if j < chars.len() && (chars[j] == '+' || chars[j] == '-') {
+                    j += 1;
+                }
+                if j < chars.len() && chars[j].is_ascii_digit() {
+                    i = j;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+            }
+            let literal: String = chars[start..i].iter().collect();
+            Token::Number(
+                literal
+                    .parse::<f64>()
+                    .map_err(|_| parse_error(start, &format!("invalid number '{}'", literal)))?,
+            )
+        } else if c.is_alphabetic() || c == '_' {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            Token::Name(chars[start..i].iter().collect())
+        } else {
+            i += 1;
+            match c {
+                '*' if i < chars.len() && chars[i] == '*' => {
+                    i += 1;
+                    Token::Op('^')
+                }
+                '+' | '-' | '*' | '/' | '^' => Token::Op(c),
+                '(' => Token::LParen,
+                ')' => Token::RParen,
+                ',' => Token::Comma,
+                _ => return Err(parse_error(start, &format!("unexpected character '{}'", c))),
+            }
+        };
+        tokens.push((start, token));
+    }
+    Ok(tokens)
+}
+
+/// The maximum nesting of parentheses, function calls, signs and exponents in an expression.
+const MAX_DEPTH: usize = 256;
+
+/// A recursive descent parser, with `^` binding tighter than unary minus, e.g. `-x^2` is
+/// `-(x^2)`, and right associative, e.g. `2^3^2` is `2^(3^2)`.
+///
+/// Every recursion passes through [Parser::unary], which limits its `depth` to [MAX_DEPTH].
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    index: usize,
+    end: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|(_, t)| t)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens.get(self.index).map_or(self.end, |(p, _)| *p)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.index).map(|(_, t)| t.clone());
+        self.index += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token, description: &str) -> Result<(), PyErr> {
+        let position = self.position();
+        match self.next() {
+            Some(t) if t == expected => Ok(()),
+            _ => Err(parse_error(position, &format!("expected {}", description))),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Node, PyErr> {
+        let mut node = self.term()?;
+        while let Some(Token::Op(op @ ('+' | '-'))) = self.peek() {
+            let op = if *op == '+' {
+                BinaryOp::Add
+            } else {
+                BinaryOp::Sub
+            };
+            self.index += 1;
+            node = Node::Binary(op, Box::new(node), Box::new(self.term()?));
+        }
+        Ok(node)
+    }
+
+    fn term(&mut self) -> Result<Node, PyErr> {
+        let mut node = self.unary()?;
+        while let Some(Token::Op(op @ ('*' | '/'))) = self.peek() {
+            let op = if *op == '*' {
+                BinaryOp::Mul
+            } else {
+                BinaryOp::Div
+            };
+            self.index += 1;
+            node = Node::Binary(op, Box::new(node), Box::new(self.unary()?));
+        }
+        Ok(node)
+    }
+
+    fn unary(&mut self) -> Result<Node, PyErr> {
+        if self.depth == MAX_DEPTH {
+            return Err(parse_error(
+                self.position(),
+                &format!("nested deeper than {} levels", MAX_DEPTH),
+            ));
+        }
+        self.depth += 1;
+        let node = match self.peek() {
+            Some(Token::Op('-')) => {
+                self.index += 1;
+                self.unary().map(|n| Node::Neg(Box::new(n)))
+            }
+            Some(Token::Op('+')) => {
+                self.index += 1;
+                self.unary()
+            }
+            _ => self.power(),
+        };
+        self.depth -= 1;
+        node
+    }
+
+    fn power(&mut self) -> Result<Node, PyErr> {
+        let base = self.atom()?;
+        if let Some(Token::Op('^')) = self.peek() {
+            self.index += 1;
+            let exponent = self.unary()?;
+            return Ok(Node::Binary(
+                BinaryOp::Pow,
+                Box::new(base),
+                Box::new(exponent),
+            ));
+        }
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<Node, PyErr> {
+        let position = self.position();
+        match self.next() {
+            Some(Token::Number(f)) => Ok(Node::Constant(f)),
+            Some(Token::LParen) => {
+                let node = self.expression()?;
+                self.expect(Token::RParen, "')'")?;
+                Ok(node)
+            }
+            Some(Token::Name(name)) => {
+                if self.peek() != Some(&Token::LParen) {
+                    return Ok(Node::Variable(name));
+                }
+                let function = Function::from_name(&name).ok_or_else(|| {
+                    parse_error(position, &format!("unknown function '{}'", name))
+                })?;
+                self.index += 1;
+                let mut args = vec![self.expression()?];
+                while self.peek() == Some(&Token::Comma) {
+                    self.index += 1;
+                    args.push(self.expression()?);
+                }
+                self.expect(Token::RParen, "')'")?;
+                if !function.accepts(args.len()) {
+                    return Err(parse_error(
+                        position,
+                        &format!("wrong number of arguments to '{}'", name),
+                    ));
+                }
+                Ok(Node::Call(function, args))
+            }
+            _ => Err(parse_error(position, "expected a number, variable or '('")),
+        }
+    }
+}
+
+impl Expression {
+    /// Parse an expression.
+    pub fn parse(source: &str) -> Result<Self, PyErr> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            tokens,
+            index: 0,
+            end: source.chars().count(),
+            depth: 0,
+        };
+        let node = parser.expression()?;
+        if parser.index < parser.tokens.len() {
+            return Err(parse_error(parser.position(), "unexpected token"));
+        }
+        Ok(Expression {
+            source: source.to_string(),
+            node,
+        })
+    }
+
+    /// The names of the variables of the expression, in the order first used.
+    pub fn variables(&self) -> Vec<String> {
+        fn collect(node: &Node, names: &mut IndexSet<String>) {
+            match node {
+                Node::Constant(_) => {}
+                Node::Variable(name) => {
+                    names.insert(name.clone());
+                }
+                Node::Neg(a) => collect(a, names),
+                Node::Binary(_, a, b) => {
+                    collect(a, names);
+                    collect(b, names);
+                }
+                Node::Call(_, args) => args.iter().for_each(|a| collect(a, names)),
+            }
+        }
+        let mut names = IndexSet::new();
+        collect(&self.node, &mut names);
+        names.into_iter().collect()
+    }
+
+    /// Evaluate the expression with values of its variables.
+    ///
+    /// The values must not mix [Dual](crate::dual::Dual) and [Dual2](crate::dual::Dual2).
+    /// `min` and `max` return one of their arguments, so carry its sensitivities. A power with a
+    /// dual exponent is evaluated as `exp(b * log(a))`.
+    pub fn evaluate(&self, values: &HashMap<String, Number>) -> Result<Number, PyErr> {
+        let mut variables: Vec<&Number> = Vec::new();
+        for name in self.variables() {
+            variables.push(values.get(&name).ok_or_else(|| {
+                PyValueError::new_err(format!("No value given for variable '{}'.", name))
+            })?);
+        }
+        let has_dual = variables.iter().any(|v| matches!(v, Number::Dual(_)));
+        let has_dual2 = variables.iter().any(|v| matches!(v, Number::Dual2(_)));
+        if has_dual && has_dual2 {
+            return Err(PyValueError::new_err(
+                "Variables of an expression cannot mix `Dual` and `Dual2` values.",
+            ));
+        }
+        Ok(evaluate(&self.node, values))
+    }
+}
+
+fn evaluate(node: &Node, values: &HashMap<String, Number>) -> Number {
+    match node {
+        Node::Constant(f) => Number::F64(*f),
+        Node::Variable(name) => values[name].clone(),
+        Node::Neg(a) => -evaluate(a, values),
+        Node::Binary(op, a, b) => {
+            let (a, b) = (evaluate(a, values), evaluate(b, values));
+            match op {
+                BinaryOp::Add => a + b,
+                BinaryOp::Sub => a - b,
+                BinaryOp::Mul => a * b,
+                BinaryOp::Div => a / b,
+                BinaryOp::Pow => pow(a, b),
+            }
+        }
+        Node::Call(function, args) => {
+            let mut args = args.iter().map(|a| evaluate(a, values));
+            let first = args.next().unwrap();
+            match function {
+                Function::Exp => first.exp(),
+                Function::Log => first.log(),
+                Function::Pow => pow(first, args.next().unwrap()),
+                Function::Min => args.fold(first, |m, a| if a < m { a } else { m }),
+                Function::Max => args.fold(first, |m, a| if a > m { a } else { m }),
+            }
+        }
+    }
+}
+
+fn pow(base: Number, exponent: Number) -> Number {
+    match exponent {
+        Number::F64(p) if p.fract() == 0.0 && p.abs() <= i32::MAX as f64 => base.powi(p as i32),
+        Number::F64(p) => base.pow(p),
+        _ => (exponent * base.log()).exp(),
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dual::{Dual, Dual2, Gradient1};
+
+    fn values(pairs: Vec<(&str, Number)>) -> HashMap<String, Number> {
+        pairs.into_iter().map(|(k, v)| (k.to_string(), v)).collect()
+    }
+
+    #[test]
+    fn test_precedence_and_functions() {
+        let cases = [
+            ("1 + 2 * 3", 7.0),
+            ("(1 + 2) * 3", 9.0),
+            ("-2^2", -4.0),
+            ("2**3**2", 512.0),
+            ("10 / 4 - 1", 1.5),
+            ("pow(2, 0.5)^2", 2.0),
+            ("min(3, 1, 2) + max(3, 1e1, -2)", 11.0),
+            ("log(exp(1.5))", 1.5),
+        ];
+        for (source, expected) in cases {
+            let result = Expression::parse(source)
+                .unwrap()
+                .evaluate(&HashMap::new())
+                .unwrap();
+            assert!((f64::from(result) - expected).abs() < 1e-14, "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_sensitivities() {
+        let expr = Expression::parse("notional * max(rate - 0.02, 0) ^ 2").unwrap();
+        assert_eq!(expr.variables(), vec!["notional", "rate"]);
+        let vars = vec!["r".to_string()];
+        let rate = Dual::new(0.05, vars.clone());
+        let result = expr
+            .evaluate(&values(vec![
+                ("notional", Number::F64(100.0)),
+                ("rate", Number::Dual(rate)),
+            ]))
+            .unwrap();
+        let Number::Dual(d) = result else {
+            panic!("expected a Dual")
+        };
+        assert!((d.real() - 0.09).abs() < 1e-14);
+        // d/dr 100 (r - 0.02)^2 = 200 (r - 0.02)
+        assert!((d.gradient1(vars)[0] - 6.0).abs() < 1e-12);
+
+        // a dual exponent
+        let x = Dual::new(2.0, vec!["x".to_string()]);
+        let result = Expression::parse("3 ^ x")
+            .unwrap()
+            .evaluate(&values(vec![("x", Number::Dual(x))]))
+            .unwrap();
+        let Number::Dual(d) = result else {
+            panic!("expected a Dual")
+        };
+        assert!((d.real() - 9.0).abs() < 1e-12);
+        assert!((d.gradient1(vec!["x".to_string()])[0] - 9.0 * 3_f64.ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        for source in [
+            "1 +",
+            "(1 + 2",
+            "foo(1)",
+            "exp(1, 2)",
+            "max(1)",
+            "1 $ 2",
+            "2 3",
+            "",
+        ] {
+            assert!(Expression::parse(source).is_err(), "{}", source);
+        }
+        // nesting is limited rather than overflowing the stack
+        let nested = |open: &str, n: usize| format!("{}x{}", open.repeat(n), ")".repeat(n));
+        assert!(Expression::parse(&nested("(", 200)).is_ok());
+        for source in [
+            nested("(", 100_000),
+            nested("exp(", 100_000),
+            "-".repeat(100_000) + "x",
+            "x^".repeat(100_000) + "x",
+        ] {
+            assert!(Expression::parse(&source).is_err());
+        }
+        let expr = Expression::parse("x + y").unwrap();
+        assert!(expr
+            .evaluate(&values(vec![("x", Number::F64(1.0))]))
+            .is_err());
+        let mixed = values(vec![
+            ("x", Number::Dual(Dual::new(1.0, vec!["x".to_string()]))),
+            ("y", Number::Dual2(Dual2::new(1.0, vec!["y".to_string()]))),
+        ]);
+        assert!(expr.evaluate(&mixed).is_err());
+    }
+}
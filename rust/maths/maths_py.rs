@@ -0,0 +1,43 @@
+//! Wrapper module to export Rust numerical methods to Python using pyo3 bindings.
+
+use crate::dual::Number;
+use crate::maths::expression::Expression;
+use pyo3::prelude::*;
+use std::collections::HashMap;
+
+#[pymethods]
+impl Expression {
+    #[new]
+    fn new_py(source: &str) -> PyResult<Self> {
+        Expression::parse(source)
+    }
+
+    /// The names of the variables of the expression, in the order first used.
+    #[getter(variables)]
+    fn variables_py(&self) -> Vec<String> {
+        self.variables()
+    }
+
+    /// Evaluate the expression with values of its variables.
+    ///
+    /// Parameters
+    /// ----------
+    /// values: dict[str, float | Dual | Dual2]
+    ///     The value of each variable. Must not mix *Dual* and *Dual2*.
+    ///
+    /// Returns
+    /// -------
+    /// float, Dual or Dual2
+    #[pyo3(name = "evaluate")]
+    fn evaluate_py(&self, values: HashMap<String, Number>) -> PyResult<Number> {
+        self.evaluate(&values)
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Expression: '{}'>", self.source)
+    }
+}
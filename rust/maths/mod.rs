@@ -0,0 +1,8 @@
+//! Numerical methods operating on [f64], [Dual](crate::dual::Dual) and
+//! [Dual2](crate::dual::Dual2) values.
+
+pub mod expression;
+pub(crate) mod maths_py;
+pub mod quadrature;
+pub mod roots;
+pub mod sampling;
@@ -0,0 +1,194 @@
+//! Numerical integration of functions of a real variable returning [Number].
+//!
+//! The integrand may return dual numbers, in which case the integral carries the sensitivities
+//! of the integrand, since integration is linear in the values of the integrand.
+
+use crate::dual::Number;
+use pyo3::exceptions::PyValueError;
+use pyo3::PyErr;
+use std::f64::consts::PI;
+
+/// Return the nodes and weights of `n` point Gauss-Legendre quadrature on the interval [-1, 1].
+///
+/// The nodes are the roots of the Legendre polynomial of degree `n`, found by Newton iteration.
+pub fn gauss_legendre_nodes(n: usize) -> Result<(Vec<f64>, Vec<f64>), PyErr> {
+    if n == 0 {
+        return Err(PyValueError::new_err(
+            "Gauss-Legendre quadrature requires at least one node.",
+        ));
+    }
+    let mut nodes = vec![0.0; n];
+    let mut weights = vec![0.0; n];
+    for i in 0..n {
+        let mut x = (PI * (i as f64 + 0.75) / (n as f64 + 0.5)).cos();
+        let mut dp = 0.0;
+        for _ in 0..100 {
+            // evaluate P_n(x) and P_{n-1}(x) by the three term recurrence
+            let (mut p0, mut p1) = (1.0, x);
+            for j in 2..=n {
+                let p2 = ((2 * j - 1) as f64 * x * p1 - (j - 1) as f64 * p0) / j as f64;
+                (p0, p1) = (p1, p2);
+            }
+            let (p, p_prev) = if n == 1 { (x, 1.0) } else { (p1, p0) };
+            dp = n as f64 * (x * p - p_prev) / (x * x - 1.0);
+            let dx = p / dp;
+            x -= dx;
+            if dx.abs() < 1e-15 {
+                break;
+            }
+        }
+        nodes[i] = x;
+        weights[i] = 2.0 / ((1.0 - x * x) * dp * dp);
+    }
+    Ok((nodes, weights))
+}
+
+/// Integrate `f` over [a, b] with `n` point Gauss-Legendre quadrature.
+///
+/// The rule is exact for polynomials of degree up to `2n - 1`.
+pub fn gauss_legendre<F>(f: F, a: f64, b: f64, n: usize) -> Result<Number, PyErr>
+where
+    F: Fn(f64) -> Number,
+{
+    let (nodes, weights) = gauss_legendre_nodes(n)?;
+    let (mid, half) = ((a + b) / 2.0, (b - a) / 2.0);
+    let mut total = Number::F64(0.0);
+    for (x, w) in nodes.iter().zip(weights.iter()) {
+        total = total + f(mid + half * x) * (w * half);
+    }
+    Ok(total)
+}
+
+struct Simpson {
+    a: f64,
+    b: f64,
+    fa: Number,
+    fm: Number,
+    fb: Number,
+    value: Number,
+}
+
+impl Simpson {
+    fn new<F: Fn(f64) -> Number>(f: &F, a: f64, b: f64, fa: Number, fb: Number) -> Self {
+        let fm = f((a + b) / 2.0);
+        let value = (&fa + &fm * 4.0 + &fb) * ((b - a) / 6.0);
+        Simpson {
+            a,
+            b,
+            fa,
+            fm,
+            fb,
+            value,
+        }
+    }
+}
+
+fn adaptive_simpson_<F>(f: &F, whole: Simpson, tol: f64, depth: usize) -> Result<Number, PyErr>
+where
+    F: Fn(f64) -> Number,
+{
+    let m = (whole.a + whole.b) / 2.0;
+    let left = Simpson::new(f, whole.a, m, whole.fa.clone(), whole.fm.clone());
+    let right = Simpson::new(f, m, whole.b, whole.fm, whole.fb);
+    let delta = &left.value + &right.value - &whole.value;
+    if f64::from(&delta).abs() <= 15.0 * tol {
+        // Richardson extrapolation of the two estimates
+        return Ok(&left.value + &right.value + delta / 15.0);
+    }
+    if depth == 0 {
+        return Err(PyValueError::new_err(
+            "Adaptive Simpson integration did not converge within `max_depth`.",
+        ));
+    }
+    Ok(adaptive_simpson_(f, left, tol / 2.0, depth - 1)?
+        + adaptive_simpson_(f, right, tol / 2.0, depth - 1)?)
+}
+
+/// Integrate `f` over [a, b] with adaptive Simpson quadrature to an absolute tolerance.
+///
+/// Intervals are bisected until the real part of the integral over each meets its share of the
+/// tolerance, and an error is returned if this requires more than `max_depth` bisections.
+pub fn adaptive_simpson<F>(
+    f: F,
+    a: f64,
+    b: f64,
+    tol: f64,
+    max_depth: usize,
+) -> Result<Number, PyErr>
+where
+    F: Fn(f64) -> Number,
+{
+    if tol <= 0.0 {
+        return Err(PyValueError::new_err("`tol` must be positive."));
+    }
+    let whole = Simpson::new(&f, a, b, f(a), f(b));
+    adaptive_simpson_(&f, whole, tol, max_depth)
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dual::{Dual, Gradient1, MathFuncs};
+
+    fn integrand(x: &Dual) -> impl Fn(f64) -> Number + '_ {
+        move |t| Number::Dual((x * t).exp())
+    }
+
+    // int_0^1 exp(x t) dt = (exp(x) - 1) / x, with derivative (x exp(x) - exp(x) + 1) / x^2
+    fn expected(x: f64) -> (f64, f64) {
+        ((x.exp() - 1.0) / x, (x * x.exp() - x.exp() + 1.0) / (x * x))
+    }
+
+    fn assert_dual(result: Number, x: f64, tol: f64) {
+        let Number::Dual(d) = result else {
+            panic!("expected a Dual integral")
+        };
+        let (value, gradient) = expected(x);
+        assert!((d.real - value).abs() < tol);
+        assert!((d.gradient1(vec!["x".to_string()])[0] - gradient).abs() < tol);
+    }
+
+    #[test]
+    fn test_gauss_legendre_nodes() {
+        let (nodes, weights) = gauss_legendre_nodes(3).unwrap();
+        let r = (0.6_f64).sqrt();
+        for (n, e) in nodes.iter().zip([r, 0.0, -r]) {
+            assert!((n - e).abs() < 1e-14);
+        }
+        for (w, e) in weights.iter().zip([5.0 / 9.0, 8.0 / 9.0, 5.0 / 9.0]) {
+            assert!((w - e).abs() < 1e-14);
+        }
+        assert!(gauss_legendre_nodes(0).is_err());
+    }
+
+    #[test]
+    fn test_gauss_legendre_exact_for_polynomials() {
+        let f = |t: f64| Number::F64(t.powi(5) - 2.0 * t * t + 1.0);
+        let result = f64::from(gauss_legendre(f, 0.0, 2.0, 3).unwrap());
+        assert!((result - (64.0 / 6.0 - 16.0 / 3.0 + 2.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_gauss_legendre_dual() {
+        let x = Dual::new(1.5, vec!["x".to_string()]);
+        assert_dual(
+            gauss_legendre(integrand(&x), 0.0, 1.0, 12).unwrap(),
+            1.5,
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn test_adaptive_simpson_dual() {
+        let x = Dual::new(1.5, vec!["x".to_string()]);
+        let result = adaptive_simpson(integrand(&x), 0.0, 1.0, 1e-10, 20).unwrap();
+        assert_dual(result, 1.5, 1e-9);
+    }
+
+    #[test]
+    fn test_adaptive_simpson_depth_error() {
+        let f = |t: f64| Number::F64(t.sqrt());
+        assert!(adaptive_simpson(f, 0.0, 1.0, 1e-14, 2).is_err());
+    }
+}
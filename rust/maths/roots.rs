@@ -0,0 +1,140 @@
+//! Root finding of functions of a real variable.
+//!
+//! Roots are found in [f64]. Callers which need the sensitivities of a root to dual inputs refine
+//! it by a Newton step in the arithmetic of those inputs, which gives the derivatives of the
+//! implicit function.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::PyErr;
+
+/// The maximum number of iterations, enough to bisect any [f64] bracket to a single ulp.
+const MAX_ITER: usize = 2100;
+
+/// Return a root of `f` within a `bracket` at whose ends `f` has opposite signs, starting from
+/// `x0`.
+///
+/// `f` returns its value and, optionally, its derivative. Newton steps are taken with the
+/// derivative, or secant steps through the previous iterate without it, and each iterate narrows
+/// the bracket around the root. A step which would leave the bracket is replaced by a bisection,
+/// so the root is always found. Iteration stops when a step, or the bracket, is no larger than
+/// `tol`, or `f` is zero.
+///
+/// If `x0` is not inside the bracket the first iterate is its midpoint.
+///
+/// # Errors
+///
+/// If `f` errors, the ends of the bracket do not have opposite signs, or `f` is not finite
+/// within the bracket.
+pub fn bracketed_root<F>(mut f: F, x0: f64, bracket: (f64, f64), tol: f64) -> Result<f64, PyErr>
+where
+    F: FnMut(f64) -> Result<(f64, Option<f64>), PyErr>,
+{
+    let (a, b) = bracket;
+    let ((f_a, _), (f_b, _)) = (f(a)?, f(b)?);
+    if f_a == 0.0 {
+        return Ok(a);
+    }
+    if f_b == 0.0 {
+        return Ok(b);
+    }
+    if !(f_a.is_finite() && f_b.is_finite() && f_a.signum() != f_b.signum()) {
+        return Err(PyValueError::new_err(
+            "The function must have opposite signs at the ends of the bracket to find a root.",
+        ));
+    }
+    // orient the bracket so that the function is negative at `lo` and positive at `hi`
+    let (mut lo, mut hi) = if f_a < 0.0 { (a, b) } else { (b, a) };
+    let within = |x: f64, lo: f64, hi: f64| x > lo.min(hi) && x < lo.max(hi);
+    let mut x = if within(x0, lo, hi) {
+        x0
+    } else {
+        0.5 * (lo + hi)
+    };
+    let mut previous = (lo, if f_a < 0.0 { f_a } else { f_b });
+    for _ in 0..MAX_ITER {
+        let (fx, dfx) = f(x)?;
+        if !fx.is_finite() {
+            return Err(PyValueError::new_err(
+                "The function is not finite within the bracket of its root.",
+            ));
+        }
+        if fx == 0.0 {
+            return Ok(x);
+        }
+        if fx < 0.0 {
+            lo = x;
+        } else {
+            hi = x;
+        }
+        let slope = dfx.unwrap_or((fx - previous.1) / (x - previous.0));
+        previous = (x, fx);
+        let mut next = x - fx / slope;
+        if !within(next, lo, hi) {
+            next = 0.5 * (lo + hi);
+        }
+        if (next - x).abs() <= tol || (hi - lo).abs() <= tol {
+            return Ok(next);
+        }
+        x = next;
+    }
+    Err(PyValueError::new_err("The root did not converge."))
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_newton_and_secant() {
+        let newton = |x: f64| Ok((x * x - 2.0, Some(2.0 * x)));
+        let secant = |x: f64| Ok((x * x - 2.0, None));
+        for f in [
+            &newton as &dyn Fn(f64) -> Result<(f64, Option<f64>), PyErr>,
+            &secant,
+        ] {
+            let root = bracketed_root(f, 1.0, (0.0, 2.0), 1e-14).unwrap();
+            assert!((root - 2.0_f64.sqrt()).abs() < 1e-14);
+            // the bracket may be given in either order, and a guess outside it is ignored
+            let root = bracketed_root(f, 5.0, (2.0, 0.0), 1e-14).unwrap();
+            assert!((root - 2.0_f64.sqrt()).abs() < 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_counts_newton_iterations() {
+        let mut count = 0;
+        let f = |x: f64| {
+            count += 1;
+            Ok((x.exp() - 3.0, Some(x.exp())))
+        };
+        let root = bracketed_root(f, 1.0, (-10.0, 10.0), 1e-12).unwrap();
+        assert!((root - 3.0_f64.ln()).abs() < 1e-14);
+        assert!(count < 10);
+    }
+
+    #[test]
+    fn test_bisects_when_newton_leaves_bracket() {
+        // Newton's method on atan from 1.5 diverges
+        let f = |x: f64| Ok((x.atan(), Some(1.0 / (1.0 + x * x))));
+        let root = bracketed_root(f, 1.5, (-1.0, 2.0), 1e-14).unwrap();
+        assert!(root.abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_flat_derivative_bisects() {
+        let f = |x: f64| Ok(((x - 1.0).powi(3), Some(0.0)));
+        let root = bracketed_root(f, 1.0 + 1e-3, (0.0, 3.0), 1e-14).unwrap();
+        assert!((root - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_errors() {
+        let f = |x: f64| Ok((x * x + 1.0, None));
+        assert!(bracketed_root(f, 0.0, (-1.0, 1.0), 1e-12).is_err());
+        let f = |x: f64| Ok((1.0 / x - 1.0, None));
+        assert!(bracketed_root(f, 0.0, (0.0, 2.0), 1e-12).is_err());
+        let f = |_: f64| -> Result<(f64, Option<f64>), PyErr> { Err(PyValueError::new_err("f")) };
+        assert!(bracketed_root(f, 0.0, (-1.0, 1.0), 1e-12).is_err());
+    }
+}
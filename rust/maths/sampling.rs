@@ -0,0 +1,110 @@
+//! Adaptive sampling of functions of a real variable, e.g. for plotting.
+//!
+//! An interval is bisected while the function at its midpoint differs from the linear
+//! interpolation of its end points by more than a tolerance, so points are concentrated where
+//! the curvature of the function is high.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::PyErr;
+
+/// The number of equal intervals sampled before refinement, so that features narrower than the
+/// whole interval are not missed by the first bisection.
+const INITIAL_INTERVALS: usize = 8;
+
+/// The maximum number of bisections of an initial interval.
+const MAX_DEPTH: u32 = 16;
+
+/// Sample `f` from `start` to `end`, refining intervals until linear interpolation of the
+/// samples is within `tol` of `f` at each midpoint, or an interval is narrower than `min_step`.
+///
+/// Returns the increasing sample points, including `start` and `end`, and the value of `f` at
+/// each.
+pub fn adaptive_sample<F>(
+    f: F,
+    start: f64,
+    end: f64,
+    tol: f64,
+    min_step: f64,
+) -> Result<(Vec<f64>, Vec<f64>), PyErr>
+where
+    F: Fn(f64) -> Result<f64, PyErr>,
+{
+    if end <= start {
+        return Err(PyValueError::new_err("`end` must be after `start`."));
+    }
+    if tol <= 0.0 {
+        return Err(PyValueError::new_err("`tol` must be positive."));
+    }
+    let step = (end - start) / INITIAL_INTERVALS as f64;
+    let (mut x, mut y) = (vec![start], vec![f(start)?]);
+    for i in 1..=INITIAL_INTERVALS {
+        let b = if i == INITIAL_INTERVALS {
+            end
+        } else {
+            start + step * i as f64
+        };
+        let (a, fa) = (x[x.len() - 1], y[y.len() - 1]);
+        let fb = f(b)?;
+        refine(&f, (a, fa), (b, fb), tol, min_step, 0, &mut x, &mut y)?;
+    }
+    Ok((x, y))
+}
+
+/// Append the samples of `f` in the interval from `a`, exclusive, to `b`, inclusive.
+#[allow(clippy::too_many_arguments)]
+fn refine<F>(
+    f: &F,
+    a: (f64, f64),
+    b: (f64, f64),
+    tol: f64,
+    min_step: f64,
+    depth: u32,
+    x: &mut Vec<f64>,
+    y: &mut Vec<f64>,
+) -> Result<(), PyErr>
+where
+    F: Fn(f64) -> Result<f64, PyErr>,
+{
+    if depth < MAX_DEPTH && b.0 - a.0 > 2.0 * min_step {
+        let m = 0.5 * (a.0 + b.0);
+        let fm = f(m)?;
+        if (fm - 0.5 * (a.1 + b.1)).abs() > tol {
+            refine(f, a, (m, fm), tol, min_step, depth + 1, x, y)?;
+            return refine(f, (m, fm), b, tol, min_step, depth + 1, x, y);
+        }
+    }
+    x.push(b.0);
+    y.push(b.1);
+    Ok(())
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adaptive_sample() {
+        let (x, y) = adaptive_sample(|x| Ok(2.0 * x), 0.0, 1.0, 1e-6, 0.0).unwrap();
+        assert_eq!(x.len(), INITIAL_INTERVALS + 1);
+        assert_eq!((x[0], x[x.len() - 1], y[y.len() - 1]), (0.0, 1.0, 2.0));
+
+        // points concentrate where the curvature is high
+        let (x, y) = adaptive_sample(|x| Ok(x.powi(8)), 0.0, 1.0, 1e-4, 0.0).unwrap();
+        assert!(x.windows(2).all(|w| w[0] < w[1]));
+        assert!(
+            x.iter().filter(|x| **x > 0.5).count() > 2 * x.iter().filter(|x| **x < 0.5).count()
+        );
+        for (w, v) in x.windows(2).zip(y.windows(2)) {
+            let m = 0.5 * (w[0] + w[1]);
+            assert!((m.powi(8) - 0.5 * (v[0] + v[1])).abs() <= 1e-4);
+        }
+
+        // the minimum step limits refinement
+        let (x, _) = adaptive_sample(|x| Ok(x.powi(8)), 0.0, 1.0, 1e-12, 0.1).unwrap();
+        assert!(x.windows(2).all(|w| w[1] - w[0] > 0.05));
+
+        assert!(adaptive_sample(|x| Ok(x), 1.0, 1.0, 1e-4, 0.0).is_err());
+        assert!(adaptive_sample(|x| Ok(x), 0.0, 1.0, 0.0, 0.0).is_err());
+    }
+}
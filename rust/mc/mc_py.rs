@@ -0,0 +1,139 @@
+//! Wrapper module to export Rust Monte Carlo simulation to Python using pyo3 bindings.
+
+use crate::dual::{get_variable_tags, Dual, Gradient1, Number};
+use crate::mc::paths::{simulate_paths, McOptions, PathModel};
+use crate::mc::rng::{NormalGenerator, Rng, Sobol};
+use crate::progress::{CancellationToken, Progress};
+use ndarray::{Array2, Array3};
+use numpy::{PyArray2, PyArray3, ToPyArray};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// Simulated values with, optionally, their derivatives to the model parameters.
+type SimulatedPaths<'py> = (Bound<'py, PyArray2<f64>>, Option<Bound<'py, PyArray3<f64>>>);
+
+/// Simulate paths of a stochastic model.
+///
+/// Parameters
+/// ----------
+/// model: str
+///     The model, either *"gbm"* with ``params`` (spot, drift, vol) or *"hull_white"* with
+///     ``params`` (rate, mean_reversion, level, vol).
+/// params: list[float]
+///     The parameters of the model.
+/// times: list[float]
+///     The positive and strictly increasing times, in years, at which to record each path.
+/// n_paths: int
+///     The number of paths to simulate.
+/// seed: int, optional
+///     The seed of the pseudo-random number generator.
+/// sobol: bool, optional
+///     Whether to use a Sobol sequence rather than pseudo-random numbers. The number of
+///     ``times`` must then not exceed the dimensions available to the sequence.
+/// ad: bool, optional
+///     Whether to return the pathwise derivatives of each value to each parameter.
+/// antithetic: bool, optional
+///     Whether to simulate antithetic pairs of paths. ``n_paths`` must then be even.
+/// brownian_bridge: bool, optional
+///     Whether to construct each path by a Brownian bridge.
+/// callback: Callable[[int, float], Any], optional
+///     A function called with the number of draws, each being a path or an antithetic pair, and
+///     the fraction of paths simulated every ``every`` draws. An exception it raises stops the
+///     simulation.
+/// every: int, optional
+///     The number of draws between calls of ``callback`` and checks for cancellation.
+/// token: CancellationToken, optional
+///     A token which cancels the simulation, raising a *ValueError*, when cancelled from another
+///     thread. A *KeyboardInterrupt* also cancels the simulation.
+///
+/// Returns
+/// -------
+/// tuple of ndarray and ndarray or None
+///
+/// Notes
+/// -----
+/// Values are returned with shape (n_paths, n_times) and derivatives, if requested, with shape
+/// (n_paths, n_times, n_params).
+#[pyfunction]
+#[pyo3(name = "mc_simulate", signature = (model, params, times, n_paths, seed=0, sobol=false, ad=false, antithetic=false, brownian_bridge=false, callback=None, every=1000, token=None))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn mc_simulate_py<'py>(
+    py: Python<'py>,
+    model: &str,
+    params: Vec<f64>,
+    times: Vec<f64>,
+    n_paths: usize,
+    seed: u64,
+    sobol: bool,
+    ad: bool,
+    antithetic: bool,
+    brownian_bridge: bool,
+    callback: Option<PyObject>,
+    every: usize,
+    token: Option<CancellationToken>,
+) -> PyResult<SimulatedPaths<'py>> {
+    let progress = Progress::from_py(callback, every, token)?;
+    let vars = get_variable_tags("p", params.len());
+    let p: Vec<Number> = if ad {
+        let first = Dual::new(0.0, vars.clone());
+        params
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let mut grad = ndarray::Array1::zeros(params.len());
+                grad[i] = 1.0;
+                Number::Dual(Dual::clone_from(&first, *v, grad))
+            })
+            .collect()
+    } else {
+        params.iter().map(|v| Number::F64(*v)).collect()
+    };
+    let path_model = match (model.to_lowercase().as_str(), p.as_slice()) {
+        ("gbm", [spot, drift, vol]) => PathModel::GBM {
+            spot: spot.clone(),
+            drift: drift.clone(),
+            vol: vol.clone(),
+        },
+        ("hull_white", [rate, mean_reversion, level, vol]) => PathModel::HullWhite {
+            rate: rate.clone(),
+            mean_reversion: mean_reversion.clone(),
+            level: level.clone(),
+            vol: vol.clone(),
+        },
+        _ => {
+            return Err(PyValueError::new_err(
+                "`model` must be 'gbm' with 3 `params` or 'hull_white' with 4 `params`.",
+            ))
+        }
+    };
+    let mut generator = if sobol {
+        NormalGenerator::Sobol(Sobol::try_new(times.len())?)
+    } else {
+        NormalGenerator::Pseudo(Rng::new(seed))
+    };
+    let options = McOptions {
+        antithetic,
+        brownian_bridge,
+        control_variate: None,
+        progress: Some(&progress),
+    };
+    let paths = py
+        .allow_threads(|| simulate_paths(&path_model, &times, n_paths, &mut generator, &options))?;
+
+    let values = Array2::from_shape_fn((n_paths, times.len()), |(i, j)| f64::from(&paths[i][j]));
+    let gradients = if ad {
+        let mut g = Array3::zeros((n_paths, times.len(), params.len()));
+        for (i, path) in paths.iter().enumerate() {
+            for (j, x) in path.iter().enumerate() {
+                if let Number::Dual(d) = x {
+                    g.slice_mut(ndarray::s![i, j, ..])
+                        .assign(&d.gradient1(vars.clone()));
+                }
+            }
+        }
+        Some(g.to_pyarray_bound(py))
+    } else {
+        None
+    };
+    Ok((values.to_pyarray_bound(py), gradients))
+}
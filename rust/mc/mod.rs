@@ -0,0 +1,13 @@
+//! Monte Carlo simulation of stochastic models with pathwise sensitivities.
+//!
+//! Model parameters may be dual numbers, in which case each simulated value carries its
+//! derivatives to the parameters along its path, and so does any payoff computed from it.
+
+pub(crate) mod mc_py;
+mod paths;
+mod rng;
+mod variance;
+
+pub use crate::mc::paths::{mc_expectation, simulate_paths, McOptions, McResult, PathModel};
+pub use crate::mc::rng::{NormalGenerator, Rng, Sobol, SOBOL_MAX_DIM};
+pub use crate::mc::variance::{ControlVariate, ForwardControl};
@@ -0,0 +1,446 @@
+use crate::dual::{sum_pairwise, MathFuncs, Number};
+use crate::mc::rng::NormalGenerator;
+use crate::mc::variance::{brownian_bridge, ControlVariate};
+use crate::progress::Progress;
+use num_traits::Pow;
+use pyo3::exceptions::PyValueError;
+use pyo3::PyErr;
+
+/// A stochastic process simulated by its exact transition over each time step.
+///
+/// Parameters may be dual numbers, in which case every simulated value carries its pathwise
+/// sensitivities to them.
+#[derive(Clone, Debug)]
+pub enum PathModel {
+    /// Geometric Brownian motion, `dS = drift S dt + vol S dW`.
+    GBM {
+        spot: Number,
+        drift: Number,
+        vol: Number,
+    },
+    /// A Hull-White short rate with a constant reversion level,
+    /// `dr = mean_reversion (level - r) dt + vol dW`.
+    HullWhite {
+        rate: Number,
+        mean_reversion: Number,
+        level: Number,
+        vol: Number,
+    },
+}
+
+impl PathModel {
+    fn validate(&self) -> Result<(), PyErr> {
+        match self {
+            PathModel::GBM { .. } => Ok(()),
+            PathModel::HullWhite { mean_reversion, .. } => {
+                if f64::from(mean_reversion) <= 0.0 {
+                    Err(PyValueError::new_err(
+                        "Hull-White `mean_reversion` must be positive.",
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    fn initial(&self) -> Number {
+        match self {
+            PathModel::GBM { spot, .. } => spot.clone(),
+            PathModel::HullWhite { rate, .. } => rate.clone(),
+        }
+    }
+
+    /// Return the value after a time step `dt` from `x` with the standard normal increment `z`.
+    fn step(&self, x: &Number, dt: f64, z: f64) -> Number {
+        match self {
+            PathModel::GBM { drift, vol, .. } => {
+                let exponent = (drift - &vol.clone().pow(2.0) * 0.5) * dt + vol * (dt.sqrt() * z);
+                x * exponent.exp()
+            }
+            PathModel::HullWhite {
+                mean_reversion,
+                level,
+                vol,
+                ..
+            } => {
+                let decay = (mean_reversion * -dt).exp();
+                let variance =
+                    (1.0 - (mean_reversion * (-2.0 * dt)).exp()) / (mean_reversion * 2.0);
+                x * &decay + level * (1.0 - &decay) + vol * variance.pow(0.5) * z
+            }
+        }
+    }
+}
+
+/// Options for the simulation of paths and the reduction of the variance of estimates.
+#[derive(Clone, Copy, Default)]
+pub struct McOptions<'a> {
+    /// Simulate paths in antithetic pairs, the second with the negated normals of the first.
+    pub antithetic: bool,
+    /// Construct the Brownian motion of each path by a Brownian bridge.
+    pub brownian_bridge: bool,
+    /// A payoff with a known expectation whose estimation error is regressed out of estimates.
+    pub control_variate: Option<&'a (dyn ControlVariate + Sync)>,
+    /// An observer of the simulation, to which each draw, being a path or an antithetic pair,
+    /// is reported with the fraction of paths simulated.
+    pub progress: Option<&'a Progress<'a>>,
+}
+
+fn simulate_path(model: &PathModel, dts: &[f64], z: &[f64]) -> Vec<Number> {
+    let mut x = model.initial();
+    let mut path = Vec::with_capacity(dts.len());
+    for (dt, z) in dts.iter().zip(z) {
+        x = model.step(&x, *dt, *z);
+        path.push(x.clone());
+    }
+    path
+}
+
+/// Simulate paths of a model, returning the value of each path at each of `times`.
+///
+/// `times` are measured in years from the initial value and must be strictly increasing. With
+/// antithetic paths `n_paths` must be even and each consecutive pair of paths is antithetic.
+///
+/// # Errors
+///
+/// If the model or `times` are invalid, or the simulation is cancelled by its progress observer.
+pub fn simulate_paths(
+    model: &PathModel,
+    times: &[f64],
+    n_paths: usize,
+    generator: &mut NormalGenerator,
+    options: &McOptions,
+) -> Result<Vec<Vec<Number>>, PyErr> {
+    model.validate()?;
+    if options.antithetic && !n_paths.is_multiple_of(2) {
+        return Err(PyValueError::new_err(
+            "`n_paths` must be even for antithetic paths.",
+        ));
+    }
+    let mut prev = 0.0;
+    let mut dts = Vec::with_capacity(times.len());
+    for t in times {
+        if *t <= prev {
+            return Err(PyValueError::new_err(
+                "`times` must be positive and strictly increasing.",
+            ));
+        }
+        dts.push(t - prev);
+        prev = *t;
+    }
+    let n_draws = if options.antithetic {
+        n_paths / 2
+    } else {
+        n_paths
+    };
+    let mut paths = Vec::with_capacity(n_paths);
+    for draw in 1..=n_draws {
+        let mut z = generator.normals(dts.len())?;
+        if options.brownian_bridge {
+            z = brownian_bridge(times, &z);
+        }
+        paths.push(simulate_path(model, &dts, &z));
+        if options.antithetic {
+            let z: Vec<f64> = z.iter().map(|x| -x).collect();
+            paths.push(simulate_path(model, &dts, &z));
+        }
+        if let Some(progress) = options.progress {
+            progress.report(draw, draw as f64 / n_draws as f64)?;
+        }
+    }
+    Ok(paths)
+}
+
+/// The Monte Carlo estimate of the expected payoff of a path.
+#[derive(Clone, Debug)]
+pub struct McResult {
+    /// The average payoff, with its pathwise sensitivities.
+    pub estimate: Number,
+    /// The real part of each independent sample of the payoff. An antithetic pair of paths is
+    /// one sample and a control variate adjustment is included.
+    pub samples: Vec<f64>,
+    /// The standard error of the real part of the estimate.
+    pub stderr: f64,
+}
+
+impl McResult {
+    /// Return the confidence interval of the real part of the estimate at a confidence level,
+    /// from the normal approximation of its error.
+    pub fn confidence_interval(&self, confidence: f64) -> (f64, f64) {
+        let z = (0.5 + confidence / 2.0).inv_norm_cdf();
+        let mid = f64::from(&self.estimate);
+        (mid - z * self.stderr, mid + z * self.stderr)
+    }
+}
+
+fn mean_and_stderr(samples: &[f64]) -> (f64, f64) {
+    let n = samples.len() as f64;
+    let mean = sum_pairwise(samples) / n;
+    if samples.len() < 2 {
+        return (mean, f64::NAN);
+    }
+    let var = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, (var / n).sqrt())
+}
+
+/// Estimate the expected payoff of a function of the values of a path at each of `times`.
+pub fn mc_expectation<F>(
+    model: &PathModel,
+    times: &[f64],
+    n_paths: usize,
+    generator: &mut NormalGenerator,
+    payoff: F,
+    options: &McOptions,
+) -> Result<McResult, PyErr>
+where
+    F: Fn(&[Number]) -> Number,
+{
+    if n_paths == 0 {
+        return Err(PyValueError::new_err("`n_paths` must be positive."));
+    }
+    let paths = simulate_paths(model, times, n_paths, generator, options)?;
+    let group = if options.antithetic { 2 } else { 1 };
+    let average = |f: &dyn Fn(&[Number]) -> Number| -> Vec<Number> {
+        paths
+            .chunks(group)
+            .map(|c| sum_pairwise(&c.iter().map(|p| f(p)).collect::<Vec<Number>>()) / group as f64)
+            .collect()
+    };
+    let mut payoffs = average(&payoff);
+    let n = payoffs.len() as f64;
+
+    if let Some(cv) = options.control_variate {
+        let controls = average(&|p| cv.payoff(p));
+        let y: Vec<f64> = payoffs.iter().map(f64::from).collect();
+        let c: Vec<f64> = controls.iter().map(f64::from).collect();
+        let (y_mean, c_mean) = (sum_pairwise(&y) / n, sum_pairwise(&c) / n);
+        let cov: f64 = y
+            .iter()
+            .zip(c.iter())
+            .map(|(y, c)| (y - y_mean) * (c - c_mean))
+            .sum();
+        let var: f64 = c.iter().map(|c| (c - c_mean).powi(2)).sum();
+        let beta = if var > 0.0 { cov / var } else { 0.0 };
+        let expectation = cv.expectation();
+        payoffs = payoffs
+            .iter()
+            .zip(controls.iter())
+            .map(|(y, c)| y - (c - &expectation) * beta)
+            .collect();
+    }
+
+    let samples: Vec<f64> = payoffs.iter().map(f64::from).collect();
+    let (_, stderr) = mean_and_stderr(&samples);
+    Ok(McResult {
+        estimate: sum_pairwise(&payoffs) / n,
+        samples,
+        stderr,
+    })
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dual::{Dual, Gradient1};
+    use crate::mc::rng::{Rng, Sobol};
+    use crate::mc::variance::ForwardControl;
+
+    fn gbm() -> PathModel {
+        let vars = vec!["s".to_string(), "v".to_string()];
+        let spot = Dual::try_new(100.0, vars.clone(), vec![1.0, 0.0]).unwrap();
+        let vol = Dual::clone_from(&spot, 0.2, ndarray::arr1(&[0.0, 1.0]));
+        PathModel::GBM {
+            spot: Number::Dual(spot),
+            drift: Number::F64(0.03),
+            vol: Number::Dual(vol),
+        }
+    }
+
+    #[test]
+    fn test_gbm_forward_and_pathwise_delta() {
+        let mut generator = NormalGenerator::Pseudo(Rng::new(1));
+        let result = mc_expectation(
+            &gbm(),
+            &[0.5, 1.0],
+            20000,
+            &mut generator,
+            |p| p[1].clone(),
+            &McOptions::default(),
+        )
+        .unwrap();
+        let Number::Dual(d) = result.estimate else {
+            panic!("expected a Dual estimate")
+        };
+        let forward = 100.0 * 0.03_f64.exp();
+        assert!((d.real - forward).abs() < 0.5);
+        // the pathwise delta of the terminal value is S_T / S_0
+        let grad = d.gradient1(vec!["s".to_string(), "v".to_string()]);
+        assert!((grad[0] - d.real / 100.0).abs() < 1e-12);
+        assert_eq!(result.samples.len(), 20000);
+    }
+
+    #[test]
+    fn test_sobol_call_price() {
+        let mut generator = NormalGenerator::Sobol(Sobol::try_new(1).unwrap());
+        let result = mc_expectation(
+            &gbm(),
+            &[1.0],
+            1 << 14,
+            &mut generator,
+            |p| {
+                let x = &p[0] - 100.0;
+                if f64::from(&x) > 0.0 {
+                    x
+                } else {
+                    Number::F64(0.0)
+                }
+            },
+            &McOptions::default(),
+        )
+        .unwrap();
+        let Number::Dual(d) = result.estimate else {
+            panic!("expected a Dual estimate")
+        };
+        // Black-Scholes: price 9.4134, delta 0.5987, vega 38.67
+        let disc = 0.03_f64.exp();
+        assert!((d.real / disc - 9.4134).abs() < 0.01);
+        let grad = d.gradient1(vec!["s".to_string(), "v".to_string()]);
+        assert!((grad[0] / disc - 0.5987).abs() < 0.01);
+        assert!((grad[1] / disc - 38.67).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_hull_white_moments() {
+        let model = PathModel::HullWhite {
+            rate: Number::F64(0.01),
+            mean_reversion: Number::F64(0.5),
+            level: Number::F64(0.04),
+            vol: Number::F64(0.01),
+        };
+        let mut generator = NormalGenerator::Pseudo(Rng::new(7));
+        let paths = simulate_paths(
+            &model,
+            &[1.0, 2.0],
+            20000,
+            &mut generator,
+            &McOptions::default(),
+        )
+        .unwrap();
+        let terminal: Vec<f64> = paths.iter().map(|p| f64::from(&p[1])).collect();
+        let mean = terminal.iter().sum::<f64>() / 20000.0;
+        let var = terminal.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / 20000.0;
+        let e = (-1.0_f64).exp();
+        assert!((mean - (0.01 * e + 0.04 * (1.0 - e))).abs() < 2e-4);
+        assert!((var - 0.0001 * (1.0 - e * e) / 1.0).abs() < 5e-6);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let mut generator = NormalGenerator::Pseudo(Rng::new(1));
+        assert!(simulate_paths(
+            &gbm(),
+            &[1.0, 0.5],
+            1,
+            &mut generator,
+            &McOptions::default()
+        )
+        .is_err());
+        let mut sobol = NormalGenerator::Sobol(Sobol::try_new(1).unwrap());
+        assert!(simulate_paths(&gbm(), &[0.5, 1.0], 1, &mut sobol, &McOptions::default()).is_err());
+    }
+
+    fn call(p: &[Number]) -> Number {
+        let x = &p[p.len() - 1] - 100.0;
+        if f64::from(&x) > 0.0 {
+            x
+        } else {
+            Number::F64(0.0)
+        }
+    }
+
+    #[test]
+    fn test_variance_reduction_reduces_stderr() {
+        let model = gbm();
+        let control = ForwardControl {
+            model: model.clone(),
+            index: 0,
+            time: 1.0,
+        };
+        let plain = mc_expectation(
+            &model,
+            &[1.0],
+            20000,
+            &mut NormalGenerator::Pseudo(Rng::new(3)),
+            call,
+            &McOptions::default(),
+        )
+        .unwrap();
+        let reduced = mc_expectation(
+            &model,
+            &[1.0],
+            20000,
+            &mut NormalGenerator::Pseudo(Rng::new(3)),
+            call,
+            &McOptions {
+                antithetic: true,
+                control_variate: Some(&control),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(reduced.samples.len(), 10000);
+        assert!(reduced.stderr < 0.5 * plain.stderr);
+        let expected = 9.4134 * 0.03_f64.exp();
+        let (lo, hi) = reduced.confidence_interval(0.99);
+        assert!(lo < expected && expected < hi);
+        assert!(hi - lo < 0.2);
+    }
+
+    #[test]
+    fn test_sobol_brownian_bridge() {
+        let times = [0.25, 0.5, 0.75, 1.0];
+        let result = mc_expectation(
+            &gbm(),
+            &times,
+            1 << 14,
+            &mut NormalGenerator::Sobol(Sobol::try_new(4).unwrap()),
+            call,
+            &McOptions {
+                brownian_bridge: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let expected = 9.4134 * 0.03_f64.exp();
+        assert!((f64::from(&result.estimate) - expected).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_antithetic_requires_even_paths() {
+        let options = McOptions {
+            antithetic: true,
+            ..Default::default()
+        };
+        let mut generator = NormalGenerator::Pseudo(Rng::new(1));
+        assert!(simulate_paths(&gbm(), &[1.0], 3, &mut generator, &options).is_err());
+    }
+
+    #[test]
+    fn test_cancelled_simulation() {
+        let token = crate::progress::CancellationToken::new();
+        let progress = Progress::new(100).with_token(token.clone());
+        let options = McOptions {
+            antithetic: true,
+            progress: Some(&progress),
+            ..Default::default()
+        };
+        let mut generator = NormalGenerator::Pseudo(Rng::new(1));
+        assert!(simulate_paths(&gbm(), &[1.0], 1000, &mut generator, &options).is_ok());
+        token.cancel();
+        assert!(simulate_paths(&gbm(), &[1.0], 1000, &mut generator, &options).is_err());
+        // fewer draws than `every` are never checked
+        assert!(simulate_paths(&gbm(), &[1.0], 100, &mut generator, &options).is_ok());
+    }
+}
@@ -0,0 +1,206 @@
+use crate::dual::MathFuncs;
+use pyo3::exceptions::PyValueError;
+use pyo3::PyErr;
+
+/// A seedable pseudo-random number generator, being xoshiro256** seeded by splitmix64.
+///
+/// The same seed always produces the same sequence, on every platform.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: [u64; 4],
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        let mut x = seed;
+        let mut splitmix = || {
+            x = x.wrapping_add(0x9E3779B97F4A7C15);
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+            z ^ (z >> 31)
+        };
+        Rng {
+            state: [splitmix(), splitmix(), splitmix(), splitmix()],
+        }
+    }
+
+    /// Return the next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// Return a uniform random number in the open interval (0, 1).
+    pub fn uniform(&mut self) -> f64 {
+        ((self.next_u64() >> 11) as f64 + 0.5) / (1_u64 << 53) as f64
+    }
+
+    /// Return a standard normal random number.
+    pub fn normal(&mut self) -> f64 {
+        self.uniform().inv_norm_cdf()
+    }
+}
+
+/// Direction number parameters `(s, a, m)` of Joe and Kuo for dimensions after the first.
+const SOBOL_PARAMS: [(u32, u32, &[u32]); 9] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+];
+
+/// The maximum dimension of a [Sobol] sequence.
+pub const SOBOL_MAX_DIM: usize = SOBOL_PARAMS.len() + 1;
+
+/// A Sobol low discrepancy sequence of points in the unit hypercube, generated in Gray code order.
+///
+/// The initial point at the origin is skipped.
+#[derive(Clone, Debug)]
+pub struct Sobol {
+    directions: Vec<[u32; 32]>,
+    x: Vec<u32>,
+    index: u32,
+}
+
+impl Sobol {
+    pub fn try_new(dim: usize) -> Result<Self, PyErr> {
+        if dim == 0 || dim > SOBOL_MAX_DIM {
+            return Err(PyValueError::new_err(format!(
+                "Sobol sequences are available in dimensions 1 to {}.",
+                SOBOL_MAX_DIM
+            )));
+        }
+        let mut directions = vec![[0_u32; 32]; dim];
+        for (k, v) in directions[0].iter_mut().enumerate() {
+            *v = 1 << (31 - k);
+        }
+        for (d, (s, a, m)) in SOBOL_PARAMS.iter().take(dim - 1).enumerate() {
+            let v = &mut directions[d + 1];
+            let s = *s as usize;
+            for k in 0..32 {
+                if k < s {
+                    v[k] = m[k] << (31 - k);
+                } else {
+                    v[k] = v[k - s] ^ (v[k - s] >> s);
+                    for l in 1..s {
+                        v[k] ^= ((a >> (s - 1 - l)) & 1) * v[k - l];
+                    }
+                }
+            }
+        }
+        Ok(Sobol {
+            directions,
+            x: vec![0; dim],
+            index: 0,
+        })
+    }
+
+    /// The dimension of the points of the sequence.
+    pub fn dim(&self) -> usize {
+        self.x.len()
+    }
+
+    /// Return the next point of the sequence.
+    pub fn next_point(&mut self) -> Vec<f64> {
+        let c = self.index.trailing_ones() as usize;
+        self.index = self.index.wrapping_add(1);
+        self.x
+            .iter_mut()
+            .zip(self.directions.iter())
+            .map(|(x, v)| {
+                *x ^= v[c];
+                *x as f64 / (1_u64 << 32) as f64
+            })
+            .collect()
+    }
+}
+
+/// A source of standard normal random numbers.
+#[derive(Clone, Debug)]
+pub enum NormalGenerator {
+    Pseudo(Rng),
+    Sobol(Sobol),
+}
+
+impl NormalGenerator {
+    /// Return `n` standard normal numbers, being the increments of a single path.
+    ///
+    /// A Sobol generator returns each point as the increments of a path, so `n` must equal its
+    /// dimension.
+    pub fn normals(&mut self, n: usize) -> Result<Vec<f64>, PyErr> {
+        match self {
+            NormalGenerator::Pseudo(rng) => Ok((0..n).map(|_| rng.normal()).collect()),
+            NormalGenerator::Sobol(sobol) => {
+                if sobol.dim() != n {
+                    return Err(PyValueError::new_err(
+                        "A Sobol generator must have the dimension of the number of time steps.",
+                    ));
+                }
+                Ok(sobol
+                    .next_point()
+                    .iter()
+                    .map(|u| u.inv_norm_cdf())
+                    .collect())
+            }
+        }
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng_reproducible_and_uniform() {
+        let (mut a, mut b) = (Rng::new(42), Rng::new(42));
+        let x: Vec<f64> = (0..10000).map(|_| a.uniform()).collect();
+        let y: Vec<f64> = (0..10000).map(|_| b.uniform()).collect();
+        assert_eq!(x, y);
+        assert!(x.iter().all(|u| *u > 0.0 && *u < 1.0));
+        let mean = x.iter().sum::<f64>() / 10000.0;
+        assert!((mean - 0.5).abs() < 0.01);
+        assert_ne!(Rng::new(43).uniform(), x[0]);
+    }
+
+    #[test]
+    fn test_sobol_points() {
+        let mut sobol = Sobol::try_new(3).unwrap();
+        let points: Vec<Vec<f64>> = (0..4).map(|_| sobol.next_point()).collect();
+        assert_eq!(points[0], vec![0.5, 0.5, 0.5]);
+        assert_eq!(points[1], vec![0.75, 0.25, 0.25]);
+        assert_eq!(points[2], vec![0.25, 0.75, 0.75]);
+        assert_eq!(points[3], vec![0.375, 0.375, 0.625]);
+        assert!(Sobol::try_new(0).is_err());
+        assert!(Sobol::try_new(SOBOL_MAX_DIM + 1).is_err());
+    }
+
+    #[test]
+    fn test_sobol_uniformity() {
+        let mut sobol = Sobol::try_new(SOBOL_MAX_DIM).unwrap();
+        let n = 1 << 12;
+        let mut sums = vec![0.0; SOBOL_MAX_DIM];
+        for _ in 0..n {
+            for (s, x) in sums.iter_mut().zip(sobol.next_point()) {
+                *s += x;
+            }
+        }
+        for s in sums {
+            assert!((s / n as f64 - 0.5).abs() < 1e-3);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::dual::{MathFuncs, Number};
+use crate::mc::paths::PathModel;
+
+/// A payoff of a path with an analytically known expectation, used as a control variate.
+///
+/// Both the payoff and its expectation may carry sensitivities, so that the adjustment to an
+/// estimate preserves its pathwise derivatives.
+pub trait ControlVariate {
+    /// The payoff of a path, given its values at each simulated time.
+    fn payoff(&self, path: &[Number]) -> Number;
+    /// The exact expectation of the payoff.
+    fn expectation(&self) -> Number;
+}
+
+/// A forward on the simulated quantity of a model, paying its value at one of the path times.
+#[derive(Clone, Debug)]
+pub struct ForwardControl {
+    pub model: PathModel,
+    /// The index of the paying time within the path times.
+    pub index: usize,
+    /// The paying time, in years.
+    pub time: f64,
+}
+
+impl ControlVariate for ForwardControl {
+    fn payoff(&self, path: &[Number]) -> Number {
+        path[self.index].clone()
+    }
+
+    fn expectation(&self) -> Number {
+        match &self.model {
+            PathModel::GBM { spot, drift, .. } => spot * (drift * self.time).exp(),
+            PathModel::HullWhite {
+                rate,
+                mean_reversion,
+                level,
+                ..
+            } => {
+                let decay = (mean_reversion * -self.time).exp();
+                rate * &decay + level * (1.0 - &decay)
+            }
+        }
+    }
+}
+
+/// The order in which a Brownian bridge fills the points of a path, with the known neighbours
+/// of each, as `(point, left, right)` where `None` on the left is the origin.
+pub(crate) fn bridge_order(n: usize) -> Vec<(usize, Option<usize>, Option<usize>)> {
+    let mut order = Vec::with_capacity(n);
+    if n == 0 {
+        return order;
+    }
+    order.push((n - 1, None, None));
+    // bisect each interval of unfilled points, coarsest first
+    let mut intervals = std::collections::VecDeque::from([(None, n - 1)]);
+    while let Some((left, right)) = intervals.pop_front() {
+        let start = left.map_or(0, |l: usize| l + 1);
+        if start >= right {
+            continue;
+        }
+        let mid = (start + right - 1) / 2;
+        order.push((mid, left, Some(right)));
+        intervals.push_back((left, mid));
+        intervals.push_back((Some(mid), right));
+    }
+    order
+}
+
+/// Map standard normals to the normalised increments of a Brownian path at `times`, constructed
+/// by a Brownian bridge.
+///
+/// The first normal determines the terminal value and later normals fill successively finer
+/// intervals, concentrating the variance of the path in the first dimensions, which suits low
+/// discrepancy sequences.
+pub(crate) fn brownian_bridge(times: &[f64], z: &[f64]) -> Vec<f64> {
+    let n = times.len();
+    let t = |i: Option<usize>| i.map_or(0.0, |i| times[i]);
+    let mut w = vec![0.0; n];
+    for ((point, left, right), z) in bridge_order(n).into_iter().zip(z) {
+        let (tl, tm) = (t(left), times[point]);
+        let wl = left.map_or(0.0, |l| w[l]);
+        w[point] = match right {
+            None => wl + (tm - tl).sqrt() * z,
+            Some(r) => {
+                let tr = times[r];
+                ((tr - tm) * wl + (tm - tl) * w[r]) / (tr - tl)
+                    + ((tm - tl) * (tr - tm) / (tr - tl)).sqrt() * z
+            }
+        };
+    }
+    (0..n)
+        .map(|i| {
+            let (w0, t0) = if i == 0 {
+                (0.0, 0.0)
+            } else {
+                (w[i - 1], times[i - 1])
+            };
+            (w[i] - w0) / (times[i] - t0).sqrt()
+        })
+        .collect()
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bridge_order_fills_every_point_once() {
+        for n in 1..20 {
+            let mut points: Vec<usize> = bridge_order(n).iter().map(|p| p.0).collect();
+            assert_eq!(points[0], n - 1);
+            points.sort();
+            assert_eq!(points, (0..n).collect::<Vec<usize>>());
+        }
+    }
+
+    #[test]
+    fn test_brownian_bridge_terminal_value() {
+        let times = [0.25, 0.5, 1.0, 2.0];
+        let increments = brownian_bridge(&times, &[1.0, 0.0, 0.0, 0.0]);
+        // the terminal value is sqrt(T) z, shared linearly over the path
+        let dts = [0.25, 0.25, 0.5, 1.0];
+        let w: f64 = increments
+            .iter()
+            .zip(dts)
+            .map(|(z, dt)| z * f64::sqrt(dt))
+            .sum();
+        assert!((w - 2.0_f64.sqrt()).abs() < 1e-14);
+        for (z, dt) in increments.iter().zip(dts) {
+            assert!((z * dt.sqrt() - dt / 2.0_f64.sqrt()).abs() < 1e-14);
+        }
+    }
+}
@@ -0,0 +1,596 @@
+//! The Hull-White one factor short rate model fitted to a discount factor curve.
+//!
+//! The model is expressed in the state `x(t) = r(t) - f(0, t)`, with `x(0) = 0` and
+//! `dx = (y(t) - a x) dt + vol(t) dW`, where `a` is the mean reversion and
+//! `y(t) = int_0^t vol(s)^2 exp(-2a (t - s)) ds`. Time is measured in years on Act365F from the
+//! initial node of the curve. The curve is reproduced exactly by construction and every price
+//! carries the sensitivities of the curve and of the model parameters.
+
+use crate::calendars::DateRoll;
+use crate::curves::{CurveDF, CurveInterpolation};
+use crate::dual::linalg::fdsolve;
+use crate::dual::{get_variable_tags, ADOrder, Dual, Dual2, Gradient1, MathFuncs, Number};
+use crate::instruments::IRS;
+use crate::progress::Progress;
+use crate::solver::{levenberg_marquardt, SolverResult};
+use chrono::prelude::*;
+use ndarray::{Array1, Array2};
+use num_traits::Pow;
+use pyo3::exceptions::PyValueError;
+use pyo3::PyErr;
+
+/// Hull-White model parameters, with a constant mean reversion and a piecewise constant
+/// volatility.
+///
+/// `vols[0]` applies until `vol_times[0]`, `vols[i]` from `vol_times[i - 1]` until
+/// `vol_times[i]`, and the last volatility thereafter.
+#[derive(Clone, Debug)]
+pub struct HullWhite {
+    pub mean_reversion: Number,
+    pub vol_times: Vec<f64>,
+    pub vols: Vec<Number>,
+}
+
+/// An instrument with an analytic price under [HullWhite], for calibration.
+#[derive(Clone)]
+pub enum CalibrationInstrument {
+    /// A caplet on the simple rate between two dates, with a strike in percent.
+    Caplet {
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+        dcf: f64,
+        strike: f64,
+        notional: f64,
+    },
+    /// An option, expiring at the effective date of the swap, to pay its fixed rate.
+    Swaption(IRS),
+}
+
+fn year_fraction<T: CurveInterpolation, U: DateRoll>(
+    curve: &CurveDF<T, U>,
+    date: &NaiveDateTime,
+) -> f64 {
+    (date.and_utc().timestamp() - curve.nodes.first_key()) as f64 / (365.0 * 86400.0)
+}
+
+impl HullWhite {
+    pub fn try_new(
+        mean_reversion: Number,
+        vol_times: Vec<f64>,
+        vols: Vec<Number>,
+    ) -> Result<Self, PyErr> {
+        if f64::from(&mean_reversion) <= 0.0 {
+            return Err(PyValueError::new_err("`mean_reversion` must be positive."));
+        }
+        if vols.len() != vol_times.len() + 1 {
+            return Err(PyValueError::new_err(
+                "`vols` must have one more element than `vol_times`.",
+            ));
+        }
+        let positive = vol_times.first().is_none_or(|t| *t > 0.0);
+        if !positive || vol_times.windows(2).any(|w| w[1] <= w[0]) {
+            return Err(PyValueError::new_err(
+                "`vol_times` must be positive and strictly increasing.",
+            ));
+        }
+        Ok(HullWhite {
+            mean_reversion,
+            vol_times,
+            vols,
+        })
+    }
+
+    /// Return `B(t, T) = (1 - exp(-a (T - t))) / a`.
+    pub fn b(&self, t: f64, maturity: f64) -> Number {
+        let a = &self.mean_reversion;
+        (1.0 - (a * (t - maturity)).exp()) / a
+    }
+
+    /// Return the variance `y(t)` of the state at time `t`.
+    pub fn variance(&self, t: f64) -> Number {
+        let a2 = &self.mean_reversion * 2.0;
+        let mut total = Number::F64(0.0);
+        let mut start = 0.0;
+        for (i, vol) in self.vols.iter().enumerate() {
+            if start >= t {
+                break;
+            }
+            let end = self.vol_times.get(i).map_or(t, |e| e.min(t));
+            let weight = ((&a2 * (end - t)).exp() - (&a2 * (start - t)).exp()) / &a2;
+            total = total + vol.clone().pow(2.0) * weight;
+            start = end;
+        }
+        total
+    }
+
+    /// Return the discount factor at `maturity` observed at `date` in the state `x`.
+    pub fn zero_bond<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+        date: &NaiveDateTime,
+        maturity: &NaiveDateTime,
+        x: &Number,
+    ) -> Number {
+        let (t, s) = (year_fraction(curve, date), year_fraction(curve, maturity));
+        let b = self.b(t, s);
+        let exponent = -(&b * x) - b.clone().pow(2.0) * self.variance(t) * 0.5;
+        curve.interpolated_value(maturity) / curve.interpolated_value(date) * exponent.exp()
+    }
+
+    /// Return the price of a European option expiring at `t` on the zero bond maturing at `s`.
+    ///
+    /// `p_t` and `p_s` are the discount factors of the curve at `t` and `s`. If the zero bond
+    /// has no volatility to expiry, e.g. when `t` is the initial node of the curve, the option
+    /// is worth its intrinsic value.
+    fn zero_bond_option(
+        &self,
+        p_t: &Number,
+        p_s: &Number,
+        (t, s): (f64, f64),
+        strike: &Number,
+        call: bool,
+    ) -> Number {
+        let sigma_p = self.b(t, s) * self.variance(t).pow(0.5);
+        if f64::from(&sigma_p) <= 0.0 {
+            let intrinsic = if call {
+                p_s - &(strike * p_t)
+            } else {
+                strike * p_t - p_s
+            };
+            return if f64::from(&intrinsic) > 0.0 {
+                intrinsic
+            } else {
+                Number::F64(0.0)
+            };
+        }
+        let h = (p_s / &(strike * p_t)).log() / &sigma_p + &sigma_p * 0.5;
+        if call {
+            p_s * h.norm_cdf() - strike * p_t * (&h - &sigma_p).norm_cdf()
+        } else {
+            strike * p_t * (&sigma_p - &h).norm_cdf() - p_s * (-h).norm_cdf()
+        }
+    }
+
+    /// Return the price of a caplet on the simple rate between `start` and `end`, with a strike
+    /// in percent, paid at `end`.
+    pub fn caplet<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+        start: &NaiveDateTime,
+        end: &NaiveDateTime,
+        dcf: f64,
+        strike: f64,
+        notional: f64,
+    ) -> Number {
+        let k = 1.0 + strike / 100.0 * dcf;
+        let times = (year_fraction(curve, start), year_fraction(curve, end));
+        let (p_t, p_s) = (
+            curve.interpolated_value(start),
+            curve.interpolated_value(end),
+        );
+        self.zero_bond_option(&p_t, &p_s, times, &Number::F64(1.0 / k), false) * (k * notional)
+    }
+
+    /// Return the price of a payer swaption by the decomposition of Jamshidian.
+    ///
+    /// The option expires at the effective date of the swap. Its float leg is valued at par, so
+    /// fixed payments are assumed at the end of each accrual period and the notional of the first
+    /// period applies throughout.
+    pub fn payer_swaption<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+        irs: &IRS,
+    ) -> Result<Number, PyErr> {
+        let leg = &irs.fixed_leg;
+        let expiry = leg.aschedule[0];
+        let dates = &leg.aschedule[1..];
+        let mut coupons = Vec::with_capacity(dates.len());
+        for i in 0..leg.n_periods() {
+            coupons.push(leg.rates[i] / 100.0 * leg.dcf(i)?);
+        }
+        coupons[dates.len() - 1] += 1.0;
+
+        // find the state x* at which the coupon bond is worth par, in f64, then take one Newton
+        // step in dual arithmetic so that x* carries its implicit sensitivities
+        let bond = |x: &Number| -> (Number, f64) {
+            let mut value = Number::F64(-1.0);
+            let mut slope = 0.0;
+            for (c, d) in coupons.iter().zip(dates) {
+                let p = self.zero_bond(curve, &expiry, d, x) * *c;
+                let b = self.b(year_fraction(curve, &expiry), year_fraction(curve, d));
+                slope -= f64::from(&p) * f64::from(&b);
+                value = value + p;
+            }
+            (value, slope)
+        };
+        let mut x = 0.0;
+        for _ in 0..100 {
+            let (g, slope) = bond(&Number::F64(x));
+            let dx = f64::from(&g) / slope;
+            x -= dx;
+            if dx.abs() < 1e-14 {
+                break;
+            }
+        }
+        let (g, slope) = bond(&Number::F64(x));
+        let x_star = (g / slope) * -1.0 + x;
+
+        let t = year_fraction(curve, &expiry);
+        let p_t = curve.interpolated_value(&expiry);
+        let mut price = Number::F64(0.0);
+        for (c, d) in coupons.iter().zip(dates) {
+            let strike = self.zero_bond(curve, &expiry, d, &x_star);
+            let p_s = curve.interpolated_value(d);
+            let s = year_fraction(curve, d);
+            price = price + self.zero_bond_option(&p_t, &p_s, (t, s), &strike, false) * *c;
+        }
+        Ok(price * leg.notionals[0])
+    }
+
+    /// Return the price of a calibration instrument.
+    pub fn price<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curve: &CurveDF<T, U>,
+        instrument: &CalibrationInstrument,
+    ) -> Result<Number, PyErr> {
+        match instrument {
+            CalibrationInstrument::Caplet {
+                start,
+                end,
+                dcf,
+                strike,
+                notional,
+            } => Ok(self.caplet(curve, start, end, *dcf, *strike, *notional)),
+            CalibrationInstrument::Swaption(irs) => self.payer_swaption(curve, irs),
+        }
+    }
+
+    fn params(&self) -> Vec<f64> {
+        let mut params = vec![f64::from(&self.mean_reversion)];
+        params.extend(self.vols.iter().map(f64::from));
+        params
+    }
+
+    fn with_params(&self, params: &[f64], vars: Option<&[String]>) -> HullWhite {
+        let numbers: Vec<Number> = match vars {
+            None => params.iter().map(|p| Number::F64(*p)).collect(),
+            Some(vars) => {
+                let base = Dual::new(0.0, vars.to_vec());
+                params
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| {
+                        let mut grad = Array1::zeros(params.len());
+                        grad[i] = 1.0;
+                        Number::Dual(Dual::clone_from(&base, *p, grad))
+                    })
+                    .collect()
+            }
+        };
+        HullWhite {
+            mean_reversion: numbers[0].clone(),
+            vol_times: self.vol_times.clone(),
+            vols: numbers[1..].to_vec(),
+        }
+    }
+}
+
+/// Calibrate the mean reversion and volatilities of a model to the prices of instruments.
+///
+/// The sum of squared price errors is minimised by Levenberg-Marquardt iteration from the
+/// parameters of `initial`, with the Jacobian of prices to parameters calculated by AD. If the
+/// curve has an AD order a Gauss-Newton step from the solution, with prices carrying the curve's
+/// sensitivities, gives the calibrated parameters the sensitivities of the curve. Whether the
+/// calibration converged is reported by the [SolverResult], and each iteration to `progress`.
+pub fn calibrate_hull_white<T, U>(
+    curve: &CurveDF<T, U>,
+    instruments: &[CalibrationInstrument],
+    prices: &[f64],
+    initial: &HullWhite,
+    tol: f64,
+    max_iter: usize,
+    progress: &Progress,
+) -> Result<(HullWhite, SolverResult), PyErr>
+where
+    T: CurveInterpolation + Clone,
+    U: DateRoll + Clone,
+{
+    if instruments.len() != prices.len() {
+        return Err(PyValueError::new_err(
+            "`instruments` and `prices` must have the same length.",
+        ));
+    }
+    let mut f64_curve = curve.clone();
+    f64_curve.set_ad_order(ADOrder::Zero)?;
+    let vars = get_variable_tags("hw", initial.params().len());
+
+    let residuals = |params: &[f64]| -> Result<Array1<f64>, PyErr> {
+        if params.iter().any(|p| *p <= 0.0) {
+            return Ok(Array1::from_elem(prices.len(), f64::INFINITY));
+        }
+        let model = initial.with_params(params, None);
+        let mut r = Array1::zeros(prices.len());
+        for (i, (instrument, target)) in instruments.iter().zip(prices).enumerate() {
+            r[i] = f64::from(model.price(&f64_curve, instrument)?) - target;
+        }
+        Ok(r)
+    };
+    let jacobian = |params: &[f64]| -> Result<(Array1<f64>, Array2<f64>), PyErr> {
+        let model = initial.with_params(params, Some(&vars));
+        let mut jac = Array2::zeros((prices.len(), params.len()));
+        let mut r = Array1::zeros(prices.len());
+        for (i, (instrument, target)) in instruments.iter().zip(prices).enumerate() {
+            let Number::Dual(price) = model.price(&f64_curve, instrument)? else {
+                unreachable!("dual parameters give dual prices")
+            };
+            jac.row_mut(i).assign(&price.gradient1(vars.clone()));
+            r[i] = price.real - target;
+        }
+        Ok((r, jac))
+    };
+
+    let (params, result) = levenberg_marquardt(
+        &initial.params(),
+        residuals,
+        jacobian,
+        tol,
+        max_iter,
+        progress,
+    )?;
+
+    // a Gauss-Newton step from the solution with prices on the curve at its own AD order gives
+    // the implicit sensitivities of the parameters to the curve
+    let model = initial.with_params(&params, None);
+    let mut r: Vec<Number> = Vec::with_capacity(prices.len());
+    for (instrument, target) in instruments.iter().zip(prices) {
+        r.push(model.price(curve, instrument)? - *target);
+    }
+    let lsq = prices.len() > params.len();
+    let numbers: Vec<Number> = match curve.ad() {
+        ADOrder::Zero => return Ok((model, result)),
+        ADOrder::One => {
+            let r: Array1<Dual> = r.iter().map(Dual::from).collect();
+            let step = fdsolve(&result.jacobian.view(), &r.view(), lsq);
+            params
+                .iter()
+                .zip(step.iter())
+                .map(|(p, s)| Number::Dual(s * -1.0 + *p))
+                .collect()
+        }
+        ADOrder::Two => {
+            let r: Array1<Dual2> = r.iter().map(Dual2::from).collect();
+            let step = fdsolve(&result.jacobian.view(), &r.view(), lsq);
+            params
+                .iter()
+                .zip(step.iter())
+                .map(|(p, s)| Number::Dual2(s * -1.0 + *p))
+                .collect()
+        }
+    };
+    Ok((
+        HullWhite {
+            mean_reversion: numbers[0].clone(),
+            vol_times: initial.vol_times.clone(),
+            vols: numbers[1..].to_vec(),
+        },
+        result,
+    ))
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, CalType, Convention, Modifier, NamedCal};
+    use crate::curves::{LogLinearInterpolator, Nodes};
+    use indexmap::IndexMap;
+
+    fn curve() -> CurveDF<LogLinearInterpolator, NamedCal> {
+        curve_with_df(0.86)
+    }
+
+    /// The test curve with the discount factor of its 5Y node.
+    fn curve_with_df(df: f64) -> CurveDF<LogLinearInterpolator, NamedCal> {
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2022, 1, 1), 1.0_f64),
+            (ndt(2027, 1, 1), df),
+            (ndt(2032, 1, 1), 0.72_f64),
+        ]));
+        CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            "sofr",
+            Convention::Act365F,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn model(a: f64, vols: [f64; 2]) -> HullWhite {
+        HullWhite::try_new(
+            Number::F64(a),
+            vec![2.0],
+            vols.iter().map(|v| Number::F64(*v)).collect(),
+        )
+        .unwrap()
+    }
+
+    fn swap(start: NaiveDateTime, end: NaiveDateTime, rate: f64) -> IRS {
+        let cal = CalType::NamedCal(NamedCal::try_new("all").unwrap());
+        IRS::try_new(
+            start,
+            end,
+            Some(12),
+            1e6,
+            rate,
+            Some(Convention::Act365F),
+            Some(&cal),
+            Some(Modifier::ModF),
+            Some(0),
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_variance_of_constant_vol() {
+        let hw = model(0.1, [0.01, 0.01]);
+        let expected = 0.0001 * (1.0 - (-0.2 * 3.0_f64).exp()) / 0.2;
+        assert!((f64::from(hw.variance(3.0)) - expected).abs() < 1e-16);
+        assert!(
+            HullWhite::try_new(Number::F64(0.1), vec![2.0, 1.0], vec![Number::F64(0.01); 3])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_zero_bond_without_vol_is_forward() {
+        let hw = model(0.1, [0.0, 0.0]);
+        let c = curve();
+        let (t, s) = (ndt(2023, 1, 1), ndt(2026, 1, 1));
+        let p = hw.zero_bond(&c, &t, &s, &Number::F64(0.0));
+        let forward = c.interpolated_value(&s) / c.interpolated_value(&t);
+        assert!((f64::from(p) - f64::from(forward)).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_one_period_swaption_is_caplet() {
+        let hw = model(0.05, [0.01, 0.012]);
+        let c = curve();
+        let (start, end) = (ndt(2024, 1, 1), ndt(2025, 1, 1));
+        let irs = swap(start, end, 3.0);
+        let swaption = f64::from(hw.payer_swaption(&c, &irs).unwrap());
+        let dcf = irs.fixed_leg.dcf(0).unwrap();
+        let caplet = f64::from(hw.caplet(&c, &start, &end, dcf, 3.0, 1e6));
+        assert!(caplet > 1000.0);
+        assert!((swaption - caplet).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_swaption_sensitivity_to_vol() {
+        let c = curve();
+        let irs = swap(ndt(2024, 1, 1), ndt(2029, 1, 1), 3.0);
+        let base = model(0.05, [0.01, 0.012]);
+        let vars = get_variable_tags("hw", 3);
+        let dual = base.with_params(&[0.05, 0.01, 0.012], Some(&vars));
+        let Number::Dual(price) = dual.payer_swaption(&c, &irs).unwrap() else {
+            panic!("expected a Dual price")
+        };
+        let grad = price.gradient1(vars);
+        for (i, bumped) in [[0.05 + 1e-6, 0.01, 0.012], [0.05, 0.01, 0.012 + 1e-6]]
+            .iter()
+            .enumerate()
+        {
+            let up = f64::from(
+                base.with_params(bumped, None)
+                    .payer_swaption(&c, &irs)
+                    .unwrap(),
+            );
+            let fd = (up - price.real) / 1e-6;
+            let analytic = grad[if i == 0 { 0 } else { 2 }];
+            assert!((fd - analytic).abs() < 1e-3 * analytic.abs().max(1.0));
+        }
+    }
+
+    #[test]
+    fn test_options_without_volatility_are_intrinsic() {
+        let c = curve();
+        let start = ndt(2022, 1, 1); // the initial node of the curve
+        let end = ndt(2023, 1, 1);
+        let hw = model(0.05, [0.01, 0.012]);
+        let caplet = f64::from(hw.caplet(&c, &start, &end, 1.0, 0.0, 1e6));
+        let intrinsic = (1.0 - f64::from(c.interpolated_value(&end))) * 1e6;
+        assert!((caplet - intrinsic).abs() < 1e-9);
+        assert_eq!(f64::from(hw.caplet(&c, &start, &end, 1.0, 50.0, 1e6)), 0.0);
+        let swaption = f64::from(hw.payer_swaption(&c, &swap(start, end, 0.0)).unwrap());
+        assert!((swaption - intrinsic).abs() < 1e-6);
+
+        let zero_vol = model(0.05, [0.0, 0.0]);
+        let caplet = zero_vol.caplet(&c, &ndt(2024, 1, 1), &ndt(2025, 1, 1), 1.0, 0.0, 1e6);
+        assert!(f64::from(caplet) > 0.0);
+    }
+
+    fn calibration_instruments() -> Vec<CalibrationInstrument> {
+        vec![
+            CalibrationInstrument::Caplet {
+                start: ndt(2023, 1, 1),
+                end: ndt(2023, 7, 1),
+                dcf: 0.5,
+                strike: 3.0,
+                notional: 1e6,
+            },
+            CalibrationInstrument::Swaption(swap(ndt(2023, 7, 1), ndt(2028, 7, 1), 3.0)),
+            CalibrationInstrument::Swaption(swap(ndt(2025, 1, 1), ndt(2030, 1, 1), 3.0)),
+            CalibrationInstrument::Swaption(swap(ndt(2027, 1, 1), ndt(2029, 1, 1), 3.0)),
+        ]
+    }
+
+    #[test]
+    fn test_calibration_recovers_parameters() {
+        let c = curve();
+        let truth = model(0.05, [0.008, 0.012]);
+        let instruments = calibration_instruments();
+        let prices: Vec<f64> = instruments
+            .iter()
+            .map(|i| f64::from(truth.price(&c, i).unwrap()))
+            .collect();
+        let (calibrated, result) = calibrate_hull_white(
+            &c,
+            &instruments,
+            &prices,
+            &model(0.1, [0.01, 0.01]),
+            1e-6,
+            50,
+            &Progress::default(),
+        )
+        .unwrap();
+        assert!(result.converged);
+        assert_eq!(result.jacobian.dim(), (4, 3));
+        assert!((f64::from(&calibrated.mean_reversion) - 0.05).abs() < 1e-6);
+        assert!((f64::from(&calibrated.vols[0]) - 0.008).abs() < 1e-7);
+        assert!((f64::from(&calibrated.vols[1]) - 0.012).abs() < 1e-7);
+    }
+    #[test]
+    fn test_calibration_carries_curve_sensitivities() {
+        let truth = model(0.05, [0.008, 0.012]);
+        let instruments = calibration_instruments();
+        let prices: Vec<f64> = instruments
+            .iter()
+            .map(|i| f64::from(truth.price(&curve(), i).unwrap()))
+            .collect();
+        let calibrate = |c: &CurveDF<LogLinearInterpolator, NamedCal>| {
+            calibrate_hull_white(
+                c,
+                &instruments,
+                &prices,
+                &model(0.1, [0.01, 0.01]),
+                1e-9,
+                50,
+                &Progress::default(),
+            )
+            .unwrap()
+            .0
+        };
+
+        let mut c = curve();
+        c.set_ad_order(ADOrder::One).unwrap();
+        let calibrated = calibrate(&c);
+        let vars = get_variable_tags("sofr", 3);
+        let Number::Dual(vol) = &calibrated.vols[1] else {
+            panic!("expected a Dual volatility")
+        };
+        assert!((vol.real - 0.012).abs() < 1e-7);
+
+        let bump = 1e-6;
+        let bumped = calibrate(&curve_with_df(0.86 + bump));
+        let fd = (f64::from(&bumped.vols[1]) - vol.real) / bump;
+        let analytic = vol.gradient1(vars)[1];
+        assert!(analytic.abs() > 1e-3);
+        assert!((fd - analytic).abs() < 1e-3 * analytic.abs());
+
+        // a curve without AD order gives parameters without sensitivities
+        assert!(matches!(calibrate(&curve()).vols[1], Number::F64(_)));
+    }
+}
@@ -0,0 +1,276 @@
+//! Black prices of European options and their implied volatility.
+//!
+//! Prices are undiscounted, i.e. forward premiums, so a premium paid at a date other than the
+//! expiry must be divided by the relevant discount factor before use.
+//!
+//! The implied volatility is found in the normalised form of Jäckel's *By Implication*, with
+//! `x = ln(F / K)`, the total volatility `s = vol * sqrt(t)` and the price `b = P / sqrt(F K)` of
+//! the out-of-the-money option. An initial guess from the rational approximations either side of
+//! the inflection point `s_c = sqrt(2 |x|)` is refined by third order Householder iteration,
+//! which is performed on `ln b` below the inflection point so that deep out-of-the-money options
+//! converge as quickly as those at-the-money. Each iterate is kept within a bracket of the root.
+
+use crate::dual::{MathFuncs, Number};
+use crate::splines::check_number_types;
+use pyo3::exceptions::PyValueError;
+use pyo3::PyErr;
+use std::f64::consts::PI;
+
+/// The relative change in total volatility at which iteration stops, if the price is not first
+/// matched to machine precision.
+pub const TOLERANCE: f64 = 1e-14;
+
+/// The maximum number of Householder iterations.
+const MAX_ITER: usize = 50;
+
+/// Return the undiscounted Black price of a call or put expiring in `t` years.
+///
+/// Panics if the inputs mix `Dual` and `Dual2`, as with the arithmetic of [Number].
+pub fn black_price(forward: &Number, strike: &Number, vol: &Number, t: f64, call: bool) -> Number {
+    let sigma = vol * t.sqrt();
+    let d1 = (forward / strike).log() / &sigma + &sigma * 0.5;
+    let d2 = &d1 - &sigma;
+    if call {
+        forward * d1.norm_cdf() - strike * d2.norm_cdf()
+    } else {
+        strike * (-d2).norm_cdf() - forward * (-d1).norm_cdf()
+    }
+}
+
+/// Return the undiscounted Black vega, the derivative of [black_price] by `vol`.
+///
+/// Panics if the inputs mix `Dual` and `Dual2`, as with the arithmetic of [Number].
+pub fn black_vega(forward: &Number, strike: &Number, vol: &Number, t: f64) -> Number {
+    let sigma = vol * t.sqrt();
+    let d1 = (forward / strike).log() / &sigma + &sigma * 0.5;
+    forward * (&d1 * &d1 * -0.5).exp() * (t / (2.0 * PI)).sqrt()
+}
+
+/// Return the Black volatility implied by the undiscounted price of a call or put expiring in
+/// `t` years.
+///
+/// The volatility is found in [f64] and then refined by two Newton steps in the arithmetic of the
+/// inputs, so that its first and second order sensitivities to the `price`, `forward` and
+/// `strike` are those of the implicit function.
+///
+/// # Errors
+///
+/// If `forward`, `strike` or `t` are not positive, or the `price` is not strictly between the
+/// intrinsic value and the upper bound of the option, being `F` for a call and `K` for a put,
+/// or if the inputs mix `Dual` and `Dual2`.
+pub fn implied_vol(
+    price: &Number,
+    forward: &Number,
+    strike: &Number,
+    t: f64,
+    call: bool,
+) -> Result<Number, PyErr> {
+    // the refinement below combines the inputs, so they must not mix dual types
+    check_number_types([price, forward, strike].into_iter())?;
+    let (p, f, k) = (f64::from(price), f64::from(forward), f64::from(strike));
+    if f <= 0.0 || k <= 0.0 || t <= 0.0 {
+        return Err(PyValueError::new_err(
+            "`forward`, `strike` and `t` must be positive to imply a volatility.",
+        ));
+    }
+    let s = normalised_implied_vol((f / k).ln(), p / (f * k).sqrt(), call)?;
+    let mut vol = Number::F64(s / t.sqrt());
+    for _ in 0..2 {
+        let error = black_price(forward, strike, &vol, t, call) - price;
+        vol = &vol - error / black_vega(forward, strike, &vol, t);
+    }
+    Ok(vol)
+}
+
+/// Return the normalised price of an out-of-the-money call, with `x <= 0`.
+fn normalised_black(x: f64, s: f64) -> f64 {
+    (0.5 * x).exp() * (x / s + 0.5 * s).norm_cdf() - (-0.5 * x).exp() * (x / s - 0.5 * s).norm_cdf()
+}
+
+/// Return the normalised vega, the derivative of [normalised_black] by `s`.
+fn normalised_vega(x: f64, s: f64) -> f64 {
+    (-0.5 * (x * x / (s * s) + 0.25 * s * s)).exp() / (2.0 * PI).sqrt()
+}
+
+/// Return the initial guess of total volatility for the normalised out-of-the-money price `b`.
+///
+/// Below the price `b_c` at the inflection point the lower branch approximation is used, and
+/// above it the upper branch, which is exact at-the-money.
+fn initial_guess(x: f64, b: f64, s_c: f64, b_c: f64) -> f64 {
+    if b < b_c {
+        (2.0 * x * x / (x.abs() - 4.0 * (b / b_c).ln())).sqrt()
+    } else {
+        let (e_p, e_m) = ((0.5 * x).exp(), (-0.5 * x).exp());
+        let guess = -2.0 * ((e_p - b) / (e_p + e_m)).inv_norm_cdf();
+        match guess.is_finite() && guess > 0.0 {
+            true => guess,
+            false => s_c.max(1.0),
+        }
+    }
+}
+
+/// Return the total volatility of a normalised price `beta` of a call or put.
+fn normalised_implied_vol(x: f64, beta: f64, call: bool) -> Result<f64, PyErr> {
+    // Reduce to the out-of-the-money call by put-call parity and the symmetry in `x`
+    let theta = if call { 1.0 } else { -1.0 };
+    let intrinsic = (theta * ((0.5 * x).exp() - (-0.5 * x).exp())).max(0.0);
+    let (x, b) = (-x.abs(), beta - intrinsic);
+    if b.is_nan() || b <= 0.0 {
+        return Err(PyValueError::new_err(
+            "The option price must be greater than its intrinsic value to imply a volatility.",
+        ));
+    }
+    if b >= (0.5 * x).exp() {
+        return Err(PyValueError::new_err(
+            "The option price must be less than its upper bound to imply a volatility.",
+        ));
+    }
+
+    let s_c = (2.0 * x.abs()).sqrt();
+    let b_c = normalised_black(x, s_c);
+    let log_space = b < b_c;
+    let mut s = initial_guess(x, b, s_c, b_c);
+    let (mut lo, mut hi) = (0.0_f64, f64::INFINITY);
+    for _ in 0..MAX_ITER {
+        let value = normalised_black(x, s);
+        if (value - b).abs() <= 2.0 * f64::EPSILON * b {
+            // the price is matched to machine precision
+            return Ok(s);
+        }
+        if value > b {
+            hi = s;
+        } else {
+            lo = s;
+        }
+        // ratios of the second and third derivatives to the first of the objective function
+        let h2 = x * x / (s * s * s) - 0.25 * s;
+        let h3 = h2 * h2 - 3.0 * x * x / (s * s * s * s) - 0.25;
+        let vega = normalised_vega(x, s);
+        let step = if log_space && value <= 0.0 {
+            f64::NAN
+        } else {
+            let (g, g1, r2, r3) = if log_space {
+                let q = vega / value;
+                (
+                    value.ln() - b.ln(),
+                    q,
+                    h2 - q,
+                    h3 - 3.0 * q * h2 + 2.0 * q * q,
+                )
+            } else {
+                (value - b, vega, h2, h3)
+            };
+            let nu = -g / g1;
+            nu * (1.0 + 0.5 * nu * r2) / (1.0 + nu * (r2 + nu * r3 / 6.0))
+        };
+        let mut next = s + step;
+        if !(next > lo && next < hi) {
+            next = match hi.is_finite() {
+                true => 0.5 * (lo + hi),
+                false => 2.0 * s,
+            };
+        }
+        if (next - s).abs() <= TOLERANCE * s {
+            return Ok(next);
+        }
+        s = next;
+    }
+    Err(PyValueError::new_err(
+        "The implied volatility did not converge.",
+    ))
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dual::{Dual, Dual2};
+
+    fn f(v: f64) -> Number {
+        Number::F64(v)
+    }
+
+    #[test]
+    fn test_round_trip_across_moneyness() {
+        for strike in [20.0, 50.0, 80.0, 100.0, 125.0, 200.0, 500.0] {
+            for vol in [0.01, 0.05, 0.2, 1.0, 3.0] {
+                for t in [0.01, 1.0, 10.0] {
+                    for call in [true, false] {
+                        let price = black_price(&f(100.0), &f(strike), &f(vol), t, call);
+                        let intrinsic =
+                            (if call { 100.0 - strike } else { strike - 100.0 }).max(0.0);
+                        if f64::from(&price) - intrinsic < 1e-10 {
+                            continue; // no time value to resolve in f64
+                        }
+                        let Ok(iv) = implied_vol(&price, &f(100.0), &f(strike), t, call) else {
+                            panic!("K={strike}, vol={vol}, t={t}, call={call}: no convergence")
+                        };
+                        assert!(
+                            (f64::from(&iv) - vol).abs() < 1e-8 * vol.max(1.0),
+                            "K={strike}, vol={vol}, t={t}, call={call}: {iv:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_deep_out_of_the_money() {
+        let x = (100.0_f64 / 300.0).ln();
+        let b = normalised_black(x, 0.1);
+        assert!(b < 1e-20);
+        assert!((normalised_implied_vol(x, b, true).unwrap() - 0.1).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_first_order_sensitivities() {
+        let price = Number::Dual(Dual::new(8.0, vec!["p".to_string()]));
+        let iv = implied_vol(&price, &f(100.0), &f(100.0), 1.0, true).unwrap();
+        let Number::Dual(d) = &iv else {
+            panic!("a dual price gives a dual volatility")
+        };
+        let vega = black_vega(&f(100.0), &f(100.0), &f(d.real), 1.0);
+        assert!((d.dual[0] - 1.0 / f64::from(&vega)).abs() < 1e-12);
+
+        // a higher forward raises the call price so lowers the implied volatility
+        let forward = Number::Dual(Dual::new(100.0, vec!["f".to_string()]));
+        let iv = implied_vol(&f(8.0), &forward, &f(100.0), 1.0, true).unwrap();
+        let Number::Dual(d) = &iv else {
+            panic!("a dual forward gives a dual volatility")
+        };
+        assert!(d.dual[0] < 0.0);
+    }
+
+    #[test]
+    fn test_second_order_sensitivity() {
+        let price = Number::Dual2(Dual2::new(12.0, vec!["p".to_string()]));
+        let iv = implied_vol(&price, &f(100.0), &f(110.0), 0.5, false).unwrap();
+        let Number::Dual2(d) = &iv else {
+            panic!("a dual2 price gives a dual2 volatility")
+        };
+        let h = 1e-4;
+        let vol =
+            |p: f64| f64::from(&implied_vol(&f(p), &f(100.0), &f(110.0), 0.5, false).unwrap());
+        let second = (vol(12.0 + h) - 2.0 * vol(12.0) + vol(12.0 - h)) / (h * h);
+        assert!((2.0 * d.dual2[[0, 0]] - second).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_prices_outside_bounds() {
+        // below intrinsic value
+        assert!(implied_vol(&f(9.0), &f(110.0), &f(100.0), 1.0, true).is_err());
+        assert!(implied_vol(&f(9.0), &f(100.0), &f(110.0), 1.0, false).is_err());
+        // above the forward for a call and the strike for a put
+        assert!(implied_vol(&f(100.0), &f(100.0), &f(90.0), 1.0, true).is_err());
+        assert!(implied_vol(&f(90.0), &f(100.0), &f(90.0), 1.0, false).is_err());
+        assert!(implied_vol(&f(5.0), &f(100.0), &f(90.0), 0.0, true).is_err());
+    }
+
+    #[test]
+    fn test_mixed_dual_types_error() {
+        let price = Number::Dual(Dual::new(10.0, vec!["p".to_string()]));
+        let forward = Number::Dual2(Dual2::new(100.0, vec!["f".to_string()]));
+        assert!(implied_vol(&price, &forward, &f(100.0), 1.0, true).is_err());
+    }
+}
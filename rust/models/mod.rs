@@ -0,0 +1,4 @@
+//! Term structure models calibrated to curves and option prices.
+
+pub mod hull_white;
+pub mod implied_vol;
@@ -0,0 +1,187 @@
+//! Observe the progress of long-running calculations, such as solves and Monte Carlo
+//! simulations, and cancel them.
+//!
+//! A calculation reports each iteration to a [Progress], which every `every` iterations calls
+//! its callback and checks whether the calculation has been cancelled, either by a
+//! [CancellationToken] or, when called from Python, by an interrupt such as *Ctrl-C*. A
+//! cancelled calculation returns an error rather than a partial result.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A flag shared between a calculation and another thread, which may set it to cancel the
+/// calculation.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Create a token which is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel every calculation observing the token, or a clone of it.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed)
+    }
+
+    /// Whether the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// A function called with the number of iterations performed and a measure of progress, such as
+/// the residual norm of a solve, which may return an error to stop the calculation.
+type Callback<'a> = Box<dyn Fn(usize, f64) -> Result<(), PyErr> + Send + Sync + 'a>;
+
+/// The observer of a long-running calculation.
+pub struct Progress<'a> {
+    every: usize,
+    callback: Option<Callback<'a>>,
+    token: Option<CancellationToken>,
+    check_signals: bool,
+}
+
+impl Default for Progress<'_> {
+    fn default() -> Self {
+        Progress::new(1)
+    }
+}
+
+impl<'a> Progress<'a> {
+    /// Create an observer which acts on every `every` iterations, with no callback or token.
+    pub fn new(every: usize) -> Self {
+        Progress {
+            every: every.max(1),
+            callback: None,
+            token: None,
+            check_signals: false,
+        }
+    }
+
+    /// Add a callback of the number of iterations performed and the measure of progress.
+    pub fn with_callback<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(usize, f64) -> Result<(), PyErr> + Send + Sync + 'a,
+    {
+        self.callback = Some(Box::new(callback));
+        self
+    }
+
+    /// Add a token by which the calculation may be cancelled.
+    pub fn with_token(mut self, token: CancellationToken) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    /// Report that an iteration of the calculation has been performed.
+    ///
+    /// # Errors
+    ///
+    /// On an iteration which is a multiple of `every`, if the calculation has been cancelled or
+    /// the callback returns an error.
+    pub fn report(&self, iteration: usize, value: f64) -> Result<(), PyErr> {
+        if !iteration.is_multiple_of(self.every) {
+            return Ok(());
+        }
+        if self.token.as_ref().is_some_and(|t| t.is_cancelled()) {
+            return Err(PyValueError::new_err("The calculation was cancelled."));
+        }
+        if self.check_signals {
+            Python::with_gil(|py| py.check_signals())?;
+        }
+        match &self.callback {
+            Some(callback) => callback(iteration, value),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Progress<'static> {
+    /// Create an observer of a calculation called from Python, which also stops it on a pending
+    /// interrupt, such as a `KeyboardInterrupt`.
+    pub(crate) fn from_py(
+        callback: Option<PyObject>,
+        every: usize,
+        token: Option<CancellationToken>,
+    ) -> Result<Self, PyErr> {
+        if every == 0 {
+            return Err(PyValueError::new_err("`every` must be positive."));
+        }
+        let mut progress = Progress::new(every);
+        progress.check_signals = true;
+        progress.token = token;
+        if let Some(callback) = callback {
+            progress = progress.with_callback(move |iteration, value| {
+                Python::with_gil(|py| callback.call1(py, (iteration, value)).map(|_| ()))
+            });
+        }
+        Ok(progress)
+    }
+}
+
+#[pymethods]
+impl CancellationToken {
+    #[new]
+    fn new_py() -> Self {
+        CancellationToken::new()
+    }
+
+    /// Cancel every calculation observing the token.
+    ///
+    /// Returns
+    /// -------
+    /// None
+    #[pyo3(name = "cancel")]
+    fn cancel_py(&self) {
+        self.cancel()
+    }
+
+    /// Whether the token has been cancelled.
+    #[getter]
+    #[pyo3(name = "cancelled")]
+    fn cancelled_py(&self) -> bool {
+        self.is_cancelled()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<CancellationToken: cancelled={}>", self.is_cancelled())
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_callback_every() {
+        let calls = Mutex::new(Vec::new());
+        let progress = Progress::new(3).with_callback(|i, v| {
+            calls.lock().unwrap().push((i, v));
+            Ok(())
+        });
+        for i in 1..=7 {
+            progress.report(i, i as f64 / 10.0).unwrap();
+        }
+        drop(progress);
+        assert_eq!(calls.into_inner().unwrap(), vec![(3, 0.3), (6, 0.6)]);
+    }
+
+    #[test]
+    fn test_cancellation() {
+        let token = CancellationToken::new();
+        let progress = Progress::new(2).with_token(token.clone());
+        assert!(progress.report(2, 0.0).is_ok());
+        token.cancel();
+        assert!(progress.report(3, 0.0).is_ok());
+        assert!(progress.report(4, 0.0).is_err());
+    }
+}
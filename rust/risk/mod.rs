@@ -0,0 +1,237 @@
+//! Aggregate the derivatives contained in dual numbers into standard risk representations.
+//!
+//! Sensitivities calculated by AD are expressed against each individual curve node variable.
+//! The functions in this module re-express those sensitivities in formats convenient for
+//! risk reporting, for example a key-rate ladder measured against standard tenors.
+
+use crate::dual::linalg::fdsolve;
+use crate::dual::{get_variable_tags, Dual, Gradient1, Vars};
+use indexmap::IndexMap;
+use ndarray::{Array1, ArrayView2};
+use pyo3::exceptions::PyValueError;
+use pyo3::PyErr;
+
+pub mod pnl;
+pub(crate) mod risk_py;
+pub mod var;
+
+/// Convert a tenor string, e.g. *"1D"*, *"2W"*, *"6M"* or *"10Y"*, into a year fraction.
+///
+/// Days and weeks are measured against a 365 day year, months as twelfths of a year.
+pub fn tenor_to_years(tenor: &str) -> Result<f64, PyErr> {
+    let tenor = tenor.trim().to_uppercase();
+    let err = || PyValueError::new_err(format!("`tenor` could not be parsed: '{}'.", tenor));
+    if tenor.len() < 2 {
+        return Err(err());
+    }
+    let (value, unit) = tenor.split_at(tenor.len() - 1);
+    let value: f64 = value.parse().map_err(|_| err())?;
+    match unit {
+        "D" => Ok(value / 365.0),
+        "W" => Ok(value * 7.0 / 365.0),
+        "M" => Ok(value / 12.0),
+        "Y" => Ok(value),
+        _ => Err(err()),
+    }
+}
+
+/// Group the derivatives of a [Dual] associated with a curve into a key-rate ladder.
+///
+/// The curve's variables are identified by `curve_id` and are assumed to be tagged
+/// in node order, e.g. *"curve0"*, *"curve1"*, ..., with `node_tenors` giving the year fraction
+/// of each node measured from the initial node. Each node sensitivity is allocated to the
+/// two adjacent buckets of `tenor_labels` by linear interpolation. Sensitivities of nodes
+/// outside the range of the buckets are allocated entirely to the first or last bucket.
+///
+/// Returns an array of the same length as `tenor_labels`. Variables of the dual not
+/// associated with the curve are ignored.
+pub fn keyrate_ladder(
+    dual: &Dual,
+    curve_id: &str,
+    node_tenors: &[f64],
+    tenor_labels: &[String],
+) -> Result<Array1<f64>, PyErr> {
+    if tenor_labels.is_empty() {
+        return Err(PyValueError::new_err(
+            "`tenor_labels` must contain at least one tenor.",
+        ));
+    }
+    let buckets: Vec<f64> = tenor_labels
+        .iter()
+        .map(|t| tenor_to_years(t))
+        .collect::<Result<Vec<f64>, PyErr>>()?;
+    if buckets.windows(2).any(|w| w[0] >= w[1]) {
+        return Err(PyValueError::new_err(
+            "`tenor_labels` must be given in strictly ascending order.",
+        ));
+    }
+
+    let mut ladder = Array1::<f64>::zeros(buckets.len());
+    let tags = get_variable_tags(curve_id, node_tenors.len());
+    for (tag, t) in tags.iter().zip(node_tenors.iter()) {
+        let Some(idx) = dual.vars().get_index_of(tag) else {
+            continue;
+        };
+        let s = dual.dual[idx];
+        let j = buckets.partition_point(|b| b <= t);
+        if j == 0 {
+            ladder[0] += s;
+        } else if j == buckets.len() {
+            ladder[j - 1] += s;
+        } else {
+            let w = (t - buckets[j - 1]) / (buckets[j] - buckets[j - 1]);
+            ladder[j - 1] += (1.0 - w) * s;
+            ladder[j] += w * s;
+        }
+    }
+    Ok(ladder)
+}
+
+/// Transform the derivatives of a [Dual] from curve variables into the market quotes of the
+/// instruments calibrating the curves.
+///
+/// `jacobian` has a row for each of `quote_labels` and a column for each of `vars`, whose
+/// entries are the derivatives of each quote to each variable. The sensitivities to the quotes,
+/// `x`, solve `J^T x = g`, where `g` are the derivatives of the dual to `vars`, by least squares
+/// if there are more variables than quotes.
+///
+/// Returns the sensitivity to each of `quote_labels`, in order, measured per unit of each quote.
+///
+/// # Errors
+///
+/// If the shapes are inconsistent, or if any sensitivity is not finite, e.g. from a singular
+/// `jacobian`.
+pub fn to_quote_space(
+    dual: &Dual,
+    jacobian: &ArrayView2<f64>,
+    vars: &[String],
+    quote_labels: &[String],
+) -> Result<IndexMap<String, f64>, PyErr> {
+    let (n_quotes, n_vars) = jacobian.dim();
+    if n_quotes != quote_labels.len() || n_vars != vars.len() {
+        return Err(PyValueError::new_err(
+            "`jacobian` must have a row for each quote label and a column for each variable.",
+        ));
+    }
+    if n_vars < n_quotes {
+        return Err(PyValueError::new_err(
+            "`jacobian` cannot have fewer variables than quotes.",
+        ));
+    }
+    let gradient = dual.gradient1(vars.to_vec());
+    let deltas = fdsolve(&jacobian.t(), &gradient.view(), n_vars > n_quotes);
+    if deltas.iter().any(|x| !x.is_finite()) {
+        return Err(PyValueError::new_err(
+            "Sensitivities to the quotes are not finite: `jacobian` may be singular.",
+        ));
+    }
+    Ok(quote_labels.iter().cloned().zip(deltas).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(v: &[&str]) -> Vec<String> {
+        v.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_tenor_to_years() {
+        assert_eq!(tenor_to_years("6m").unwrap(), 0.5);
+        assert_eq!(tenor_to_years("10Y").unwrap(), 10.0);
+        assert_eq!(tenor_to_years("73D").unwrap(), 0.2);
+        assert!(tenor_to_years("1X").is_err());
+        assert!(tenor_to_years("Y").is_err());
+    }
+
+    #[test]
+    fn test_keyrate_ladder_interpolation() {
+        let d = Dual::try_new(
+            1.0,
+            vec!["crv0".to_string(), "crv1".to_string(), "crv2".to_string()],
+            vec![1.0, 2.0, 4.0],
+        )
+        .unwrap();
+        let result =
+            keyrate_ladder(&d, "crv", &[0.0, 1.5, 5.0], &labels(&["1Y", "2Y", "3Y"])).unwrap();
+        assert_eq!(result, Array1::from_vec(vec![1.0 + 1.0, 1.0, 4.0]));
+        assert_eq!(result.sum(), 7.0);
+    }
+
+    #[test]
+    fn test_keyrate_ladder_ignores_other_vars() {
+        let d = Dual::try_new(
+            1.0,
+            vec!["crv0".to_string(), "other0".to_string()],
+            vec![1.0, 2.0],
+        )
+        .unwrap();
+        let result = keyrate_ladder(&d, "crv", &[2.0], &labels(&["1Y", "2Y"])).unwrap();
+        assert_eq!(result, Array1::from_vec(vec![0.0, 1.0]));
+    }
+
+    #[test]
+    fn test_keyrate_ladder_unordered_error() {
+        let d = Dual::new(1.0, vec!["crv0".to_string()]);
+        assert!(keyrate_ladder(&d, "crv", &[0.0], &labels(&["2Y", "1Y"])).is_err());
+    }
+
+    #[test]
+    fn test_to_quote_space() {
+        use ndarray::arr2;
+        // quotes are s0 = v0 + v1 and s1 = v1, so P = 2 v0 + 3 v1 = 2 s0 + s1
+        let d = Dual::try_new(
+            1.0,
+            vec!["v0".to_string(), "v1".to_string(), "other".to_string()],
+            vec![2.0, 3.0, 7.0],
+        )
+        .unwrap();
+        let jacobian = arr2(&[[1.0, 1.0], [0.0, 1.0]]);
+        let result = to_quote_space(
+            &d,
+            &jacobian.view(),
+            &labels(&["v0", "v1"]),
+            &labels(&["s0", "s1"]),
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            IndexMap::from([("s0".to_string(), 2.0), ("s1".to_string(), 1.0)])
+        );
+        assert_eq!(result.keys().collect::<Vec<_>>(), ["s0", "s1"]);
+    }
+
+    #[test]
+    fn test_to_quote_space_singular_error() {
+        use ndarray::arr2;
+        let d = Dual::try_new(
+            1.0,
+            vec!["v0".to_string(), "v1".to_string()],
+            vec![1.0, 2.0],
+        )
+        .unwrap();
+        let jacobian = arr2(&[[1.0, 1.0], [1.0, 1.0]]);
+        let result = to_quote_space(
+            &d,
+            &jacobian.view(),
+            &labels(&["v0", "v1"]),
+            &labels(&["s0", "s1"]),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_to_quote_space_shape_error() {
+        use ndarray::arr2;
+        let d = Dual::new(1.0, vec!["v0".to_string()]);
+        let jacobian = arr2(&[[1.0], [1.0]]);
+        let result = to_quote_space(
+            &d,
+            &jacobian.view(),
+            &labels(&["v0"]),
+            &labels(&["s0", "s1"]),
+        );
+        assert!(result.is_err());
+    }
+}
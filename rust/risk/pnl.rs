@@ -0,0 +1,233 @@
+//! Attribution of the P&L of a portfolio between two market states.
+//!
+//! The P&L is explained by a sequence of revaluations. First the market at `t0` is rolled to the
+//! date of the market at `t1` with its forward discount factors unchanged, which isolates carry.
+//! The change of each node from the rolled market to the market at `t1` is then attributed to
+//! delta and gamma by the first and second order sensitivities of the portfolio to the nodes of
+//! the rolled market, leaving any higher order effects as residual.
+
+use crate::calendars::DateRoll;
+use crate::curves::nodes::{node_date, NodesTimestamp};
+use crate::curves::{CurveDF, CurveInterpolation};
+use crate::dual::{get_variable_tags, ADOrder, Gradient1, Gradient2, Number};
+use crate::instruments::{CurveInstrument, InstrumentType};
+use crate::state::next_state_id;
+use chrono::prelude::*;
+use indexmap::IndexMap;
+use ndarray::{Array1, Array2};
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, PyErr};
+
+/// The P&L of a portfolio between two market states, attributed to its sources.
+///
+/// The components sum to the total. Portfolios are priced from curves alone, which carry no
+/// volatility, so `vol` is zero and completes the standard form of the report.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PnlExplain {
+    pub total: f64,
+    pub carry: f64,
+    pub delta: f64,
+    pub gamma: f64,
+    pub vol: f64,
+    pub residual: f64,
+}
+
+/// Return the curve at `t0` rolled to the initial node of the curve at `t1`.
+///
+/// The rolled curve has the nodes, and therefore the variables, of the curve at `t1`, with
+/// discount factors forward from its initial node on the curve at `t0`.
+pub fn rolled_curve<T, U>(
+    curve_t0: &CurveDF<T, U>,
+    curve_t1: &CurveDF<T, U>,
+) -> Result<CurveDF<T, U>, PyErr>
+where
+    T: CurveInterpolation + Clone,
+    U: DateRoll + Clone,
+{
+    let dates: Vec<NaiveDateTime> = curve_t1
+        .nodes
+        .keys()
+        .iter()
+        .map(|k| node_date(*k))
+        .collect();
+    let v0 = f64::from(curve_t0.interpolated_value(&dates[0]));
+    let mut rolled = curve_t1.clone();
+    rolled.set_ad_order(ADOrder::Zero)?;
+    rolled.nodes = NodesTimestamp::F64(IndexMap::from_iter(dates.iter().map(|d| {
+        (
+            d.and_utc().timestamp(),
+            f64::from(curve_t0.interpolated_value(d)) / v0,
+        )
+    })));
+    rolled.state_id = next_state_id();
+    Ok(rolled)
+}
+
+fn portfolio_npv<T: CurveInterpolation, U: DateRoll>(
+    portfolio: &[InstrumentType],
+    curve: &CurveDF<T, U>,
+) -> Result<Number, PyErr> {
+    let mut npv = Number::F64(0.0);
+    for instrument in portfolio {
+        npv = npv + instrument.npv(curve)?;
+    }
+    Ok(npv)
+}
+
+fn node_values<T: CurveInterpolation, U: DateRoll>(curve: &CurveDF<T, U>) -> Array1<f64> {
+    let dates: Vec<NaiveDateTime> = curve.nodes.keys().iter().map(|k| node_date(*k)).collect();
+    dates
+        .iter()
+        .map(|d| f64::from(curve.interpolated_value(d)))
+        .collect()
+}
+
+/// Explain the P&L of a portfolio from the market at `t0` to the market at `t1`.
+///
+/// The sensitivities of delta and gamma are those of the market at `t0`, evaluated on its
+/// [rolled_curve], rather than on the curve at `t0` itself. The rolled curve keeps the forward
+/// discount factors of `t0` but has the nodes of `t1`, so the node moves to `t1` are measured
+/// against the same variables as the sensitivities.
+///
+/// A portfolio with no cashflows after the initial node of the rolled curve, such as an empty or
+/// matured portfolio, has no sensitivity to its nodes, so its delta and gamma are zero.
+pub fn pnl_explain<T, U>(
+    portfolio: &[InstrumentType],
+    curve_t0: &CurveDF<T, U>,
+    curve_t1: &CurveDF<T, U>,
+) -> Result<PnlExplain, PyErr>
+where
+    T: CurveInterpolation + Clone,
+    U: DateRoll + Clone,
+{
+    if curve_t0.id != curve_t1.id {
+        return Err(PyValueError::new_err(
+            "The curves of each market state must have the same `id`.",
+        ));
+    }
+    let mut base = curve_t0.clone();
+    base.set_ad_order(ADOrder::Zero)?;
+    let v0 = f64::from(portfolio_npv(portfolio, &base)?);
+
+    let mut rolled = rolled_curve(curve_t0, curve_t1)?;
+    rolled.set_ad_order(ADOrder::Two)?;
+    let vars = get_variable_tags(&rolled.id, rolled.nodes.keys().len());
+    let n = vars.len();
+    let (v_rolled, grad, hess) = match portfolio_npv(portfolio, &rolled)? {
+        Number::Dual2(d) => (d.real, d.gradient1(vars.clone()), d.gradient2(vars)),
+        // no value depends on a node of the curve
+        v => (f64::from(v), Array1::zeros(n), Array2::zeros((n, n))),
+    };
+
+    let mut end = curve_t1.clone();
+    end.set_ad_order(ADOrder::Zero)?;
+    let v1 = f64::from(portfolio_npv(portfolio, &end)?);
+
+    let moves = node_values(&end) - node_values(&rolled);
+
+    let carry = v_rolled - v0;
+    let delta = grad.dot(&moves);
+    let gamma = 0.5 * moves.dot(&hess.dot(&moves));
+    let total = v1 - v0;
+    Ok(PnlExplain {
+        total,
+        carry,
+        delta,
+        gamma,
+        vol: 0.0,
+        residual: total - carry - delta - gamma,
+    })
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, CalType, Convention, Modifier, NamedCal};
+    use crate::curves::{LogLinearInterpolator, Nodes};
+    use crate::instruments::IRS;
+
+    fn curve(initial: NaiveDateTime, dfs: [f64; 3]) -> CurveDF<LogLinearInterpolator, NamedCal> {
+        curve_with_nodes([initial, ndt(2027, 1, 1), ndt(2032, 1, 1)], dfs)
+    }
+
+    fn curve_with_nodes(
+        dates: [NaiveDateTime; 3],
+        dfs: [f64; 3],
+    ) -> CurveDF<LogLinearInterpolator, NamedCal> {
+        let nodes = Nodes::F64(IndexMap::from_iter(dates.into_iter().zip(dfs)));
+        CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            "sofr",
+            Convention::Act360,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn portfolio() -> Vec<InstrumentType> {
+        let cal = CalType::NamedCal(NamedCal::try_new("all").unwrap());
+        vec![InstrumentType::IRS(
+            IRS::try_new(
+                ndt(2022, 1, 1),
+                ndt(2030, 1, 1),
+                Some(12),
+                1e6,
+                2.5,
+                Some(Convention::Act360),
+                Some(&cal),
+                Some(Modifier::ModF),
+                Some(0),
+                None,
+            )
+            .unwrap(),
+        )]
+    }
+
+    #[test]
+    fn test_unchanged_forwards_is_carry_only() {
+        let t0 = curve(ndt(2022, 1, 1), [1.0, 0.88, 0.74]);
+        let t1 = rolled_curve(&t0, &curve(ndt(2022, 1, 15), [1.0, 0.9, 0.8])).unwrap();
+        let report = pnl_explain(&portfolio(), &t0, &t1).unwrap();
+        assert!(report.delta.abs() < 1e-9);
+        assert!(report.gamma.abs() < 1e-9);
+        assert!((report.carry - report.total).abs() < 1e-9);
+        assert!(report.residual.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_empty_and_matured_portfolios() {
+        let t0 = curve(ndt(2022, 1, 1), [1.0, 0.88, 0.74]);
+        let t1 = curve(ndt(2022, 1, 15), [1.0, 0.875, 0.735]);
+        let report = pnl_explain(&[], &t0, &t1).unwrap();
+        assert_eq!(report.total, 0.0);
+        assert_eq!(
+            (report.delta, report.gamma, report.residual),
+            (0.0, 0.0, 0.0)
+        );
+
+        // every cashflow is paid before the initial node of the rolled curve
+        let dates = |initial| [initial, ndt(2031, 1, 1), ndt(2032, 1, 1)];
+        let t0 = curve_with_nodes(dates(ndt(2029, 12, 1)), [1.0, 0.97, 0.95]);
+        let t1 = curve_with_nodes(dates(ndt(2030, 6, 1)), [1.0, 0.98, 0.96]);
+        let report = pnl_explain(&portfolio(), &t0, &t1).unwrap();
+        assert_eq!((report.delta, report.gamma), (0.0, 0.0));
+        let sum = report.carry + report.delta + report.gamma + report.vol + report.residual;
+        assert!((sum - report.total).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_explain_sums_to_total_with_small_residual() {
+        let t0 = curve(ndt(2022, 1, 1), [1.0, 0.88, 0.74]);
+        let t1 = curve(ndt(2022, 1, 15), [1.0, 0.875, 0.735]);
+        let report = pnl_explain(&portfolio(), &t0, &t1).unwrap();
+        let sum = report.carry + report.delta + report.gamma + report.vol + report.residual;
+        assert!((sum - report.total).abs() < 1e-9);
+        assert!(report.delta.abs() > 1000.0);
+        assert!(report.residual.abs() < 1e-3 * report.delta.abs());
+    }
+}
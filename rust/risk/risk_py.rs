@@ -0,0 +1,199 @@
+//! Wrapper module to export Rust risk functions to Python using pyo3 bindings.
+
+use crate::curves::curve_py::Curve;
+use crate::dual::Dual;
+use crate::instruments::InstrumentType;
+use crate::risk::pnl::{pnl_explain, PnlExplain};
+use crate::risk::var::historical_var;
+use crate::risk::{keyrate_ladder, to_quote_space};
+use indexmap::IndexMap;
+use numpy::{PyArray1, PyArray2, PyArrayMethods, ToPyArray};
+use pyo3::prelude::*;
+
+/// Group the derivatives of a dual number by the nodes of a curve into a key-rate ladder.
+///
+/// Parameters
+/// ----------
+/// dual: Dual
+///     The dual number, e.g. an *npv*, containing sensitivities to the curve's variables.
+/// curve: Curve
+///     The curve whose ``id`` tags the variables and whose node dates determine node tenors.
+/// tenor_labels: list[str]
+///     The ascending tenor buckets of the ladder, e.g. ``["1Y", "2Y", "5Y", "10Y"]``.
+///
+/// Returns
+/// -------
+/// tuple of list[str] and ndarray
+///
+/// Notes
+/// -----
+/// Each node sensitivity is allocated to its two adjacent buckets by linear interpolation on
+/// the year fraction of the node date from the initial node date. Nodes beyond the first or last
+/// bucket are allocated entirely to that bucket.
+#[pyfunction]
+#[pyo3(name = "keyrate_ladder")]
+pub(crate) fn keyrate_ladder_py<'py>(
+    py: Python<'py>,
+    dual: Dual,
+    curve: &Curve,
+    tenor_labels: Vec<String>,
+) -> PyResult<(Vec<String>, Bound<'py, PyArray1<f64>>)> {
+    let keys = curve.inner.nodes.keys();
+    let node_tenors: Vec<f64> = keys
+        .iter()
+        .map(|k| (k - keys[0]) as f64 / (365.0 * 86400.0))
+        .collect();
+    let ladder = keyrate_ladder(&dual, &curve.inner.id, &node_tenors, &tenor_labels)?;
+    Ok((tenor_labels, ladder.to_pyarray_bound(py)))
+}
+
+/// Transform the derivatives of a dual number from curve variables into market quotes.
+///
+/// Parameters
+/// ----------
+/// dual: Dual
+///     The dual number, e.g. an *npv*, containing sensitivities to the curve variables.
+/// jacobian: ndarray
+///     A 2D array of the derivatives of each quote (rows) to each variable (columns), e.g. of
+///     the rates of the instruments calibrating the curves.
+/// vars: list[str]
+///     The variables of the columns of the ``jacobian``.
+/// quote_labels: list[str]
+///     The labels of the rows of the ``jacobian``.
+///
+/// Returns
+/// -------
+/// dict of str to float
+///
+/// Notes
+/// -----
+/// The sensitivities to the quotes, :math:`x`, solve :math:`J^T x = g` where :math:`g` are the
+/// derivatives of the ``dual`` to ``vars``, by least squares if there are more variables than
+/// quotes. Sensitivities are measured per unit of each quote. Raises if any is not finite, e.g.
+/// from a singular ``jacobian``.
+#[pyfunction]
+#[pyo3(name = "to_quote_space")]
+pub(crate) fn to_quote_space_py<'py>(
+    dual: Dual,
+    jacobian: &Bound<'py, PyArray2<f64>>,
+    vars: Vec<String>,
+    quote_labels: Vec<String>,
+) -> PyResult<IndexMap<String, f64>> {
+    let jacobian = jacobian.to_owned_array();
+    to_quote_space(&dual, &jacobian.view(), &vars, &quote_labels)
+}
+
+/// Revalue a portfolio under historical scenarios of curve node moves.
+///
+/// Parameters
+/// ----------
+/// portfolio: list[IRS, Spread or Fly]
+///     The instruments of the portfolio.
+/// curve: Curve
+///     The base curve.
+/// scenarios: ndarray
+///     A 2D array with a row for each scenario and a column for each node of the curve, shifting
+///     the continuously compounded zero rate of each node by a number of basis points.
+/// confidence: float, optional
+///     The confidence level of the VaR and expected shortfall.
+///
+/// Returns
+/// -------
+/// tuple of ndarray, float and float
+///
+/// Notes
+/// -----
+/// Returns the P&L of each scenario relative to the base curve, and the VaR and expected
+/// shortfall of the worst ``n - floor(confidence * n)`` outcomes, as positive losses. Scenarios
+/// are revalued in parallel without AD.
+#[pyfunction]
+#[pyo3(name = "historical_var", signature = (portfolio, curve, scenarios, confidence=0.99))]
+pub(crate) fn historical_var_py<'py>(
+    py: Python<'py>,
+    portfolio: Vec<InstrumentType>,
+    curve: &Curve,
+    scenarios: &Bound<'py, PyArray2<f64>>,
+    confidence: f64,
+) -> PyResult<(Bound<'py, PyArray1<f64>>, f64, f64)> {
+    let scenarios = scenarios.to_owned_array();
+    let report =
+        py.allow_threads(|| historical_var(&portfolio, &curve.inner, &scenarios, confidence))?;
+    Ok((report.pnl.to_pyarray_bound(py), report.var, report.es))
+}
+
+#[pymethods]
+impl PnlExplain {
+    /// The total P&L.
+    #[getter]
+    fn total(&self) -> f64 {
+        self.total
+    }
+
+    /// The P&L of rolling the market to the later date with forwards unchanged.
+    #[getter]
+    fn carry(&self) -> f64 {
+        self.carry
+    }
+
+    /// The P&L attributed to the first order sensitivities to curve nodes.
+    #[getter]
+    fn delta(&self) -> f64 {
+        self.delta
+    }
+
+    /// The P&L attributed to the second order sensitivities to curve nodes.
+    #[getter]
+    fn gamma(&self) -> f64 {
+        self.gamma
+    }
+
+    /// The P&L attributed to volatility.
+    #[getter]
+    fn vol(&self) -> f64 {
+        self.vol
+    }
+
+    /// The P&L not explained by the other components.
+    #[getter]
+    fn residual(&self) -> f64 {
+        self.residual
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<rl.PnlExplain total={:.2} carry={:.2} delta={:.2} gamma={:.2} vol={:.2} residual={:.2}>",
+            self.total, self.carry, self.delta, self.gamma, self.vol, self.residual
+        )
+    }
+}
+
+/// Explain the P&L of a portfolio between two market states.
+///
+/// Parameters
+/// ----------
+/// portfolio: list[IRS, Spread or Fly]
+///     The instruments of the portfolio.
+/// curve_t0: Curve
+///     The curve of the initial market state.
+/// curve_t1: Curve
+///     The curve of the final market state, with the same ``id``.
+///
+/// Returns
+/// -------
+/// PnlExplain
+///
+/// Notes
+/// -----
+/// The initial market is first rolled to the initial node of the final market with forward
+/// discount factors unchanged, giving the carry. The change of each node from the rolled market
+/// to the final market is attributed to delta and gamma by the sensitivities of the portfolio at
+/// the rolled market. Any remainder is residual.
+#[pyfunction]
+#[pyo3(name = "pnl_explain")]
+pub(crate) fn pnl_explain_py(
+    portfolio: Vec<InstrumentType>,
+    curve_t0: &Curve,
+    curve_t1: &Curve,
+) -> PyResult<PnlExplain> {
+    pnl_explain(&portfolio, &curve_t0.inner, &curve_t1.inner)
+}
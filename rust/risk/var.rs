@@ -0,0 +1,234 @@
+//! Historical value-at-risk of a portfolio from scenarios of curve node moves.
+//!
+//! Each scenario shifts the continuously compounded zero rate of every node of a base curve by a
+//! number of basis points. The portfolio is revalued under every scenario, in parallel, with
+//! curves carrying no AD order, so that only `f64` arithmetic is performed.
+
+use crate::calendars::DateRoll;
+use crate::curves::nodes::NodesTimestamp;
+use crate::curves::{CurveDF, CurveInterpolation};
+use crate::dual::ADOrder;
+use crate::instruments::{CurveInstrument, InstrumentType};
+use crate::state::next_state_id;
+use indexmap::IndexMap;
+use ndarray::{Array2, ArrayView1};
+use pyo3::exceptions::PyValueError;
+use pyo3::PyErr;
+use std::num::NonZeroUsize;
+use std::thread;
+
+/// The P&L of a portfolio under each scenario, with the VaR and expected shortfall of its losses.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VarReport {
+    pub pnl: Vec<f64>,
+    pub var: f64,
+    pub es: f64,
+}
+
+/// Return a copy of a curve, without AD order, with the zero rate of each node shifted by a
+/// number of basis points.
+pub fn bumped_curve<T, U>(
+    curve: &CurveDF<T, U>,
+    bumps: ArrayView1<f64>,
+) -> Result<CurveDF<T, U>, PyErr>
+where
+    T: CurveInterpolation + Clone,
+    U: DateRoll + Clone,
+{
+    let keys = curve.nodes.keys();
+    if bumps.len() != keys.len() {
+        return Err(PyValueError::new_err(
+            "Each scenario must have a bump for every node of the curve.",
+        ));
+    }
+    let mut bumped = curve.clone();
+    bumped.set_ad_order(ADOrder::Zero)?;
+    let NodesTimestamp::F64(nodes) = &bumped.nodes else {
+        unreachable!("a curve without AD order has f64 nodes")
+    };
+    bumped.nodes = NodesTimestamp::F64(IndexMap::from_iter(nodes.iter().zip(bumps).map(
+        |((k, v), b)| {
+            let t = (k - keys[0]) as f64 / (365.0 * 86400.0);
+            (*k, v * (-b / 10000.0 * t).exp())
+        },
+    )));
+    bumped.state_id = next_state_id();
+    Ok(bumped)
+}
+
+fn portfolio_npv<T: CurveInterpolation, U: DateRoll>(
+    portfolio: &[InstrumentType],
+    curve: &CurveDF<T, U>,
+) -> Result<f64, PyErr> {
+    let mut npv = 0.0;
+    for instrument in portfolio {
+        npv += f64::from(instrument.npv(curve)?);
+    }
+    Ok(npv)
+}
+
+/// Return the P&L of a portfolio under each scenario, being each row of `scenarios`, relative to
+/// its value on the base curve.
+///
+/// Scenarios are revalued in parallel over the available threads.
+pub fn scenario_pnl<T, U>(
+    portfolio: &[InstrumentType],
+    curve: &CurveDF<T, U>,
+    scenarios: &Array2<f64>,
+) -> Result<Vec<f64>, PyErr>
+where
+    T: CurveInterpolation + Clone + Sync,
+    U: DateRoll + Clone + Sync,
+{
+    let mut base = curve.clone();
+    base.set_ad_order(ADOrder::Zero)?;
+    let base_npv = portfolio_npv(portfolio, &base)?;
+
+    let n_threads = thread::available_parallelism()
+        .map(NonZeroUsize::get)
+        .unwrap_or(1);
+    let chunk = scenarios.nrows().div_ceil(n_threads).max(1);
+    let rows: Vec<ArrayView1<f64>> = scenarios.rows().into_iter().collect();
+    thread::scope(|s| {
+        let handles: Vec<_> = rows
+            .chunks(chunk)
+            .map(|rows| {
+                s.spawn(move || {
+                    rows.iter()
+                        .map(|bumps| {
+                            Ok(portfolio_npv(portfolio, &bumped_curve(curve, *bumps)?)? - base_npv)
+                        })
+                        .collect::<Result<Vec<f64>, PyErr>>()
+                })
+            })
+            .collect();
+        let mut pnl = Vec::with_capacity(scenarios.nrows());
+        for handle in handles {
+            pnl.extend(handle.join().expect("scenario revaluation panicked")?);
+        }
+        Ok(pnl)
+    })
+}
+
+/// Return the VaR and expected shortfall of a P&L vector at a confidence level.
+///
+/// The tail contains the worst `n - floor(confidence * n)` outcomes. VaR is the loss of the best
+/// outcome in the tail and expected shortfall is the average loss of the tail, both expressed as
+/// positive numbers for losses.
+pub fn var_and_es(pnl: &[f64], confidence: f64) -> Result<(f64, f64), PyErr> {
+    if pnl.is_empty() {
+        return Err(PyValueError::new_err(
+            "`pnl` must contain at least one outcome.",
+        ));
+    }
+    if !(0.0..1.0).contains(&confidence) {
+        return Err(PyValueError::new_err("`confidence` must be in [0, 1)."));
+    }
+    let mut sorted = pnl.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let n = sorted.len();
+    let k = (n - (confidence * n as f64).floor() as usize).max(1);
+    let tail = &sorted[..k];
+    Ok((-tail[k - 1], -tail.iter().sum::<f64>() / k as f64))
+}
+
+/// Revalue a portfolio under historical scenarios of curve node moves and return the P&L, VaR
+/// and expected shortfall.
+pub fn historical_var<T, U>(
+    portfolio: &[InstrumentType],
+    curve: &CurveDF<T, U>,
+    scenarios: &Array2<f64>,
+    confidence: f64,
+) -> Result<VarReport, PyErr>
+where
+    T: CurveInterpolation + Clone + Sync,
+    U: DateRoll + Clone + Sync,
+{
+    let pnl = scenario_pnl(portfolio, curve, scenarios)?;
+    let (var, es) = var_and_es(&pnl, confidence)?;
+    Ok(VarReport { pnl, var, es })
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, CalType, Convention, Modifier, NamedCal};
+    use crate::curves::{LogLinearInterpolator, Nodes};
+    use crate::instruments::IRS;
+    use ndarray::{arr1, Array2};
+
+    fn curve() -> CurveDF<LogLinearInterpolator, NamedCal> {
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2022, 1, 1), 1.0_f64),
+            (ndt(2027, 1, 1), 0.88_f64),
+            (ndt(2032, 1, 1), 0.74_f64),
+        ]));
+        let mut curve = CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            "sofr",
+            Convention::Act360,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap();
+        curve.set_ad_order(ADOrder::One).unwrap();
+        curve
+    }
+
+    fn portfolio() -> Vec<InstrumentType> {
+        let cal = CalType::NamedCal(NamedCal::try_new("all").unwrap());
+        vec![InstrumentType::IRS(
+            IRS::try_new(
+                ndt(2022, 1, 1),
+                ndt(2030, 1, 1),
+                Some(12),
+                1e6,
+                2.5,
+                Some(Convention::Act360),
+                Some(&cal),
+                Some(Modifier::ModF),
+                Some(0),
+                None,
+            )
+            .unwrap(),
+        )]
+    }
+
+    #[test]
+    fn test_bumped_curve() {
+        let bumped = bumped_curve(&curve(), arr1(&[0.0, 10.0, 0.0]).view()).unwrap();
+        let df = f64::from(bumped.interpolated_value(&ndt(2027, 1, 1)));
+        assert!((df - 0.88 * (-0.001_f64 * 1826.0 / 365.0).exp()).abs() < 1e-14);
+        assert_eq!(bumped.ad(), ADOrder::Zero);
+    }
+
+    #[test]
+    fn test_scenario_pnl() {
+        let scenarios = Array2::from_shape_fn((25, 3), |(i, j)| (i as f64 - 12.0) * j as f64);
+        let pnl = scenario_pnl(&portfolio(), &curve(), &scenarios).unwrap();
+        assert_eq!(pnl.len(), 25);
+        // the payer swap gains when rates rise
+        assert!(pnl[0] < 0.0 && pnl[24] > 0.0);
+        assert!(pnl[12].abs() < 1e-8);
+        for (i, row) in scenarios.rows().into_iter().enumerate() {
+            let expected = portfolio()[0]
+                .npv(&bumped_curve(&curve(), row).unwrap())
+                .unwrap();
+            let base = portfolio()[0].npv(&curve()).unwrap();
+            assert!((pnl[i] - (f64::from(expected) - f64::from(base))).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_var_and_es() {
+        let pnl: Vec<f64> = (1..=100).map(|i| i as f64 - 50.0).collect();
+        let (var, es) = var_and_es(&pnl, 0.95).unwrap();
+        assert_eq!(var, 45.0);
+        assert_eq!(es, 47.0);
+        assert!(var_and_es(&[], 0.95).is_err());
+        assert!(var_and_es(&pnl, 1.0).is_err());
+    }
+}
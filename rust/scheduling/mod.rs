@@ -0,0 +1,375 @@
+//! Generation of the unadjusted dates of regular schedules.
+//!
+//! A regular schedule has periods of a whole number of months between an `effective` and a
+//! `termination` date, each falling on the same [RollDay]. The roll day is often implied by the
+//! dates themselves, which [infer_roll] determines. A period which is not a whole number of
+//! regular periods is split by [split_period] with an irregular stub at either end.
+
+use crate::calendars::{get_roll, Cal, DateRoll, Modifier, RollDay};
+use chrono::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, PyErr};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+pub(crate) mod scheduling_py;
+
+/// The reason a [RollDay] cannot be inferred from schedule dates.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RollInferenceError {
+    /// The dates are not separated by a positive whole number of regular periods.
+    IrregularPeriod,
+    /// No roll day is consistent with both dates.
+    Inconsistent,
+    /// More than one roll day is consistent with both dates but they generate different
+    /// schedules.
+    Ambiguous(Vec<RollDay>),
+}
+
+impl fmt::Display for RollInferenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RollInferenceError::IrregularPeriod => write!(
+                f,
+                "`effective` and `termination` must be a whole number of regular periods apart."
+            ),
+            RollInferenceError::Inconsistent => write!(
+                f,
+                "`effective` and `termination` do not share a consistent roll day."
+            ),
+            RollInferenceError::Ambiguous(rolls) => write!(
+                f,
+                "`effective` and `termination` are consistent with multiple roll days: {:?}.",
+                rolls
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RollInferenceError {}
+
+impl From<RollInferenceError> for PyErr {
+    fn from(err: RollInferenceError) -> Self {
+        PyValueError::new_err(err.to_string())
+    }
+}
+
+/// The placement of the irregular period of a schedule which is not a whole number of regular
+/// periods.
+#[pyclass(module = "rateslib.rs", eq, eq_int)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Stub {
+    /// A first period shorter than a regular period.
+    ShortFront,
+    /// A first period longer than a regular period, combining a short stub with the next period.
+    LongFront,
+    /// A final period shorter than a regular period.
+    ShortBack,
+    /// A final period longer than a regular period, combining a short stub with the previous
+    /// period.
+    LongBack,
+}
+
+impl FromStr for Stub {
+    type Err = PyErr;
+
+    /// Parse a stub from `"SHORTFRONT"`, `"LONGFRONT"`, `"SHORTBACK"` or `"LONGBACK"`, case
+    /// insensitive.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_uppercase().as_str() {
+            "SHORTFRONT" => Ok(Stub::ShortFront),
+            "LONGFRONT" => Ok(Stub::LongFront),
+            "SHORTBACK" => Ok(Stub::ShortBack),
+            "LONGBACK" => Ok(Stub::LongBack),
+            _ => Err(PyValueError::new_err(format!(
+                "'{}' is not a valid stub, expected one of: 'SHORTFRONT', 'LONGFRONT', \
+                 'SHORTBACK', 'LONGBACK'.",
+                s
+            ))),
+        }
+    }
+}
+
+/// Return the number of calendar months from the month of `start` to the month of `end`.
+fn months_between(start: &NaiveDateTime, end: &NaiveDateTime) -> i32 {
+    (end.year() - start.year()) * 12 + end.month() as i32 - start.month() as i32
+}
+
+/// Return the roll dates of each period of the schedule from `effective` to `termination`.
+fn roll_dates(
+    effective: &NaiveDateTime,
+    periods: i32,
+    frequency_months: i32,
+    roll: &RollDay,
+) -> Vec<NaiveDateTime> {
+    (0..=periods)
+        .map(|k| {
+            let month = effective.month0() as i32 + k * frequency_months;
+            get_roll(
+                effective.year() + month.div_euclid(12),
+                month.rem_euclid(12) as u32 + 1,
+                roll,
+            )
+            .unwrap()
+        })
+        .collect()
+}
+
+/// Infer the [RollDay] of a regular schedule from its `effective` and `termination` dates.
+///
+/// Every roll day that generates both dates is a candidate, in preference order `IMM`, `EoM`
+/// and then each day of the month. Candidates that generate the same schedule are equivalent,
+/// e.g. `EoM` and `Int{31}`, and the preferred one is returned. Otherwise the inference is
+/// ambiguous, e.g. from 28th February to 30th April with monthly periods either the 30th or the
+/// end of the month is a valid roll for the intermediate 31st March.
+pub fn infer_roll(
+    effective: &NaiveDateTime,
+    termination: &NaiveDateTime,
+    frequency_months: i32,
+) -> Result<RollDay, RollInferenceError> {
+    let months = months_between(effective, termination);
+    if frequency_months < 1 || months < 1 || months % frequency_months != 0 {
+        return Err(RollInferenceError::IrregularPeriod);
+    }
+    let periods = months / frequency_months;
+
+    let candidates = [RollDay::IMM {}, RollDay::EoM {}]
+        .into_iter()
+        .chain((1..=31).map(|day| RollDay::Int { day }));
+    let mut schedules: Vec<(RollDay, Vec<NaiveDateTime>)> = vec![];
+    for roll in candidates {
+        let dates = roll_dates(effective, periods, frequency_months, &roll);
+        if dates[0] != *effective || dates[dates.len() - 1] != *termination {
+            continue;
+        }
+        if !schedules.iter().any(|(_, s)| *s == dates) {
+            schedules.push((roll, dates));
+        }
+    }
+    match schedules.len() {
+        0 => Err(RollInferenceError::Inconsistent),
+        1 => Ok(schedules[0].0),
+        _ => Err(RollInferenceError::Ambiguous(
+            schedules.into_iter().map(|(r, _)| r).collect(),
+        )),
+    }
+}
+
+/// Add a number of months to a date on a roll day, without adjustment for business days.
+pub(crate) fn unadjusted_add_months(
+    date: &NaiveDateTime,
+    months: i32,
+    roll: &RollDay,
+) -> NaiveDateTime {
+    Cal::new(vec![], vec![]).add_months(date, months, &Modifier::Act, roll, false)
+}
+
+/// Add a tenor, e.g. *"10D"*, *"2W"*, *"6M"* or *"1Y"*, to a date without adjustment for
+/// business days. Months and years roll on the day of the month of `date`.
+pub fn add_tenor(date: &NaiveDateTime, tenor: &str) -> Result<NaiveDateTime, PyErr> {
+    let tenor = tenor.trim().to_uppercase();
+    let err = || PyValueError::new_err(format!("`tenor` could not be parsed: '{}'.", tenor));
+    if tenor.len() < 2 {
+        return Err(err());
+    }
+    let (value, unit) = tenor.split_at(tenor.len() - 1);
+    let value: i32 = value.parse().map_err(|_| err())?;
+    let roll = RollDay::Int { day: date.day() };
+    match unit {
+        "D" => Ok(*date + chrono::Duration::days(value.into())),
+        "W" => Ok(*date + chrono::Duration::weeks(value.into())),
+        "M" => Ok(unadjusted_add_months(date, value, &roll)),
+        "Y" => Ok(unadjusted_add_months(date, 12 * value, &roll)),
+        _ => Err(err()),
+    }
+}
+
+/// Return the boundaries of the periods from `start` to `end`, which must be before it.
+///
+/// Front stubs roll backwards from `end` on its day of the month and back stubs roll forwards
+/// from `start` on its day of the month. A long stub merges a short stub with its adjacent
+/// regular period, so there is no stub when the dates are a whole number of periods apart.
+pub(crate) fn split_dates(
+    start: &NaiveDateTime,
+    end: &NaiveDateTime,
+    frequency_months: i32,
+    stub: Stub,
+) -> Vec<NaiveDateTime> {
+    let (anchor, limit, step) = match stub {
+        Stub::ShortFront | Stub::LongFront => (end, start, -frequency_months),
+        Stub::ShortBack | Stub::LongBack => (start, end, frequency_months),
+    };
+    let roll = RollDay::Int { day: anchor.day() };
+    let beyond = |d: &NaiveDateTime| match step < 0 {
+        true => d <= limit,
+        false => d >= limit,
+    };
+    let mut dates = vec![*anchor];
+    let mut k = 1;
+    loop {
+        let date = unadjusted_add_months(anchor, k * step, &roll);
+        if beyond(&date) {
+            if date != *limit && dates.len() > 1 && matches!(stub, Stub::LongFront | Stub::LongBack)
+            {
+                dates.pop();
+            }
+            dates.push(*limit);
+            break;
+        }
+        dates.push(date);
+        k += 1;
+    }
+    if step < 0 {
+        dates.reverse();
+    }
+    dates
+}
+
+/// Split the period from `start` to `end` into regular periods of `frequency_months`, with any
+/// irregular period placed as `stub`, returning the unadjusted period boundaries.
+///
+/// # Errors
+///
+/// If `start` is not before `end` or `frequency_months` is not positive.
+pub fn split_period(
+    start: &NaiveDateTime,
+    end: &NaiveDateTime,
+    frequency_months: i32,
+    stub: Stub,
+) -> Result<Vec<NaiveDateTime>, PyErr> {
+    if start >= end {
+        return Err(PyValueError::new_err("`start` must be before `end`."));
+    }
+    if frequency_months < 1 {
+        return Err(PyValueError::new_err(
+            "`frequency_months` must be positive.",
+        ));
+    }
+    Ok(split_dates(start, end, frequency_months, stub))
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::ndt;
+
+    #[test]
+    fn test_infer_roll_day() {
+        let result = infer_roll(&ndt(2024, 1, 15), &ndt(2026, 7, 15), 6).unwrap();
+        assert_eq!(result, RollDay::Int { day: 15 });
+        let result = infer_roll(&ndt(2023, 2, 28), &ndt(2024, 2, 28), 3).unwrap();
+        assert_eq!(result, RollDay::Int { day: 28 });
+    }
+
+    #[test]
+    fn test_infer_roll_eom() {
+        let result = infer_roll(&ndt(2024, 2, 29), &ndt(2025, 8, 31), 6).unwrap();
+        assert_eq!(result, RollDay::EoM {});
+        let result = infer_roll(&ndt(2024, 6, 30), &ndt(2024, 12, 31), 6).unwrap();
+        assert_eq!(result, RollDay::EoM {});
+        // a single period has no intermediate dates to distinguish the 30th and end of month
+        let result = infer_roll(&ndt(2023, 2, 28), &ndt(2023, 4, 30), 2).unwrap();
+        assert_eq!(result, RollDay::EoM {});
+    }
+
+    #[test]
+    fn test_infer_roll_imm() {
+        let result = infer_roll(&ndt(2024, 3, 20), &ndt(2025, 3, 19), 3).unwrap();
+        assert_eq!(result, RollDay::IMM {});
+    }
+
+    #[test]
+    fn test_infer_roll_ambiguous() {
+        let result = infer_roll(&ndt(2023, 2, 28), &ndt(2023, 4, 30), 1);
+        assert_eq!(
+            result,
+            Err(RollInferenceError::Ambiguous(vec![
+                RollDay::EoM {},
+                RollDay::Int { day: 30 }
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_split_period_stubs() {
+        let (start, end) = (ndt(2024, 2, 10), ndt(2025, 1, 15));
+        let split = |stub| split_period(&start, &end, 3, stub).unwrap();
+        assert_eq!(
+            split(Stub::ShortFront),
+            vec![
+                start,
+                ndt(2024, 4, 15),
+                ndt(2024, 7, 15),
+                ndt(2024, 10, 15),
+                end
+            ]
+        );
+        assert_eq!(
+            split(Stub::LongFront),
+            vec![start, ndt(2024, 7, 15), ndt(2024, 10, 15), end]
+        );
+        assert_eq!(
+            split(Stub::ShortBack),
+            vec![
+                start,
+                ndt(2024, 5, 10),
+                ndt(2024, 8, 10),
+                ndt(2024, 11, 10),
+                end
+            ]
+        );
+        assert_eq!(
+            split(Stub::LongBack),
+            vec![start, ndt(2024, 5, 10), ndt(2024, 8, 10), end]
+        );
+    }
+
+    #[test]
+    fn test_split_period_regular_and_single() {
+        let regular = vec![ndt(2024, 1, 15), ndt(2024, 7, 15), ndt(2025, 1, 15)];
+        for stub in [
+            Stub::ShortFront,
+            Stub::LongFront,
+            Stub::ShortBack,
+            Stub::LongBack,
+        ] {
+            assert_eq!(
+                split_period(&regular[0], &regular[2], 6, stub).unwrap(),
+                regular
+            );
+            // a period shorter than a regular period is a single stub
+            assert_eq!(
+                split_period(&ndt(2024, 1, 15), &ndt(2024, 3, 1), 6, stub).unwrap(),
+                vec![ndt(2024, 1, 15), ndt(2024, 3, 1)]
+            );
+        }
+        assert!(split_period(&regular[2], &regular[0], 6, Stub::ShortFront).is_err());
+        assert!(split_period(&regular[0], &regular[2], 0, Stub::ShortFront).is_err());
+        assert_eq!(Stub::from_str("longBack").unwrap(), Stub::LongBack);
+        assert!(Stub::from_str("front").is_err());
+    }
+
+    #[test]
+    fn test_add_tenor() {
+        let date = ndt(2024, 1, 31);
+        assert_eq!(add_tenor(&date, "10d").unwrap(), ndt(2024, 2, 10));
+        assert_eq!(add_tenor(&date, "2W").unwrap(), ndt(2024, 2, 14));
+        assert_eq!(add_tenor(&date, "1M").unwrap(), ndt(2024, 2, 29));
+        assert_eq!(add_tenor(&date, "-3M").unwrap(), ndt(2023, 10, 31));
+        assert_eq!(add_tenor(&date, "2Y").unwrap(), ndt(2026, 1, 31));
+        assert!(add_tenor(&date, "1X").is_err());
+        assert!(add_tenor(&date, "M").is_err());
+    }
+
+    #[test]
+    fn test_infer_roll_errors() {
+        let result = infer_roll(&ndt(2024, 1, 15), &ndt(2024, 7, 16), 6);
+        assert_eq!(result, Err(RollInferenceError::Inconsistent));
+        let result = infer_roll(&ndt(2024, 1, 15), &ndt(2024, 8, 15), 6);
+        assert_eq!(result, Err(RollInferenceError::IrregularPeriod));
+        let result = infer_roll(&ndt(2024, 1, 15), &ndt(2024, 1, 15), 6);
+        assert_eq!(result, Err(RollInferenceError::IrregularPeriod));
+    }
+}
@@ -0,0 +1,127 @@
+//! Wrapper module to export Rust scheduling functions to Python using pyo3 bindings.
+
+use crate::calendars::RollDay;
+use crate::dates::dates_py::extract_date;
+use crate::scheduling::{infer_roll, split_period, Stub};
+use bincode::{deserialize, serialize};
+use chrono::NaiveDateTime;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyString};
+use std::str::FromStr;
+
+#[pymethods]
+impl Stub {
+    // Pickling
+    #[new]
+    fn new_py(item: u8) -> PyResult<Stub> {
+        match item {
+            0_u8 => Ok(Stub::ShortFront),
+            1_u8 => Ok(Stub::LongFront),
+            2_u8 => Ok(Stub::ShortBack),
+            3_u8 => Ok(Stub::LongBack),
+            _ => Err(PyValueError::new_err("unreachable code on Stub pickle.")),
+        }
+    }
+    pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
+        *self = deserialize(state.as_bytes()).unwrap();
+        Ok(())
+    }
+    pub fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new_bound(py, &serialize(&self).unwrap()))
+    }
+    pub fn __getnewargs__(&self) -> PyResult<(u8,)> {
+        match self {
+            Stub::ShortFront => Ok((0_u8,)),
+            Stub::LongFront => Ok((1_u8,)),
+            Stub::ShortBack => Ok((2_u8,)),
+            Stub::LongBack => Ok((3_u8,)),
+        }
+    }
+
+    /// Parse a stub from a string.
+    ///
+    /// Parameters
+    /// ----------
+    /// value: str
+    ///     One of *"SHORTFRONT"*, *"LONGFRONT"*, *"SHORTBACK"* or *"LONGBACK"*, case insensitive.
+    ///
+    /// Returns
+    /// -------
+    /// Stub
+    #[staticmethod]
+    #[pyo3(name = "from_str")]
+    fn from_str_py(value: &str) -> PyResult<Stub> {
+        Stub::from_str(value)
+    }
+}
+
+/// Extract a stub given as a `Stub` or as a string, see [Stub::from_str].
+fn extract_stub(obj: &Bound<'_, PyAny>) -> PyResult<Stub> {
+    match obj.downcast::<PyString>() {
+        Ok(s) => Stub::from_str(&s.to_cow()?),
+        Err(_) => obj.extract::<Stub>(),
+    }
+}
+
+/// Infer the roll day of a regular schedule from its effective and termination dates.
+///
+/// Parameters
+/// ----------
+/// effective: datetime
+///     The unadjusted effective date of the schedule.
+/// termination: datetime
+///     The unadjusted termination date of the schedule.
+/// frequency: int
+///     The number of months in a regular period.
+///
+/// Returns
+/// -------
+/// RollDay
+///
+/// Raises
+/// ------
+/// ValueError
+///     If the dates are not separated by regular periods, do not share a roll day, or are
+///     consistent with roll days generating different schedules.
+#[pyfunction]
+#[pyo3(name = "infer_roll")]
+pub(crate) fn infer_roll_py(
+    #[pyo3(from_py_with = "extract_date")] effective: NaiveDateTime,
+    #[pyo3(from_py_with = "extract_date")] termination: NaiveDateTime,
+    frequency: i32,
+) -> PyResult<RollDay> {
+    Ok(infer_roll(&effective, &termination, frequency)?)
+}
+
+/// Split a period into regular periods with any irregular stub at the front or back.
+///
+/// Parameters
+/// ----------
+/// start: datetime
+///     The unadjusted start date of the period.
+/// end: datetime
+///     The unadjusted end date of the period.
+/// frequency: int
+///     The number of months in a regular period.
+/// stub: Stub or str
+///     The placement of any irregular period, e.g. *"SHORTFRONT"*.
+///
+/// Returns
+/// -------
+/// list[datetime]
+///
+/// Notes
+/// -----
+/// Front stubs roll backwards from ``end`` on its day of the month and back stubs roll forwards
+/// from ``start``. The dates are unadjusted for business days.
+#[pyfunction]
+#[pyo3(name = "split_period")]
+pub(crate) fn split_period_py(
+    #[pyo3(from_py_with = "extract_date")] start: NaiveDateTime,
+    #[pyo3(from_py_with = "extract_date")] end: NaiveDateTime,
+    frequency: i32,
+    #[pyo3(from_py_with = "extract_stub")] stub: Stub,
+) -> PyResult<Vec<NaiveDateTime>> {
+    split_period(&start, &end, frequency, stub)
+}
@@ -0,0 +1,198 @@
+use crate::calendars::DateRoll;
+use crate::curves::nodes::NodesTimestamp;
+use crate::curves::{CurveDF, CurveInterpolation};
+use crate::dual::{get_variable_tags, ADOrder, Gradient1, Number};
+use crate::instruments::CurveInstrument;
+use crate::logging::{event, span};
+use crate::progress::Progress;
+use crate::solver::{initial_guess, levenberg_marquardt, InitialGuess, SolverResult};
+use crate::state::next_state_id;
+use indexmap::IndexMap;
+use ndarray::{Array1, Array2};
+use pyo3::exceptions::PyValueError;
+use pyo3::PyErr;
+
+/// Return a copy of a curve, without AD order, with the values of every node after the first
+/// replaced by `params`.
+pub(crate) fn with_node_values<T, U>(curve: &CurveDF<T, U>, params: &[f64]) -> CurveDF<T, U>
+where
+    T: CurveInterpolation + Clone,
+    U: DateRoll + Clone,
+{
+    let mut solved = curve.clone();
+    let keys = curve.nodes.keys();
+    let first = f64::from(curve.nodes.get_index(0).unwrap().1);
+    solved.nodes = NodesTimestamp::F64(IndexMap::from_iter(
+        keys.into_iter()
+            .zip(std::iter::once(first).chain(params.iter().cloned())),
+    ));
+    solved.state_id = next_state_id();
+    event!(
+        trace,
+        curve = curve.id.as_str(),
+        "curve rebuilt with trial nodes"
+    );
+    solved
+}
+
+/// Calibrate the nodes of a curve so that the rates of `instruments` equal `rates`.
+///
+/// The value of the initial node is fixed and every other node is solved, from the values given
+/// by `initial`, to minimise the sum of squared rate errors in the quoted units of the
+/// instruments.
+/// The Jacobian of the [SolverResult] has a row for each instrument and a column for each
+/// solved node. The returned curve has the AD order of `curve`. Each iteration is reported to
+/// `progress`.
+///
+/// # Errors
+///
+/// If `instruments` and `rates` differ in length, the curve has fewer than two nodes or the
+/// initial guess cannot be generated, or if `progress` cancels the solve.
+pub fn solve_curve<T, U, I>(
+    curve: &CurveDF<T, U>,
+    instruments: &[I],
+    rates: &[f64],
+    initial: InitialGuess,
+    tol: f64,
+    max_iter: usize,
+    progress: &Progress,
+) -> Result<(CurveDF<T, U>, SolverResult), PyErr>
+where
+    T: CurveInterpolation + Clone,
+    U: DateRoll + Clone,
+    I: CurveInstrument,
+{
+    if instruments.len() != rates.len() || instruments.is_empty() {
+        return Err(PyValueError::new_err(
+            "`instruments` and `rates` must have the same, non-zero, length.",
+        ));
+    }
+    let n = curve.nodes.len();
+    if n < 2 {
+        return Err(PyValueError::new_err(
+            "A curve must have at least two nodes to be solved.",
+        ));
+    }
+    span!(info_span, "solve_curve", curve = curve.id.as_str());
+    let vars = get_variable_tags(&curve.id, n)[1..].to_vec();
+
+    let residuals = |params: &[f64]| -> Result<Array1<f64>, PyErr> {
+        if params.iter().any(|p| *p <= 0.0) {
+            return Ok(Array1::from_elem(rates.len(), f64::INFINITY));
+        }
+        let trial = with_node_values(curve, params);
+        let mut r = Array1::zeros(rates.len());
+        for (i, (instrument, target)) in instruments.iter().zip(rates).enumerate() {
+            r[i] = f64::from(instrument.rate(&trial)?) - target;
+        }
+        Ok(r)
+    };
+    let jacobian = |params: &[f64]| -> Result<(Array1<f64>, Array2<f64>), PyErr> {
+        let mut trial = with_node_values(curve, params);
+        trial.set_ad_order(ADOrder::One)?;
+        let mut r = Array1::zeros(rates.len());
+        let mut jac = Array2::zeros((rates.len(), vars.len()));
+        for (i, (instrument, target)) in instruments.iter().zip(rates).enumerate() {
+            let rate = instrument.rate(&trial)?;
+            r[i] = f64::from(&rate) - target;
+            if let Number::Dual(d) = rate {
+                jac.row_mut(i).assign(&d.gradient1(vars.clone()));
+            }
+        }
+        Ok((r, jac))
+    };
+
+    let initial = initial_guess(curve, instruments, rates, initial)?;
+    let (params, result) =
+        levenberg_marquardt(&initial, residuals, jacobian, tol, max_iter, progress)?;
+    let mut solved = with_node_values(curve, &params);
+    solved.set_ad_order(curve.ad())?;
+    Ok((solved, result))
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, CalType, Convention, Modifier, NamedCal};
+    use crate::curves::{LogLinearInterpolator, Nodes};
+    use crate::instruments::IRS;
+    use chrono::NaiveDateTime;
+
+    fn curve() -> CurveDF<LogLinearInterpolator, NamedCal> {
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2022, 1, 1), 1.0_f64),
+            (ndt(2025, 1, 1), 1.0_f64),
+            (ndt(2032, 1, 1), 1.0_f64),
+        ]));
+        let mut curve = CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            "sofr",
+            Convention::Act360,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap();
+        curve.set_ad_order(ADOrder::One).unwrap();
+        curve
+    }
+
+    fn irs(end: NaiveDateTime) -> IRS {
+        let cal = CalType::NamedCal(NamedCal::try_new("all").unwrap());
+        IRS::try_new(
+            ndt(2022, 1, 1),
+            end,
+            Some(12),
+            1e6,
+            0.0,
+            Some(Convention::Act360),
+            Some(&cal),
+            Some(Modifier::ModF),
+            Some(0),
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_solve_curve() {
+        let instruments = vec![irs(ndt(2025, 1, 1)), irs(ndt(2032, 1, 1))];
+        let rates = [2.5, 3.25];
+        let (solved, result) = solve_curve(
+            &curve(),
+            &instruments,
+            &rates,
+            InitialGuess::Curve,
+            1e-10,
+            50,
+            &Progress::default(),
+        )
+        .unwrap();
+        assert!(result.converged);
+        assert!(result.iterations > 0);
+        assert_eq!(result.jacobian.dim(), (2, 2));
+        assert_eq!(solved.ad(), ADOrder::One);
+        for (instrument, rate) in instruments.iter().zip(rates) {
+            assert!((f64::from(instrument.rate(&solved).unwrap()) - rate).abs() < 1e-9);
+        }
+        // the first swap does not depend on the last node
+        assert_eq!(result.jacobian[[0, 1]], 0.0);
+    }
+
+    #[test]
+    fn test_solve_curve_validation() {
+        let instruments = vec![irs(ndt(2025, 1, 1))];
+        assert!(solve_curve(
+            &curve(),
+            &instruments,
+            &[2.5, 3.0],
+            InitialGuess::Curve,
+            1e-10,
+            50,
+            &Progress::default(),
+        )
+        .is_err());
+    }
+}
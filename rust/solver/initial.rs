@@ -0,0 +1,203 @@
+use crate::calendars::DateRoll;
+use crate::curves::{CurveDF, CurveInterpolation};
+use crate::instruments::CurveInstrument;
+use crate::maths::roots::bracketed_root;
+use crate::solver::curve::with_node_values;
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, PyErr};
+use serde::{Deserialize, Serialize};
+
+/// The bracket of the continuously compounded zero rate of each bootstrapped node.
+const ZERO_RATE_BRACKET: (f64, f64) = (-1.0, 1.0);
+
+/// The method of generating the node values of a curve from which it is solved.
+#[pyclass(module = "rateslib.rs", eq, eq_int)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum InitialGuess {
+    /// The node values of the curve as given.
+    Curve,
+    /// Discount factors of a flat continuously compounded zero rate equal to the average of the
+    /// rates, which are assumed to be in percent.
+    Flat,
+    /// Discount factors bootstrapped node by node, assuming that each instrument in turn
+    /// depends on the nodes up to and including the next node.
+    Bootstrap,
+}
+
+/// The year fraction on Act365F of each node from the initial node.
+fn node_times<T: CurveInterpolation, U: DateRoll>(curve: &CurveDF<T, U>) -> Vec<f64> {
+    let keys = curve.nodes.keys();
+    keys.iter()
+        .map(|k| (k - keys[0]) as f64 / (365.0 * 86400.0))
+        .collect()
+}
+
+/// Return the values of every node after the first from which to solve a curve.
+///
+/// The bootstrap solves, for each node in turn, the zero rate of that node and all later nodes
+/// which reprices its instrument, by [bracketed_root] with secant steps, or leaves the rate of
+/// the instrument if no root is found in the bracket. It is an approximation where
+/// instruments depend on later nodes, but only needs to be close enough to reduce the
+/// iterations of the global solve.
+///
+/// # Errors
+///
+/// If `method` is [InitialGuess::Bootstrap] and there is not one instrument for each node after
+/// the first.
+pub fn initial_guess<T, U, I>(
+    curve: &CurveDF<T, U>,
+    instruments: &[I],
+    rates: &[f64],
+    method: InitialGuess,
+) -> Result<Vec<f64>, PyErr>
+where
+    T: CurveInterpolation + Clone,
+    U: DateRoll + Clone,
+    I: CurveInstrument,
+{
+    let times = node_times(curve);
+    let n = times.len();
+    match method {
+        InitialGuess::Curve => Ok(curve
+            .nodes
+            .iter()
+            .skip(1)
+            .map(|(_, v)| f64::from(v))
+            .collect()),
+        InitialGuess::Flat => {
+            let z = rates.iter().sum::<f64>() / rates.len() as f64 / 100.0;
+            Ok(times[1..].iter().map(|t| (-z * t).exp()).collect())
+        }
+        InitialGuess::Bootstrap => {
+            if instruments.len() != n - 1 {
+                return Err(PyValueError::new_err(
+                    "A bootstrapped initial guess requires one instrument for each node after the first.",
+                ));
+            }
+            let mut values: Vec<f64> = vec![1.0; n - 1];
+            for i in 1..n {
+                // the error of instrument `i - 1` with nodes from `i` at a zero rate of `z`
+                let mut error = |z: f64| -> Result<f64, PyErr> {
+                    for j in i..n {
+                        values[j - 1] = (-z * times[j]).exp();
+                    }
+                    let trial = with_node_values(curve, &values);
+                    Ok(f64::from(instruments[i - 1].rate(&trial)?) - rates[i - 1])
+                };
+                let guess = rates[i - 1] / 100.0;
+                let z = bracketed_root(|z| Ok((error(z)?, None)), guess, ZERO_RATE_BRACKET, 1e-12)
+                    .unwrap_or(guess);
+                error(z)?;
+            }
+            Ok(values)
+        }
+    }
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, CalType, Convention, Modifier, NamedCal};
+    use crate::curves::{LogLinearInterpolator, Nodes};
+    use crate::instruments::IRS;
+    use crate::progress::Progress;
+    use crate::solver::solve_curve;
+    use chrono::NaiveDateTime;
+    use indexmap::IndexMap;
+
+    fn curve() -> CurveDF<LogLinearInterpolator, NamedCal> {
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2022, 1, 1), 1.0_f64),
+            (ndt(2024, 1, 1), 1.0_f64),
+            (ndt(2027, 1, 1), 1.0_f64),
+            (ndt(2032, 1, 1), 1.0_f64),
+        ]));
+        CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            "sofr",
+            Convention::Act360,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn irs(end: NaiveDateTime) -> IRS {
+        let cal = CalType::NamedCal(NamedCal::try_new("all").unwrap());
+        IRS::try_new(
+            ndt(2022, 1, 1),
+            end,
+            Some(12),
+            1e6,
+            0.0,
+            Some(Convention::Act360),
+            Some(&cal),
+            Some(Modifier::ModF),
+            Some(0),
+            None,
+        )
+        .unwrap()
+    }
+
+    fn instruments() -> Vec<IRS> {
+        vec![
+            irs(ndt(2024, 1, 1)),
+            irs(ndt(2027, 1, 1)),
+            irs(ndt(2032, 1, 1)),
+        ]
+    }
+
+    #[test]
+    fn test_flat_guess() {
+        let values = initial_guess(
+            &curve(),
+            &instruments(),
+            &[2.0, 3.0, 4.0],
+            InitialGuess::Flat,
+        )
+        .unwrap();
+        assert!((values[1] - (-0.03 * 1826.0 / 365.0_f64).exp()).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_bootstrap_guess_reprices_sequential_instruments() {
+        let (instruments, rates) = (instruments(), [2.0, 3.0, 4.0]);
+        let values =
+            initial_guess(&curve(), &instruments, &rates, InitialGuess::Bootstrap).unwrap();
+        let guess = with_node_values(&curve(), &values);
+        // each swap matures at a node so the bootstrap is exact
+        for (instrument, rate) in instruments.iter().zip(rates) {
+            assert!((f64::from(instrument.rate(&guess).unwrap()) - rate).abs() < 1e-10);
+        }
+        assert!(initial_guess(
+            &curve(),
+            &instruments[..2],
+            &rates[..2],
+            InitialGuess::Bootstrap
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_bootstrap_reduces_iterations() {
+        let (instruments, rates) = (instruments(), [2.0, 3.0, 4.0]);
+        let solve = |method| {
+            solve_curve(
+                &curve(),
+                &instruments,
+                &rates,
+                method,
+                1e-8,
+                50,
+                &Progress::default(),
+            )
+        };
+        let (_, cold) = solve(InitialGuess::Curve).unwrap();
+        let (_, warm) = solve(InitialGuess::Bootstrap).unwrap();
+        assert!(cold.converged && warm.converged);
+        assert!(warm.iterations < cold.iterations);
+    }
+}
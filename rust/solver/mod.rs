@@ -0,0 +1,278 @@
+//! Calibrate parameters, such as the nodes of curves, to market prices.
+//!
+//! Every solve is performed by Levenberg-Marquardt iteration and returns a [SolverResult]
+//! recording its convergence, the residual norm of each iteration and the final Jacobian, so
+//! that a failed calibration can be inspected rather than only reported.
+
+use crate::dual::linalg::fdsolve;
+use crate::logging::{event, span};
+use crate::progress::Progress;
+use ndarray::{s, Array1, Array2};
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, PyErr};
+use std::time::Instant;
+
+mod curve;
+pub use crate::solver::curve::solve_curve;
+
+mod initial;
+pub use crate::solver::initial::{initial_guess, InitialGuess};
+
+mod multi;
+pub use crate::solver::multi::{solve_curves, BlockInstrument, CalibrationInstrument, CurveBlock};
+
+mod xccy;
+pub use crate::solver::xccy::{solve_xccy_curve, FXSwapPoints};
+
+pub(crate) mod solver_py;
+
+/// The default tolerance of the L2 norm of the residuals of a solve.
+pub const DEFAULT_TOLERANCE: f64 = 1e-10;
+
+/// The default maximum number of iterations of a solve.
+pub const DEFAULT_MAX_ITER: usize = 50;
+
+/// The outcome of a solve, with its iteration history and diagnostics.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolverResult {
+    /// Whether the residual norm reached the tolerance.
+    pub converged: bool,
+    /// The number of accepted iterations.
+    pub iterations: usize,
+    /// The L2 norm of the residuals at the initial parameters and after each iteration.
+    pub residual_norms: Vec<f64>,
+    /// The derivatives of each residual to each parameter at the final parameters.
+    pub jacobian: Array2<f64>,
+    /// The elapsed time of the solve, in seconds.
+    pub time_taken: f64,
+}
+
+impl SolverResult {
+    /// The residual norm at the final parameters.
+    pub fn final_residual_norm(&self) -> f64 {
+        *self.residual_norms.last().unwrap()
+    }
+}
+
+/// Minimise the sum of squared residuals over parameters by Levenberg-Marquardt iteration.
+///
+/// `residuals` returns the residuals at some parameters, and may return non-finite values to
+/// reject parameters outside a valid domain. `jacobian` returns the residuals and their
+/// derivatives to each parameter. Iteration stops when the L2 norm of the residuals is less than
+/// `tol`, after `max_iter` iterations, or when no damped step reduces the residuals, in which
+/// case the result is not converged. Each iteration is reported to `progress` with its residual
+/// norm, which may cancel the solve.
+///
+/// Returns the final parameters and the [SolverResult].
+pub fn levenberg_marquardt<F, G>(
+    initial: &[f64],
+    residuals: F,
+    jacobian: G,
+    tol: f64,
+    max_iter: usize,
+    progress: &Progress,
+) -> Result<(Vec<f64>, SolverResult), PyErr>
+where
+    F: Fn(&[f64]) -> Result<Array1<f64>, PyErr>,
+    G: Fn(&[f64]) -> Result<(Array1<f64>, Array2<f64>), PyErr>,
+{
+    levenberg_marquardt_blocks(
+        initial,
+        &[initial.len()],
+        residuals,
+        jacobian,
+        tol,
+        max_iter,
+        progress,
+    )
+}
+
+/// Minimise the sum of squared residuals over parameters partitioned into consecutive blocks,
+/// of the given `block_sizes`, by Levenberg-Marquardt iteration.
+///
+/// Each parameter is damped in proportion to its diagonal of `J'J`, with a floor relative to the
+/// average diagonal of its own block. This preconditions each block separately, so that a
+/// parameter with little influence on the residuals is regularised on the scale of the other
+/// parameters of its block, e.g. the nodes of the same curve, rather than of every block.
+pub fn levenberg_marquardt_blocks<F, G>(
+    initial: &[f64],
+    block_sizes: &[usize],
+    residuals: F,
+    jacobian: G,
+    tol: f64,
+    max_iter: usize,
+    progress: &Progress,
+) -> Result<(Vec<f64>, SolverResult), PyErr>
+where
+    F: Fn(&[f64]) -> Result<Array1<f64>, PyErr>,
+    G: Fn(&[f64]) -> Result<(Array1<f64>, Array2<f64>), PyErr>,
+{
+    if block_sizes.iter().sum::<usize>() != initial.len() {
+        return Err(PyValueError::new_err(
+            "`block_sizes` must sum to the number of parameters.",
+        ));
+    }
+    span!(info_span, "levenberg_marquardt", parameters = initial.len());
+    let start = Instant::now();
+    let norm = |r: &Array1<f64>| r.dot(r).sqrt();
+    let mut params = initial.to_vec();
+    let mut current = norm(&residuals(&params)?);
+    let mut residual_norms = vec![current];
+    let mut lambda = 1e-3;
+    let mut stalled = false;
+    while current >= tol && residual_norms.len() <= max_iter && !stalled {
+        let (r, jac) = jacobian(&params)?;
+        // solve the damped normal equations (J'J + lambda D) d = -J'r
+        let jtj = jac.t().dot(&jac);
+        let jtr = -jac.t().dot(&r);
+        let mut scale = Vec::with_capacity(params.len());
+        let mut k = 0_usize;
+        for size in block_sizes {
+            let floor = (jtj.diag().slice(s![k..k + size]).mean().unwrap_or(0.0) * 1e-6).max(1e-12);
+            scale.extend((k..k + size).map(|i| jtj[[i, i]].max(floor)));
+            k += size;
+        }
+        loop {
+            let mut damped = jtj.clone();
+            for (k, d) in scale.iter().enumerate() {
+                damped[[k, k]] += lambda * d;
+            }
+            let step = fdsolve(&damped.view(), &jtr.view(), false);
+            let trial: Vec<f64> = params.iter().zip(step.iter()).map(|(p, d)| p + d).collect();
+            let trial_norm = norm(&residuals(&trial)?);
+            if trial_norm < current {
+                params = trial;
+                current = trial_norm;
+                residual_norms.push(current);
+                lambda = (lambda / 10.0).max(1e-12);
+                event!(
+                    debug,
+                    iteration = residual_norms.len() - 1,
+                    residual_norm = current,
+                    lambda,
+                    "iteration"
+                );
+                progress.report(residual_norms.len() - 1, current)?;
+                break;
+            }
+            lambda *= 10.0;
+            if lambda > 1e12 {
+                event!(warn, residual_norm = current, "stalled");
+                stalled = true;
+                break;
+            }
+        }
+    }
+    let (_, jacobian) = jacobian(&params)?;
+    let result = SolverResult {
+        converged: current < tol,
+        iterations: residual_norms.len() - 1,
+        residual_norms,
+        jacobian,
+        time_taken: start.elapsed().as_secs_f64(),
+    };
+    event!(
+        info,
+        converged = result.converged,
+        iterations = result.iterations,
+        residual_norm = current,
+        time_taken = result.time_taken,
+        "finished"
+    );
+    Ok((params, result))
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr2;
+
+    // the residuals of the Rosenbrock function, minimised at (1, 1)
+    fn rosenbrock(x: &[f64]) -> Result<Array1<f64>, PyErr> {
+        Ok(Array1::from_vec(vec![
+            10.0 * (x[1] - x[0] * x[0]),
+            1.0 - x[0],
+        ]))
+    }
+
+    fn rosenbrock_jacobian(x: &[f64]) -> Result<(Array1<f64>, Array2<f64>), PyErr> {
+        Ok((rosenbrock(x)?, arr2(&[[-20.0 * x[0], 10.0], [-1.0, 0.0]])))
+    }
+
+    #[test]
+    fn test_levenberg_marquardt_history() {
+        let (x, result) = levenberg_marquardt(
+            &[-1.2, 1.0],
+            rosenbrock,
+            rosenbrock_jacobian,
+            1e-12,
+            100,
+            &Progress::default(),
+        )
+        .unwrap();
+        assert!(result.converged);
+        assert!((x[0] - 1.0).abs() < 1e-10 && (x[1] - 1.0).abs() < 1e-10);
+        assert_eq!(result.residual_norms.len(), result.iterations + 1);
+        assert!(result.residual_norms.windows(2).all(|w| w[1] < w[0]));
+        assert!(result.final_residual_norm() < 1e-12);
+        assert_eq!(result.jacobian, arr2(&[[-20.0 * x[0], 10.0], [-1.0, 0.0]]));
+    }
+
+    #[test]
+    fn test_levenberg_marquardt_not_converged() {
+        let (_, result) = levenberg_marquardt(
+            &[-1.2, 1.0],
+            rosenbrock,
+            rosenbrock_jacobian,
+            1e-12,
+            2,
+            &Progress::default(),
+        )
+        .unwrap();
+        assert!(!result.converged);
+        assert_eq!(result.iterations, 2);
+        assert!(result.final_residual_norm() > 1e-12);
+    }
+
+    #[test]
+    fn test_levenberg_marquardt_progress_and_cancellation() {
+        let norms = std::sync::Mutex::new(Vec::new());
+        let progress = Progress::new(2).with_callback(|_, norm| {
+            norms.lock().unwrap().push(norm);
+            Ok(())
+        });
+        let (_, result) = levenberg_marquardt(
+            &[-1.2, 1.0],
+            rosenbrock,
+            rosenbrock_jacobian,
+            1e-12,
+            100,
+            &progress,
+        )
+        .unwrap();
+        drop(progress);
+        let expected: Vec<f64> = result
+            .residual_norms
+            .iter()
+            .skip(2)
+            .step_by(2)
+            .cloned()
+            .collect();
+        assert_eq!(norms.into_inner().unwrap(), expected);
+
+        let token = crate::progress::CancellationToken::new();
+        token.cancel();
+        let progress = Progress::default().with_token(token);
+        assert!(levenberg_marquardt(
+            &[-1.2, 1.0],
+            rosenbrock,
+            rosenbrock_jacobian,
+            1e-12,
+            100,
+            &progress
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,379 @@
+use crate::calendars::DateRoll;
+use crate::curves::{CurveDF, CurveInterpolation};
+use crate::dual::{get_variable_tags, ADOrder, Gradient1, Number};
+use crate::instruments::{IRS, SBS};
+use crate::logging::span;
+use crate::progress::Progress;
+use crate::solver::curve::with_node_values;
+use crate::solver::{initial_guess, levenberg_marquardt_blocks, InitialGuess, SolverResult};
+use ndarray::{s, Array1, Array2};
+use pyo3::exceptions::PyValueError;
+use pyo3::PyErr;
+use std::collections::HashSet;
+
+/// An instrument calibrating a [CurveBlock].
+#[derive(Clone)]
+pub enum CalibrationInstrument {
+    /// A swap, with its float leg forecast from the curve of the `forecast` block.
+    IRS(IRS),
+    /// A basis swap, with its first leg forecast from the curve of the `forecast` block and its
+    /// second leg from the curve of the block with the given index.
+    SBS(SBS, usize),
+}
+
+/// An instrument calibrating a [CurveBlock], forecast from one curve and discounted by another,
+/// each identified by the index of its block.
+#[derive(Clone)]
+pub struct BlockInstrument {
+    pub instrument: CalibrationInstrument,
+    pub forecast: usize,
+    pub discount: usize,
+}
+
+impl BlockInstrument {
+    /// The indexes of the blocks of the curves the instrument is priced from.
+    fn blocks(&self) -> Vec<usize> {
+        match &self.instrument {
+            CalibrationInstrument::IRS(_) => vec![self.forecast, self.discount],
+            CalibrationInstrument::SBS(_, forecast2) => {
+                vec![self.forecast, *forecast2, self.discount]
+            }
+        }
+    }
+
+    /// The mid-market rate of the instrument priced from the curves of the blocks, in percent for
+    /// a swap and in basis points for a basis swap.
+    pub fn rate<T: CurveInterpolation, U: DateRoll>(
+        &self,
+        curves: &[CurveDF<T, U>],
+    ) -> Result<Number, PyErr> {
+        match &self.instrument {
+            CalibrationInstrument::IRS(irs) => {
+                irs.rate_discounted(&curves[self.forecast], &curves[self.discount])
+            }
+            CalibrationInstrument::SBS(sbs, forecast2) => sbs.rate(
+                &curves[self.forecast],
+                &curves[*forecast2],
+                &curves[self.discount],
+            ),
+        }
+    }
+}
+
+/// A curve to be solved, with its calibrating instruments and their market rates, in the units
+/// of [BlockInstrument::rate].
+///
+/// The initial guess is generated from the swaps priced from the block's curve alone, so a block
+/// calibrated by any basis swaps must use [InitialGuess::Curve].
+#[derive(Clone)]
+pub struct CurveBlock<T: CurveInterpolation, U: DateRoll> {
+    pub curve: CurveDF<T, U>,
+    pub instruments: Vec<BlockInstrument>,
+    pub rates: Vec<f64>,
+    pub initial: InitialGuess,
+}
+
+/// Calibrate several interdependent curves simultaneously, e.g. a discounting curve and a
+/// projection curve discounted by it.
+///
+/// The parameters are the nodes after the first of each curve, in block order, and the
+/// residuals are the rate errors of each block's instruments, in block order. The Jacobian is
+/// block sparse, since the rows of an instrument only depend on the columns of its forecast and
+/// discount curves, so only those blocks are calculated and every other block is zero. The
+/// damping of each curve's nodes is preconditioned by its own block, see
+/// [levenberg_marquardt_blocks](crate::solver::levenberg_marquardt_blocks).
+///
+/// Returns the solved curves, each with the AD order of its input curve. Each iteration is
+/// reported to `progress`.
+///
+/// # Errors
+///
+/// If no blocks are given, curve ids are not unique, an instrument refers to a block that does not exist, the
+/// instruments and rates of a block differ in length, a curve has fewer than two nodes, a block
+/// with basis swaps has an initial guess other than [InitialGuess::Curve], or `progress` cancels
+/// the solve.
+pub fn solve_curves<T, U>(
+    blocks: &[CurveBlock<T, U>],
+    tol: f64,
+    max_iter: usize,
+    progress: &Progress,
+) -> Result<(Vec<CurveDF<T, U>>, SolverResult), PyErr>
+where
+    T: CurveInterpolation + Clone,
+    U: DateRoll + Clone,
+{
+    if blocks.is_empty() {
+        return Err(PyValueError::new_err(
+            "At least one curve block must be given.",
+        ));
+    }
+    let ids: HashSet<&str> = blocks.iter().map(|b| b.curve.id.as_str()).collect();
+    if ids.len() != blocks.len() {
+        return Err(PyValueError::new_err(
+            "The curves of simultaneously solved blocks must have unique ids.",
+        ));
+    }
+    for block in blocks {
+        if block.instruments.len() != block.rates.len() {
+            return Err(PyValueError::new_err(
+                "The instruments and rates of a block must have the same length.",
+            ));
+        }
+        if block.curve.nodes.len() < 2 {
+            return Err(PyValueError::new_err(
+                "A curve must have at least two nodes to be solved.",
+            ));
+        }
+        if block
+            .instruments
+            .iter()
+            .any(|i| i.blocks().iter().any(|b| *b >= blocks.len()))
+        {
+            return Err(PyValueError::new_err(
+                "An instrument's forecast or discount curve is not one of the blocks.",
+            ));
+        }
+        let has_basis = block
+            .instruments
+            .iter()
+            .any(|i| matches!(i.instrument, CalibrationInstrument::SBS(..)));
+        if has_basis && block.initial != InitialGuess::Curve {
+            return Err(PyValueError::new_err(
+                "A block calibrated by basis swaps must use the initial guess of its curve.",
+            ));
+        }
+    }
+
+    span!(info_span, "solve_curves", curves = blocks.len());
+    // the number of parameters of each curve block and the index of its first parameter
+    let sizes: Vec<usize> = blocks.iter().map(|b| b.curve.nodes.len() - 1).collect();
+    let offsets: Vec<usize> = sizes
+        .iter()
+        .scan(0, |acc, n| {
+            *acc += n;
+            Some(*acc - n)
+        })
+        .collect();
+    let rows: Vec<(&BlockInstrument, f64)> = blocks
+        .iter()
+        .flat_map(|b| b.instruments.iter().zip(b.rates.iter().cloned()))
+        .collect();
+    let vars: Vec<Vec<String>> = blocks
+        .iter()
+        .map(|b| get_variable_tags(&b.curve.id, b.curve.nodes.len())[1..].to_vec())
+        .collect();
+
+    let curves_at = |params: &[f64], ad: ADOrder| -> Result<Vec<CurveDF<T, U>>, PyErr> {
+        let mut curves = Vec::with_capacity(blocks.len());
+        for (b, block) in blocks.iter().enumerate() {
+            let mut curve =
+                with_node_values(&block.curve, &params[offsets[b]..offsets[b] + sizes[b]]);
+            curve.set_ad_order(ad)?;
+            curves.push(curve);
+        }
+        Ok(curves)
+    };
+    let residuals = |params: &[f64]| -> Result<Array1<f64>, PyErr> {
+        if params.iter().any(|p| *p <= 0.0) {
+            return Ok(Array1::from_elem(rows.len(), f64::INFINITY));
+        }
+        let curves = curves_at(params, ADOrder::Zero)?;
+        let mut r = Array1::zeros(rows.len());
+        for (i, (row, target)) in rows.iter().enumerate() {
+            r[i] = f64::from(row.rate(&curves)?) - target;
+        }
+        Ok(r)
+    };
+    let jacobian = |params: &[f64]| -> Result<(Array1<f64>, Array2<f64>), PyErr> {
+        let curves = curves_at(params, ADOrder::One)?;
+        let mut r = Array1::zeros(rows.len());
+        let mut jac = Array2::zeros((rows.len(), params.len()));
+        for (i, (row, target)) in rows.iter().enumerate() {
+            let rate = row.rate(&curves)?;
+            r[i] = f64::from(&rate) - target;
+            let Number::Dual(d) = rate else { continue };
+            for b in row.blocks() {
+                jac.slice_mut(s![i, offsets[b]..offsets[b] + sizes[b]])
+                    .assign(&d.gradient1(vars[b].clone()));
+            }
+        }
+        Ok((r, jac))
+    };
+
+    let mut initial = Vec::with_capacity(sizes.iter().sum());
+    for block in blocks {
+        let instruments: Vec<IRS> = block
+            .instruments
+            .iter()
+            .filter_map(|i| match &i.instrument {
+                CalibrationInstrument::IRS(irs) => Some(irs.clone()),
+                CalibrationInstrument::SBS(..) => None,
+            })
+            .collect();
+        initial.extend(initial_guess(
+            &block.curve,
+            &instruments,
+            &block.rates,
+            block.initial,
+        )?);
+    }
+    let (params, result) = levenberg_marquardt_blocks(
+        &initial, &sizes, residuals, jacobian, tol, max_iter, progress,
+    )?;
+    let mut solved = curves_at(&params, ADOrder::Zero)?;
+    for (curve, block) in solved.iter_mut().zip(blocks) {
+        curve.set_ad_order(block.curve.ad())?;
+    }
+    Ok((solved, result))
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, CalType, Convention, Modifier, NamedCal};
+    use crate::curves::{LogLinearInterpolator, Nodes};
+    use chrono::NaiveDateTime;
+    use indexmap::IndexMap;
+
+    fn curve(id: &str) -> CurveDF<LogLinearInterpolator, NamedCal> {
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2022, 1, 1), 1.0_f64),
+            (ndt(2025, 1, 1), 1.0_f64),
+            (ndt(2032, 1, 1), 1.0_f64),
+        ]));
+        CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            id,
+            Convention::Act360,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn swap(end: NaiveDateTime, forecast: usize, discount: usize) -> BlockInstrument {
+        let cal = CalType::NamedCal(NamedCal::try_new("all").unwrap());
+        BlockInstrument {
+            instrument: CalibrationInstrument::IRS(
+                IRS::try_new(
+                    ndt(2022, 1, 1),
+                    end,
+                    Some(12),
+                    1e6,
+                    0.0,
+                    Some(Convention::Act360),
+                    Some(&cal),
+                    Some(Modifier::ModF),
+                    Some(0),
+                    None,
+                )
+                .unwrap(),
+            ),
+            forecast,
+            discount,
+        }
+    }
+
+    fn blocks() -> Vec<CurveBlock<LogLinearInterpolator, NamedCal>> {
+        vec![
+            CurveBlock {
+                curve: curve("ois"),
+                instruments: vec![swap(ndt(2025, 1, 1), 0, 0), swap(ndt(2032, 1, 1), 0, 0)],
+                rates: vec![2.0, 2.5],
+                initial: InitialGuess::Bootstrap,
+            },
+            CurveBlock {
+                curve: curve("ibor"),
+                instruments: vec![swap(ndt(2025, 1, 1), 1, 0), swap(ndt(2032, 1, 1), 1, 0)],
+                rates: vec![2.3, 2.9],
+                initial: InitialGuess::Flat,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_solve_curves() {
+        let blocks = blocks();
+        let (curves, result) = solve_curves(&blocks, 1e-10, 50, &Progress::default()).unwrap();
+        assert!(result.converged);
+        for block in &blocks {
+            for (row, rate) in block.instruments.iter().zip(&block.rates) {
+                let solved = row.rate(&curves).unwrap();
+                assert!((f64::from(solved) - rate).abs() < 1e-9);
+            }
+        }
+        // the discounting curve does not depend on the projection curve
+        assert!(result
+            .jacobian
+            .slice(s![0..2, 2..4])
+            .iter()
+            .all(|x| *x == 0.0));
+        assert!(result
+            .jacobian
+            .slice(s![2..4, 0..2])
+            .iter()
+            .any(|x| *x != 0.0));
+    }
+
+    fn basis_swap(end: NaiveDateTime, float_spread: f64) -> BlockInstrument {
+        let cal = CalType::NamedCal(NamedCal::try_new("all").unwrap());
+        BlockInstrument {
+            instrument: CalibrationInstrument::SBS(
+                SBS::try_new(
+                    ndt(2022, 1, 1),
+                    end,
+                    (Some(3), Some(12)),
+                    1e6,
+                    float_spread,
+                    Some(Convention::Act360),
+                    Some(&cal),
+                    Some(Modifier::ModF),
+                    Some(0),
+                    None,
+                )
+                .unwrap(),
+                0,
+            ),
+            forecast: 1,
+            discount: 0,
+        }
+    }
+
+    #[test]
+    fn test_solve_curves_with_basis_swaps() {
+        let mut blocks = blocks();
+        blocks[1].instruments = vec![
+            basis_swap(ndt(2025, 1, 1), 0.0),
+            basis_swap(ndt(2032, 1, 1), 0.0),
+        ];
+        blocks[1].rates = vec![-10.0, -15.0];
+        blocks[1].initial = InitialGuess::Curve;
+        let (curves, result) = solve_curves(&blocks, 1e-10, 50, &Progress::default()).unwrap();
+        assert!(result.converged);
+        for (row, rate) in blocks[1].instruments.iter().zip(&blocks[1].rates) {
+            assert!((f64::from(row.rate(&curves).unwrap()) - rate).abs() < 1e-9);
+        }
+        // the IBOR curve is below the OIS curve, forecasting the higher rates
+        let d = ndt(2030, 1, 1);
+        assert!(
+            f64::from(curves[1].interpolated_value(&d))
+                < f64::from(curves[0].interpolated_value(&d))
+        );
+
+        blocks[1].initial = InitialGuess::Bootstrap;
+        assert!(solve_curves(&blocks, 1e-10, 50, &Progress::default()).is_err());
+    }
+
+    #[test]
+    fn test_solve_curves_validation() {
+        let mut bad = blocks();
+        bad[1].instruments[0].discount = 2;
+        assert!(solve_curves(&bad, 1e-10, 50, &Progress::default()).is_err());
+        let mut bad = blocks();
+        bad[1].curve = curve("ois");
+        assert!(solve_curves(&bad, 1e-10, 50, &Progress::default()).is_err());
+    }
+}
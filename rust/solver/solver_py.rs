@@ -0,0 +1,313 @@
+//! Wrapper module to export Rust solver functions to Python using pyo3 bindings.
+
+use crate::config::tolerances;
+use crate::curves::curve_py::Curve;
+use crate::fx::rates::Ccy;
+use crate::instruments::{InstrumentType, IRS, SBS};
+use crate::market::market_py::MarketPy;
+use crate::market::CurveKey;
+use crate::progress::{CancellationToken, Progress};
+use crate::solver::{
+    solve_curve, solve_curves, solve_xccy_curve, BlockInstrument, CalibrationInstrument,
+    CurveBlock, FXSwapPoints, InitialGuess, SolverResult, DEFAULT_MAX_ITER,
+};
+use bincode::{deserialize, serialize};
+use chrono::NaiveDateTime;
+use numpy::{PyArray2, ToPyArray};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+#[pymethods]
+impl InitialGuess {
+    // Pickling
+    #[new]
+    fn new_py(item: u8) -> PyResult<InitialGuess> {
+        match item {
+            0_u8 => Ok(InitialGuess::Curve),
+            1_u8 => Ok(InitialGuess::Flat),
+            2_u8 => Ok(InitialGuess::Bootstrap),
+            _ => Err(PyValueError::new_err(
+                "unreachable code on InitialGuess pickle.",
+            )),
+        }
+    }
+    pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
+        *self = deserialize(state.as_bytes()).unwrap();
+        Ok(())
+    }
+    pub fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new_bound(py, &serialize(&self).unwrap()))
+    }
+    pub fn __getnewargs__(&self) -> PyResult<(u8,)> {
+        match self {
+            InitialGuess::Curve => Ok((0_u8,)),
+            InitialGuess::Flat => Ok((1_u8,)),
+            InitialGuess::Bootstrap => Ok((2_u8,)),
+        }
+    }
+}
+
+#[pymethods]
+impl SolverResult {
+    /// Whether the residual norm reached the tolerance.
+    #[getter]
+    fn converged(&self) -> bool {
+        self.converged
+    }
+
+    /// The number of accepted iterations.
+    #[getter]
+    fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// The L2 norm of the residuals at the initial parameters and after each iteration.
+    #[getter]
+    fn residual_norms(&self) -> Vec<f64> {
+        self.residual_norms.clone()
+    }
+
+    /// The derivatives of each residual to each parameter at the final parameters.
+    #[getter]
+    fn jacobian<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray2<f64>> {
+        self.jacobian.to_pyarray_bound(py)
+    }
+
+    /// The elapsed time of the solve, in seconds.
+    #[getter]
+    fn time_taken(&self) -> f64 {
+        self.time_taken
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<SolverResult: converged={} iterations={} residual={:.3e}>",
+            self.converged,
+            self.iterations,
+            self.final_residual_norm()
+        )
+    }
+}
+
+/// Calibrate the nodes of a curve so that the rates of instruments equal market rates.
+///
+/// Parameters
+/// ----------
+/// curve: Curve
+///     The curve to solve, whose initial node value is fixed.
+/// instruments: list[IRS | Spread | Fly]
+///     The calibrating instruments.
+/// rates: list[float]
+///     The market rate of each instrument, in its quoted units.
+/// initial_guess: InitialGuess, optional
+///     The method of generating the node values from which the curve is solved.
+/// tol: float, optional
+///     The tolerance of the L2 norm of the rate errors, by default the configured solver
+///     tolerance.
+/// max_iter: int, optional
+///     The maximum number of iterations.
+/// callback: Callable[[int, float], Any], optional
+///     A function called with the number of iterations and the residual norm every ``every``
+///     iterations. An exception it raises stops the solve.
+/// every: int, optional
+///     The number of iterations between calls of ``callback`` and checks for cancellation.
+/// token: CancellationToken, optional
+///     A token which cancels the solve, raising a *ValueError*, when cancelled from another
+///     thread. A *KeyboardInterrupt* also cancels the solve.
+///
+/// Returns
+/// -------
+/// tuple[Curve, SolverResult]
+#[pyfunction]
+#[pyo3(name = "solve_curve", signature = (curve, instruments, rates, initial_guess=InitialGuess::Curve, tol=None, max_iter=DEFAULT_MAX_ITER, callback=None, every=1, token=None))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn solve_curve_py(
+    curve: Curve,
+    instruments: Vec<InstrumentType>,
+    rates: Vec<f64>,
+    initial_guess: InitialGuess,
+    tol: Option<f64>,
+    max_iter: usize,
+    callback: Option<PyObject>,
+    every: usize,
+    token: Option<CancellationToken>,
+) -> PyResult<(Curve, SolverResult)> {
+    let progress = Progress::from_py(callback, every, token)?;
+    let (inner, result) = solve_curve(
+        &curve.inner,
+        &instruments,
+        &rates,
+        initial_guess,
+        tol.unwrap_or_else(|| tolerances().solver),
+        max_iter,
+        &progress,
+    )?;
+    Ok((Curve { inner }, result))
+}
+
+/// A calibrating swap with its forecast and discount block indexes, or a basis swap with the
+/// forecast block indexes of each leg and its discount block index.
+#[derive(FromPyObject)]
+pub(crate) enum BlockInstrumentPy {
+    Swap(IRS, usize, usize),
+    BasisSwap(SBS, usize, usize, usize),
+}
+
+impl From<BlockInstrumentPy> for BlockInstrument {
+    fn from(value: BlockInstrumentPy) -> Self {
+        match value {
+            BlockInstrumentPy::Swap(irs, forecast, discount) => BlockInstrument {
+                instrument: CalibrationInstrument::IRS(irs),
+                forecast,
+                discount,
+            },
+            BlockInstrumentPy::BasisSwap(sbs, forecast1, forecast2, discount) => BlockInstrument {
+                instrument: CalibrationInstrument::SBS(sbs, forecast2),
+                forecast: forecast1,
+                discount,
+            },
+        }
+    }
+}
+
+/// A curve, its calibrating instruments with their block indexes, rates and initial guess.
+type CurveBlockPy = (Curve, Vec<BlockInstrumentPy>, Vec<f64>, InitialGuess);
+
+/// Calibrate several interdependent curves simultaneously, e.g. a discounting curve and a
+/// projection curve discounted by it.
+///
+/// Parameters
+/// ----------
+/// blocks: list[tuple[Curve, list[tuple[IRS, int, int] | tuple[SBS, int, int, int]], list[float], InitialGuess]]
+///     Each curve to solve with its calibrating instruments, each given with the indexes of the
+///     blocks of its forecast curves, one for each float leg, and its discount curve, their
+///     market rates, in percent for swaps and basis points for basis swaps, and the method of its
+///     initial guess. A block with basis swaps must use *InitialGuess.Curve*. Curve ids must be
+///     unique.
+/// tol: float, optional
+///     The tolerance of the L2 norm of the rate errors, by default the configured solver
+///     tolerance.
+/// max_iter: int, optional
+///     The maximum number of iterations.
+/// callback: Callable[[int, float], Any], optional
+///     A function called with the number of iterations and the residual norm every ``every``
+///     iterations. An exception it raises stops the solve.
+/// every: int, optional
+///     The number of iterations between calls of ``callback`` and checks for cancellation.
+/// token: CancellationToken, optional
+///     A token which cancels the solve, raising a *ValueError*, when cancelled from another
+///     thread. A *KeyboardInterrupt* also cancels the solve.
+///
+/// Returns
+/// -------
+/// tuple[list[Curve], SolverResult]
+#[pyfunction]
+#[pyo3(name = "solve_curves", signature = (blocks, tol=None, max_iter=DEFAULT_MAX_ITER, callback=None, every=1, token=None))]
+pub(crate) fn solve_curves_py(
+    blocks: Vec<CurveBlockPy>,
+    tol: Option<f64>,
+    max_iter: usize,
+    callback: Option<PyObject>,
+    every: usize,
+    token: Option<CancellationToken>,
+) -> PyResult<(Vec<Curve>, SolverResult)> {
+    let progress = Progress::from_py(callback, every, token)?;
+    let blocks: Vec<CurveBlock<_, _>> = blocks
+        .into_iter()
+        .map(|(curve, instruments, rates, initial)| CurveBlock {
+            curve: curve.inner,
+            instruments: instruments.into_iter().map(BlockInstrument::from).collect(),
+            rates,
+            initial,
+        })
+        .collect();
+    let (curves, result) = solve_curves(
+        &blocks,
+        tol.unwrap_or_else(|| tolerances().solver),
+        max_iter,
+        &progress,
+    )?;
+    Ok((
+        curves.into_iter().map(|inner| Curve { inner }).collect(),
+        result,
+    ))
+}
+
+/// Calibrate the discount curve of a foreign currency collateralised in a domestic currency to
+/// FX swap points, by covered interest parity with the domestic discount curve of a market.
+///
+/// Parameters
+/// ----------
+/// market: Market
+///     The market containing both curves and the FX rate of the pair.
+/// domestic: tuple[Ccy, str]
+///     The currency and index of the domestic discount curve.
+/// foreign: tuple[Ccy, str]
+///     The currency and index of the foreign curve, whose initial node, interpolation and
+///     convention the solved curve takes and whose discount factors are the initial guess.
+/// spot: datetime
+///     The settlement date of the FX rate.
+/// maturities: list[datetime]
+///     The increasing forward settlement dates of the quotes, which are the curve's nodes.
+/// points: list[float]
+///     The FX swap points quoted for each maturity, in units of domestic per unit of foreign.
+/// scale: float, optional
+///     The size of one point in units of the FX rate.
+/// tol: float, optional
+///     The tolerance of the L2 norm of the point errors, by default the configured solver
+///     tolerance.
+/// max_iter: int, optional
+///     The maximum number of iterations.
+/// callback: Callable[[int, float], Any], optional
+///     A function called with the number of iterations and the residual norm every ``every``
+///     iterations. An exception it raises stops the solve.
+/// every: int, optional
+///     The number of iterations between calls of ``callback`` and checks for cancellation.
+/// token: CancellationToken, optional
+///     A token which cancels the solve, raising a *ValueError*, when cancelled from another
+///     thread. A *KeyboardInterrupt* also cancels the solve.
+///
+/// Returns
+/// -------
+/// tuple[Curve, SolverResult]
+///
+/// Notes
+/// -----
+/// The curve has id of the foreign and domestic currencies, e.g. *"eurusd"*, and AD order one
+/// with sensitivities to each quote tagged by the id and the quote's position, e.g.
+/// *"eurusd_pts0"*.
+#[pyfunction]
+#[pyo3(name = "solve_xccy_curve", signature = (market, domestic, foreign, spot, maturities, points, scale=1e-4, tol=None, max_iter=DEFAULT_MAX_ITER, callback=None, every=1, token=None))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn solve_xccy_curve_py(
+    market: &MarketPy,
+    domestic: (Ccy, String),
+    foreign: (Ccy, String),
+    spot: NaiveDateTime,
+    maturities: Vec<NaiveDateTime>,
+    points: Vec<f64>,
+    scale: f64,
+    tol: Option<f64>,
+    max_iter: usize,
+    callback: Option<PyObject>,
+    every: usize,
+    token: Option<CancellationToken>,
+) -> PyResult<(Curve, SolverResult)> {
+    let progress = Progress::from_py(callback, every, token)?;
+    let (inner, result) = solve_xccy_curve(
+        &market.inner,
+        &CurveKey::try_new(domestic.0, &domestic.1)?,
+        &CurveKey::try_new(foreign.0, &foreign.1)?,
+        &FXSwapPoints {
+            spot,
+            maturities,
+            points,
+            scale,
+        },
+        tol.unwrap_or_else(|| tolerances().solver),
+        max_iter,
+        &progress,
+    )?;
+    Ok((Curve { inner }, result))
+}
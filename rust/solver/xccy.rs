@@ -0,0 +1,334 @@
+use crate::calendars::DateRoll;
+use crate::curves::nodes::node_date;
+use crate::curves::{CurveDF, CurveInterpolation, Nodes};
+use crate::dual::linalg::fdsolve;
+use crate::dual::{get_variable_tags, ADOrder, Dual, Gradient1, Number};
+use crate::logging::span;
+use crate::market::{CurveKey, Market};
+use crate::progress::Progress;
+use crate::solver::curve::with_node_values;
+use crate::solver::{levenberg_marquardt, SolverResult};
+use chrono::NaiveDateTime;
+use indexmap::IndexMap;
+use ndarray::{Array1, Array2};
+use pyo3::exceptions::PyValueError;
+use pyo3::PyErr;
+
+/// Market FX swap points of a currency pair, quoted for forward settlement dates against spot.
+///
+/// The outright forward rate settling at `maturities[i]` is the spot rate plus
+/// `points[i] * scale`, e.g. a scale of 0.0001 for pips of EURUSD.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FXSwapPoints {
+    pub spot: NaiveDateTime,
+    pub maturities: Vec<NaiveDateTime>,
+    pub points: Vec<f64>,
+    pub scale: f64,
+}
+
+/// Calibrate the discount curve of cashflows in a foreign currency collateralised in a domestic
+/// currency, to FX swap points of the pair in units of domestic currency per unit of foreign.
+///
+/// The spot rate is the FX rate of the market and the domestic discount curve is the market
+/// curve with key `domestic`. The solved curve has the initial node of the foreign curve, with
+/// key `foreign`, and a node at each maturity, and the interpolation, convention and calendar of
+/// the foreign curve, whose discount factors are the initial guess. Its nodes are solved so that
+/// the forward rates, by covered interest parity,
+///
+/// `F(m) = S * (w(m) / w(spot)) / (v(m) / v(spot))`,
+///
+/// of the solved curve `w` and domestic curve `v` reprice the market forward rates. The returned
+/// curve has AD order one, with sensitivities of its nodes to each of the quoted points, tagged
+/// by the curve id, e.g. `"eurusd_pts0"`, found by a Newton step in [Dual] arithmetic from the
+/// solution. Its id is the concatenation of the foreign and domestic currencies.
+///
+/// # Errors
+///
+/// If the curves or the FX rate are not in the market, the currencies are the same, the quotes
+/// are empty, differ in length or are not increasing after spot, spot is before the initial
+/// node of the foreign curve, `scale` is not positive, or if `progress` cancels the solve.
+pub fn solve_xccy_curve<T, U>(
+    market: &Market<T, U>,
+    domestic: &CurveKey,
+    foreign: &CurveKey,
+    quotes: &FXSwapPoints,
+    tol: f64,
+    max_iter: usize,
+    progress: &Progress,
+) -> Result<(CurveDF<T, U>, SolverResult), PyErr>
+where
+    T: CurveInterpolation + Clone,
+    U: DateRoll + Clone,
+{
+    if domestic.currency == foreign.currency {
+        return Err(PyValueError::new_err(
+            "The domestic and foreign curves must be of different currencies.",
+        ));
+    }
+    let FXSwapPoints {
+        spot,
+        maturities,
+        points,
+        scale,
+    } = quotes;
+    if maturities.len() != points.len() || maturities.is_empty() {
+        return Err(PyValueError::new_err(
+            "`maturities` and `points` must have the same, non-zero, length.",
+        ));
+    }
+    if maturities[0] <= *spot || maturities.windows(2).any(|w| w[1] <= w[0]) {
+        return Err(PyValueError::new_err(
+            "`maturities` must be increasing and after `spot`.",
+        ));
+    }
+    if *scale <= 0.0 {
+        return Err(PyValueError::new_err("`scale` must be positive."));
+    }
+    let discount = market.curve(domestic)?;
+    let foreign_curve = market.curve(foreign)?;
+    let fx = f64::from(market.fx_rate(&foreign.currency, &domestic.currency)?);
+    let initial = node_date(foreign_curve.nodes.keys()[0]);
+    if *spot < initial {
+        return Err(PyValueError::new_err(
+            "`spot` must not be before the initial node of the foreign curve.",
+        ));
+    }
+
+    let id = format!("{}{}", foreign.currency.name, domestic.currency.name);
+    span!(info_span, "solve_xccy_curve", curve = id.as_str());
+    let nodes = Nodes::F64(IndexMap::from_iter(
+        std::iter::once((initial, 1.0)).chain(
+            maturities
+                .iter()
+                .map(|m| (*m, f64::from(foreign_curve.interpolated_value(m)))),
+        ),
+    ));
+    let curve = CurveDF::try_new(
+        nodes,
+        foreign_curve.interpolator.clone(),
+        &id,
+        foreign_curve.convention,
+        foreign_curve.modifier,
+        None,
+        foreign_curve.calendar.clone(),
+    )?;
+    let vars = get_variable_tags(&id, maturities.len() + 1)[1..].to_vec();
+
+    // the domestic discount factors of each maturity relative to spot
+    let v_spot = f64::from(discount.interpolated_value(spot));
+    let v: Vec<f64> = maturities
+        .iter()
+        .map(|m| f64::from(discount.interpolated_value(m)) / v_spot)
+        .collect();
+    // the forward points implied by a trial curve
+    let implied = |trial: &CurveDF<T, U>| -> Vec<Number> {
+        let w_spot = trial.interpolated_value(spot);
+        maturities
+            .iter()
+            .zip(v.iter())
+            .map(|(m, v)| (trial.interpolated_value(m) / &w_spot * (fx / v) - fx) / *scale)
+            .collect()
+    };
+    let residuals = |params: &[f64]| -> Result<Array1<f64>, PyErr> {
+        if params.iter().any(|p| *p <= 0.0) {
+            return Ok(Array1::from_elem(points.len(), f64::INFINITY));
+        }
+        let trial = with_node_values(&curve, params);
+        Ok(Array1::from_iter(
+            implied(&trial)
+                .iter()
+                .zip(points.iter())
+                .map(|(p, q)| f64::from(p) - q),
+        ))
+    };
+    let jacobian = |params: &[f64]| -> Result<(Array1<f64>, Array2<f64>), PyErr> {
+        let mut trial = with_node_values(&curve, params);
+        trial.set_ad_order(ADOrder::One)?;
+        let mut r = Array1::zeros(points.len());
+        let mut jac = Array2::zeros((points.len(), vars.len()));
+        for (i, (p, q)) in implied(&trial).iter().zip(points.iter()).enumerate() {
+            r[i] = f64::from(p) - q;
+            if let Number::Dual(d) = p {
+                jac.row_mut(i).assign(&d.gradient1(vars.clone()));
+            }
+        }
+        Ok((r, jac))
+    };
+
+    let initial_params: Vec<f64> = maturities
+        .iter()
+        .map(|m| f64::from(foreign_curve.interpolated_value(m)))
+        .collect();
+    let (params, result) = levenberg_marquardt(
+        &initial_params,
+        residuals,
+        jacobian,
+        tol,
+        max_iter,
+        progress,
+    )?;
+
+    // a Newton step from the solution with dual-valued quotes gives the node sensitivities
+    let r = residuals(&params)?;
+    let r_dual: Array1<Dual> =
+        Array1::from_iter(r.iter().enumerate().map(|(i, r)| {
+            Dual::try_new(*r, vec![format!("{}_pts{}", id, i)], vec![-1.0]).unwrap()
+        }));
+    let step = fdsolve(&result.jacobian.view(), &r_dual.view(), false);
+    let nodes = Nodes::Dual(IndexMap::from_iter(
+        std::iter::once((initial, Dual::new(1.0, vec![]))).chain(
+            maturities
+                .iter()
+                .zip(params.iter().zip(step.iter()))
+                .map(|(m, (p, s))| (*m, s * -1.0 + *p)),
+        ),
+    ));
+    let solved = CurveDF::try_new(
+        nodes,
+        curve.interpolator.clone(),
+        &id,
+        curve.convention,
+        curve.modifier,
+        None,
+        curve.calendar.clone(),
+    )?;
+    Ok((solved, result))
+}
+
+// UNIT TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::{ndt, Convention, Modifier, NamedCal};
+    use crate::curves::LogLinearInterpolator;
+    use crate::fx::rates::{Ccy, FXRate, FXRates};
+
+    fn curve(id: &str, dfs: [f64; 2]) -> CurveDF<LogLinearInterpolator, NamedCal> {
+        let nodes = Nodes::F64(IndexMap::from_iter(vec![
+            (ndt(2022, 1, 1), 1.0_f64),
+            (ndt(2023, 1, 1), dfs[0]),
+            (ndt(2025, 1, 1), dfs[1]),
+        ]));
+        CurveDF::try_new(
+            nodes,
+            LogLinearInterpolator::new(),
+            id,
+            Convention::Act360,
+            Modifier::ModF,
+            None,
+            NamedCal::try_new("all").unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn market() -> Market<LogLinearInterpolator, NamedCal> {
+        let fx = FXRates::try_new(
+            vec![FXRate::try_new("eur", "usd", Number::F64(1.08), None).unwrap()],
+            None,
+        )
+        .unwrap();
+        Market::try_new(
+            vec![
+                (key("usd"), curve("usd_ois", [0.96, 0.88])),
+                (key("eur"), curve("eur_ois", [0.98, 0.94])),
+            ],
+            Some(fx),
+        )
+        .unwrap()
+    }
+
+    fn key(ccy: &str) -> CurveKey {
+        CurveKey::try_new(Ccy::try_new(ccy).unwrap(), "ois").unwrap()
+    }
+
+    fn quotes(points: Vec<f64>) -> FXSwapPoints {
+        FXSwapPoints {
+            spot: ndt(2022, 1, 3),
+            maturities: vec![ndt(2022, 7, 3), ndt(2023, 1, 3), ndt(2024, 1, 3)],
+            points,
+            scale: 1e-4,
+        }
+    }
+
+    fn solve(points: Vec<f64>) -> CurveDF<LogLinearInterpolator, NamedCal> {
+        let (curve, result) = solve_xccy_curve(
+            &market(),
+            &key("usd"),
+            &key("eur"),
+            &quotes(points),
+            1e-9,
+            50,
+            &Progress::default(),
+        )
+        .unwrap();
+        assert!(result.converged);
+        curve
+    }
+
+    #[test]
+    fn test_solve_xccy_curve_reprices_forwards() {
+        let q = quotes(vec![120.0, 250.0, 520.0]);
+        let curve = solve(q.points.clone());
+        assert_eq!(curve.id, "eurusd");
+        assert_eq!(curve.ad(), ADOrder::One);
+        let usd = market().curve(&key("usd")).unwrap().clone();
+        let df = |c: &CurveDF<_, _>, d: &NaiveDateTime| f64::from(c.interpolated_value(d));
+        for (m, p) in q.maturities.iter().zip(q.points.iter()) {
+            let forward =
+                1.08 * (df(&curve, m) / df(&curve, &q.spot)) / (df(&usd, m) / df(&usd, &q.spot));
+            assert!((forward - (1.08 + p * 1e-4)).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_solve_xccy_curve_sensitivity_to_points() {
+        let points = vec![120.0, 250.0, 520.0];
+        let curve = solve(points.clone());
+        let date = ndt(2023, 6, 1);
+        let Number::Dual(d) = curve.interpolated_value(&date) else {
+            panic!("expected a Dual value")
+        };
+        let vars: Vec<String> = (0..3).map(|i| format!("eurusd_pts{}", i)).collect();
+        let grad = d.gradient1(vars);
+        for i in 0..3 {
+            let bump = |h: f64| {
+                let mut p = points.clone();
+                p[i] += h;
+                f64::from(solve(p).interpolated_value(&date))
+            };
+            let fd = (bump(0.01) - bump(-0.01)) / 0.02;
+            assert!((grad[i] - fd).abs() < 1e-9);
+        }
+        // a date before the second maturity does not depend on the quotes of later maturities
+        let Number::Dual(d) = curve.interpolated_value(&ndt(2022, 5, 1)) else {
+            panic!("expected a Dual value")
+        };
+        assert_eq!(d.gradient1(vec!["eurusd_pts2".to_string()])[0], 0.0);
+    }
+
+    #[test]
+    fn test_solve_xccy_curve_validation() {
+        let m = market();
+        let err = |domestic: &str, foreign: &str, q: FXSwapPoints| {
+            solve_xccy_curve(
+                &m,
+                &key(domestic),
+                &key(foreign),
+                &q,
+                1e-9,
+                50,
+                &Progress::default(),
+            )
+            .is_err()
+        };
+        assert!(err("usd", "usd", quotes(vec![1.0, 2.0, 3.0])));
+        assert!(err("usd", "gbp", quotes(vec![1.0, 2.0, 3.0])));
+        assert!(err("usd", "eur", quotes(vec![1.0, 2.0])));
+        let mut q = quotes(vec![1.0, 2.0, 3.0]);
+        q.maturities[2] = ndt(2022, 1, 1);
+        assert!(err("usd", "eur", q));
+        let mut q = quotes(vec![1.0, 2.0, 3.0]);
+        q.scale = 0.0;
+        assert!(err("usd", "eur", q));
+    }
+}
@@ -0,0 +1,376 @@
+//! Market convention templates for instruments, resolvable by name.
+//!
+//! Each spec, e.g. *"usd_irs"* or *"eur_irs6"*, is a collection of default keyword arguments
+//! for constructing an instrument according to its market conventions. Arguments for a second
+//! leg are prefixed with *"leg2_"*. Instrument constructors consult a spec to fill any
+//! parameters that are not explicitly given.
+//!
+//! The default specs are loaded from the same data as the Python defaults system. Additional
+//! specs may be registered at runtime, either from Rust or from Python.
+//!
+//! ```rust
+//! # use rateslib::specs::{get_spec, SpecValue};
+//! let spec = get_spec("usd_irs").unwrap();
+//! assert_eq!(spec.get("calendar"), Some(&SpecValue::Str("nyc".to_string())));
+//! ```
+
+use crate::calendars::{CalType, Convention, Modifier, NamedCal};
+use indexmap::IndexMap;
+use pyo3::exceptions::PyValueError;
+use pyo3::{FromPyObject, PyErr};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::{OnceLock, RwLock};
+
+pub(crate) mod specs_py;
+
+const SPEC_DATA: &str = include_str!("../../python/rateslib/data/__instrument_spec.csv");
+
+const ALIASES: &[(&str, &str)] = &[
+    ("sofr", "usd_irs"),
+    ("ust", "us_gb"),
+    ("ustb", "us_gbb"),
+    ("ukt", "uk_gb"),
+    ("gilt", "uk_gb"),
+    ("cadgb", "ca_gb"),
+    ("sgb", "se_gb"),
+    ("sgbb", "se_gbb"),
+    ("ukti", "gbp_gbi"),
+    ("sofr3mf", "usd_stir"),
+    ("sofr1mf", "usd_stir1"),
+    ("sonia3mf", "gbp_stir"),
+    ("estr1mf", "eur_stir1"),
+    ("estr3mf", "eur_stir"),
+    ("euribor3mf", "eur_stir3"),
+];
+
+static SPECS: OnceLock<RwLock<IndexMap<String, InstrumentSpec>>> = OnceLock::new();
+
+/// A value of a keyword argument in an instrument spec.
+#[derive(Debug, Clone, PartialEq, FromPyObject, Serialize, Deserialize)]
+pub enum SpecValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+/// A named collection of default keyword arguments for an instrument.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InstrumentSpec {
+    pub(crate) kwargs: IndexMap<String, SpecValue>,
+}
+
+impl InstrumentSpec {
+    pub fn new(kwargs: IndexMap<String, SpecValue>) -> Self {
+        InstrumentSpec { kwargs }
+    }
+
+    /// Return the default value of a keyword argument, if specified.
+    pub fn get(&self, kwarg: &str) -> Option<&SpecValue> {
+        self.kwargs.get(kwarg)
+    }
+
+    /// Return the given keyword arguments with any unspecified arguments filled from the spec.
+    pub fn resolve(&self, kwargs: IndexMap<String, SpecValue>) -> IndexMap<String, SpecValue> {
+        let mut resolved = self.kwargs.clone();
+        resolved.extend(kwargs);
+        resolved
+    }
+}
+
+fn specs() -> &'static RwLock<IndexMap<String, InstrumentSpec>> {
+    SPECS.get_or_init(|| RwLock::new(parse_spec_data(SPEC_DATA)))
+}
+
+/// Return the instrument spec registered under `name`.
+pub fn get_spec(name: &str) -> Result<InstrumentSpec, PyErr> {
+    specs()
+        .read()
+        .unwrap()
+        .get(&name.to_lowercase())
+        .cloned()
+        .ok_or_else(|| PyValueError::new_err(format!("Instrument spec '{}' not found.", name)))
+}
+
+/// Register an instrument spec under `name`, replacing any existing spec of the same name.
+pub fn register_spec(name: &str, spec: InstrumentSpec) {
+    specs().write().unwrap().insert(name.to_lowercase(), spec);
+}
+
+/// Return the names of all registered instrument specs.
+pub fn spec_names() -> Vec<String> {
+    specs().read().unwrap().keys().cloned().collect()
+}
+
+/// Return the instrument spec registered under `name`, if a name is given.
+pub fn get_optional_spec(name: Option<&str>) -> Result<Option<InstrumentSpec>, PyErr> {
+    name.map(get_spec).transpose()
+}
+
+/// Return `value` if it is given, or else the value of `kwarg` in the `spec` converted by
+/// `parse`.
+///
+/// # Errors
+///
+/// If `value` is not given and there is no `spec` or it does not specify `kwarg`, or if the
+/// specified value cannot be converted.
+pub fn fill_from_spec<T>(
+    value: Option<T>,
+    spec: Option<&InstrumentSpec>,
+    kwarg: &str,
+    parse: fn(&SpecValue) -> Result<T, PyErr>,
+) -> Result<T, PyErr> {
+    match (value, spec.and_then(|s| s.get(kwarg))) {
+        (Some(v), _) => Ok(v),
+        (None, Some(v)) => parse(v),
+        (None, None) => Err(PyValueError::new_err(format!(
+            "`{}` must be given, or be specified by the instrument `spec`.",
+            kwarg
+        ))),
+    }
+}
+
+/// Return `value` if it is given, or else the value of `kwarg` in the `spec` converted by
+/// `parse`, or else `default`, mirroring the defaults of *rateslib (Python)*.
+///
+/// # Errors
+///
+/// If the specified value cannot be converted.
+pub fn fill_from_spec_or<T>(
+    value: Option<T>,
+    spec: Option<&InstrumentSpec>,
+    kwarg: &str,
+    parse: fn(&SpecValue) -> Result<T, PyErr>,
+    default: T,
+) -> Result<T, PyErr> {
+    match spec.and_then(|s| s.get(kwarg)) {
+        Some(_) => fill_from_spec(value, spec, kwarg, parse),
+        None => Ok(value.unwrap_or(default)),
+    }
+}
+
+fn spec_str<'a>(value: &'a SpecValue, kind: &str) -> Result<&'a str, PyErr> {
+    match value {
+        SpecValue::Str(s) => Ok(s),
+        _ => Err(PyValueError::new_err(format!(
+            "The instrument spec value {:?} is not a valid {}.",
+            value, kind
+        ))),
+    }
+}
+
+/// Convert a spec frequency code, e.g. *"q"*, to the number of months in a period.
+pub fn spec_frequency_months(value: &SpecValue) -> Result<i32, PyErr> {
+    match spec_str(value, "frequency")?.to_uppercase().as_str() {
+        "M" => Ok(1),
+        "B" => Ok(2),
+        "Q" => Ok(3),
+        "T" => Ok(4),
+        "S" => Ok(6),
+        "A" => Ok(12),
+        _ => Err(PyValueError::new_err(format!(
+            "The instrument spec value {:?} is not a valid frequency.",
+            value
+        ))),
+    }
+}
+
+/// Convert a spec day count convention, e.g. *"act360"*.
+pub fn spec_convention(value: &SpecValue) -> Result<Convention, PyErr> {
+    Convention::from_str(spec_str(value, "convention")?)
+}
+
+/// Convert a spec calendar, e.g. *"tgt,nyc"*, to a named calendar.
+pub fn spec_calendar(value: &SpecValue) -> Result<CalType, PyErr> {
+    Ok(CalType::NamedCal(NamedCal::try_new(spec_str(
+        value, "calendar",
+    )?)?))
+}
+
+/// Convert a spec modifier, e.g. *"mf"*.
+pub fn spec_modifier(value: &SpecValue) -> Result<Modifier, PyErr> {
+    Modifier::from_str(spec_str(value, "modifier")?)
+}
+
+/// Convert a spec integer number of days, e.g. a `payment_lag` or `ex_div`.
+pub fn spec_days(value: &SpecValue) -> Result<i32, PyErr> {
+    match value {
+        SpecValue::Int(i) => i32::try_from(*i).map_err(|_| {
+            PyValueError::new_err(format!(
+                "The instrument spec value {} is out of range for a number of days.",
+                i
+            ))
+        }),
+        _ => Err(PyValueError::new_err(format!(
+            "The instrument spec value {:?} is not a valid number of days.",
+            value
+        ))),
+    }
+}
+
+/// Split a line of CSV data into fields, respecting double quoted fields containing commas.
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    for c in line.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+fn parse_value(value: &str, dtype: &str) -> Option<SpecValue> {
+    match dtype {
+        "bool" => match value.to_uppercase().as_str() {
+            "TRUE" => Some(SpecValue::Bool(true)),
+            "FALSE" => Some(SpecValue::Bool(false)),
+            _ => None,
+        },
+        "int" => value.parse().ok().map(SpecValue::Int),
+        "float" => value.parse().ok().map(SpecValue::Float),
+        // untyped values, such as `roll`, may be either an int or a str.
+        _ => Some(
+            value
+                .parse()
+                .map(SpecValue::Int)
+                .unwrap_or_else(|_| SpecValue::Str(value.to_string())),
+        ),
+    }
+}
+
+/// Parse the instrument spec table, with columns *kind, leg, kwarg, dtype*, followed by a
+/// column for each spec.
+fn parse_spec_data(data: &str) -> IndexMap<String, InstrumentSpec> {
+    let mut lines = data.trim_start_matches('\u{feff}').lines();
+    let header = split_csv_line(lines.next().unwrap_or_default());
+    let mut specs: IndexMap<String, InstrumentSpec> = header
+        .iter()
+        .skip(4)
+        .map(|name| (name.trim().to_string(), InstrumentSpec::default()))
+        .collect();
+    for line in lines {
+        let fields = split_csv_line(line);
+        if fields.len() < 4 || fields[1] == "meta" {
+            continue;
+        }
+        let prefix = if fields[1] == "leg2" { "leg2_" } else { "" };
+        let kwarg = format!("{}{}", prefix, fields[2]);
+        for (i, value) in fields.iter().enumerate().skip(4) {
+            if value.is_empty() {
+                continue;
+            }
+            if let (Some((_, spec)), Some(v)) =
+                (specs.get_index_mut(i - 4), parse_value(value, &fields[3]))
+            {
+                spec.kwargs.insert(kwarg.clone(), v);
+            }
+        }
+    }
+    for (alias, name) in ALIASES {
+        if let Some(spec) = specs.get(*name).cloned() {
+            specs.insert(alias.to_string(), spec);
+        }
+    }
+    specs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_csv_line() {
+        let result = split_csv_line("a,\"b,c\",,d");
+        assert_eq!(result, vec!["a", "b,c", "", "d"]);
+    }
+
+    #[test]
+    fn test_get_spec() {
+        let spec = get_spec("usd_irs").unwrap();
+        assert_eq!(
+            spec.get("frequency"),
+            Some(&SpecValue::Str("a".to_string()))
+        );
+        assert_eq!(spec.get("eom"), Some(&SpecValue::Bool(false)));
+        assert_eq!(spec.get("payment_lag"), Some(&SpecValue::Int(2)));
+        assert_eq!(
+            spec.get("leg2_fixing_method"),
+            Some(&SpecValue::Str("rfr_payment_delay".to_string()))
+        );
+        assert_eq!(spec.get("notional"), None);
+        assert_eq!(get_spec("SOFR").unwrap(), spec);
+        assert!(get_spec("bad_spec").is_err());
+    }
+
+    #[test]
+    fn test_quoted_calendar_values() {
+        let spec = get_spec("eurusd_xcs").unwrap();
+        assert_eq!(
+            spec.get("calendar"),
+            Some(&SpecValue::Str("tgt,nyc".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_fill_from_spec() {
+        let spec = get_optional_spec(Some("eur_sbs36")).unwrap();
+        let spec = spec.as_ref();
+        assert_eq!(
+            fill_from_spec(None, spec, "frequency", spec_frequency_months).unwrap(),
+            3
+        );
+        assert_eq!(
+            fill_from_spec(None, spec, "leg2_frequency", spec_frequency_months).unwrap(),
+            6
+        );
+        assert_eq!(
+            fill_from_spec(Some(12), spec, "frequency", spec_frequency_months).unwrap(),
+            12
+        );
+        assert_eq!(
+            fill_from_spec(None, spec, "convention", spec_convention).unwrap(),
+            Convention::Act360
+        );
+        assert_eq!(
+            fill_from_spec(None, spec, "modifier", spec_modifier).unwrap(),
+            Modifier::ModF
+        );
+        assert_eq!(
+            fill_from_spec(None, spec, "payment_lag", spec_days).unwrap(),
+            0
+        );
+        let calendar = fill_from_spec(None, spec, "calendar", spec_calendar).unwrap();
+        assert_eq!(
+            calendar,
+            CalType::NamedCal(NamedCal::try_new("tgt").unwrap())
+        );
+        assert!(fill_from_spec(None, spec, "ex_div", spec_days).is_err());
+        assert!(fill_from_spec(None, None, "frequency", spec_frequency_months).is_err());
+        assert!(get_optional_spec(None).unwrap().is_none());
+        assert!(spec_frequency_months(&SpecValue::Str("z".to_string())).is_err());
+        assert_eq!(spec_days(&SpecValue::Int(1000)).unwrap(), 1000);
+        assert!(spec_days(&SpecValue::Int(i64::from(i32::MAX) + 1)).is_err());
+    }
+
+    #[test]
+    fn test_register_and_resolve() {
+        let spec = InstrumentSpec::new(IndexMap::from([
+            ("frequency".to_string(), SpecValue::Str("q".to_string())),
+            ("payment_lag".to_string(), SpecValue::Int(1)),
+        ]));
+        register_spec("Custom_Spec", spec);
+        assert!(spec_names().contains(&"custom_spec".to_string()));
+        let resolved = get_spec("custom_spec").unwrap().resolve(IndexMap::from([(
+            "payment_lag".to_string(),
+            SpecValue::Int(0),
+        )]));
+        assert_eq!(resolved["payment_lag"], SpecValue::Int(0));
+        assert_eq!(resolved["frequency"], SpecValue::Str("q".to_string()));
+    }
+}
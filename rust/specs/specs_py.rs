@@ -0,0 +1,54 @@
+//! Wrapper module to export Rust instrument specs to Python using pyo3 bindings.
+
+use crate::specs::{get_spec, register_spec, spec_names, InstrumentSpec, SpecValue};
+use indexmap::IndexMap;
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyFloat, PyString};
+
+impl IntoPy<PyObject> for SpecValue {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        match self {
+            SpecValue::Bool(b) => PyBool::new_bound(py, b).to_object(py),
+            SpecValue::Int(i) => i.into_py(py),
+            SpecValue::Float(f) => PyFloat::new_bound(py, f).to_object(py),
+            SpecValue::Str(s) => PyString::new_bound(py, &s).to_object(py),
+        }
+    }
+}
+
+/// Return the default keyword arguments of a named instrument spec.
+///
+/// Parameters
+/// ----------
+/// name: str
+///     The name of the spec, e.g. *"usd_irs"*.
+///
+/// Returns
+/// -------
+/// dict
+#[pyfunction]
+#[pyo3(name = "get_spec")]
+pub(crate) fn get_spec_py(name: &str) -> PyResult<IndexMap<String, SpecValue>> {
+    Ok(get_spec(name)?.kwargs)
+}
+
+/// Register a named instrument spec, replacing any existing spec of the same name.
+///
+/// Parameters
+/// ----------
+/// name: str
+///     The name of the spec.
+/// kwargs: dict
+///     The default keyword arguments, with values of type *bool*, *int*, *float* or *str*.
+#[pyfunction]
+#[pyo3(name = "register_spec")]
+pub(crate) fn register_spec_py(name: &str, kwargs: IndexMap<String, SpecValue>) {
+    register_spec(name, InstrumentSpec::new(kwargs))
+}
+
+/// Return the names of all registered instrument specs.
+#[pyfunction]
+#[pyo3(name = "spec_names")]
+pub(crate) fn spec_names_py() -> Vec<String> {
+    spec_names()
+}
@@ -3,7 +3,9 @@
 mod spline;
 pub(crate) mod spline_py;
 
+pub(crate) use crate::splines::spline::check_number_types;
 pub use crate::splines::spline::{
-    bspldnev_single_dual, bspldnev_single_dual2, bspldnev_single_f64, bsplev_single_dual,
-    bsplev_single_dual2, bsplev_single_f64, PPSpline, PPSplineDual, PPSplineDual2, PPSplineF64,
+    bspldnev_single_dual, bspldnev_single_dual2, bspldnev_single_f64, bspldnev_single_number,
+    bsplev_single_dual, bsplev_single_dual2, bsplev_single_f64, bsplev_single_number, PPSpline,
+    PPSplineDual, PPSplineDual2, PPSplineF64, SplineBoundary, SplineError,
 };
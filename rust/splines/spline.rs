@@ -1,5 +1,6 @@
-use crate::dual::linalg::{dmul11_, fdmul11_, fdsolve, fouter11_};
+use crate::dual::linalg::{dmul11_, fdmul11_, fdmul21_, fdsolve, fouter11_};
 use crate::dual::{Dual, Dual2, Gradient1, Gradient2, Number, NumberMapping};
+use crate::maths::sampling::adaptive_sample;
 use ndarray::{Array1, Array2};
 use num_traits::{Signed, Zero};
 use pyo3::exceptions::{PyTypeError, PyValueError};
@@ -7,7 +8,8 @@ use pyo3::{pyclass, PyErr};
 use serde::{Deserialize, Serialize};
 use std::{
     cmp::PartialEq,
-    iter::{zip, Sum},
+    fmt,
+    iter::Sum,
     ops::{Mul, Sub},
 };
 
@@ -167,6 +169,265 @@ pub fn bspldnev_single_dual2(
     Dual2::clone_from(x, b_f64, dbdx_f64 * x.dual(), dual2)
 }
 
+/// Evaluate the `x` value on the `i`'th B-spline with order `k` and knot sequence `t`, where
+/// the knot positions may themselves be dual-valued.
+///
+/// The derivatives of the value with respect to the variables of `x` and of each knot are
+/// propagated through the Cox-de Boor recursion by the chain rule. The support of each
+/// b-spline is determined by the real parts of `x` and `t`.
+///
+/// Note `org_k` should be input as None, it is used internally for recursively calculating
+/// spline derivatives, where it is set to the original `k` value from the outer scope.
+pub fn bsplev_single_number(
+    x: &Number,
+    i: usize,
+    k: &usize,
+    t: &[Number],
+    org_k: Option<usize>,
+) -> Number {
+    let org_k: usize = org_k.unwrap_or(*k);
+    let xr = f64::from(x);
+    let tr = |j: usize| f64::from(&t[j]);
+
+    // Short circuit (positivity and support property)
+    if xr < tr(i) || xr > tr(i + k) {
+        return Number::F64(0.0_f64);
+    }
+
+    // Right side end point support
+    if xr == tr(t.len() - 1) && i >= (t.len() - org_k - 1) {
+        return Number::F64(1.0_f64);
+    }
+
+    // Recursion
+    if *k == 1_usize {
+        if tr(i) <= xr && xr < tr(i + 1) {
+            Number::F64(1.0_f64)
+        } else {
+            Number::F64(0.0_f64)
+        }
+    } else {
+        let mut r = Number::F64(0.0_f64);
+        if tr(i) != tr(i + k - 1) {
+            r = r
+                + (x - &t[i]) / (&t[i + k - 1] - &t[i])
+                    * bsplev_single_number(x, i, &(k - 1), t, None);
+        }
+        if tr(i + 1) != tr(i + k) {
+            r = r
+                + (&t[i + k] - x) / (&t[i + k] - &t[i + 1])
+                    * bsplev_single_number(x, i + 1, &(k - 1), t, None);
+        }
+        r
+    }
+}
+
+/// Evaluate the `m`'th order derivative of the `x` value on the `i`'th B-spline with
+/// order `k` and knot sequence `t`, where the knot positions may themselves be dual-valued.
+///
+/// See [bsplev_single_number].
+///
+/// Note `org_k` should be input as None, it is used internally for recursively calculating
+/// spline derivatives, where it is set to the original `k` value from the outer scope.
+pub fn bspldnev_single_number(
+    x: &Number,
+    i: usize,
+    k: &usize,
+    t: &[Number],
+    m: usize,
+    org_k: Option<usize>,
+) -> Number {
+    if m == 0 {
+        return bsplev_single_number(x, i, k, t, None);
+    } else if *k == 1 || m >= *k {
+        return Number::F64(0.0_f64);
+    }
+
+    let org_k: usize = org_k.unwrap_or(*k);
+    let mut r = Number::F64(0.0_f64);
+    let div1 = &t[i + k - 1] - &t[i];
+    let div2 = &t[i + k] - &t[i + 1];
+
+    if f64::from(&div1) != 0_f64 {
+        let b = match m {
+            1 => bsplev_single_number(x, i, &(k - 1), t, Some(org_k)),
+            _ => bspldnev_single_number(x, i, &(k - 1), t, m - 1, Some(org_k)),
+        };
+        r = r + b / div1;
+    }
+    if f64::from(&div2) != 0_f64 {
+        let b = match m {
+            1 => bsplev_single_number(x, i + 1, &(k - 1), t, Some(org_k)),
+            _ => bspldnev_single_number(x, i + 1, &(k - 1), t, m - 1, Some(org_k)),
+        };
+        r = r - b / div2;
+    }
+    r * ((k - 1) as f64)
+}
+
+/// The AD order of a [Number], used to prohibit mixing [Dual] and [Dual2] values.
+fn number_order(x: &Number) -> u8 {
+    match x {
+        Number::F64(_) => 0,
+        Number::Dual(_) => 1,
+        Number::Dual2(_) => 2,
+    }
+}
+
+/// Return an error if `values` contain both [Dual] and [Dual2] types.
+pub(crate) fn check_number_types<'a>(
+    values: impl Iterator<Item = &'a Number>,
+) -> Result<(), PyErr> {
+    let orders = values.fold(0_u8, |acc, x| acc | number_order(x));
+    match orders {
+        3 => Err(PyTypeError::new_err("Cannot mix `Dual` and `Dual2` types.")),
+        _ => Ok(()),
+    }
+}
+
+/// The reason a [PPSpline] cannot be constructed or solved.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SplineError {
+    /// The order `k` is zero.
+    ZeroOrder,
+    /// There are not more knots than the order, so there are no b-splines.
+    TooFewKnots { knots: usize, k: usize },
+    /// The knot at `index` is less than the knot before it.
+    Decreasing { index: usize },
+    /// The first or last knot is not repeated `k` times.
+    EndMultiplicity { left: usize, right: usize, k: usize },
+    /// An interior knot is repeated more than `k` times, so a b-spline is zero everywhere.
+    InteriorMultiplicity {
+        knot: f64,
+        multiplicity: usize,
+        k: usize,
+    },
+    /// The number of coefficients is not the number of b-splines, `n`.
+    CoefficientLength { given: usize, n: usize },
+    /// There are fewer data points than b-splines, `n`, or more without least squares.
+    DataPoints { given: usize, n: usize },
+    /// The data point at `index` is not greater than the data point before it.
+    UnsortedPoints { index: usize },
+    /// The boundary condition is not available for a spline of this degree.
+    BoundaryDegree {
+        boundary: SplineBoundary,
+        degree: usize,
+    },
+}
+
+impl fmt::Display for SplineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SplineError::ZeroOrder => write!(f, "The spline order `k` must be at least 1."),
+            SplineError::TooFewKnots { knots, k } => write!(
+                f,
+                "The knot sequence `t` must have more than `k` knots, got {} knots for k={}.",
+                knots, k
+            ),
+            SplineError::Decreasing { index } => write!(
+                f,
+                "The knot sequence `t` must be non-decreasing, but `t[{}]` < `t[{}]`.",
+                index,
+                index - 1
+            ),
+            SplineError::EndMultiplicity { left, right, k } => write!(
+                f,
+                "The first and last knots of `t` must each be repeated k={} times, got {} and {}.",
+                k, left, right
+            ),
+            SplineError::InteriorMultiplicity {
+                knot,
+                multiplicity,
+                k,
+            } => write!(
+                f,
+                "The interior knot {} is repeated {} times, more than k={}.",
+                knot, multiplicity, k
+            ),
+            SplineError::CoefficientLength { given, n } => write!(
+                f,
+                "The spline coefficients `c` must have length n={}, got {}.",
+                n, given
+            ),
+            SplineError::DataPoints { given, n } => write!(
+                f,
+                "`csolve` requires n={} data points, or more with `allow_lsq`, got {}.",
+                n, given
+            ),
+            SplineError::UnsortedPoints { index } => write!(
+                f,
+                "The data points `x` must be increasing, but `x[{}]` <= `x[{}]`.",
+                index,
+                index - 1
+            ),
+            SplineError::BoundaryDegree { boundary, degree } => write!(
+                f,
+                "The boundary condition {:?} is not available for a spline of degree {}.",
+                boundary, degree
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SplineError {}
+
+impl From<SplineError> for PyErr {
+    fn from(err: SplineError) -> Self {
+        PyValueError::new_err(err.to_string())
+    }
+}
+
+/// The boundary condition of a spline interpolating data points, see [PPSpline::from_points].
+#[pyclass(module = "rateslib.rs", eq, eq_int)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SplineBoundary {
+    /// The knots are the data points excluding those adjacent to each end, so that the first
+    /// and last polynomial pieces each span several data points. Available for odd degrees.
+    NotAKnot,
+    /// The knots are the data points and the second derivative is zero at each end. Available
+    /// for cubic splines.
+    Natural,
+    /// The knots are the data points and the first derivative is zero at each end. Available
+    /// for cubic splines.
+    Clamped,
+}
+
+/// Validate a knot sequence for a spline of order `k`.
+///
+/// The knots must be non-decreasing, with the first and last knots each repeated `k` times and
+/// no interior knot repeated more than `k` times.
+fn validate_knots(k: usize, t: &[f64]) -> Result<(), SplineError> {
+    if k == 0 {
+        return Err(SplineError::ZeroOrder);
+    }
+    if t.len() <= k {
+        return Err(SplineError::TooFewKnots { knots: t.len(), k });
+    }
+    if let Some(index) = (1..t.len()).find(|i| t[*i] < t[*i - 1]) {
+        return Err(SplineError::Decreasing { index });
+    }
+    let (left, right) = (
+        t.iter().take_while(|v| **v == t[0]).count(),
+        t.iter().rev().take_while(|v| **v == t[t.len() - 1]).count(),
+    );
+    if left != k || right != k {
+        return Err(SplineError::EndMultiplicity { left, right, k });
+    }
+    let mut i = left;
+    while i < t.len() - right {
+        let multiplicity = t[i..].iter().take_while(|v| **v == t[i]).count();
+        if multiplicity > k {
+            return Err(SplineError::InteriorMultiplicity {
+                knot: t[i],
+                multiplicity,
+                k,
+            });
+        }
+        i += multiplicity;
+    }
+    Ok(())
+}
+
 /// A piecewise polynomial spline of given order and knot sequence.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct PPSpline<T> {
@@ -174,6 +435,7 @@ pub struct PPSpline<T> {
     t: Vec<f64>,
     c: Option<Array1<T>>,
     n: usize,
+    t_dual: Option<Vec<Number>>,
 }
 
 impl<T> PPSpline<T> {
@@ -192,6 +454,63 @@ impl<T> PPSpline<T> {
     pub fn c(&self) -> &Option<Array1<T>> {
         &self.c
     }
+
+    /// The dual-valued knot sequence, if set by [PPSpline::with_dual_knots].
+    pub fn t_dual(&self) -> &Option<Vec<Number>> {
+        &self.t_dual
+    }
+
+    /// Return the spline with dual-valued knot positions, e.g. sensitive to a time to expiry.
+    ///
+    /// The real parts of `t` replace the knot sequence, and the coefficients are unchanged.
+    /// Evaluation by [PPSpline::ppdnev_single_number], and by [NumberMapping], then propagates
+    /// the derivatives of the knot positions to the spline value. Every other method evaluates
+    /// the spline with the real knot sequence.
+    ///
+    /// # Errors
+    ///
+    /// If `t` is not the same length as the knot sequence, its real parts are not a valid knot
+    /// sequence, see [PPSpline::try_new], or it mixes [Dual] and [Dual2] types.
+    pub fn with_dual_knots(mut self, t: Vec<Number>) -> Result<Self, PyErr> {
+        if t.len() != self.t.len() {
+            return Err(PyValueError::new_err(
+                "Dual knots must have the same length as the knot sequence.",
+            ));
+        }
+        check_number_types(t.iter())?;
+        let real: Vec<f64> = t.iter().map(f64::from).collect();
+        validate_knots(self.k, &real)?;
+        self.t = real;
+        self.t_dual = Some(t);
+        Ok(self)
+    }
+}
+
+impl<T> PPSpline<T>
+where
+    T: Clone + Into<Number>,
+{
+    /// Evaluate the `m`'th order derivative of the spline, from the right, at `x`, propagating
+    /// the derivatives of `x`, the coefficients and any dual-valued knots.
+    ///
+    /// # Errors
+    ///
+    /// If the coefficients have not been solved, or `x`, the coefficients and the knots mix
+    /// [Dual] and [Dual2] types.
+    pub fn ppdnev_single_number(&self, x: &Number, m: usize) -> Result<Number, PyErr> {
+        let c = self.c.as_ref().ok_or_else(|| {
+            PyValueError::new_err("Must call `csolve` before evaluating PPSpline.")
+        })?;
+        let c: Vec<Number> = c.iter().map(|v| v.clone().into()).collect();
+        let t: Vec<Number> = match &self.t_dual {
+            Some(t) => t.clone(),
+            None => self.t.iter().map(Number::from).collect(),
+        };
+        check_number_types(std::iter::once(x).chain(c.iter()).chain(t.iter()))?;
+        Ok((0..self.n).fold(Number::F64(0.0_f64), |acc, i| {
+            acc + &c[i] * bspldnev_single_number(x, i, &self.k, &t, m, None)
+        }))
+    }
 }
 
 impl<T> PPSpline<T>
@@ -201,13 +520,35 @@ where
     for<'a> &'a f64: Mul<&'a T, Output = T>,
 {
     /// Create a PPSpline from its order `k`, knot sequence `t` and optional spline coefficents `c`.
+    ///
+    /// Panics if the knot sequence or coefficients are invalid, see [PPSpline::try_new].
     pub fn new(k: usize, t: Vec<f64>, c: Option<Vec<T>>) -> Self {
-        // t is given and is non-decreasing
-        assert!(t.len() > 1);
-        assert!(zip(&t[1..], &t[..(t.len() - 1)]).all(|(a, b)| a >= b));
+        Self::try_new(k, t, c).unwrap()
+    }
+
+    /// Create a PPSpline from its order `k`, knot sequence `t` and optional spline coefficents `c`.
+    ///
+    /// # Errors
+    ///
+    /// If `t` is not non-decreasing, has fewer than `k + 1` knots, does not repeat its first and
+    /// last knots `k` times or repeats an interior knot more than `k` times, or if `c` is given
+    /// and its length is not the number of b-splines, `n = len(t) - k`.
+    pub fn try_new(k: usize, t: Vec<f64>, c: Option<Vec<T>>) -> Result<Self, SplineError> {
+        validate_knots(k, &t)?;
         let n = t.len() - k;
+        if let Some(c) = &c {
+            if c.len() != n {
+                return Err(SplineError::CoefficientLength { given: c.len(), n });
+            }
+        }
         let c_ = c.map(Array1::from_vec);
-        PPSpline { k, t, n, c: c_ }
+        Ok(PPSpline {
+            k,
+            t,
+            n,
+            c: c_,
+            t_dual: None,
+        })
     }
 
     pub fn ppdnev_single(&self, x: &f64, m: usize) -> Result<T, PyErr> {
@@ -224,6 +565,89 @@ where
         }
     }
 
+    /// Create a PPSpline of the given `degree` interpolating the data points `y` at `x`.
+    ///
+    /// Generates the knot sequence from the data points under the boundary condition `bc`, with
+    /// the first and last data points repeated `degree + 1` times, and solves the coefficients.
+    ///
+    /// # Errors
+    ///
+    /// If `x` is not increasing, `x` and `y` differ in length, there are too few data points
+    /// for the degree, or the boundary condition is not available for the degree.
+    pub fn from_points(
+        x: &[f64],
+        y: &[T],
+        degree: usize,
+        bc: SplineBoundary,
+    ) -> Result<Self, PyErr> {
+        if let Some(index) = (1..x.len()).find(|i| x[*i] <= x[*i - 1]) {
+            return Err(SplineError::UnsortedPoints { index }.into());
+        }
+        if x.len() != y.len() {
+            return Err(PyValueError::new_err(
+                "`x` and `y` must have the same length.",
+            ));
+        }
+        let k = degree + 1;
+        let (interior, tau, y, derivative): (&[f64], Vec<f64>, Vec<T>, usize) = match bc {
+            SplineBoundary::NotAKnot => {
+                if degree.is_multiple_of(2) {
+                    return Err(SplineError::BoundaryDegree {
+                        boundary: bc,
+                        degree,
+                    }
+                    .into());
+                }
+                if x.len() < k {
+                    return Err(SplineError::DataPoints {
+                        given: x.len(),
+                        n: k,
+                    }
+                    .into());
+                }
+                (&x[k / 2..x.len() - k / 2], x.to_vec(), y.to_vec(), 0)
+            }
+            SplineBoundary::Natural | SplineBoundary::Clamped => {
+                if degree != 3 {
+                    return Err(SplineError::BoundaryDegree {
+                        boundary: bc,
+                        degree,
+                    }
+                    .into());
+                }
+                if x.len() < 2 {
+                    return Err(SplineError::DataPoints {
+                        given: x.len(),
+                        n: 2,
+                    }
+                    .into());
+                }
+                let n = x.len();
+                let tau = [&[x[0]], x, &[x[n - 1]]].concat();
+                let y = [&[T::zero()], y, &[T::zero()]].concat();
+                let derivative = if bc == SplineBoundary::Natural { 2 } else { 1 };
+                (&x[1..n - 1], tau, y, derivative)
+            }
+        };
+        let t = [vec![x[0]; k], interior.to_vec(), vec![x[x.len() - 1]; k]].concat();
+        let mut spline = Self::try_new(k, t, None)?;
+        spline.csolve(&tau, &y, derivative, derivative, false)?;
+        Ok(spline)
+    }
+
+    /// Evaluate the `m`'th order derivative of the spline, from the right, at each of `x`.
+    ///
+    /// Derivatives of order greater than the degree of the spline, i.e. `m >= k`, are zero.
+    pub fn ppdnev(&self, x: &[f64], m: usize) -> Result<Array1<T>, PyErr> {
+        let c = self.c.as_ref().ok_or_else(|| {
+            PyValueError::new_err("Must call `csolve` before evaluating PPSpline.")
+        })?;
+        let b: Array2<f64> = Array2::from_shape_fn((x.len(), self.n), |(j, i)| {
+            bspldnev_single_f64(&x[j], i, &self.k, &self.t, m, None)
+        });
+        Ok(fdmul21_(&b.view(), &c.view()))
+    }
+
     pub fn csolve(
         &mut self,
         tau: &[f64],
@@ -233,9 +657,11 @@ where
         allow_lsq: bool,
     ) -> Result<(), PyErr> {
         if tau.len() != self.n && !(allow_lsq && tau.len() > self.n) {
-            return Err(PyValueError::new_err(
-                "`csolve` cannot complete if length of `tau` < n or `allow_lsq` is false.",
-            ));
+            return Err(SplineError::DataPoints {
+                given: tau.len(),
+                n: self.n,
+            }
+            .into());
         }
         if tau.len() != y.len() {
             return Err(PyValueError::new_err(
@@ -273,8 +699,28 @@ where
     }
 }
 
+impl<T> PPSpline<T>
+where
+    T: PartialOrd + Signed + Clone + Sum + Zero + Into<Number>,
+    for<'a> &'a T: Sub<&'a T, Output = T>,
+    for<'a> &'a f64: Mul<&'a T, Output = T>,
+{
+    /// Sample the real value of the spline from `start` to `end`, with more points where its
+    /// curvature is high, e.g. for plotting.
+    ///
+    /// Intervals are bisected until linear interpolation of the samples is within `tol` of the
+    /// spline at each midpoint, see [adaptive_sample].
+    pub fn sample(&self, start: f64, end: f64, tol: f64) -> Result<(Vec<f64>, Vec<f64>), PyErr> {
+        let f = |x: f64| Ok(f64::from(self.ppdnev_single(&x, 0)?.into()));
+        adaptive_sample(f, start, end, tol, 0.0)
+    }
+}
+
 impl NumberMapping for PPSpline<f64> {
     fn mapped_value(&self, x: &Number) -> Result<Number, PyErr> {
+        if self.t_dual.is_some() {
+            return self.ppdnev_single_number(x, 0_usize);
+        }
         match x {
             Number::F64(f) => Ok(Number::F64(self.ppdnev_single(f, 0_usize)?)),
             Number::Dual(d) => Ok(Number::Dual(self.ppdnev_single_dual(d, 0_usize)?)),
@@ -315,6 +761,9 @@ impl PPSpline<f64> {
 
 impl NumberMapping for PPSpline<Dual> {
     fn mapped_value(&self, x: &Number) -> Result<Number, PyErr> {
+        if self.t_dual.is_some() {
+            return self.ppdnev_single_number(x, 0_usize);
+        }
         match x {
             Number::F64(f) => Ok(Number::Dual(self.ppdnev_single(f, 0_usize)?)),
             Number::Dual(d) => Ok(Number::Dual(self.ppdnev_single_dual(d, 0_usize)?)),
@@ -347,6 +796,9 @@ impl PPSpline<Dual> {
 
 impl NumberMapping for PPSpline<Dual2> {
     fn mapped_value(&self, x: &Number) -> Result<Number, PyErr> {
+        if self.t_dual.is_some() {
+            return self.ppdnev_single_number(x, 0_usize);
+        }
         match x {
             Number::F64(f) => Ok(Number::Dual2(self.ppdnev_single(f, 0_usize)?)),
             Number::Dual(d) => Ok(Number::Dual(self.ppdnev_single_dual(d, 0_usize)?)),
@@ -390,6 +842,17 @@ where
         if !self.t.eq(&other.t) {
             return false;
         }
+        let t_dual_eq = match (&self.t_dual, &other.t_dual) {
+            (Some(t1), Some(t2)) => t1
+                .iter()
+                .zip(t2.iter())
+                .all(|(a, b)| number_order(a) | number_order(b) != 3 && a == b),
+            (None, None) => true,
+            _ => false,
+        };
+        if !t_dual_eq {
+            return false;
+        }
         match (&self.c, &other.c) {
             (Some(c1), Some(c2)) => c1.eq(&c2),
             (Some(_c), None) => false,
@@ -641,6 +1104,44 @@ mod tests {
         assert!(is_close(&r3, &1.136, None));
     }
 
+    #[test]
+    fn ppdnev_beyond_degree() {
+        let t = vec![1., 1., 1., 1., 2., 2., 2., 3., 4., 4., 4., 4.];
+        let x = [1.0, 1.5, 2.7, 4.0];
+        let c = vec![1., 2., -1., 2., 1., 1., 2., 2.];
+        let pps = PPSpline::new(4, t.clone(), Some(c.clone()));
+        // a cubic spline has a constant third derivative and zero derivatives beyond it
+        assert!(pps.ppdnev_single(&1.5, 3).unwrap() != 0.0);
+        for m in 4..7 {
+            assert!(pps.ppdnev(&x, m).unwrap().iter().all(|v| *v == 0.0));
+            let d = pps.ppdnev_single_dual(&Dual::new(2.5, vec!["x".to_string()]), m);
+            assert_eq!(d.unwrap(), Dual::new(0.0, vec!["x".to_string()]) * 0.0);
+        }
+
+        let d1 = Dual::new(1.0, vec!["c".to_string()]);
+        let pps_dual = PPSpline::new(4, t.clone(), Some(c.iter().map(|v| v * &d1).collect()));
+        assert!(pps_dual.ppdnev_single(&1.5, 4).unwrap() == 0.0);
+        let d2 = Dual2::new(1.0, vec!["c".to_string()]);
+        let pps_dual2 = PPSpline::new(4, t, Some(c.iter().map(|v| v * &d2).collect()));
+        assert!(pps_dual2.ppdnev(&x, 5).unwrap().iter().all(|v| *v == 0.0));
+    }
+
+    #[test]
+    fn ppdnev_matches_single() {
+        let t = vec![1., 1., 1., 1., 2., 2., 2., 3., 4., 4., 4., 4.];
+        let pps = PPSpline::new(4, t, Some(vec![1., 2., -1., 2., 1., 1., 2., 2.]));
+        let x = [1.0, 1.5, 2.0, 2.7, 3.9];
+        for m in 0..4 {
+            let result = pps.ppdnev(&x, m).unwrap();
+            for (r, xi) in result.iter().zip(x.iter()) {
+                assert!(is_close(r, &pps.ppdnev_single(xi, m).unwrap(), None));
+            }
+        }
+        assert!(PPSpline::<f64>::new(2, vec![1., 1., 2., 2.], None)
+            .ppdnev(&x, 0)
+            .is_err());
+    }
+
     #[test]
     fn partialeq_() {
         let pp1 = PPSpline::<f64>::new(2, vec![1., 1., 2., 2.], None);
@@ -653,6 +1154,222 @@ mod tests {
         assert!(pp1 != pp4);
     }
 
+    #[test]
+    fn knot_validation() {
+        let t = vec![1., 1., 1., 1., 2., 3., 3., 3., 3.];
+        assert!(PPSpline::<f64>::try_new(4, t.clone(), None).is_ok());
+        assert_eq!(
+            PPSpline::<f64>::try_new(0, t.clone(), None).unwrap_err(),
+            SplineError::ZeroOrder
+        );
+        assert_eq!(
+            PPSpline::<f64>::try_new(4, vec![1., 1., 1., 1.], None).unwrap_err(),
+            SplineError::TooFewKnots { knots: 4, k: 4 }
+        );
+        assert_eq!(
+            PPSpline::<f64>::try_new(2, vec![1., 1., 3., 2., 4., 4.], None).unwrap_err(),
+            SplineError::Decreasing { index: 3 }
+        );
+        assert_eq!(
+            PPSpline::<f64>::try_new(3, t.clone(), None).unwrap_err(),
+            SplineError::EndMultiplicity {
+                left: 4,
+                right: 4,
+                k: 3
+            }
+        );
+        assert_eq!(
+            PPSpline::<f64>::try_new(2, vec![1., 1., 2., 2., 2., 3., 3.], None).unwrap_err(),
+            SplineError::InteriorMultiplicity {
+                knot: 2.,
+                multiplicity: 3,
+                k: 2
+            }
+        );
+        assert_eq!(
+            PPSpline::try_new(4, t, Some(vec![1., 2.])).unwrap_err(),
+            SplineError::CoefficientLength { given: 2, n: 5 }
+        );
+    }
+
+    #[test]
+    fn csolve_too_few_data_points() {
+        let mut pps = PPSpline::<f64>::new(4, vec![0., 0., 0., 0., 4., 4., 4., 4.], None);
+        assert!(pps.csolve(&[0., 4.], &[1., 2.], 0, 0, true).is_err());
+        assert!(pps.c().is_none());
+    }
+
+    #[test]
+    fn from_points_interpolates() {
+        let x = [0., 1., 2.5, 3., 4.];
+        let y = [1., 2., 0.5, 1.5, 3.];
+        for bc in [
+            SplineBoundary::NotAKnot,
+            SplineBoundary::Natural,
+            SplineBoundary::Clamped,
+        ] {
+            let pps = PPSpline::from_points(&x, &y, 3, bc).unwrap();
+            let values = pps.ppdnev(&x, 0).unwrap();
+            assert!(values
+                .iter()
+                .zip(y.iter())
+                .all(|(a, b)| is_close(a, b, None)));
+        }
+        let natural = PPSpline::from_points(&x, &y, 3, SplineBoundary::Natural).unwrap();
+        assert!(is_close(&natural.ppdnev_single(&0., 2).unwrap(), &0., None));
+        let clamped = PPSpline::from_points(&x, &y, 3, SplineBoundary::Clamped).unwrap();
+        assert!(is_close(&clamped.ppdnev_single(&4., 1).unwrap(), &0., None));
+        let linear = PPSpline::from_points(&x, &y, 1, SplineBoundary::NotAKnot).unwrap();
+        assert!(is_close(
+            &linear.ppdnev_single(&0.5, 0).unwrap(),
+            &1.5,
+            None
+        ));
+    }
+
+    #[test]
+    fn from_points_dual_values() {
+        let x = [0., 1., 2., 3.];
+        let y: Vec<Dual> = (0..4)
+            .map(|i| Dual::new(i as f64 * 0.5, vec![format!("y{}", i)]))
+            .collect();
+        let pps = PPSpline::from_points(&x, &y, 3, SplineBoundary::Natural).unwrap();
+        let value = pps.ppdnev_single(&2., 0).unwrap();
+        assert!(is_close(&value.real(), &1.0, None));
+        // interpolation at a data point depends only on that data point
+        let grad = value.gradient1(vec!["y1".to_string(), "y2".to_string()]);
+        assert!(is_close(&grad[0], &0., None) && is_close(&grad[1], &1., None));
+    }
+
+    #[test]
+    fn from_points_errors() {
+        let y = [1., 2., 3.];
+        let err = |x: &[f64], degree, bc| PPSpline::from_points(x, &y, degree, bc).is_err();
+        assert!(err(&[0., 2., 1.], 3, SplineBoundary::Natural));
+        assert!(err(&[0., 1., 2.], 2, SplineBoundary::NotAKnot));
+        assert!(err(&[0., 1., 2.], 3, SplineBoundary::NotAKnot));
+        assert!(err(&[0., 1., 2.], 1, SplineBoundary::Clamped));
+        assert!(err(&[0., 1., 2.], 5, SplineBoundary::NotAKnot));
+    }
+
+    #[test]
+    fn sample_dual_spline() {
+        let x = [0., 1., 2., 3.];
+        let y: Vec<Dual> = [0., 1., 0., 1.]
+            .iter()
+            .map(|v| Dual::new(*v, vec!["y".to_string()]))
+            .collect();
+        let pps = PPSpline::from_points(&x, &y, 3, SplineBoundary::Natural).unwrap();
+        let (xs, ys) = pps.sample(0., 3., 1e-4).unwrap();
+        assert_eq!((xs[0], xs[xs.len() - 1]), (0., 3.));
+        for (xi, yi) in xs.iter().zip(ys) {
+            assert!(is_close(
+                &pps.ppdnev_single(xi, 0).unwrap().real(),
+                &yi,
+                None
+            ));
+        }
+        assert!(pps.sample(3., 0., 1e-4).is_err());
+    }
+
+    #[test]
+    fn dual_knots_match_finite_difference() {
+        let t = vec![0., 0., 0., 0., 1.5, 3., 3., 3., 3.];
+        let c = vec![1., 2., -1., 2., 1.5];
+        let pps = PPSpline::new(4, t.clone(), Some(c.clone()));
+        let mut t_dual: Vec<Number> = t.iter().map(Number::from).collect();
+        t_dual[4] = Number::Dual(Dual::new(1.5, vec!["s".to_string()]));
+        let pps_dual = pps.clone().with_dual_knots(t_dual).unwrap();
+        assert_eq!(pps_dual.t(), &t);
+
+        let h = 1e-6;
+        let bumped = |s: f64| {
+            let mut t_ = t.clone();
+            t_[4] += s;
+            PPSpline::new(4, t_, Some(c.clone()))
+        };
+        for x in [0.5, 1.2, 2.0, 2.9] {
+            for m in 0..3 {
+                let value = pps_dual.ppdnev_single_number(&Number::F64(x), m).unwrap();
+                assert!(is_close(
+                    &f64::from(&value),
+                    &pps.ppdnev_single(&x, m).unwrap(),
+                    None
+                ));
+                let fd = (bumped(h).ppdnev_single(&x, m).unwrap()
+                    - bumped(-h).ppdnev_single(&x, m).unwrap())
+                    / (2.0 * h);
+                let Number::Dual(d) = value else {
+                    panic!("expected a Dual value")
+                };
+                assert!(is_close(
+                    &d.gradient1(vec!["s".to_string()])[0],
+                    &fd,
+                    Some(1e-5)
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn dual_knots_with_dual_x() {
+        let t = vec![1., 1., 1., 1., 2., 2., 2., 3., 4., 4., 4., 4.];
+        let pps = PPSpline::new(4, t.clone(), Some(vec![1., 2., -1., 2., 1., 1., 2., 2.]));
+        let x = Dual::new(2.5, vec!["x".to_string()]);
+        // with real knots the result matches evaluation with a dual x
+        let expected = pps.ppdnev_single_dual(&x, 0).unwrap();
+        let value = pps
+            .ppdnev_single_number(&Number::Dual(x.clone()), 0)
+            .unwrap();
+        let Number::Dual(d) = value else {
+            panic!("expected a Dual value")
+        };
+        assert!(is_close(&d.real(), &expected.real(), None));
+        let grad = d.gradient1(vec!["x".to_string()]);
+        assert!(is_close(
+            &grad[0],
+            &expected.gradient1(vec!["x".to_string()])[0],
+            None
+        ));
+
+        // shifting every knot and x together leaves the spline value unchanged
+        let t_dual: Vec<Number> = t
+            .iter()
+            .map(|v| Number::Dual(Dual::new(*v, vec!["x".to_string()])))
+            .collect();
+        let shifted = pps.clone().with_dual_knots(t_dual).unwrap();
+        let value = shifted.mapped_value(&Number::Dual(x)).unwrap();
+        assert!(is_close(&f64::from(&value), &expected.real(), None));
+        let Number::Dual(d) = value else {
+            panic!("expected a Dual value")
+        };
+        assert!(is_close(&d.gradient1(vec!["x".to_string()])[0], &0., None));
+    }
+
+    #[test]
+    fn dual_knots_errors() {
+        let t = vec![1., 1., 2., 2.];
+        let pps = PPSpline::new(2, t, Some(vec![1., 2.]));
+        let d = Number::Dual(Dual::new(1.0, vec!["a".to_string()]));
+        let d2 = Number::Dual2(Dual2::new(2.0, vec!["b".to_string()]));
+        let f = Number::F64;
+        assert!(pps.clone().with_dual_knots(vec![f(1.), f(2.)]).is_err());
+        assert!(pps
+            .clone()
+            .with_dual_knots(vec![f(1.), f(1.), f(2.), f(0.5)])
+            .is_err());
+        assert!(pps
+            .clone()
+            .with_dual_knots(vec![d.clone(), f(1.), d2.clone(), f(2.)])
+            .is_err());
+        let pps_dual = pps
+            .clone()
+            .with_dual_knots(vec![d, f(1.), f(2.), f(2.)])
+            .unwrap();
+        assert!(pps_dual.ppdnev_single_number(&d2, 0).is_err());
+        assert!(pps_dual != pps);
+    }
+
     #[test]
     #[should_panic]
     fn backwards_definition() {
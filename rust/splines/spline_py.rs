@@ -5,12 +5,15 @@ use crate::json::json_py::DeserializedObj;
 use crate::json::JSON;
 use crate::splines::spline::{
     bspldnev_single_f64, bsplev_single_f64, PPSpline, PPSplineDual, PPSplineDual2, PPSplineF64,
+    SplineBoundary,
 };
+use bincode::{deserialize, serialize};
 use std::cmp::PartialEq;
 
 use numpy::{PyArray2, ToPyArray};
 use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
+use pyo3::types::PyBytes;
 
 macro_rules! create_interface {
     ($name: ident, $type: ident) => {
@@ -18,10 +21,34 @@ macro_rules! create_interface {
         impl $name {
             #[new]
             #[pyo3(signature = (k, t, c=None))]
-            fn new(k: usize, t: Vec<f64>, c: Option<Vec<$type>>) -> Self {
-                Self {
-                    inner: PPSpline::new(k, t, c),
-                }
+            fn new(k: usize, t: Vec<f64>, c: Option<Vec<$type>>) -> PyResult<Self> {
+                Ok(Self {
+                    inner: PPSpline::try_new(k, t, c)?,
+                })
+            }
+
+            /// Create a pp spline interpolating data points.
+            ///
+            /// Parameters
+            /// ----------
+            /// x: sequence of float
+            ///     The increasing x-axis values of the data points.
+            /// y: sequence of float, Dual or Dual2, based on self
+            ///     The y-axis values of the data points.
+            /// degree: int, optional
+            ///     The degree of the spline, which has order ``degree + 1``.
+            /// bc: SplineBoundary, optional
+            ///     The boundary condition which determines the knot sequence.
+            ///
+            /// Returns
+            /// -------
+            /// PPSplineF64, PPSplineDual or PPSplineDual2, based on self
+            #[staticmethod]
+            #[pyo3(signature = (x, y, degree=3, bc=SplineBoundary::Natural))]
+            fn from_points(x: Vec<f64>, y: Vec<$type>, degree: usize, bc: SplineBoundary) -> PyResult<Self> {
+                Ok(Self {
+                    inner: PPSpline::from_points(&x, &y, degree, bc)?,
+                })
             }
 
             #[getter]
@@ -47,6 +74,34 @@ macro_rules! create_interface {
                 }
             }
 
+            #[getter]
+            fn t_dual(&self) -> PyResult<Option<Vec<Number>>> {
+                Ok(self.inner.t_dual().clone())
+            }
+
+            /// Return a copy of the pp spline with dual-valued knot positions.
+            ///
+            /// Parameters
+            /// ----------
+            /// t: list[float, Dual or Dual2]
+            ///     The knot sequence, of the same length as *t*, whose positions may be
+            ///     sensitive to variables, e.g. a time to expiry.
+            ///
+            /// Returns
+            /// -------
+            /// PPSplineF64, PPSplineDual or PPSplineDual2, based on self
+            ///
+            /// Notes
+            /// -----
+            /// The real parts of the knots replace *t* and the coefficients, *c*, are unchanged.
+            /// Only :meth:`~rateslib.splines.PPSplineF64.ppdnev_single_number` propagates the
+            /// sensitivities of the knots.
+            fn with_dual_knots(&self, t: Vec<Number>) -> PyResult<Self> {
+                Ok(Self {
+                    inner: self.inner.clone().with_dual_knots(t)?,
+                })
+            }
+
             /// Solve the spline coefficients given the data sites.
             ///
             /// Parameters
@@ -177,8 +232,33 @@ macro_rules! create_interface {
             /// -------
             /// 1-d array of float
             fn ppev(&self, x: Vec<f64>) -> PyResult<Vec<$type>> {
-                let out: Vec<$type> = x.iter().map(|v| self.inner.ppdnev_single(&v, 0)).collect::<Result<Vec<$type>, _>>()?;
-                Ok(out)
+                Ok(self.inner.ppdnev(&x, 0)?.to_vec())
+            }
+
+            /// Sample the real value of the pp spline for plotting, with more points where its
+            /// curvature is high.
+            ///
+            /// Parameters
+            /// ----------
+            /// start: float
+            ///     The first *x* coordinate.
+            /// end: float
+            ///     The last *x* coordinate, after ``start``.
+            /// tol: float, optional
+            ///     The maximum difference, at the midpoint of each interval, between the spline
+            ///     and linear interpolation of the samples.
+            ///
+            /// Returns
+            /// -------
+            /// tuple[list[float], list[float]]
+            ///
+            /// Notes
+            /// -----
+            /// Each interval is bisected until within ``tol``, to a maximum depth, so plots
+            /// need far fewer evaluations than a uniform grid of similar accuracy.
+            #[pyo3(signature = (start, end, tol=1e-6))]
+            fn sample(&self, start: f64, end: f64, tol: f64) -> PyResult<(Vec<f64>, Vec<f64>)> {
+                self.inner.sample(start, end, tol)
             }
 
             /// Evaluate a single *x* coordinate derivative from the right on the pp spline.
@@ -266,9 +346,38 @@ macro_rules! create_interface {
                 }
             }
 
+            /// Evaluate a single *x* coordinate derivative from the right on the pp spline,
+            /// including the sensitivities of any dual-valued knots.
+            ///
+            /// Parameters
+            /// ----------
+            /// x: float, Dual or Dual2
+            ///     The x-axis value at which to evaluate value.
+            /// m: int, optional
+            ///     The order of derivative to calculate value for (0 is function value).
+            ///
+            /// Returns
+            /// -------
+            /// float, Dual or Dual2
+            ///
+            /// Notes
+            /// -----
+            /// The derivatives of each b-spline with respect to *x* and to the knot positions set
+            /// by :meth:`~rateslib.splines.PPSplineF64.with_dual_knots` are propagated through
+            /// the recursive definition of the b-splines by the chain rule. This function
+            /// prohibits type mixing and will raise if both *Dual* and *Dual2* data types are
+            /// encountered.
+            #[pyo3(signature = (x, m=0))]
+            fn ppdnev_single_number(&self, x: Number, m: usize) -> PyResult<Number> {
+                self.inner.ppdnev_single_number(&x, m)
+            }
+
             /// Evaluate an array of x coordinates derivatives on the pp spline.
             ///
-            /// Repeatedly applies :meth:`~rateslib.splines.PPSplineF64.ppdnev_single`.
+            /// Equivalent to repeatedly applying
+            /// :meth:`~rateslib.splines.PPSplineF64.ppdnev_single`, but evaluates the b-spline
+            /// derivatives of all coordinates as a matrix. Derivatives of order greater than the
+            /// degree of the spline are zero.
             ///
             /// .. warning::
             ///
@@ -287,8 +396,7 @@ macro_rules! create_interface {
             /// -------
             /// 1-d array of float
             fn ppdnev(&self, x: Vec<f64>, m: usize) -> PyResult<Vec<$type>> {
-                let out: Vec<$type> = x.iter().map(|v| self.inner.ppdnev_single(&v, m)).collect::<Result<Vec<$type>, _>>()?;
-                Ok(out)
+                Ok(self.inner.ppdnev(&x, m)?.to_vec())
             }
 
             /// Evaluate value of the *i* th b-spline at x coordinates.
@@ -399,10 +507,54 @@ macro_rules! create_interface {
                     Err(_) => Err(PyValueError::new_err("Failed to serialize `PPSpline` to JSON.")),
                 }
             }
+
+            /// Return a hash of the canonical JSON representation of the object, which is stable
+            /// across sessions.
+            ///
+            /// Returns
+            /// -------
+            /// int
+            #[pyo3(name = "canonical_hash")]
+            fn canonical_hash_py(&self) -> PyResult<u64> {
+                match DeserializedObj::$name(self.clone()).canonical_hash() {
+                    Ok(v) => Ok(v),
+                    Err(_) => Err(PyValueError::new_err("Failed to serialize `PPSpline` to JSON.")),
+                }
+            }
         }
     };
 }
 
+#[pymethods]
+impl SplineBoundary {
+    // Pickling
+    #[new]
+    fn new_py(item: u8) -> PyResult<SplineBoundary> {
+        match item {
+            0_u8 => Ok(SplineBoundary::NotAKnot),
+            1_u8 => Ok(SplineBoundary::Natural),
+            2_u8 => Ok(SplineBoundary::Clamped),
+            _ => Err(PyValueError::new_err(
+                "unreachable code on SplineBoundary pickle.",
+            )),
+        }
+    }
+    pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
+        *self = deserialize(state.as_bytes()).unwrap();
+        Ok(())
+    }
+    pub fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new_bound(py, &serialize(&self).unwrap()))
+    }
+    pub fn __getnewargs__(&self) -> PyResult<(u8,)> {
+        match self {
+            SplineBoundary::NotAKnot => Ok((0_u8,)),
+            SplineBoundary::Natural => Ok((1_u8,)),
+            SplineBoundary::Clamped => Ok((2_u8,)),
+        }
+    }
+}
+
 create_interface!(PPSplineF64, f64);
 create_interface!(PPSplineDual, Dual);
 create_interface!(PPSplineDual2, Dual2);
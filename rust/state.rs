@@ -0,0 +1,88 @@
+//! Identify the mutable state of market data objects, to allow caching layers to detect
+//! stale calculations without performing deep comparisons.
+//!
+//! Every state-bearing object, e.g. a `Curve` or `FXRates`, is assigned a `state_id` on
+//! construction which is replaced by a new value whenever the object is mutated. Ids are drawn
+//! from a single global counter so that no two states of any objects share an id.
+
+use pyo3::prelude::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static STATE_COUNTER: AtomicU64 = AtomicU64::new(1);
+
+/// Return a new, globally unique, state id.
+pub fn next_state_id() -> u64 {
+    STATE_COUNTER.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Combine the state ids of multiple children into a single state id.
+///
+/// The combined id changes if any of the child ids change or if their order changes.
+#[pyclass(module = "rateslib.rs")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CompositeState {
+    pub(crate) children: Vec<u64>,
+}
+
+impl CompositeState {
+    pub fn new(children: Vec<u64>) -> Self {
+        CompositeState { children }
+    }
+
+    /// Return the state id of the composite, as a hash of the children's state ids.
+    pub fn state_id(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.children.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+#[pymethods]
+impl CompositeState {
+    #[new]
+    fn new_py(children: Vec<u64>) -> Self {
+        CompositeState::new(children)
+    }
+
+    /// The state ids of the children.
+    #[getter]
+    fn children(&self) -> Vec<u64> {
+        self.children.clone()
+    }
+
+    /// The combined state id of the children.
+    #[getter]
+    #[pyo3(name = "state_id")]
+    fn state_id_py(&self) -> u64 {
+        self.state_id()
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_state_id_unique() {
+        let a = next_state_id();
+        let b = next_state_id();
+        assert!(b > a);
+    }
+
+    #[test]
+    fn test_composite_state() {
+        let a = CompositeState::new(vec![1, 2, 3]);
+        let b = CompositeState::new(vec![1, 2, 3]);
+        let c = CompositeState::new(vec![1, 2, 4]);
+        let d = CompositeState::new(vec![3, 2, 1]);
+        assert_eq!(a.state_id(), b.state_id());
+        assert_ne!(a.state_id(), c.state_id());
+        assert_ne!(a.state_id(), d.state_id());
+    }
+}
@@ -0,0 +1,170 @@
+//! Second order derivatives of the functions of [MathFuncs] and of powers, checked against their
+//! closed forms at random inputs.
+//!
+//! Each function is applied to `g(x, y) = x * y + x`, so the chain rule is exercised with
+//! correlated first order gradients and a non-zero Hessian of the input. By the chain rule the
+//! Hessian of `f(g)` is `f''(g) * grad(g) grad(g)^T + f'(g) * H(g)`.
+
+use crate::dual::{Dual, Dual2, Gradient1, Gradient2, MathFuncs};
+use crate::mc::Rng;
+use ndarray::{arr1, arr2, Array1, Array2};
+use num_traits::Pow;
+
+const SAMPLES: usize = 50;
+const TOL: f64 = 1e-10;
+
+/// A function of a real variable with its value, first and second derivatives in closed form.
+struct Reference {
+    name: &'static str,
+    f: fn(&Dual2) -> Dual2,
+    f0: fn(f64) -> f64,
+    f1: fn(f64) -> f64,
+    f2: fn(f64) -> f64,
+}
+
+fn references() -> Vec<Reference> {
+    vec![
+        Reference {
+            name: "exp",
+            f: |u| u.exp(),
+            f0: f64::exp,
+            f1: f64::exp,
+            f2: f64::exp,
+        },
+        Reference {
+            name: "log",
+            f: |u| u.log(),
+            f0: f64::ln,
+            f1: |g| 1.0 / g,
+            f2: |g| -1.0 / (g * g),
+        },
+        Reference {
+            name: "sqrt",
+            f: |u| u.sqrt(),
+            f0: f64::sqrt,
+            f1: |g| 0.5 / g.sqrt(),
+            f2: |g| -0.25 / g.powf(1.5),
+        },
+        Reference {
+            name: "log1p",
+            f: |u| u.log1p(),
+            f0: f64::ln_1p,
+            f1: |g| 1.0 / (1.0 + g),
+            f2: |g| -1.0 / (1.0 + g).powi(2),
+        },
+        Reference {
+            name: "powf",
+            f: |u| u.pow(2.7),
+            f0: |g| g.powf(2.7),
+            f1: |g| 2.7 * g.powf(1.7),
+            f2: |g| 2.7 * 1.7 * g.powf(0.7),
+        },
+        Reference {
+            name: "norm_cdf",
+            f: |u| (u * 0.25).norm_cdf(),
+            f0: |g| (0.25 * g).norm_cdf(),
+            f1: |g| 0.25 * pdf(0.25 * g),
+            f2: |g| -0.0625 * 0.25 * g * pdf(0.25 * g),
+        },
+    ]
+}
+
+/// The standard normal probability density function.
+fn pdf(z: f64) -> f64 {
+    (-0.5 * z * z).exp() / (2.0 * std::f64::consts::PI).sqrt()
+}
+
+fn vars() -> Vec<String> {
+    vec!["x".to_string(), "y".to_string()]
+}
+
+/// Random inputs in (0.5, 2.5), so every function is defined at `g(x, y)`.
+fn random_inputs() -> Vec<(f64, f64)> {
+    let mut rng = Rng::new(4747);
+    (0..SAMPLES)
+        .map(|_| (0.5 + 2.0 * rng.uniform(), 0.5 + 2.0 * rng.uniform()))
+        .collect()
+}
+
+fn assert_close(result: &Dual2, value: f64, grad: &Array1<f64>, hessian: &Array2<f64>, msg: &str) {
+    let scale = 1.0 + value.abs();
+    assert!((result.real() - value).abs() < TOL * scale, "{msg}: value");
+    let g = result.gradient1(vars());
+    assert!(
+        (&g - grad).iter().all(|d| d.abs() < TOL * scale),
+        "{msg}: gradient"
+    );
+    let h = result.gradient2(vars());
+    assert!(
+        (&h - hessian).iter().all(|d| d.abs() < TOL * scale),
+        "{msg}: hessian"
+    );
+}
+
+#[test]
+fn dual2_funcs_match_closed_forms() {
+    for (x0, y0) in random_inputs() {
+        let x = Dual2::new(x0, vec!["x".to_string()]);
+        let y = Dual2::new(y0, vec!["y".to_string()]);
+        let u = &x * &y + &x;
+        let g = x0 * y0 + x0;
+        let grad_g = arr1(&[y0 + 1.0, x0]);
+        let hess_g = arr2(&[[0.0, 1.0], [1.0, 0.0]]);
+        let outer = arr2(&[
+            [grad_g[0] * grad_g[0], grad_g[0] * grad_g[1]],
+            [grad_g[1] * grad_g[0], grad_g[1] * grad_g[1]],
+        ]);
+        for r in references() {
+            let (f1, f2) = ((r.f1)(g), (r.f2)(g));
+            assert_close(
+                &(r.f)(&u),
+                (r.f0)(g),
+                &(f1 * &grad_g),
+                &(f2 * &outer + f1 * &hess_g),
+                &format!("{} at ({x0}, {y0})", r.name),
+            );
+        }
+    }
+}
+
+#[test]
+fn dual2_dual_exponent_matches_closed_form() {
+    for (x0, y0) in random_inputs() {
+        let x = Dual2::new(x0, vec!["x".to_string()]);
+        let y = Dual2::new(y0, vec!["y".to_string()]);
+        let (h, ln) = (x0.powf(y0), x0.ln());
+        let grad = arr1(&[y0 * x0.powf(y0 - 1.0), h * ln]);
+        let hxy = x0.powf(y0 - 1.0) * (1.0 + y0 * ln);
+        let hessian = arr2(&[
+            [y0 * (y0 - 1.0) * x0.powf(y0 - 2.0), hxy],
+            [hxy, h * ln * ln],
+        ]);
+        let msg = format!("x^y at ({x0}, {y0})");
+        assert_close(&(&x).pow(&y), h, &grad, &hessian, &msg);
+
+        // a constant base
+        let result = 2.0_f64.pow(&y);
+        let ln2 = 2.0_f64.ln();
+        let value = 2.0_f64.powf(y0);
+        let grad = arr1(&[0.0, value * ln2]);
+        let hessian = arr2(&[[0.0, 0.0], [0.0, value * ln2 * ln2]]);
+        assert_close(&result, value, &grad, &hessian, &msg);
+    }
+}
+
+#[test]
+fn dual_funcs_match_first_order() {
+    for (x0, _) in random_inputs() {
+        let x = Dual::new(x0, vec!["x".to_string()]);
+        let cases: [(Dual, f64, f64); 3] = [
+            (x.sqrt(), x0.sqrt(), 0.5 / x0.sqrt()),
+            (x.log1p(), x0.ln_1p(), 1.0 / (1.0 + x0)),
+            ((&x).pow(&x), x0.powf(x0), x0.powf(x0) * (x0.ln() + 1.0)),
+        ];
+        for (result, value, derivative) in cases {
+            assert!((result.real() - value).abs() < TOL * value.abs().max(1.0));
+            let d = result.gradient1(vec!["x".to_string()])[0];
+            assert!((d - derivative).abs() < TOL * derivative.abs().max(1.0));
+        }
+    }
+}
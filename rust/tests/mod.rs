@@ -1 +1,2 @@
 // mod dual1;
+mod dual2_funcs;